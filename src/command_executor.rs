@@ -1,15 +1,59 @@
 use unescape::unescape;
 
+use chrono::Utc;
 use indy_utils::did::DidValue;
+use indy_vdr::pool::PreparedRequest;
 use std::{
     cell::RefCell,
     collections::{BTreeMap, HashMap},
     fmt::{Debug, Formatter},
+    io::{self, BufRead},
+    path::Path,
     rc::Rc,
+    thread,
+    time::{Duration, Instant},
 };
 
-use crate::tools::{pool::Pool, wallet::Wallet};
+use crate::{
+    commands::{
+        common::{
+            schedule::{self, ScheduleUnits},
+            tutorial::Tutorial,
+        },
+        ledger::send_with_status,
+    },
+    tools::{did::alias::NymAlias, pool::Pool, wallet::Wallet},
+    utils::{
+        file::read_file,
+        metrics::{Metrics, MetricsServer},
+        redirect,
+        telemetry::{Exporter, Span},
+        term::{is_term, print_prompt},
+        time::timestamp_to_datetime,
+    },
+};
 use linefeed::{Interface, ReadResult};
+use serde_json::Value as JsonValue;
+use zeroize::{Zeroize, Zeroizing};
+
+// Parameters carrying secrets which are wiped from memory right after the command execution
+const SECRET_PARAMS: [&str; 4] = ["seed", "key", "rekey", "export_key"];
+
+// Prefix of a command word referring to a file with the command parameters: `ledger cred-def @cred_def.yaml`
+const PARAMS_FILE_PREFIX: &str = "@";
+
+// Context values which change on their own and are never rolled back by `undo`
+const VOLATILE_CONTEXT_VALUES: [&str; 7] = [
+    "POOL_LAST_ACTIVITY",
+    "LAST_WRITE_TIME",
+    "LEDGER_REQUEST_TXN_TYPE",
+    "LEDGER_REQUEST_LATENCY",
+    "LAST_RESPONSE",
+    "ACTIVE_DID_ROLE_KEY",
+    "ACTIVE_DID_ROLE",
+];
+// Number of the latest context changes which can be undone
+const MAX_CONTEXT_CHANGES: usize = 50;
 
 #[derive(Debug)]
 pub struct ParamMetadata {
@@ -236,6 +280,37 @@ pub struct CommandContext {
     string_values: RefCell<HashMap<&'static str, String>>,
     taa_acceptance_mechanism: RefCell<String>,
     is_batch_mode: RefCell<bool>,
+    context_events: RefCell<Vec<ContextEvent>>,
+    context_changes: RefCell<Vec<ContextChange>>,
+    metrics_server: RefCell<Option<Rc<MetricsServer>>>,
+}
+
+/// Local state of the context which can be rolled back by `undo`:
+/// active DID, stored transaction and options set by commands.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContextState {
+    did: Option<DidValue>,
+    int_values: HashMap<&'static str, i32>,
+    uint_values: HashMap<&'static str, u64>,
+    string_values: HashMap<&'static str, String>,
+    taa_acceptance_mechanism: String,
+}
+
+/// Reversible change made by a command.
+#[derive(Debug, Clone)]
+pub enum ContextEvent {
+    State(ContextState),
+    NymAlias {
+        alias: String,
+        previous: Option<DidValue>,
+    },
+}
+
+/// Events of a single command execution, undone together.
+#[derive(Debug)]
+pub struct ContextChange {
+    pub command: String,
+    pub events: Vec<ContextEvent>,
 }
 
 impl Debug for CommandContext {
@@ -270,6 +345,9 @@ impl CommandContext {
             string_values: RefCell::new(HashMap::new()),
             taa_acceptance_mechanism: RefCell::new(String::new()),
             is_batch_mode: RefCell::new(false),
+            context_events: RefCell::new(Vec::new()),
+            context_changes: RefCell::new(Vec::new()),
+            metrics_server: RefCell::new(None),
         }
     }
 
@@ -329,6 +407,15 @@ impl CommandContext {
         self.pool.borrow().clone()
     }
 
+    /// Metrics endpoint started with `--serve` option, shared by the commands publishing metrics.
+    pub fn set_metrics_server(&self, value: Option<MetricsServer>) {
+        self.metrics_server.replace(value.map(Rc::new));
+    }
+
+    pub fn get_metrics_server(&self) -> Option<Rc<MetricsServer>> {
+        self.metrics_server.borrow().clone()
+    }
+
     pub fn set_did(&self, value: Option<DidValue>) {
         match value {
             Some(value) => self.did.replace(Some(Rc::new(value))),
@@ -383,6 +470,61 @@ impl CommandContext {
     pub fn is_batch_mode(&self) -> bool {
         *self.is_batch_mode.borrow()
     }
+
+    pub fn get_context_state(&self) -> ContextState {
+        fn reversible<V: Clone>(values: &HashMap<&'static str, V>) -> HashMap<&'static str, V> {
+            values
+                .iter()
+                .filter(|(key, _)| !VOLATILE_CONTEXT_VALUES.contains(key))
+                .map(|(key, value)| (*key, value.clone()))
+                .collect()
+        }
+        ContextState {
+            did: self.get_did().map(|did| did.as_ref().clone()),
+            int_values: reversible(&self.int_values.borrow()),
+            uint_values: reversible(&self.uint_values.borrow()),
+            string_values: reversible(&self.string_values.borrow()),
+            taa_acceptance_mechanism: self.get_taa_acceptance_mechanism(),
+        }
+    }
+
+    /// Restore the state. Volatile values are kept as they are.
+    pub fn restore_context_state(&self, state: ContextState) {
+        fn restore<V>(values: &mut HashMap<&'static str, V>, state: HashMap<&'static str, V>) {
+            values.retain(|key, _| VOLATILE_CONTEXT_VALUES.contains(key));
+            values.extend(state);
+        }
+        self.set_did(state.did);
+        restore(&mut self.int_values.borrow_mut(), state.int_values);
+        restore(&mut self.uint_values.borrow_mut(), state.uint_values);
+        restore(&mut self.string_values.borrow_mut(), state.string_values);
+        self.set_taa_acceptance_mechanism(&state.taa_acceptance_mechanism);
+    }
+
+    /// Record the change made by the running command besides the context values.
+    pub fn add_context_event(&self, event: ContextEvent) {
+        self.context_events.borrow_mut().push(event);
+    }
+
+    pub fn take_context_events(&self) -> Vec<ContextEvent> {
+        self.context_events.take()
+    }
+
+    pub fn add_context_change(&self, change: ContextChange) {
+        let mut changes = self.context_changes.borrow_mut();
+        changes.push(change);
+        if changes.len() > MAX_CONTEXT_CHANGES {
+            changes.remove(0);
+        }
+    }
+
+    pub fn pop_context_change(&self) -> Option<ContextChange> {
+        self.context_changes.borrow_mut().pop()
+    }
+
+    pub fn reset_context_changes(&self) {
+        self.context_changes.borrow_mut().clear();
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -390,8 +532,15 @@ pub enum DynamicCompletionType {
     Wallet,
     Pool,
     Did,
+    Role,
 }
 
+const DEFAULT_WAIT_FOR_TIMEOUT: u64 = 60;
+const DEFAULT_WAIT_FOR_INTERVAL: u64 = 5;
+const DEFAULT_RETRY_ATTEMPTS: u32 = 3;
+const DEFAULT_RETRY_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_RETRY_BACKOFF: Duration = Duration::from_secs(600);
+
 pub type CommandParams = HashMap<&'static str, String>;
 pub type CommandResult = Result<(), ()>;
 pub type CommandExecute = fn(&CommandContext, &CommandParams) -> CommandResult;
@@ -466,10 +615,39 @@ impl CommandGroup {
     }
 }
 
+/// Old command syntax (e.g. of libindy based indy-cli) which is still recognized to ease porting of existing scripts.
+/// Commands are given with their group: `pool connect`.
+#[derive(Debug)]
+pub enum Deprecation {
+    /// The command has been renamed, it is executed under the new name.
+    Command {
+        old: &'static str,
+        new: &'static str,
+        note: &'static str,
+    },
+    /// The parameter has been renamed (`new` is set) or is not supported anymore and is ignored.
+    Param {
+        command: &'static str,
+        old: &'static str,
+        new: Option<&'static str>,
+        note: &'static str,
+    },
+    /// The command has been removed without a replacement, it fails with the note.
+    /// In indy-cli compatibility mode the command is skipped with a warning instead.
+    Removed {
+        command: &'static str,
+        note: &'static str,
+    },
+}
+
 pub struct CommandExecutor {
     ctx: CommandContext,
     commands: HashMap<&'static str, Command>,
     grouped_commands: HashMap<&'static str, (CommandGroup, HashMap<&'static str, Command>)>,
+    deprecations: Vec<Deprecation>,
+    exporter: RefCell<Option<Exporter>>,
+    // Executed commands by name and success for the metrics endpoint
+    command_counts: RefCell<BTreeMap<(String, bool), u64>>,
 }
 
 unsafe impl Send for CommandExecutor {}
@@ -480,17 +658,58 @@ impl CommandExecutor {
         CommandExecutorBuilder {
             commands: HashMap::new(),
             grouped_commands: HashMap::new(),
+            deprecations: Vec::new(),
         }
     }
 
     pub fn execute(&self, line: &str) -> Result<(), ()> {
-        let (cmd, params) = CommandExecutor::_split_first_word(line);
+        let (line, redirection) =
+            redirect::split_redirection(line).map_err(|err| println_err!(&self.ctx, "{}", err))?;
+
+        let _redirect = match redirection {
+            Some(redirection) => Some(
+                redirection
+                    .start()
+                    .map_err(|err| println_err!(&self.ctx, "{}", err))?,
+            ),
+            None => None,
+        };
+
+        self._execute_line(line)
+    }
+
+    fn _execute_line(&self, line: &str) -> Result<(), ()> {
+        let line = match self._migrate_line(line)? {
+            Some(line) => Zeroizing::new(line),
+            None => return Ok(()),
+        };
+        let (cmd, params) = CommandExecutor::_split_first_word(&line);
 
         if cmd == "help" {
             self._print_help();
             return Ok(());
         }
 
+        if cmd == "wait-for" {
+            return self._execute_wait_for(params);
+        }
+
+        if cmd == "retry" {
+            return self._execute_retry(params);
+        }
+
+        if cmd == "schedule" {
+            return self._execute_schedule(params);
+        }
+
+        if cmd == "tutorial" {
+            return self._execute_tutorial(params);
+        }
+
+        if cmd == "undo" {
+            return self._execute_undo(params);
+        }
+
         if let Some(&(ref group, ref commands)) = self.grouped_commands.get(cmd) {
             return self._execute_group_command(group, commands, params);
         }
@@ -499,7 +718,7 @@ impl CommandExecutor {
             return self._execute_command(None, command, params);
         }
 
-        println_err!("Unknown group or command \"{}\"", cmd);
+        println_err!(&self.ctx, "Unknown group or command \"{}\"", cmd);
         println!("Type \"help\" to display the help");
         Err(())
     }
@@ -508,6 +727,165 @@ impl CommandExecutor {
         &self.ctx
     }
 
+    /// Rewrite deprecated syntax on the line into the current one printing the migration notes.
+    fn _migrate_line(&self, line: &str) -> Result<Option<String>, ()> {
+        // Rewritten copies of the line may carry secrets as well
+        let mut line = Zeroizing::new(line.trim().to_string());
+
+        for deprecation in self.deprecations.iter() {
+            match *deprecation {
+                Deprecation::Command { old, new, note } => {
+                    if let Some(params) = CommandExecutor::_strip_command(&line, old) {
+                        println_warn!(
+                            &self.ctx,
+                            "\"{}\" command is deprecated, use \"{}\" instead. {}",
+                            old,
+                            new,
+                            note
+                        );
+                        line = Zeroizing::new(format!("{} {}", new, params).trim_end().to_string());
+                    }
+                }
+                Deprecation::Param {
+                    command,
+                    old,
+                    new,
+                    note,
+                } => {
+                    let mut params = match CommandExecutor::_strip_command(&line, command) {
+                        Some(params) => CommandExecutor::_split_words(params),
+                        None => continue,
+                    };
+                    let prefix = format!("{}=", old);
+                    let index = match params.iter().position(|param| param.starts_with(&prefix)) {
+                        Some(index) => index,
+                        None => continue,
+                    };
+                    let renamed;
+                    match new {
+                        Some(new) => {
+                            println_warn!(
+                                &self.ctx,
+                                "\"{}\" parameter of \"{}\" command is deprecated, use \"{}\" instead. {}",
+                                old,
+                                command,
+                                new,
+                                note
+                            );
+                            renamed = format!("{}={}", new, &params[index][prefix.len()..]);
+                            params[index] = &renamed;
+                        }
+                        None => {
+                            println_warn!(
+                                &self.ctx,
+                                "\"{}\" parameter of \"{}\" command is not supported anymore and has been ignored. {}",
+                                old,
+                                command,
+                                note
+                            );
+                            params.remove(index);
+                        }
+                    }
+                    line = Zeroizing::new(
+                        format!("{} {}", command, params.join(" "))
+                            .trim_end()
+                            .to_string(),
+                    );
+                }
+                Deprecation::Removed { command, note } => {
+                    if CommandExecutor::_strip_command(&line, command).is_none() {
+                        continue;
+                    }
+                    if self.ctx.is_indy_cli_compat() {
+                        println_warn!(
+                            &self.ctx,
+                            "\"{}\" command has been removed and is skipped in indy-cli compatibility mode. {}",
+                            command,
+                            note
+                        );
+                        return Ok(None);
+                    }
+                    println_err!(
+                        &self.ctx,
+                        "\"{}\" command has been removed. {}",
+                        command,
+                        note
+                    );
+                    return Err(());
+                }
+            }
+        }
+
+        Ok(Some(std::mem::take(&mut *line)))
+    }
+
+    // Parameters of the line if it calls the command
+    fn _strip_command<'a>(line: &'a str, command: &str) -> Option<&'a str> {
+        let mut rest = line;
+        for word in command.split_whitespace() {
+            let (first, tail) = CommandExecutor::_split_first_word(rest);
+            if first != word {
+                return None;
+            }
+            rest = tail;
+        }
+        Some(&line[line.len() - rest.len()..])
+    }
+
+    fn _split_words(s: &str) -> Vec<&str> {
+        let mut words = Vec::new();
+        let mut rest = s;
+        loop {
+            let (word, tail) = CommandExecutor::_split_first_word(rest);
+            if word.is_empty() {
+                return words;
+            }
+            words.push(word);
+            rest = tail;
+        }
+    }
+
+    /// Whether the command on the line accepts secret parameters (seeds, wallet keys).
+    pub fn is_secret_bearing(&self, line: &str) -> bool {
+        let (cmd, params) = CommandExecutor::_split_first_word(line);
+
+        let command = match self.grouped_commands.get(cmd) {
+            Some(&(_, ref commands)) => {
+                let (cmd, _) = CommandExecutor::_split_first_word(params);
+                commands.get(cmd)
+            }
+            None => self.commands.get(cmd),
+        };
+
+        command
+            .map(|command| {
+                let metadata = command.metadata();
+                metadata
+                    .main_param()
+                    .into_iter()
+                    .chain(metadata.params().iter())
+                    .any(|param| SECRET_PARAMS.contains(&param.name()))
+            })
+            .unwrap_or(false)
+    }
+
+    /// Whether a secret (e.g. `seed=...`) is typed on the line itself instead of being entered on prompt.
+    pub fn has_inline_secret(line: &str) -> bool {
+        CommandExecutor::_split_words(line).iter().any(|word| {
+            let mut split = word.splitn(2, '=');
+            let name = split.next().unwrap_or_default();
+            split.next().is_some() && SECRET_PARAMS.contains(&name)
+        })
+    }
+
+    fn _wipe_secret_params(params: &mut CommandParams) {
+        for (name, value) in params.iter_mut() {
+            if SECRET_PARAMS.contains(name) {
+                value.zeroize();
+            }
+        }
+    }
+
     fn _get_dynamic_completions(
         &self,
         dynamic_completion_type: DynamicCompletionType,
@@ -517,6 +895,7 @@ impl CommandExecutor {
             DynamicCompletionType::Wallet => crate::commands::wallet::wallet_names(),
             DynamicCompletionType::Did => crate::commands::did::did_list(self.ctx()),
             DynamicCompletionType::Pool => crate::commands::pool::pool_list(),
+            DynamicCompletionType::Role => crate::commands::ledger::role_list(self.ctx()),
         };
 
         completions
@@ -819,7 +1198,12 @@ impl CommandExecutor {
             return self._execute_command(Some(group), command, params);
         }
 
-        println_err!("Unknown command \"{} {}\"", group.metadata().name(), cmd);
+        println_err!(
+            &self.ctx,
+            "Unknown command \"{} {}\"",
+            group.metadata().name(),
+            cmd
+        );
         println!(
             "Type \"{} help\" to display the help for \"{}\" group",
             group.metadata().name(),
@@ -842,9 +1226,13 @@ impl CommandExecutor {
         }
 
         match CommandExecutor::_parse_params(command.metadata(), params) {
-            Ok(ref params) => command.execute(&self.ctx, params),
+            Ok(mut params) => {
+                let res = self._execute_recorded(group, command, &params);
+                CommandExecutor::_wipe_secret_params(&mut params);
+                res
+            }
             Err(ref err) => {
-                println_err!("{}", err);
+                println_err!(&self.ctx, "{}", err);
                 if group.is_some() {
                     println!(
                         "Type \"{} {} help\" to display the help for \"{} {}\" command",
@@ -865,175 +1253,842 @@ impl CommandExecutor {
         }
     }
 
-    fn _print_help(&self) {
-        println_acc!("Hyperledger Indy CLI");
-        println!();
-        println_acc!("Usage:");
-        println!("\t[<command-group>] <command> [[<main-param-name>=]<main-param-value>] [<param_name-1>=<param_value-1>]...[<param_name-n>=<param_value-n>]");
-        println!();
-        println_acc!("Getting help:");
-        println!("\thelp - Display this help");
-        println!("\t<command-group> help - Display the help for the specific command group");
-        println!("\t[<command-group>] <command> help - Display the help for the specific command");
-        println!();
-        println_acc!("Command groups are:");
-
-        for &(ref group, _) in self.grouped_commands.values() {
-            println!(
-                "\t{} - {}",
-                group.metadata().name(),
-                group.metadata().help()
-            )
+    // Changes of the local context made by the command are recorded to be rolled back by `undo`
+    fn _execute_recorded(
+        &self,
+        group: Option<&CommandGroup>,
+        command: &Command,
+        params: &CommandParams,
+    ) -> CommandResult {
+        let owner = self._context_owner();
+        let before = self.ctx.get_context_state();
+
+        let res = self._execute_traced(group, command, params);
+        self._publish_metrics(group, command, res.is_ok());
+
+        let mut events = self.ctx.take_context_events();
+        if self._context_owner() != owner {
+            // DIDs and transactions selected with another wallet or pool cannot be brought back
+            self.ctx.reset_context_changes();
+            return res;
+        }
+        if self.ctx.get_context_state() != before {
+            events.insert(0, ContextEvent::State(before));
         }
+        if !events.is_empty() {
+            self.ctx.add_context_change(ContextChange {
+                command: CommandExecutor::_command_name(group, command),
+                events,
+            });
+        }
+        res
+    }
 
-        println!();
-        println_acc!("Top level commands are:");
+    fn _context_owner(&self) -> (Option<String>, Option<String>) {
+        (
+            self.ctx.get_wallet().map(|wallet| wallet.name.to_string()),
+            self.ctx.get_pool().map(|pool| pool.name.to_string()),
+        )
+    }
 
-        for command in self.commands.values() {
-            println!(
-                "\t{} - {}",
-                command.metadata().name(),
-                command.metadata().help()
-            )
+    fn _command_name(group: Option<&CommandGroup>, command: &Command) -> String {
+        match group {
+            Some(group) => format!("{} {}", group.metadata().name(), command.metadata().name()),
+            None => command.metadata().name().to_string(),
         }
-
-        println!();
     }
 
-    fn _print_group_help(&self, group: &CommandGroup, commands: &HashMap<&'static str, Command>) {
-        println_acc!("Group:");
-        println!(
-            "\t{} - {}",
-            group.metadata().name(),
-            group.metadata().help()
-        );
-        println!();
-        println_acc!("Usage:");
-        println!("\t{} <command> [[<main-param-name>=]<main-param-value>] [<param_name-1>=<param_value-1>]...[<param_name-n>=<param_value-n>]", group.metadata().name());
-        println!();
-        println_acc!("Getting help:");
-        println!(
-            "\t{} <command> help - Display the help for the specific command",
-            group.metadata().name()
-        );
-        println!();
-        println_acc!("Group commands are:");
+    // Command outcomes and ledger request stats are served at the `--serve` endpoint
+    fn _publish_metrics(&self, group: Option<&CommandGroup>, command: &Command, success: bool) {
+        let server = match self.ctx.get_metrics_server() {
+            Some(server) => server,
+            None => return,
+        };
 
-        for command in commands.values() {
-            println!(
-                "\t{} - {}",
-                command.metadata().name(),
-                command.metadata().help()
-            )
-        }
+        let mut counts = self.command_counts.borrow_mut();
+        *counts
+            .entry((CommandExecutor::_command_name(group, command), success))
+            .or_insert(0) += 1;
+
+        let mut metrics = Metrics::new();
+        metrics.counter(
+            "indy_cli_commands_total",
+            "Number of executed commands by outcome",
+            &counts
+                .iter()
+                .map(|((name, success), count)| {
+                    let status = if *success { "success" } else { "error" };
+                    (
+                        vec![("command", name.as_str()), ("status", status)],
+                        *count as f64,
+                    )
+                })
+                .collect::<Vec<_>>(),
+        );
+        server.publish("commands", metrics);
 
-        println!();
+        let ledger = match self.ctx.get_pool() {
+            Some(pool) => pool.latency_stats().metrics(&pool.name),
+            None => Metrics::new(),
+        };
+        server.publish("ledger", ledger);
     }
 
-    fn _print_command_help(&self, group: Option<&CommandGroup>, command: &Command) {
-        println_acc!("Command:");
+    fn _execute_traced(
+        &self,
+        group: Option<&CommandGroup>,
+        command: &Command,
+        params: &CommandParams,
+    ) -> CommandResult {
+        let endpoint = match self.ctx.get_otel_endpoint() {
+            Some(endpoint) => endpoint,
+            None => return command.execute(&self.ctx, params),
+        };
 
-        if let Some(group) = group {
-            println!(
-                "\t{} {} - {}",
-                group.metadata().name(),
-                command.metadata().name(),
-                command.metadata().help()
-            );
-        } else {
-            println!(
-                "\t{} - {}",
-                command.metadata().name(),
-                command.metadata().help()
-            );
-        }
+        let name = CommandExecutor::_command_name(group, command);
 
-        println!();
-        println_acc!("Usage:");
+        self.ctx.reset_ledger_request_info();
+        let mut span = Span::start(&name);
 
-        if let Some(group) = group {
-            print!(
-                "\t{} {}",
-                group.metadata().name(),
-                command.metadata().name()
-            );
-        } else {
-            print!("\t{}", command.metadata().name());
+        let res = command.execute(&self.ctx, params);
+
+        if let Some(pool) = self.ctx.get_connected_pool() {
+            span.set_attribute("indy.pool.name", pool.name.clone());
+        }
+        if let Some((txn_type, latency)) = self.ctx.get_ledger_request_info() {
+            span.set_attribute("indy.txn.type", txn_type);
+            span.set_attribute("indy.ledger.request.latency_ms", latency);
         }
 
-        if let Some(ref main_param) = command.metadata().main_param() {
-            print!(" <{}-value>", main_param.name());
+        let mut exporter = self.exporter.borrow_mut();
+        if exporter.as_ref().map(Exporter::endpoint) != Some(endpoint.as_str()) {
+            *exporter = Exporter::start(&endpoint)
+                .map_err(|err| {
+                    warn!(
+                        "Unable to export telemetry of \"{}\" command: {}",
+                        name, err
+                    )
+                })
+                .ok();
+        }
+        if let Some(exporter) = exporter.as_ref() {
+            exporter.export(span, res.is_ok());
         }
 
-        for param in command.metadata().params() {
-            match (param.is_optional(), param.is_deferred()) {
-                (true, true) => print!(" [{}[=<{}-value>]]", param.name(), param.name()),
-                (true, false) => print!(" [{}=<{}-value>]", param.name(), param.name()),
-                (false, true) => print!(" {}[=<{}-value>]", param.name(), param.name()),
-                (false, false) => print!(" {}=<{}-value>", param.name(), param.name()),
+        res
+    }
+
+    fn _execute_undo(&self, line: &str) -> Result<(), ()> {
+        let (first_word, _) = CommandExecutor::_split_first_word(line);
+
+        if first_word == "help" {
+            self._print_undo_help();
+            return Ok(());
+        }
+
+        let change = self.ctx.pop_context_change().ok_or_else(|| {
+            println_err!(&self.ctx, "There are no context changes to undo");
+        })?;
+
+        // Events are reverted in the order opposite to the one they have been recorded in
+        for event in change.events.into_iter().rev() {
+            match event {
+                ContextEvent::State(state) => {
+                    self.ctx.restore_context_state(state);
+                    match self.ctx.get_did() {
+                        Some(did) => self.ctx.set_active_did(did.as_ref().clone()),
+                        None => self.ctx.reset_active_did(),
+                    }
+                }
+                ContextEvent::NymAlias { alias, previous } => {
+                    let wallet = self.ctx.ensure_opened_wallet()?;
+                    match previous {
+                        Some(did) => NymAlias::store(&wallet, &alias, &did).map(|_| ()),
+                        None => NymAlias::remove(&wallet, &alias),
+                    }
+                    .map_err(|err| err.print(&self.ctx, None))?;
+                }
             }
         }
 
-        println!();
+        println_succ!(
+            &self.ctx,
+            "Context changes made by \"{}\" command have been undone",
+            change.command
+        );
+        Ok(())
+    }
 
-        if command.metadata().main_param().is_some() || !command.metadata().params().is_empty() {
-            println!();
-            println_acc!("Parameters are:");
+    fn _execute_wait_for(&self, line: &str) -> Result<(), ()> {
+        let (first_word, _) = CommandExecutor::_split_first_word(line);
 
-            if let Some(ref main_param) = command.metadata().main_param() {
-                println!("\t{} - {}", main_param.name(), main_param.help())
-            }
+        if first_word == "help" {
+            self._print_wait_for_help();
+            return Ok(());
+        }
 
-            for param in command.metadata().params() {
-                print!("\t{} - ", param.name());
+        let (command, timeout, interval) =
+            CommandExecutor::_parse_wait_for_params(line).map_err(|err| {
+                println_err!(&self.ctx, "{}", err);
+                println!("Type \"wait-for help\" to display the help for \"wait-for\" command");
+            })?;
 
-                if param.is_optional() {
-                    print!("(optional) ")
-                }
+        let deadline = Instant::now() + Duration::from_secs(timeout);
 
-                if param.is_deferred() {
-                    print!("(leave empty for deferred input) ")
-                }
+        loop {
+            if self._execute_line(command).is_ok() {
+                return Ok(());
+            }
 
-                println!("{}", param.help());
+            if Instant::now() + Duration::from_secs(interval) > deadline {
+                println_err!(
+                    &self.ctx,
+                    "Command has not succeeded within {} seconds",
+                    timeout
+                );
+                return Err(());
             }
+
+            println_warn!(
+                &self.ctx,
+                "Command has not succeeded yet. Retry in {} seconds",
+                interval
+            );
+            thread::sleep(Duration::from_secs(interval));
         }
+    }
 
-        if !command.metadata().examples().is_empty() {
-            println!();
-            println_acc!("Examples:");
+    fn _parse_wait_for_params(line: &str) -> Result<(&str, u64, u64), String> {
+        let mut command = line.trim();
+        let mut timeout = None;
+        let mut interval = None;
 
-            for example in command.metadata().examples() {
-                println!("\t{}", example);
+        // `timeout` and `interval` belong to `wait-for` only if they trail the wrapped command
+        while let Some((head, last)) = command.rsplit_once(char::is_whitespace) {
+            match last.split_once('=') {
+                Some(("timeout", value)) if timeout.is_none() => {
+                    timeout = Some(CommandExecutor::_parse_seconds("timeout", value)?)
+                }
+                Some(("interval", value)) if interval.is_none() => {
+                    interval = Some(CommandExecutor::_parse_seconds("interval", value)?)
+                }
+                _ => break,
             }
+            command = head.trim_end();
         }
 
-        println!();
+        if command.is_empty() {
+            return Err("No command to wait for present".to_string());
+        }
+
+        Ok((
+            command,
+            timeout.unwrap_or(DEFAULT_WAIT_FOR_TIMEOUT),
+            interval.unwrap_or(DEFAULT_WAIT_FOR_INTERVAL),
+        ))
     }
 
-    fn _parse_params(command: &CommandMetadata, params: &str) -> Result<CommandParams, String> {
-        let mut res = CommandParams::new();
-        let mut params = params;
+    fn _execute_retry(&self, line: &str) -> Result<(), ()> {
+        let (first_word, _) = CommandExecutor::_split_first_word(line);
 
-        // Read main param
-        if let Some(param_metadata) = command.main_param() {
-            let (param_value, tail) = CommandExecutor::_split_first_word(params);
-            params = tail;
+        if first_word == "help" {
+            self._print_retry_help();
+            return Ok(());
+        }
 
-            if param_value.is_empty() {
-                return Err(format!(
-                    "No main \"{}\" parameter present",
-                    param_metadata.name()
-                ));
+        let (command, attempts, mut backoff) =
+            CommandExecutor::_parse_retry_params(line).map_err(|err| {
+                println_err!(&self.ctx, "{}", err);
+                println!("Type \"retry help\" to display the help for \"retry\" command");
+            })?;
+
+        for attempt in 1..=attempts {
+            if self._execute_line(command).is_ok() {
+                return Ok(());
             }
 
-            if let Some(param_value) = unescape(CommandExecutor::_trim_quotes(param_value)) {
-                res.insert(param_metadata.name(), param_value);
-            } else {
-                return Err(format!(
-                    "Invalid escape sequence for \"{}\" parameter present",
-                    param_metadata.name()
-                ));
+            if attempt < attempts {
+                println_warn!(
+                    &self.ctx,
+                    "Attempt {} of {} failed. Retry in {:?}",
+                    attempt,
+                    attempts,
+                    backoff
+                );
+                thread::sleep(backoff);
+                backoff = CommandExecutor::_next_backoff(backoff);
+            }
+        }
+
+        println_err!(&self.ctx, "Command has failed after {} attempts", attempts);
+        Err(())
+    }
+
+    fn _next_backoff(backoff: Duration) -> Duration {
+        backoff.saturating_mul(2).min(MAX_RETRY_BACKOFF)
+    }
+
+    fn _execute_schedule(&self, line: &str) -> Result<(), ()> {
+        let (first_word, _) = CommandExecutor::_split_first_word(line);
+
+        if first_word == "help" {
+            self._print_schedule_help();
+            return Ok(());
+        }
+
+        let (command, time, unit) = CommandExecutor::_parse_schedule_params(line)
+            .and_then(|(command, time, unit)| {
+                let time = schedule::parse_time(time)?;
+                if time <= Utc::now() {
+                    return Err(format!("Time {} has already passed", time.to_rfc3339()));
+                }
+                Ok((command, time, unit))
+            })
+            .map_err(|err| {
+                println_err!(&self.ctx, "{}", err);
+                println!("Type \"schedule help\" to display the help for \"schedule\" command");
+            })?;
+
+        let pool = self.ctx.ensure_connected_pool()?;
+
+        // The transaction is built and signed now, only sending is deferred
+        self.ctx.set_context_transaction(None, None);
+        self._execute_line(&format!("{} send=false", command))?;
+        let transaction = self.ctx.get_context_transaction(None).ok_or_else(|| {
+            println_err!(
+                &self.ctx,
+                "The command has not prepared a transaction to schedule"
+            );
+        })?;
+        let request = PreparedRequest::from_request_json(&transaction)
+            .map_err(|_| println_err!(&self.ctx, "Invalid formatted transaction provided."))?;
+
+        if let Some(dir) = unit {
+            let units = ScheduleUnits::new(Path::new(dir), &request.req_json);
+            units
+                .write(&pool.name, &request.req_json, time)
+                .map_err(|err| println_err!(&self.ctx, "{}", err))?;
+            println_succ!(
+                &self.ctx,
+                "Systemd units \"{}\" and \"{}\" sending the transaction at {} have been written.",
+                units.service.display(),
+                units.timer.display(),
+                timestamp_to_datetime(&self.ctx, time.timestamp())
+            );
+            println!(
+                "Copy them into ~/.config/systemd/user and run `systemctl --user enable --now {}.timer` to activate the timer. \
+                Run `loginctl enable-linger` to keep the timer active after logging out.",
+                units.name
+            );
+            return Ok(());
+        }
+
+        println_succ!(
+            &self.ctx,
+            "Transaction will be sent at {}. Keep CLI running until then, exiting cancels the transaction.",
+            timestamp_to_datetime(&self.ctx, time.timestamp())
+        );
+        schedule::wait_until(time);
+
+        let status = send_with_status(&self.ctx, &request);
+        if status.starts_with("Committed") {
+            println_succ!(&self.ctx, "Scheduled transaction has been sent. {}", status);
+            Ok(())
+        } else {
+            println_err!(
+                &self.ctx,
+                "Scheduled transaction has not been committed. {}",
+                status
+            );
+            Err(())
+        }
+    }
+
+    fn _parse_schedule_params(line: &str) -> Result<(&str, &str, Option<&str>), String> {
+        let (options, command) = match line.split_once(" -- ") {
+            Some((options, command)) => (options, command.trim()),
+            None => return Err("No \"--\" separator before command present".to_string()),
+        };
+
+        let mut time = None;
+        let mut unit = None;
+
+        for option in options.split_whitespace() {
+            match option.split_once('=') {
+                Some(("at", value)) if time.is_none() => {
+                    time = Some(CommandExecutor::_trim_quotes(value))
+                }
+                Some(("unit", value)) if unit.is_none() => {
+                    unit = Some(CommandExecutor::_trim_quotes(value))
+                }
+                Some((name, _)) if name == "at" || name == "unit" => {
+                    return Err(format!("\"{}\" parameter presented multiple times", name))
+                }
+                _ => return Err(format!("Unknown \"{}\" parameter present", option)),
+            }
+        }
+
+        let time = time.ok_or_else(|| "No required \"at\" parameter present".to_string())?;
+
+        let (group, params) = CommandExecutor::_split_first_word(command);
+        if group != "ledger" || params.trim().is_empty() {
+            return Err("Only ledger commands can be scheduled".to_string());
+        }
+        // Sending is controlled by `schedule`, endorsed transactions are not sent by the author at all
+        if command
+            .split_whitespace()
+            .any(|param| param.starts_with("send=") || param.starts_with("endorser="))
+        {
+            return Err(
+                "\"send\" and \"endorser\" parameters cannot be used in a scheduled command"
+                    .to_string(),
+            );
+        }
+
+        Ok((command, time, unit))
+    }
+
+    fn _execute_tutorial(&self, line: &str) -> Result<(), ()> {
+        let (first_word, _) = CommandExecutor::_split_first_word(line);
+
+        if first_word == "help" {
+            self._print_tutorial_help();
+            return Ok(());
+        }
+
+        let gen_txn_file = match line.trim().strip_prefix("gen_txn_file=") {
+            Some(file) if !file.is_empty() => file.trim_matches('"'),
+            _ => {
+                println_err!(
+                    &self.ctx,
+                    "Genesis transactions file of a sandbox network is not specified"
+                );
+                println!("Type \"tutorial help\" to display the help for \"tutorial\" command");
+                return Err(());
+            }
+        };
+
+        if self.ctx.get_wallet().is_some() || self.ctx.get_pool().is_some() {
+            println_err!(
+                &self.ctx,
+                "Close the wallet and disconnect the pool before starting the tutorial"
+            );
+            return Err(());
+        }
+
+        let tutorial =
+            Tutorial::new(gen_txn_file).map_err(|err| println_err!(&self.ctx, "{}", err))?;
+        let res = self._run_tutorial(&tutorial);
+
+        println!();
+        println_acc!(&self.ctx, "Removing the tutorial wallet and pool");
+        let cleanup_commands = tutorial.cleanup_commands(
+            self.ctx.get_pool().is_some(),
+            self.ctx.get_wallet().is_some(),
+        );
+        for command in cleanup_commands {
+            self._execute_line(&command).ok();
+        }
+        tutorial.remove_dir();
+
+        res
+    }
+
+    fn _run_tutorial(&self, tutorial: &Tutorial) -> Result<(), ()> {
+        let steps = tutorial
+            .steps()
+            .map_err(|err| println_err!(&self.ctx, "{}", err))?;
+
+        println_acc!(&self.ctx, "Welcome to Indy CLI!");
+        println!("The tutorial runs the real commands against the sandbox network step by step.");
+        println!("Everything it creates is removed at the end.");
+
+        for (index, step) in steps.iter().enumerate() {
+            println!();
+            println_acc!(
+                &self.ctx,
+                "Step {} of {}: {}",
+                index + 1,
+                steps.len(),
+                step.title
+            );
+            println!("{}", step.explanation);
+            println!();
+            println!(
+                "\t{}> {}",
+                self.ctx.get_prompt().trim_end_matches("> "),
+                step.command
+            );
+            print_prompt("Would you like to run the command? (y/n)");
+
+            if !wait_for_user_reply(&self.ctx) {
+                println!("The tutorial has been stopped.");
+                return Ok(());
+            }
+
+            if self._execute_line(&step.command).is_err() {
+                println_err!(
+                    &self.ctx,
+                    "The step has failed. Make sure the sandbox network is running and reachable."
+                );
+                return Err(());
+            }
+        }
+
+        println!();
+        println_succ!(
+            &self.ctx,
+            "Congratulations! You have published your first DID to the ledger."
+        );
+        println!("Type \"help\" to see all the commands.");
+        Ok(())
+    }
+
+    fn _parse_retry_params(line: &str) -> Result<(&str, u32, Duration), String> {
+        let (options, command) = match line.split_once(" -- ") {
+            Some((options, command)) => (options, command.trim()),
+            None => match line.trim().strip_prefix("--") {
+                Some(command) => ("", command.trim()),
+                None => return Err("No \"--\" separator before command present".to_string()),
+            },
+        };
+
+        let mut attempts = None;
+        let mut backoff = None;
+
+        for option in options.split_whitespace() {
+            match option.split_once('=') {
+                Some(("attempts", value)) if attempts.is_none() => {
+                    let value = value.parse::<u32>().map_err(|err| {
+                        format!(
+                            "Can't parse number parameter \"attempts\": value: \"{}\", err \"{}\"",
+                            value, err
+                        )
+                    })?;
+                    if value == 0 {
+                        return Err("\"attempts\" parameter must be greater than 0".to_string());
+                    }
+                    attempts = Some(value);
+                }
+                Some(("backoff", value)) if backoff.is_none() => {
+                    backoff = Some(CommandExecutor::_parse_duration("backoff", value)?)
+                }
+                Some((name, _)) if name == "attempts" || name == "backoff" => {
+                    return Err(format!("\"{}\" parameter presented multiple times", name))
+                }
+                _ => return Err(format!("Unknown \"{}\" parameter present", option)),
+            }
+        }
+
+        if command.is_empty() {
+            return Err("No command to retry present".to_string());
+        }
+
+        Ok((
+            command,
+            attempts.unwrap_or(DEFAULT_RETRY_ATTEMPTS),
+            backoff.unwrap_or(DEFAULT_RETRY_BACKOFF),
+        ))
+    }
+
+    fn _parse_duration(name: &str, value: &str) -> Result<Duration, String> {
+        if let Some(millis) = value.strip_suffix("ms") {
+            return CommandExecutor::_parse_seconds(name, millis).map(Duration::from_millis);
+        }
+        let seconds = value.strip_suffix('s').unwrap_or(value);
+        CommandExecutor::_parse_seconds(name, seconds).map(Duration::from_secs)
+    }
+
+    fn _parse_seconds(name: &str, value: &str) -> Result<u64, String> {
+        value.parse::<u64>().map_err(|err| {
+            format!(
+                "Can't parse number parameter \"{}\": value: \"{}\", err \"{}\"",
+                name, value, err
+            )
+        })
+    }
+
+    fn _print_wait_for_help(&self) {
+        println_acc!(&self.ctx, "Command:");
+        println!("\twait-for - Repeat the command until it succeeds or the timeout expires");
+        println!();
+        println_acc!(&self.ctx, "Usage:");
+        println!("\twait-for <command> [timeout=<timeout-value>] [interval=<interval-value>]");
+        println!();
+        println_acc!(&self.ctx, "Parameters are:");
+        println!("\tcommand - Command to repeat (with its own parameters)");
+        println!(
+            "\ttimeout - (optional) Number of seconds to wait for the command to succeed ({} by default)",
+            DEFAULT_WAIT_FOR_TIMEOUT
+        );
+        println!(
+            "\tinterval - (optional) Number of seconds between attempts ({} by default)",
+            DEFAULT_WAIT_FOR_INTERVAL
+        );
+        println!();
+        println_acc!(&self.ctx, "Examples:");
+        println!("\twait-for ledger get-nym did=VsKV7grR1BUE29mG2Fm2kX timeout=60 interval=5");
+        println!();
+    }
+
+    fn _print_tutorial_help(&self) {
+        println_acc!(&self.ctx, "Command:");
+        println!("\ttutorial - Walk through the wallet creation, pool connection, DID creation and publishing a NYM");
+        println!();
+        println_acc!(&self.ctx, "Usage:");
+        println!("\ttutorial gen_txn_file=<path-to-sandbox-genesis-file>");
+        println!();
+        println_acc!(&self.ctx, "Notes:");
+        println!("\tThe real commands are executed against a sandbox network (e.g. local indy-node pool or von-network),");
+        println!(
+            "\twhich has a TRUSTEE with the well-known seed 000000000000000000000000Trustee1."
+        );
+        println!("\tThe wallet and pool created by the tutorial are removed at the end.");
+        println!();
+        println_acc!(&self.ctx, "Examples:");
+        println!("\ttutorial gen_txn_file=/home/sandbox_genesis");
+        println!();
+    }
+
+    fn _print_schedule_help(&self) {
+        println_acc!(&self.ctx, "Command:");
+        println!("\tschedule - Prepare a ledger transaction now and send it at the given time");
+        println!();
+        println_acc!(&self.ctx, "Usage:");
+        println!("\tschedule at=<time-value> [unit=<unit-value>] -- <ledger command>");
+        println!();
+        println_acc!(&self.ctx, "Parameters are:");
+        println!("\tat - Time to send the transaction at in RFC 3339 format (`2024-07-01T02:00Z`)");
+        println!("\tunit - (optional) Directory to write systemd service and timer units sending the transaction into instead of waiting in CLI");
+        println!("\tcommand - Ledger command building the transaction (with its own parameters)");
+        println!();
+        println_acc!(&self.ctx, "Notes:");
+        println!("\tThe transaction is built and signed with the active DID and accepted Transaction Author Agreement right away.");
+        println!("\tWithout `unit` CLI waits for the time and sends the transaction to the connected pool.");
+        println!();
+        println_acc!(&self.ctx, "Examples:");
+        println!("\tschedule at=2024-07-01T02:00Z -- ledger txn-author-agreement text=\"Agreement\" version=2.0 ratification-timestamp=1719799200");
+        println!("\tschedule at=2024-07-01T02:00Z unit=/tmp -- ledger nym did=VsKV7grR1BUE29mG2Fm2kX role=ENDORSER");
+        println!();
+    }
+
+    fn _print_retry_help(&self) {
+        println_acc!(&self.ctx, "Command:");
+        println!("\tretry - Retry the command on failure with exponential backoff");
+        println!();
+        println_acc!(&self.ctx, "Usage:");
+        println!("\tretry [attempts=<attempts-value>] [backoff=<backoff-value>] -- <command>");
+        println!();
+        println_acc!(&self.ctx, "Parameters are:");
+        println!(
+            "\tattempts - (optional) Maximum number of attempts ({} by default)",
+            DEFAULT_RETRY_ATTEMPTS
+        );
+        println!(
+            "\tbackoff - (optional) Delay before the second attempt, doubled after each failure up to {}s. Accepts seconds (`2`, `2s`) or milliseconds (`500ms`) ({}s by default)",
+            MAX_RETRY_BACKOFF.as_secs(),
+            DEFAULT_RETRY_BACKOFF.as_secs()
+        );
+        println!("\tcommand - Command to execute (with its own parameters)");
+        println!();
+        println_acc!(&self.ctx, "Examples:");
+        println!("\tretry attempts=5 backoff=2s -- ledger get-nym did=VsKV7grR1BUE29mG2Fm2kX");
+        println!();
+    }
+
+    fn _print_undo_help(&self) {
+        println_acc!(&self.ctx, "Command:");
+        println!("\tundo - Roll back the local context changes made by the latest command: active DID, stored transaction, options and NYM aliases");
+        println!();
+        println_acc!(&self.ctx, "Usage:");
+        println!("\tundo");
+        println!();
+        println!(
+            "\tUp to {} latest changes are kept. Opening another wallet or connecting to another pool clears them.",
+            MAX_CONTEXT_CHANGES
+        );
+        println!();
+    }
+
+    fn _print_help(&self) {
+        println_acc!(&self.ctx, "Hyperledger Indy CLI");
+        println!();
+        println_acc!(&self.ctx, "Usage:");
+        println!("\t[<command-group>] <command> [[<main-param-name>=]<main-param-value>] [<param_name-1>=<param_value-1>]...[<param_name-n>=<param_value-n>]");
+        println!();
+        println_acc!(&self.ctx, "Getting help:");
+        println!("\thelp - Display this help");
+        println!("\t<command-group> help - Display the help for the specific command group");
+        println!("\t[<command-group>] <command> help - Display the help for the specific command");
+        println!();
+        println_acc!(&self.ctx, "Command groups are:");
+
+        for &(ref group, _) in self.grouped_commands.values() {
+            println!(
+                "\t{} - {}",
+                group.metadata().name(),
+                group.metadata().help()
+            )
+        }
+
+        println!();
+        println_acc!(&self.ctx, "Top level commands are:");
+
+        for command in self.commands.values() {
+            println!(
+                "\t{} - {}",
+                command.metadata().name(),
+                command.metadata().help()
+            )
+        }
+
+        println!();
+        println_acc!(&self.ctx, "Meta commands are:");
+        println!("\twait-for - Repeat the command until it succeeds or the timeout expires");
+        println!("\tretry - Retry the command on failure with exponential backoff");
+        println!("\tschedule - Prepare a ledger transaction now and send it at the given time");
+        println!("\ttutorial - Walk through the first steps with CLI using a sandbox network");
+        println!("\tundo - Roll back the local context changes made by the latest command");
+        println!();
+    }
+
+    fn _print_group_help(&self, group: &CommandGroup, commands: &HashMap<&'static str, Command>) {
+        println_acc!(&self.ctx, "Group:");
+        println!(
+            "\t{} - {}",
+            group.metadata().name(),
+            group.metadata().help()
+        );
+        println!();
+        println_acc!(&self.ctx, "Usage:");
+        println!("\t{} <command> [[<main-param-name>=]<main-param-value>] [<param_name-1>=<param_value-1>]...[<param_name-n>=<param_value-n>]", group.metadata().name());
+        println!();
+        println_acc!(&self.ctx, "Getting help:");
+        println!(
+            "\t{} <command> help - Display the help for the specific command",
+            group.metadata().name()
+        );
+        println!();
+        println_acc!(&self.ctx, "Group commands are:");
+
+        for command in commands.values() {
+            println!(
+                "\t{} - {}",
+                command.metadata().name(),
+                command.metadata().help()
+            )
+        }
+
+        println!();
+    }
+
+    fn _print_command_help(&self, group: Option<&CommandGroup>, command: &Command) {
+        println_acc!(&self.ctx, "Command:");
+
+        if let Some(group) = group {
+            println!(
+                "\t{} {} - {}",
+                group.metadata().name(),
+                command.metadata().name(),
+                command.metadata().help()
+            );
+        } else {
+            println!(
+                "\t{} - {}",
+                command.metadata().name(),
+                command.metadata().help()
+            );
+        }
+
+        println!();
+        println_acc!(&self.ctx, "Usage:");
+
+        if let Some(group) = group {
+            print!(
+                "\t{} {}",
+                group.metadata().name(),
+                command.metadata().name()
+            );
+        } else {
+            print!("\t{}", command.metadata().name());
+        }
+
+        if let Some(ref main_param) = command.metadata().main_param() {
+            print!(" <{}-value>", main_param.name());
+        }
+
+        for param in command.metadata().params() {
+            match (param.is_optional(), param.is_deferred()) {
+                (true, true) => print!(" [{}[=<{}-value>]]", param.name(), param.name()),
+                (true, false) => print!(" [{}=<{}-value>]", param.name(), param.name()),
+                (false, true) => print!(" {}[=<{}-value>]", param.name(), param.name()),
+                (false, false) => print!(" {}=<{}-value>", param.name(), param.name()),
+            }
+        }
+
+        println!();
+
+        if command.metadata().main_param().is_some() || !command.metadata().params().is_empty() {
+            println!();
+            println_acc!(&self.ctx, "Parameters are:");
+
+            if let Some(ref main_param) = command.metadata().main_param() {
+                println!("\t{} - {}", main_param.name(), main_param.help())
+            }
+
+            for param in command.metadata().params() {
+                print!("\t{} - ", param.name());
+
+                if param.is_optional() {
+                    print!("(optional) ")
+                }
+
+                if param.is_deferred() {
+                    print!("(leave empty for deferred input) ")
+                }
+
+                println!("{}", param.help());
+            }
+        }
+
+        if !command.metadata().examples().is_empty() {
+            println!();
+            println_acc!(&self.ctx, "Examples:");
+
+            for example in command.metadata().examples() {
+                println!("\t{}", example);
+            }
+        }
+
+        println!();
+    }
+
+    fn _parse_params(command: &CommandMetadata, params: &str) -> Result<CommandParams, String> {
+        let mut res = CommandParams::new();
+        let mut params = params;
+        let mut file_params = Vec::new();
+
+        // Read main param
+        if let Some(param_metadata) = command.main_param() {
+            let (param_value, tail) = CommandExecutor::_split_first_word(params);
+
+            if let Some(file) = param_value.strip_prefix(PARAMS_FILE_PREFIX) {
+                // The main param is expected in the file then
+                file_params.extend(CommandExecutor::_read_params_file(command, file)?);
+                params = tail;
+            } else if param_value.is_empty() {
+                return Err(format!(
+                    "No main \"{}\" parameter present",
+                    param_metadata.name()
+                ));
+            } else if let Some(param_value) = unescape(CommandExecutor::_trim_quotes(param_value)) {
+                res.insert(param_metadata.name(), param_value);
+                params = tail;
+            } else {
+                return Err(format!(
+                    "Invalid escape sequence for \"{}\" parameter present",
+                    param_metadata.name()
+                ));
             }
         }
 
@@ -1048,6 +2103,11 @@ impl CommandExecutor {
                 break;
             }
 
+            if let Some(file) = param.strip_prefix(PARAMS_FILE_PREFIX) {
+                file_params.extend(CommandExecutor::_read_params_file(command, file)?);
+                continue;
+            }
+
             let mut split = param.splitn(2, '=');
             let param_name = split.next().unwrap();
             let param_value = split.next();
@@ -1078,22 +2138,39 @@ impl CommandExecutor {
             }
         }
 
+        // Inline params override the ones read from files; a later file overrides an earlier one
+        for (param_name, param_value) in file_params.into_iter().rev() {
+            if !res.contains_key(param_name) {
+                res.insert(param_name, param_value);
+            }
+        }
+
+        if let Some(param_metadata) = command.main_param() {
+            if !res.contains_key(param_metadata.name()) {
+                return Err(format!(
+                    "No main \"{}\" parameter present",
+                    param_metadata.name()
+                ));
+            }
+        }
+
+        deferred_params.retain(|param| !res.contains_key(param));
+
         for param in deferred_params {
-            println!("Enter value for {}:", param);
+            print_prompt(&format!("Enter value for {}:", param));
             let val;
             loop {
                 match rpassword::read_password() {
                     Ok(v) => {
                         if v.is_empty() {
-                            println!("Please enter value for {}:", param);
+                            print_prompt(&format!("Please enter value for {}:", param));
                         } else {
                             val = v;
                             break;
                         }
                     }
                     Err(err) => {
-                        println_err!("{}", err.to_string());
-                        println!("Please enter value for {}:", param);
+                        print_prompt(&format!("{}. Please enter value for {}:", err, param));
                     }
                 }
             }
@@ -1103,6 +2180,67 @@ impl CommandExecutor {
         Ok(res)
     }
 
+    // Parameters of the command stored in a JSON or YAML (by `.yaml`/`.yml` extension) object.
+    // Strings are taken as is, other values are passed in their JSON form.
+    fn _read_params_file(
+        command: &CommandMetadata,
+        file: &str,
+    ) -> Result<Vec<(&'static str, String)>, String> {
+        let file = CommandExecutor::_trim_quotes(file);
+        if file.is_empty() {
+            return Err(format!(
+                "No file name present after \"{}\"",
+                PARAMS_FILE_PREFIX
+            ));
+        }
+
+        let content = read_file(file)
+            .map_err(|err| format!("Unable to read parameters file \"{}\": {}", file, err))?;
+
+        let is_yaml = Path::new(file)
+            .extension()
+            .map(|extension| extension == "yaml" || extension == "yml")
+            .unwrap_or(false);
+        let values: JsonValue = if is_yaml {
+            serde_yaml::from_str(&content).map_err(|err| err.to_string())
+        } else {
+            serde_json::from_str(&content).map_err(|err| err.to_string())
+        }
+        .map_err(|err| format!("Unable to parse parameters file \"{}\": {}", file, err))?;
+
+        let values = match values {
+            JsonValue::Object(values) => values,
+            _ => {
+                return Err(format!(
+                    "Parameters file \"{}\" must contain an object of parameter names and values",
+                    file
+                ))
+            }
+        };
+
+        let mut res = Vec::new();
+        for (param_name, param_value) in values {
+            let param_metadata = command
+                .main_param()
+                .into_iter()
+                .chain(command.params().iter())
+                .find(|p| p.name() == param_name)
+                .ok_or_else(|| {
+                    format!(
+                        "Unknown \"{}\" parameter present in file \"{}\"",
+                        param_name, file
+                    )
+                })?;
+            let param_value = match param_value {
+                JsonValue::Null => continue,
+                JsonValue::String(value) => value,
+                value => value.to_string(),
+            };
+            res.push((param_metadata.name(), param_value));
+        }
+        Ok(res)
+    }
+
     fn _split_first_word(s: &str) -> (&str, &str) {
         let mut is_quote_escape = false;
         let mut is_whitespace_escape = false;
@@ -1171,13 +2309,14 @@ impl Drop for CommandExecutor {
             }
         }
 
-        println_succ!("Goodbye...");
+        println_succ!(&self.ctx, "Goodbye...");
     }
 }
 
 pub struct CommandExecutorBuilder {
     commands: HashMap<&'static str, Command>,
     grouped_commands: HashMap<&'static str, (CommandGroup, HashMap<&'static str, Command>)>,
+    deprecations: Vec<Deprecation>,
 }
 
 impl CommandExecutorBuilder {
@@ -1185,6 +2324,7 @@ impl CommandExecutorBuilder {
         CommandExecutorGroupBuilder {
             commands: self.commands,
             grouped_commands: self.grouped_commands,
+            deprecations: self.deprecations,
             group,
             group_commands: HashMap::new(),
         }
@@ -1195,11 +2335,19 @@ impl CommandExecutorBuilder {
         self
     }
 
+    pub fn add_deprecation(mut self, deprecation: Deprecation) -> CommandExecutorBuilder {
+        self.deprecations.push(deprecation);
+        self
+    }
+
     pub fn finalize(self) -> CommandExecutor {
         CommandExecutor {
             ctx: CommandContext::new(),
             commands: self.commands,
             grouped_commands: self.grouped_commands,
+            deprecations: self.deprecations,
+            exporter: RefCell::new(None),
+            command_counts: RefCell::new(BTreeMap::new()),
         }
     }
 }
@@ -1207,6 +2355,7 @@ impl CommandExecutorBuilder {
 pub struct CommandExecutorGroupBuilder {
     commands: HashMap<&'static str, Command>,
     grouped_commands: HashMap<&'static str, (CommandGroup, HashMap<&'static str, Command>)>,
+    deprecations: Vec<Deprecation>,
     group: CommandGroup,
     group_commands: HashMap<&'static str, Command>,
 }
@@ -1226,6 +2375,7 @@ impl CommandExecutorGroupBuilder {
         CommandExecutorBuilder {
             commands: self.commands,
             grouped_commands: self.grouped_commands,
+            deprecations: self.deprecations,
         }
     }
 }
@@ -1236,28 +2386,39 @@ pub fn wait_for_user_reply(ctx: &CommandContext) -> bool {
         return true;
     }
 
+    // The line editor draws on stdout which is a file while the command output is redirected
+    if !is_term() {
+        return io::stdin()
+            .lock()
+            .lines()
+            .map_while(Result::ok)
+            .find_map(|line| parse_user_reply(&line))
+            .unwrap_or(false);
+    }
+
     let reader = Interface::new("User Reply Reader").unwrap();
 
     while let Ok(ReadResult::Input(line)) = reader.read_line() {
-        let line = line.trim();
-        if line.is_empty() {
-            continue;
-        }
-
-        if line == "y" || line == "yes" {
-            return true;
-        } else if line == "n" || line == "no" {
-            return false;
-        } else {
-            continue;
+        if let Some(reply) = parse_user_reply(&line) {
+            return reply;
         }
     }
     false
 }
 
+fn parse_user_reply(line: &str) -> Option<bool> {
+    match line.trim() {
+        "y" | "yes" => Some(true),
+        "n" | "no" => Some(false),
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::utils::{environment::EnvironmentUtils, file::write_file};
+    use std::fs;
 
     pub mod test_group {
         use super::*;
@@ -1279,6 +2440,33 @@ mod tests {
         }
     }
 
+    pub mod use_did_command {
+        use super::*;
+
+        command!(
+            CommandMetadata::build("use_did_command", "Use did command help")
+                .add_main_param("did", "Did help")
+                .finalize()
+        );
+
+        fn execute(ctx: &CommandContext, params: &CommandParams) -> Result<(), ()> {
+            ctx.set_active_did(DidValue(params["did"].to_string()));
+            ctx.set_last_response(Some(params["did"].to_string()));
+            Ok(())
+        }
+    }
+
+    pub mod write_command {
+        use super::*;
+
+        command!(CommandMetadata::build("write_command", "Write command help").finalize());
+
+        fn execute(ctx: &CommandContext, _params: &CommandParams) -> Result<(), ()> {
+            ctx.set_last_write_time(1);
+            Ok(())
+        }
+    }
+
     #[test]
     pub fn execute_works() {
         let cmd_executor = CommandExecutor::build()
@@ -1290,6 +2478,409 @@ mod tests {
         cmd_executor.execute("test_group test_command \"main param\" param1=\"param1 value\" param2=param2-value").unwrap();
     }
 
+    #[test]
+    pub fn parse_user_reply_works() {
+        assert_eq!(Some(true), parse_user_reply(" yes\n"));
+        assert_eq!(Some(false), parse_user_reply("n"));
+        assert_eq!(None, parse_user_reply(""));
+        assert_eq!(None, parse_user_reply("maybe"));
+    }
+
+    pub mod test_secret_command {
+        use super::*;
+
+        command!(
+            CommandMetadata::build("test_secret_command", "Test secret command help")
+                .add_required_deferred_param("key", "Key help")
+                .finalize()
+        );
+
+        fn execute(_ctx: &CommandContext, _params: &CommandParams) -> Result<(), ()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    pub fn is_secret_bearing_works() {
+        let cmd_executor = CommandExecutor::build()
+            .add_group(test_group::new())
+            .add_command(test_secret_command::new())
+            .finalize_group()
+            .add_command(test_command::new())
+            .finalize();
+        assert!(cmd_executor.is_secret_bearing("test_group test_secret_command"));
+        assert!(!cmd_executor.is_secret_bearing("test_command main param1=value"));
+        assert!(!cmd_executor.is_secret_bearing("unknown_command"));
+    }
+
+    #[test]
+    pub fn has_inline_secret_works() {
+        assert!(CommandExecutor::has_inline_secret(
+            "did new seed=000000000000000000000000Trustee1"
+        ));
+        assert!(CommandExecutor::has_inline_secret(
+            "wallet open w key=\"my key\""
+        ));
+        assert!(CommandExecutor::has_inline_secret(
+            "wallet create w key export_key=secret"
+        ));
+        assert!(!CommandExecutor::has_inline_secret("wallet open w key"));
+        assert!(!CommandExecutor::has_inline_secret(
+            "did new metadata=seed=abc"
+        ));
+        assert!(!CommandExecutor::has_inline_secret(
+            "ledger nym did=VsKV7grR1BUE29mG2Fm2kX"
+        ));
+    }
+
+    #[test]
+    pub fn _wipe_secret_params_works() {
+        let mut params = CommandParams::new();
+        params.insert("key", "secret".to_string());
+        params.insert("param1", "value".to_string());
+        CommandExecutor::_wipe_secret_params(&mut params);
+        assert!(params["key"].is_empty());
+        assert_eq!(params["param1"], "value");
+    }
+
+    #[test]
+    pub fn execute_deprecation_works() {
+        let cmd_executor = CommandExecutor::build()
+            .add_group(test_group::new())
+            .add_command(test_command::new())
+            .finalize_group()
+            .add_deprecation(Deprecation::Command {
+                old: "test_group old_command",
+                new: "test_group test_command",
+                note: "",
+            })
+            .add_deprecation(Deprecation::Param {
+                command: "test_group test_command",
+                old: "old_param",
+                new: Some("param1"),
+                note: "",
+            })
+            .add_deprecation(Deprecation::Param {
+                command: "test_group test_command",
+                old: "dropped_param",
+                new: None,
+                note: "",
+            })
+            .add_deprecation(Deprecation::Removed {
+                command: "test_group removed_command",
+                note: "",
+            })
+            .finalize();
+        cmd_executor
+            .execute("test_group old_command main old_param=\"param1 value\" dropped_param=value")
+            .unwrap();
+        cmd_executor
+            .execute("test_group removed_command main")
+            .unwrap_err();
+    }
+
+    #[test]
+    pub fn execute_works_for_metrics_server() {
+        use std::{
+            io::{Read, Write},
+            net::TcpStream,
+        };
+
+        let cmd_executor = CommandExecutor::build()
+            .add_command(test_command::new())
+            .finalize();
+        let server = MetricsServer::start("127.0.0.1:0").unwrap();
+        let address = server.address.clone();
+        cmd_executor.ctx().set_metrics_server(Some(server));
+
+        cmd_executor
+            .execute("test_command main param1=value")
+            .unwrap();
+        cmd_executor
+            .execute("test_command main param1=value")
+            .unwrap();
+
+        let mut stream = TcpStream::connect(address).unwrap();
+        write!(stream, "GET /metrics HTTP/1.1\r\n\r\n").unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        assert!(response
+            .contains("indy_cli_commands_total{command=\"test_command\",status=\"success\"} 2\n"));
+    }
+
+    #[test]
+    pub fn execute_deprecation_works_for_indy_cli_compat() {
+        let cmd_executor = CommandExecutor::build()
+            .add_command(test_command::new())
+            .add_deprecation(Deprecation::Removed {
+                command: "ledger payment",
+                note: "",
+            })
+            .finalize();
+        cmd_executor.ctx().set_indy_cli_compat(true);
+        cmd_executor
+            .execute("ledger payment payment_address=pay:null:1 amount=10")
+            .unwrap();
+        assert_eq!(
+            cmd_executor
+                ._migrate_line("ledger payment amount=10")
+                .unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    pub fn _migrate_line_works() {
+        let cmd_executor = CommandExecutor::build()
+            .add_deprecation(Deprecation::Param {
+                command: "ledger nym",
+                old: "fees_inputs",
+                new: None,
+                note: "",
+            })
+            .add_deprecation(Deprecation::Param {
+                command: "ledger nym",
+                old: "alias_name",
+                new: Some("alias"),
+                note: "",
+            })
+            .finalize();
+        assert_eq!(
+            cmd_executor
+                ._migrate_line(
+                    "ledger nym did=VsKV7grR1BUE29mG2Fm2kX fees_inputs=txo alias_name=\"my alias\""
+                )
+                .unwrap()
+                .unwrap(),
+            "ledger nym did=VsKV7grR1BUE29mG2Fm2kX alias=\"my alias\""
+        );
+        assert_eq!(
+            cmd_executor
+                ._migrate_line("ledger get-nym did=VsKV7grR1BUE29mG2Fm2kX alias_name=test")
+                .unwrap()
+                .unwrap(),
+            "ledger get-nym did=VsKV7grR1BUE29mG2Fm2kX alias_name=test"
+        );
+    }
+
+    #[test]
+    pub fn execute_wait_for_works() {
+        let cmd_executor = CommandExecutor::build()
+            .add_command(test_command::new())
+            .finalize();
+        cmd_executor
+            .execute("wait-for test_command main param1=value timeout=1 interval=1")
+            .unwrap();
+        cmd_executor
+            .execute("wait-for unknown_command timeout=0 interval=1")
+            .unwrap_err();
+    }
+
+    #[test]
+    pub fn _parse_wait_for_params_works() {
+        assert_eq!(
+            CommandExecutor::_parse_wait_for_params("ledger get-nym did=VsKV7grR1BUE29mG2Fm2kX")
+                .unwrap(),
+            (
+                "ledger get-nym did=VsKV7grR1BUE29mG2Fm2kX",
+                DEFAULT_WAIT_FOR_TIMEOUT,
+                DEFAULT_WAIT_FOR_INTERVAL
+            )
+        );
+        assert_eq!(
+            CommandExecutor::_parse_wait_for_params(
+                "ledger get-nym did=VsKV7grR1BUE29mG2Fm2kX timeout=30 interval=2"
+            )
+            .unwrap(),
+            ("ledger get-nym did=VsKV7grR1BUE29mG2Fm2kX", 30, 2)
+        );
+        CommandExecutor::_parse_wait_for_params("").unwrap_err();
+        CommandExecutor::_parse_wait_for_params("ledger get-nym timeout=abc").unwrap_err();
+    }
+
+    #[test]
+    pub fn execute_retry_works() {
+        let cmd_executor = CommandExecutor::build()
+            .add_command(test_command::new())
+            .finalize();
+        cmd_executor
+            .execute("retry attempts=2 backoff=1ms -- test_command main param1=value")
+            .unwrap();
+        cmd_executor
+            .execute("retry attempts=2 backoff=1ms -- unknown_command")
+            .unwrap_err();
+    }
+
+    #[test]
+    pub fn execute_undo_works() {
+        let cmd_executor = CommandExecutor::build()
+            .add_command(use_did_command::new())
+            .add_command(test_command::new())
+            .add_command(write_command::new())
+            .finalize();
+        cmd_executor.execute("undo").unwrap_err();
+
+        cmd_executor
+            .execute("use_did_command VsKV7grR1BUE29mG2Fm2kX")
+            .unwrap();
+        cmd_executor
+            .execute("use_did_command CnEDk9HrMnmiHXEV1WFgbVCRteYnPqsJwrTdcZaNhFVW")
+            .unwrap();
+        // Commands which do not change the context are not recorded
+        cmd_executor
+            .execute("test_command main param1=value")
+            .unwrap();
+        // Rate limit timestamps are volatile
+        cmd_executor.execute("write_command").unwrap();
+
+        cmd_executor.execute("undo").unwrap();
+        assert_eq!(
+            "VsKV7grR1BUE29mG2Fm2kX",
+            cmd_executor.ctx().get_did().unwrap().0
+        );
+        // Volatile values are not rolled back
+        assert_eq!(
+            "CnEDk9HrMnmiHXEV1WFgbVCRteYnPqsJwrTdcZaNhFVW",
+            cmd_executor.ctx().get_last_response().unwrap()
+        );
+
+        cmd_executor.execute("undo").unwrap();
+        assert!(cmd_executor.ctx().get_did().is_none());
+        cmd_executor.execute("undo").unwrap_err();
+    }
+
+    #[test]
+    pub fn _next_backoff_works() {
+        assert_eq!(
+            Duration::from_secs(2),
+            CommandExecutor::_next_backoff(Duration::from_secs(1))
+        );
+        assert_eq!(
+            MAX_RETRY_BACKOFF,
+            CommandExecutor::_next_backoff(Duration::from_secs(400))
+        );
+        assert_eq!(
+            MAX_RETRY_BACKOFF,
+            CommandExecutor::_next_backoff(Duration::MAX)
+        );
+    }
+
+    #[test]
+    pub fn _parse_retry_params_works() {
+        assert_eq!(
+            CommandExecutor::_parse_retry_params(
+                "attempts=5 backoff=2s -- ledger get-nym did=VsKV7grR1BUE29mG2Fm2kX"
+            )
+            .unwrap(),
+            (
+                "ledger get-nym did=VsKV7grR1BUE29mG2Fm2kX",
+                5,
+                Duration::from_secs(2)
+            )
+        );
+        assert_eq!(
+            CommandExecutor::_parse_retry_params("-- pool list").unwrap(),
+            ("pool list", DEFAULT_RETRY_ATTEMPTS, DEFAULT_RETRY_BACKOFF)
+        );
+        assert_eq!(
+            CommandExecutor::_parse_retry_params("backoff=500ms -- pool list").unwrap(),
+            (
+                "pool list",
+                DEFAULT_RETRY_ATTEMPTS,
+                Duration::from_millis(500)
+            )
+        );
+        CommandExecutor::_parse_retry_params("attempts=5 pool list").unwrap_err();
+        CommandExecutor::_parse_retry_params("attempts=0 -- pool list").unwrap_err();
+        CommandExecutor::_parse_retry_params("timeout=5 -- pool list").unwrap_err();
+    }
+
+    #[test]
+    pub fn _parse_schedule_params_works() {
+        assert_eq!(
+            CommandExecutor::_parse_schedule_params(
+                "at=\"2024-07-01T02:00Z\" -- ledger nym did=VsKV7grR1BUE29mG2Fm2kX"
+            )
+            .unwrap(),
+            (
+                "ledger nym did=VsKV7grR1BUE29mG2Fm2kX",
+                "2024-07-01T02:00Z",
+                None
+            )
+        );
+        assert_eq!(
+            CommandExecutor::_parse_schedule_params(
+                "at=2024-07-01T02:00Z unit=/tmp -- ledger nym did=VsKV7grR1BUE29mG2Fm2kX"
+            )
+            .unwrap(),
+            (
+                "ledger nym did=VsKV7grR1BUE29mG2Fm2kX",
+                "2024-07-01T02:00Z",
+                Some("/tmp")
+            )
+        );
+        CommandExecutor::_parse_schedule_params("-- ledger nym did=VsKV7grR1BUE29mG2Fm2kX")
+            .unwrap_err();
+        CommandExecutor::_parse_schedule_params("at=2024-07-01T02:00Z ledger nym").unwrap_err();
+        CommandExecutor::_parse_schedule_params("at=2024-07-01T02:00Z -- pool list").unwrap_err();
+        CommandExecutor::_parse_schedule_params(
+            "at=2024-07-01T02:00Z -- ledger nym did=VsKV7grR1BUE29mG2Fm2kX send=false",
+        )
+        .unwrap_err();
+    }
+
+    #[test]
+    pub fn execute_schedule_works_for_passed_time() {
+        let cmd_executor = CommandExecutor::build().finalize();
+        cmd_executor
+            .execute("schedule at=2020-01-01T00:00Z -- ledger nym did=VsKV7grR1BUE29mG2Fm2kX")
+            .unwrap_err();
+    }
+
+    #[test]
+    pub fn _parse_params_works_for_params_file() {
+        let command = test_command::new();
+        let metadata = command.metadata();
+        let yaml = EnvironmentUtils::tmp_file_path("test_command_params.yaml");
+        write_file(
+            &yaml,
+            "main_param: main\nparam1: value 1\nparam2:\n  nested: true\n",
+        )
+        .unwrap();
+        let json = EnvironmentUtils::tmp_file_path("test_command_params.json");
+        write_file(&json, r#"{"param1": "value 2"}"#).unwrap();
+
+        let params = CommandExecutor::_parse_params(
+            metadata,
+            &format!("@{} param1=inline", yaml.to_str().unwrap()),
+        )
+        .unwrap();
+        assert_eq!("main", params["main_param"]);
+        assert_eq!("inline", params["param1"]);
+        assert_eq!(r#"{"nested":true}"#, params["param2"]);
+
+        let params = CommandExecutor::_parse_params(
+            metadata,
+            &format!(
+                "main @{} @{}",
+                yaml.to_str().unwrap(),
+                json.to_str().unwrap()
+            ),
+        )
+        .unwrap();
+        assert_eq!("main", params["main_param"]);
+        assert_eq!("value 2", params["param1"]);
+
+        write_file(&json, r#"{"param3": "value"}"#).unwrap();
+        CommandExecutor::_parse_params(metadata, &format!("main @{}", json.to_str().unwrap()))
+            .unwrap_err();
+        CommandExecutor::_parse_params(metadata, &format!("@{}", json.to_str().unwrap()))
+            .unwrap_err();
+
+        fs::remove_file(yaml).unwrap();
+        fs::remove_file(json).unwrap();
+    }
+
     #[test]
     pub fn _trim_quites_works() {
         assert_eq!(CommandExecutor::_trim_quotes(""), "");
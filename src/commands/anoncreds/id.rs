@@ -0,0 +1,234 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::{
+    command_executor::{Command, CommandContext, CommandMetadata, CommandParams},
+    params_parser::ParamParser,
+    tools::{
+        anoncreds_id::{AnonCredsId, AnonCredsObject},
+        did::Did,
+    },
+    utils::table::print_table,
+};
+
+pub mod id_command {
+    use super::*;
+
+    command!(CommandMetadata::build(
+        "id",
+        "Parse, validate or build AnonCreds object identifier (schema, credential definition or revocation registry) \
+        and show it in legacy and did:indy forms."
+    )
+    .add_optional_param("id", "Identifier to parse (legacy or did:indy form)")
+    .add_optional_param("type", "Type of identifier to build: schema, cred-def or rev-reg")
+    .add_optional_param("did", "Issuer DID (for building)")
+    .add_optional_param("name", "Schema name (for building schema id)")
+    .add_optional_param("version", "Schema version (for building schema id)")
+    .add_optional_param("schema", "Schema sequence number (for building cred-def and rev-reg ids)")
+    .add_optional_param("cred_def_tag", "Credential definition tag (for building rev-reg id)")
+    .add_optional_param("tag", "Tag (for building cred-def and rev-reg ids)")
+    .add_optional_param(
+        "namespace",
+        "Indy network namespace used for did:indy form (e.g. sovrin:staging). \
+        Taken from the identifier or the connected pool by default"
+    )
+    .add_example("anoncreds id id=VsKV7grR1BUE29mG2Fm2kX:2:gvt:1.0 namespace=sovrin")
+    .add_example("anoncreds id id=did:indy:sovrin:VsKV7grR1BUE29mG2Fm2kX/anoncreds/v0/CLAIM_DEF/10/default")
+    .add_example("anoncreds id type=schema did=VsKV7grR1BUE29mG2Fm2kX name=gvt version=1.0")
+    .add_example("anoncreds id type=cred-def did=VsKV7grR1BUE29mG2Fm2kX schema=10 tag=default")
+    .add_example("anoncreds id type=rev-reg did=VsKV7grR1BUE29mG2Fm2kX schema=10 cred_def_tag=default tag=1")
+    .finalize());
+
+    fn execute(ctx: &CommandContext, params: &CommandParams) -> Result<(), ()> {
+        trace!("execute >> ctx {:?} params {:?}", ctx, params);
+
+        let id = ParamParser::get_opt_str_param(ctx, "id", params)?;
+        let id_type = ParamParser::get_opt_str_param(ctx, "type", params)?;
+        let namespace = ParamParser::get_opt_str_param(ctx, "namespace", params)?;
+
+        let mut id = match (id, id_type) {
+            (Some(id), None) => AnonCredsId::parse(id).map_err(|err| err.print(ctx, None))?,
+            (None, Some(id_type)) => build_id(ctx, params, id_type)?,
+            _ => {
+                println_err!(
+                    ctx,
+                    "Either `id` to parse or `type` to build must be specified"
+                );
+                return Err(());
+            }
+        };
+
+        if let Some(namespace) = namespace {
+            id = AnonCredsId::new(&id.did, Some(namespace), id.object)
+                .map_err(|err| err.print(ctx, None))?;
+        } else if id.namespace.is_none() {
+            id.namespace = ctx
+                .get_connected_pool()
+                .and_then(|pool| pool.namespace.clone());
+        }
+
+        let did_indy = id.to_did_indy();
+
+        print_table(
+            ctx,
+            &json!({
+                "type": id.type_name(),
+                "did": id.did,
+                "legacy": id.to_legacy(),
+                "didIndy": did_indy,
+            }),
+            &[
+                ("type", "Type"),
+                ("did", "Issuer DID"),
+                ("legacy", "Legacy Id"),
+                ("didIndy", "did:indy Id"),
+            ],
+        );
+
+        if did_indy.is_none() {
+            println_warn!(
+                ctx,
+                "Specify `namespace` or connect to a pool to get the did:indy form"
+            );
+        }
+
+        trace!("execute <<");
+        Ok(())
+    }
+
+    fn build_id(
+        ctx: &CommandContext,
+        params: &CommandParams,
+        id_type: &str,
+    ) -> Result<AnonCredsId, ()> {
+        let did = ParamParser::get_did_param(ctx, "did", params)?;
+
+        let object = match id_type {
+            "schema" => AnonCredsObject::Schema {
+                name: ParamParser::get_str_param(ctx, "name", params)?.to_string(),
+                version: ParamParser::get_str_param(ctx, "version", params)?.to_string(),
+            },
+            "cred-def" => AnonCredsObject::CredDef {
+                schema_seq_no: ParamParser::get_number_param::<u64>("schema", params)?,
+                tag: ParamParser::get_str_param(ctx, "tag", params)?.to_string(),
+            },
+            "rev-reg" => AnonCredsObject::RevReg {
+                schema_seq_no: ParamParser::get_number_param::<u64>("schema", params)?,
+                cred_def_tag: ParamParser::get_str_param(ctx, "cred_def_tag", params)?.to_string(),
+                tag: ParamParser::get_str_param(ctx, "tag", params)?.to_string(),
+            },
+            id_type => {
+                println_err!(
+                    ctx,
+                    "Unsupported identifier type \"{}\". Expected one of: schema, cred-def, rev-reg",
+                    id_type
+                );
+                return Err(());
+            }
+        };
+
+        AnonCredsId::new(
+            &Did::short_form(&did).0,
+            Did::namespace(&did).as_deref(),
+            object,
+        )
+        .map_err(|err| err.print(ctx, None))
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::commands::{setup, tear_down};
+
+    const DID: &str = "VsKV7grR1BUE29mG2Fm2kX";
+    const SCHEMA_ID: &str = "VsKV7grR1BUE29mG2Fm2kX:2:gvt:1.0";
+    const CRED_DEF_ID: &str = "VsKV7grR1BUE29mG2Fm2kX:3:CL:10:default";
+    const REV_REG_ID: &str =
+        "VsKV7grR1BUE29mG2Fm2kX:4:VsKV7grR1BUE29mG2Fm2kX:3:CL:10:default:CL_ACCUM:1";
+
+    mod id {
+        use super::*;
+
+        #[test]
+        pub fn id_works_for_parse() {
+            let ctx = setup();
+            {
+                let cmd = id_command::new();
+                let mut params = CommandParams::new();
+                params.insert("id", SCHEMA_ID.to_string());
+                params.insert("namespace", "sovrin".to_string());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            tear_down();
+        }
+
+        #[test]
+        pub fn id_works_for_build() {
+            let ctx = setup();
+            {
+                let cmd = id_command::new();
+                let mut params = CommandParams::new();
+                params.insert("type", "rev-reg".to_string());
+                params.insert("did", DID.to_string());
+                params.insert("schema", "10".to_string());
+                params.insert("cred_def_tag", "default".to_string());
+                params.insert("tag", "1".to_string());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            tear_down();
+        }
+
+        #[test]
+        pub fn id_works_for_invalid_id() {
+            let ctx = setup();
+            {
+                let cmd = id_command::new();
+                let mut params = CommandParams::new();
+                params.insert(
+                    "id",
+                    "VsKV7grR1BUE29mG2Fm2kX:3:CL:schema:default".to_string(),
+                );
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            tear_down();
+        }
+
+        #[test]
+        pub fn id_works_for_no_id_and_type() {
+            let ctx = setup();
+            {
+                let cmd = id_command::new();
+                let params = CommandParams::new();
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            tear_down();
+        }
+
+        #[test]
+        pub fn anoncreds_id_conversion_works() {
+            for legacy in [SCHEMA_ID, CRED_DEF_ID, REV_REG_ID] {
+                let mut id = AnonCredsId::parse(legacy).unwrap();
+                assert_eq!(id.to_legacy(), legacy);
+
+                id.namespace = Some("sovrin:staging".to_string());
+                let did_indy = id.to_did_indy().unwrap();
+                assert!(did_indy
+                    .starts_with("did:indy:sovrin:staging:VsKV7grR1BUE29mG2Fm2kX/anoncreds/v0/"));
+                assert_eq!(AnonCredsId::parse(&did_indy).unwrap(), id);
+            }
+        }
+
+        #[test]
+        pub fn anoncreds_id_parse_works_for_invalid_parts() {
+            assert!(AnonCredsId::parse("VsKV7grR1BUE29mG2Fm2kX:2:gvt").is_err());
+            assert!(AnonCredsId::parse("invalid:2:gvt:1.0").is_err());
+            assert!(AnonCredsId::parse("VsKV7grR1BUE29mG2Fm2kX:3:CL:0:default").is_err());
+            assert!(AnonCredsId::parse(
+                "did:indy:sovrin:VsKV7grR1BUE29mG2Fm2kX/anoncreds/v0/UNKNOWN/1"
+            )
+            .is_err());
+        }
+    }
+}
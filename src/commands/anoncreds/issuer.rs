@@ -0,0 +1,88 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::{
+    command_executor::{Command, CommandContext, CommandMetadata, CommandParams},
+    params_parser::ParamParser,
+    tools::anoncreds::CredentialDefinitionKeys,
+};
+
+use super::{output_object, required_input_object};
+
+pub mod create_offer_command {
+    use super::*;
+
+    command!(CommandMetadata::build("create-offer", "Create credential offer for the credential definition which keys are stored in the opened wallet \
+                                    (created by `ledger cred-def` without `primary`).")
+                .add_required_param("cred_def_id", "Identifier of the credential definition")
+                .add_optional_param("out", "Path to the file to write the offer to. The offer is printed if omitted")
+                .add_example("anoncreds create-offer cred_def_id=VsKV7grR1BUE29mG2Fm2kX:3:CL:10:TAG")
+                .add_example("anoncreds create-offer cred_def_id=VsKV7grR1BUE29mG2Fm2kX:3:CL:10:TAG out=/home/offer.json")
+                .finalize()
+    );
+
+    fn execute(ctx: &CommandContext, params: &CommandParams) -> Result<(), ()> {
+        trace!("execute >> ctx {:?} params {:?}", ctx, params);
+
+        let wallet = ctx.ensure_opened_wallet()?;
+
+        let cred_def_id = ParamParser::get_str_param(ctx, "cred_def_id", params)?;
+        let out = ParamParser::get_opt_str_param(ctx, "out", params)?;
+
+        let keys = CredentialDefinitionKeys::get(&wallet, cred_def_id)
+            .map_err(|err| err.print(ctx, None))?;
+        let offer = keys
+            .create_offer(cred_def_id)
+            .map_err(|err| err.print(ctx, None))?;
+
+        output_object(ctx, "Credential offer", &offer, out)?;
+
+        trace!("execute <<");
+        Ok(())
+    }
+}
+
+pub mod issue_command {
+    use super::*;
+
+    command!(CommandMetadata::build("issue", "Issue credential for the credential request with the keys of the credential definition stored in the opened wallet.")
+                .add_optional_param("offer", "Credential offer created by `anoncreds create-offer` in json format")
+                .add_optional_param("offer_file", "The path to file containing the credential offer (an alternative to the `offer` parameter)")
+                .add_optional_param("request", "Credential request created by `anoncreds create-request` in json format")
+                .add_optional_param("request_file", "The path to file containing the credential request (an alternative to the `request` parameter)")
+                .add_required_param("values", "Raw values of the credential attributes in json format: {\"<name>\": \"<value>\"}")
+                .add_optional_param("out", "Path to the file to write the credential to. The credential is printed if omitted")
+                .add_example(r#"anoncreds issue offer_file=/home/offer.json request_file=/home/request.json values={"name":"Alex","age":"28"} out=/home/credential.json"#)
+                .finalize()
+    );
+
+    fn execute(ctx: &CommandContext, params: &CommandParams) -> Result<(), ()> {
+        trace!("execute >> ctx {:?} params {:?}", ctx, params);
+
+        let wallet = ctx.ensure_opened_wallet()?;
+
+        let offer = required_input_object(ctx, params, "offer", "offer_file")?;
+        let request = required_input_object(ctx, params, "request", "request_file")?;
+        let values = ParamParser::get_object_param(ctx, "values", params)?;
+        let out = ParamParser::get_opt_str_param(ctx, "out", params)?;
+
+        let cred_def_id = offer["cred_def_id"].as_str().ok_or_else(|| {
+            println_err!(
+                ctx,
+                "Credential offer does not contain credential definition id"
+            )
+        })?;
+
+        let keys = CredentialDefinitionKeys::get(&wallet, cred_def_id)
+            .map_err(|err| err.print(ctx, None))?;
+        let credential = keys
+            .issue(&offer, &request, &values)
+            .map_err(|err| err.print(ctx, None))?;
+
+        output_object(ctx, "Credential", &credential, out)?;
+
+        trace!("execute <<");
+        Ok(())
+    }
+}
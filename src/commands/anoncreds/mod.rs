@@ -0,0 +1,183 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::{
+    command_executor::{CommandContext, CommandGroup, CommandGroupMetadata, CommandParams},
+    commands::ledger::{
+        common::export_anoncreds_object,
+        prefetch::{build_request, parse_object},
+    },
+    params_parser::ParamParser,
+    tools::{anoncreds::CredentialDefinitionKeys, anoncreds_id::AnonCredsId, ledger::Ledger},
+    utils::file::read_file,
+};
+
+use serde_json::Value as JsonValue;
+use std::collections::BTreeSet;
+
+pub mod id;
+pub mod issuer;
+pub mod prover;
+pub mod tails;
+pub mod verifier;
+
+pub use self::{id::*, issuer::*, prover::*, tails::*, verifier::*};
+
+pub mod group {
+    use super::*;
+
+    command_group!(CommandGroupMetadata::new(
+        "anoncreds",
+        "AnonCreds object helpers and local credential issuance"
+    ));
+}
+
+/// Write the created object into the file or print it if no file is given.
+pub fn output_object(
+    ctx: &CommandContext,
+    name: &str,
+    object: &JsonValue,
+    out: Option<&str>,
+) -> Result<(), ()> {
+    match out {
+        Some(file) => export_anoncreds_object(ctx, file, name, object),
+        None => {
+            println_succ!(ctx, "{} has been created", name);
+            println!(
+                "{}",
+                serde_json::to_string_pretty(object).unwrap_or_default()
+            );
+            Ok(())
+        }
+    }
+}
+
+/// JSON object given either inline with `name` parameter or as the path to the file with `file_name` parameter.
+pub fn input_object(
+    ctx: &CommandContext,
+    params: &CommandParams,
+    name: &str,
+    file_name: &str,
+) -> Result<Option<JsonValue>, ()> {
+    let file = ParamParser::get_opt_str_param(ctx, file_name, params)?;
+    match (ParamParser::get_opt_object_param(ctx, name, params)?, file) {
+        (Some(_), Some(_)) => {
+            println_err!(
+                ctx,
+                "Only one of `{}` and `{}` can be specified",
+                name,
+                file_name
+            );
+            Err(())
+        }
+        (Some(object), None) => Ok(Some(object)),
+        (None, Some(file)) => {
+            let content = read_file(file).map_err(|err| println_err!(ctx, "{}", err))?;
+            serde_json::from_str(&content)
+                .map(Some)
+                .map_err(|err| println_err!(ctx, "Invalid JSON in \"{}\" file: {}", file, err))
+        }
+        (None, None) => Ok(None),
+    }
+}
+
+pub fn required_input_object(
+    ctx: &CommandContext,
+    params: &CommandParams,
+    name: &str,
+    file_name: &str,
+) -> Result<JsonValue, ()> {
+    input_object(ctx, params, name, file_name)?.ok_or_else(|| {
+        println_err!(
+            ctx,
+            "Either `{}` or `{}` must be specified",
+            name,
+            file_name
+        )
+    })
+}
+
+/// Schemas and credential definitions with the ids in the AnonCreds object format, keyed by id.
+/// Objects passed with `schemas` and `cred_defs` parameters are taken first, then credential definitions
+/// which keys are stored in the opened wallet. The rest is fetched from the connected pool.
+pub fn resolve_objects(
+    ctx: &CommandContext,
+    params: &CommandParams,
+    schema_ids: &BTreeSet<String>,
+    cred_def_ids: &BTreeSet<String>,
+) -> Result<(JsonValue, JsonValue), ()> {
+    let mut schemas =
+        input_object(ctx, params, "schemas", "schemas_file")?.unwrap_or_else(|| json!({}));
+    let mut cred_defs =
+        input_object(ctx, params, "cred_defs", "cred_defs_file")?.unwrap_or_else(|| json!({}));
+    if !schemas.is_object() || !cred_defs.is_object() {
+        println_err!(
+            ctx,
+            "Schemas and credential definitions must be passed as JSON object keyed by id"
+        );
+        return Err(());
+    }
+
+    for id in schema_ids {
+        if schemas.get(id).is_none() {
+            schemas[id.as_str()] = fetch_object(ctx, id)?;
+        }
+    }
+    for id in cred_def_ids {
+        if cred_defs.get(id).is_none() {
+            let stored = ctx
+                .get_opened_wallet()
+                .and_then(|wallet| CredentialDefinitionKeys::get(&wallet, id).ok());
+            cred_defs[id.as_str()] = match stored {
+                Some(keys) => keys.definition,
+                None => fetch_object(ctx, id)?,
+            };
+        }
+    }
+    Ok((schemas, cred_defs))
+}
+
+fn fetch_object(ctx: &CommandContext, id: &str) -> Result<JsonValue, ()> {
+    let pool = ctx.get_connected_pool().ok_or_else(|| {
+        println_err!(
+            ctx,
+            "\"{}\" is not passed and there is no connected pool to fetch it from.",
+            id
+        )
+    })?;
+    let parsed = AnonCredsId::parse(id).map_err(|err| err.print(ctx, None))?;
+    let response = build_request(&pool, None, &parsed)
+        .and_then(|request| Ledger::submit_request(&pool, &request))
+        .map_err(|err| err.print(ctx, Some(&pool.name)))?;
+    parse_object(&parsed, &response)
+        .map_err(|err| println_err!(ctx, "Unable to fetch \"{}\": {}", id, err))
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    pub const ISSUER_DID: &str = "V4SGRU86Z58d6TV7PBUe6f";
+    pub const SCHEMA_ID: &str = "V4SGRU86Z58d6TV7PBUe6f:2:gvt:1.0";
+
+    pub fn schema() -> JsonValue {
+        json!({
+            "issuerId": ISSUER_DID,
+            "name": "gvt",
+            "version": "1.0",
+            "attrNames": ["name", "age"]
+        })
+    }
+
+    /// Generate keys of credential definition with `name` and `age` attributes and store them in the opened wallet.
+    pub fn generate_cred_def(ctx: &CommandContext) -> String {
+        let cred_def_id = format!("{}:3:CL:10:TAG", ISSUER_DID);
+        let keys =
+            CredentialDefinitionKeys::generate(ISSUER_DID, SCHEMA_ID, &schema(), "TAG", false)
+                .unwrap();
+        keys.store(&ctx.ensure_opened_wallet().unwrap(), &cred_def_id)
+            .unwrap();
+        cred_def_id
+    }
+}
@@ -0,0 +1,363 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::{
+    command_executor::{Command, CommandContext, CommandMetadata, CommandParams},
+    params_parser::ParamParser,
+    tools::anoncreds::{CredentialDefinitionKeys, Prover},
+};
+
+use serde_json::Value as JsonValue;
+use std::collections::{BTreeSet, HashMap};
+
+use super::{input_object, output_object, required_input_object, resolve_objects};
+
+const DEFAULT_LINK_SECRET: &str = "default";
+
+pub mod create_link_secret_command {
+    use super::*;
+
+    command!(CommandMetadata::build(
+        "create-link-secret",
+        "Create link secret and store it in the opened wallet. \
+                                    Credentials are bound to the link secret when requested."
+    )
+    .add_optional_param("id", "Identifier of the link secret (`default` by default)")
+    .add_example("anoncreds create-link-secret")
+    .add_example("anoncreds create-link-secret id=alice")
+    .finalize());
+
+    fn execute(ctx: &CommandContext, params: &CommandParams) -> Result<(), ()> {
+        trace!("execute >> ctx {:?} params {:?}", ctx, params);
+
+        let wallet = ctx.ensure_opened_wallet()?;
+
+        let id = ParamParser::get_opt_str_param(ctx, "id", params)?.unwrap_or(DEFAULT_LINK_SECRET);
+
+        Prover::create_link_secret(&wallet, id).map_err(|err| err.print(ctx, None))?;
+
+        println_succ!(ctx, "Link secret \"{}\" has been created", id);
+
+        trace!("execute <<");
+        Ok(())
+    }
+}
+
+pub mod create_request_command {
+    use super::*;
+
+    command!(CommandMetadata::build("create-request", "Create credential request for the credential offer using the link secret stored in the opened wallet.")
+                .add_optional_param("offer", "Credential offer in json format")
+                .add_optional_param("offer_file", "The path to file containing the credential offer (an alternative to the `offer` parameter)")
+                .add_optional_param("cred_def", "Credential definition in AnonCreds object format. \
+                    Taken from the opened wallet by default if its keys are stored there")
+                .add_optional_param("cred_def_file", "The path to file containing the credential definition as exported by `ledger get-cred-def` (an alternative to the `cred_def` parameter)")
+                .add_optional_param("link_secret", "Identifier of the link secret (`default` by default)")
+                .add_optional_param("out", "Path to the file to write the request to. The request is printed if omitted")
+                .add_example("anoncreds create-request offer_file=/home/offer.json out=/home/request.json")
+                .add_example("anoncreds create-request offer_file=/home/offer.json cred_def_file=/home/cred_def.json link_secret=alice")
+                .finalize()
+    );
+
+    fn execute(ctx: &CommandContext, params: &CommandParams) -> Result<(), ()> {
+        trace!("execute >> ctx {:?} params {:?}", ctx, params);
+
+        let wallet = ctx.ensure_opened_wallet()?;
+        let prover_did = ctx.get_active_did()?;
+
+        let offer = required_input_object(ctx, params, "offer", "offer_file")?;
+        let cred_def = input_object(ctx, params, "cred_def", "cred_def_file")?;
+        let link_secret = ParamParser::get_opt_str_param(ctx, "link_secret", params)?
+            .unwrap_or(DEFAULT_LINK_SECRET);
+        let out = ParamParser::get_opt_str_param(ctx, "out", params)?;
+
+        let cred_def = get_cred_def(ctx, cred_def, &offer)?;
+
+        let request = Prover::create_request(
+            &wallet,
+            prover_did.as_ref().map(|did| did.0.as_str()),
+            &cred_def,
+            &offer,
+            link_secret,
+        )
+        .map_err(|err| err.print(ctx, None))?;
+
+        output_object(ctx, "Credential request", &request, out)?;
+
+        trace!("execute <<");
+        Ok(())
+    }
+}
+
+pub mod store_credential_command {
+    use super::*;
+
+    command!(CommandMetadata::build("store-credential", "Store credential issued for the request created by `anoncreds create-request` in the opened wallet.")
+                .add_optional_param("credential", "Issued credential in json format")
+                .add_optional_param("credential_file", "The path to file containing the issued credential (an alternative to the `credential` parameter)")
+                .add_optional_param("cred_def", "Credential definition in AnonCreds object format. \
+                    Taken from the opened wallet by default if its keys are stored there")
+                .add_optional_param("cred_def_file", "The path to file containing the credential definition as exported by `ledger get-cred-def` (an alternative to the `cred_def` parameter)")
+                .add_optional_param("id", "Identifier to store the credential with. Generated by default")
+                .add_example("anoncreds store-credential credential_file=/home/credential.json")
+                .add_example("anoncreds store-credential credential_file=/home/credential.json id=degree")
+                .finalize()
+    );
+
+    fn execute(ctx: &CommandContext, params: &CommandParams) -> Result<(), ()> {
+        trace!("execute >> ctx {:?} params {:?}", ctx, params);
+
+        let wallet = ctx.ensure_opened_wallet()?;
+
+        let credential = required_input_object(ctx, params, "credential", "credential_file")?;
+        let cred_def = input_object(ctx, params, "cred_def", "cred_def_file")?;
+        let id = ParamParser::get_opt_str_param(ctx, "id", params)?;
+
+        let cred_def = get_cred_def(ctx, cred_def, &credential)?;
+
+        let id = Prover::store_credential(&wallet, id, &cred_def, &credential)
+            .map_err(|err| err.print(ctx, None))?;
+
+        println_succ!(
+            ctx,
+            "Credential has been stored in the wallet with id \"{}\"",
+            id
+        );
+
+        trace!("execute <<");
+        Ok(())
+    }
+}
+
+pub mod create_proof_command {
+    use super::*;
+
+    command!(CommandMetadata::build("create-proof", "Create proof for the proof request from the credentials stored in the opened wallet. \
+                                    All requested attributes are revealed. Revocation is not supported.")
+                .add_optional_param("proof_request", "Proof request in json format")
+                .add_optional_param("proof_request_file", "The path to file containing the proof request (an alternative to the `proof_request` parameter)")
+                .add_optional_param("credentials", "Credentials to use for referents of the proof request in json format: {\"<referent>\": \"<credential id>\"}. \
+                    The first stored credential satisfying the referent is used for the rest")
+                .add_optional_param("self_attested", "Values of self attested attributes in json format: {\"<referent>\": \"<value>\"}")
+                .add_optional_param("link_secret", "Identifier of the link secret (`default` by default)")
+                .add_optional_param("schemas", "Schemas of the credentials in json format: {\"<schema id>\": <schema>}. Fetched from the connected pool by default")
+                .add_optional_param("schemas_file", "The path to file containing the schemas (an alternative to the `schemas` parameter)")
+                .add_optional_param("cred_defs", "Credential definitions of the credentials in json format: {\"<cred def id>\": <cred def>}. \
+                    Taken from the opened wallet or fetched from the connected pool by default")
+                .add_optional_param("cred_defs_file", "The path to file containing the credential definitions (an alternative to the `cred_defs` parameter)")
+                .add_optional_param("out", "Path to the file to write the proof to. The proof is printed if omitted")
+                .add_example("anoncreds create-proof proof_request_file=/home/proof_request.json out=/home/proof.json")
+                .add_example(r#"anoncreds create-proof proof_request_file=/home/proof_request.json credentials={"attr1_referent":"degree"} self_attested={"attr2_referent":"8-800-300"}"#)
+                .finalize()
+    );
+
+    fn execute(ctx: &CommandContext, params: &CommandParams) -> Result<(), ()> {
+        trace!("execute >> ctx {:?} params {:?}", ctx, params);
+
+        let wallet = ctx.ensure_opened_wallet()?;
+
+        let proof_request =
+            required_input_object(ctx, params, "proof_request", "proof_request_file")?;
+        let chosen = get_string_map(ctx, "credentials", params)?;
+        let self_attested = get_string_map(ctx, "self_attested", params)?;
+        let link_secret = ParamParser::get_opt_str_param(ctx, "link_secret", params)?
+            .unwrap_or(DEFAULT_LINK_SECRET);
+        let out = ParamParser::get_opt_str_param(ctx, "out", params)?;
+
+        let credentials = Prover::list_credentials(&wallet).map_err(|err| err.print(ctx, None))?;
+        let selected =
+            Prover::select_credentials(&credentials, &proof_request, &chosen, &self_attested)
+                .map_err(|err| err.print(ctx, None))?;
+
+        let selected_ids = selected.credential_ids();
+        let mut schema_ids = BTreeSet::new();
+        let mut cred_def_ids = BTreeSet::new();
+        for (id, credential) in credentials.iter() {
+            if selected_ids.contains(&id.as_str()) {
+                schema_ids.extend(credential["schema_id"].as_str().map(String::from));
+                cred_def_ids.extend(credential["cred_def_id"].as_str().map(String::from));
+            }
+        }
+        let (schemas, cred_defs) = resolve_objects(ctx, params, &schema_ids, &cred_def_ids)?;
+
+        let proof = Prover::create_presentation(
+            &wallet,
+            &proof_request,
+            &selected,
+            self_attested,
+            link_secret,
+            &schemas,
+            &cred_defs,
+        )
+        .map_err(|err| err.print(ctx, None))?;
+
+        output_object(ctx, "Proof", &proof, out)?;
+
+        trace!("execute <<");
+        Ok(())
+    }
+
+    fn get_string_map(
+        ctx: &CommandContext,
+        name: &str,
+        params: &CommandParams,
+    ) -> Result<HashMap<String, String>, ()> {
+        match ParamParser::get_opt_object_param(ctx, name, params)? {
+            Some(object) => serde_json::from_value(object).map_err(|_| {
+                println_err!(ctx, "`{}` must be JSON object with string values", name)
+            }),
+            None => Ok(HashMap::new()),
+        }
+    }
+}
+
+// Offers and credentials refer to the credential definition by `cred_def_id` field
+fn get_cred_def(
+    ctx: &CommandContext,
+    cred_def: Option<JsonValue>,
+    object: &JsonValue,
+) -> Result<JsonValue, ()> {
+    if let Some(cred_def) = cred_def {
+        return Ok(cred_def);
+    }
+    let wallet = ctx.ensure_opened_wallet()?;
+    let cred_def_id = object["cred_def_id"].as_str().unwrap_or_default();
+    CredentialDefinitionKeys::get(&wallet, cred_def_id)
+        .map(|keys| keys.definition)
+        .map_err(|err| {
+            println_err!(
+                ctx,
+                "{} Pass the credential definition with `cred_def` parameter",
+                err.message(None)
+            )
+        })
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::{
+        anoncreds::{
+            create_offer_command, issue_command,
+            tests::{generate_cred_def, schema, SCHEMA_ID},
+        },
+        commands::{setup_with_wallet, tear_down_with_wallet},
+        tools::anoncreds::CATEGORY_CREDENTIAL,
+        utils::{environment::EnvironmentUtils, file::read_file, futures::block_on},
+    };
+
+    mod create_link_secret {
+        use super::*;
+
+        #[test]
+        pub fn create_link_secret_works() {
+            let ctx = setup_with_wallet();
+            {
+                let cmd = create_link_secret_command::new();
+                let params = CommandParams::new();
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            {
+                // Existing link secret is never replaced
+                let cmd = create_link_secret_command::new();
+                let mut params = CommandParams::new();
+                params.insert("id", DEFAULT_LINK_SECRET.to_string());
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            tear_down_with_wallet(&ctx);
+        }
+    }
+
+    /// Issue credential with `name` (Alex) and `age` (28) attributes and store it in the opened wallet with the id.
+    pub fn issue_credential(ctx: &CommandContext, id: &str) {
+        let cred_def_id = generate_cred_def(ctx);
+        let offer_path = EnvironmentUtils::tmp_file_path("offer.json");
+        let request_path = EnvironmentUtils::tmp_file_path("request.json");
+        let credential_path = EnvironmentUtils::tmp_file_path("credential.json");
+        {
+            let cmd = create_link_secret_command::new();
+            let params = CommandParams::new();
+            cmd.execute(ctx, &params).unwrap();
+        }
+        {
+            let cmd = create_offer_command::new();
+            let mut params = CommandParams::new();
+            params.insert("cred_def_id", cred_def_id.clone());
+            params.insert("out", offer_path.display().to_string());
+            cmd.execute(ctx, &params).unwrap();
+        }
+        {
+            let cmd = create_request_command::new();
+            let mut params = CommandParams::new();
+            params.insert("offer_file", offer_path.display().to_string());
+            params.insert("out", request_path.display().to_string());
+            cmd.execute(ctx, &params).unwrap();
+        }
+        {
+            let cmd = issue_command::new();
+            let mut params = CommandParams::new();
+            params.insert("offer", read_file(&offer_path).unwrap());
+            params.insert("request_file", request_path.display().to_string());
+            params.insert("values", r#"{"name":"Alex","age":"28"}"#.to_string());
+            params.insert("out", credential_path.display().to_string());
+            cmd.execute(ctx, &params).unwrap();
+        }
+        {
+            let cmd = store_credential_command::new();
+            let mut params = CommandParams::new();
+            params.insert("credential", read_file(&credential_path).unwrap());
+            params.insert("id", id.to_string());
+            cmd.execute(ctx, &params).unwrap();
+        }
+    }
+
+    mod issuance {
+        use super::*;
+
+        #[test]
+        pub fn issuance_works() {
+            let ctx = setup_with_wallet();
+            issue_credential(&ctx, "credential");
+            let wallet = ctx.ensure_opened_wallet().unwrap();
+            let stored = block_on(async {
+                wallet
+                    .fetch_record(CATEGORY_CREDENTIAL, "credential", false)
+                    .await
+                    .unwrap()
+            });
+            assert!(stored.is_some());
+            tear_down_with_wallet(&ctx);
+        }
+    }
+
+    mod create_proof {
+        use super::*;
+
+        #[test]
+        pub fn create_proof_works_for_unsatisfied_predicate() {
+            let ctx = setup_with_wallet();
+            issue_credential(&ctx, "credential");
+            {
+                let cmd = create_proof_command::new();
+                let mut params = CommandParams::new();
+                params.insert(
+                    "proof_request",
+                    json!({
+                        "name": "proof",
+                        "version": "1.0",
+                        "nonce": "1234567890",
+                        "requested_attributes": {},
+                        "requested_predicates": {
+                            "predicate1_referent": {"name": "age", "p_type": ">=", "p_value": 30}
+                        }
+                    })
+                    .to_string(),
+                );
+                params.insert("schemas", json!({ SCHEMA_ID: schema() }).to_string());
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            tear_down_with_wallet(&ctx);
+        }
+    }
+}
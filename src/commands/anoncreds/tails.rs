@@ -0,0 +1,109 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::{
+    command_executor::{Command, CommandContext, CommandMetadata, CommandParams},
+    params_parser::ParamParser,
+    tools::anoncreds::tails,
+};
+
+use std::path::Path;
+
+pub mod tails_hash_command {
+    use super::*;
+
+    command!(CommandMetadata::build(
+        "tails-hash",
+        "Compute the hash of the tails file as published in the revocation registry definition."
+    )
+    .add_required_param("file", "The path to the tails file")
+    .add_example(
+        "anoncreds tails-hash file=/home/tails/5sSVCxLTYHtAJvRKJXTaGY5qAsHn6TpzBpBdJQ2dAqD"
+    )
+    .finalize());
+
+    fn execute(ctx: &CommandContext, params: &CommandParams) -> Result<(), ()> {
+        trace!("execute >> ctx {:?} params {:?}", ctx, params);
+
+        let file = ParamParser::get_str_param(ctx, "file", params)?;
+
+        let hash = tails::tails_hash(Path::new(file)).map_err(|err| {
+            println_err!(ctx, "Unable to read \"{}\": {}", file, err.message(None))
+        })?;
+
+        println_succ!(ctx, "Tails hash: {}", hash);
+
+        trace!("execute <<");
+        Ok(())
+    }
+}
+
+pub mod upload_tails_command {
+    use super::*;
+
+    command!(CommandMetadata::build("upload-tails", "Upload the tails file to the tails server. \
+                                    The file is put by its hash, the printed location is to be published as `tails_location` of the revocation registry.")
+                .add_required_param("file", "The path to the tails file")
+                .add_required_param("server", "URL of the tails server")
+                .add_example("anoncreds upload-tails file=/home/tails/5sSVCxLTYHtAJvRKJXTaGY5qAsHn6TpzBpBdJQ2dAqD server=https://tails.example.com")
+                .finalize()
+    );
+
+    fn execute(ctx: &CommandContext, params: &CommandParams) -> Result<(), ()> {
+        trace!("execute >> ctx {:?} params {:?}", ctx, params);
+
+        let file = ParamParser::get_str_param(ctx, "file", params)?;
+        let server = ParamParser::get_str_param(ctx, "server", params)?;
+
+        let location =
+            tails::upload(server, Path::new(file)).map_err(|err| err.print(ctx, None))?;
+
+        println_succ!(ctx, "Tails file has been uploaded to \"{}\"", location);
+
+        trace!("execute <<");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::{
+        commands::{setup, tear_down},
+        utils::environment::EnvironmentUtils,
+    };
+    use std::fs;
+
+    mod tails_hash {
+        use super::*;
+
+        #[test]
+        pub fn tails_hash_works() {
+            let ctx = setup();
+            let path = EnvironmentUtils::tmp_file_path("tails_file");
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(&path, [0u8, 2u8]).unwrap();
+            {
+                let cmd = tails_hash_command::new();
+                let mut params = CommandParams::new();
+                params.insert("file", path.display().to_string());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            fs::remove_file(&path).unwrap();
+            tear_down();
+        }
+
+        #[test]
+        pub fn tails_hash_works_for_missing_file() {
+            let ctx = setup();
+            {
+                let cmd = tails_hash_command::new();
+                let mut params = CommandParams::new();
+                params.insert("file", "/unknown/tails".to_string());
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            tear_down();
+        }
+    }
+}
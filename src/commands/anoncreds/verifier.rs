@@ -0,0 +1,191 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::{
+    command_executor::{Command, CommandContext, CommandMetadata, CommandParams},
+    tools::anoncreds::Verifier,
+    utils::table::print_list_table,
+};
+
+use serde_json::Value as JsonValue;
+use std::collections::BTreeSet;
+
+use super::{required_input_object, resolve_objects};
+
+pub mod verify_proof_command {
+    use super::*;
+
+    command!(CommandMetadata::build("verify-proof", "Verify proof against the proof request and show the revealed attributes. Revocation is not checked.")
+                .add_optional_param("proof", "Proof in json format")
+                .add_optional_param("proof_file", "The path to file containing the proof (an alternative to the `proof` parameter)")
+                .add_optional_param("proof_request", "Proof request in json format")
+                .add_optional_param("proof_request_file", "The path to file containing the proof request (an alternative to the `proof_request` parameter)")
+                .add_optional_param("schemas", "Schemas referred by the proof in json format: {\"<schema id>\": <schema>}. Fetched from the connected pool by default")
+                .add_optional_param("schemas_file", "The path to file containing the schemas (an alternative to the `schemas` parameter)")
+                .add_optional_param("cred_defs", "Credential definitions referred by the proof in json format: {\"<cred def id>\": <cred def>}. \
+                    Taken from the opened wallet or fetched from the connected pool by default")
+                .add_optional_param("cred_defs_file", "The path to file containing the credential definitions (an alternative to the `cred_defs` parameter)")
+                .add_example("anoncreds verify-proof proof_file=/home/proof.json proof_request_file=/home/proof_request.json")
+                .finalize()
+    );
+
+    fn execute(ctx: &CommandContext, params: &CommandParams) -> Result<(), ()> {
+        trace!("execute >> ctx {:?} params {:?}", ctx, params);
+
+        let proof = required_input_object(ctx, params, "proof", "proof_file")?;
+        let proof_request =
+            required_input_object(ctx, params, "proof_request", "proof_request_file")?;
+
+        let mut schema_ids = BTreeSet::new();
+        let mut cred_def_ids = BTreeSet::new();
+        for identifier in proof["identifiers"].as_array().into_iter().flatten() {
+            schema_ids.extend(identifier["schema_id"].as_str().map(String::from));
+            cred_def_ids.extend(identifier["cred_def_id"].as_str().map(String::from));
+        }
+        let (schemas, cred_defs) = resolve_objects(ctx, params, &schema_ids, &cred_def_ids)?;
+
+        let valid = Verifier::verify(&proof, &proof_request, &schemas, &cred_defs)
+            .map_err(|err| err.print(ctx, None))?;
+        if !valid {
+            println_err!(ctx, "Proof is invalid");
+            return Err(());
+        }
+
+        println_succ!(ctx, "Proof is valid");
+        print_list_table(
+            ctx,
+            &revealed_values(&proof, &proof_request),
+            &[
+                ("referent", "Referent"),
+                ("name", "Attribute"),
+                ("value", "Value"),
+            ],
+            "",
+        );
+
+        trace!("execute <<");
+        Ok(())
+    }
+}
+
+// Attributes revealed one by one are named in the proof request only
+fn revealed_values(proof: &JsonValue, proof_request: &JsonValue) -> Vec<JsonValue> {
+    let requested_proof = &proof["requested_proof"];
+    let mut rows = Vec::new();
+    for (referent, attr) in requested_proof["revealed_attrs"]
+        .as_object()
+        .into_iter()
+        .flatten()
+    {
+        let name = &proof_request["requested_attributes"][referent]["name"];
+        rows.push(json!({ "referent": referent, "name": name, "value": attr["raw"] }));
+    }
+    for (referent, group) in requested_proof["revealed_attr_groups"]
+        .as_object()
+        .into_iter()
+        .flatten()
+    {
+        for (name, attr) in group["values"].as_object().into_iter().flatten() {
+            rows.push(json!({ "referent": referent, "name": name, "value": attr["raw"] }));
+        }
+    }
+    for (referent, value) in requested_proof["self_attested_attrs"]
+        .as_object()
+        .into_iter()
+        .flatten()
+    {
+        rows.push(json!({ "referent": referent, "name": "(self attested)", "value": value }));
+    }
+    for (referent, _) in requested_proof["predicates"]
+        .as_object()
+        .into_iter()
+        .flatten()
+    {
+        let predicate = &proof_request["requested_predicates"][referent];
+        rows.push(json!({
+            "referent": referent,
+            "name": predicate["name"],
+            "value": format!("{} {}", predicate["p_type"].as_str().unwrap_or_default(), predicate["p_value"]),
+        }));
+    }
+    rows
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::{
+        anoncreds::{
+            create_proof_command,
+            prover::tests::issue_credential,
+            tests::{schema, SCHEMA_ID},
+        },
+        commands::{setup_with_wallet, tear_down_with_wallet},
+        utils::{environment::EnvironmentUtils, file::read_file},
+    };
+
+    mod verify_proof {
+        use super::*;
+
+        fn proof_request() -> JsonValue {
+            json!({
+                "name": "proof",
+                "version": "1.0",
+                "nonce": "1234567890",
+                "requested_attributes": {
+                    "attr1_referent": {"name": "name", "restrictions": [{"schema_name": "gvt"}]},
+                    "attr2_referent": {"name": "phone"}
+                },
+                "requested_predicates": {
+                    "predicate1_referent": {"name": "age", "p_type": ">=", "p_value": 18}
+                }
+            })
+        }
+
+        #[test]
+        pub fn verify_proof_works() {
+            let ctx = setup_with_wallet();
+            issue_credential(&ctx, "credential");
+            let proof_path = EnvironmentUtils::tmp_file_path("proof.json");
+            let schemas = json!({ SCHEMA_ID: schema() }).to_string();
+            {
+                let cmd = create_proof_command::new();
+                let mut params = CommandParams::new();
+                params.insert("proof_request", proof_request().to_string());
+                params.insert(
+                    "self_attested",
+                    r#"{"attr2_referent":"8-800-300"}"#.to_string(),
+                );
+                params.insert("schemas", schemas.clone());
+                params.insert("out", proof_path.display().to_string());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            let proof: JsonValue = serde_json::from_str(&read_file(&proof_path).unwrap()).unwrap();
+            assert_eq!(
+                "Alex",
+                proof["requested_proof"]["revealed_attrs"]["attr1_referent"]["raw"]
+            );
+            {
+                let cmd = verify_proof_command::new();
+                let mut params = CommandParams::new();
+                params.insert("proof_file", proof_path.display().to_string());
+                params.insert("proof_request", proof_request().to_string());
+                params.insert("schemas", schemas.clone());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            {
+                // The proof was created for another nonce
+                let mut proof_request = proof_request();
+                proof_request["nonce"] = json!("1234567891");
+                let cmd = verify_proof_command::new();
+                let mut params = CommandParams::new();
+                params.insert("proof_file", proof_path.display().to_string());
+                params.insert("proof_request", proof_request.to_string());
+                params.insert("schemas", schemas);
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            tear_down_with_wallet(&ctx);
+        }
+    }
+}
@@ -11,22 +11,27 @@ pub mod about_command {
 
     command!(CommandMetadata::build("about", "Show about information").finalize());
 
-    fn execute(_ctx: &CommandContext, _params: &CommandParams) -> CommandResult {
-        trace!("execute >> _ctx: params: {:?}", _params);
+    fn execute(ctx: &CommandContext, _params: &CommandParams) -> CommandResult {
+        trace!("execute >> ctx: params: {:?}", _params);
 
         println_succ!(
+            ctx,
             "Hyperledger Aries Indy CLI (https://github.com/hyperledger/indy-cli-rs.git)"
         );
         println!();
         println_succ!(
+            ctx,
             "This is CLI tool for Hyperledger Indy (https://www.hyperledger.org/projects),"
         );
-        println_succ!("which provides a distributed-ledger-based foundation for");
-        println_succ!("self-sovereign identity (https://sovrin.org/).");
+        println_succ!(
+            ctx,
+            "which provides a distributed-ledger-based foundation for"
+        );
+        println_succ!(ctx, "self-sovereign identity (https://sovrin.org/).");
         println!();
-        println_succ!("Version: {}", env!("CARGO_PKG_VERSION"));
-        println_succ!("Apache License Version 2.0");
-        println_succ!("Copyright © 2023 Province of British Columbia");
+        println_succ!(ctx, "Version: {}", env!("CARGO_PKG_VERSION"));
+        println_succ!(ctx, "Apache License Version 2.0");
+        println_succ!(ctx, "Copyright © 2023 Province of British Columbia");
         println!();
 
         let res = Ok(());
@@ -0,0 +1,458 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::{
+    command_executor::{Command, CommandContext, CommandMetadata, CommandParams, CommandResult},
+    commands::pool::constants::DEFAULT_POOL_PROTOCOL_VERSION,
+    params_parser::ParamParser,
+    tools::{
+        ledger::{Ledger, LedgerHelpers},
+        pool::{health, Pool},
+        wallet::Wallet,
+    },
+    utils::{environment::EnvironmentUtils, table::print_list_table, time::format_duration},
+};
+
+use serde_json::Value as JsonValue;
+use std::{
+    fs,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+const DEFAULT_NETWORK_TIMEOUT: u64 = 5;
+// Ledger state is re-signed every 5 minutes even without new transactions
+const MAX_LEDGER_STATE_AGE: u64 = 900;
+const MAX_CLOCK_SKEW: u64 = 60;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Status {
+    Ok,
+    Warning,
+    Failure,
+    Skipped,
+}
+
+impl Status {
+    fn name(&self) -> &'static str {
+        match self {
+            Status::Ok => "OK",
+            Status::Warning => "WARNING",
+            Status::Failure => "FAILURE",
+            Status::Skipped => "SKIPPED",
+        }
+    }
+}
+
+struct Finding {
+    check: String,
+    status: Status,
+    details: String,
+    fix: &'static str,
+}
+
+impl Finding {
+    fn new(check: &str, status: Status, details: String, fix: &'static str) -> Finding {
+        Finding {
+            check: check.to_string(),
+            status,
+            details,
+            fix,
+        }
+    }
+
+    fn ok(check: &str, details: String) -> Finding {
+        Finding::new(check, Status::Ok, details, "")
+    }
+}
+
+pub mod doctor_command {
+    use super::*;
+
+    command!(CommandMetadata::build(
+        "doctor",
+        "Check CLI environment: home directory, pool genesis files, wallet storages, network reachability, clock skew and compatibility."
+    )
+    .add_optional_param("network", "Check reachability of pool nodes (true by default)")
+    .add_optional_param("timeout", "Timeout for a node connection (in sec, 5 by default)")
+    .add_example("doctor")
+    .add_example("doctor network=false")
+    .add_example("doctor timeout=10")
+    .finalize());
+
+    fn execute(ctx: &CommandContext, params: &CommandParams) -> CommandResult {
+        trace!("execute >> ctx: {:?}, params: {:?}", ctx, params);
+
+        let network = ParamParser::get_opt_bool_param(ctx, "network", params)?.unwrap_or(true);
+        let timeout = ParamParser::get_opt_number_param::<u64>("timeout", params)?
+            .unwrap_or(DEFAULT_NETWORK_TIMEOUT);
+
+        let mut findings = vec![check_home_directory()];
+        findings.extend(check_pools(network, Duration::from_secs(timeout)));
+        findings.extend(check_wallets());
+        findings.push(check_clock_skew(ctx));
+        findings.push(check_protocol_version(ctx));
+
+        let rows = findings
+            .iter()
+            .map(|finding| {
+                json!({
+                    "check": finding.check,
+                    "status": finding.status.name(),
+                    "details": finding.details,
+                    "fix": finding.fix,
+                })
+            })
+            .collect::<Vec<JsonValue>>();
+        print_list_table(
+            ctx,
+            &rows,
+            &[
+                ("check", "Check"),
+                ("status", "Status"),
+                ("details", "Details"),
+                ("fix", "How to fix"),
+            ],
+            "",
+        );
+
+        let problems = findings
+            .iter()
+            .filter(|finding| matches!(finding.status, Status::Warning | Status::Failure))
+            .count();
+        let res = if problems == 0 {
+            println_succ!(
+                ctx,
+                "No problems have been found. CLI version: {}",
+                env!("CARGO_PKG_VERSION")
+            );
+            Ok(())
+        } else if findings
+            .iter()
+            .any(|finding| finding.status == Status::Failure)
+        {
+            println_err!(ctx, "{} problem(s) have been found", problems);
+            Err(())
+        } else {
+            println_warn!(ctx, "{} warning(s) have been found", problems);
+            Ok(())
+        };
+
+        trace!("execute << {:?}", res);
+        res
+    }
+}
+
+fn check_home_directory() -> Finding {
+    const CHECK: &str = "Home directory";
+    let path = EnvironmentUtils::indy_home_path();
+
+    if !path.exists() {
+        return Finding::new(
+            CHECK,
+            Status::Skipped,
+            format!("\"{}\" does not exist yet", path.display()),
+            "It is created with the first wallet or pool",
+        );
+    }
+
+    let probe = path.join(".doctor");
+    if let Err(err) = fs::write(&probe, b"").and_then(|_| fs::remove_file(&probe)) {
+        return Finding::new(
+            CHECK,
+            Status::Failure,
+            format!("\"{}\" is not writable: {}", path.display(), err),
+            "Change the directory owner to the current user",
+        );
+    }
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+
+        if let Ok(metadata) = fs::metadata(&path) {
+            let mode = metadata.permissions().mode() & 0o777;
+            if mode & 0o077 != 0 {
+                return Finding::new(
+                    CHECK,
+                    Status::Warning,
+                    format!(
+                        "\"{}\" is accessible by other users (mode {:o})",
+                        path.display(),
+                        mode
+                    ),
+                    "Run `chmod 700` for the directory",
+                );
+            }
+        }
+    }
+
+    Finding::ok(CHECK, format!("\"{}\"", path.display()))
+}
+
+fn check_pools(network: bool, timeout: Duration) -> Vec<Finding> {
+    let pools = Pool::list()
+        .ok()
+        .and_then(|pools| serde_json::from_str::<Vec<JsonValue>>(&pools).ok())
+        .unwrap_or_default();
+
+    if pools.is_empty() {
+        return vec![Finding::new(
+            "Pools",
+            Status::Skipped,
+            "There are no pools".to_string(),
+            "Add a pool with `pool create`",
+        )];
+    }
+
+    let mut findings = Vec::new();
+    for pool in pools.iter().filter_map(|pool| pool["pool"].as_str()) {
+        let check = format!("Pool \"{}\" genesis", pool);
+        let genesis = match Pool::genesis_txn_path(pool) {
+            Ok(genesis) => genesis,
+            Err(_) => {
+                findings.push(Finding::new(
+                    &check,
+                    Status::Failure,
+                    "Pool config cannot be read".to_string(),
+                    "Delete the pool and create it again",
+                ));
+                continue;
+            }
+        };
+        match health::read_nodes(&genesis) {
+            Ok(nodes) if nodes.is_empty() => findings.push(Finding::new(
+                &check,
+                Status::Failure,
+                "No validator nodes are listed".to_string(),
+                "Recreate the pool with a genesis file of the network",
+            )),
+            Ok(nodes) => findings.push(Finding::ok(
+                &check,
+                format!("{} validator nodes", nodes.len()),
+            )),
+            Err(err) => {
+                findings.push(Finding::new(
+                    &check,
+                    Status::Failure,
+                    format!("\"{}\" is invalid: {}", genesis, err.message(None)),
+                    "Recreate the pool with a genesis file of the network",
+                ));
+                continue;
+            }
+        }
+
+        if network {
+            findings.push(check_pool_network(pool, timeout));
+        }
+    }
+    findings
+}
+
+fn check_pool_network(pool: &str, timeout: Duration) -> Finding {
+    let check = format!("Pool \"{}\" network", pool);
+
+    let nodes = match Pool::check_nodes(pool, timeout) {
+        Ok(nodes) => nodes,
+        Err(err) => {
+            return Finding::new(&check, Status::Failure, err.message(Some(pool)), "");
+        }
+    };
+
+    let unreachable = nodes
+        .iter()
+        .filter(|node| !node.is_reachable())
+        .map(|node| node.alias.as_str())
+        .collect::<Vec<&str>>();
+    let reachable = nodes.len() - unreachable.len();
+
+    if unreachable.is_empty() {
+        Finding::ok(&check, format!("All {} nodes are reachable", nodes.len()))
+    } else if reachable < health::consensus_quorum(nodes.len()) {
+        Finding::new(
+            &check,
+            Status::Failure,
+            format!(
+                "{} of {} nodes are reachable, not enough for consensus. Unreachable: {}",
+                reachable,
+                nodes.len(),
+                unreachable.join(", ")
+            ),
+            "Check the firewall and proxy settings or run `pool refresh` if the node addresses have changed",
+        )
+    } else {
+        Finding::new(
+            &check,
+            Status::Warning,
+            format!("Unreachable nodes: {}", unreachable.join(", ")),
+            "Run `pool refresh` if the node addresses have changed",
+        )
+    }
+}
+
+fn check_wallets() -> Vec<Finding> {
+    let mut findings = Vec::new();
+
+    for config in Wallet::list() {
+        let id = match config["id"].as_str() {
+            Some(id) => id,
+            None => continue,
+        };
+        let check = format!("Wallet \"{}\" storage", id);
+        findings.push(match Wallet::check_storage(id) {
+            Ok(true) => Finding::ok(&check, "Storage is readable".to_string()),
+            Ok(false) => Finding::new(
+                &check,
+                Status::Skipped,
+                "Remote storage is checked on open only".to_string(),
+                "",
+            ),
+            Err(err) => Finding::new(
+                &check,
+                Status::Failure,
+                err.message(None),
+                "Restore the wallet from a backup with `wallet import`",
+            ),
+        });
+    }
+
+    for name in Wallet::list_legacy() {
+        findings.push(Finding::new(
+            &format!("Wallet \"{}\" compatibility", name),
+            Status::Warning,
+            "Wallet has been created by the old Indy-CLI and cannot be opened".to_string(),
+            "Migrate it with `wallet migrate`",
+        ));
+    }
+
+    if findings.is_empty() {
+        findings.push(Finding::new(
+            "Wallets",
+            Status::Skipped,
+            "There are no wallets".to_string(),
+            "Create a wallet with `wallet create`",
+        ));
+    }
+    findings
+}
+
+fn check_clock_skew(ctx: &CommandContext) -> Finding {
+    const CHECK: &str = "Clock skew";
+
+    let pool = match ctx.get_connected_pool() {
+        Some(pool) => pool,
+        None => {
+            return Finding::new(
+                CHECK,
+                Status::Skipped,
+                "No pool is connected".to_string(),
+                "Connect to a pool to compare the local clock with the ledger",
+            )
+        }
+    };
+
+    let timestamp = Ledger::build_get_txn_author_agreement_request(Some(&pool), None, None)
+        .and_then(|request| Ledger::submit_request(&pool, &request))
+        .ok()
+        .and_then(|response| serde_json::from_str::<JsonValue>(&response).ok())
+        .and_then(|response| LedgerHelpers::get_state_proof_timestamp(&response));
+    let timestamp = match timestamp {
+        Some(timestamp) => timestamp,
+        None => {
+            return Finding::new(
+                CHECK,
+                Status::Skipped,
+                "Ledger reply carries no state proof timestamp".to_string(),
+                "",
+            )
+        }
+    };
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    if timestamp > now + MAX_CLOCK_SKEW {
+        Finding::new(
+            CHECK,
+            Status::Failure,
+            format!(
+                "Local clock is {} behind the ledger",
+                format_duration(timestamp - now)
+            ),
+            "Enable time synchronization (NTP): TAA acceptance time and freshness checks rely on the clock",
+        )
+    } else if now.saturating_sub(timestamp) > MAX_LEDGER_STATE_AGE {
+        Finding::new(
+            CHECK,
+            Status::Warning,
+            format!(
+                "Local clock is {} ahead of the ledger state",
+                format_duration(now - timestamp)
+            ),
+            "Enable time synchronization (NTP) or check that the pool is writing its state",
+        )
+    } else {
+        Finding::ok(
+            CHECK,
+            format!("Ledger \"{}\" state time is consistent", pool.name),
+        )
+    }
+}
+
+fn check_protocol_version(ctx: &CommandContext) -> Finding {
+    const CHECK: &str = "Compatibility";
+
+    let protocol_version = ctx.get_pool_protocol_version();
+    if protocol_version != DEFAULT_POOL_PROTOCOL_VERSION {
+        return Finding::new(
+            CHECK,
+            Status::Warning,
+            format!(
+                "Pool protocol version {} is used. Indy Node 1.4+ networks expect version {}",
+                protocol_version, DEFAULT_POOL_PROTOCOL_VERSION
+            ),
+            "Run `pool set-protocol-version 2` unless the network runs an old Indy Node",
+        );
+    }
+
+    Finding::ok(
+        CHECK,
+        format!(
+            "CLI {}, pool protocol version {}",
+            env!("CARGO_PKG_VERSION"),
+            protocol_version
+        ),
+    )
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::commands::{setup, tear_down};
+
+    mod doctor {
+        use super::*;
+
+        #[test]
+        pub fn doctor_works() {
+            let ctx = setup();
+            {
+                let cmd = doctor_command::new();
+                let mut params = CommandParams::new();
+                params.insert("network", "false".to_string());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            tear_down();
+        }
+
+        #[test]
+        pub fn check_protocol_version_works() {
+            let ctx = setup();
+            assert_eq!(check_protocol_version(&ctx).status, Status::Ok);
+            ctx.set_pool_protocol_version(1);
+            assert_eq!(check_protocol_version(&ctx).status, Status::Warning);
+            tear_down();
+        }
+    }
+}
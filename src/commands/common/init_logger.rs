@@ -16,17 +16,18 @@ pub mod init_logger_command {
                             .add_example("init-logger /home/logger.yml")
                             .finalize());
 
-    fn execute(_ctx: &CommandContext, params: &CommandParams) -> CommandResult {
+    fn execute(ctx: &CommandContext, params: &CommandParams) -> CommandResult {
         trace!("execute >> params: {:?}", params);
 
-        let file = ParamParser::get_str_param("file", params)?;
+        let file = ParamParser::get_str_param(ctx, "file", params)?;
 
         match logger::IndyCliLogger::init(&file) {
             Ok(()) => println_succ!(
+                ctx,
                 "Logger has been initialized according to the config file: \"{}\"",
                 file
             ),
-            Err(err) => println_err!("{}", err),
+            Err(err) => println_err!(ctx, "{}", err),
         };
 
         trace!("execute << ");
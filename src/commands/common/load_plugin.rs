@@ -20,9 +20,16 @@ pub mod load_plugin_command {
             .finalize()
     );
 
-    fn execute(_ctx: &CommandContext, params: &CommandParams) -> CommandResult {
+    fn execute(ctx: &CommandContext, params: &CommandParams) -> CommandResult {
         trace!("execute >> params: {:?}", params);
-        println_warn!("Command DEPRECATED!");
+        if ctx.is_indy_cli_compat() {
+            println_warn!(
+                ctx,
+                "Libindy plugins are not supported, the command has been skipped."
+            );
+        } else {
+            println_warn!(ctx, "Command DEPRECATED!");
+        }
         trace!("execute << ");
         Ok(())
     }
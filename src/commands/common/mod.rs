@@ -3,10 +3,20 @@
     https://digital.gov.bc.ca/digital-trust
 */
 pub mod about;
+pub mod doctor;
 pub mod exit;
 pub mod init_logger;
 pub mod load_plugin;
 pub mod prompt;
+pub mod schedule;
+pub mod self_update;
+pub mod selftest;
 pub mod show;
+pub mod sleep;
+pub mod tutorial;
+pub mod whoami;
 
-pub use self::{about::*, exit::*, init_logger::*, load_plugin::*, prompt::*, show::*};
+pub use self::{
+    about::*, doctor::*, exit::*, init_logger::*, load_plugin::*, prompt::*, self_update::*,
+    selftest::*, show::*, sleep::*, whoami::*,
+};
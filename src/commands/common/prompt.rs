@@ -4,9 +4,23 @@
 */
 use crate::{
     command_executor::{Command, CommandContext, CommandMetadata, CommandParams, CommandResult},
+    commands::ledger::get_did_role,
     params_parser::ParamParser,
+    tools::crypto_policy::CryptoPolicy,
+    utils::term::prompt_badge,
 };
 
+use ansi_term::Color;
+use indy_vdr::ledger::constants::TRUSTEE;
+
+pub const PRODUCTION_WARNING: &str = "production";
+pub const TRUSTEE_WARNING: &str = "trustee";
+pub const NON_STRICT_WARNING: &str = "non-strict";
+pub const PROMPT_WARNINGS: [&str; 3] = [PRODUCTION_WARNING, TRUSTEE_WARNING, NON_STRICT_WARNING];
+
+// Badges go before all other prompt parts
+const WARNINGS_PROMPT_POSITION: usize = 0;
+
 pub mod prompt_command {
     use super::*;
 
@@ -18,13 +32,97 @@ pub mod prompt_command {
     fn execute(ctx: &CommandContext, params: &CommandParams) -> CommandResult {
         trace!("execute >> ctx: {:?}, params: {:?}", ctx, params);
 
-        let prompt = ParamParser::get_str_param("prompt", params)?;
+        let prompt = ParamParser::get_str_param(ctx, "prompt", params)?;
 
         ctx.set_main_prompt(prompt.to_owned());
-        println_succ!("Command prompt has been set to \"{}\"", prompt);
+        println_succ!(ctx, "Command prompt has been set to \"{}\"", prompt);
         let res = Ok(());
 
         trace!("execute << {:?}", res);
         res
     }
 }
+
+/// Refresh the prompt badges warning about dangerous CLI states enabled in the CLI configuration:
+/// connected production pool, active DID having TRUSTEE role and not strict crypto policy.
+pub fn update_prompt_warnings(ctx: &CommandContext) {
+    let mut badges = Vec::new();
+    let pool = ctx.get_connected_pool();
+
+    if ctx.is_prompt_warning_enabled(PRODUCTION_WARNING) {
+        if let Some(pool) = pool
+            .as_ref()
+            .filter(|pool| ctx.is_production_pool(&pool.name))
+        {
+            badges.push(prompt_badge(&format!("PROD {}", pool.name), Color::Red));
+        }
+    }
+
+    if ctx.is_prompt_warning_enabled(TRUSTEE_WARNING) {
+        if let (Some(pool), Some(did)) = (pool.as_ref(), ctx.get_did()) {
+            // The role is requested from the ledger once for every pair of pool and DID
+            let key = format!("{}:{}", pool.name, did);
+            let role = match ctx.get_active_did_role(&key) {
+                Some(role) => role,
+                None => {
+                    let role = get_did_role(pool, &did);
+                    ctx.set_active_did_role(&key, role.clone());
+                    role
+                }
+            };
+            if role.as_deref() == Some(TRUSTEE) {
+                badges.push(prompt_badge("TRUSTEE", Color::Red));
+            }
+        }
+    }
+
+    if ctx.is_prompt_warning_enabled(NON_STRICT_WARNING)
+        && ctx.get_crypto_policy() != CryptoPolicy::Strict
+    {
+        badges.push(prompt_badge("NON-STRICT", Color::Yellow));
+    }
+
+    let badges = if badges.is_empty() {
+        None
+    } else {
+        Some(badges.join(""))
+    };
+    ctx.set_sub_prompt(WARNINGS_PROMPT_POSITION, badges);
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::{
+        commands::{setup, setup_with_wallet_and_pool, tear_down, tear_down_with_wallet_and_pool},
+        ledger::tests::use_trustee,
+    };
+
+    mod prompt_warnings {
+        use super::*;
+
+        #[test]
+        pub fn update_prompt_warnings_works() {
+            let ctx = setup_with_wallet_and_pool();
+            use_trustee(&ctx);
+            let pool = ctx.ensure_connected_pool().unwrap();
+            ctx.set_production_pools(&[pool.name.to_string()]);
+            ctx.set_prompt_warnings(&[PRODUCTION_WARNING.to_string(), TRUSTEE_WARNING.to_string()]);
+            update_prompt_warnings(&ctx);
+            let prompt = ctx.get_prompt();
+            assert!(prompt.starts_with(&format!("[PROD {}][TRUSTEE]:", pool.name)));
+            tear_down_with_wallet_and_pool(&ctx);
+        }
+
+        #[test]
+        pub fn update_prompt_warnings_works_for_non_strict() {
+            let ctx = setup();
+            update_prompt_warnings(&ctx);
+            assert_eq!(ctx.get_prompt(), "indy-cli-rs> ");
+            ctx.set_prompt_warnings(&[NON_STRICT_WARNING.to_string()]);
+            update_prompt_warnings(&ctx);
+            assert_eq!(ctx.get_prompt(), "[NON-STRICT]:indy-cli-rs> ");
+            tear_down();
+        }
+    }
+}
@@ -0,0 +1,178 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::utils::file::write_private_file;
+
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde_json::Value as JsonValue;
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    thread,
+    time::Duration,
+};
+
+// Waiting is split so that the wake up time follows system clock adjustments
+const MAX_SLEEP: Duration = Duration::from_secs(60);
+
+/// Parse the time a transaction is scheduled to: RFC 3339 with optional seconds, e.g. `2024-07-01T02:00Z`.
+pub fn parse_time(value: &str) -> Result<DateTime<Utc>, String> {
+    if let Ok(time) = DateTime::parse_from_rfc3339(value) {
+        return Ok(time.with_timezone(&Utc));
+    }
+    if let Ok(time) = DateTime::parse_from_str(value, "%Y-%m-%dT%H:%M%:z") {
+        return Ok(time.with_timezone(&Utc));
+    }
+    value
+        .strip_suffix('Z')
+        .and_then(|value| NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M").ok())
+        .map(|time| DateTime::<Utc>::from_utc(time, Utc))
+        .ok_or_else(|| {
+            format!(
+                "Invalid time \"{}\" provided. Expected RFC 3339 format, e.g. 2024-07-01T02:00Z",
+                value
+            )
+        })
+}
+
+/// Block the current thread until the scheduled time.
+pub fn wait_until(time: DateTime<Utc>) {
+    while let Ok(remaining) = (time - Utc::now()).to_std() {
+        if remaining.is_zero() {
+            break;
+        }
+        thread::sleep(remaining.min(MAX_SLEEP));
+    }
+}
+
+/// Files making a one-shot systemd user timer which sends the prepared transaction.
+/// The service runs CLI with a script connecting to the pool and sending the saved transaction.
+/// User units are run by the user who scheduled the transaction, so CLI finds the pool and wallet in the same home directory.
+pub struct ScheduleUnits {
+    pub name: String,
+    pub transaction: PathBuf,
+    pub script: PathBuf,
+    pub service: PathBuf,
+    pub timer: PathBuf,
+}
+
+impl ScheduleUnits {
+    pub fn new(dir: &Path, transaction: &JsonValue) -> ScheduleUnits {
+        let name = format!(
+            "indy-cli-schedule-{}",
+            transaction["reqId"].as_u64().unwrap_or_default()
+        );
+        ScheduleUnits {
+            transaction: dir.join(format!("{}.json", name)),
+            script: dir.join(format!("{}.cli", name)),
+            service: dir.join(format!("{}.service", name)),
+            timer: dir.join(format!("{}.timer", name)),
+            name,
+        }
+    }
+
+    pub fn write(
+        &self,
+        pool_name: &str,
+        transaction: &JsonValue,
+        time: DateTime<Utc>,
+    ) -> Result<(), String> {
+        let cli = env::current_exe()
+            .map_err(|err| format!("Unable to get the path of CLI executable: {}", err))?;
+
+        let script = format!(
+            "# Send the transaction scheduled to {}\n\
+            pool connect {}\n\
+            ledger load-transaction {}\n\
+            ledger custom context\n",
+            time.to_rfc3339(),
+            pool_name,
+            self.transaction.display()
+        );
+        let service = format!(
+            "[Unit]\n\
+            Description=Send scheduled Indy ledger transaction {}\n\
+            \n\
+            [Service]\n\
+            Type=oneshot\n\
+            ExecStart={} {}\n",
+            self.name,
+            cli.display(),
+            self.script.display()
+        );
+        let timer = format!(
+            "[Unit]\n\
+            Description=Timer for scheduled Indy ledger transaction {}\n\
+            \n\
+            [Timer]\n\
+            OnCalendar={}\n\
+            AccuracySec=1s\n\
+            \n\
+            [Install]\n\
+            WantedBy=timers.target\n",
+            self.name,
+            time.format("%Y-%m-%d %H:%M:%S UTC")
+        );
+
+        // the signed transaction is readable by the owner only
+        let write = |path: &Path, content: &str| {
+            fs::remove_file(path).ok();
+            write_private_file(&path.to_string_lossy(), content.as_bytes())
+                .map_err(|err| format!("Unable to write \"{}\": {}", path.display(), err))
+        };
+        write(&self.transaction, &transaction.to_string())?;
+        write(&self.script, &script)?;
+        write(&self.service, &service)?;
+        write(&self.timer, &timer)
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::utils::environment::EnvironmentUtils;
+
+    #[test]
+    pub fn parse_time_works() {
+        let expected = DateTime::parse_from_rfc3339("2024-07-01T02:00:00Z").unwrap();
+        assert_eq!(parse_time("2024-07-01T02:00Z").unwrap(), expected);
+        assert_eq!(parse_time("2024-07-01T02:00:00Z").unwrap(), expected);
+        assert_eq!(parse_time("2024-07-01T04:00+02:00").unwrap(), expected);
+        parse_time("2024-07-01").unwrap_err();
+        parse_time("tomorrow").unwrap_err();
+    }
+
+    #[test]
+    pub fn schedule_units_write_works() {
+        let dir = EnvironmentUtils::tmp_file_path("schedule");
+        fs::create_dir_all(&dir).unwrap();
+        let transaction = json!({"reqId": 1, "operation": {"type": "1"}});
+        let time = parse_time("2024-07-01T02:00Z").unwrap();
+
+        let units = ScheduleUnits::new(&dir, &transaction);
+        units.write("sandbox", &transaction, time).unwrap();
+
+        assert_eq!(units.name, "indy-cli-schedule-1");
+        let timer = fs::read_to_string(&units.timer).unwrap();
+        assert!(timer.contains("OnCalendar=2024-07-01 02:00:00 UTC"));
+        let script = fs::read_to_string(&units.script).unwrap();
+        assert!(script.contains("pool connect sandbox"));
+        assert!(script.contains(&units.transaction.display().to_string()));
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(&units.transaction)
+                .unwrap()
+                .permissions()
+                .mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+
+        // the units are overwritten when the transaction is scheduled once more
+        units.write("sandbox", &transaction, time).unwrap();
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,508 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::{
+    command_executor::{
+        wait_for_user_reply, Command, CommandContext, CommandMetadata, CommandParams, CommandResult,
+    },
+    params_parser::ParamParser,
+    tools::did::key::Key,
+    utils::term::print_prompt,
+};
+
+use flate2::read::GzDecoder;
+use indy_utils::{base64, hash::SHA256};
+use std::{
+    cmp::Ordering,
+    env,
+    env::consts::{ARCH, EXE_SUFFIX, OS},
+    fs,
+    io::Read,
+    path::Path,
+    time::Duration,
+};
+
+const RELEASES_URL: &str = "https://api.github.com/repos/hyperledger/indy-cli-rs/releases";
+const BINARY_NAME: &str = "indy-cli-rs";
+const CHANNELS: [&str; 2] = ["stable", "prerelease"];
+const REQUEST_TIMEOUT: u64 = 30;
+const DOWNLOAD_TIMEOUT: u64 = 300;
+// Verkey of the release publisher, embedded by the release workflow from `RELEASE_VERKEY` repository variable
+const RELEASE_VERKEY: Option<&str> = option_env!("INDY_CLI_RELEASE_VERKEY");
+
+#[derive(Debug, Deserialize)]
+struct Release {
+    tag_name: String,
+    #[serde(default)]
+    draft: bool,
+    #[serde(default)]
+    prerelease: bool,
+    #[serde(default)]
+    assets: Vec<ReleaseAsset>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ReleaseAsset {
+    name: String,
+    browser_download_url: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Version {
+    major: u64,
+    minor: u64,
+    patch: u64,
+    pre: Option<String>,
+}
+
+impl Version {
+    fn parse(version: &str) -> Option<Version> {
+        let version = version.trim().trim_start_matches('v');
+        let (version, pre) = match version.split_once('-') {
+            Some((version, pre)) => (version, Some(pre.to_string())),
+            None => (version, None),
+        };
+        let parts = version
+            .split('.')
+            .map(|part| part.parse::<u64>().ok())
+            .collect::<Option<Vec<u64>>>()?;
+        match parts.as_slice() {
+            [major, minor, patch] => Some(Version {
+                major: *major,
+                minor: *minor,
+                patch: *patch,
+                pre,
+            }),
+            _ => None,
+        }
+    }
+}
+
+// Pre-release precedes the release of the same version: 0.2.0-rc.1 < 0.2.0
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.major, self.minor, self.patch)
+            .cmp(&(other.major, other.minor, other.patch))
+            .then_with(|| match (&self.pre, &other.pre) {
+                (None, None) => Ordering::Equal,
+                (None, Some(_)) => Ordering::Greater,
+                (Some(_), None) => Ordering::Less,
+                (Some(pre), Some(other_pre)) => compare_pre_release(pre, other_pre),
+            })
+    }
+}
+
+// Identifiers are compared one by one as semver does: numeric ones as numbers and before alphanumeric ones,
+// so 0.2.0-rc.9 < 0.2.0-rc.10. A shorter pre-release precedes the longer one with the same prefix.
+fn compare_pre_release(pre: &str, other_pre: &str) -> Ordering {
+    let mut identifiers = pre.split('.');
+    let mut other_identifiers = other_pre.split('.');
+    loop {
+        let ordering = match (identifiers.next(), other_identifiers.next()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(identifier), Some(other_identifier)) => {
+                match (identifier.parse::<u64>(), other_identifier.parse::<u64>()) {
+                    (Ok(number), Ok(other_number)) => number.cmp(&other_number),
+                    (Ok(_), Err(_)) => Ordering::Less,
+                    (Err(_), Ok(_)) => Ordering::Greater,
+                    (Err(_), Err(_)) => identifier.cmp(other_identifier),
+                }
+            }
+        };
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+pub mod self_update_command {
+    use super::*;
+
+    command!(CommandMetadata::build(
+        "self-update",
+        "Update CLI to the latest released version: download the binary for the current platform, verify its checksum and signature and replace the executable."
+    )
+    .add_optional_param("channel", "Release channel: stable (default) or prerelease")
+    .add_optional_param("check", "Only check whether a new version is available (false by default)")
+    .add_optional_param(
+        "verkey",
+        "Verkey of the release publisher to verify the release signature with. The verkey embedded into the build is used by default"
+    )
+    .add_example("self-update")
+    .add_example("self-update check=true")
+    .add_example("self-update channel=prerelease")
+    .add_example("self-update verkey=GJ1SzoWzavQYfNL9XkaJdrQejfztN4XqdsiV4ct3LXKL")
+    .finalize());
+
+    fn execute(ctx: &CommandContext, params: &CommandParams) -> CommandResult {
+        trace!("execute >> ctx: {:?}, params: {:?}", ctx, params);
+
+        let channel = ParamParser::get_opt_str_param(ctx, "channel", params)?.unwrap_or("stable");
+        let check = ParamParser::get_opt_bool_param(ctx, "check", params)?.unwrap_or(false);
+        let verkey = ParamParser::get_opt_str_param(ctx, "verkey", params)?
+            .or(RELEASE_VERKEY.filter(|verkey| !verkey.is_empty()));
+
+        if !CHANNELS.contains(&channel) {
+            println_err!(
+                ctx,
+                "Unsupported release channel \"{}\". Expected one of: {}",
+                channel,
+                CHANNELS.join(", ")
+            );
+            return Err(());
+        }
+
+        let current = env!("CARGO_PKG_VERSION");
+        let current_version = Version::parse(current)
+            .ok_or_else(|| println_err!(ctx, "Invalid CLI version \"{}\"", current))?;
+
+        let releases = fetch_releases().map_err(|err| println_err!(ctx, "{}", err))?;
+        let (release, version) = select_release(&releases, channel == "prerelease")
+            .ok_or_else(|| println_err!(ctx, "No {} release has been found", channel))?;
+
+        if version <= current_version {
+            println_succ!(ctx, "CLI is up to date: {}", current);
+            return Ok(());
+        }
+
+        println!(
+            "New version {} is available (current version: {})",
+            release.tag_name, current
+        );
+        if check {
+            return Ok(());
+        }
+
+        let verkey = verkey.ok_or_else(|| {
+            println_err!(
+                ctx,
+                "The release publisher verkey is not embedded into this build. \
+                Pass the publisher `verkey` to verify the release signature"
+            )
+        })?;
+
+        let asset_name = asset_name(&release.tag_name);
+        let asset = find_asset(release, &asset_name).ok_or_else(|| {
+            println_err!(
+                ctx,
+                "Release {} has no binary for the current platform ({}-{})",
+                release.tag_name,
+                OS,
+                ARCH
+            )
+        })?;
+        let checksum_asset =
+            find_asset(release, &format!("{}.sha256", asset_name)).ok_or_else(|| {
+                println_err!(
+                    ctx,
+                    "Release {} has no checksum for \"{}\". The binary cannot be verified",
+                    release.tag_name,
+                    asset_name
+                )
+            })?;
+        let signature_asset =
+            find_asset(release, &format!("{}.sig", asset_name)).ok_or_else(|| {
+                println_err!(
+                    ctx,
+                    "Release {} has no signature for \"{}\". The binary cannot be verified",
+                    release.tag_name,
+                    asset_name
+                )
+            })?;
+
+        let executable = env::current_exe()
+            .map_err(|err| println_err!(ctx, "Can't locate the CLI executable: {}", err))?;
+
+        print_prompt(&format!(
+            "The executable \"{}\" will be replaced with {}. Would you like to continue? (y/n)",
+            executable.display(),
+            release.tag_name
+        ));
+        if !wait_for_user_reply(ctx) {
+            println!("Update has been cancelled");
+            return Ok(());
+        }
+
+        println!("Downloading \"{}\"...", asset.name);
+        let archive =
+            download(&asset.browser_download_url).map_err(|err| println_err!(ctx, "{}", err))?;
+
+        let checksum = download(&checksum_asset.browser_download_url)
+            .map_err(|err| println_err!(ctx, "{}", err))?;
+        verify_checksum(&archive, &checksum).map_err(|err| println_err!(ctx, "{}", err))?;
+
+        let signature = download(&signature_asset.browser_download_url)
+            .map_err(|err| println_err!(ctx, "{}", err))?;
+        verify_signature(&archive, &signature, verkey)
+            .map_err(|err| println_err!(ctx, "{}", err))?;
+
+        let binary = extract_binary(&archive).map_err(|err| println_err!(ctx, "{}", err))?;
+        replace_executable(&executable, &binary).map_err(|err| println_err!(ctx, "{}", err))?;
+
+        println_succ!(
+            ctx,
+            "CLI has been updated to {}. Restart it to use the new version",
+            release.tag_name
+        );
+
+        trace!("execute <<");
+        Ok(())
+    }
+}
+
+fn fetch_releases() -> Result<Vec<Release>, String> {
+    let to_error = |err: attohttpc::Error| format!("Can't fetch the list of releases: {}", err);
+    attohttpc::get(RELEASES_URL)
+        .header("Accept", "application/vnd.github+json")
+        .header("User-Agent", user_agent())
+        .timeout(Duration::from_secs(REQUEST_TIMEOUT))
+        .send()
+        .and_then(|response| response.error_for_status())
+        .map_err(to_error)?
+        .json::<Vec<Release>>()
+        .map_err(to_error)
+}
+
+fn download(url: &str) -> Result<Vec<u8>, String> {
+    let to_error = |err: attohttpc::Error| format!("Can't download \"{}\": {}", url, err);
+    attohttpc::get(url)
+        .header("User-Agent", user_agent())
+        .timeout(Duration::from_secs(DOWNLOAD_TIMEOUT))
+        .send()
+        .and_then(|response| response.error_for_status())
+        .map_err(to_error)?
+        .bytes()
+        .map_err(to_error)
+}
+
+// GitHub API rejects requests without User-Agent
+fn user_agent() -> String {
+    format!("{}/{}", BINARY_NAME, env!("CARGO_PKG_VERSION"))
+}
+
+fn select_release(releases: &[Release], include_prerelease: bool) -> Option<(&Release, Version)> {
+    releases
+        .iter()
+        .filter(|release| !release.draft && (include_prerelease || !release.prerelease))
+        .filter_map(|release| Version::parse(&release.tag_name).map(|version| (release, version)))
+        .max_by(|(_, version), (_, other_version)| version.cmp(other_version))
+}
+
+fn find_asset<'a>(release: &'a Release, name: &str) -> Option<&'a ReleaseAsset> {
+    release.assets.iter().find(|asset| asset.name == name)
+}
+
+// Asset names follow the release workflow: indy-cli-rs-<version>-<os>-<arch>.tar.gz
+fn asset_name(tag_name: &str) -> String {
+    let os = match OS {
+        "macos" => "darwin",
+        os => os,
+    };
+    format!(
+        "{}-{}-{}-{}.tar.gz",
+        BINARY_NAME,
+        tag_name.trim_start_matches('v'),
+        os,
+        ARCH
+    )
+}
+
+fn verify_checksum(archive: &[u8], checksum: &[u8]) -> Result<(), String> {
+    // The checksum file is either a bare digest or `sha256sum` output: "<digest>  <file name>"
+    let expected = String::from_utf8_lossy(checksum)
+        .split_whitespace()
+        .next()
+        .map(|digest| digest.to_lowercase())
+        .ok_or_else(|| "The release checksum is empty".to_string())?;
+    let actual = hex::encode(SHA256::digest(archive));
+    if expected != actual {
+        return Err(format!(
+            "Checksum mismatch: expected {}, got {}. The downloaded binary has been discarded",
+            expected, actual
+        ));
+    }
+    Ok(())
+}
+
+// The signature file holds the base64 encoded Ed25519 signature of the archive
+fn verify_signature(archive: &[u8], signature: &[u8], verkey: &str) -> Result<(), String> {
+    let signature = base64::decode(String::from_utf8_lossy(signature).trim())
+        .map_err(|_| "Invalid release signature: not a base64 string".to_string())?;
+    let valid = Key::verify(verkey, archive, &signature).map_err(|err| err.message(None))?;
+    if !valid {
+        return Err(
+            "Release signature is invalid. The downloaded binary has been discarded".to_string(),
+        );
+    }
+    Ok(())
+}
+
+fn extract_binary(archive: &[u8]) -> Result<Vec<u8>, String> {
+    let binary_name = format!("{}{}", BINARY_NAME, EXE_SUFFIX);
+    let to_error = |err: std::io::Error| format!("Can't unpack the release archive: {}", err);
+
+    let mut archive = tar::Archive::new(GzDecoder::new(archive));
+    for entry in archive.entries().map_err(to_error)? {
+        let mut entry = entry.map_err(to_error)?;
+        let is_binary = entry
+            .path()
+            .map_err(to_error)?
+            .file_name()
+            .map(|name| name == binary_name.as_str())
+            .unwrap_or(false);
+        if is_binary {
+            let mut binary = Vec::new();
+            entry.read_to_end(&mut binary).map_err(to_error)?;
+            return Ok(binary);
+        }
+    }
+    Err(format!(
+        "The release archive does not contain \"{}\"",
+        binary_name
+    ))
+}
+
+// The new binary is written next to the executable, so that the rename below stays within one file system.
+// A running executable can't be overwritten on Windows but can be renamed, so it is moved aside first.
+fn replace_executable(executable: &Path, binary: &[u8]) -> Result<(), String> {
+    let new_executable = executable.with_extension("new");
+    let to_error = |err: std::io::Error| {
+        fs::remove_file(&new_executable).ok();
+        format!(
+            "Can't replace the executable \"{}\": {}",
+            executable.display(),
+            err
+        )
+    };
+
+    fs::write(&new_executable, binary).map_err(to_error)?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&new_executable, fs::Permissions::from_mode(0o755))
+            .map_err(to_error)?;
+    }
+
+    let old_executable = executable.with_extension("old");
+    if cfg!(windows) {
+        fs::remove_file(&old_executable).ok();
+        fs::rename(executable, &old_executable).map_err(to_error)?;
+    }
+
+    if let Err(err) = fs::rename(&new_executable, executable) {
+        // the moved aside executable is put back so that CLI stays installed
+        if cfg!(windows) {
+            fs::rename(&old_executable, executable).ok();
+        }
+        return Err(to_error(err));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use aries_askar::kms::{KeyAlg, LocalKey};
+    use indy_utils::base58;
+
+    fn release(tag_name: &str, prerelease: bool) -> Release {
+        Release {
+            tag_name: tag_name.to_string(),
+            draft: false,
+            prerelease,
+            assets: Vec::new(),
+        }
+    }
+
+    mod self_update {
+        use super::*;
+
+        #[test]
+        pub fn version_ordering_works() {
+            let version = |version: &str| Version::parse(version).unwrap();
+            assert!(version("v0.2.0") > version("0.1.9"));
+            assert!(version("0.10.0") > version("0.9.1"));
+            assert!(version("0.2.0") > version("0.2.0-rc.1"));
+            assert!(version("0.2.0-rc.10") > version("0.2.0-rc.9"));
+            assert!(version("0.2.0-rc.1") > version("0.2.0-beta.2"));
+            assert!(version("0.2.0-rc") > version("0.2.0-1"));
+            assert!(version("0.2.0-rc.1") > version("0.2.0-rc"));
+            assert_eq!(version("v0.1.0"), version("0.1.0"));
+            assert!(Version::parse("latest").is_none());
+        }
+
+        #[test]
+        pub fn select_release_works_for_channels() {
+            let mut draft = release("v0.4.0", false);
+            draft.draft = true;
+            let releases = vec![
+                release("v0.2.0", false),
+                release("v0.3.0-rc.1", true),
+                release("nightly", true),
+                draft,
+            ];
+
+            let (stable, _) = select_release(&releases, false).unwrap();
+            assert_eq!(stable.tag_name, "v0.2.0");
+
+            let (prerelease, _) = select_release(&releases, true).unwrap();
+            assert_eq!(prerelease.tag_name, "v0.3.0-rc.1");
+        }
+
+        #[test]
+        pub fn extract_binary_works() {
+            let binary = b"binary".to_vec();
+            let mut builder = tar::Builder::new(flate2::write::GzEncoder::new(
+                Vec::new(),
+                flate2::Compression::default(),
+            ));
+            let mut header = tar::Header::new_gnu();
+            header.set_size(binary.len() as u64);
+            header.set_mode(0o755);
+            header.set_cksum();
+            builder
+                .append_data(
+                    &mut header,
+                    format!("./{}{}", BINARY_NAME, EXE_SUFFIX),
+                    binary.as_slice(),
+                )
+                .unwrap();
+            let archive = builder.into_inner().unwrap().finish().unwrap();
+
+            assert_eq!(extract_binary(&archive).unwrap(), binary);
+            assert!(extract_binary(b"not an archive").is_err());
+        }
+
+        #[test]
+        pub fn verify_checksum_works() {
+            let archive = b"archive";
+            let digest = hex::encode(SHA256::digest(archive));
+            verify_checksum(archive, digest.as_bytes()).unwrap();
+            verify_checksum(archive, format!("{}  archive.tar.gz\n", digest).as_bytes()).unwrap();
+            verify_checksum(b"modified", digest.as_bytes()).unwrap_err();
+        }
+
+        #[test]
+        pub fn verify_signature_works() {
+            let key = LocalKey::from_secret_bytes(KeyAlg::Ed25519, &[1; 32]).unwrap();
+            let verkey = base58::encode(key.to_public_bytes().unwrap());
+            let archive = b"archive";
+            let signature = base64::encode(key.sign_message(archive, None).unwrap());
+
+            verify_signature(archive, signature.as_bytes(), &verkey).unwrap();
+            verify_signature(archive, format!("{}\n", signature).as_bytes(), &verkey).unwrap();
+            verify_signature(b"modified", signature.as_bytes(), &verkey).unwrap_err();
+            verify_signature(archive, b"not a signature", &verkey).unwrap_err();
+        }
+    }
+}
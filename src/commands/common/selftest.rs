@@ -0,0 +1,464 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::{
+    command_executor::{Command, CommandContext, CommandMetadata, CommandParams, CommandResult},
+    ledger::get_active_transaction_author_agreement,
+    params_parser::ParamParser,
+    tools::{
+        did::Did,
+        ledger::{Ledger, Response, ResponseType},
+        pool::Pool,
+        wallet::{wallet_config::WalletConfig, Credentials, Wallet},
+    },
+    utils::table::print_list_table,
+};
+
+use aries_askar::kms::{KeyAlg, LocalKey};
+use chrono::Utc;
+use indy_utils::{base58, did::DidValue};
+use indy_vdr::{
+    config::PoolConfig,
+    ledger::{
+        identifiers::SchemaId,
+        requests::schema::{AttributeNames, Schema, SchemaV1},
+    },
+    pool::{PreparedRequest, ProtocolVersion},
+};
+use serde_json::Value as JsonValue;
+use std::time::Instant;
+
+const SELFTEST_SCHEMA_NAME: &str = "selftest";
+const DOMAIN_LEDGER_ID: i32 = 1;
+
+// (text, version, acceptance mechanism, time of acceptance)
+pub type Acceptance = (String, String, String, u64);
+
+#[derive(Default)]
+struct Report {
+    rows: Vec<JsonValue>,
+    failed: usize,
+}
+
+impl Report {
+    /// Run the step and record its outcome. Returns the step result if it passed.
+    fn run<T>(&mut self, step: &str, f: impl FnOnce() -> Result<(T, String), String>) -> Option<T> {
+        let start = Instant::now();
+        let res = f();
+        let elapsed = format!("{} ms", start.elapsed().as_millis());
+        match res {
+            Ok((value, details)) => {
+                self.push(step, "PASS", &details, &elapsed);
+                Some(value)
+            }
+            Err(details) => {
+                self.failed += 1;
+                self.push(step, "FAIL", &details, &elapsed);
+                None
+            }
+        }
+    }
+
+    fn skip(&mut self, step: &str, reason: &str) {
+        self.push(step, "SKIPPED", reason, "");
+    }
+
+    fn push(&mut self, step: &str, status: &str, details: &str, elapsed: &str) {
+        self.rows.push(json!({
+            "step": step,
+            "status": status,
+            "details": details,
+            "elapsed": elapsed,
+        }))
+    }
+}
+
+pub mod selftest_command {
+    use super::*;
+
+    command!(CommandMetadata::build(
+        "selftest",
+        "Run an end-to-end smoke test against the pool: create a temporary wallet and DID, \
+        write a NYM signed by the trustee and a schema, and read them back. \
+        The connected pool, opened wallet and active DID are not affected."
+    )
+    .add_required_param("pool", "The name of the pool to test")
+    .add_optional_deferred_param(
+        "trustee_seed",
+        "Seed of a Trustee DID on the pool. Write steps are skipped without it"
+    )
+    .add_example("selftest pool=sandbox")
+    .add_example("selftest pool=sandbox trustee_seed")
+    .finalize());
+
+    fn execute(ctx: &CommandContext, params: &CommandParams) -> CommandResult {
+        trace!("execute >> ctx: {:?}, params: {:?}", ctx, secret!(params));
+
+        let pool_name = ParamParser::get_str_param(ctx, "pool", params)?;
+        let trustee_seed = ParamParser::get_opt_str_param(ctx, "trustee_seed", params)?;
+
+        let mut report = Report::default();
+        run(ctx, &mut report, pool_name, trustee_seed);
+
+        print_list_table(
+            ctx,
+            &report.rows,
+            &[
+                ("step", "Step"),
+                ("status", "Status"),
+                ("details", "Details"),
+                ("elapsed", "Time"),
+            ],
+            "",
+        );
+
+        let res = if report.failed == 0 {
+            println_succ!(ctx, "Pool \"{}\" has passed the self-test", pool_name);
+            Ok(())
+        } else {
+            println_err!(
+                ctx,
+                "Pool \"{}\" has failed {} step(s) of the self-test",
+                pool_name,
+                report.failed
+            );
+            Err(())
+        };
+
+        trace!("execute << {:?}", res);
+        res
+    }
+}
+
+fn run(ctx: &CommandContext, report: &mut Report, pool_name: &str, trustee_seed: Option<&str>) {
+    let pool = match report.run("Connect to pool", || {
+        let protocol_version = ProtocolVersion::from_id(ctx.get_pool_protocol_version() as i64)
+            .map_err(|err| err.to_string())?;
+        let config = PoolConfig {
+            protocol_version,
+            ..PoolConfig::default()
+        };
+        Pool::open(pool_name, config, None)
+            .map(|pool| (pool, format!("Pool \"{}\" has been connected", pool_name)))
+            .map_err(|err| err.message(Some(pool_name)))
+    }) {
+        Some(pool) => pool,
+        None => return,
+    };
+
+    let (config, credentials) = match temporary_wallet("selftest") {
+        Ok(wallet) => wallet,
+        Err(err) => {
+            report.run::<()>("Create temporary wallet", || Err(err));
+            pool.close().ok();
+            return;
+        }
+    };
+    if let Some(wallet) = report.run("Create temporary wallet", || {
+        Wallet::create(&config, &credentials, ctx.get_crypto_policy())
+            .and_then(|_| Wallet::open(&config, &credentials, ctx.get_crypto_policy()))
+            .map(|wallet| (wallet, format!("Wallet \"{}\"", config.id)))
+            .map_err(|err| err.message(Some(&config.id)))
+    }) {
+        run_ledger_steps(ctx, report, &pool, &wallet, trustee_seed);
+        wallet.close().ok();
+        Wallet::delete(&config, &credentials).ok();
+    }
+
+    pool.close().ok();
+}
+
+fn run_ledger_steps(
+    ctx: &CommandContext,
+    report: &mut Report,
+    pool: &Pool,
+    wallet: &Wallet,
+    trustee_seed: Option<&str>,
+) {
+    let (did, verkey) = match report.run("Create DID", || {
+        Did::create(wallet, None, None, None, None)
+            .map(|(did, verkey)| ((did.clone(), verkey), format!("DID {}", did)))
+            .map_err(|err| err.message(None))
+    }) {
+        Some(did) => did,
+        None => return,
+    };
+    let did = DidValue(did);
+
+    report.run("Read genesis transaction", || {
+        let request = Ledger::build_get_txn_request(Some(pool), DOMAIN_LEDGER_ID, 1)
+            .map_err(|err| err.message(None))?;
+        let result = read(pool, &request)?;
+        Ok(((), format!("Domain ledger transaction {}", result["seqNo"])))
+    });
+
+    const WRITE_STEPS: [&str; 5] = [
+        "Transaction author agreement",
+        "Write NYM",
+        "Read NYM",
+        "Write schema",
+        "Read schema",
+    ];
+    let trustee_seed = match trustee_seed {
+        Some(trustee_seed) => trustee_seed,
+        None => {
+            for step in WRITE_STEPS {
+                report.skip(step, "`trustee_seed` is not provided");
+            }
+            return;
+        }
+    };
+
+    let acceptance = match report.run(WRITE_STEPS[0], || {
+        transaction_author_agreement(ctx, pool, "self-test")
+    }) {
+        Some(acceptance) => acceptance,
+        None => return,
+    };
+
+    let trustee = match Did::create(wallet, None, Some(trustee_seed), None, None) {
+        Ok((trustee, _)) => DidValue(trustee),
+        Err(err) => {
+            report.run::<()>(WRITE_STEPS[1], || Err(err.message(None)));
+            return;
+        }
+    };
+
+    // the new DID becomes an Endorser to be able to write the schema
+    let written = report.run(WRITE_STEPS[1], || {
+        let request = Ledger::build_nym_request(
+            Some(pool),
+            &trustee,
+            &did,
+            Some(&verkey),
+            None,
+            Some("ENDORSER"),
+        )
+        .map_err(|err| err.message(None))?;
+        let result = write(pool, wallet, &trustee, request, acceptance.as_ref())?;
+        Ok((
+            (),
+            format!("NYM {} signed by {}", did, trustee) + &seq_no(&result),
+        ))
+    });
+    if written.is_none() {
+        return;
+    }
+
+    report.run(WRITE_STEPS[2], || {
+        let request = Ledger::build_get_nym_request(Some(pool), None, &did)
+            .map_err(|err| err.message(None))?;
+        let result = read(pool, &request)?;
+        let data = result["data"]
+            .as_str()
+            .and_then(|data| serde_json::from_str::<JsonValue>(data).ok())
+            .ok_or_else(|| format!("NYM {} has not been found", did))?;
+        if data["verkey"].as_str() != Some(verkey.as_str()) {
+            return Err(format!("NYM {} has an unexpected verkey", did));
+        }
+        Ok(((), format!("NYM {} with role {}", did, data["role"])))
+    });
+
+    // unique version so that the schema can be written on every run
+    let version = format!("1.{}", Utc::now().timestamp());
+    let id = SchemaId::new(&did, SELFTEST_SCHEMA_NAME, &version);
+    let written = report.run(WRITE_STEPS[3], || {
+        let schema = Schema::SchemaV1(SchemaV1 {
+            id: id.clone(),
+            name: SELFTEST_SCHEMA_NAME.to_string(),
+            version: version.clone(),
+            attr_names: AttributeNames::from(["name"].as_slice()),
+            seq_no: None,
+        });
+        let request = Ledger::build_schema_request(Some(pool), &did, schema)
+            .map_err(|err| err.message(None))?;
+        let result = write(pool, wallet, &did, request, acceptance.as_ref())?;
+        Ok(((), format!("Schema {}", id) + &seq_no(&result)))
+    });
+    if written.is_none() {
+        return;
+    }
+
+    report.run(WRITE_STEPS[4], || {
+        let request = Ledger::build_get_schema_request(Some(pool), None, &id)
+            .map_err(|err| err.message(None))?;
+        let result = read(pool, &request)?;
+        match result["seqNo"].as_i64() {
+            Some(seq_no) => Ok(((), format!("Schema {} (seqNo {})", id, seq_no))),
+            None => Err(format!("Schema {} has not been found", id)),
+        }
+    });
+}
+
+// The wallet is removed after use, so its key is random and never shown
+pub fn temporary_wallet(prefix: &str) -> Result<(WalletConfig, Credentials), String> {
+    let key = LocalKey::generate(KeyAlg::Ed25519, true)
+        .and_then(|key| key.to_secret_bytes())
+        .map_err(|err| err.to_string())?;
+    let config = WalletConfig {
+        id: format!("{}-{}", prefix, Utc::now().timestamp_millis()),
+        storage_type: "default".to_string(),
+        storage_config: None,
+    };
+    let credentials = Credentials {
+        key: base58::encode(&key[..]),
+        key_derivation_method: None,
+        rekey: None,
+        rekey_derivation_method: None,
+        storage_credentials: None,
+    };
+    Ok((config, credentials))
+}
+
+// Acceptance of the pool Agreement: the one accepted in the CLI session or an automatic one if `taaAutoAccept` is set
+pub fn transaction_author_agreement(
+    ctx: &CommandContext,
+    pool: &Pool,
+    purpose: &str,
+) -> Result<(Option<Acceptance>, String), String> {
+    let (text, version, _) = match get_active_transaction_author_agreement(ctx, pool)
+        .map_err(|_| "Unable to read the Agreement".to_string())?
+    {
+        Some(agreement) => agreement,
+        None => return Ok((None, "The pool has no Agreement".to_string())),
+    };
+
+    let mechanism = ctx.get_taa_acceptance_mechanism();
+    if mechanism.is_empty() {
+        return Err("Transaction author agreement Acceptance Mechanism isn't set.".to_string());
+    }
+    if let Some((accepted_text, accepted_version, _, time)) = ctx.get_transaction_author_info() {
+        if accepted_text == text && accepted_version == version {
+            let details = format!("Version \"{}\" accepted in the session", version);
+            return Ok((Some((text, version, mechanism, time)), details));
+        }
+    }
+    if !ctx.is_taa_auto_accept() {
+        return Err(format!(
+            "Version \"{}\" is not accepted. Accept it with `pool show-taa` or set `taaAutoAccept` in the CLI config",
+            version
+        ));
+    }
+
+    info!(
+        target: "audit",
+        "Transaction Author Agreement accepted automatically for {}: pool \"{}\", version \"{}\", mechanism \"{}\"",
+        purpose,
+        pool.name,
+        version,
+        mechanism
+    );
+    let time = Utc::now().timestamp() as u64;
+    let details = format!("Version \"{}\" accepted automatically", version);
+    Ok((Some((text, version, mechanism, time)), details))
+}
+
+pub fn write(
+    pool: &Pool,
+    wallet: &Wallet,
+    submitter_did: &DidValue,
+    mut request: PreparedRequest,
+    acceptance: Option<&Acceptance>,
+) -> Result<JsonValue, String> {
+    if let Some((text, version, mechanism, time)) = acceptance {
+        Ledger::append_txn_author_agreement_acceptance_to_request(
+            Some(pool),
+            &mut request,
+            Some(text),
+            Some(version),
+            None,
+            mechanism,
+            *time,
+        )
+        .map_err(|err| err.message(None))?;
+    }
+    let response = Ledger::sign_and_submit_request(pool, wallet, submitter_did, &mut request)
+        .map_err(|err| err.message(Some(&pool.name)))?;
+    reply(&response)
+}
+
+fn read(pool: &Pool, request: &PreparedRequest) -> Result<JsonValue, String> {
+    let response =
+        Ledger::submit_request(pool, request).map_err(|err| err.message(Some(&pool.name)))?;
+    let result = reply(&response)?;
+    if result["data"].is_null() {
+        return Err("Not found".to_string());
+    }
+    Ok(result)
+}
+
+pub fn reply(response: &str) -> Result<JsonValue, String> {
+    let response = serde_json::from_str::<Response<JsonValue>>(response)
+        .map_err(|err| format!("Invalid data has been received: {}", err))?;
+    match response {
+        Response {
+            op: ResponseType::REPLY,
+            result: Some(result),
+            ..
+        } => Ok(result),
+        Response {
+            op,
+            reason: Some(reason),
+            ..
+        } => Err(format!("{:?}: {}", op, reason)),
+        Response { op, .. } => Err(format!("{:?}: no result", op)),
+    }
+}
+
+fn seq_no(result: &JsonValue) -> String {
+    match result["txnMetadata"]["seqNo"].as_i64() {
+        Some(seq_no) => format!(" (seqNo {})", seq_no),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::{
+        commands::{setup, tear_down},
+        did::tests::SEED_TRUSTEE,
+        pool::tests::{create_pool, delete_pool, POOL},
+    };
+
+    mod selftest {
+        use super::*;
+
+        #[test]
+        pub fn selftest_works() {
+            let ctx = setup();
+            create_pool(&ctx);
+            {
+                let cmd = selftest_command::new();
+                let mut params = CommandParams::new();
+                params.insert("pool", POOL.to_string());
+                params.insert("trustee_seed", SEED_TRUSTEE.to_string());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            assert!(ctx.get_connected_pool().is_none());
+            delete_pool(&ctx);
+            tear_down();
+        }
+
+        #[test]
+        pub fn selftest_works_for_unknown_pool() {
+            let ctx = setup();
+            {
+                let cmd = selftest_command::new();
+                let mut params = CommandParams::new();
+                params.insert("pool", "unknown_pool".to_string());
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            tear_down();
+        }
+
+        #[test]
+        pub fn reply_works() {
+            let result = reply(r#"{"op":"REPLY","result":{"seqNo":1}}"#).unwrap();
+            assert_eq!(result["seqNo"], 1);
+            let err = reply(r#"{"op":"REJECT","reason":"client request invalid"}"#).unwrap_err();
+            assert!(err.contains("client request invalid"));
+        }
+    }
+}
@@ -5,30 +5,238 @@
 use crate::{
     command_executor::{Command, CommandContext, CommandMetadata, CommandParams, CommandResult},
     params_parser::ParamParser,
-    utils::file::read_file,
+    utils::{file::read_file, pager, term::is_term},
 };
 
+use ansi_term::Color;
+use serde_json::Value as JsonValue;
+
 pub mod show_command {
     use super::*;
 
-    command!(
-        CommandMetadata::build("show", "Print the content of text file")
-            .add_main_param("file", "The path to file to show")
-            .add_example("show /home/file.txt")
-            .finalize()
+    const LAST_RESPONSE: &str = "last-response";
+
+    command!(CommandMetadata::build("show", "Print the content of text file. JSON content is pretty-printed.")
+                .add_main_param("file", "The path to file to show or `last-response` to show the latest response received from the Ledger")
+                .add_optional_param("lines", "Range of lines to show: <from>-<to>, <from>- or -<to> (numbering starts from 1)")
+                .add_example("show /home/file.txt")
+                .add_example("show /home/transaction.json lines=100-200")
+                .add_example("show last-response")
+                .finalize()
     );
 
-    fn execute(_ctx: &CommandContext, params: &CommandParams) -> CommandResult {
+    fn execute(ctx: &CommandContext, params: &CommandParams) -> CommandResult {
         trace!("execute >> params: {:?}", params);
 
-        let file = ParamParser::get_str_param("file", params)?;
+        let file = ParamParser::get_str_param(ctx, "file", params)?;
+        let lines = ParamParser::get_opt_str_param(ctx, "lines", params)?;
+
+        let lines = lines
+            .map(|range| parse_lines_range(ctx, range))
+            .transpose()?;
+
+        let content = if file == LAST_RESPONSE {
+            ctx.get_last_response().ok_or_else(|| {
+                println_err!(ctx, "There is no response from the Ledger received yet");
+            })?
+        } else {
+            read_file(file).map_err(|err| println_err!(ctx, "{}", err))?
+        };
+
+        let json = serde_json::from_str::<JsonValue>(&content).ok();
+        let content = match json {
+            Some(ref json) => serde_json::to_string_pretty(json).unwrap_or(content),
+            None => content,
+        };
+
+        let mut content_lines = content.lines().collect::<Vec<&str>>();
+
+        if let Some((from, to)) = lines {
+            let total = content_lines.len();
+            if from > total {
+                println_err!(
+                    ctx,
+                    "Invalid lines range: the content has only {} line(s)",
+                    total
+                );
+                return Err(());
+            }
+            let to = to.unwrap_or(total).min(total);
+            content_lines = content_lines[from - 1..to].to_vec();
+        }
 
-        let content = read_file(file).map_err(|err| println_err!("{}", err))?;
+        let output = content_lines
+            .into_iter()
+            .map(|line| {
+                if json.is_some() && is_term() {
+                    highlight_json_line(line)
+                } else {
+                    line.to_string()
+                }
+            })
+            .collect::<Vec<String>>()
+            .join("\n");
 
-        println!("{}", content);
+        pager::print(&output);
         let res = Ok(());
 
         trace!("execute << {:?}", res);
         res
     }
+
+    fn parse_lines_range(ctx: &CommandContext, range: &str) -> Result<(usize, Option<usize>), ()> {
+        let invalid_range = || {
+            println_err!(
+                ctx,
+                "Invalid lines range \"{}\". Expected format: <from>-<to>",
+                range
+            )
+        };
+
+        let (from, to) = match range.split_once('-') {
+            Some((from, to)) => (from.trim(), to.trim()),
+            None => (range.trim(), range.trim()),
+        };
+
+        let from = if from.is_empty() {
+            1
+        } else {
+            from.parse::<usize>().map_err(|_| invalid_range())?
+        };
+        let to = if to.is_empty() {
+            None
+        } else {
+            Some(to.parse::<usize>().map_err(|_| invalid_range())?)
+        };
+
+        if from == 0 || to.map(|to| to < from).unwrap_or(false) {
+            invalid_range();
+            return Err(());
+        }
+
+        Ok((from, to))
+    }
+
+    // Pretty-printed JSON never splits tokens across lines so every line can be colored on its own
+    fn highlight_json_line(line: &str) -> String {
+        let chars = line.chars().collect::<Vec<char>>();
+        let mut output = String::new();
+        let mut i = 0;
+
+        while i < chars.len() {
+            let c = chars[i];
+            if c == '"' {
+                let start = i;
+                i += 1;
+                while i < chars.len() && chars[i] != '"' {
+                    if chars[i] == '\\' {
+                        i += 1;
+                    }
+                    i += 1;
+                }
+                i = (i + 1).min(chars.len());
+                let token = chars[start..i].iter().collect::<String>();
+                let is_key = chars[i..].iter().find(|c| !c.is_whitespace()) == Some(&':');
+                let color = if is_key { Color::Blue } else { Color::Green };
+                output.push_str(&color.paint(token).to_string());
+            } else if c == '-' || c.is_ascii_digit() || c.is_ascii_alphabetic() {
+                let start = i;
+                while i < chars.len()
+                    && (chars[i].is_ascii_alphanumeric() || "+-.".contains(chars[i]))
+                {
+                    i += 1;
+                }
+                let token = chars[start..i].iter().collect::<String>();
+                let color = match token.as_str() {
+                    "true" | "false" | "null" => Color::Purple,
+                    _ => Color::Cyan,
+                };
+                output.push_str(&color.paint(token).to_string());
+            } else {
+                output.push(c);
+                i += 1;
+            }
+        }
+
+        output
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::{
+        commands::{setup, tear_down},
+        utils::{environment::EnvironmentUtils, file::write_file},
+    };
+
+    mod show {
+        use super::*;
+
+        fn write_test_file(name: &str, content: &str) -> String {
+            let path = EnvironmentUtils::tmp_file_path(name);
+            write_file(&path, content).unwrap();
+            path.to_str().unwrap().to_string()
+        }
+
+        #[test]
+        pub fn show_works_for_json_file_with_lines() {
+            let ctx = setup();
+            let file = write_test_file("show.json", r#"{"a":1,"b":{"c":true},"d":"text"}"#);
+            {
+                let cmd = show_command::new();
+                let mut params = CommandParams::new();
+                params.insert("file", file);
+                params.insert("lines", "2-4".to_string());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            tear_down();
+        }
+
+        #[test]
+        pub fn show_works_for_last_response() {
+            let ctx = setup();
+            ctx.set_last_response(Some(r#"{"op":"REPLY"}"#.to_string()));
+            {
+                let cmd = show_command::new();
+                let mut params = CommandParams::new();
+                params.insert("file", "last-response".to_string());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            tear_down();
+        }
+
+        #[test]
+        pub fn show_works_for_no_last_response() {
+            let ctx = setup();
+            {
+                let cmd = show_command::new();
+                let mut params = CommandParams::new();
+                params.insert("file", "last-response".to_string());
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            tear_down();
+        }
+
+        #[test]
+        pub fn show_works_for_invalid_lines() {
+            let ctx = setup();
+            let file = write_test_file("show.txt", "line 1\nline 2");
+            {
+                let cmd = show_command::new();
+                let mut params = CommandParams::new();
+                params.insert("file", file.clone());
+                params.insert("lines", "5-3".to_string());
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            {
+                let cmd = show_command::new();
+                let mut params = CommandParams::new();
+                params.insert("file", file);
+                params.insert("lines", "3-".to_string());
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            tear_down();
+        }
+    }
 }
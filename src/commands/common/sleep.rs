@@ -0,0 +1,33 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::{
+    command_executor::{Command, CommandContext, CommandMetadata, CommandParams, CommandResult},
+    params_parser::ParamParser,
+};
+
+use std::{thread, time::Duration};
+
+pub mod sleep_command {
+    use super::*;
+
+    command!(
+        CommandMetadata::build("sleep", "Pause execution for the given number of seconds")
+            .add_required_param("seconds", "Number of seconds to wait")
+            .add_example("sleep seconds=5")
+            .finalize()
+    );
+
+    fn execute(ctx: &CommandContext, params: &CommandParams) -> CommandResult {
+        trace!("execute >> ctx: {:?}, params: {:?}", ctx, params);
+
+        let seconds = ParamParser::get_number_param::<u64>("seconds", params)?;
+
+        thread::sleep(Duration::from_secs(seconds));
+        let res = Ok(());
+
+        trace!("execute << {:?}", res);
+        res
+    }
+}
@@ -0,0 +1,186 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::{
+    tools::did::{key::Key, Did},
+    utils::environment::EnvironmentUtils,
+};
+
+use aries_askar::crypto::random::fill_random;
+use std::{
+    env, fs,
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+// Sandbox networks (local indy-node pool, von-network) are started with this TRUSTEE
+const SANDBOX_TRUSTEE_SEED: &str = "000000000000000000000000Trustee1";
+
+pub struct TutorialStep {
+    pub title: &'static str,
+    pub explanation: &'static str,
+    pub command: String,
+}
+
+/// Onboarding walk-through executing real commands against a sandbox network.
+/// The wallet and pool created by the tutorial get unique names and are removed at the end,
+/// the genesis file is copied into a temporary directory.
+pub struct Tutorial {
+    pub name: String,
+    pub dir: PathBuf,
+    key: String,
+    seed: String,
+}
+
+impl Tutorial {
+    pub fn new(gen_txn_file: &str) -> Result<Tutorial, String> {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let name = format!("tutorial-{}", timestamp);
+
+        let mut dir = env::temp_dir();
+        dir.push(format!("indy-cli-{}", name));
+        fs::create_dir_all(&dir)
+            .map_err(|err| format!("Can't create tutorial directory: {}", err))?;
+        fs::copy(gen_txn_file, dir.join("genesis.txn")).map_err(|err| {
+            fs::remove_dir_all(&dir).ok();
+            format!("Can't read genesis file \"{}\": {}", gen_txn_file, err)
+        })?;
+
+        Ok(Tutorial {
+            name,
+            dir,
+            key: random_hex(8),
+            seed: random_hex(16),
+        })
+    }
+
+    pub fn steps(&self) -> Result<Vec<TutorialStep>, String> {
+        let (trustee_did, _) = did_from_seed(SANDBOX_TRUSTEE_SEED)?;
+        let (did, verkey) = did_from_seed(&self.seed)?;
+        let genesis = self.dir.join("genesis.txn");
+
+        Ok(vec![
+            TutorialStep {
+                title: "Create a wallet",
+                explanation: "Wallet is an encrypted storage for your keys and DIDs. \
+                    The key protects it: usually you omit its value and enter it on prompt, so that it is not shown on the screen.",
+                command: format!("wallet create {} key={}", self.name, self.key),
+            },
+            TutorialStep {
+                title: "Open the wallet",
+                explanation: "Most of the commands work with the opened wallet. \
+                    The wallet name is shown in the prompt while it is open.",
+                command: format!("wallet open {} key={}", self.name, self.key),
+            },
+            TutorialStep {
+                title: "Add the sandbox pool",
+                explanation: "Pool configuration points CLI to a ledger network. \
+                    The genesis transactions file lists the network nodes and their addresses.",
+                command: format!("pool create {} gen_txn_file={}", self.name, genesis.display()),
+            },
+            TutorialStep {
+                title: "Connect to the pool",
+                explanation: "CLI connects to the nodes and catches up with the latest pool state. \
+                    The connected pool is shown in the prompt.",
+                command: format!("pool connect {}", self.name),
+            },
+            TutorialStep {
+                title: "Import the sandbox TRUSTEE",
+                explanation: "Only identities having a role on the ledger may publish new DIDs. \
+                    Sandbox networks come with a TRUSTEE whose seed is well known. Never use such seeds on real networks!",
+                command: format!("did new seed={}", SANDBOX_TRUSTEE_SEED),
+            },
+            TutorialStep {
+                title: "Create your DID",
+                explanation: "DID is derived from a new key pair. The seed makes the key reproducible, \
+                    without it a random key is generated.",
+                command: format!("did new seed={}", self.seed),
+            },
+            TutorialStep {
+                title: "Act as the TRUSTEE",
+                explanation: "Write transactions are signed by the active DID.",
+                command: format!("did use {}", trustee_did),
+            },
+            TutorialStep {
+                title: "Publish your DID",
+                explanation: "NYM transaction writes your DID and verkey to the ledger, \
+                    so that anyone can resolve them and verify your signatures.",
+                command: format!("ledger nym did={} verkey={}", did, verkey),
+            },
+            TutorialStep {
+                title: "Read your DID from the ledger",
+                explanation: "GET_NYM request reads the DID back. Read requests need no signature and no role.",
+                command: format!("ledger get-nym did={}", did),
+            },
+        ])
+    }
+
+    /// Commands removing the wallet and pool created by the tutorial.
+    /// The tutorial may be stopped at any step, so only the existing ones are removed.
+    pub fn cleanup_commands(&self, is_pool_connected: bool, is_wallet_opened: bool) -> Vec<String> {
+        let mut commands = Vec::new();
+        if is_pool_connected {
+            commands.push("pool disconnect".to_string());
+        }
+        if EnvironmentUtils::pool_path(&self.name).exists() {
+            commands.push(format!("pool delete {}", self.name));
+        }
+        if is_wallet_opened {
+            commands.push("wallet close".to_string());
+        }
+        if EnvironmentUtils::wallet_config_path(&self.name).exists() {
+            commands.push(format!("wallet delete {} key={}", self.name, self.key));
+        }
+        commands
+    }
+
+    pub fn remove_dir(&self) {
+        fs::remove_dir_all(&self.dir).ok();
+    }
+}
+
+fn did_from_seed(seed: &str) -> Result<(String, String), String> {
+    let verkey = Key::verkey_from_seed(seed).map_err(|err| err.to_string())?;
+    let did = Did::from_verkey(&verkey).map_err(|err| err.to_string())?;
+    Ok((did, verkey))
+}
+
+fn random_hex(len: usize) -> String {
+    let mut bytes = vec![0u8; len];
+    fill_random(&mut bytes);
+    hex::encode(bytes)
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::did::tests::DID_TRUSTEE;
+
+    #[test]
+    pub fn tutorial_works() {
+        let tutorial = Tutorial::new("docker_pool_transactions_genesis").unwrap();
+        assert!(tutorial.dir.join("genesis.txn").exists());
+
+        let steps = tutorial.steps().unwrap();
+        assert!(steps
+            .iter()
+            .any(|step| step.command == format!("did use {}", DID_TRUSTEE)));
+        assert!(steps
+            .iter()
+            .all(|step| !step.command.contains("wallet") || step.command.contains(&tutorial.name)));
+
+        assert!(tutorial.cleanup_commands(false, false).is_empty());
+
+        tutorial.remove_dir();
+        assert!(!tutorial.dir.exists());
+    }
+
+    #[test]
+    pub fn tutorial_works_for_missing_genesis() {
+        assert!(Tutorial::new("unknown_pool_transactions_genesis").is_err());
+    }
+}
@@ -0,0 +1,171 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::{
+    command_executor::{Command, CommandContext, CommandMetadata, CommandParams},
+    commands::ledger::get_active_transaction_author_agreement,
+    tools::{
+        did::Did,
+        ledger::{permissions::get_role, LedgerHelpers},
+        pool::Pool,
+    },
+    utils::{table::print_table, time::timestamp_to_datetime},
+};
+
+use indy_utils::did::DidValue;
+use serde_json::Value as JsonValue;
+
+pub mod whoami_command {
+    use super::*;
+
+    command!(CommandMetadata::build(
+        "whoami",
+        "Show the active DID with its verkey and ledger role, the opened wallet, the connected pool \
+                 and the transaction author agreement acceptance used for write requests."
+    )
+    .finalize());
+
+    fn execute(ctx: &CommandContext, params: &CommandParams) -> Result<(), ()> {
+        trace!("execute >> ctx {:?} params {:?}", ctx, params);
+
+        let wallet = ctx.get_opened_wallet();
+        let pool = ctx.get_connected_pool();
+        let did = ctx.get_active_did()?;
+
+        let mut info = json!({
+            "did": did.as_ref().map(|did| did.to_string()).unwrap_or_else(|| "-".to_string()),
+            "wallet": wallet.as_ref().map(|wallet| wallet.name.to_string()).unwrap_or_else(|| "-".to_string()),
+            "pool": pool.as_ref().map(|pool| pool.name.to_string()).unwrap_or_else(|| "-".to_string()),
+            "verkey": "-",
+            "full_verkey": "-",
+            "role": "-",
+        });
+
+        if let (Some(did), Some(wallet)) = (did.as_ref(), wallet.as_ref()) {
+            match Did::get(wallet, did) {
+                Ok(did_info) => {
+                    info["verkey"] = json!(Did::abbreviate_verkey(&did_info.did, &did_info.verkey)
+                        .unwrap_or_else(|_| did_info.verkey.to_string()));
+                    info["full_verkey"] = json!(Did::full_verkey(&did_info.did, &did_info.verkey)
+                        .unwrap_or_else(|_| did_info.verkey.to_string()));
+                }
+                Err(err) => println_warn!(ctx, "{}", err.message(None)),
+            }
+        }
+
+        if let (Some(did), Some(pool)) = (did.as_ref(), pool.as_ref()) {
+            info["role"] = json!(describe_role(pool, did));
+        }
+
+        info["taa"] = json!(describe_taa_acceptance(ctx, pool.as_deref())?);
+
+        print_table(
+            ctx,
+            &info,
+            &[
+                ("did", "Did"),
+                ("verkey", "Verkey"),
+                ("full_verkey", "Full Verkey"),
+                ("role", "Ledger Role"),
+                ("wallet", "Wallet"),
+                ("pool", "Pool"),
+                ("taa", "Transaction Author Agreement"),
+            ],
+        );
+
+        trace!("execute <<");
+        Ok(())
+    }
+
+    fn describe_role(pool: &Pool, did: &DidValue) -> String {
+        match get_role(pool, did) {
+            Ok(None) => "Not written on the ledger".to_string(),
+            Ok(Some(role)) if role.is_empty() => "No role".to_string(),
+            Ok(Some(role)) => {
+                LedgerHelpers::get_role_title(&JsonValue::String(role), &pool.custom_roles)
+                    .as_str()
+                    .unwrap_or_default()
+                    .to_string()
+            }
+            Err(err) => format!("Unknown: {}", err.message(Some(&pool.name))),
+        }
+    }
+
+    // The acceptance which is appended to the write requests compared with the agreement active on the ledger
+    fn describe_taa_acceptance(ctx: &CommandContext, pool: Option<&Pool>) -> Result<String, ()> {
+        let active_version = match pool {
+            Some(pool) => Some(
+                get_active_transaction_author_agreement(ctx, pool)?.map(|(_, version, _)| version),
+            ),
+            None => None,
+        };
+
+        let description = match (ctx.get_transaction_author_info(), active_version) {
+            (Some((_, version, mechanism, time)), active_version) => {
+                let accepted = format!(
+                    "Version {} accepted with \"{}\" on {}",
+                    version,
+                    mechanism,
+                    timestamp_to_datetime(ctx, time as i64)
+                );
+                match active_version {
+                    Some(Some(active)) if active != version => {
+                        format!(
+                            "{}. Outdated: version {} is active on the ledger",
+                            accepted, active
+                        )
+                    }
+                    Some(None) => format!("{}. Not required by the ledger", accepted),
+                    _ => accepted,
+                }
+            }
+            (None, Some(Some(active))) if ctx.is_taa_auto_accept() => format!(
+                "Not accepted. Version {} will be accepted automatically on the next connection",
+                active
+            ),
+            (None, Some(Some(active))) => format!(
+                "Not accepted. Version {} is required by the ledger for write requests",
+                active
+            ),
+            (None, Some(None)) => "Not required by the ledger".to_string(),
+            (None, None) => "Not accepted".to_string(),
+        };
+        Ok(description)
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::commands::{setup, setup_with_wallet, tear_down, tear_down_with_wallet};
+
+    mod whoami {
+        use super::*;
+        use crate::did::tests::{new_did, use_did, DID_TRUSTEE, SEED_TRUSTEE};
+
+        #[test]
+        pub fn whoami_works() {
+            let ctx = setup_with_wallet();
+            new_did(&ctx, SEED_TRUSTEE);
+            use_did(&ctx, DID_TRUSTEE);
+            {
+                let cmd = whoami_command::new();
+                let params = CommandParams::new();
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            tear_down_with_wallet(&ctx);
+        }
+
+        #[test]
+        pub fn whoami_works_for_empty_context() {
+            let ctx = setup();
+            {
+                let cmd = whoami_command::new();
+                let params = CommandParams::new();
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            tear_down();
+        }
+    }
+}
@@ -0,0 +1,181 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::{
+    command_executor::{
+        Command, CommandContext, CommandMetadata, CommandParams, DynamicCompletionType,
+    },
+    params_parser::ParamParser,
+    tools::did::{paper::PaperBackup, Did},
+    utils::file::write_private_file,
+};
+
+const FORMAT_PDF: &str = "pdf";
+const FORMAT_PNG: &str = "png";
+
+pub mod export_paper_command {
+    use super::*;
+
+    command!(
+        CommandMetadata::build(
+            "export-paper",
+            "Write the seed of the DID key into a printable page (QR code, word list and checksum) for cold storage. \
+            The file gives full control over the DID: print it and delete the file."
+        )
+        .add_main_param_with_dynamic_completion(
+            "did",
+            "Did stored in wallet",
+            DynamicCompletionType::Did
+        )
+        .add_required_param("file", "Path to the file to create")
+        .add_optional_param("format", "Format of the file. One of:
+                            pdf - A4 PDF document (used by default)
+                            png - A4 image at 216 dpi")
+        .add_example("did export-paper VsKV7grR1BUE29mG2Fm2kX file=/media/usb/trustee-key.pdf")
+        .add_example("did export-paper VsKV7grR1BUE29mG2Fm2kX file=/media/usb/trustee-key.png format=png")
+        .finalize()
+    );
+
+    fn execute(ctx: &CommandContext, params: &CommandParams) -> Result<(), ()> {
+        trace!("execute >> ctx {:?} params {:?}", ctx, params);
+
+        let store = ctx.ensure_opened_wallet()?;
+
+        let did = ParamParser::get_did_param(ctx, "did", params)?;
+        let file = ParamParser::get_str_param(ctx, "file", params)?;
+        let format = ParamParser::get_opt_str_param(ctx, "format", params)?.unwrap_or(FORMAT_PDF);
+
+        if ![FORMAT_PDF, FORMAT_PNG].contains(&format) {
+            println_err!(
+                ctx,
+                "Unsupported paper backup format \"{}\". One of: {}, {}",
+                format,
+                FORMAT_PDF,
+                FORMAT_PNG
+            );
+            return Err(());
+        }
+
+        let (did_info, seed) =
+            Did::export_seed(&store, &did).map_err(|err| err.print(ctx, None))?;
+        let backup = PaperBackup::new(&did_info.did, &did_info.verkey, seed.as_ref());
+        let content = match format {
+            FORMAT_PNG => backup.to_png(),
+            _ => backup.to_pdf(),
+        }
+        .map_err(|err| println_err!(ctx, "{}", err))?;
+
+        write_private_file(file, &content)
+            .map_err(|err| println_err!(ctx, "Unable to write \"{}\": {}", file, err))?;
+
+        println_succ!(
+            ctx,
+            "Paper backup of DID \"{}\" key has been written to \"{}\"",
+            did_info.did,
+            file
+        );
+        println_warn!(
+            ctx,
+            "The file contains the DID secret key. Delete it once the page is printed."
+        );
+
+        trace!("execute <<");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::{
+        commands::{setup_with_wallet, tear_down_with_wallet},
+        did::tests::{new_did, DID_MY1, DID_TRUSTEE, SEED_TRUSTEE},
+        utils::environment::EnvironmentUtils,
+    };
+    use std::fs;
+
+    mod did_export_paper {
+        use super::*;
+
+        #[test]
+        pub fn export_paper_works() {
+            let ctx = setup_with_wallet();
+            new_did(&ctx, SEED_TRUSTEE);
+            let path = EnvironmentUtils::tmp_file_path("trustee-key.pdf");
+            let _ = fs::remove_file(&path);
+            {
+                let cmd = export_paper_command::new();
+                let mut params = CommandParams::new();
+                params.insert("did", DID_TRUSTEE.to_string());
+                params.insert("file", path.display().to_string());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            let content = fs::read(&path).unwrap();
+            let content = String::from_utf8_lossy(&content);
+            assert!(content.starts_with("%PDF"));
+            assert!(content.contains(&hex::encode(SEED_TRUSTEE)));
+            {
+                // Existing file is not overwritten
+                let cmd = export_paper_command::new();
+                let mut params = CommandParams::new();
+                params.insert("did", DID_TRUSTEE.to_string());
+                params.insert("file", path.display().to_string());
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            fs::remove_file(&path).unwrap();
+            tear_down_with_wallet(&ctx);
+        }
+
+        #[test]
+        pub fn export_paper_works_for_png_format() {
+            let ctx = setup_with_wallet();
+            new_did(&ctx, SEED_TRUSTEE);
+            let path = EnvironmentUtils::tmp_file_path("trustee-key.png");
+            let _ = fs::remove_file(&path);
+            {
+                let cmd = export_paper_command::new();
+                let mut params = CommandParams::new();
+                params.insert("did", DID_TRUSTEE.to_string());
+                params.insert("file", path.display().to_string());
+                params.insert("format", "png".to_string());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            assert!(fs::read(&path).unwrap().starts_with(b"\x89PNG"));
+            fs::remove_file(&path).unwrap();
+            tear_down_with_wallet(&ctx);
+        }
+
+        #[test]
+        pub fn export_paper_works_for_unsupported_format() {
+            let ctx = setup_with_wallet();
+            new_did(&ctx, SEED_TRUSTEE);
+            let path = EnvironmentUtils::tmp_file_path("trustee-key.svg");
+            {
+                let cmd = export_paper_command::new();
+                let mut params = CommandParams::new();
+                params.insert("did", DID_TRUSTEE.to_string());
+                params.insert("file", path.display().to_string());
+                params.insert("format", "svg".to_string());
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            assert!(!path.exists());
+            tear_down_with_wallet(&ctx);
+        }
+
+        #[test]
+        pub fn export_paper_works_for_unknown_did() {
+            let ctx = setup_with_wallet();
+            let path = EnvironmentUtils::tmp_file_path("unknown-key.pdf");
+            {
+                let cmd = export_paper_command::new();
+                let mut params = CommandParams::new();
+                params.insert("did", DID_MY1.to_string());
+                params.insert("file", path.display().to_string());
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            assert!(!path.exists());
+            tear_down_with_wallet(&ctx);
+        }
+    }
+}
@@ -10,7 +10,17 @@ use crate::{
 
 pub mod import_command {
     use super::*;
-    use crate::utils::file::read_file;
+    use crate::{
+        tools::{
+            did::{constants::KEY_TYPE, key::Key},
+            wallet::Wallet,
+        },
+        utils::{file::read_file, table::print_list_table},
+    };
+    use indy_utils::{did::DidValue, Qualifiable};
+    use std::collections::HashSet;
+
+    const SUPPORTED_VERSIONS: [usize; 2] = [1, 2];
 
     #[derive(Debug, Deserialize)]
     struct DidImportConfig {
@@ -19,9 +29,27 @@ pub mod import_command {
     }
 
     #[derive(Debug, Deserialize)]
+    #[serde(rename_all = "camelCase")]
     struct DidImportInfo {
         did: Option<String>,
         seed: String,
+        // fields below are supported starting from version 2
+        verkey: Option<String>,
+        verkey_type: Option<String>,
+        method: Option<String>,
+        metadata: Option<String>,
+    }
+
+    #[derive(Debug, Serialize)]
+    struct DidImportEntry {
+        did: String,
+        verkey: String,
+        method: Option<String>,
+        metadata: Option<String>,
+        #[serde(skip)]
+        unqualified_did: Option<String>,
+        #[serde(skip)]
+        seed: String,
     }
 
     command!(CommandMetadata::build(
@@ -29,14 +57,25 @@ pub mod import_command {
         "Import DIDs entities from file to the current wallet.
         File format:
         {
-            \"version\": 1,
+            \"version\": 2,
             \"dids\": [{
-                \"did\": \"did\",
-                \"seed\": \"UTF-8, base64 or hex string\"
+                \"did\": \"DID (optional, can be fully qualified)\",
+                \"seed\": \"UTF-8, base64 or hex string\",
+                \"verkey\": \"expected verkey to validate the seed against (optional)\",
+                \"verkeyType\": \"ed25519 (optional)\",
+                \"method\": \"DID method name to qualify the DID (optional)\",
+                \"metadata\": \"DID metadata (optional)\"
             }]
-        }"
+        }
+        Version 1 supports only `did` and `seed` fields."
     )
     .add_main_param("file", "Path to file with DIDs")
+    .add_optional_param(
+        "dry_run",
+        "Validate the file and show DIDs which would be created without changing the wallet (False by default)"
+    )
+    .add_example("did import /home/did_import.json")
+    .add_example("did import /home/did_import.json dry_run=true")
     .finalize());
 
     fn execute(ctx: &CommandContext, params: &CommandParams) -> Result<(), ()> {
@@ -44,37 +83,324 @@ pub mod import_command {
 
         let store = ctx.ensure_opened_wallet()?;
 
-        let path = ParamParser::get_str_param("file", params)?;
+        let path = ParamParser::get_str_param(ctx, "file", params)?;
+        let dry_run = ParamParser::get_opt_bool_param(ctx, "dry_run", params)?.unwrap_or(false);
 
-        let data = read_file(path)
-            .map_err(|_| println_err!("Unable to read DID import config from the provided file"))?;
+        let data = read_file(path).map_err(|_| {
+            println_err!(
+                ctx,
+                "Unable to read DID import config from the provided file"
+            )
+        })?;
 
-        let config: DidImportConfig = serde_json::from_str(&data)
-            .map_err(|_| println_err!("Unable to read DID import config from the provided file"))?;
+        let config: DidImportConfig = serde_json::from_str(&data).map_err(|_| {
+            println_err!(
+                ctx,
+                "Unable to read DID import config from the provided file"
+            )
+        })?;
 
-        if config.version != 1 {
-            println_err!("Unsupported DID import config version");
+        if !SUPPORTED_VERSIONS.contains(&config.version) {
+            println_err!(ctx, "Unsupported DID import config version");
             return Err(());
         }
 
-        for did in config.dids {
+        let entries = validate_entries(ctx, &store, config)?;
+
+        if dry_run {
+            let rows = entries
+                .iter()
+                .map(|entry| json!(entry))
+                .collect::<Vec<serde_json::Value>>();
+            print_list_table(
+                ctx,
+                &rows,
+                &[
+                    ("did", "Did"),
+                    ("verkey", "Verkey"),
+                    ("method", "Method"),
+                    ("metadata", "Metadata"),
+                ],
+                "There are no DIDs to import",
+            );
+            println_succ!(ctx, "Dry run: {} DIDs would be created", entries.len());
+            return Ok(());
+        }
+
+        for entry in entries {
             let (did, vk) = Did::create(
                 &store,
-                did.did.as_ref().map(String::as_str),
-                Some(&did.seed),
-                None,
-                None,
+                entry.unqualified_did.as_deref(),
+                Some(&entry.seed),
+                entry.metadata.as_deref(),
+                entry.method.as_deref(),
             )
-            .map_err(|err| println_err!("{}", err.message(None)))?;
+            .map_err(|err| err.print(ctx, None))?;
 
             let vk = Did::abbreviate_verkey(&did, &vk).unwrap_or(vk);
 
-            println_succ!("Did \"{}\" has been created with \"{}\" verkey", did, vk)
+            println_succ!(
+                ctx,
+                "Did \"{}\" has been created with \"{}\" verkey",
+                did,
+                vk
+            )
         }
 
-        println_succ!("DIDs import finished");
+        println_succ!(ctx, "DIDs import finished");
 
         trace!("execute << ");
         Ok(())
     }
+
+    fn validate_entries(
+        ctx: &CommandContext,
+        store: &Wallet,
+        config: DidImportConfig,
+    ) -> Result<Vec<DidImportEntry>, ()> {
+        let mut entries = Vec::new();
+        let mut errors = Vec::new();
+        let mut seen = HashSet::new();
+
+        for (index, info) in config.dids.into_iter().enumerate() {
+            match validate_entry(store, config.version, info, &mut seen) {
+                Ok(entry) => entries.push(entry),
+                Err(err) => errors.push(format!("Entry #{}: {}", index + 1, err)),
+            }
+        }
+
+        if !errors.is_empty() {
+            println_err!(ctx, "DID import config contains invalid entries:");
+            for error in errors {
+                println_err!(ctx, "\t{}", error);
+            }
+            return Err(());
+        }
+
+        Ok(entries)
+    }
+
+    fn validate_entry(
+        store: &Wallet,
+        version: usize,
+        info: DidImportInfo,
+        seen: &mut HashSet<String>,
+    ) -> Result<DidImportEntry, String> {
+        if version == 1
+            && (info.verkey.is_some()
+                || info.verkey_type.is_some()
+                || info.method.is_some()
+                || info.metadata.is_some())
+        {
+            return Err(
+                "Fields `verkey`, `verkeyType`, `method` and `metadata` require version 2"
+                    .to_string(),
+            );
+        }
+
+        if let Some(ref verkey_type) = info.verkey_type {
+            if verkey_type != KEY_TYPE {
+                return Err(format!(
+                    "Unsupported verkey type \"{}\". Only \"{}\" is supported",
+                    verkey_type, KEY_TYPE
+                ));
+            }
+        }
+
+        let verkey = Key::verkey_from_seed(&info.seed).map_err(|err| err.message(None))?;
+
+        let (unqualified_did, method) = match info.did {
+            Some(ref did) => {
+                let did = DidValue::from_str(did)
+                    .map_err(|_| format!("Invalid DID \"{}\" provided", did))?;
+                let method = match (did.get_method(), info.method) {
+                    (Some(did_method), Some(method)) if did_method != method => {
+                        return Err(format!(
+                            "DID method \"{}\" does not match the specified method \"{}\"",
+                            did_method, method
+                        ))
+                    }
+                    (did_method, method) => method.or(did_method),
+                };
+                (Some(did.to_unqualified().to_string()), method)
+            }
+            None => (None, info.method),
+        };
+
+        let did = match unqualified_did {
+            Some(ref did) => did.to_string(),
+            None => Did::from_verkey(&verkey).map_err(|err| err.message(None))?,
+        };
+
+        if let Some(ref expected_verkey) = info.verkey {
+            let abbreviated_verkey = Did::abbreviate_verkey(&did, &verkey).ok();
+            if expected_verkey != &verkey && Some(expected_verkey) != abbreviated_verkey.as_ref() {
+                return Err(format!(
+                    "Verkey \"{}\" does not match the provided seed",
+                    expected_verkey
+                ));
+            }
+        }
+
+        let did = match method {
+            Some(ref method) => DidValue(did)
+                .to_qualified(method)
+                .map_err(|_| format!("Invalid DID method \"{}\" provided", method))?
+                .to_string(),
+            None => did,
+        };
+
+        if !seen.insert(did.clone()) {
+            return Err(format!("DID \"{}\" is duplicated in the file", did));
+        }
+
+        if Did::get(store, &DidValue(did.clone())).is_ok() {
+            return Err(format!("DID \"{}\" already exists in the wallet", did));
+        }
+
+        Ok(DidImportEntry {
+            did,
+            verkey,
+            method,
+            metadata: info.metadata,
+            unqualified_did,
+            seed: info.seed,
+        })
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::{
+        commands::{setup_with_wallet, tear_down_with_wallet},
+        did::tests::{get_did_info, DID_MY1, SEED_MY1, VERKEY_MY1, VERKEY_MY3},
+        utils::{environment::EnvironmentUtils, file::write_file},
+    };
+
+    mod did_import {
+        use super::*;
+
+        fn write_config(name: &str, content: &str) -> String {
+            let path = EnvironmentUtils::tmp_file_path(name);
+            write_file(&path, content).unwrap();
+            path.to_str().unwrap().to_string()
+        }
+
+        #[test]
+        pub fn import_works_for_version_1() {
+            let ctx = setup_with_wallet();
+            let path = write_config(
+                "did_import_v1.json",
+                &json!({"version": 1, "dids": [{"seed": SEED_MY1}]}).to_string(),
+            );
+            {
+                let cmd = import_command::new();
+                let mut params = CommandParams::new();
+                params.insert("file", path);
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            assert_eq!(get_did_info(&ctx, DID_MY1).verkey, VERKEY_MY1);
+            tear_down_with_wallet(&ctx);
+        }
+
+        #[test]
+        pub fn import_works_for_version_2() {
+            let ctx = setup_with_wallet();
+            let path = write_config(
+                "did_import_v2.json",
+                &json!({"version": 2, "dids": [{
+                    "seed": SEED_MY1,
+                    "verkey": VERKEY_MY1,
+                    "verkeyType": "ed25519",
+                    "method": "sov",
+                    "metadata": "imported"
+                }]})
+                .to_string(),
+            );
+            {
+                let cmd = import_command::new();
+                let mut params = CommandParams::new();
+                params.insert("file", path);
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            let did_info = get_did_info(&ctx, &format!("did:sov:{}", DID_MY1));
+            assert_eq!(did_info.metadata, Some("imported".to_string()));
+            tear_down_with_wallet(&ctx);
+        }
+
+        #[test]
+        pub fn import_works_for_dry_run() {
+            let ctx = setup_with_wallet();
+            let path = write_config(
+                "did_import_dry_run.json",
+                &json!({"version": 2, "dids": [{"seed": SEED_MY1}]}).to_string(),
+            );
+            {
+                let cmd = import_command::new();
+                let mut params = CommandParams::new();
+                params.insert("file", path);
+                params.insert("dry_run", "true".to_string());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            let wallet = ctx.ensure_opened_wallet().unwrap();
+            assert!(Did::list(&wallet).unwrap().is_empty());
+            tear_down_with_wallet(&ctx);
+        }
+
+        #[test]
+        pub fn import_works_for_verkey_mismatch() {
+            let ctx = setup_with_wallet();
+            let path = write_config(
+                "did_import_verkey_mismatch.json",
+                &json!({"version": 2, "dids": [{
+                    "seed": SEED_MY1,
+                    "verkey": VERKEY_MY3
+                }]})
+                .to_string(),
+            );
+            {
+                let cmd = import_command::new();
+                let mut params = CommandParams::new();
+                params.insert("file", path);
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            let wallet = ctx.ensure_opened_wallet().unwrap();
+            assert!(Did::list(&wallet).unwrap().is_empty());
+            tear_down_with_wallet(&ctx);
+        }
+
+        #[test]
+        pub fn import_works_for_version_2_fields_in_version_1() {
+            let ctx = setup_with_wallet();
+            let path = write_config(
+                "did_import_v1_with_method.json",
+                &json!({"version": 1, "dids": [{"seed": SEED_MY1, "method": "sov"}]}).to_string(),
+            );
+            {
+                let cmd = import_command::new();
+                let mut params = CommandParams::new();
+                params.insert("file", path);
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            tear_down_with_wallet(&ctx);
+        }
+
+        #[test]
+        pub fn import_works_for_duplicated_did() {
+            let ctx = setup_with_wallet();
+            let path = write_config(
+                "did_import_duplicate.json",
+                &json!({"version": 2, "dids": [{"seed": SEED_MY1}, {"seed": SEED_MY1}]})
+                    .to_string(),
+            );
+            {
+                let cmd = import_command::new();
+                let mut params = CommandParams::new();
+                params.insert("file", path);
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            tear_down_with_wallet(&ctx);
+        }
+    }
 }
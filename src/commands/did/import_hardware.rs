@@ -0,0 +1,93 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::{
+    command_executor::{Command, CommandContext, CommandMetadata, CommandParams},
+    params_parser::ParamParser,
+    tools::did::{hardware::HardwareKey, Did},
+};
+
+pub mod import_hardware_command {
+    use super::*;
+
+    command!(CommandMetadata::build(
+        "import-hardware",
+        "Add DID controlled by the key held by a connected hardware wallet (experimental). \
+        Only the public key is stored in the wallet, requests of the DID are signed on the device."
+    )
+    .add_required_param(
+        "path",
+        "BIP32 derivation path of the key on the device, e.g. 44'/1'/0'"
+    )
+    .add_optional_param("device", "Hardware wallet type: ledger (default)")
+    .add_optional_param("did", "Known DID of the key")
+    .add_optional_param("method", "Method name to create fully qualified DID")
+    .add_optional_param("metadata", "DID metadata")
+    .add_example("did import-hardware path=44'/1'/0'")
+    .add_example("did import-hardware path=44'/1'/0' did=VsKV7grR1BUE29mG2Fm2kX metadata=trustee")
+    .finalize());
+
+    fn execute(ctx: &CommandContext, params: &CommandParams) -> Result<(), ()> {
+        trace!("execute >> ctx {:?} params {:?}", ctx, params);
+
+        let store = ctx.ensure_opened_wallet()?;
+
+        let path = ParamParser::get_str_param(ctx, "path", params)?;
+        let device = ParamParser::get_opt_str_param(ctx, "device", params)?.unwrap_or("ledger");
+        let did = ParamParser::get_opt_str_param(ctx, "did", params)?;
+        let method = ParamParser::get_opt_str_param(ctx, "method", params)?;
+        let metadata = ParamParser::get_opt_empty_str_param("metadata", params)?;
+
+        let hardware_key = HardwareKey::new(device, path).map_err(|err| err.print(ctx, None))?;
+
+        let (did, verkey) = Did::create_hardware(&store, did, &hardware_key, metadata, method)
+            .map_err(|err| err.print(ctx, None))?;
+
+        println_succ!(
+            ctx,
+            "Did \"{}\" has been created with \"{}\" verkey held by hardware wallet \"{}\"",
+            did,
+            verkey,
+            hardware_key.locator()
+        );
+
+        trace!("execute <<");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::commands::{setup_with_wallet, tear_down_with_wallet};
+
+    mod did_import_hardware {
+        use super::*;
+
+        #[test]
+        pub fn import_hardware_works_for_unsupported_device() {
+            let ctx = setup_with_wallet();
+            {
+                let cmd = import_hardware_command::new();
+                let mut params = CommandParams::new();
+                params.insert("path", "44'/1'/0'".to_string());
+                params.insert("device", "trezor".to_string());
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            tear_down_with_wallet(&ctx);
+        }
+
+        #[test]
+        pub fn import_hardware_works_for_invalid_path() {
+            let ctx = setup_with_wallet();
+            {
+                let cmd = import_hardware_command::new();
+                let mut params = CommandParams::new();
+                params.insert("path", "44/1/0".to_string());
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            tear_down_with_wallet(&ctx);
+        }
+    }
+}
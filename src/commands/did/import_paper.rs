@@ -0,0 +1,116 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::{
+    command_executor::{Command, CommandContext, CommandMetadata, CommandParams},
+    params_parser::ParamParser,
+    tools::did::{paper::PaperBackup, Did},
+};
+
+use zeroize::Zeroizing;
+
+pub mod import_paper_command {
+    use super::*;
+
+    command!(CommandMetadata::build(
+        "import-paper",
+        "Restore the DID key from the seed words of a paper backup written by `did export-paper`"
+    )
+    .add_main_param("did", "Did written on the paper backup")
+    .add_required_deferred_param(
+        "seed",
+        "Seed words in the order written on the paper backup"
+    )
+    .add_optional_param(
+        "checksum",
+        "Checksum words written on the paper backup to check the seed words against"
+    )
+    .add_optional_param("metadata", "DID metadata")
+    .add_example(
+        "did import-paper V4SGRU86Z58d6TV7PBUe6f seed checksum=\"bench blend fever avoid\""
+    )
+    .finalize());
+
+    fn execute(ctx: &CommandContext, params: &CommandParams) -> Result<(), ()> {
+        trace!("execute >> ctx {:?} params {:?}", ctx, secret!(params));
+
+        let store = ctx.ensure_opened_wallet()?;
+
+        let did = ParamParser::get_str_param(ctx, "did", params)?;
+        let words = ParamParser::get_str_param(ctx, "seed", params)?;
+        let checksum = ParamParser::get_opt_str_param(ctx, "checksum", params)?;
+        let metadata = ParamParser::get_opt_empty_str_param("metadata", params)?;
+
+        let seed =
+            PaperBackup::seed_from_words(words, checksum).map_err(|err| err.print(ctx, None))?;
+        let seed = Zeroizing::new(hex::encode(seed.as_slice()));
+
+        let (did, vk) = Did::create(&store, Some(did), Some(&seed), metadata, None)
+            .map_err(|err| err.print(ctx, None))?;
+
+        let vk = Did::abbreviate_verkey(&did, &vk).unwrap_or(vk);
+
+        println_succ!(
+            ctx,
+            "Did \"{}\" has been restored with \"{}\" verkey",
+            did,
+            vk
+        );
+
+        trace!("execute <<");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::{
+        commands::{setup_with_wallet, tear_down_with_wallet},
+        did::tests::{get_did_info, DID_TRUSTEE, SEED_TRUSTEE, VERKEY_TRUSTEE},
+    };
+
+    mod did_import_paper {
+        use super::*;
+
+        fn backup() -> PaperBackup {
+            PaperBackup::new(DID_TRUSTEE, VERKEY_TRUSTEE, SEED_TRUSTEE.as_bytes())
+        }
+
+        #[test]
+        pub fn import_paper_works() {
+            let ctx = setup_with_wallet();
+            let backup = backup();
+            {
+                let cmd = import_paper_command::new();
+                let mut params = CommandParams::new();
+                params.insert("did", DID_TRUSTEE.to_string());
+                params.insert("seed", backup.words().join(" "));
+                params.insert("checksum", backup.checksum().join(" "));
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            let did = get_did_info(&ctx, DID_TRUSTEE);
+            assert_eq!(did.verkey, VERKEY_TRUSTEE);
+
+            tear_down_with_wallet(&ctx);
+        }
+
+        #[test]
+        pub fn import_paper_works_for_wrong_checksum() {
+            let ctx = setup_with_wallet();
+            let backup = backup();
+            let mut words = backup.words();
+            words.swap(0, 31);
+            {
+                let cmd = import_paper_command::new();
+                let mut params = CommandParams::new();
+                params.insert("did", DID_TRUSTEE.to_string());
+                params.insert("seed", words.join(" "));
+                params.insert("checksum", backup.checksum().join(" "));
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            tear_down_with_wallet(&ctx);
+        }
+    }
+}
@@ -20,7 +20,7 @@ pub mod list_command {
 
         let store = ctx.ensure_opened_wallet()?;
 
-        let mut dids = Did::list(&store).map_err(|err| println_err!("{}", err.message(None)))?;
+        let mut dids = Did::list(&store).map_err(|err| err.print(ctx, None))?;
 
         for did_info in dids.iter_mut() {
             did_info.verkey = Did::abbreviate_verkey(&did_info.did, &did_info.verkey)
@@ -28,6 +28,7 @@ pub mod list_command {
         }
 
         print_list_table(
+            ctx,
             &dids
                 .iter()
                 .map(|did| json!(did))
@@ -40,7 +41,7 @@ pub mod list_command {
             "There are no dids",
         );
         if let Some(cur_did) = ctx.get_active_did()? {
-            println_succ!("Current did \"{}\"", cur_did);
+            println_succ!(ctx, "Current did \"{}\"", cur_did);
         }
 
         trace!("execute <<");
@@ -4,16 +4,27 @@
 */
 use crate::command_executor::{CommandGroup, CommandGroupMetadata};
 
+pub mod export_paper;
 pub mod import;
+pub mod import_hardware;
+pub mod import_paper;
 pub mod list;
 pub mod new;
+pub mod qr;
 pub mod qualify;
+pub mod qualify_all;
+pub mod resolve;
 pub mod rotate_key;
 pub mod set_metadata;
+pub mod show;
+pub mod sign;
 pub mod use_did;
+pub mod verify;
 
 pub use self::{
-    import::*, list::*, new::*, qualify::*, rotate_key::*, set_metadata::*, use_did::*,
+    export_paper::*, import::*, import_hardware::*, import_paper::*, list::*, new::*, qr::*,
+    qualify::*, qualify_all::*, resolve::*, rotate_key::*, set_metadata::*, show::*, sign::*,
+    use_did::*, verify::*,
 };
 
 pub mod group {
@@ -5,9 +5,12 @@
 use crate::{
     command_executor::{Command, CommandContext, CommandMetadata, CommandParams},
     params_parser::ParamParser,
-    tools::did::Did,
+    tools::did::{Did, DidInfo},
+    utils::clipboard::copy_value,
 };
 
+use super::qr::{print_qr, QR_VALUES};
+
 pub mod new_command {
     use super::*;
 
@@ -19,11 +22,18 @@ pub mod new_command {
         )
         .add_optional_param("method", "Method name to create fully qualified DID")
         .add_optional_param("metadata", "DID metadata")
+        .add_optional_param("copy", "Copy created value to the clipboard: did or verkey")
+        .add_optional_param(
+            "qr",
+            "Render created value as QR code: did, verkey or did-key"
+        )
         .add_example("did new")
         .add_example("did new did=VsKV7grR1BUE29mG2Fm2kX")
         .add_example("did new did=VsKV7grR1BUE29mG2Fm2kX method=indy")
         .add_example("did new did=VsKV7grR1BUE29mG2Fm2kX seed=00000000000000000000000000000My1")
         .add_example("did new seed=00000000000000000000000000000My1 metadata=did_metadata")
+        .add_example("did new copy=verkey")
+        .add_example("did new qr=did-key")
         .finalize());
 
     fn execute(ctx: &CommandContext, params: &CommandParams) -> Result<(), ()> {
@@ -31,17 +41,58 @@ pub mod new_command {
 
         let store = ctx.ensure_opened_wallet()?;
 
-        let did = ParamParser::get_opt_str_param("did", params)?;
-        let seed = ParamParser::get_opt_str_param("seed", params)?;
-        let method = ParamParser::get_opt_str_param("method", params)?;
+        let did = ParamParser::get_opt_str_param(ctx, "did", params)?;
+        let seed = ParamParser::get_opt_str_param(ctx, "seed", params)?;
+        let method = ParamParser::get_opt_str_param(ctx, "method", params)?;
         let metadata = ParamParser::get_opt_empty_str_param("metadata", params)?;
+        let copy = ParamParser::get_opt_str_param(ctx, "copy", params)?;
+        let qr = ParamParser::get_opt_str_param(ctx, "qr", params)?;
 
-        let (did, vk) = Did::create(&store, did, seed, metadata, method)
-            .map_err(|err| println_err!("{}", err.message(None)))?;
+        if let Some(copy) = copy.filter(|copy| !["did", "verkey"].contains(copy)) {
+            println_err!(
+                ctx,
+                "Unsupported value to copy \"{}\". Expected one of: did, verkey",
+                copy
+            );
+            return Err(());
+        }
+
+        if let Some(qr) = qr.filter(|qr| !QR_VALUES.contains(qr)) {
+            println_err!(
+                ctx,
+                "Unsupported value to render \"{}\". Expected one of: {}",
+                qr,
+                QR_VALUES.join(", ")
+            );
+            return Err(());
+        }
+
+        let (did, vk) =
+            Did::create(&store, did, seed, metadata, method).map_err(|err| err.print(ctx, None))?;
 
         let vk = Did::abbreviate_verkey(&did, &vk).unwrap_or(vk);
 
-        println_succ!("Did \"{}\" has been created with \"{}\" verkey", did, vk);
+        println_succ!(
+            ctx,
+            "Did \"{}\" has been created with \"{}\" verkey",
+            did,
+            vk
+        );
+
+        match copy {
+            Some("did") => copy_value(ctx, &did, "Did")?,
+            Some("verkey") => copy_value(ctx, &vk, "Verkey")?,
+            _ => {}
+        }
+
+        if let Some(qr) = qr {
+            let did_info = DidInfo {
+                did: did.clone(),
+                verkey: vk.clone(),
+                ..DidInfo::default()
+            };
+            print_qr(ctx, &did_info, qr)?;
+        }
 
         trace!("execute <<");
         Ok(())
@@ -104,6 +155,22 @@ pub mod tests {
             tear_down_with_wallet(&ctx);
         }
 
+        #[test]
+        pub fn new_works_for_qr() {
+            let ctx = setup_with_wallet();
+            {
+                let cmd = new_command::new();
+                let mut params = CommandParams::new();
+                params.insert("seed", SEED_TRUSTEE.to_string());
+                params.insert("qr", "did-key".to_string());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            let did = get_did_info(&ctx, DID_TRUSTEE);
+            assert_eq!(did.did, DID_TRUSTEE);
+
+            tear_down_with_wallet(&ctx);
+        }
+
         #[test]
         pub fn new_works_for_hex_seed() {
             let ctx = setup_with_wallet();
@@ -0,0 +1,139 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::{
+    command_executor::{
+        Command, CommandContext, CommandMetadata, CommandParams, DynamicCompletionType,
+    },
+    params_parser::ParamParser,
+    tools::did::{Did, DidInfo},
+    utils::qr,
+};
+
+pub const QR_VALUES: [&str; 3] = ["did", "verkey", "did-key"];
+
+pub mod qr_command {
+    use super::*;
+
+    command!(
+        CommandMetadata::build("qr", "Render DID stored in the opened wallet as QR code.")
+            .add_main_param_with_dynamic_completion(
+                "did",
+                "Did stored in wallet (the active DID is used by default)",
+                DynamicCompletionType::Did
+            )
+            .add_optional_param("value", "Value to render: did (default), verkey or did-key")
+            .add_example("did qr VsKV7grR1BUE29mG2Fm2kX")
+            .add_example("did qr VsKV7grR1BUE29mG2Fm2kX value=did-key")
+            .finalize()
+    );
+
+    fn execute(ctx: &CommandContext, params: &CommandParams) -> Result<(), ()> {
+        trace!("execute >> ctx {:?} params {:?}", ctx, params);
+
+        let store = ctx.ensure_opened_wallet()?;
+
+        let did = match params.get("did") {
+            Some(_) => ParamParser::get_did_param(ctx, "did", params)?,
+            None => ctx.ensure_active_did()?.as_ref().clone(),
+        };
+        let value = ParamParser::get_opt_str_param(ctx, "value", params)?.unwrap_or("did");
+
+        let did_info = Did::get(&store, &did).map_err(|err| err.print(ctx, None))?;
+
+        print_qr(ctx, &did_info, value)?;
+
+        trace!("execute <<");
+        Ok(())
+    }
+}
+
+/// Print QR code for `did`, `verkey` or `did-key` value of the DID.
+pub fn print_qr(ctx: &CommandContext, did_info: &DidInfo, value: &str) -> Result<(), ()> {
+    let data = match value {
+        "did" => did_info.did.clone(),
+        "verkey" => {
+            Did::full_verkey(&did_info.did, &did_info.verkey).map_err(|err| err.print(ctx, None))?
+        }
+        "did-key" => Did::full_verkey(&did_info.did, &did_info.verkey)
+            .and_then(|verkey| Did::to_did_key(&verkey))
+            .map_err(|err| err.print(ctx, None))?,
+        value => {
+            println_err!(
+                ctx,
+                "Unsupported value to render \"{}\". Expected one of: {}",
+                value,
+                QR_VALUES.join(", ")
+            );
+            return Err(());
+        }
+    };
+
+    let code = qr::render(&data).map_err(|err| println_err!(ctx, "{}", err))?;
+    println!("{}", code);
+    println!("{}", data);
+    Ok(())
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    mod did_qr {
+        use super::*;
+        use crate::{
+            commands::{setup_with_wallet, tear_down_with_wallet},
+            did::tests::{new_did, use_did, DID_TRUSTEE, SEED_TRUSTEE, VERKEY_TRUSTEE},
+        };
+
+        #[test]
+        pub fn qr_works() {
+            let ctx = setup_with_wallet();
+            new_did(&ctx, SEED_TRUSTEE);
+            {
+                let cmd = qr_command::new();
+                let mut params = CommandParams::new();
+                params.insert("did", DID_TRUSTEE.to_string());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            tear_down_with_wallet(&ctx);
+        }
+
+        #[test]
+        pub fn qr_works_for_active_did_key() {
+            let ctx = setup_with_wallet();
+            new_did(&ctx, SEED_TRUSTEE);
+            use_did(&ctx, DID_TRUSTEE);
+            {
+                let cmd = qr_command::new();
+                let mut params = CommandParams::new();
+                params.insert("value", "did-key".to_string());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            tear_down_with_wallet(&ctx);
+        }
+
+        #[test]
+        pub fn qr_works_for_unknown_value() {
+            let ctx = setup_with_wallet();
+            new_did(&ctx, SEED_TRUSTEE);
+            {
+                let cmd = qr_command::new();
+                let mut params = CommandParams::new();
+                params.insert("did", DID_TRUSTEE.to_string());
+                params.insert("value", "seed".to_string());
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            tear_down_with_wallet(&ctx);
+        }
+
+        #[test]
+        pub fn to_did_key_works() {
+            assert_eq!(
+                Did::to_did_key(VERKEY_TRUSTEE).unwrap(),
+                "did:key:z6MkukGVb3mRvTu1msArDKY9UwxeZFGjmwnCKtdQttr4Fk6i"
+            );
+        }
+    }
+}
@@ -33,8 +33,8 @@ pub mod qualify_command {
         trace!("execute >> ctx {:?}, params {:?}", ctx, params);
 
         let wallet = ctx.ensure_opened_wallet()?;
-        let did = ParamParser::get_did_param("did", params)?;
-        let method = ParamParser::get_str_param("method", params)?;
+        let did = ParamParser::get_did_param(ctx, "did", params)?;
+        let method = ParamParser::get_str_param(ctx, "method", params)?;
 
         let method = if method.contains("did:") {
             &method[4..]
@@ -42,15 +42,18 @@ pub mod qualify_command {
             method
         };
 
-        let full_qualified_did = Did::qualify(&wallet, &did, &method)
-            .map_err(|err| println_err!("{}", err.message(None)))?;
+        let full_qualified_did =
+            Did::qualify(&wallet, &did, &method).map_err(|err| err.print(ctx, None))?;
 
-        println_succ!("Fully qualified DID \"{}\"", full_qualified_did);
+        println_succ!(ctx, "Fully qualified DID \"{}\"", full_qualified_did);
 
         if let Some(active_did) = ctx.get_active_did()? {
             if *active_did == did {
                 ctx.set_active_did(full_qualified_did);
-                println_succ!("Target DID is the same as CLI active. Active DID has been updated");
+                println_succ!(
+                    ctx,
+                    "Target DID is the same as CLI active. Active DID has been updated"
+                );
             }
         }
 
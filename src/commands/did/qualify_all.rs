@@ -0,0 +1,180 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::{
+    command_executor::{
+        wait_for_user_reply, Command, CommandContext, CommandMetadata, CommandParams,
+    },
+    params_parser::ParamParser,
+    tools::did::Did,
+    utils::{table::print_list_table, term::print_prompt},
+};
+
+use indy_utils::{did::DidValue, Qualifiable};
+
+pub mod qualify_all_command {
+    use super::*;
+
+    command!(CommandMetadata::build(
+        "qualify-all",
+        "Make all unqualified DIDs stored in the wallet fully qualified with the given method."
+    )
+    .add_required_param(
+        "method",
+        "Method to apply to the DIDs. Note that `did:` prefix must be omitted`"
+    )
+    .add_optional_param(
+        "force",
+        "Migrate DIDs without asking for confirmation (False by default)"
+    )
+    .add_example("did qualify-all method=indy:sovrin")
+    .add_example("did qualify-all method=indy:sovrin force=true")
+    .finalize());
+
+    fn execute(ctx: &CommandContext, params: &CommandParams) -> Result<(), ()> {
+        trace!("execute >> ctx {:?}, params {:?}", ctx, params);
+
+        let wallet = ctx.ensure_opened_wallet()?;
+        let method = ParamParser::get_str_param(ctx, "method", params)?;
+        let force = ParamParser::get_opt_bool_param(ctx, "force", params)?.unwrap_or(false);
+
+        let method = if method.contains("did:") {
+            &method[4..]
+        } else {
+            method
+        };
+
+        let dids = Did::list(&wallet)
+            .map_err(|err| err.print(ctx, None))?
+            .into_iter()
+            .map(|did_info| DidValue(did_info.did))
+            .filter(|did| !did.is_fully_qualified())
+            .collect::<Vec<DidValue>>();
+
+        if dids.is_empty() {
+            println_succ!(ctx, "There are no unqualified DIDs in the wallet");
+            return Ok(());
+        }
+
+        if !force {
+            print_prompt(&format!(
+                "{} DIDs will be qualified with \"{}\" method. Do you want to continue? (y/n)",
+                dids.len(),
+                method
+            ));
+            if !wait_for_user_reply(ctx) {
+                println!("DIDs have not been qualified.");
+                return Ok(());
+            }
+        }
+
+        let active_did = ctx.get_active_did()?;
+
+        let mut results = Vec::new();
+        let mut failed = 0;
+
+        for did in dids {
+            match Did::qualify(&wallet, &did, method) {
+                Ok(qualified_did) => {
+                    if active_did.as_deref() == Some(&did) {
+                        ctx.set_active_did(qualified_did.clone());
+                        println_succ!(
+                            ctx,
+                            "Target DID is the same as CLI active. Active DID has been updated"
+                        );
+                    }
+                    results.push(json!({
+                        "did": did.to_string(),
+                        "qualified_did": qualified_did.to_string(),
+                        "status": "Migrated",
+                    }));
+                }
+                Err(err) => {
+                    failed += 1;
+                    results.push(json!({
+                        "did": did.to_string(),
+                        "qualified_did": "-",
+                        "status": format!("Failed: {}", err.message(None)),
+                    }));
+                }
+            }
+        }
+
+        print_list_table(
+            ctx,
+            &results,
+            &[
+                ("did", "Did"),
+                ("qualified_did", "Qualified Did"),
+                ("status", "Status"),
+            ],
+            "",
+        );
+
+        let migrated = results.len() - failed;
+        if failed > 0 {
+            println_err!(
+                ctx,
+                "{} DIDs have been qualified, {} DIDs have failed",
+                migrated,
+                failed
+            );
+            return Err(());
+        }
+
+        println_succ!(ctx, "{} DIDs have been qualified", migrated);
+
+        trace!("execute <<");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    mod qualify_all_dids {
+        use super::*;
+        use crate::{
+            commands::{setup_with_wallet, tear_down_with_wallet},
+            did::tests::{get_did_info, new_did, use_did, DID_MY1, DID_MY3, SEED_MY1, SEED_MY3},
+        };
+
+        const METHOD: &str = "indy:sovrin";
+
+        #[test]
+        pub fn qualify_all_dids_works() {
+            let ctx = setup_with_wallet();
+            new_did(&ctx, SEED_MY1);
+            new_did(&ctx, SEED_MY3);
+            use_did(&ctx, DID_MY1);
+            {
+                let cmd = qualify_all_command::new();
+                let mut params = CommandParams::new();
+                params.insert("method", METHOD.to_string());
+                params.insert("force", "true".to_string());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            get_did_info(&ctx, &format!("did:{}:{}", METHOD, DID_MY1));
+            get_did_info(&ctx, &format!("did:{}:{}", METHOD, DID_MY3));
+            assert_eq!(
+                ctx.get_active_did().unwrap().unwrap().to_string(),
+                format!("did:{}:{}", METHOD, DID_MY1)
+            );
+            tear_down_with_wallet(&ctx);
+        }
+
+        #[test]
+        pub fn qualify_all_dids_works_for_empty_wallet() {
+            let ctx = setup_with_wallet();
+            {
+                let cmd = qualify_all_command::new();
+                let mut params = CommandParams::new();
+                params.insert("method", METHOD.to_string());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            tear_down_with_wallet(&ctx);
+        }
+    }
+}
@@ -0,0 +1,150 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::{
+    command_executor::{Command, CommandContext, CommandMetadata, CommandParams},
+    ledger::{handle_transaction_response, submit_request},
+    params_parser::ParamParser,
+    tools::{
+        did::{constants::INDY_DID_PREFIX, document::DidDocument, Did},
+        ledger::{Ledger, Response},
+    },
+    utils::renderer::print_value,
+};
+
+use indy_utils::{did::DidValue, Qualifiable};
+use indy_vdr::pool::PreparedRequest;
+use serde_json::Value as JsonValue;
+
+pub mod resolve_command {
+    use super::*;
+
+    command!(CommandMetadata::build(
+        "resolve",
+        "Resolve DID Document of a DID written on the ledger of the connected pool. \
+        The document is built from the NYM verkey and either the NYM `diddocContent` or the `endpoint` ATTRIB."
+    )
+    .add_main_param("did", "DID to resolve")
+    .add_example("did resolve V4SGRU86Z58d6TV7PBUe6f")
+    .add_example("did resolve did:indy:sovrin:staging:V4SGRU86Z58d6TV7PBUe6f")
+    .finalize());
+
+    fn execute(ctx: &CommandContext, params: &CommandParams) -> Result<(), ()> {
+        trace!("execute >> ctx {:?} params {:?}", ctx, params);
+
+        let pool = ctx.ensure_connected_pool()?;
+        let did = ParamParser::get_did_param(ctx, "did", params)?;
+        let short_did = Did::short_form(&did);
+
+        let request = Ledger::build_get_nym_request(Some(&pool), None, &short_did)
+            .map_err(|err| err.print(ctx, None))?;
+        let nym = read_data(ctx, &request)?.ok_or_else(|| {
+            println_err!(ctx, "DID \"{}\" is not found on the ledger", did);
+        })?;
+        let verkey = nym["verkey"].as_str().ok_or_else(|| {
+            println_err!(ctx, "DID \"{}\" has no verkey: it cannot be resolved", did);
+        })?;
+        let verkey = Did::full_verkey(&short_did.0, verkey).map_err(|err| err.print(ctx, None))?;
+        let diddoc_content = match &nym["diddocContent"] {
+            JsonValue::Null => None,
+            JsonValue::String(content) => Some(
+                serde_json::from_str::<JsonValue>(content)
+                    .map_err(|_| println_err!(ctx, "Invalid diddocContent has been received"))?,
+            ),
+            content => Some(content.clone()),
+        };
+
+        // the legacy endpoint ATTRIB is not used once the NYM carries the document content
+        let endpoint = match diddoc_content {
+            Some(_) => None,
+            None => {
+                let request = Ledger::build_get_attrib_request(
+                    Some(&pool),
+                    None,
+                    &short_did,
+                    Some("endpoint"),
+                    None,
+                    None,
+                )
+                .map_err(|err| err.print(ctx, None))?;
+                read_data(ctx, &request)?.map(|attrib| attrib["endpoint"].clone())
+            }
+        };
+
+        let id = qualified_id(&did, &short_did, pool.namespace.as_deref());
+        let document = DidDocument {
+            id: &id,
+            verkey: &verkey,
+            endpoint: endpoint.as_ref().filter(|endpoint| !endpoint.is_null()),
+            diddoc_content: diddoc_content.as_ref(),
+        }
+        .to_json()
+        .map_err(|err| err.print(ctx, None))?;
+
+        print_value(ctx, &document);
+
+        trace!("execute <<");
+        Ok(())
+    }
+
+    /// Parsed `data` of a read reply, `None` if the ledger has no such entry.
+    fn read_data(ctx: &CommandContext, request: &PreparedRequest) -> Result<Option<JsonValue>, ()> {
+        let response = submit_request(ctx, request)?;
+        let response = serde_json::from_str::<Response<JsonValue>>(&response)
+            .map_err(|err| println_err!(ctx, "Invalid data has been received: {:?}", err))?;
+        let result = handle_transaction_response(ctx, response)?;
+        match result["data"].as_str() {
+            Some(data) => serde_json::from_str::<JsonValue>(data)
+                .map(Some)
+                .map_err(|_| println_err!(ctx, "Wrong data has been received")),
+            None => Ok(None),
+        }
+    }
+
+    // Unqualified DIDs get `did:indy` identifier if the pool defines its namespace
+    fn qualified_id(did: &DidValue, short_did: &DidValue, namespace: Option<&str>) -> String {
+        match namespace {
+            _ if did.is_fully_qualified() => did.0.to_string(),
+            Some(namespace) => format!("{}{}:{}", INDY_DID_PREFIX, namespace, short_did.0),
+            None => format!("did:sov:{}", short_did.0),
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::{
+        commands::{setup_with_wallet_and_pool, tear_down_with_wallet_and_pool},
+        did::tests::{DID_MY3, DID_TRUSTEE},
+    };
+
+    mod did_resolve {
+        use super::*;
+
+        #[test]
+        pub fn resolve_works() {
+            let ctx = setup_with_wallet_and_pool();
+            {
+                let cmd = resolve_command::new();
+                let mut params = CommandParams::new();
+                params.insert("did", DID_TRUSTEE.to_string());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            tear_down_with_wallet_and_pool(&ctx);
+        }
+
+        #[test]
+        pub fn resolve_works_for_unknown_did() {
+            let ctx = setup_with_wallet_and_pool();
+            {
+                let cmd = resolve_command::new();
+                let mut params = CommandParams::new();
+                params.insert("did", DID_MY3.to_string());
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            tear_down_with_wallet_and_pool(&ctx);
+        }
+    }
+}
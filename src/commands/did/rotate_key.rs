@@ -31,9 +31,9 @@ pub mod rotate_key_command {
     fn execute(ctx: &CommandContext, params: &CommandParams) -> Result<(), ()> {
         trace!("execute >> ctx {:?} params {:?}", ctx, secret!(params));
 
-        let seed = ParamParser::get_opt_str_param("seed", params)?;
+        let seed = ParamParser::get_opt_str_param(ctx, "seed", params)?;
 
-        let resume = ParamParser::get_opt_bool_param("resume", params)?.unwrap_or(false);
+        let resume = ParamParser::get_opt_bool_param(ctx, "resume", params)?.unwrap_or(false);
 
         let did = ctx.ensure_active_did()?;
         let pool = ctx.get_connected_pool();
@@ -41,7 +41,7 @@ pub mod rotate_key_command {
 
         // get verkey from ledger
         let ledger_verkey = match pool {
-            Some(pool) => get_current_verkey(&pool, &store, &did)?,
+            Some(pool) => get_current_verkey(ctx, &pool, &store, &did)?,
             None => None,
         };
 
@@ -50,11 +50,10 @@ pub mod rotate_key_command {
         let (new_verkey, update_ledger) = if resume {
             // get temp and current verkey from wallet.
 
-            let did_info =
-                Did::get(&store, &did).map_err(|err| println_err!("{}", err.message(None)))?;
+            let did_info = Did::get(&store, &did).map_err(|err| err.print(ctx, None))?;
 
             let temp_verkey = did_info.next_verkey.ok_or_else(|| {
-                println_err!("Unable to resume, have you already run rotate-key?")
+                println_err!(ctx, "Unable to resume, have you already run rotate-key?")
             })?;
             let curr_verkey = did_info.verkey;
 
@@ -62,8 +61,10 @@ pub mod rotate_key_command {
                 Some(ledger_verkey) => {
                     // if ledger verkey is abbreviated, abbreviate other also.
                     let (temp_verkey, curr_verkey) = if ledger_verkey.starts_with('~') {
-                        let temp_verkey = Did::abbreviate_verkey(&did, &temp_verkey)
-                            .map_err(|_e| println_err!("Invalid temp verkey: {}", temp_verkey))?;
+                        let temp_verkey =
+                            Did::abbreviate_verkey(&did, &temp_verkey).map_err(|_e| {
+                                println_err!(ctx, "Invalid temp verkey: {}", temp_verkey)
+                            })?;
                         let curr_verkey =
                             Did::abbreviate_verkey(&did, &curr_verkey).unwrap_or(curr_verkey);
                         Ok((temp_verkey, curr_verkey))
@@ -71,9 +72,9 @@ pub mod rotate_key_command {
                         Ok((temp_verkey, curr_verkey))
                     }?;
 
-                    println_succ!("Verkey on ledger: {}", ledger_verkey);
-                    println_succ!("Current verkey in wallet: {}", curr_verkey);
-                    println_succ!("Temp verkey in wallet: {}", temp_verkey);
+                    println_succ!(ctx, "Verkey on ledger: {}", ledger_verkey);
+                    println_succ!(ctx, "Current verkey in wallet: {}", curr_verkey);
+                    println_succ!(ctx, "Temp verkey in wallet: {}", temp_verkey);
 
                     if ledger_verkey == temp_verkey {
                         // ledger is updated, need to apply change to wallet.
@@ -83,18 +84,18 @@ pub mod rotate_key_command {
                         Ok((temp_verkey, true))
                     } else {
                         // some invalid state
-                        println_err!("Unable to resume, verkey on ledger is completely different from verkey in wallet");
+                        println_err!(ctx, "Unable to resume, verkey on ledger is completely different from verkey in wallet");
                         Err(())
                     }
                 }
                 None => {
-                    println_warn!("DID is not registered on the ledger");
+                    println_warn!(ctx, "DID is not registered on the ledger");
                     Ok((temp_verkey, false))
                 }
             }?
         } else {
-            let new_verkey = Did::replace_keys_start(&store, &did, seed)
-                .map_err(|err| println_err!("{}", err.message(None)))?;
+            let new_verkey =
+                Did::replace_keys_start(&store, &did, seed).map_err(|err| err.print(ctx, None))?;
 
             (new_verkey, true)
         };
@@ -105,26 +106,25 @@ pub mod rotate_key_command {
             send_nym(&ctx, &pool, &store, &did, &new_verkey).map_err(|err| match err {
                 CliError::VdrError(ref vdr_err) => match vdr_err.kind() {
                     VdrErrorKind::PoolTimeout => {
-                        println_err!("Transaction response has not been received");
-                        println_err!("Use command `did rotate-key resume=true` to complete");
+                        println_err!(ctx, "Transaction response has not been received");
+                        println_err!(ctx, "Use command `did rotate-key resume=true` to complete");
                     }
                     _ => {
-                        println_err!("{}", err.message(Some(&pool.name)));
+                        err.print(ctx, Some(&pool.name));
                     }
                 },
                 _ => {
-                    println_err!("{}", err.message(Some(&pool.name)));
+                    err.print(ctx, Some(&pool.name));
                 }
             })?;
         };
 
-        Did::replace_keys_apply(&store, &did)
-            .map_err(|err| println_err!("{}", err.message(None)))?;
+        Did::replace_keys_apply(&store, &did).map_err(|err| err.print(ctx, None))?;
 
         let vk = Did::abbreviate_verkey(&did, &new_verkey).unwrap_or(new_verkey);
 
-        println_succ!("Verkey for did \"{}\" has been updated", did);
-        println_succ!("New verkey is \"{}\"", vk);
+        println_succ!(ctx, "Verkey for did \"{}\" has been updated", did);
+        println_succ!(ctx, "New verkey is \"{}\"", vk);
 
         trace!("execute <<");
         Ok(())
@@ -151,7 +151,7 @@ pub mod tests {
             let wallet = ctx.ensure_opened_wallet().unwrap();
             let did = DidValue(did.to_string());
             let mut request = Ledger::build_get_nym_request(Some(&pool), None, &did).unwrap();
-            Ledger::sign_request(&wallet, &did, &mut request).unwrap();
+            Ledger::sign_request(Some(&pool), &wallet, &did, &mut request).unwrap();
             submit_retry(ctx, &request, |response| {
                 let res = req_for_nym(response);
                 match res {
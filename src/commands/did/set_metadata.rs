@@ -31,21 +31,23 @@ pub mod set_metadata_command {
         trace!("execute >> ctx {:?}, params {:?}", ctx, params);
 
         let wallet = ctx.ensure_opened_wallet()?;
-        let did_param = ParamParser::get_opt_did_param("did", params)?;
-        let metadata = ParamParser::get_str_param("metadata", params)?;
+        let did_param = ParamParser::get_opt_did_param(ctx, "did", params)?;
+        let metadata = ParamParser::get_str_param(ctx, "metadata", params)?;
         let active_did = ctx.get_active_did()?;
 
         let did = match did_param {
             Some(ref did) => did,
             None => active_did.as_ref().ok_or_else(|| {
-                println_err!("DID must be either passed as the parameter or set as the active")
+                println_err!(
+                    ctx,
+                    "DID must be either passed as the parameter or set as the active"
+                )
             })?,
         };
 
-        Did::set_metadata(&wallet, &did, metadata)
-            .map_err(|err| println_err!("{}", err.message(None)))?;
+        Did::set_metadata(&wallet, &did, metadata).map_err(|err| err.print(ctx, None))?;
 
-        println_succ!("DID Metadata updated");
+        println_succ!(ctx, "DID Metadata updated");
 
         trace!("execute <<");
         Ok(())
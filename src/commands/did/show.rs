@@ -0,0 +1,136 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::{
+    command_executor::{
+        Command, CommandContext, CommandMetadata, CommandParams, DynamicCompletionType,
+    },
+    params_parser::ParamParser,
+    tools::did::Did,
+    utils::{clipboard::copy_value, table::print_table},
+};
+
+pub mod show_command {
+    use super::*;
+
+    command!(
+        CommandMetadata::build("show", "Show DID stored in the opened wallet.")
+            .add_main_param_with_dynamic_completion(
+                "did",
+                "Did stored in wallet (the active DID is shown by default)",
+                DynamicCompletionType::Did
+            )
+            .add_optional_param("copy", "Copy DID value to the clipboard: did or verkey")
+            .add_example("did show VsKV7grR1BUE29mG2Fm2kX")
+            .add_example("did show VsKV7grR1BUE29mG2Fm2kX copy=verkey")
+            .finalize()
+    );
+
+    fn execute(ctx: &CommandContext, params: &CommandParams) -> Result<(), ()> {
+        trace!("execute >> ctx {:?} params {:?}", ctx, params);
+
+        let store = ctx.ensure_opened_wallet()?;
+
+        let did = match params.get("did") {
+            Some(_) => ParamParser::get_did_param(ctx, "did", params)?,
+            None => ctx.ensure_active_did()?.as_ref().clone(),
+        };
+        let copy = ParamParser::get_opt_str_param(ctx, "copy", params)?;
+
+        let did_info = Did::get(&store, &did).map_err(|err| err.print(ctx, None))?;
+
+        print_table(
+            ctx,
+            &json!(did_info),
+            &[
+                ("did", "Did"),
+                ("verkey", "Verkey"),
+                ("method", "Method"),
+                ("metadata", "Metadata"),
+                ("next_verkey", "Next Verkey"),
+            ],
+        );
+
+        match copy {
+            Some("did") => copy_value(ctx, &did_info.did, "Did")?,
+            Some("verkey") => copy_value(ctx, &did_info.verkey, "Verkey")?,
+            Some(copy) => {
+                println_err!(
+                    ctx,
+                    "Unsupported value to copy \"{}\". Expected one of: did, verkey",
+                    copy
+                );
+                return Err(());
+            }
+            None => {}
+        }
+
+        trace!("execute <<");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    mod did_show {
+        use super::*;
+        use crate::{
+            commands::{setup_with_wallet, tear_down_with_wallet},
+            did::tests::{new_did, use_did, DID_TRUSTEE, SEED_TRUSTEE},
+        };
+
+        #[test]
+        pub fn show_works() {
+            let ctx = setup_with_wallet();
+            new_did(&ctx, SEED_TRUSTEE);
+            {
+                let cmd = show_command::new();
+                let mut params = CommandParams::new();
+                params.insert("did", DID_TRUSTEE.to_string());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            tear_down_with_wallet(&ctx);
+        }
+
+        #[test]
+        pub fn show_works_for_active_did() {
+            let ctx = setup_with_wallet();
+            new_did(&ctx, SEED_TRUSTEE);
+            use_did(&ctx, DID_TRUSTEE);
+            {
+                let cmd = show_command::new();
+                let params = CommandParams::new();
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            tear_down_with_wallet(&ctx);
+        }
+
+        #[test]
+        pub fn show_works_for_no_active_did() {
+            let ctx = setup_with_wallet();
+            {
+                let cmd = show_command::new();
+                let params = CommandParams::new();
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            tear_down_with_wallet(&ctx);
+        }
+
+        #[test]
+        pub fn show_works_for_unknown_copy_value() {
+            let ctx = setup_with_wallet();
+            new_did(&ctx, SEED_TRUSTEE);
+            {
+                let cmd = show_command::new();
+                let mut params = CommandParams::new();
+                params.insert("did", DID_TRUSTEE.to_string());
+                params.insert("copy", "seed".to_string());
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            tear_down_with_wallet(&ctx);
+        }
+    }
+}
@@ -0,0 +1,134 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::{
+    command_executor::{Command, CommandContext, CommandMetadata, CommandParams},
+    params_parser::ParamParser,
+    tools::did::Did,
+    utils::{encoding::Encoding, renderer::print_value},
+};
+
+use std::fs;
+
+pub const DEFAULT_SIGNATURE_ENCODING: &str = "base58";
+
+pub mod sign_command {
+    use super::*;
+
+    command!(CommandMetadata::build(
+        "sign",
+        "Sign a message with the key of the active DID to prove control of the DID off-ledger."
+    )
+    .add_optional_param("message", "Text to sign (UTF-8)")
+    .add_optional_param("file", "Path to file with bytes to sign")
+    .add_optional_param(
+        "encoding",
+        "Encoding of the signature: base58 (default), base58check, base64, base64url, hex or multibase"
+    )
+    .add_example("did sign message=hello")
+    .add_example("did sign file=/home/challenge.bin encoding=base64")
+    .finalize());
+
+    fn execute(ctx: &CommandContext, params: &CommandParams) -> Result<(), ()> {
+        trace!("execute >> ctx {:?} params {:?}", ctx, params);
+
+        let wallet = ctx.ensure_opened_wallet()?;
+        let did = ctx.ensure_active_did()?;
+
+        let message = read_message(ctx, params)?;
+        let encoding = get_signature_encoding(ctx, params)?;
+
+        let signature =
+            Did::sign_message(&wallet, &did, &message).map_err(|err| err.print(ctx, None))?;
+
+        print_value(ctx, &json!(encoding.encode(&signature)));
+
+        trace!("execute <<");
+        Ok(())
+    }
+}
+
+/// Bytes of the message given as `message` text or read from `file`.
+pub fn read_message(ctx: &CommandContext, params: &CommandParams) -> Result<Vec<u8>, ()> {
+    match (
+        ParamParser::get_opt_str_param(ctx, "message", params)?,
+        ParamParser::get_opt_str_param(ctx, "file", params)?,
+    ) {
+        (Some(message), None) => Ok(message.as_bytes().to_vec()),
+        (None, Some(file)) => fs::read(file)
+            .map_err(|err| println_err!(ctx, "Can't read the file \"{}\": {}", file, err)),
+        _ => {
+            println_err!(ctx, "Either `message` or `file` must be specified");
+            Err(())
+        }
+    }
+}
+
+pub fn get_signature_encoding(
+    ctx: &CommandContext,
+    params: &CommandParams,
+) -> Result<Encoding, ()> {
+    let encoding = ParamParser::get_opt_str_param(ctx, "encoding", params)?
+        .unwrap_or(DEFAULT_SIGNATURE_ENCODING);
+    Encoding::from_str(encoding).map_err(|err| println_err!(ctx, "{}", err))
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::{
+        commands::{setup_with_wallet, tear_down_with_wallet},
+        did::tests::{new_did, use_did, DID_TRUSTEE, SEED_TRUSTEE},
+    };
+    use indy_utils::did::DidValue;
+
+    pub const MESSAGE: &str = "hello";
+
+    pub fn sign(ctx: &CommandContext, message: &str) -> String {
+        let wallet = ctx.ensure_opened_wallet().unwrap();
+        let signature = Did::sign_message(
+            &wallet,
+            &DidValue(DID_TRUSTEE.to_string()),
+            message.as_bytes(),
+        )
+        .unwrap();
+        Encoding::Base58.encode(&signature)
+    }
+
+    mod did_sign {
+        use super::*;
+
+        #[test]
+        pub fn sign_works() {
+            let ctx = setup_with_wallet();
+            new_did(&ctx, SEED_TRUSTEE);
+            use_did(&ctx, DID_TRUSTEE);
+            {
+                let cmd = sign_command::new();
+                let mut params = CommandParams::new();
+                params.insert("message", MESSAGE.to_string());
+                params.insert("encoding", "base64".to_string());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            tear_down_with_wallet(&ctx);
+        }
+
+        #[test]
+        pub fn sign_works_for_no_active_did() {
+            let ctx = setup_with_wallet();
+            {
+                let cmd = sign_command::new();
+                let mut params = CommandParams::new();
+                params.insert("message", MESSAGE.to_string());
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            tear_down_with_wallet(&ctx);
+        }
+
+        #[test]
+        pub fn read_message_works_for_missing_message() {
+            read_message(&CommandContext::new(), &CommandParams::new()).unwrap_err();
+        }
+    }
+}
@@ -6,6 +6,7 @@ use crate::{
     command_executor::{
         Command, CommandContext, CommandMetadata, CommandParams, DynamicCompletionType,
     },
+    ledger::check_did_namespace,
     params_parser::ParamParser,
     tools::did::Did,
 };
@@ -25,15 +26,17 @@ pub mod use_command {
     fn execute(ctx: &CommandContext, params: &CommandParams) -> Result<(), ()> {
         trace!("execute >> ctx {:?}, params {:?}", ctx, params);
 
-        let did = ParamParser::get_did_param("did", params)?;
+        let did = ParamParser::get_did_param(ctx, "did", params)?;
 
         let store = ctx.ensure_opened_wallet()?;
 
-        Did::get(&store, &did).map_err(|err| println_err!("{}", err.message(None)))?;
+        Did::get(&store, &did).map_err(|err| err.print(ctx, None))?;
 
-        println_succ!("Did \"{}\" has been set as active", did);
+        println_succ!(ctx, "Did \"{}\" has been set as active", did);
         ctx.set_active_did(did);
 
+        check_did_namespace(ctx);
+
         trace!("execute <<");
         Ok(())
     }
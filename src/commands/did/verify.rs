@@ -0,0 +1,131 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::{
+    command_executor::{Command, CommandContext, CommandMetadata, CommandParams},
+    did::{get_signature_encoding, read_message},
+    ledger::resolve_verkey,
+    params_parser::ParamParser,
+    tools::did::{key::Key, Did},
+};
+
+pub mod verify_command {
+    use super::*;
+
+    command!(CommandMetadata::build(
+        "verify",
+        "Verify a signature of a message made by `did sign`. \
+        The verkey of the DID is taken from the opened wallet or the ledger of the connected pool unless `verkey` is given."
+    )
+    .add_required_param("did", "DID the message has been signed by")
+    .add_optional_param("message", "Signed text (UTF-8)")
+    .add_optional_param("file", "Path to file with the signed bytes")
+    .add_required_param("signature", "Signature to verify")
+    .add_optional_param(
+        "encoding",
+        "Encoding of the signature: base58 (default), base58check, base64, base64url, hex or multibase"
+    )
+    .add_optional_param("verkey", "Verkey of the DID to verify the signature with")
+    .add_example("did verify did=V4SGRU86Z58d6TV7PBUe6f message=hello signature=4Be93xM...")
+    .add_example("did verify did=V4SGRU86Z58d6TV7PBUe6f file=/home/challenge.bin signature=tB3H... encoding=base64 verkey=~CoRER63DVYnWZtK8uAzNbx")
+    .finalize());
+
+    fn execute(ctx: &CommandContext, params: &CommandParams) -> Result<(), ()> {
+        trace!("execute >> ctx {:?} params {:?}", ctx, params);
+
+        let did = ParamParser::get_str_param(ctx, "did", params)?;
+        let signature = ParamParser::get_str_param(ctx, "signature", params)?;
+        let verkey = ParamParser::get_opt_str_param(ctx, "verkey", params)?;
+
+        let message = read_message(ctx, params)?;
+        let encoding = get_signature_encoding(ctx, params)?;
+        let signature = encoding
+            .decode(signature)
+            .map_err(|err| println_err!(ctx, "Invalid signature: {}", err))?;
+
+        let verkey = match verkey {
+            Some(verkey) => Did::full_verkey(did, verkey).map_err(|err| err.print(ctx, None))?,
+            None => resolve_verkey(ctx, did).ok_or_else(|| {
+                println_err!(
+                    ctx,
+                    "Verkey of DID \"{}\" is not found in the opened wallet or on the connected pool. Pass it as `verkey`",
+                    did
+                )
+            })?,
+        };
+
+        let valid =
+            Key::verify(&verkey, &message, &signature).map_err(|err| err.print(ctx, None))?;
+        let res = if valid {
+            println_succ!(
+                ctx,
+                "Signature is valid: the message has been signed by DID \"{}\"",
+                did
+            );
+            Ok(())
+        } else {
+            println_err!(ctx, "Signature is invalid for DID \"{}\"", did);
+            Err(())
+        };
+
+        trace!("execute << {:?}", res);
+        res
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::{
+        commands::{setup, setup_with_wallet, tear_down, tear_down_with_wallet},
+        did::{
+            sign::tests::{sign, MESSAGE},
+            tests::{new_did, DID_TRUSTEE, SEED_TRUSTEE, VERKEY_TRUSTEE},
+        },
+    };
+
+    mod did_verify {
+        use super::*;
+
+        fn verify(
+            ctx: &CommandContext,
+            message: &str,
+            signature: &str,
+            verkey: Option<&str>,
+        ) -> Result<(), ()> {
+            let cmd = verify_command::new();
+            let mut params = CommandParams::new();
+            params.insert("did", DID_TRUSTEE.to_string());
+            params.insert("message", message.to_string());
+            params.insert("signature", signature.to_string());
+            if let Some(verkey) = verkey {
+                params.insert("verkey", verkey.to_string());
+            }
+            cmd.execute(ctx, &params)
+        }
+
+        #[test]
+        pub fn verify_works() {
+            let ctx = setup_with_wallet();
+            new_did(&ctx, SEED_TRUSTEE);
+            let signature = sign(&ctx, MESSAGE);
+            verify(&ctx, MESSAGE, &signature, None).unwrap();
+            verify(&ctx, "other message", &signature, None).unwrap_err();
+            tear_down_with_wallet(&ctx);
+        }
+
+        #[test]
+        pub fn verify_works_for_verkey() {
+            let ctx = setup_with_wallet();
+            new_did(&ctx, SEED_TRUSTEE);
+            let signature = sign(&ctx, MESSAGE);
+            tear_down_with_wallet(&ctx);
+
+            let ctx = setup();
+            verify(&ctx, MESSAGE, &signature, None).unwrap_err();
+            verify(&ctx, MESSAGE, &signature, Some(VERKEY_TRUSTEE)).unwrap();
+            tear_down();
+        }
+    }
+}
@@ -0,0 +1,148 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::{
+    command_executor::{Command, CommandContext, CommandMetadata, CommandParams},
+    commands::explore::{
+        fetch_txns, get_ledger_size, print_txns, CONFIG_LEDGER, DOMAIN_LEDGER, POOL_LEDGER,
+    },
+    params_parser::ParamParser,
+};
+
+const DEFAULT_COUNT: i32 = 10;
+
+fn show_latest_txns(
+    ctx: &CommandContext,
+    params: &CommandParams,
+    ledger_type: i32,
+) -> Result<(), ()> {
+    let count = ParamParser::get_opt_number_param::<i32>("last", params)?.unwrap_or(DEFAULT_COUNT);
+
+    if count <= 0 {
+        println_err!(ctx, "Number of transactions to show must be positive");
+        return Err(());
+    }
+
+    let size = get_ledger_size(ctx, ledger_type)?;
+
+    let seq_nos = ((size - count + 1).max(1)..=size)
+        .rev()
+        .collect::<Vec<i32>>();
+    let txns = fetch_txns(ctx, ledger_type, &seq_nos)?
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
+
+    println_succ!(ctx, "Ledger contains {} transaction(s)", size);
+    print_txns(ctx, &txns, "There are no transactions on the ledger");
+    Ok(())
+}
+
+pub mod domain_command {
+    use super::*;
+
+    command!(CommandMetadata::build(
+        "domain",
+        "Show the latest transactions of the domain ledger."
+    )
+    .add_optional_param("last", "Number of transactions to show (10 by default)")
+    .add_example("explore domain")
+    .add_example("explore domain last=20")
+    .finalize());
+
+    fn execute(ctx: &CommandContext, params: &CommandParams) -> Result<(), ()> {
+        trace!("execute >> ctx {:?} params {:?}", ctx, params);
+        let res = show_latest_txns(ctx, params, DOMAIN_LEDGER);
+        trace!("execute << {:?}", res);
+        res
+    }
+}
+
+pub mod pool_command {
+    use super::*;
+
+    command!(
+        CommandMetadata::build("pool", "Show the latest transactions of the pool ledger.")
+            .add_optional_param("last", "Number of transactions to show (10 by default)")
+            .add_example("explore pool")
+            .add_example("explore pool last=20")
+            .finalize()
+    );
+
+    fn execute(ctx: &CommandContext, params: &CommandParams) -> Result<(), ()> {
+        trace!("execute >> ctx {:?} params {:?}", ctx, params);
+        let res = show_latest_txns(ctx, params, POOL_LEDGER);
+        trace!("execute << {:?}", res);
+        res
+    }
+}
+
+pub mod config_command {
+    use super::*;
+
+    command!(CommandMetadata::build(
+        "config",
+        "Show the latest transactions of the config ledger."
+    )
+    .add_optional_param("last", "Number of transactions to show (10 by default)")
+    .add_example("explore config")
+    .add_example("explore config last=20")
+    .finalize());
+
+    fn execute(ctx: &CommandContext, params: &CommandParams) -> Result<(), ()> {
+        trace!("execute >> ctx {:?} params {:?}", ctx, params);
+        let res = show_latest_txns(ctx, params, CONFIG_LEDGER);
+        trace!("execute << {:?}", res);
+        res
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::commands::{setup_with_wallet_and_pool, tear_down_with_wallet_and_pool};
+
+    mod explore_domain {
+        use super::*;
+
+        #[test]
+        pub fn explore_domain_works() {
+            let ctx = setup_with_wallet_and_pool();
+            {
+                let cmd = domain_command::new();
+                let mut params = CommandParams::new();
+                params.insert("last", "5".to_string());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            tear_down_with_wallet_and_pool(&ctx);
+        }
+
+        #[test]
+        pub fn explore_domain_works_for_invalid_count() {
+            let ctx = setup_with_wallet_and_pool();
+            {
+                let cmd = domain_command::new();
+                let mut params = CommandParams::new();
+                params.insert("last", "0".to_string());
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            tear_down_with_wallet_and_pool(&ctx);
+        }
+    }
+
+    mod explore_pool {
+        use super::*;
+
+        #[test]
+        pub fn explore_pool_works() {
+            let ctx = setup_with_wallet_and_pool();
+            {
+                let cmd = pool_command::new();
+                let params = CommandParams::new();
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            tear_down_with_wallet_and_pool(&ctx);
+        }
+    }
+}
@@ -0,0 +1,329 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::{
+    command_executor::{CommandContext, CommandGroup, CommandGroupMetadata},
+    commands::{
+        ledger::{get_custom_roles, handle_transaction_response},
+        note::print_txn_notes,
+    },
+    error::CliResult,
+    tools::ledger::{Ledger, LedgerHelpers, Response},
+    utils::{
+        pager,
+        table::{print_list_table, print_table},
+        time::timestamp_to_datetime,
+    },
+};
+
+use indy_vdr::ledger::constants::{
+    ATTRIB, CRED_DEF, NODE, NYM, REVOC_REG_DEF, REVOC_REG_ENTRY, SCHEMA,
+};
+use serde_json::Value as JsonValue;
+
+pub mod latest;
+pub mod search;
+pub mod txn;
+
+pub use self::{latest::*, search::*, txn::*};
+
+pub mod group {
+    use super::*;
+
+    command_group!(CommandGroupMetadata::new(
+        "explore",
+        "Read-only ledger explorer commands"
+    ));
+}
+
+// Number of GET_TXN requests sent to the pool at once
+const BATCH_SIZE: usize = 20;
+// Transactions are never read beyond this sequence number while looking for the ledger end
+const MAX_SEQ_NO_POWER: i32 = 30;
+const SUMMARY_LENGTH: usize = 60;
+
+pub const POOL_LEDGER: i32 = 0;
+pub const DOMAIN_LEDGER: i32 = 1;
+pub const CONFIG_LEDGER: i32 = 2;
+
+const TXN_HEADERS: [(&str, &str); 5] = [
+    ("seqNo", "Seq No"),
+    ("type", "Type"),
+    ("time", "Time"),
+    ("author", "Author"),
+    ("summary", "Summary"),
+];
+
+pub fn parse_ledger_type(ctx: &CommandContext, ledger: Option<&str>) -> Result<i32, ()> {
+    match ledger {
+        None | Some("domain") => Ok(DOMAIN_LEDGER),
+        Some("pool") => Ok(POOL_LEDGER),
+        Some("config") => Ok(CONFIG_LEDGER),
+        Some(ledger) => {
+            println_err!(
+                ctx,
+                "Unknown ledger \"{}\". Expected one of: domain, pool, config",
+                ledger
+            );
+            Err(())
+        }
+    }
+}
+
+pub fn ledger_name(ledger_type: i32) -> &'static str {
+    match ledger_type {
+        POOL_LEDGER => "pool",
+        CONFIG_LEDGER => "config",
+        _ => "domain",
+    }
+}
+
+/// Read transactions with GET_TXN requests sent in batches.
+/// `None` is returned for sequence numbers which are not written yet.
+pub fn fetch_txns(
+    ctx: &CommandContext,
+    ledger_type: i32,
+    seq_nos: &[i32],
+) -> Result<Vec<Option<JsonValue>>, ()> {
+    let pool = ctx.ensure_connected_pool()?;
+
+    let mut txns = Vec::with_capacity(seq_nos.len());
+    for chunk in seq_nos.chunks(BATCH_SIZE) {
+        let requests = chunk
+            .iter()
+            .map(|seq_no| Ledger::build_get_txn_request(Some(&pool), ledger_type, *seq_no))
+            .collect::<CliResult<Vec<_>>>()
+            .map_err(|err| err.print(ctx, None))?;
+
+        for response in Ledger::submit_requests(&pool, &requests) {
+            let response = response.map_err(|err| err.print(ctx, Some(&pool.name)))?;
+            let response = serde_json::from_str::<Response<JsonValue>>(&response)
+                .map_err(|err| println_err!(ctx, "Invalid data has been received: {:?}", err))?;
+            let result = handle_transaction_response(ctx, response)?;
+            txns.push(match result["data"] {
+                JsonValue::Null => None,
+                ref data => Some(data.clone()),
+            });
+        }
+    }
+    ctx.set_pool_last_activity();
+
+    Ok(txns)
+}
+
+/// Find the number of transactions written to the ledger.
+/// Powers of two are probed first and then the range containing the ledger end is split until it is found.
+pub fn get_ledger_size(ctx: &CommandContext, ledger_type: i32) -> Result<i32, ()> {
+    let probes = (0..=MAX_SEQ_NO_POWER)
+        .map(|power| 1 << power)
+        .collect::<Vec<i32>>();
+    let txns = fetch_txns(ctx, ledger_type, &probes)?;
+    let (mut low, mut high) = narrow_range(&probes, &txns, 0, i32::MAX);
+
+    while high - low > 1 {
+        let step = ((high - low) / (BATCH_SIZE as i32 + 1)).max(1);
+        let probes = (1..=BATCH_SIZE as i32)
+            .map(|i| low + i * step)
+            .filter(|seq_no| *seq_no < high)
+            .collect::<Vec<i32>>();
+        let txns = fetch_txns(ctx, ledger_type, &probes)?;
+        (low, high) = narrow_range(&probes, &txns, low, high);
+    }
+
+    Ok(low)
+}
+
+fn narrow_range(probes: &[i32], txns: &[Option<JsonValue>], low: i32, high: i32) -> (i32, i32) {
+    probes
+        .iter()
+        .zip(txns)
+        .fold((low, high), |(low, high), (seq_no, txn)| match txn {
+            Some(_) => (low.max(*seq_no), high),
+            None => (low, high.min(*seq_no)),
+        })
+}
+
+/// Check transaction type given either by name or by code.
+pub fn has_txn_type(txn: &JsonValue, txn_type: &str) -> bool {
+    let code = &txn["txn"]["type"];
+    code == txn_type || LedgerHelpers::get_txn_title(code) == txn_type.to_uppercase()
+}
+
+pub fn txn_row(ctx: &CommandContext, txn: &JsonValue) -> JsonValue {
+    json!({
+        "seqNo": txn["txnMetadata"]["seqNo"],
+        "type": LedgerHelpers::get_txn_title(&txn["txn"]["type"]),
+        "time": txn["txnMetadata"]["txnTime"].as_i64().map(|time| timestamp_to_datetime(ctx, time)),
+        "author": txn["txn"]["metadata"]["from"],
+        "endorser": txn["txn"]["metadata"]["endorser"],
+        "summary": txn_summary(ctx, txn),
+    })
+}
+
+fn txn_summary(ctx: &CommandContext, txn: &JsonValue) -> String {
+    let data = &txn["txn"]["data"];
+    let str_value = |value: &JsonValue| value.as_str().unwrap_or("-").to_string();
+
+    match txn["txn"]["type"].as_str() {
+        Some(NYM) => format!(
+            "dest={} role={} verkey={}",
+            str_value(&data["dest"]),
+            LedgerHelpers::get_role_title(&data["role"], &get_custom_roles(ctx))
+                .as_str()
+                .unwrap_or("-"),
+            str_value(&data["verkey"])
+        ),
+        Some(ATTRIB) => {
+            let value = match data["raw"].as_str() {
+                Some(raw) => serde_json::from_str::<JsonValue>(raw)
+                    .ok()
+                    .and_then(|raw| raw.as_object().map(|raw| raw.keys().cloned().collect()))
+                    .map(|keys: Vec<String>| format!("raw={}", keys.join(",")))
+                    .unwrap_or_else(|| "raw".to_string()),
+                None if data["hash"].is_string() => "hash".to_string(),
+                None => "enc".to_string(),
+            };
+            format!("dest={} {}", str_value(&data["dest"]), value)
+        }
+        Some(SCHEMA) => format!(
+            "{}:{} attrs={}",
+            str_value(&data["data"]["name"]),
+            str_value(&data["data"]["version"]),
+            data["data"]["attr_names"]
+                .as_array()
+                .map(|attrs| attrs
+                    .iter()
+                    .filter_map(|attr| attr.as_str())
+                    .collect::<Vec<&str>>()
+                    .join(","))
+                .unwrap_or_default()
+        ),
+        Some(CRED_DEF) => format!("tag={} schema={}", str_value(&data["tag"]), data["ref"]),
+        Some(NODE) => format!(
+            "alias={} dest={}",
+            str_value(&data["data"]["alias"]),
+            str_value(&data["dest"])
+        ),
+        Some(REVOC_REG_DEF) => format!(
+            "tag={} credDefId={}",
+            str_value(&data["tag"]),
+            str_value(&data["credDefId"])
+        ),
+        Some(REVOC_REG_ENTRY) => format!("revocRegDefId={}", str_value(&data["revocRegDefId"])),
+        _ => {
+            let data = data.to_string();
+            match data.char_indices().nth(SUMMARY_LENGTH) {
+                Some((end, _)) => format!("{}...", &data[..end]),
+                None => data,
+            }
+        }
+    }
+}
+
+/// Explorer commands referencing the transactions related to the given one.
+pub fn txn_references(txn: &JsonValue) -> Vec<String> {
+    let data = &txn["txn"]["data"];
+    let mut references = Vec::new();
+
+    for did in [
+        &txn["txn"]["metadata"]["from"],
+        &txn["txn"]["metadata"]["endorser"],
+    ] {
+        if let Some(did) = did.as_str() {
+            references.push(format!("explore search type=NYM dest={}", did));
+        }
+    }
+
+    match txn["txn"]["type"].as_str() {
+        Some(ATTRIB) => {
+            if let Some(dest) = data["dest"].as_str() {
+                references.push(format!("explore search type=NYM dest={}", dest));
+            }
+        }
+        Some(SCHEMA) => {
+            if let Some(seq_no) = txn["txnMetadata"]["seqNo"].as_u64() {
+                references.push(format!("explore search type=CRED_DEF ref={}", seq_no));
+            }
+        }
+        Some(CRED_DEF) => {
+            if let Some(schema_seq_no) = data["ref"].as_u64() {
+                references.push(format!("explore txn {}", schema_seq_no));
+            }
+        }
+        Some(REVOC_REG_DEF) => {
+            // Credential definition id: <did>:3:CL:<schema seq no>:<tag>
+            if let Some(schema_seq_no) = data["credDefId"]
+                .as_str()
+                .and_then(|id| id.split(':').nth(3))
+                .and_then(|seq_no| seq_no.parse::<u64>().ok())
+            {
+                references.push(format!("explore txn {}", schema_seq_no));
+            }
+        }
+        _ => {}
+    }
+
+    references.dedup();
+    references
+}
+
+pub fn print_txn(ctx: &CommandContext, ledger_type: i32, txn: &JsonValue) {
+    let mut headers = TXN_HEADERS.to_vec();
+    headers.insert(4, ("endorser", "Endorser"));
+    print_table(ctx, &txn_row(ctx, txn), &headers);
+
+    let references = txn_references(txn);
+    if !references.is_empty() {
+        println!("Related:");
+        for reference in references {
+            println!("  -> {}", reference);
+        }
+    }
+
+    if let Some(seq_no) = txn["txnMetadata"]["seqNo"].as_i64() {
+        print_txn_notes(ctx, ledger_type, seq_no as i32);
+    }
+
+    println!("Transaction:");
+    pager::print(&serde_json::to_string_pretty(txn).unwrap_or_default());
+}
+
+pub fn print_txns(ctx: &CommandContext, txns: &[JsonValue], empty_msg: &str) {
+    let rows = txns
+        .iter()
+        .map(|txn| txn_row(ctx, txn))
+        .collect::<Vec<JsonValue>>();
+    print_list_table(ctx, &rows, &TXN_HEADERS, empty_msg);
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    #[test]
+    pub fn narrow_range_works() {
+        let probes = [1, 2, 4, 8];
+        let txns = [Some(json!({})), Some(json!({})), None, None];
+        assert_eq!(narrow_range(&probes, &txns, 0, i32::MAX), (2, 4));
+    }
+
+    #[test]
+    pub fn txn_references_works_for_cred_def() {
+        let txn = json!({
+            "txn": {
+                "type": CRED_DEF,
+                "data": {"ref": 10, "tag": "tag"},
+                "metadata": {"from": "V4SGRU86Z58d6TV7PBUe6f"}
+            },
+            "txnMetadata": {"seqNo": 11}
+        });
+        assert_eq!(
+            txn_references(&txn),
+            vec![
+                "explore search type=NYM dest=V4SGRU86Z58d6TV7PBUe6f".to_string(),
+                "explore txn 10".to_string()
+            ]
+        );
+    }
+}
@@ -0,0 +1,134 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::{
+    command_executor::{Command, CommandContext, CommandMetadata, CommandParams},
+    commands::explore::{fetch_txns, get_ledger_size, has_txn_type, parse_ledger_type, print_txns},
+    params_parser::ParamParser,
+};
+
+use serde_json::Value as JsonValue;
+
+pub mod search_command {
+    use super::*;
+
+    const DEFAULT_DEPTH: i32 = 100;
+
+    command!(CommandMetadata::build(
+        "search",
+        "Search the latest ledger transactions matching all the given filters."
+    )
+    .add_optional_param(
+        "type",
+        "Transaction type name or code (e.g. NYM, SCHEMA, CRED_DEF, 101)"
+    )
+    .add_optional_param(
+        "name",
+        "Schema name, credential definition tag or node alias"
+    )
+    .add_optional_param("dest", "Target DID of NYM, ATTRIB and NODE transactions")
+    .add_optional_param("from", "Author DID of the transaction")
+    .add_optional_param(
+        "ref",
+        "Schema sequence number referenced by credential definition"
+    )
+    .add_optional_param(
+        "depth",
+        "Number of the latest transactions to look through (100 by default)"
+    )
+    .add_optional_param("ledger", "Ledger to read: domain (default), pool or config")
+    .add_example("explore search type=SCHEMA name=gvt")
+    .add_example("explore search type=NYM dest=VsKV7grR1BUE29mG2Fm2kX")
+    .add_example("explore search type=CRED_DEF ref=10 depth=1000")
+    .finalize());
+
+    fn execute(ctx: &CommandContext, params: &CommandParams) -> Result<(), ()> {
+        trace!("execute >> ctx {:?} params {:?}", ctx, params);
+
+        let txn_type = ParamParser::get_opt_str_param(ctx, "type", params)?;
+        let name = ParamParser::get_opt_str_param(ctx, "name", params)?;
+        let dest = ParamParser::get_opt_str_param(ctx, "dest", params)?;
+        let from = ParamParser::get_opt_str_param(ctx, "from", params)?;
+        let reference = ParamParser::get_opt_number_param::<u64>("ref", params)?;
+        let depth =
+            ParamParser::get_opt_number_param::<i32>("depth", params)?.unwrap_or(DEFAULT_DEPTH);
+        let ledger = ParamParser::get_opt_str_param(ctx, "ledger", params)?;
+
+        let ledger_type = parse_ledger_type(ctx, ledger)?;
+
+        if depth <= 0 {
+            println_err!(ctx, "Search depth must be positive");
+            return Err(());
+        }
+
+        let size = get_ledger_size(ctx, ledger_type)?;
+
+        let seq_nos = ((size - depth + 1).max(1)..=size)
+            .rev()
+            .collect::<Vec<i32>>();
+        let txns = fetch_txns(ctx, ledger_type, &seq_nos)?
+            .into_iter()
+            .flatten()
+            .filter(|txn| {
+                txn_type.map_or(true, |txn_type| has_txn_type(txn, txn_type))
+                    && name.map_or(true, |name| matches_name(txn, name))
+                    && dest.map_or(true, |dest| txn["txn"]["data"]["dest"] == dest)
+                    && from.map_or(true, |from| txn["txn"]["metadata"]["from"] == from)
+                    && reference.map_or(true, |reference| txn["txn"]["data"]["ref"] == reference)
+            })
+            .collect::<Vec<JsonValue>>();
+
+        println_succ!(
+            ctx,
+            "{} transaction(s) found among the latest {}",
+            txns.len(),
+            seq_nos.len()
+        );
+        print_txns(ctx, &txns, "");
+
+        trace!("execute <<");
+        Ok(())
+    }
+
+    fn matches_name(txn: &JsonValue, name: &str) -> bool {
+        let data = &txn["txn"]["data"];
+        data["data"]["name"] == name || data["data"]["alias"] == name || data["tag"] == name
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::commands::{setup_with_wallet_and_pool, tear_down_with_wallet_and_pool};
+
+    mod explore_search {
+        use super::*;
+        use crate::commands::did::tests::DID_TRUSTEE;
+
+        #[test]
+        pub fn explore_search_works() {
+            let ctx = setup_with_wallet_and_pool();
+            {
+                let cmd = search_command::new();
+                let mut params = CommandParams::new();
+                params.insert("type", "NYM".to_string());
+                params.insert("dest", DID_TRUSTEE.to_string());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            tear_down_with_wallet_and_pool(&ctx);
+        }
+
+        #[test]
+        pub fn explore_search_works_for_unknown_ledger() {
+            let ctx = setup_with_wallet_and_pool();
+            {
+                let cmd = search_command::new();
+                let mut params = CommandParams::new();
+                params.insert("ledger", "audit".to_string());
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            tear_down_with_wallet_and_pool(&ctx);
+        }
+    }
+}
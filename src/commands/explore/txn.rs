@@ -0,0 +1,96 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::{
+    command_executor::{Command, CommandContext, CommandMetadata, CommandParams},
+    commands::explore::{fetch_txns, parse_ledger_type, print_txn},
+    params_parser::ParamParser,
+};
+
+pub mod txn_command {
+    use super::*;
+
+    command!(CommandMetadata::build(
+        "txn",
+        "Show ledger transaction with its summary and references to related transactions."
+    )
+    .add_main_param("seq_no", "Sequence number of the transaction")
+    .add_optional_param("ledger", "Ledger to read: domain (default), pool or config")
+    .add_example("explore txn 10")
+    .add_example("explore txn 1 ledger=pool")
+    .finalize());
+
+    fn execute(ctx: &CommandContext, params: &CommandParams) -> Result<(), ()> {
+        trace!("execute >> ctx {:?} params {:?}", ctx, params);
+
+        let seq_no = ParamParser::get_number_param::<i32>("seq_no", params)?;
+        let ledger = ParamParser::get_opt_str_param(ctx, "ledger", params)?;
+
+        let ledger_type = parse_ledger_type(ctx, ledger)?;
+
+        let txn = fetch_txns(ctx, ledger_type, &[seq_no])?
+            .pop()
+            .flatten()
+            .ok_or_else(|| {
+                println_err!(
+                    ctx,
+                    "Transaction with sequence number {} is not found on {} ledger",
+                    seq_no,
+                    ledger.unwrap_or("domain")
+                )
+            })?;
+
+        print_txn(ctx, ledger_type, &txn);
+
+        trace!("execute <<");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::commands::{setup_with_wallet_and_pool, tear_down_with_wallet_and_pool};
+
+    mod explore_txn {
+        use super::*;
+
+        #[test]
+        pub fn explore_txn_works() {
+            let ctx = setup_with_wallet_and_pool();
+            {
+                let cmd = txn_command::new();
+                let mut params = CommandParams::new();
+                params.insert("seq_no", "1".to_string());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            tear_down_with_wallet_and_pool(&ctx);
+        }
+
+        #[test]
+        pub fn explore_txn_works_for_pool_ledger() {
+            let ctx = setup_with_wallet_and_pool();
+            {
+                let cmd = txn_command::new();
+                let mut params = CommandParams::new();
+                params.insert("seq_no", "1".to_string());
+                params.insert("ledger", "pool".to_string());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            tear_down_with_wallet_and_pool(&ctx);
+        }
+
+        #[test]
+        pub fn explore_txn_works_for_not_written() {
+            let ctx = setup_with_wallet_and_pool();
+            {
+                let cmd = txn_command::new();
+                let mut params = CommandParams::new();
+                params.insert("seq_no", "100000000".to_string());
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            tear_down_with_wallet_and_pool(&ctx);
+        }
+    }
+}
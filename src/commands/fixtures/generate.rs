@@ -0,0 +1,348 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::{
+    command_executor::{Command, CommandContext, CommandMetadata, CommandParams},
+    commands::common::selftest::{
+        reply, temporary_wallet, transaction_author_agreement, write, Acceptance,
+    },
+    error::{CliError, CliResult},
+    params_parser::ParamParser,
+    tools::{did::Did, ledger::Ledger, pool::Pool, wallet::Wallet},
+    utils::{file::write_file, table::print_list_table},
+};
+
+use indy_utils::did::DidValue;
+use indy_vdr::{
+    ledger::{
+        identifiers::SchemaId,
+        requests::schema::{AttributeNames, Schema, SchemaV1},
+    },
+    pool::PreparedRequest,
+};
+use serde_json::Value as JsonValue;
+use std::path::Path;
+
+const MANIFEST_FILE: &str = "manifest.json";
+const DEFAULT_DIDS: usize = 10;
+const DEFAULT_SCHEMAS: usize = 3;
+const DEFAULT_SEED_PREFIX: &str = "fixture";
+const SEED_LENGTH: usize = 32;
+const DID_ROLE: &str = "ENDORSER";
+const SCHEMA_VERSION: &str = "1.0";
+const SCHEMA_ATTRIBUTES: [&str; 3] = ["name", "age", "email"];
+
+const STATUS_WRITTEN: &str = "Written";
+const STATUS_EXISTING: &str = "Already on ledger";
+
+pub mod generate_command {
+    use super::*;
+
+    command!(CommandMetadata::build(
+        "generate",
+        "Write a reproducible data set to the connected pool and a manifest of the created objects. \
+        DIDs are derived from deterministic seeds and written as Endorsers by the trustee, \
+        schemas are written by the created DIDs in turn. \
+        The keys are kept in a temporary wallet removed afterwards: the opened wallet is not affected. \
+        Objects already written by a previous run are reused, so the command can be repeated against the same pool."
+    )
+    .add_required_param("out", "Directory to write `manifest.json` to")
+    .add_optional_param("dids", "Number of DIDs to create (10 by default)")
+    .add_optional_param("schemas", "Number of schemas to create (3 by default)")
+    .add_optional_param(
+        "seed",
+        "Prefix of the DID seeds: the seed of the N-th DID is the prefix followed by N, \
+        left-padded with zeros to 32 characters (\"fixture\" by default)"
+    )
+    .add_required_deferred_param("trustee_seed", "Seed of a Trustee DID on the pool")
+    .add_example("fixtures generate out=./fixtures dids=10 schemas=3 trustee_seed")
+    .add_example("fixtures generate out=./fixtures dids=2 schemas=0 seed=acme trustee_seed")
+    .finalize());
+
+    fn execute(ctx: &CommandContext, params: &CommandParams) -> Result<(), ()> {
+        trace!("execute >> ctx {:?} params {:?}", ctx, secret!(params));
+
+        let out = ParamParser::get_str_param(ctx, "out", params)?;
+        let dids =
+            ParamParser::get_opt_number_param::<usize>("dids", params)?.unwrap_or(DEFAULT_DIDS);
+        let schemas = ParamParser::get_opt_number_param::<usize>("schemas", params)?
+            .unwrap_or(DEFAULT_SCHEMAS);
+        let seed_prefix =
+            ParamParser::get_opt_str_param(ctx, "seed", params)?.unwrap_or(DEFAULT_SEED_PREFIX);
+        let trustee_seed = ParamParser::get_str_param(ctx, "trustee_seed", params)?;
+
+        let seeds = fixture_seeds(seed_prefix, dids).map_err(|err| err.print(ctx, None))?;
+
+        let pool = ctx.ensure_connected_pool()?;
+
+        let (config, credentials) =
+            temporary_wallet("fixtures").map_err(|err| println_err!(ctx, "{}", err))?;
+        let wallet = Wallet::create(&config, &credentials, ctx.get_crypto_policy())
+            .and_then(|_| Wallet::open(&config, &credentials, ctx.get_crypto_policy()))
+            .map_err(|err| err.print(ctx, Some(&config.id)))?;
+
+        let mut rows = Vec::new();
+        let res = generate(
+            ctx,
+            &pool,
+            &wallet,
+            trustee_seed,
+            &seeds,
+            schemas,
+            &mut rows,
+        );
+
+        wallet.close().ok();
+        Wallet::delete(&config, &credentials).ok();
+
+        if !rows.is_empty() {
+            print_list_table(
+                ctx,
+                &rows,
+                &[("type", "Type"), ("id", "Id"), ("status", "Status")],
+                "",
+            );
+        }
+
+        let manifest = res.map_err(|err| println_err!(ctx, "{}", err))?;
+
+        let path = Path::new(out).join(MANIFEST_FILE);
+        let content = serde_json::to_string_pretty(&manifest)
+            .map_err(|err| println_err!(ctx, "Invalid manifest: {}", err))?;
+        write_file(&path, &content).map_err(|err| println_err!(ctx, "{}", err))?;
+
+        println_succ!(
+            ctx,
+            "Fixtures of {} DID(s) and {} schema(s) are on pool \"{}\". Manifest has been written to \"{}\"",
+            dids,
+            schemas,
+            pool.name,
+            path.display()
+        );
+        println_warn!(
+            ctx,
+            "The manifest contains the DID seeds. Use the data set on test networks only."
+        );
+
+        trace!("execute <<");
+        Ok(())
+    }
+}
+
+// Seeds are ASCII so that the manifest can be passed to agents taking the seed as a string
+fn fixture_seeds(prefix: &str, count: usize) -> CliResult<Vec<String>> {
+    if !prefix.is_ascii() {
+        return Err(CliError::InvalidInput(format!(
+            "Seed prefix \"{}\" must contain ASCII characters only",
+            prefix
+        )));
+    }
+    (1..=count)
+        .map(|index| {
+            let seed = format!("{}{}", prefix, index);
+            if seed.len() > SEED_LENGTH {
+                return Err(CliError::InvalidInput(format!(
+                    "Seed prefix \"{}\" is too long to build {} DID seeds of {} characters",
+                    prefix, count, SEED_LENGTH
+                )));
+            }
+            Ok(format!("{:0>width$}", seed, width = SEED_LENGTH))
+        })
+        .collect()
+}
+
+fn generate(
+    ctx: &CommandContext,
+    pool: &Pool,
+    wallet: &Wallet,
+    trustee_seed: &str,
+    seeds: &[String],
+    schemas: usize,
+    rows: &mut Vec<JsonValue>,
+) -> Result<JsonValue, String> {
+    let (acceptance, _) = transaction_author_agreement(ctx, pool, "fixtures")?;
+
+    let (trustee, _) = Did::create(wallet, None, Some(trustee_seed), None, None)
+        .map_err(|err| err.message(None))?;
+    let trustee = DidValue(trustee);
+
+    let mut authors = Vec::new();
+    let mut dids = Vec::new();
+    for seed in seeds {
+        let (did, verkey) =
+            Did::create(wallet, None, Some(seed), None, None).map_err(|err| err.message(None))?;
+        let did = DidValue(did);
+        let status = write_nym(pool, wallet, &trustee, &did, &verkey, acceptance.as_ref())?;
+        rows.push(json!({ "type": "NYM", "id": did.0, "status": status }));
+        dids.push(json!({
+            "did": did.0,
+            "verkey": verkey,
+            "seed": seed,
+            "role": DID_ROLE,
+        }));
+        authors.push(did);
+    }
+
+    // the trustee is the author when no DIDs are requested
+    if authors.is_empty() {
+        authors.push(trustee.clone());
+    }
+
+    let mut written_schemas = Vec::new();
+    for index in 0..schemas {
+        let author = &authors[index % authors.len()];
+        let name = format!("fixture_schema_{}", index + 1);
+        let id = SchemaId::new(author, &name, SCHEMA_VERSION);
+        let (seq_no, status) = write_schema(pool, wallet, author, &id, &name, acceptance.as_ref())?;
+        rows.push(json!({ "type": "SCHEMA", "id": id.0, "status": status }));
+        written_schemas.push(json!({
+            "id": id.0,
+            "seqNo": seq_no,
+            "name": name,
+            "version": SCHEMA_VERSION,
+            "attrNames": SCHEMA_ATTRIBUTES,
+            "issuerId": author.0,
+        }));
+    }
+
+    Ok(json!({
+        "pool": pool.name,
+        "trustee": trustee.0,
+        "dids": dids,
+        "schemas": written_schemas,
+    }))
+}
+
+fn write_nym(
+    pool: &Pool,
+    wallet: &Wallet,
+    trustee: &DidValue,
+    did: &DidValue,
+    verkey: &str,
+    acceptance: Option<&Acceptance>,
+) -> Result<&'static str, String> {
+    let request =
+        Ledger::build_get_nym_request(Some(pool), None, did).map_err(|err| err.message(None))?;
+    let result = lookup(pool, &request)?;
+    if let Some(data) = result["data"]
+        .as_str()
+        .and_then(|data| serde_json::from_str::<JsonValue>(data).ok())
+    {
+        return match data["verkey"].as_str() {
+            Some(current) if current == verkey => Ok(STATUS_EXISTING),
+            _ => Err(format!(
+                "NYM {} exists on the ledger with another verkey",
+                did
+            )),
+        };
+    }
+
+    let request =
+        Ledger::build_nym_request(Some(pool), trustee, did, Some(verkey), None, Some(DID_ROLE))
+            .map_err(|err| err.message(None))?;
+    write(pool, wallet, trustee, request, acceptance)?;
+    Ok(STATUS_WRITTEN)
+}
+
+fn write_schema(
+    pool: &Pool,
+    wallet: &Wallet,
+    author: &DidValue,
+    id: &SchemaId,
+    name: &str,
+    acceptance: Option<&Acceptance>,
+) -> Result<(i64, &'static str), String> {
+    let request =
+        Ledger::build_get_schema_request(Some(pool), None, id).map_err(|err| err.message(None))?;
+    if let Some(seq_no) = lookup(pool, &request)?["seqNo"].as_i64() {
+        return Ok((seq_no, STATUS_EXISTING));
+    }
+
+    let schema = Schema::SchemaV1(SchemaV1 {
+        id: id.clone(),
+        name: name.to_string(),
+        version: SCHEMA_VERSION.to_string(),
+        attr_names: AttributeNames::from(SCHEMA_ATTRIBUTES.as_slice()),
+        seq_no: None,
+    });
+    let request = Ledger::build_schema_request(Some(pool), author, schema)
+        .map_err(|err| err.message(None))?;
+    let result = write(pool, wallet, author, request, acceptance)?;
+    let seq_no = result["txnMetadata"]["seqNo"]
+        .as_i64()
+        .ok_or_else(|| format!("No sequence number of schema {} in the reply", id))?;
+    Ok((seq_no, STATUS_WRITTEN))
+}
+
+fn lookup(pool: &Pool, request: &PreparedRequest) -> Result<JsonValue, String> {
+    let response =
+        Ledger::submit_request(pool, request).map_err(|err| err.message(Some(&pool.name)))?;
+    reply(&response)
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::{
+        commands::{setup, tear_down},
+        did::tests::SEED_TRUSTEE,
+        pool::tests::{create_and_connect_pool, disconnect_and_delete_pool},
+        utils::{environment::EnvironmentUtils, file::read_file},
+    };
+    use std::fs;
+
+    mod generate {
+        use super::*;
+
+        fn generate_fixtures(ctx: &CommandContext, out: &Path) -> JsonValue {
+            let cmd = generate_command::new();
+            let mut params = CommandParams::new();
+            params.insert("out", out.display().to_string());
+            params.insert("dids", "2".to_string());
+            params.insert("schemas", "3".to_string());
+            params.insert("seed", "fixturestest".to_string());
+            params.insert("trustee_seed", SEED_TRUSTEE.to_string());
+            cmd.execute(ctx, &params).unwrap();
+            serde_json::from_str(&read_file(out.join(MANIFEST_FILE)).unwrap()).unwrap()
+        }
+
+        #[test]
+        pub fn generate_works() {
+            let ctx = setup();
+            create_and_connect_pool(&ctx);
+            let out = EnvironmentUtils::tmp_file_path("fixtures");
+            let _ = fs::remove_dir_all(&out);
+
+            let manifest = generate_fixtures(&ctx, &out);
+            let dids = manifest["dids"].as_array().unwrap();
+            let schemas = manifest["schemas"].as_array().unwrap();
+            assert_eq!(2, dids.len());
+            assert_eq!(3, schemas.len());
+            assert_eq!("0000000000000000000fixturestest1", dids[0]["seed"]);
+            assert_eq!(dids[0]["did"], schemas[2]["issuerId"]);
+            assert!(ctx.get_opened_wallet().is_none());
+
+            // the same data set is reused on the next run
+            assert_eq!(manifest, generate_fixtures(&ctx, &out));
+
+            fs::remove_dir_all(&out).unwrap();
+            disconnect_and_delete_pool(&ctx);
+            tear_down();
+        }
+
+        #[test]
+        pub fn fixture_seeds_works() {
+            let seeds = fixture_seeds("fixture", 2).unwrap();
+            assert_eq!(
+                vec![
+                    "000000000000000000000000fixture1".to_string(),
+                    "000000000000000000000000fixture2".to_string()
+                ],
+                seeds
+            );
+            fixture_seeds("a_very_long_prefix_of_the_seeds_", 1).unwrap_err();
+            fixture_seeds("fixtüre", 1).unwrap_err();
+        }
+    }
+}
@@ -0,0 +1,18 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::command_executor::{CommandGroup, CommandGroupMetadata};
+
+pub mod generate;
+
+pub use self::generate::*;
+
+pub mod group {
+    use super::*;
+
+    command_group!(CommandGroupMetadata::new(
+        "fixtures",
+        "Reproducible ledger data sets for integration tests"
+    ));
+}
@@ -16,14 +16,16 @@ use super::common::{
 
 pub mod attrib_command {
     use super::*;
+    use crate::commands::pool::validate_raw_attrib;
 
     command!(CommandMetadata::build("attrib", r#"Send Attribute transaction to the Ledger for exists NYM."#)
                 .add_required_param("did",  "DID of identity presented in Ledger")
                 .add_optional_param("hash", "Hash of attribute data")
-                .add_optional_param("raw", "JSON representation of attribute data")
+                .add_optional_param("raw", "JSON representation of attribute data. It is checked against the attribute schemas registered for the connected pool (see `pool set-attrib-schema`)")
                 .add_optional_param("enc", "Encrypted attribute data")
                 .add_optional_param("sign","Sign the request (True by default)")
                 .add_optional_param("send","Send the request to the Ledger (True by default). If false then created request will be printed and stored into CLI context.")
+                .add_optional_param("queue","Spool the request to the local queue if the pool cannot be reached (False by default). Queued requests are sent by `queue flush` command.")
                 .add_optional_param("endorser","DID of the Endorser that will submit the transaction to the ledger later. \
                     Note that specifying of this parameter implies send=false so the transaction will be prepared to pass to the endorser instead of sending to the ledger.\
                     The created request will be printed and stored into CLI context.")
@@ -41,10 +43,14 @@ pub mod attrib_command {
         let pool = ctx.get_connected_pool();
         let submitter_did = ctx.ensure_active_did()?;
 
-        let target_did = ParamParser::get_did_param("did", params)?;
-        let hash = ParamParser::get_opt_str_param("hash", params)?;
-        let raw = ParamParser::get_opt_object_param("raw", params)?;
-        let enc = ParamParser::get_opt_str_param("enc", params)?;
+        let target_did = ParamParser::get_did_param(ctx, "did", params)?;
+        let hash = ParamParser::get_opt_str_param(ctx, "hash", params)?;
+        let raw = ParamParser::get_opt_object_param(ctx, "raw", params)?;
+        let enc = ParamParser::get_opt_str_param(ctx, "enc", params)?;
+
+        if let (Some(raw), Some(pool)) = (raw.as_ref(), pool.as_ref()) {
+            validate_raw_attrib(ctx, &pool.name, raw)?;
+        }
 
         let mut request = Ledger::build_attrib_request(
             pool.as_deref(),
@@ -54,7 +60,7 @@ pub mod attrib_command {
             raw.as_ref(),
             enc,
         )
-        .map_err(|err| println_err!("{}", err.message(None)))?;
+        .map_err(|err| err.print(ctx, None))?;
 
         set_author_agreement(ctx, &mut request)?;
 
@@ -69,8 +75,9 @@ pub mod attrib_command {
             ("enc", "Encrypted value")
         };
 
-        handle_transaction_response(response).map(|result| {
+        handle_transaction_response(ctx, response).map(|result| {
             print_transaction_response(
+                ctx,
                 result,
                 "Attrib request has been sent to Ledger.",
                 None,
@@ -84,18 +91,262 @@ pub mod attrib_command {
     }
 }
 
+pub mod register_agent_command {
+    use super::*;
+    use crate::commands::pool::validate_raw_attrib;
+
+    command!(CommandMetadata::build("register-agent", r#"Send ATTRIB transaction publishing the DIDComm service endpoint of an agent or mediator in the structure used by Aries agents."#)
+                .add_required_param("did",  "DID of identity presented in Ledger")
+                .add_required_param("endpoint", "Service endpoint URL (http, https, ws or wss)")
+                .add_optional_param("routing_keys", "Routing keys of the mediator split by comma (verkeys or did:key)")
+                .add_optional_param("types", "Endpoint types split by comma. One of: endpoint, did-communication, DIDComm (all of them by default)")
+                .add_optional_param("sign","Sign the request (True by default)")
+                .add_optional_param("send","Send the request to the Ledger (True by default). If false then created request will be printed and stored into CLI context.")
+                .add_optional_param("queue","Spool the request to the local queue if the pool cannot be reached (False by default). Queued requests are sent by `queue flush` command.")
+                .add_optional_param("endorser","DID of the Endorser that will submit the transaction to the ledger later. \
+                    Note that specifying of this parameter implies send=false so the transaction will be prepared to pass to the endorser instead of sending to the ledger.\
+                    The created request will be printed and stored into CLI context.")
+                .add_example("ledger register-agent did=VsKV7grR1BUE29mG2Fm2kX endpoint=https://agent.example.com")
+                .add_example("ledger register-agent did=VsKV7grR1BUE29mG2Fm2kX endpoint=https://mediator.example.com routing_keys=3Dn1SJNPaCXcvvJvSbsFWP2xaCjMom3can8CQNhWrTRx types=DIDComm")
+                .finalize()
+    );
+
+    fn execute(ctx: &CommandContext, params: &CommandParams) -> Result<(), ()> {
+        trace!("execute >> ctx {:?} params {:?}", ctx, params);
+
+        let wallet = ctx.ensure_opened_wallet()?;
+        let pool = ctx.get_connected_pool();
+        let submitter_did = ctx.ensure_active_did()?;
+
+        let target_did = ParamParser::get_did_param(ctx, "did", params)?;
+        let endpoint = ParamParser::get_str_param(ctx, "endpoint", params)?;
+        let routing_keys =
+            ParamParser::get_opt_str_array_param("routing_keys", params)?.unwrap_or_default();
+        let types = ParamParser::get_opt_str_array_param("types", params)?
+            .unwrap_or_else(|| ENDPOINT_TYPES.to_vec());
+
+        let raw = agent_endpoint(endpoint, &routing_keys, &types)
+            .map_err(|err| println_err!(ctx, "{}", err))?;
+
+        if let Some(pool) = pool.as_ref() {
+            validate_raw_attrib(ctx, &pool.name, &raw)?;
+        }
+
+        let mut request = Ledger::build_attrib_request(
+            pool.as_deref(),
+            &submitter_did,
+            &target_did,
+            None,
+            Some(&raw),
+            None,
+        )
+        .map_err(|err| err.print(ctx, None))?;
+
+        set_author_agreement(ctx, &mut request)?;
+
+        let (_, response): (String, Response<JsonValue>) =
+            send_write_request!(ctx, params, &mut request, &wallet, &submitter_did);
+
+        handle_transaction_response(ctx, response).map(|result| {
+            print_transaction_response(
+                ctx,
+                result,
+                "Agent endpoint has been sent to Ledger.",
+                None,
+                &[("raw", "Raw value")],
+                true,
+            )
+        })?;
+
+        trace!("execute <<");
+        Ok(())
+    }
+}
+
+pub mod clear_attrib_command {
+    use super::*;
+    use crate::{
+        command_executor::wait_for_user_reply, ledger::submit_request, utils::term::print_prompt,
+    };
+    use indy_utils::did::DidValue;
+
+    command!(CommandMetadata::build("clear-attrib", r#"Send ATTRIB transaction setting the raw attribute to null. Attributes cannot be deleted from the ledger, a null value is read as not set."#)
+                .add_required_param("did",  "DID of identity presented in Ledger")
+                .add_required_param("name", "Name of the raw attribute to clear")
+                .add_optional_param("sign","Sign the request (True by default)")
+                .add_optional_param("send","Send the request to the Ledger (True by default). If false then created request will be printed and stored into CLI context.")
+                .add_optional_param("queue","Spool the request to the local queue if the pool cannot be reached (False by default). Queued requests are sent by `queue flush` command.")
+                .add_optional_param("endorser","DID of the Endorser that will submit the transaction to the ledger later. \
+                    Note that specifying of this parameter implies send=false so the transaction will be prepared to pass to the endorser instead of sending to the ledger.\
+                    The created request will be printed and stored into CLI context.")
+                .add_example("ledger clear-attrib did=VsKV7grR1BUE29mG2Fm2kX name=endpoint")
+                .finalize()
+    );
+
+    fn execute(ctx: &CommandContext, params: &CommandParams) -> Result<(), ()> {
+        trace!("execute >> ctx {:?} params {:?}", ctx, params);
+
+        let wallet = ctx.ensure_opened_wallet()?;
+        let pool = ctx.get_connected_pool();
+        let submitter_did = ctx.ensure_active_did()?;
+
+        let target_did = ParamParser::get_did_param(ctx, "did", params)?;
+        let name = ParamParser::get_str_param(ctx, "name", params)?;
+
+        if pool.is_some() {
+            match get_current_value(ctx, &target_did, name)? {
+                Some(JsonValue::Null) | None => {
+                    println_warn!(
+                        ctx,
+                        "Attribute \"{}\" of DID \"{}\" is not set on the ledger",
+                        name,
+                        target_did
+                    );
+                    return Ok(());
+                }
+                Some(value) => {
+                    println!("Current value of attribute \"{}\": {}", name, value);
+                }
+            }
+        } else {
+            println_warn!(
+                ctx,
+                "There is no connected pool: the current value of the attribute cannot be shown"
+            );
+        }
+        print_prompt(&format!(
+            "Would you like to clear attribute \"{}\" of DID \"{}\"? (y/n)",
+            name, target_did
+        ));
+        if !wait_for_user_reply(ctx) {
+            println!("The transaction has not been sent.");
+            return Ok(());
+        }
+
+        // The blank value is not checked against the attribute schemas
+        let raw = json!({ name: JsonValue::Null });
+        let mut request = Ledger::build_attrib_request(
+            pool.as_deref(),
+            &submitter_did,
+            &target_did,
+            None,
+            Some(&raw),
+            None,
+        )
+        .map_err(|err| err.print(ctx, None))?;
+
+        set_author_agreement(ctx, &mut request)?;
+
+        let (_, response): (String, Response<JsonValue>) =
+            send_write_request!(ctx, params, &mut request, &wallet, &submitter_did);
+
+        handle_transaction_response(ctx, response).map(|result| {
+            print_transaction_response(
+                ctx,
+                result,
+                "Attribute has been cleared on Ledger.",
+                None,
+                &[("raw", "Raw value")],
+                true,
+            )
+        })?;
+
+        trace!("execute <<");
+        Ok(())
+    }
+
+    // Value of the raw attribute, `None` if it has never been written
+    fn get_current_value(
+        ctx: &CommandContext,
+        did: &DidValue,
+        name: &str,
+    ) -> Result<Option<JsonValue>, ()> {
+        let pool = ctx.ensure_connected_pool()?;
+        let request =
+            Ledger::build_get_attrib_request(Some(&pool), None, did, Some(name), None, None)
+                .map_err(|err| err.print(ctx, None))?;
+        let response = submit_request(ctx, &request)?;
+        let response = serde_json::from_str::<Response<JsonValue>>(&response)
+            .map_err(|err| println_err!(ctx, "Invalid data has been received: {:?}", err))?;
+        let result = handle_transaction_response(ctx, response)?;
+        match result["data"].as_str() {
+            Some(data) => serde_json::from_str::<JsonValue>(data)
+                .map(|mut data| Some(data[name].take()))
+                .map_err(|_| println_err!(ctx, "Wrong data has been received")),
+            None => Ok(None),
+        }
+    }
+}
+
+const ENDPOINT_TYPES: [&str; 3] = ["endpoint", "did-communication", "DIDComm"];
+const ENDPOINT_SCHEMES: [&str; 4] = ["http://", "https://", "ws://", "wss://"];
+
+/// Raw value of the `endpoint` ATTRIB as read by Aries agents resolving `did:sov` DIDs.
+pub fn agent_endpoint(
+    endpoint: &str,
+    routing_keys: &[&str],
+    types: &[&str],
+) -> Result<JsonValue, String> {
+    if !ENDPOINT_SCHEMES
+        .iter()
+        .any(|scheme| endpoint.starts_with(scheme) && endpoint.len() > scheme.len())
+    {
+        return Err(format!(
+            "Invalid endpoint \"{}\": URL with one of {} schemes expected",
+            endpoint,
+            ENDPOINT_SCHEMES.join(", ")
+        ));
+    }
+    for key in routing_keys {
+        let is_verkey = indy_utils::base58::decode(key)
+            .map(|key| key.len() == 32)
+            .unwrap_or(false);
+        if !is_verkey && !key.starts_with("did:key:") {
+            return Err(format!(
+                "Invalid routing key \"{}\": base58 verkey or did:key expected",
+                key
+            ));
+        }
+    }
+    if let Some(endpoint_type) = types
+        .iter()
+        .find(|endpoint_type| !ENDPOINT_TYPES.contains(endpoint_type))
+    {
+        return Err(format!(
+            "Unknown endpoint type \"{}\". Expected one of: {}",
+            endpoint_type,
+            ENDPOINT_TYPES.join(", ")
+        ));
+    }
+
+    Ok(json!({
+        "endpoint": {
+            "endpoint": endpoint,
+            "routingKeys": routing_keys,
+            "types": types,
+        }
+    }))
+}
+
 pub mod get_attrib_command {
     use super::*;
+    use crate::tools::{did::Did, wallet::Wallet};
+    use indy_utils::{base64, did::DidValue, hash::SHA256};
 
     command!(CommandMetadata::build("get-attrib", "Get ATTRIB from Ledger.")
                 .add_required_param("did", "DID of identity presented in Ledger")
                 .add_optional_param("raw", "Name of attribute")
                 .add_optional_param("hash", "Hash of attribute data")
                 .add_optional_param("enc", "Encrypted value of attribute data")
+                .add_optional_param("raw_value", "JSON representation of attribute data. SHA256 hash of the value will be computed locally and used to query the attribute by hash")
+                .add_optional_param("decrypt", "Decrypt received `enc` attribute using the key of DID stored in the opened wallet (False by default). \
+                    The value is expected to be a sealed box (base64 or hex encoded) for the DID verkey")
                 .add_optional_param("send","Send the request to the Ledger (True by default). If false then created request will be printed and stored into CLI context.")
                 .add_example("ledger get-attrib did=VsKV7grR1BUE29mG2Fm2kX raw=endpoint")
                 .add_example("ledger get-attrib did=VsKV7grR1BUE29mG2Fm2kX hash=83d907821df1c87db829e96569a11f6fc2e7880acba5e43d07ab786959e13bd3")
                 .add_example("ledger get-attrib did=VsKV7grR1BUE29mG2Fm2kX enc=aa3f41f619aa7e5e6b6d0d")
+                .add_example(r#"ledger get-attrib did=VsKV7grR1BUE29mG2Fm2kX raw_value={"endpoint":{"ha":"127.0.0.1:5555"}}"#)
+                .add_example("ledger get-attrib did=VsKV7grR1BUE29mG2Fm2kX enc=aa3f41f619aa7e5e6b6d0d decrypt=true")
                 .finalize()
     );
 
@@ -105,40 +356,76 @@ pub mod get_attrib_command {
         let submitter_did = ctx.get_active_did()?;
         let pool = ctx.get_connected_pool();
 
-        let target_did = ParamParser::get_did_param("did", params)?;
-        let raw = ParamParser::get_opt_str_param("raw", params)?;
-        let hash = ParamParser::get_opt_str_param("hash", params)?;
-        let enc = ParamParser::get_opt_str_param("enc", params)?;
+        let target_did = ParamParser::get_did_param(ctx, "did", params)?;
+        let raw = ParamParser::get_opt_str_param(ctx, "raw", params)?;
+        let hash = ParamParser::get_opt_str_param(ctx, "hash", params)?;
+        let enc = ParamParser::get_opt_str_param(ctx, "enc", params)?;
+        let raw_value = ParamParser::get_opt_str_param(ctx, "raw_value", params)?;
+        let decrypt = ParamParser::get_opt_bool_param(ctx, "decrypt", params)?.unwrap_or(false);
+
+        let hash = match (hash, raw_value) {
+            (Some(_), Some(_)) => {
+                println_err!(
+                    ctx,
+                    "Only one of \"hash\" or \"raw_value\" parameters can be specified"
+                );
+                return Err(());
+            }
+            (None, Some(raw_value)) => {
+                let hash = hex::encode(SHA256::digest(raw_value.as_bytes()));
+                println!("Attribute hash: {}", hash);
+                Some(hash)
+            }
+            (hash, None) => hash.map(String::from),
+        };
+
+        let wallet = if decrypt {
+            if enc.is_none() {
+                println_err!(
+                    ctx,
+                    "\"decrypt\" parameter can be used only together with \"enc\""
+                );
+                return Err(());
+            }
+            Some(ctx.ensure_opened_wallet()?)
+        } else {
+            None
+        };
 
-        let request = Ledger::build_get_attrib_request(
+        let mut request = Ledger::build_get_attrib_request(
             pool.as_deref(),
             submitter_did.as_deref(),
             &target_did,
             raw,
-            hash,
+            hash.as_deref(),
             enc,
         )
-        .map_err(|err| println_err!("{}", err.message(None)))?;
+        .map_err(|err| err.print(ctx, None))?;
 
-        let (_, mut response) = send_read_request!(&ctx, params, &request);
+        let (_, mut response) = send_read_request!(&ctx, params, &mut request);
 
         if let Some(result) = response.result.as_mut() {
             let data = result["data"]
                 .as_str()
                 .map(|data| JsonValue::String(data.to_string()));
             match data {
+                Some(JsonValue::String(data)) if wallet.is_some() => {
+                    let data = decrypt_attrib(ctx, wallet.as_deref().unwrap(), &target_did, &data)?;
+                    result["data"] = JsonValue::String(data);
+                }
                 Some(data) => {
                     result["data"] = data;
                 }
                 None => {
-                    println_err!("Attribute not found");
+                    println_err!(ctx, "Attribute not found");
                     return Err(());
                 }
             };
         };
 
-        handle_transaction_response(response).map(|result| {
+        handle_transaction_response(ctx, response).map(|result| {
             print_transaction_response(
+                ctx,
                 result,
                 "Following ATTRIB has been received.",
                 None,
@@ -150,6 +437,28 @@ pub mod get_attrib_command {
         trace!("execute <<");
         Ok(())
     }
+
+    fn decrypt_attrib(
+        ctx: &CommandContext,
+        wallet: &Wallet,
+        did: &DidValue,
+        data: &str,
+    ) -> Result<String, ()> {
+        let ciphertext = hex::decode(data)
+            .or_else(|_| base64::decode(data))
+            .map_err(|_| {
+                println_err!(
+                    ctx,
+                    "Unable to decode encrypted attribute: hex or base64 expected"
+                )
+            })?;
+
+        let value = Did::decrypt_sealed(wallet, did, &ciphertext, ctx.get_crypto_policy())
+            .map_err(|err| err.print(ctx, None))?;
+
+        String::from_utf8(value)
+            .map_err(|_| println_err!(ctx, "Decrypted attribute is not UTF-8 string"))
+    }
 }
 
 #[cfg(test)]
@@ -158,6 +467,7 @@ pub mod tests {
     use crate::{
         commands::{
             did::tests::{new_did, use_did, DID_MY3, DID_TRUSTEE, SEED_MY3},
+            pool::attrib_schema::tests::set_endpoint_schema,
             setup_with_wallet_and_pool, submit_retry, tear_down_with_wallet_and_pool,
             wallet::tests::{close_wallet, open_wallet},
         },
@@ -191,6 +501,28 @@ pub mod tests {
             tear_down_with_wallet_and_pool(&ctx);
         }
 
+        #[test]
+        pub fn attrib_works_for_raw_value_not_matching_schema() {
+            let ctx = setup_with_wallet_and_pool();
+            set_endpoint_schema(&ctx);
+            let (did, _) = use_new_endorser(&ctx);
+            {
+                let cmd = attrib_command::new();
+                let mut params = CommandParams::new();
+                params.insert("did", did.clone());
+                params.insert("raw", r#"{"endpoint":{"ha":"localhost"}}"#.to_string());
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            {
+                let cmd = attrib_command::new();
+                let mut params = CommandParams::new();
+                params.insert("did", did.clone());
+                params.insert("raw", ATTRIB_RAW_DATA.to_string());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            tear_down_with_wallet_and_pool(&ctx);
+        }
+
         #[test]
         pub fn attrib_works_for_hash_value() {
             let ctx = setup_with_wallet_and_pool();
@@ -290,7 +622,7 @@ pub mod tests {
                 cmd.execute(&ctx, &params).unwrap();
             }
             assert!(ensure_attrib_added(&ctx, &did, Some(ATTRIB_RAW_DATA), None, None).is_err());
-            assert!(ctx.get_context_transaction().is_some());
+            assert!(ctx.get_context_transaction(None).is_some());
             tear_down_with_wallet_and_pool(&ctx);
         }
 
@@ -307,7 +639,7 @@ pub mod tests {
                 params.insert("send", "false".to_string());
                 cmd.execute(&ctx, &params).unwrap();
             }
-            let transaction = ctx.get_context_transaction().unwrap();
+            let transaction = ctx.get_context_transaction(None).unwrap();
             let transaction: JsonValue = serde_json::from_str(&transaction).unwrap();
             assert!(transaction["signature"].is_null());
             tear_down_with_wallet_and_pool(&ctx);
@@ -342,8 +674,90 @@ pub mod tests {
         }
     }
 
+    mod clear_attrib {
+        use super::*;
+
+        #[test]
+        pub fn clear_attrib_works() {
+            let ctx = setup_with_wallet_and_pool();
+            let (did, _) = use_new_endorser(&ctx);
+            {
+                let cmd = attrib_command::new();
+                let mut params = CommandParams::new();
+                params.insert("did", did.clone());
+                params.insert("raw", ATTRIB_RAW_DATA.to_string());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            assert!(ensure_attrib_added(&ctx, &did, Some(ATTRIB_RAW_DATA), None, None).is_ok());
+            {
+                let cmd = clear_attrib_command::new();
+                let mut params = CommandParams::new();
+                params.insert("did", did.clone());
+                params.insert("name", "endpoint".to_string());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            assert!(
+                ensure_attrib_added(&ctx, &did, Some(r#"{"endpoint":null}"#), None, None).is_ok()
+            );
+            tear_down_with_wallet_and_pool(&ctx);
+        }
+
+        #[test]
+        pub fn clear_attrib_works_for_not_set_attribute() {
+            let ctx = setup_with_wallet_and_pool();
+            let (did, _) = use_new_endorser(&ctx);
+            {
+                let cmd = clear_attrib_command::new();
+                let mut params = CommandParams::new();
+                params.insert("did", did.clone());
+                params.insert("name", "endpoint".to_string());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            tear_down_with_wallet_and_pool(&ctx);
+        }
+    }
+
+    mod register_agent {
+        use super::*;
+
+        const ENDPOINT: &str = "https://agent.example.com";
+        const ROUTING_KEY: &str = "3Dn1SJNPaCXcvvJvSbsFWP2xaCjMom3can8CQNhWrTRx";
+
+        #[test]
+        pub fn register_agent_works() {
+            let ctx = setup_with_wallet_and_pool();
+            let (did, _) = use_new_endorser(&ctx);
+            {
+                let cmd = register_agent_command::new();
+                let mut params = CommandParams::new();
+                params.insert("did", did.clone());
+                params.insert("endpoint", ENDPOINT.to_string());
+                params.insert("routing_keys", ROUTING_KEY.to_string());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            let raw = agent_endpoint(ENDPOINT, &[ROUTING_KEY], &ENDPOINT_TYPES)
+                .unwrap()
+                .to_string();
+            assert!(ensure_attrib_added(&ctx, &did, Some(&raw), None, None).is_ok());
+            tear_down_with_wallet_and_pool(&ctx);
+        }
+
+        #[test]
+        pub fn agent_endpoint_works() {
+            assert_eq!(
+                json!({"endpoint": {"endpoint": ENDPOINT, "routingKeys": [], "types": ["DIDComm"]}}),
+                agent_endpoint(ENDPOINT, &[], &["DIDComm"]).unwrap()
+            );
+            agent_endpoint("agent.example.com", &[], &ENDPOINT_TYPES).unwrap_err();
+            agent_endpoint(ENDPOINT, &["invalid"], &ENDPOINT_TYPES).unwrap_err();
+            agent_endpoint(ENDPOINT, &[ROUTING_KEY], &["DIDCommV3"]).unwrap_err();
+        }
+    }
+
     mod get_attrib {
         use super::*;
+        use aries_askar::kms::{crypto_box_seal, KeyAlg, LocalKey};
+        use indy_utils::{base58, base64, hash::SHA256};
 
         #[test]
         pub fn get_attrib_works_for_raw_value() {
@@ -411,6 +825,90 @@ pub mod tests {
             tear_down_with_wallet_and_pool(&ctx);
         }
 
+        #[test]
+        pub fn get_attrib_works_for_raw_value_hash() {
+            let ctx = setup_with_wallet_and_pool();
+            let (did, _) = use_new_endorser(&ctx);
+            let hash = hex::encode(SHA256::digest(ATTRIB_RAW_DATA.as_bytes()));
+            {
+                let cmd = attrib_command::new();
+                let mut params = CommandParams::new();
+                params.insert("did", did.clone());
+                params.insert("hash", hash.clone());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            assert!(ensure_attrib_added(&ctx, &did, None, Some(hash.as_str()), None).is_ok());
+            {
+                let cmd = get_attrib_command::new();
+                let mut params = CommandParams::new();
+                params.insert("did", did.clone());
+                params.insert("raw_value", ATTRIB_RAW_DATA.to_string());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            tear_down_with_wallet_and_pool(&ctx);
+        }
+
+        #[test]
+        pub fn get_attrib_works_for_raw_value_and_hash() {
+            let ctx = setup_with_wallet_and_pool();
+            use_trustee(&ctx);
+            {
+                let cmd = get_attrib_command::new();
+                let mut params = CommandParams::new();
+                params.insert("did", DID_TRUSTEE.to_string());
+                params.insert("raw_value", ATTRIB_RAW_DATA.to_string());
+                params.insert("hash", ATTRIB_HASH_DATA.to_string());
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            tear_down_with_wallet_and_pool(&ctx);
+        }
+
+        #[test]
+        pub fn get_attrib_works_for_decrypt_enc_value() {
+            let ctx = setup_with_wallet_and_pool();
+            let (did, verkey) = use_new_endorser(&ctx);
+            let enc = {
+                let key =
+                    LocalKey::from_public_bytes(KeyAlg::Ed25519, &base58::decode(&verkey).unwrap())
+                        .unwrap()
+                        .convert_key(KeyAlg::X25519)
+                        .unwrap();
+                base64::encode(crypto_box_seal(&key, ATTRIB_RAW_DATA.as_bytes()).unwrap())
+            };
+            {
+                let cmd = attrib_command::new();
+                let mut params = CommandParams::new();
+                params.insert("did", did.clone());
+                params.insert("enc", enc.clone());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            assert!(ensure_attrib_added(&ctx, &did, None, None, Some(enc.as_str())).is_ok());
+            {
+                let cmd = get_attrib_command::new();
+                let mut params = CommandParams::new();
+                params.insert("did", did.clone());
+                params.insert("enc", enc.clone());
+                params.insert("decrypt", "true".to_string());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            tear_down_with_wallet_and_pool(&ctx);
+        }
+
+        #[test]
+        pub fn get_attrib_works_for_decrypt_without_enc() {
+            let ctx = setup_with_wallet_and_pool();
+            use_trustee(&ctx);
+            {
+                let cmd = get_attrib_command::new();
+                let mut params = CommandParams::new();
+                params.insert("did", DID_TRUSTEE.to_string());
+                params.insert("raw", "endpoint".to_string());
+                params.insert("decrypt", "true".to_string());
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            tear_down_with_wallet_and_pool(&ctx);
+        }
+
         #[test]
         pub fn get_attrib_works_for_no_active_did() {
             let ctx = setup_with_wallet_and_pool();
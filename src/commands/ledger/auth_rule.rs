@@ -66,15 +66,15 @@ pub mod auth_rule_command {
         let submitter_did = ctx.ensure_active_did()?;
         let pool = ctx.get_connected_pool();
 
-        let txn_type = ParamParser::get_str_param("txn_type", params)?;
-        let action = ParamParser::get_str_param("action", params)?;
-        let field = ParamParser::get_str_param("field", params)?;
-        let old_value = ParamParser::get_opt_str_param("old_value", params)?;
-        let new_value = ParamParser::get_opt_str_param("new_value", params)?;
-        let constraint = ParamParser::get_str_param("constraint", params)?;
+        let txn_type = ParamParser::get_str_param(ctx, "txn_type", params)?;
+        let action = ParamParser::get_str_param(ctx, "action", params)?;
+        let field = ParamParser::get_str_param(ctx, "field", params)?;
+        let old_value = ParamParser::get_opt_str_param(ctx, "old_value", params)?;
+        let new_value = ParamParser::get_opt_str_param(ctx, "new_value", params)?;
+        let constraint = ParamParser::get_str_param(ctx, "constraint", params)?;
 
         let txn_type = txn_name_to_code(txn_type)
-            .ok_or_else(|| println_err!("Unsupported ledger transaction."))?;
+            .ok_or_else(|| println_err!(ctx, "Unsupported ledger transaction."))?;
 
         let mut request = Ledger::build_auth_rule_request(
             pool.as_deref(),
@@ -86,7 +86,7 @@ pub mod auth_rule_command {
             new_value,
             constraint,
         )
-        .map_err(|err| println_err!("{}", err.message(None)))?;
+        .map_err(|err| err.print(ctx, None))?;
 
         let (_, mut response): (String, Response<JsonValue>) =
             send_write_request!(ctx, params, &mut request, &wallet, &submitter_did);
@@ -99,8 +99,9 @@ pub mod auth_rule_command {
             );
         }
 
-        handle_transaction_response(response).map(|result| {
+        handle_transaction_response(ctx, response).map(|result| {
             print_transaction_response(
+                ctx,
                 result,
                 "Auth Rule request has been sent to Ledger.",
                 None,
@@ -139,20 +140,21 @@ pub mod auth_rules_command {
         let submitter_did = ctx.ensure_active_did()?;
         let pool = ctx.get_connected_pool();
 
-        let rules = ParamParser::get_str_param("rules", params)?;
+        let rules = ParamParser::get_str_param(ctx, "rules", params)?;
 
         let mut request = Ledger::build_auth_rules_request(pool.as_deref(), &submitter_did, &rules)
-            .map_err(|err| println_err!("{}", err.message(None)))?;
+            .map_err(|err| err.print(ctx, None))?;
 
         let (_, response): (String, Response<JsonValue>) =
             send_write_request!(ctx, params, &mut request, &wallet, &submitter_did);
 
-        let result = handle_transaction_response(response)?;
+        let result = handle_transaction_response(ctx, response)?;
         println!("result {:?}", result);
 
-        let rules: AuthRulesData = serde_json::from_value(result["txn"]["data"]["rules"].clone())
-            .map_err(|_| println_err!("Wrong data has been received"))?;
-        print_auth_rules(rules);
+        let rules: AuthRulesData =
+            serde_json::from_value(result["txn"]["data"]["rules"].clone())
+                .map_err(|_| println_err!(ctx, "Wrong data has been received"))?;
+        print_auth_rules(ctx, rules);
 
         trace!("execute << ");
         Ok(())
@@ -182,13 +184,13 @@ pub mod get_auth_rule_command {
         let submitter_did = ctx.get_active_did()?;
         let pool = ctx.get_connected_pool();
 
-        let auth_type = ParamParser::get_opt_str_param("txn_type", params)?;
-        let auth_action = ParamParser::get_opt_str_param("action", params)?;
-        let field = ParamParser::get_opt_str_param("field", params)?;
-        let old_value = ParamParser::get_opt_str_param("old_value", params)?;
-        let new_value = ParamParser::get_opt_str_param("new_value", params)?;
+        let auth_type = ParamParser::get_opt_str_param(ctx, "txn_type", params)?;
+        let auth_action = ParamParser::get_opt_str_param(ctx, "action", params)?;
+        let field = ParamParser::get_opt_str_param(ctx, "field", params)?;
+        let old_value = ParamParser::get_opt_str_param(ctx, "old_value", params)?;
+        let new_value = ParamParser::get_opt_str_param(ctx, "new_value", params)?;
 
-        let request = Ledger::build_get_auth_rule_request(
+        let mut request = Ledger::build_get_auth_rule_request(
             pool.as_deref(),
             submitter_did.as_deref(),
             auth_type,
@@ -197,23 +199,23 @@ pub mod get_auth_rule_command {
             old_value,
             new_value,
         )
-        .map_err(|err| println_err!("{}", err.message(None)))?;
+        .map_err(|err| err.print(ctx, None))?;
 
-        let (_, response) = send_read_request!(&ctx, params, &request);
+        let (_, response) = send_read_request!(&ctx, params, &mut request);
 
-        let result = handle_transaction_response(response)?;
+        let result = handle_transaction_response(ctx, response)?;
 
         let rules: AuthRulesData = serde_json::from_value(result["data"].clone())
-            .map_err(|_| println_err!("Wrong data has been received"))?;
+            .map_err(|_| println_err!(ctx, "Wrong data has been received"))?;
 
-        print_auth_rules(rules);
+        print_auth_rules(ctx, rules);
 
         trace!("execute << ");
         Ok(())
     }
 }
 
-fn print_auth_rules(rules: AuthRulesData) {
+fn print_auth_rules(ctx: &CommandContext, rules: AuthRulesData) {
     let constraints = rules
         .into_iter()
         .map(|rule| {
@@ -240,6 +242,7 @@ fn print_auth_rules(rules: AuthRulesData) {
         .collect::<Vec<JsonValue>>();
 
     print_list_table(
+        ctx,
         &constraints,
         &[
             ("auth_type", "Type"),
@@ -383,7 +386,7 @@ pub mod tests {
                 params.insert("send", "false".to_string());
                 cmd.execute(&ctx, &params).unwrap();
             }
-            assert!(ctx.get_context_transaction().is_some());
+            assert!(ctx.get_context_transaction(None).is_some());
             tear_down_with_wallet_and_pool(&ctx);
         }
     }
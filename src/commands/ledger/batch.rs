@@ -0,0 +1,301 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::{
+    command_executor::{
+        wait_for_user_reply, Command, CommandContext, CommandMetadata, CommandParams,
+    },
+    params_parser::ParamParser,
+    tools::ledger::{rejection::Rejection, LedgerHelpers, Response, ResponseType},
+    utils::{table::print_list_table, term::print_prompt},
+};
+
+use super::common::{submit_request, throttle_write};
+
+use indy_vdr::pool::PreparedRequest;
+use serde_json::Value as JsonValue;
+
+pub mod batch_command {
+    use super::*;
+
+    command!(CommandMetadata::build(
+        "batch",
+        "Group consecutive ledger write transactions and send them together. \
+        Between `begin` and `commit` the write commands queue signed transactions instead of sending them."
+    )
+    .add_main_param("action", "Batch action: begin, show, commit or abort")
+    .add_example("ledger batch begin")
+    .add_example("ledger batch show")
+    .add_example("ledger batch commit")
+    .add_example("ledger batch abort")
+    .finalize());
+
+    fn execute(ctx: &CommandContext, params: &CommandParams) -> Result<(), ()> {
+        trace!("execute >> ctx {:?} params {:?}", ctx, params);
+
+        let action = ParamParser::get_str_param(ctx, "action", params)?;
+
+        match action {
+            "begin" => begin(ctx)?,
+            "show" => show(ctx)?,
+            "commit" => commit(ctx)?,
+            "abort" => abort(ctx)?,
+            action => {
+                println_err!(
+                    ctx,
+                    "Unknown batch action \"{}\". Expected one of: begin, show, commit, abort",
+                    action
+                );
+                return Err(());
+            }
+        }
+
+        trace!("execute <<");
+        Ok(())
+    }
+}
+
+fn begin(ctx: &CommandContext) -> Result<(), ()> {
+    if ctx.is_ledger_batch_started() {
+        println_err!(
+            ctx,
+            "Batch has already been started. Use `ledger batch commit` or `ledger batch abort` to finish it."
+        );
+        return Err(());
+    }
+    ctx.start_ledger_batch();
+    println_succ!(
+        ctx,
+        "Batch has been started. Write transactions will be queued until `ledger batch commit`."
+    );
+    Ok(())
+}
+
+fn show(ctx: &CommandContext) -> Result<(), ()> {
+    let requests = ensure_batch_requests(ctx)?;
+    print_batch(ctx, &requests, &[]);
+    Ok(())
+}
+
+fn abort(ctx: &CommandContext) -> Result<(), ()> {
+    let requests = ensure_batch_requests(ctx)?;
+    ctx.reset_ledger_batch();
+    println_succ!(
+        ctx,
+        "Batch has been aborted. {} queued transaction(s) have been discarded.",
+        requests.len()
+    );
+    Ok(())
+}
+
+fn commit(ctx: &CommandContext) -> Result<(), ()> {
+    let requests = ensure_batch_requests(ctx)?;
+    ctx.ensure_connected_pool()?;
+
+    if requests.is_empty() {
+        ctx.reset_ledger_batch();
+        println!("Batch is empty. Nothing has been sent.");
+        return Ok(());
+    }
+
+    print_batch(ctx, &requests, &[]);
+    print_prompt(&format!(
+        "{} transaction(s) will be sent to the ledger one by one. Would you like to continue? (y/n)",
+        requests.len()
+    ));
+    if !wait_for_user_reply(ctx) {
+        println!("The batch has not been sent. Use `ledger batch abort` to discard it.");
+        return Ok(());
+    }
+
+    // Transactions of a batch often depend on each other, so the first failure stops sending
+    let mut statuses = Vec::new();
+    for request in requests.iter() {
+        let status = send(ctx, request);
+        let failed = !status.starts_with("Committed");
+        statuses.push(status);
+        if failed {
+            break;
+        }
+    }
+    ctx.reset_ledger_batch();
+
+    print_batch(ctx, &requests, &statuses);
+    let committed = statuses
+        .iter()
+        .filter(|status| status.starts_with("Committed"))
+        .count();
+    if committed == requests.len() {
+        println_succ!(
+            ctx,
+            "All {} transaction(s) of the batch have been committed.",
+            committed
+        );
+        Ok(())
+    } else {
+        println_err!(
+            ctx,
+            "{} of {} transaction(s) of the batch have been committed. The rest have not been sent.",
+            committed,
+            requests.len()
+        );
+        Err(())
+    }
+}
+
+fn ensure_batch_requests(ctx: &CommandContext) -> Result<Vec<String>, ()> {
+    if !ctx.is_ledger_batch_started() {
+        println_err!(
+            ctx,
+            "There is no started batch. Use `ledger batch begin` to start it."
+        );
+        return Err(());
+    }
+    Ok(ctx.get_ledger_batch())
+}
+
+fn send(ctx: &CommandContext, request: &str) -> String {
+    match PreparedRequest::from_request_json(request) {
+        Ok(request) => send_with_status(ctx, &request),
+        Err(_) => "Failed: invalid transaction".to_string(),
+    }
+}
+
+/// Submit a write request and describe the outcome: `Committed: seqNo ...`, `Rejected: <reason>` or `Failed: ...`.
+pub fn send_with_status(ctx: &CommandContext, request: &PreparedRequest) -> String {
+    throttle_write(ctx);
+    let response = match submit_request(ctx, request) {
+        Ok(response) => response,
+        Err(()) => return "Failed: request has not been processed".to_string(),
+    };
+
+    match serde_json::from_str::<Response<JsonValue>>(&response) {
+        Ok(Response {
+            op: ResponseType::REPLY,
+            result: Some(result),
+            ..
+        }) => format!(
+            "Committed: seqNo {}",
+            result["txnMetadata"]["seqNo"].as_u64().unwrap_or_default()
+        ),
+        Ok(Response {
+            reason: Some(reason),
+            ..
+        }) => format!(
+            "Rejected: {}",
+            Rejection::explain(&reason)
+                .map(|rejection| rejection.explanation)
+                .unwrap_or(reason)
+        ),
+        _ => "Failed: invalid data has been received".to_string(),
+    }
+}
+
+fn print_batch(ctx: &CommandContext, requests: &[String], statuses: &[String]) {
+    let rows = requests
+        .iter()
+        .enumerate()
+        .map(|(index, request)| {
+            let request = serde_json::from_str::<JsonValue>(request).unwrap_or_default();
+            json!({
+                "index": index + 1,
+                "type": LedgerHelpers::get_txn_title(&request["operation"]["type"]),
+                "submitter": request["identifier"],
+                "reqId": request["reqId"],
+                "status": statuses.get(index).map(String::as_str).unwrap_or("Queued"),
+            })
+        })
+        .collect::<Vec<JsonValue>>();
+
+    print_list_table(
+        ctx,
+        &rows,
+        &[
+            ("index", "#"),
+            ("type", "Type"),
+            ("submitter", "Submitter"),
+            ("reqId", "Request ID"),
+            ("status", "Status"),
+        ],
+        "There are no transactions in the batch",
+    );
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::{
+        commands::{setup, setup_with_wallet_and_pool, tear_down, tear_down_with_wallet_and_pool},
+        ledger::{
+            nym::tests::ensure_nym_added,
+            tests::{create_new_did, send_nym, use_trustee},
+        },
+    };
+
+    fn batch(ctx: &CommandContext, action: &str) -> Result<(), ()> {
+        let cmd = batch_command::new();
+        let mut params = CommandParams::new();
+        params.insert("action", action.to_string());
+        cmd.execute(ctx, &params)
+    }
+
+    mod batch {
+        use super::*;
+
+        #[test]
+        pub fn batch_works() {
+            let ctx = setup_with_wallet_and_pool();
+            use_trustee(&ctx);
+            let (did_1, verkey_1) = create_new_did(&ctx);
+            let (did_2, verkey_2) = create_new_did(&ctx);
+            batch(&ctx, "begin").unwrap();
+            send_nym(&ctx, &did_1, &verkey_1, None);
+            send_nym(&ctx, &did_2, &verkey_2, None);
+            assert_eq!(ctx.get_ledger_batch().len(), 2);
+            ensure_nym_added(&ctx, &did_1).unwrap_err();
+            batch(&ctx, "commit").unwrap();
+            assert!(!ctx.is_ledger_batch_started());
+            ensure_nym_added(&ctx, &did_1).unwrap();
+            ensure_nym_added(&ctx, &did_2).unwrap();
+            tear_down_with_wallet_and_pool(&ctx);
+        }
+
+        #[test]
+        pub fn batch_works_for_abort() {
+            let ctx = setup_with_wallet_and_pool();
+            use_trustee(&ctx);
+            let (did, verkey) = create_new_did(&ctx);
+            batch(&ctx, "begin").unwrap();
+            send_nym(&ctx, &did, &verkey, None);
+            batch(&ctx, "show").unwrap();
+            batch(&ctx, "abort").unwrap();
+            assert!(!ctx.is_ledger_batch_started());
+            ensure_nym_added(&ctx, &did).unwrap_err();
+            tear_down_with_wallet_and_pool(&ctx);
+        }
+
+        #[test]
+        pub fn batch_works_for_not_started() {
+            let ctx = setup();
+            batch(&ctx, "commit").unwrap_err();
+            batch(&ctx, "abort").unwrap_err();
+            tear_down();
+        }
+
+        #[test]
+        pub fn batch_works_for_started_twice() {
+            let ctx = setup();
+            batch(&ctx, "begin").unwrap();
+            batch(&ctx, "begin").unwrap_err();
+            tear_down();
+        }
+
+        #[test]
+        pub fn batch_works_for_unknown_action() {
+            let ctx = setup();
+            batch(&ctx, "rollback").unwrap_err();
+            tear_down();
+        }
+    }
+}
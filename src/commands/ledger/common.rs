@@ -3,52 +3,117 @@
     https://digital.gov.bc.ca/digital-trust
 */
 use crate::{
-    command_executor::CommandContext,
+    command_executor::{CommandContext, CommandParams},
     error::CliResult,
-    tools::ledger::{parse_transaction_response, Ledger, Response, ResponseType},
-    utils::table::print_table,
+    params_parser::ParamParser,
+    tools::ledger::{parse_transaction_response, Ledger, LedgerHelpers, Response, ResponseType},
+    utils::{
+        file::write_file,
+        table::print_table,
+        time::{format_duration, timestamp_to_datetime},
+    },
 };
 
+use super::constants::SEND_REQUEST;
+use crate::commands::pool::constants::POOL_IDLE_TIMEOUT;
 use crate::{
     error::CliError,
-    tools::{pool::Pool, wallet::Wallet},
+    tools::{
+        did::Did, ledger::permissions::can_write, pool::Pool, queue::QueuedRequest, wallet::Wallet,
+    },
 };
 use indy_utils::did::DidValue;
-use indy_vdr::pool::PreparedRequest;
+use indy_vdr::{common::error::VdrErrorKind, pool::PreparedRequest};
 use serde_json::Value as JsonValue;
-use std::collections::HashMap;
+use std::{
+    collections::HashMap,
+    rc::Rc,
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
 
 macro_rules! send_write_request {
     ($ctx:expr, $params:expr, $request:expr, $wallet:expr, $submitter_did:expr) => {{
-        let sign = ParamParser::get_opt_bool_param("sign", $params)?
+        let sign = ParamParser::get_opt_bool_param($ctx, "sign", $params)?
             .unwrap_or(super::super::constants::SIGN_REQUEST);
-        let endorser = ParamParser::get_opt_did_param("endorser", $params)?;
-        let mut send = ParamParser::get_opt_bool_param("send", $params)?
+        let endorser = match ParamParser::get_opt_did_param($ctx, "endorser", $params)? {
+            Some(endorser) => Some(endorser),
+            None => crate::ledger::get_default_endorser($ctx, $submitter_did, $request),
+        };
+        let mut send = ParamParser::get_opt_bool_param($ctx, "send", $params)?
             .unwrap_or(super::super::constants::SEND_REQUEST);
 
         match endorser {
             Some(endorser_did) => {
                 send = false;
                 Ledger::append_request_endorser($request, &endorser_did).map_err(|err| {
-                    println_err!("{}", err.message(None));
+                    err.print($ctx, None);
                 })?
             }
             None => {}
         };
 
+        if send && !crate::ledger::confirm_did_namespace($ctx, $request) {
+            println_warn!($ctx, "The transaction has not been sent.");
+            return Ok(());
+        }
+
         if sign {
-            Ledger::sign_request($wallet, $submitter_did, $request).map_err(|err| {
-                println_err!("{}", err.message(None));
+            crate::wallet::ensure_key_rotated($ctx, $wallet)?;
+            Ledger::sign_request(
+                $ctx.get_connected_pool().as_deref(),
+                $wallet,
+                $submitter_did,
+                $request,
+            )
+            .map_err(|err| {
+                err.print($ctx, None);
             })?;
         };
 
-        send_request!($ctx, $params, $request, send)
+        if send && $ctx.is_ledger_batch_started() {
+            let queued = $ctx.add_to_ledger_batch(json!(&$request.req_json).to_string());
+            println_succ!(
+                $ctx,
+                "Transaction has been added to the batch ({} queued). Use `ledger batch commit` to send the batch.",
+                queued
+            );
+            return Ok(());
+        }
+
+        if send {
+            crate::ledger::throttle_write($ctx);
+        }
+
+        let queue = ParamParser::get_opt_bool_param($ctx, "queue", $params)?.unwrap_or(false);
+        if send && queue {
+            if !crate::ledger::confirm_did_namespace($ctx, $request) {
+                println_warn!($ctx, "The transaction has not been sent.");
+                return Ok(());
+            }
+
+            let started = std::time::Instant::now();
+            let response_json =
+                crate::ledger::submit_or_queue_request($ctx, $request, $submitter_did)?;
+            $ctx.set_ledger_request_info(&$request.txn_type, started.elapsed());
+            let response_json = match response_json {
+                Some(response_json) => response_json,
+                None => return Ok(()),
+            };
+
+            let response = serde_json::from_str::<Response<JsonValue>>(&response_json)
+                .map_err(|err| println_err!($ctx, "Invalid data has been received: {:?}", err))?;
+
+            (response_json, response)
+        } else {
+            send_request!($ctx, $params, $request, send)
+        }
     }};
 }
 
 macro_rules! send_read_request {
     ($ctx:expr, $params:expr, $request:expr) => {{
-        let send = ParamParser::get_opt_bool_param("send", $params)?
+        let send = ParamParser::get_opt_bool_param($ctx, "send", $params)?
             .unwrap_or(super::super::constants::SEND_REQUEST);
         send_request!($ctx, $params, $request, send)
     }};
@@ -57,20 +122,25 @@ macro_rules! send_read_request {
 macro_rules! send_request {
     ($ctx:expr, $params:expr, $request:expr, $send:expr) => {{
         if $send {
-            let pool = $ctx.ensure_connected_pool()?;
-            let response_json = Ledger::submit_request(&pool, $request).map_err(|err| {
-                println_err!("{}", err.message(None));
-            })?;
+            if !crate::ledger::confirm_did_namespace($ctx, $request) {
+                println_warn!($ctx, "The transaction has not been sent.");
+                return Ok(());
+            }
+
+            let started = std::time::Instant::now();
+            let response_json = crate::ledger::submit_request($ctx, $request);
+            $ctx.set_ledger_request_info(&$request.txn_type, started.elapsed());
+            let response_json = response_json?;
 
             let response = serde_json::from_str::<Response<JsonValue>>(&response_json)
-                .map_err(|err| println_err!("Invalid data has been received: {:?}", err))?;
+                .map_err(|err| println_err!($ctx, "Invalid data has been received: {:?}", err))?;
 
             (response_json, response)
         } else {
             let request_json = json!(&$request.req_json).to_string();
-            println_succ!("Transaction has been created:");
+            println_succ!($ctx, "Transaction has been created:");
             println!("     {}", request_json);
-            $ctx.set_context_transaction(Some(request_json));
+            $ctx.set_context_transaction(None, Some(request_json));
             return Ok(());
         }
     }};
@@ -78,12 +148,16 @@ macro_rules! send_request {
 
 macro_rules! get_transaction_to_use {
     ($ctx:expr, $param_txn:expr) => ({
+        get_transaction_to_use!($ctx, $param_txn, None)
+    });
+    ($ctx:expr, $param_txn:expr, $name:expr) => ({
+        let name_: Option<&str> = $name;
         if let Some(txn_) = $param_txn {
             PreparedRequest::from_request_json(&txn_)
-                .map_err(|_| println_err!("Invalid formatted transaction provided."))?
-        } else if let Some(txn_) = $ctx.get_context_transaction() {
+                .map_err(|_| println_err!($ctx, "Invalid formatted transaction provided."))?
+        } else if let Some(txn_) = $ctx.get_context_transaction(name_) {
             println!("Transaction stored into context: {}.", txn_);
-            println!("Would you like to use it? (y/n)");
+            crate::utils::term::print_prompt("Would you like to use it? (y/n)");
 
             let use_transaction = crate::command_executor::wait_for_user_reply($ctx);
 
@@ -93,9 +167,12 @@ macro_rules! get_transaction_to_use {
             }
 
             PreparedRequest::from_request_json(&txn_)
-                .map_err(|_| println_err!("Invalid formatted transaction provided."))?
+                .map_err(|_| println_err!($ctx, "Invalid formatted transaction provided."))?
+        } else if let Some(name_) = name_ {
+            println_err!($ctx, "There is no transaction \"{}\" stored into context", name_);
+            return Err(());
         } else {
-            println_err!("There is not a transaction to use.");
+            println_err!($ctx, "There is not a transaction to use.");
             println!("You either need to explicitly pass transaction as a parameter, or \
                     load transaction using `ledger load-transaction`, or \
                     build a transaction (with passing either `send=false` or `endorser` parameter).");
@@ -104,7 +181,341 @@ macro_rules! get_transaction_to_use {
     })
 }
 
-pub fn handle_transaction_response(response: Response<JsonValue>) -> Result<JsonValue, ()> {
+/// Warn when the active DID belongs to a `did:indy` namespace different from the connected pool one.
+/// Returns `false` in case of mismatch.
+pub fn check_did_namespace(ctx: &CommandContext) -> bool {
+    let (did, pool) = match (ctx.get_active_did(), ctx.get_connected_pool()) {
+        (Ok(Some(did)), Some(pool)) => (did, pool),
+        _ => return true,
+    };
+
+    match (Did::namespace(&did), pool.namespace.as_ref()) {
+        (Some(did_namespace), Some(pool_namespace)) if &did_namespace != pool_namespace => {
+            println_warn!(
+                ctx,
+                "Active DID \"{}\" belongs to \"{}\" namespace, but the connected pool \"{}\" is \"{}\" network.",
+                did,
+                did_namespace,
+                pool.name,
+                pool_namespace
+            );
+            false
+        }
+        _ => true,
+    }
+}
+
+/// Short form of the active DID to send ledger requests with in case of `did:indy` namespace mismatch.
+/// `Ok(None)` if there is no mismatch, `Err` if the user has declined to use the short form.
+pub fn confirm_short_did(ctx: &CommandContext) -> Result<Option<DidValue>, ()> {
+    if check_did_namespace(ctx) {
+        return Ok(None);
+    }
+
+    let short_did = match ctx.get_active_did() {
+        Ok(Some(did)) => Did::short_form(&did),
+        _ => return Ok(None),
+    };
+
+    crate::utils::term::print_prompt(&format!(
+        "The request will be sent using the short form of the DID \"{}\". Would you like to continue? (y/n)",
+        short_did
+    ));
+    if crate::command_executor::wait_for_user_reply(ctx) {
+        Ok(Some(short_did))
+    } else {
+        Err(())
+    }
+}
+
+/// Replace the active DID in the request identifier with its short form on the user confirmation.
+/// Must be called before the request is signed. Returns `false` if the user has declined.
+pub fn confirm_did_namespace(ctx: &CommandContext, request: &mut PreparedRequest) -> bool {
+    let did = match ctx.get_active_did() {
+        Ok(Some(did)) => did,
+        _ => return true,
+    };
+    // Requests of other submitters, already translated and already signed ones are left as they are
+    if !is_long_identifier(request, &did) || is_signed(request) {
+        return true;
+    }
+
+    match confirm_short_did(ctx) {
+        Ok(Some(short_did)) => {
+            request.req_json["identifier"] = JsonValue::String(short_did.0);
+            true
+        }
+        Ok(None) => true,
+        Err(()) => false,
+    }
+}
+
+fn is_long_identifier(request: &PreparedRequest, did: &DidValue) -> bool {
+    match request.req_json["identifier"].as_str() {
+        Some(identifier) => {
+            identifier != Did::short_form(did).0
+                && (identifier == did.0 || identifier == did.to_short().0)
+        }
+        None => false,
+    }
+}
+
+fn is_signed(request: &PreparedRequest) -> bool {
+    !request.req_json["signature"].is_null() || !request.req_json["signatures"].is_null()
+}
+
+/// Default endorser of the connected pool when the submitter is not allowed to send the request on its own:
+/// either to add a new entry or to change the existing one.
+/// Permission check failures are reported, and the request is left as it is.
+pub fn get_default_endorser(
+    ctx: &CommandContext,
+    submitter_did: &DidValue,
+    request: &PreparedRequest,
+) -> Option<DidValue> {
+    let pool = ctx.get_connected_pool()?;
+    let endorser = pool.endorser.clone()?;
+    if endorser.to_short() == submitter_did.to_short() {
+        return None;
+    }
+
+    match can_write(&pool, submitter_did, request) {
+        Ok(true) => None,
+        Ok(false) => {
+            let txn_title =
+                LedgerHelpers::get_txn_title(&JsonValue::String(request.txn_type.to_string()));
+            println_warn!(
+                ctx,
+                "DID \"{}\" is not allowed to send {} transactions on \"{}\" pool. The transaction is prepared for endorsement by \"{}\".",
+                submitter_did,
+                txn_title.as_str().unwrap_or(&request.txn_type),
+                pool.name,
+                endorser
+            );
+            Some(endorser)
+        }
+        Err(err) => {
+            println_warn!(
+                ctx,
+                "Unable to check permissions of DID \"{}\", the default endorser is not used: {}",
+                submitter_did,
+                err.message(Some(&pool.name))
+            );
+            None
+        }
+    }
+}
+
+/// Wait until the write rate set by `set write-rate` allows to send the next write request.
+/// Writes are spread evenly over the minute, so a long run never sends a burst of requests.
+pub fn throttle_write(ctx: &CommandContext) {
+    let max_per_minute = match ctx.get_write_rate() {
+        Some(max_per_minute) => max_per_minute,
+        None => return,
+    };
+    let interval = 60_000 / max_per_minute;
+    if let Some(last_write) = ctx.get_last_write_time() {
+        let wait = (last_write + interval).saturating_sub(now_millis());
+        if wait > 0 {
+            thread::sleep(Duration::from_millis(wait));
+        }
+    }
+    ctx.set_last_write_time(now_millis());
+}
+
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
+/// Submit request to the connected pool.
+/// The pool connection is rebuilt if it has been idle for a long time or the request failed on the network level.
+pub fn submit_request(ctx: &CommandContext, request: &PreparedRequest) -> Result<String, ()> {
+    let pool = ctx.ensure_connected_pool()?;
+    let pool_name = pool.name.to_string();
+    let response =
+        send_to_pool(ctx, pool, request).map_err(|err| err.print(ctx, Some(&pool_name)))?;
+    ctx.set_last_response(Some(response.clone()));
+    check_freshness(ctx, &response)?;
+    Ok(response)
+}
+
+/// Submit a write request or spool it to the local queue when the pool cannot be reached
+/// (there is no connected pool or the nodes do not respond).
+/// Returns `None` if the request has been queued, `queue flush` sends it later.
+pub fn submit_or_queue_request(
+    ctx: &CommandContext,
+    request: &PreparedRequest,
+    submitter_did: &DidValue,
+) -> Result<Option<String>, ()> {
+    let pool = match ctx.get_connected_pool() {
+        Some(pool) => pool,
+        None => {
+            println_warn!(ctx, "There is no opened pool now.");
+            return queue_request(ctx, request, submitter_did, None).map(|_| None);
+        }
+    };
+    let pool_name = pool.name.to_string();
+
+    match send_to_pool(ctx, pool, request) {
+        Ok(response) => {
+            ctx.set_last_response(Some(response.clone()));
+            Ok(Some(response))
+        }
+        Err(err) if is_pool_unreachable(&err) => {
+            println_warn!(ctx, "{}", err.message(Some(&pool_name)));
+            queue_request(ctx, request, submitter_did, Some(&pool_name)).map(|_| None)
+        }
+        // the nodes may have received the request: queuing it could write the transaction twice
+        Err(err) if is_outcome_unknown(&err) => {
+            err.print(ctx, Some(&pool_name));
+            println_warn!(
+                ctx,
+                "The transaction has not been queued. Check the ledger before sending it again."
+            );
+            Err(())
+        }
+        Err(err) => Err(err.print(ctx, Some(&pool_name))),
+    }
+}
+
+fn queue_request(
+    ctx: &CommandContext,
+    request: &PreparedRequest,
+    submitter_did: &DidValue,
+    pool_name: Option<&str>,
+) -> Result<(), ()> {
+    let queued = QueuedRequest::new(request, &submitter_did.to_string(), pool_name);
+    queued.save().map_err(|err| err.print(ctx, None))?;
+    println_succ!(
+        ctx,
+        "Transaction \"{}\" has been queued. Use `queue flush` to send it once the pool is reachable.",
+        queued.id
+    );
+    Ok(())
+}
+
+fn send_to_pool(
+    ctx: &CommandContext,
+    mut pool: Rc<Pool>,
+    request: &PreparedRequest,
+) -> CliResult<String> {
+    let is_stale = ctx
+        .get_pool_idle_time()
+        .map(|idle_time| idle_time > POOL_IDLE_TIMEOUT)
+        .unwrap_or(false);
+    if is_stale {
+        println_warn!(
+            ctx,
+            "Pool connection has been idle for a long time. Reconnecting..."
+        );
+        pool = reconnect_pool(ctx, &pool)?;
+    }
+
+    let mut response = Ledger::submit_request(&pool, request);
+
+    if let Err(CliError::VdrError(ref err)) = response {
+        if matches!(
+            err.kind(),
+            VdrErrorKind::PoolTimeout | VdrErrorKind::PoolNoConsensus | VdrErrorKind::Unavailable
+        ) {
+            println_warn!(
+                ctx,
+                "Request to the pool has failed. Reconnecting and sending it again..."
+            );
+            pool = reconnect_pool(ctx, &pool)?;
+            response = Ledger::submit_request(&pool, request);
+        }
+    }
+
+    ctx.set_pool_last_activity();
+    response
+}
+
+fn is_pool_unreachable(err: &CliError) -> bool {
+    match err {
+        CliError::VdrError(err) => matches!(
+            err.kind(),
+            VdrErrorKind::Connection | VdrErrorKind::Resource | VdrErrorKind::Unavailable
+        ),
+        _ => false,
+    }
+}
+
+fn is_outcome_unknown(err: &CliError) -> bool {
+    match err {
+        CliError::VdrError(err) => matches!(
+            err.kind(),
+            VdrErrorKind::PoolNoConsensus | VdrErrorKind::PoolTimeout
+        ),
+        _ => false,
+    }
+}
+
+/// Warn when the ledger state proved by a read reply is older than the configured freshness threshold.
+/// In strict mode such replies are rejected.
+pub fn check_freshness(ctx: &CommandContext, response: &str) -> Result<(), ()> {
+    let threshold = match ctx.get_freshness_threshold() {
+        Some(threshold) => threshold,
+        None => return Ok(()),
+    };
+
+    // Write replies and replies confirmed by consensus of nodes carry no state proof
+    let timestamp = match serde_json::from_str::<JsonValue>(response)
+        .ok()
+        .and_then(|response| LedgerHelpers::get_state_proof_timestamp(&response))
+    {
+        Some(timestamp) => timestamp,
+        None => return Ok(()),
+    };
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let age = now.saturating_sub(timestamp);
+    if age <= threshold {
+        return Ok(());
+    }
+
+    let message = format!(
+        "Ledger state in the reply has been signed at {} ({} ago) which exceeds the freshness threshold of {}",
+        timestamp_to_datetime(ctx, timestamp as i64),
+        format_duration(age),
+        format_duration(threshold)
+    );
+    if ctx.is_freshness_strict() {
+        println_err!(ctx, "{}. The reply has been rejected.", message);
+        Err(())
+    } else {
+        println_warn!(ctx, "{}. The data may be outdated.", message);
+        Ok(())
+    }
+}
+
+fn reconnect_pool(ctx: &CommandContext, pool: &Pool) -> CliResult<Rc<Pool>> {
+    let pool = pool.reconnect()?;
+    println_succ!(ctx, "Pool \"{}\" has been reconnected", pool.name);
+    ctx.set_connected_pool(pool);
+    ctx.get_connected_pool()
+        .ok_or_else(|| CliError::InvalidEntityState("There is no opened pool now".to_string()))
+}
+
+pub fn get_custom_roles(ctx: &CommandContext) -> HashMap<String, String> {
+    ctx.get_connected_pool()
+        .map(|pool| pool.custom_roles.clone())
+        .unwrap_or_default()
+}
+
+pub fn role_list(ctx: &CommandContext) -> Vec<String> {
+    LedgerHelpers::get_role_names(&get_custom_roles(ctx))
+}
+
+pub fn handle_transaction_response(
+    ctx: &CommandContext,
+    response: Response<JsonValue>,
+) -> Result<JsonValue, ()> {
     match response {
         Response {
             op: ResponseType::REPLY,
@@ -120,12 +531,9 @@ pub fn handle_transaction_response(response: Response<JsonValue>) -> Result<Json
             op: ResponseType::REJECT,
             result: None,
             reason: Some(reason),
-        } => {
-            println_err!("Transaction has been rejected: {}", reason);
-            Err(())
-        }
+        } => Err(CliError::rejected(&reason).print(ctx, None)),
         _ => {
-            println_err!("Invalid data has been received");
+            println_err!(ctx, "Invalid data has been received");
             Err(())
         }
     }
@@ -163,12 +571,13 @@ pub fn send_nym(
     let response_json = Ledger::sign_and_submit_request(&pool, &store, &did, &mut request)?;
     let response: Response<serde_json::Value> =
         serde_json::from_str::<Response<serde_json::Value>>(&response_json)?;
-    let response = handle_transaction_response(response)
+    let response = handle_transaction_response(ctx, response)
         .map_err(|_| CliError::InvalidInput("".to_string()))?;
     Ok(response)
 }
 
 pub fn get_current_verkey(
+    ctx: &CommandContext,
     pool: &Pool,
     store: &Wallet,
     did: &DidValue,
@@ -176,26 +585,73 @@ pub fn get_current_verkey(
     //TODO: There nym is requested. Due to freshness issues response might be stale or outdated. Something should be done with it
     let response_json = Ledger::build_get_nym_request(Some(pool), Some(did), did)
         .and_then(|mut request| Ledger::sign_and_submit_request(pool, store, did, &mut request))
-        .map_err(|err| println_err!("{}", err.message(Some(&pool.name))))?;
+        .map_err(|err| err.print(ctx, Some(&pool.name)))?;
     let response: Response<serde_json::Value> =
         serde_json::from_str::<Response<serde_json::Value>>(&response_json)
-            .map_err(|err| println_err!("Invalid data has been received: {:?}", err))?;
-    let result = handle_transaction_response(response)?;
+            .map_err(|err| println_err!(ctx, "Invalid data has been received: {:?}", err))?;
+    let result = handle_transaction_response(ctx, response)?;
     let data = serde_json::from_str::<serde_json::Value>(&result["data"].as_str().unwrap_or("{}"))
-        .map_err(|_| println_err!("Wrong data has been received"))?;
+        .map_err(|_| println_err!(ctx, "Wrong data has been received"))?;
     let verkey = data["verkey"].as_str().map(String::from);
     Ok(verkey)
 }
 
+/// Role of the DID on the ledger. `None` if the DID has no role or the request failed.
+pub fn get_did_role(pool: &Pool, did: &DidValue) -> Option<String> {
+    let response = Ledger::build_get_nym_request(Some(pool), None, did)
+        .and_then(|request| Ledger::submit_request(pool, &request))
+        .ok()?;
+    let response = serde_json::from_str::<JsonValue>(&response).ok()?;
+    let data = serde_json::from_str::<JsonValue>(response["result"]["data"].as_str()?).ok()?;
+    data["role"].as_str().map(String::from)
+}
+
+/// Sequence number of the object already written to the ledger if `is_identical` accepts its data.
+/// The GET_SCHEMA or GET_CRED_DEF read is sent only when the write request is going to be sent as well.
+pub fn find_identical_object(
+    ctx: &CommandContext,
+    params: &CommandParams,
+    build_request: impl FnOnce(&Pool) -> CliResult<PreparedRequest>,
+    is_identical: impl FnOnce(&JsonValue) -> bool,
+) -> Result<Option<i64>, ()> {
+    let send = ParamParser::get_opt_bool_param(ctx, "send", params)?.unwrap_or(SEND_REQUEST);
+    let pool = match ctx.get_connected_pool() {
+        Some(pool) if send => pool,
+        _ => return Ok(None),
+    };
+    let request = build_request(&pool).map_err(|err| err.print(ctx, None))?;
+    let response = submit_request(ctx, &request)?;
+    let response = serde_json::from_str::<JsonValue>(&response)
+        .map_err(|err| println_err!(ctx, "Invalid transaction response: {:?}", err))?;
+
+    let result = &response["result"];
+    match result["seqNo"].as_i64() {
+        Some(seq_no) if is_identical(&result["data"]) => Ok(Some(seq_no)),
+        _ => Ok(None),
+    }
+}
+
 pub fn get_active_transaction_author_agreement(
+    ctx: &CommandContext,
     pool: &Pool,
 ) -> Result<Option<(String, String, Option<String>)>, ()> {
-    let response = Ledger::build_get_txn_author_agreement_request(Some(pool), None, None)
-        .and_then(|request| Ledger::submit_request(pool, &request))
-        .map_err(|err| println_err!("{}", err.message(None)))?;
+    get_transaction_author_agreement(ctx, pool, None)
+}
+
+/// Text, version and digest of the given agreement version, the active agreement if `version` is `None`.
+pub fn get_transaction_author_agreement(
+    ctx: &CommandContext,
+    pool: &Pool,
+    version: Option<&str>,
+) -> Result<Option<(String, String, Option<String>)>, ()> {
+    let data = version.map(|version| json!({ "version": version }).to_string());
+    let response =
+        Ledger::build_get_txn_author_agreement_request(Some(pool), None, data.as_deref())
+            .and_then(|request| Ledger::submit_request(pool, &request))
+            .map_err(|err| err.print(ctx, None))?;
 
     let response = serde_json::from_str::<JsonValue>(&response)
-        .map_err(|err| println_err!("Invalid transaction response: {:?}", err))?;
+        .map_err(|err| println_err!(ctx, "Invalid transaction response: {:?}", err))?;
 
     let text = response["result"]["data"]["text"].as_str();
     let version = response["result"]["data"]["version"].as_str();
@@ -225,7 +681,7 @@ pub fn sign_and_submit_action(
         None => None,
     };
 
-    Ledger::sign_request(wallet, submitter_did, request)?;
+    Ledger::sign_request(Some(pool), wallet, submitter_did, request)?;
     let replies =
         Ledger::submit_action(pool, &request, nodes.as_ref().map(String::as_ref), timeout)?;
 
@@ -239,12 +695,33 @@ pub fn sign_and_submit_action(
 
 pub fn set_author_agreement(ctx: &CommandContext, request: &mut PreparedRequest) -> Result<(), ()> {
     let pool = ctx.get_connected_pool();
+    // Checked against the cached capabilities so the request is not rejected by the ledger later
+    let capabilities = pool.as_ref().and_then(|pool| pool.capabilities());
 
     if let Some((text, version, acc_mech_type, time_of_acceptance)) =
         ctx.get_transaction_author_info()
     {
         if acc_mech_type.is_empty() {
-            println_err!("Transaction author agreement Acceptance Mechanism isn't set.");
+            println_err!(
+                ctx,
+                "Transaction author agreement Acceptance Mechanism isn't set."
+            );
+            return Err(());
+        }
+
+        if let Some(capabilities) = capabilities.as_ref().filter(|capabilities| {
+            !capabilities.acceptance_mechanisms.is_empty()
+                && !capabilities.acceptance_mechanisms.contains(&acc_mech_type)
+        }) {
+            println_err!(
+                ctx,
+                "Acceptance Mechanism \"{}\" is not in the Acceptance Mechanisms List of the pool: {}.",
+                acc_mech_type,
+                capabilities.acceptance_mechanisms.join(", ")
+            );
+            println!(
+                "Use `pool refresh-capabilities` command if the list has been changed recently."
+            );
             return Err(());
         }
 
@@ -257,27 +734,80 @@ pub fn set_author_agreement(ctx: &CommandContext, request: &mut PreparedRequest)
             &acc_mech_type,
             time_of_acceptance,
         )
-        .map_err(|err| println_err!("{}", err.message(None)))?;
-    };
+        .map_err(|err| err.print(ctx, None))?;
+    } else if capabilities
+        .map(|capabilities| capabilities.taa_required)
+        .unwrap_or(false)
+    {
+        println_err!(
+            ctx,
+            "Transaction Author Agreement of the pool must be accepted to send the transaction."
+        );
+        println!("Use `pool show-taa` command to accept the Agreement or `pool refresh-capabilities` command if it has been disabled recently.");
+        return Err(());
+    }
+    Ok(())
+}
+
+/// Schema in the AnonCreds object format built from GET_SCHEMA reply result.
+pub fn schema_to_anoncreds(issuer_id: &str, result: &JsonValue) -> JsonValue {
+    json!({
+        "issuerId": issuer_id,
+        "name": result["data"]["name"],
+        "version": result["data"]["version"],
+        "attrNames": result["data"]["attr_names"],
+    })
+}
+
+/// Credential definition in the AnonCreds object format built from GET_CLAIM_DEF reply result.
+/// Legacy Indy credential definitions reference the schema by its sequence number.
+pub fn cred_def_to_anoncreds(issuer_id: &str, result: &JsonValue) -> JsonValue {
+    json!({
+        "issuerId": issuer_id,
+        "schemaId": result["ref"].to_string(),
+        "type": result["signature_type"],
+        "tag": result["tag"],
+        "value": result["data"],
+    })
+}
+
+/// Write ledger object into the file in the AnonCreds object format expected by Aries frameworks.
+pub fn export_anoncreds_object(
+    ctx: &CommandContext,
+    file: &str,
+    id: &str,
+    object: &JsonValue,
+) -> Result<(), ()> {
+    let content = serde_json::to_string_pretty(object)
+        .map_err(|err| println_err!(ctx, "Invalid data has been received: {:?}", err))?;
+    write_file(file, &content)
+        .map_err(|err| println_err!(ctx, "Cannot export object into the file: {:?}", err))?;
+    println_succ!(
+        ctx,
+        "Object \"{}\" has been exported into \"{}\" file.",
+        id,
+        file
+    );
     Ok(())
 }
 
 pub fn print_transaction_response(
+    ctx: &CommandContext,
     result: JsonValue,
     title: &str,
     data_sub_field: Option<&str>,
     data_headers: &[(&str, &str)],
     skip_empty: bool,
 ) {
-    println_succ!("{}", title);
+    println_succ!(ctx, "{}", title);
 
-    let (metadata_headers, metadata, data) = match parse_transaction_response(result) {
+    let (metadata_headers, metadata, data) = match parse_transaction_response(ctx, result) {
         Ok(val) => val,
         Err(_) => return,
     };
 
-    println_succ!("Metadata:");
-    print_table(&metadata, &metadata_headers);
+    println_succ!(ctx, "Metadata:");
+    print_table(ctx, &metadata, &metadata_headers);
 
     let data = if data_sub_field.is_some() {
         &data[data_sub_field.unwrap()]
@@ -289,6 +819,6 @@ pub fn print_transaction_response(
         data_headers.retain(|&(ref key, _)| !data[key].is_null());
     }
 
-    println_succ!("Data:");
-    print_table(data, &data_headers);
+    println_succ!(ctx, "Data:");
+    print_table(ctx, data, &data_headers);
 }
@@ -4,11 +4,18 @@
 */
 use crate::{
     command_executor::{Command, CommandContext, CommandMetadata, CommandParams},
+    commands::explore::{fetch_txns, DOMAIN_LEDGER},
+    error::CliError,
     params_parser::ParamParser,
-    tools::ledger::{Ledger, Response},
+    tools::{
+        anoncreds::CredentialDefinitionKeys,
+        ledger::{Ledger, Response},
+    },
 };
 
+use indy_utils::did::DidValue;
 use indy_vdr::ledger::{
+    constants::SCHEMA,
     identifiers::{CredentialDefinitionId, SchemaId},
     requests::cred_def::{
         CredentialDefinition, CredentialDefinitionData, CredentialDefinitionV1, SignatureType,
@@ -17,6 +24,7 @@ use indy_vdr::ledger::{
 use serde_json::Value as JsonValue;
 
 use super::common::{
+    cred_def_to_anoncreds, export_anoncreds_object, find_identical_object,
     handle_transaction_response, print_transaction_response, set_author_agreement,
 };
 
@@ -27,14 +35,19 @@ pub mod cred_def_command {
                 .add_required_param("schema_id", "Sequence number of schema")
                 .add_required_param("signature_type", "Signature type (only CL supported now)")
                 .add_optional_param("tag", "Allows to distinct between credential definitions for the same issuer and schema. Note that it is mandatory for indy-node version 1.4.x and higher")
-                .add_required_param("primary", "Primary key in json format")
+                .add_optional_param("primary", "Primary key in json format. If omitted, the keys are generated for the schema read from the Ledger \
+                    and the private part is stored in the opened wallet")
                 .add_optional_param("revocation", "Revocation key in json format")
+                .add_optional_param("support_revocation", "Generate the revocation key as well (False by default). Used only if `primary` is omitted")
                 .add_optional_param("sign","Sign the request (True by default)")
                 .add_optional_param("send","Send the request to the Ledger (True by default). If false then created request will be printed and stored into CLI context.")
+                .add_optional_param("queue","Spool the request to the local queue if the pool cannot be reached (False by default). Queued requests are sent by `queue flush` command.")
                 .add_optional_param("endorser","DID of the Endorser that will submit the transaction to the ledger later. \
                     Note that specifying of this parameter implies send=false so the transaction will be prepared to pass to the endorser instead of sending to the ledger.\
                     The created request will be printed and stored into CLI context.")
                 .add_example(r#"ledger cred-def schema_id=1 signature_type=CL tag=1 primary={"n":"1","s":"2","rms":"3","r":{"age":"4","name":"5"},"rctxt":"6","z":"7"}"#)
+                .add_example("ledger cred-def schema_id=1 signature_type=CL tag=1")
+                .add_example("ledger cred-def schema_id=1 signature_type=CL tag=1 support_revocation=true")
                 .finalize()
     );
 
@@ -45,18 +58,88 @@ pub mod cred_def_command {
         let submitter_did = ctx.ensure_active_did()?;
         let pool = ctx.get_connected_pool();
 
-        let schema_id = ParamParser::get_str_param("schema_id", params)?;
-        let signature_type = ParamParser::get_str_param("signature_type", params)?;
-        let tag = ParamParser::get_opt_str_param("tag", params)?.unwrap_or("");
+        let schema_id = ParamParser::get_str_param(ctx, "schema_id", params)?;
+        let signature_type = ParamParser::get_str_param(ctx, "signature_type", params)?;
+        let tag = ParamParser::get_opt_str_param(ctx, "tag", params)?.unwrap_or("");
 
-        let primary = ParamParser::get_object_param("primary", params)?;
-        let revocation = ParamParser::get_opt_object_param("revocation", params)?;
+        let primary = ParamParser::get_opt_object_param(ctx, "primary", params)?;
+        let revocation = ParamParser::get_opt_object_param(ctx, "revocation", params)?;
+        let support_revocation =
+            ParamParser::get_opt_bool_param(ctx, "support_revocation", params)?.unwrap_or(false);
 
+        let schema_seq_no = schema_id;
         let schema_id = SchemaId::from(schema_id.to_string());
         let id = CredentialDefinitionId::new(&submitter_did, &schema_id, signature_type, tag);
 
         let signature_type = SignatureType::from_str(signature_type)
-            .map_err(|_| println_err!("Unsupported signature_type {}", signature_type))?;
+            .map_err(|_| println_err!(ctx, "Unsupported signature_type {}", signature_type))?;
+
+        let (primary, revocation) = match primary {
+            Some(primary) => (primary, revocation),
+            None => {
+                if revocation.is_some() {
+                    println_err!(
+                        ctx,
+                        "\"revocation\" parameter requires \"primary\" to be specified"
+                    );
+                    return Err(());
+                }
+                // Keys stored by a previous attempt are reused so that resending never replaces them
+                let keys = match CredentialDefinitionKeys::get(&wallet, &id.0) {
+                    Ok(keys) => {
+                        println_warn!(
+                            ctx,
+                            "Keys of credential definition \"{}\" already exist in the wallet and will be used",
+                            id.0
+                        );
+                        keys
+                    }
+                    Err(CliError::NotFound(_)) => {
+                        let keys = generate_keys(
+                            ctx,
+                            &submitter_did,
+                            schema_seq_no,
+                            tag,
+                            support_revocation,
+                        )?;
+                        keys.store(&wallet, &id.0)
+                            .map_err(|err| err.print(ctx, None))?;
+                        println_succ!(
+                            ctx,
+                            "Keys of credential definition \"{}\" have been generated and stored in the wallet",
+                            id.0
+                        );
+                        keys
+                    }
+                    Err(err) => return Err(err.print(ctx, None)),
+                };
+                let public = keys.public();
+                let revocation = match public["revocation"] {
+                    JsonValue::Null => None,
+                    ref revocation => Some(revocation.clone()),
+                };
+                (public["primary"].clone(), revocation)
+            }
+        };
+
+        let identical = find_identical_object(
+            ctx,
+            params,
+            |pool| Ledger::build_get_cred_def_request(Some(pool), None, &id),
+            |data| {
+                data["primary"] == primary
+                    && revocation.as_ref().unwrap_or(&JsonValue::Null) == &data["revocation"]
+            },
+        )?;
+        if let Some(seq_no) = identical {
+            println_succ!(
+                ctx,
+                "Credential definition \"{}\" is already present on the Ledger (seqNo {}). The transaction has not been sent.",
+                id.0,
+                seq_no
+            );
+            return Ok(());
+        }
 
         let cred_def = CredentialDefinition::CredentialDefinitionV1(CredentialDefinitionV1 {
             id,
@@ -70,15 +153,16 @@ pub mod cred_def_command {
         });
 
         let mut request = Ledger::build_cred_def_request(pool.as_deref(), &submitter_did, cred_def)
-            .map_err(|err| println_err!("{}", err.message(None)))?;
+            .map_err(|err| err.print(ctx, None))?;
 
         set_author_agreement(ctx, &mut request)?;
 
         let (_, response): (String, Response<JsonValue>) =
             send_write_request!(ctx, params, &mut request, &wallet, &submitter_did);
 
-        handle_transaction_response(response).map(|result| {
+        handle_transaction_response(ctx, response).map(|result| {
             print_transaction_response(
+                ctx,
                 result,
                 "NodeConfig request has been sent to Ledger.",
                 Some("data"),
@@ -90,6 +174,53 @@ pub mod cred_def_command {
         trace!("execute <<");
         Ok(())
     }
+
+    // The schema is read from the ledger by its sequence number as only the attribute names are needed
+    fn generate_keys(
+        ctx: &CommandContext,
+        submitter_did: &DidValue,
+        schema_seq_no: &str,
+        tag: &str,
+        support_revocation: bool,
+    ) -> Result<CredentialDefinitionKeys, ()> {
+        let seq_no = schema_seq_no.parse::<i32>().map_err(|_| {
+            println_err!(
+                ctx,
+                "Keys can be generated only for schema given by its sequence number, got \"{}\"",
+                schema_seq_no
+            )
+        })?;
+
+        let txn = fetch_txns(ctx, DOMAIN_LEDGER, &[seq_no])?
+            .pop()
+            .flatten()
+            .filter(|txn| txn["txn"]["type"].as_str() == Some(SCHEMA))
+            .ok_or_else(|| println_err!(ctx, "Schema with sequence number {} not found", seq_no))?;
+
+        let schema_issuer = txn["txn"]["metadata"]["from"].as_str().unwrap_or_default();
+        let data = &txn["txn"]["data"]["data"];
+        let schema = json!({
+            "issuerId": schema_issuer,
+            "name": data["name"],
+            "version": data["version"],
+            "attrNames": data["attr_names"],
+        });
+        let schema_id = format!(
+            "{}:2:{}:{}",
+            schema_issuer,
+            data["name"].as_str().unwrap_or_default(),
+            data["version"].as_str().unwrap_or_default()
+        );
+
+        CredentialDefinitionKeys::generate(
+            &submitter_did.0,
+            &schema_id,
+            &schema,
+            tag,
+            support_revocation,
+        )
+        .map_err(|err| err.print(ctx, None))
+    }
 }
 
 pub mod get_cred_def_command {
@@ -101,7 +232,9 @@ pub mod get_cred_def_command {
                 .add_optional_param("tag", "Allows to distinct between credential definitions for the same issuer and schema. Note that it is mandatory for indy-node version 1.4.x and higher")
                 .add_required_param("origin", "Credential definition owner DID")
                 .add_optional_param("send","Send the request to the Ledger (True by default). If false then created request will be printed and stored into CLI context.")
+                .add_optional_param("export", "Path to file to write the received Credential Definition to in AnonCreds object format (as consumed by ACA-Py and AFJ)")
                 .add_example("ledger get-cred-def schema_id=1 signature_type=CL tag=1 origin=VsKV7grR1BUE29mG2Fm2kX")
+                .add_example("ledger get-cred-def schema_id=1 signature_type=CL tag=1 origin=VsKV7grR1BUE29mG2Fm2kX export=/home/cred_def.json")
                 .finalize()
     );
 
@@ -111,36 +244,43 @@ pub mod get_cred_def_command {
         let submitter_did = ctx.get_active_did()?;
         let pool = ctx.get_connected_pool();
 
-        let schema_id = ParamParser::get_str_param("schema_id", params)?;
-        let signature_type = ParamParser::get_str_param("signature_type", params)?;
-        let tag = ParamParser::get_opt_str_param("tag", params)?.unwrap_or("");
-        let origin = ParamParser::get_did_param("origin", params)?;
+        let schema_id = ParamParser::get_str_param(ctx, "schema_id", params)?;
+        let signature_type = ParamParser::get_str_param(ctx, "signature_type", params)?;
+        let tag = ParamParser::get_opt_str_param(ctx, "tag", params)?.unwrap_or("");
+        let origin = ParamParser::get_did_param(ctx, "origin", params)?;
+        let export = ParamParser::get_opt_str_param(ctx, "export", params)?;
 
         let schema_id = SchemaId::from(schema_id.to_string());
         let id = CredentialDefinitionId::new(&origin, &schema_id, signature_type, tag);
 
-        let request =
+        let mut request =
             Ledger::build_get_cred_def_request(pool.as_deref(), submitter_did.as_deref(), &id)
-                .map_err(|err| println_err!("{}", err.message(None)))?;
+                .map_err(|err| err.print(ctx, None))?;
 
-        let (_, response) = send_read_request!(&ctx, params, &request);
+        let (_, response) = send_read_request!(&ctx, params, &mut request);
 
         if let Some(result) = response.result.as_ref() {
             if !result["seqNo"].is_i64() {
-                println_err!("Credential Definition not found");
+                println_err!(ctx, "Credential Definition not found");
                 return Err(());
             }
         };
 
-        handle_transaction_response(response).map(|result| {
-            print_transaction_response(
-                result,
-                "Following Credential Definition has been received.",
-                Some("data"),
-                &[("primary", "Primary Key"), ("revocation", "Revocation Key")],
-                true,
-            )
-        })?;
+        let result = handle_transaction_response(ctx, response)?;
+
+        if let Some(file) = export {
+            let cred_def = cred_def_to_anoncreds(&origin.to_string(), &result);
+            export_anoncreds_object(ctx, file, &id.0, &cred_def)?;
+        }
+
+        print_transaction_response(
+            ctx,
+            result,
+            "Following Credential Definition has been received.",
+            Some("data"),
+            &[("primary", "Primary Key"), ("revocation", "Revocation Key")],
+            true,
+        );
 
         trace!("execute <<");
         Ok(())
@@ -157,6 +297,7 @@ pub mod tests {
             wallet::tests::{close_wallet, open_wallet},
         },
         ledger::tests::{use_new_endorser, use_trustee},
+        utils::{environment::EnvironmentUtils, file::read_file},
     };
     use indy_utils::{did::DidValue, Qualifiable};
     use indy_vdr::ledger::requests::schema::{AttributeNames, Schema, SchemaV1};
@@ -186,6 +327,52 @@ pub mod tests {
             tear_down_with_wallet_and_pool(&ctx);
         }
 
+        #[test]
+        pub fn cred_def_works_for_already_present_cred_def() {
+            let ctx = setup_with_wallet_and_pool();
+            let (did, _) = use_new_endorser(&ctx);
+            let schema_id = send_schema(&ctx, &did);
+            for _ in 0..2 {
+                let cmd = cred_def_command::new();
+                let mut params = CommandParams::new();
+                params.insert("schema_id", schema_id.clone());
+                params.insert("signature_type", "CL".to_string());
+                params.insert("tag", "TAG".to_string());
+                params.insert("primary", CRED_DEF_DATA.to_string());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            assert!(ensure_cred_def_added(&ctx, &did, &schema_id).is_ok());
+            tear_down_with_wallet_and_pool(&ctx);
+        }
+
+        #[test]
+        pub fn cred_def_works_for_generated_keys() {
+            let ctx = setup_with_wallet_and_pool();
+            let (did, _) = use_new_endorser(&ctx);
+            let schema_id = send_schema(&ctx, &did);
+            {
+                let cmd = cred_def_command::new();
+                let mut params = CommandParams::new();
+                params.insert("schema_id", schema_id.clone());
+                params.insert("signature_type", "CL".to_string());
+                params.insert("tag", "TAG".to_string());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            assert!(ensure_cred_def_added(&ctx, &did, &schema_id).is_ok());
+            {
+                let wallet = ctx.ensure_opened_wallet().unwrap();
+                let id = CredentialDefinitionId::new(
+                    &DidValue(did.clone()),
+                    &SchemaId::from(schema_id),
+                    "CL",
+                    "TAG",
+                );
+                let keys = CredentialDefinitionKeys::get(&wallet, &id.0).unwrap();
+                assert!(keys.private.is_object());
+            }
+            tear_down_with_wallet_and_pool(&ctx);
+        }
+
         #[test]
         pub fn cred_def_works_for_missed_required_params() {
             let ctx = setup_with_wallet_and_pool();
@@ -247,7 +434,7 @@ pub mod tests {
                 cmd.execute(&ctx, &params).unwrap();
             }
             assert!(ensure_cred_def_added(&ctx, &did, &schema_id).is_err());
-            assert!(ctx.get_context_transaction().is_some());
+            assert!(ctx.get_context_transaction(None).is_some());
             tear_down_with_wallet_and_pool(&ctx);
         }
     }
@@ -282,6 +469,41 @@ pub mod tests {
             tear_down_with_wallet_and_pool(&ctx);
         }
 
+        #[test]
+        pub fn get_cred_def_works_for_export() {
+            let ctx = setup_with_wallet_and_pool();
+            let (did, _) = use_new_endorser(&ctx);
+            let schema_id = send_schema(&ctx, &did);
+            {
+                let cmd = cred_def_command::new();
+                let mut params = CommandParams::new();
+                params.insert("schema_id", schema_id.clone());
+                params.insert("signature_type", "CL".to_string());
+                params.insert("tag", "TAG".to_string());
+                params.insert("primary", CRED_DEF_DATA.to_string());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            assert!(ensure_cred_def_added(&ctx, &did, &schema_id).is_ok());
+            let path = EnvironmentUtils::tmp_file_path("cred_def.json");
+            {
+                let cmd = get_cred_def_command::new();
+                let mut params = CommandParams::new();
+                params.insert("schema_id", schema_id.clone());
+                params.insert("signature_type", "CL".to_string());
+                params.insert("tag", "TAG".to_string());
+                params.insert("origin", did.clone());
+                params.insert("export", path.to_str().unwrap().to_string());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            let cred_def = serde_json::from_str::<JsonValue>(&read_file(&path).unwrap()).unwrap();
+            assert_eq!(cred_def["issuerId"], did);
+            assert_eq!(cred_def["schemaId"], schema_id);
+            assert_eq!(cred_def["type"], "CL");
+            assert_eq!(cred_def["tag"], "TAG");
+            assert!(cred_def["value"]["primary"].is_object());
+            tear_down_with_wallet_and_pool(&ctx);
+        }
+
         #[test]
         pub fn get_cred_def_works_for_unknown_cred_def() {
             let ctx = setup_with_wallet_and_pool();
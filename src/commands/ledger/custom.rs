@@ -4,13 +4,18 @@
 */
 use crate::{
     command_executor::{Command, CommandContext, CommandMetadata, CommandParams},
+    error::CliError,
     params_parser::ParamParser,
     tools::ledger::{Ledger, Response, ResponseType},
+    utils::renderer::print_value,
+    wallet::ensure_key_rotated,
 };
 
 use indy_vdr::pool::PreparedRequest;
 use serde_json::Value as JsonValue;
 
+use super::common::submit_request;
+
 pub mod custom_command {
     use super::*;
 
@@ -26,20 +31,20 @@ pub mod custom_command {
     fn execute(ctx: &CommandContext, params: &CommandParams) -> Result<(), ()> {
         trace!("execute >> ctx {:?} params {:?}", ctx, params);
 
-        let pool = ctx.ensure_connected_pool()?;
+        ctx.ensure_connected_pool()?;
 
-        let txn = ParamParser::get_str_param("txn", params)?;
-        let sign = ParamParser::get_opt_bool_param("sign", params)?.unwrap_or(false);
+        let txn = ParamParser::get_str_param(ctx, "txn", params)?;
+        let sign = ParamParser::get_opt_bool_param(ctx, "sign", params)?.unwrap_or(false);
 
         let mut transaction = txn.to_string();
 
         if txn == "context" {
-            let context_txn = ctx.get_context_transaction();
+            let context_txn = ctx.get_context_transaction(None);
 
             match context_txn {
                 Some(txn_) => {
                     println!("Transaction stored into context: {}.", txn_);
-                    println!("Would you like to send it? (y/n)");
+                    crate::utils::term::print_prompt("Would you like to send it? (y/n)");
 
                     let use_transaction = crate::command_executor::wait_for_user_reply(ctx);
 
@@ -51,7 +56,7 @@ pub mod custom_command {
                     transaction = txn_.to_string();
                 }
                 None => {
-                    println_err!("There is not a transaction stored into CLI context.");
+                    println_err!(ctx, "There is not a transaction stored into CLI context.");
                     println!("You either need to load transaction using `ledger load-transaction`, or \
                         build a transaction (with passing a `send=false`) to wallet it into CLI context.");
                     return Err(());
@@ -60,20 +65,25 @@ pub mod custom_command {
         }
 
         let mut transaction = PreparedRequest::from_request_json(transaction)
-            .map_err(|_| println_err!("Invalid formatted transaction provided."))?;
+            .map_err(|_| println_err!(ctx, "Invalid formatted transaction provided."))?;
 
-        let response_json = if sign {
+        if sign {
             let wallet = ctx.ensure_opened_wallet()?;
             let submitter_did = ctx.ensure_active_did()?;
-            Ledger::sign_and_submit_request(&pool, &wallet, &submitter_did, &mut transaction)
-                .map_err(|err| println_err!("{}", err.message(Some(&pool.name))))?
-        } else {
-            Ledger::submit_request(&pool, &transaction)
-                .map_err(|err| println_err!("{}", err.message(Some(&pool.name))))?
-        };
+            ensure_key_rotated(ctx, &wallet)?;
+            Ledger::sign_request(
+                ctx.get_connected_pool().as_deref(),
+                &wallet,
+                &submitter_did,
+                &mut transaction,
+            )
+            .map_err(|err| err.print(ctx, None))?;
+        }
+
+        let response_json = submit_request(ctx, &transaction)?;
 
         let response = serde_json::from_str::<Response<JsonValue>>(&response_json)
-            .map_err(|err| println_err!("Invalid data has been received: {:?}", err))?;
+            .map_err(|err| println_err!(ctx, "Invalid data has been received: {:?}", err))?;
 
         match response {
             Response {
@@ -81,7 +91,12 @@ pub mod custom_command {
                 result: Some(_),
                 reason: None,
             } => {
-                println!("Response: \n{}", response_json);
+                println_acc!(ctx, "Response:");
+                print_value(
+                    ctx,
+                    &serde_json::from_str::<JsonValue>(&response_json)
+                        .unwrap_or_else(|_| json!(response_json)),
+                );
             }
             Response {
                 op: ResponseType::REQNACK,
@@ -92,11 +107,9 @@ pub mod custom_command {
                 op: ResponseType::REJECT,
                 result: None,
                 reason: Some(reason),
-            } => {
-                println_err!("Transaction has been rejected: {}", reason);
-            }
+            } => CliError::rejected(&reason).print(ctx, None),
             _ => {
-                println_err!("Invalid data has been received");
+                println_err!(ctx, "Invalid data has been received");
             }
         };
 
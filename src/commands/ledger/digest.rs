@@ -0,0 +1,96 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::{
+    command_executor::{Command, CommandContext, CommandMetadata, CommandParams},
+    params_parser::ParamParser,
+};
+
+use indy_vdr::pool::PreparedRequest;
+
+pub mod digest_command {
+    use super::*;
+    use indy_utils::hash::SHA256;
+
+    command!(CommandMetadata::build(
+        "digest",
+        "Show canonical serialization and SHA-256 payload digest of transaction."
+    )
+    .add_optional_param(
+        "txn",
+        "Transaction to compute digest for. Skip to use a transaction stored into CLI context."
+    )
+    .add_example(r#"ledger digest txn={"reqId":123456789,"type":"100"}"#)
+    .add_example("ledger digest")
+    .finalize());
+
+    fn execute(ctx: &CommandContext, params: &CommandParams) -> Result<(), ()> {
+        trace!("execute >> ctx {:?} params {:?}", ctx, params);
+
+        let param_txn = ParamParser::get_opt_str_param(ctx, "txn", params)?;
+
+        let txn = get_transaction_to_use!(ctx, param_txn);
+
+        let serialized = txn.get_signature_input().map_err(|err| {
+            println_err!(ctx, "Unable to serialize transaction: {}", err);
+        })?;
+        let digest = hex::encode(SHA256::digest(serialized.as_bytes()));
+
+        println_succ!(ctx, "Canonical serialization:");
+        println!("     {}", serialized);
+        println_succ!(ctx, "Payload digest (SHA-256):");
+        println!("     {}", digest);
+
+        trace!("execute <<");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::{
+        commands::{setup, tear_down},
+        ledger::tests::TRANSACTION,
+    };
+
+    mod digest {
+        use super::*;
+
+        #[test]
+        pub fn digest_works() {
+            let ctx = setup();
+            {
+                let cmd = digest_command::new();
+                let mut params = CommandParams::new();
+                params.insert("txn", TRANSACTION.to_string());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            tear_down();
+        }
+
+        #[test]
+        pub fn digest_works_for_context_transaction() {
+            let ctx = setup();
+            ctx.set_context_transaction(None, Some(TRANSACTION.to_string()));
+            {
+                let cmd = digest_command::new();
+                let params = CommandParams::new();
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            tear_down();
+        }
+
+        #[test]
+        pub fn digest_works_for_no_transaction() {
+            let ctx = setup();
+            {
+                let cmd = digest_command::new();
+                let params = CommandParams::new();
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            tear_down();
+        }
+    }
+}
@@ -5,12 +5,26 @@
 use crate::{
     command_executor::{Command, CommandContext, CommandMetadata, CommandParams},
     params_parser::ParamParser,
-    tools::ledger::{parse_transaction_response, Ledger, Response},
-    utils::table::print_table,
+    tools::{
+        did::{Did, DidInfo},
+        ledger::{
+            parse_transaction_response, permissions::get_role, Ledger, LedgerHelpers, Response,
+        },
+        wallet::Wallet,
+    },
+    utils::{
+        file::{read_file, write_file},
+        table::{print_list_table, print_table},
+    },
+    wallet::ensure_key_rotated,
 };
 
-use super::common::handle_transaction_response;
-use indy_vdr::pool::PreparedRequest;
+use super::{
+    common::{get_custom_roles, handle_transaction_response},
+    verify_signatures::{verify_author_signature, SignatureStatus},
+};
+use indy_utils::did::DidValue;
+use indy_vdr::{ledger::constants::NYM, pool::PreparedRequest};
 use serde_json::Value as JsonValue;
 
 pub mod endorse_transaction_command {
@@ -24,8 +38,27 @@ pub mod endorse_transaction_command {
         "txn",
         "Transaction to endorse. Skip to use a transaction stored into CLI context."
     )
+    .add_optional_param(
+        "file",
+        "The path to file containing the transaction to endorse (instead of `txn`)"
+    )
+    .add_optional_param(
+        "name",
+        "Name of the context transaction to endorse. Skip to use the default one."
+    )
+    .add_optional_param(
+        "outfile",
+        "The path to file to write the endorsed transaction into instead of sending it to the ledger"
+    )
+    .add_optional_param(
+        "force",
+        "Endorse transaction even if the author signature does not match the author verkey on the ledger (False by default)"
+    )
     .add_example(r#"ledger endorse txn={"reqId":123456789,"type":"100"}"#)
     .add_example(r#"ledger endorse"#)
+    .add_example(r#"ledger endorse file=/home/author_txn.json"#)
+    .add_example(r#"ledger endorse name=schema"#)
+    .add_example(r#"ledger endorse file=/home/author_txn.json outfile=/home/endorsed_txn.json"#)
     .finalize());
 
     fn execute(ctx: &CommandContext, params: &CommandParams) -> Result<(), ()> {
@@ -33,28 +66,266 @@ pub mod endorse_transaction_command {
 
         let wallet = ctx.ensure_opened_wallet()?;
         let submitter_did = ctx.ensure_active_did()?;
+        ensure_key_rotated(ctx, &wallet)?;
+
+        let param_txn = ParamParser::get_opt_str_param(ctx, "txn", params)?;
+        let file = ParamParser::get_opt_str_param(ctx, "file", params)?;
+        let outfile = ParamParser::get_opt_str_param(ctx, "outfile", params)?;
+        let name = ParamParser::get_opt_str_param(ctx, "name", params)?;
+        let force = ParamParser::get_opt_bool_param(ctx, "force", params)?.unwrap_or(false);
+
+        let param_txn = match (param_txn, file) {
+            (Some(_), Some(_)) => {
+                println_err!(ctx, "Either `txn` or `file` can be specified");
+                return Err(());
+            }
+            (None, Some(file)) => Some(
+                read_file(file)
+                    .map_err(|err| println_err!(ctx, "{}", err))?
+                    .trim()
+                    .to_string(),
+            ),
+            (txn, None) => txn.map(String::from),
+        };
 
-        let param_txn = ParamParser::get_opt_str_param("txn", params)?;
+        let mut request = get_transaction_to_use!(ctx, param_txn, name);
 
-        let mut request = get_transaction_to_use!(ctx, param_txn);
+        check_author_signature(ctx, &request, force)?;
+        warn_own_did_changes(ctx, &wallet, &request);
 
-        Ledger::multi_sign_request(&wallet, &submitter_did, &mut request)
-            .map_err(|err| println_err!("{}", err.message(Some(&wallet.name))))?;
+        Ledger::multi_sign_request(
+            ctx.get_connected_pool().as_deref(),
+            &wallet,
+            &submitter_did,
+            &mut request,
+        )
+        .map_err(|err| err.print(ctx, Some(&wallet.name)))?;
 
-        let (_, response) = send_request!(&ctx, params, &request, true);
+        // the author (or anyone else) sends the endorsed transaction with `ledger custom`
+        if let Some(outfile) = outfile {
+            write_file(outfile, &json!(&request.req_json).to_string()).map_err(|err| {
+                println_err!(ctx, "Cannot save transaction into the file: {:?}", err)
+            })?;
+            println_succ!(
+                ctx,
+                "Endorsed transaction has been written to \"{}\". It has not been sent.",
+                outfile
+            );
+            trace!("execute <<");
+            return Ok(());
+        }
 
-        let (metadata_headers, metadata, data) = handle_transaction_response(response)
-            .and_then(|result| parse_transaction_response(result))?;
+        let (_, response) = send_request!(&ctx, params, &mut request, true);
 
-        println_succ!("Transaction has been sent to Ledger.");
+        let (metadata_headers, metadata, data) = handle_transaction_response(ctx, response)
+            .and_then(|result| parse_transaction_response(ctx, result))?;
 
-        println_succ!("Metadata:");
-        print_table(&metadata, &metadata_headers);
+        println_succ!(ctx, "Transaction has been sent to Ledger.");
 
-        println_succ!("Data:");
-        print_table(&json!({ "data": data }), &[("data", "Data")]);
+        println_succ!(ctx, "Metadata:");
+        print_table(ctx, &metadata, &metadata_headers);
+
+        println_succ!(ctx, "Data:");
+        print_table(ctx, &json!({ "data": data }), &[("data", "Data")]);
 
         trace!("execute <<");
         Ok(())
     }
 }
+
+/// Endorsers sign what they have received: make sure it is what the author has signed.
+/// Transactions with an author unknown to the ledger (or without connected pool) pass with a warning.
+pub fn check_author_signature(
+    ctx: &CommandContext,
+    request: &PreparedRequest,
+    force: bool,
+) -> Result<(), ()> {
+    match verify_author_signature(ctx, request)? {
+        SignatureStatus::Valid => Ok(()),
+        SignatureStatus::UnknownVerkey => {
+            println_warn!(
+                ctx,
+                "Author verkey cannot be read from the ledger. The author signature has not been verified."
+            );
+            Ok(())
+        }
+        status if force => {
+            println_warn!(
+                ctx,
+                "Author signature check has failed: {}. The transaction is signed anyway.",
+                status
+            );
+            Ok(())
+        }
+        status => {
+            println_err!(
+                ctx,
+                "Author signature check has failed: {}. The transaction may have been changed after the author has signed it.",
+                status
+            );
+            println_err!(ctx, "Use `force=true` to sign it anyway.");
+            Err(())
+        }
+    }
+}
+
+/// Signers of a NYM targeting a DID of the opened wallet are warned about the changes of their own identity:
+/// a verkey the wallet does not hold takes the control over the DID, a removed role downgrades it.
+pub fn warn_own_did_changes(ctx: &CommandContext, wallet: &Wallet, request: &PreparedRequest) {
+    let operation = &request.req_json["operation"];
+    if operation["type"].as_str() != Some(NYM) {
+        return;
+    }
+    let did_info = match operation["dest"]
+        .as_str()
+        .and_then(|dest| Did::get(wallet, &DidValue(dest.to_string())).ok())
+    {
+        Some(did_info) => did_info,
+        None => return,
+    };
+
+    // The role on the ledger is unknown without connected pool
+    let current_role = ctx.get_connected_pool().and_then(|pool| {
+        get_role(&pool, &DidValue(did_info.did.to_string()))
+            .ok()
+            .flatten()
+    });
+
+    let custom_roles = get_custom_roles(ctx);
+    let changes = own_did_changes(&did_info, operation, current_role.as_deref())
+        .into_iter()
+        .map(|mut change| {
+            if change["field"] == "role" {
+                for side in ["current", "new"] {
+                    if change[side].is_string() {
+                        change[side] = LedgerHelpers::get_role_title(&change[side], &custom_roles);
+                    }
+                }
+            }
+            change
+        })
+        .collect::<Vec<JsonValue>>();
+    if changes.is_empty() {
+        return;
+    }
+
+    println_warn!(
+        ctx,
+        "The transaction changes DID \"{}\" of the opened wallet. Check the changes before signing:",
+        did_info.did
+    );
+    print_list_table(
+        ctx,
+        &changes,
+        &[
+            ("field", "Field"),
+            ("current", "Current"),
+            ("new", "Transaction"),
+            ("note", "Note"),
+        ],
+        "",
+    );
+}
+
+// Verkeys are compared in the full form as either of them may be abbreviated
+fn own_did_changes(
+    did_info: &DidInfo,
+    operation: &JsonValue,
+    current_role: Option<&str>,
+) -> Vec<JsonValue> {
+    let full_verkey = |verkey: &str| {
+        Did::full_verkey(&did_info.did, verkey).unwrap_or_else(|_| verkey.to_string())
+    };
+    let mut changes = Vec::new();
+
+    match operation.get("verkey") {
+        Some(JsonValue::String(verkey)) => {
+            let new_verkey = full_verkey(verkey);
+            if new_verkey != full_verkey(&did_info.verkey) {
+                let pending_rotation = did_info
+                    .next_verkey
+                    .as_deref()
+                    .map(|next_verkey| full_verkey(next_verkey) == new_verkey)
+                    .unwrap_or(false);
+                changes.push(json!({
+                    "field": "verkey",
+                    "current": did_info.verkey,
+                    "new": verkey,
+                    "note": if pending_rotation {
+                        "Key rotation started in the wallet"
+                    } else {
+                        "The wallet does not hold the new key: the DID will be controlled by another key"
+                    },
+                }));
+            }
+        }
+        Some(JsonValue::Null) => changes.push(json!({
+            "field": "verkey",
+            "current": did_info.verkey,
+            "new": "-",
+            "note": "The verkey is removed: the DID will not be able to sign transactions",
+        })),
+        _ => {}
+    }
+
+    if let Some(role) = operation.get("role") {
+        let new_role = role.as_str().unwrap_or_default();
+        if current_role != Some(new_role) {
+            changes.push(json!({
+                "field": "role",
+                "current": current_role,
+                "new": new_role,
+                "note": if new_role.is_empty() {
+                    "The role is removed"
+                } else {
+                    "The role is changed"
+                },
+            }));
+        }
+    }
+
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DID: &str = "VsKV7grR1BUE29mG2Fm2kX";
+    const VERKEY: &str = "GjZWsBLgZCR18aL468JAT7w9CZRiBnpxUPPgyQxh4voa";
+    const NEXT_VERKEY: &str = "CnEDk9HrMnmiHXEV1WFgbVCRteYnPqsJwrTdcZaNhFVW";
+
+    fn did_info() -> DidInfo {
+        DidInfo {
+            did: DID.to_string(),
+            verkey: VERKEY.to_string(),
+            next_verkey: Some(NEXT_VERKEY.to_string()),
+            ..DidInfo::default()
+        }
+    }
+
+    #[test]
+    fn own_did_changes_works_for_same_verkey() {
+        let operation = json!({"type": "1", "dest": DID, "verkey": VERKEY});
+        assert!(own_did_changes(&did_info(), &operation, Some("101")).is_empty());
+    }
+
+    #[test]
+    fn own_did_changes_works_for_rotated_verkey_and_removed_role() {
+        let operation = json!({"type": "1", "dest": DID, "verkey": NEXT_VERKEY, "role": null});
+        let changes = own_did_changes(&did_info(), &operation, Some("101"));
+        assert_eq!(2, changes.len());
+        assert_eq!("verkey", changes[0]["field"]);
+        assert_eq!("Key rotation started in the wallet", changes[0]["note"]);
+        assert_eq!("role", changes[1]["field"]);
+        assert_eq!("", changes[1]["new"]);
+    }
+
+    #[test]
+    fn own_did_changes_works_for_foreign_verkey() {
+        let operation = json!({"type": "1", "dest": DID, "verkey": "~HYwqs2ihGTWPZvDiBfSzxF"});
+        let changes = own_did_changes(&did_info(), &operation, None);
+        assert_eq!(1, changes.len());
+        assert_eq!(VERKEY, changes[0]["current"]);
+    }
+}
@@ -0,0 +1,214 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::{
+    command_executor::{Command, CommandContext, CommandMetadata, CommandParams},
+    params_parser::ParamParser,
+    tools::ledger::{Ledger, Response},
+    utils::{table::print_table, time::timestamp_to_datetime},
+};
+
+use serde_json::Value as JsonValue;
+
+use super::common::{handle_transaction_response, print_transaction_response};
+
+pub mod flag_command {
+    use super::*;
+
+    command!(CommandMetadata::build("flag", "Send FLAG transaction to the Config Ledger to set or unset a feature flag of the network.")
+                .add_required_param("name", "Name of the flag")
+                .add_optional_param("value", "Value of the flag. Skip to unset the flag")
+                .add_optional_param("sign","Sign the request (True by default)")
+                .add_optional_param("send","Send the request to the Ledger (True by default). If false then created request will be printed and stored into CLI context.")
+                .add_optional_param("endorser","DID of the Endorser that will submit the transaction to the ledger later. \
+                    Note that specifying of this parameter implies send=false so the transaction will be prepared to pass to the endorser instead of sending to the ledger.\
+                    The created request will be printed and stored into CLI context.")
+                .add_example("ledger flag name=REV_STRATEGY_USE_COMPAT_ORDERING value=True")
+                .add_example("ledger flag name=REV_STRATEGY_USE_COMPAT_ORDERING")
+                .finalize()
+    );
+
+    fn execute(ctx: &CommandContext, params: &CommandParams) -> Result<(), ()> {
+        trace!("execute >> ctx {:?} params {:?}", ctx, params);
+
+        let wallet = ctx.ensure_opened_wallet()?;
+        let submitter_did = ctx.ensure_active_did()?;
+        let pool = ctx.get_connected_pool();
+
+        let name = ParamParser::get_str_param(ctx, "name", params)?;
+        let value = ParamParser::get_opt_str_param(ctx, "value", params)?;
+
+        let mut request = Ledger::build_flag_request(pool.as_deref(), &submitter_did, name, value)
+            .map_err(|err| err.print(ctx, None))?;
+
+        let (_, response): (String, Response<JsonValue>) =
+            send_write_request!(ctx, params, &mut request, &wallet, &submitter_did);
+
+        handle_transaction_response(ctx, response).map(|result| {
+            print_transaction_response(
+                ctx,
+                result,
+                "Flag request has been sent to Ledger.",
+                None,
+                &[("name", "Name"), ("value", "Value")],
+                false,
+            )
+        })?;
+
+        trace!("execute <<");
+        Ok(())
+    }
+}
+
+pub mod get_flag_command {
+    use super::*;
+
+    command!(CommandMetadata::build("get-flag", "Get the value of a feature flag from the Config Ledger.")
+                .add_required_param("name", "Name of the flag")
+                .add_optional_param("timestamp", "Time (in seconds since epoch) to get the value of the flag at. Skip to get the current value")
+                .add_optional_param("send","Send the request to the Ledger (True by default). If false then created request will be printed and stored into CLI context.")
+                .add_example("ledger get-flag name=REV_STRATEGY_USE_COMPAT_ORDERING")
+                .add_example("ledger get-flag name=REV_STRATEGY_USE_COMPAT_ORDERING timestamp=1700000000")
+                .finalize()
+    );
+
+    fn execute(ctx: &CommandContext, params: &CommandParams) -> Result<(), ()> {
+        trace!("execute >> ctx {:?} params {:?}", ctx, params);
+
+        let submitter_did = ctx.get_active_did()?;
+        let pool = ctx.get_connected_pool();
+
+        let name = ParamParser::get_str_param(ctx, "name", params)?;
+        let timestamp = ParamParser::get_opt_number_param::<u64>("timestamp", params)?;
+
+        let mut request = Ledger::build_get_flag_request(
+            pool.as_deref(),
+            submitter_did.as_deref(),
+            name,
+            timestamp,
+        )
+        .map_err(|err| err.print(ctx, None))?;
+
+        let (_, response) = send_read_request!(&ctx, params, &mut request);
+
+        let result = handle_transaction_response(ctx, response)?;
+
+        // The state keeps the value together with the time of the last update
+        let value = match &result["data"] {
+            JsonValue::Null => {
+                println_err!(ctx, "Flag \"{}\" is not set on the ledger", name);
+                return Err(());
+            }
+            JsonValue::Object(data) => data.get("value").cloned().unwrap_or(JsonValue::Null),
+            value => value.clone(),
+        };
+        let updated = result["data"]["lut"]
+            .as_i64()
+            .or_else(|| result["txnTime"].as_i64())
+            .map(|time| timestamp_to_datetime(ctx, time));
+
+        println_succ!(ctx, "Following Flag has been received.");
+        print_table(
+            ctx,
+            &json!({
+                "name": name,
+                "value": value,
+                "updated": updated,
+                "seqNo": result["seqNo"],
+            }),
+            &[
+                ("name", "Name"),
+                ("value", "Value"),
+                ("updated", "Updated"),
+                ("seqNo", "Sequence Number"),
+            ],
+        );
+
+        trace!("execute <<");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::{
+        commands::{setup_with_wallet_and_pool, tear_down_with_wallet_and_pool},
+        ledger::tests::{use_new_identity, use_trustee},
+    };
+
+    const FLAG_NAME: &str = "CLI_TEST_FLAG";
+
+    mod flag {
+        use super::*;
+
+        #[test]
+        pub fn flag_works() {
+            let ctx = setup_with_wallet_and_pool();
+            use_trustee(&ctx);
+            {
+                let cmd = flag_command::new();
+                let mut params = CommandParams::new();
+                params.insert("name", FLAG_NAME.to_string());
+                params.insert("value", "True".to_string());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            {
+                let cmd = get_flag_command::new();
+                let mut params = CommandParams::new();
+                params.insert("name", FLAG_NAME.to_string());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            tear_down_with_wallet_and_pool(&ctx);
+        }
+
+        #[test]
+        pub fn flag_works_for_no_send() {
+            let ctx = setup_with_wallet_and_pool();
+            use_trustee(&ctx);
+            {
+                let cmd = flag_command::new();
+                let mut params = CommandParams::new();
+                params.insert("name", FLAG_NAME.to_string());
+                params.insert("send", "false".to_string());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            let transaction = ctx.get_context_transaction(None).unwrap();
+            let transaction = serde_json::from_str::<JsonValue>(&transaction).unwrap();
+            assert_eq!("130", transaction["operation"]["type"]);
+            assert!(transaction["operation"]["value"].is_null());
+            tear_down_with_wallet_and_pool(&ctx);
+        }
+
+        #[test]
+        pub fn flag_works_for_not_trustee() {
+            let ctx = setup_with_wallet_and_pool();
+            use_new_identity(&ctx);
+            {
+                let cmd = flag_command::new();
+                let mut params = CommandParams::new();
+                params.insert("name", FLAG_NAME.to_string());
+                params.insert("value", "True".to_string());
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            tear_down_with_wallet_and_pool(&ctx);
+        }
+    }
+
+    mod get_flag {
+        use super::*;
+
+        #[test]
+        pub fn get_flag_works_for_unknown_flag() {
+            let ctx = setup_with_wallet_and_pool();
+            {
+                let cmd = get_flag_command::new();
+                let mut params = CommandParams::new();
+                params.insert("name", "CLI_UNKNOWN_FLAG".to_string());
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            tear_down_with_wallet_and_pool(&ctx);
+        }
+    }
+}
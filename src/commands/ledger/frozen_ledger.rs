@@ -25,7 +25,7 @@ pub mod ledgers_freeze_command {
 
     fn execute(ctx: &CommandContext, params: &CommandParams) -> Result<(), ()> {
         trace!("execute >> ctx {:?} params {:?}", ctx, params);
-        let ledgers_ids = ParamParser::get_number_tuple_array_param("ledgers_ids", params);
+        let ledgers_ids = ParamParser::get_number_tuple_array_param(ctx, "ledgers_ids", params);
         let submitter_did = ctx.ensure_active_did()?;
         let pool = ctx.get_connected_pool();
 
@@ -33,14 +33,14 @@ pub mod ledgers_freeze_command {
 
         let mut request =
             Ledger::build_ledgers_freeze_request(pool.as_deref(), &submitter_did, ledgers_ids?)
-                .map_err(|err| println_err!("{}", err.message(None)))?;
+                .map_err(|err| err.print(ctx, None))?;
 
         let (_, response) =
             send_write_request!(&ctx, params, &mut request, &wallet, &submitter_did);
 
-        let result = handle_transaction_response(response)?;
+        let result = handle_transaction_response(ctx, response)?;
 
-        println_succ!("result {:?}", result);
+        println_succ!(ctx, "result {:?}", result);
 
         trace!("execute <<");
         Ok(())
@@ -62,11 +62,11 @@ pub mod get_frozen_ledgers_command {
         let submitter_did = ctx.ensure_active_did()?;
         let pool = ctx.get_connected_pool();
 
-        let request = Ledger::build_get_frozen_ledgers_request(pool.as_deref(), &submitter_did)
-            .map_err(|err| println_err!("{}", err.message(None)))?;
+        let mut request = Ledger::build_get_frozen_ledgers_request(pool.as_deref(), &submitter_did)
+            .map_err(|err| err.print(ctx, None))?;
 
-        let (_, response) = send_read_request!(&ctx, params, &request);
-        let handle_response = handle_transaction_response(response)?;
+        let (_, response) = send_read_request!(&ctx, params, &mut request);
+        let handle_response = handle_transaction_response(ctx, response)?;
 
         // Flattering ap into vector
         let handle_response = handle_response
@@ -80,24 +80,28 @@ pub mod get_frozen_ledgers_command {
                 .expect("inner object is not a map")
                 .clone();
 
-            let ledger_id = serde_json::to_value(&response_ledger_key)
-                .map_err(|_| println_err!("Invalid format of Outputs: Ledger ID is incorrect."))?;
+            let ledger_id = serde_json::to_value(&response_ledger_key).map_err(|_| {
+                println_err!(ctx, "Invalid format of Outputs: Ledger ID is incorrect.")
+            })?;
             ledger_info.insert("ledger_id".to_owned(), ledger_id);
 
-            result
-                .push(serde_json::to_value(&ledger_info).map_err(|_| {
-                    println_err!("Invalid format of Outputs: result is incorrect.")
-                })?);
+            result.push(serde_json::to_value(&ledger_info).map_err(|_| {
+                println_err!(ctx, "Invalid format of Outputs: result is incorrect.")
+            })?);
         }
 
-        print_frozen_ledgers(result)?;
+        print_frozen_ledgers(ctx, result)?;
         trace!("execute <<");
         Ok(())
     }
 
-    fn print_frozen_ledgers(frozen_ledgers: Vec<JsonValue>) -> Result<(), ()> {
-        println_succ!("Frozen ledgers has been received.");
+    fn print_frozen_ledgers(
+        ctx: &CommandContext,
+        frozen_ledgers: Vec<JsonValue>,
+    ) -> Result<(), ()> {
+        println_succ!(ctx, "Frozen ledgers has been received.");
         print_list_table(
+            ctx,
             &frozen_ledgers,
             &[
                 ("ledger_id", "Ledger id"),
@@ -0,0 +1,146 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::{
+    command_executor::{Command, CommandContext, CommandMetadata, CommandParams},
+    commands::{explore::parse_ledger_type, note::print_txn_notes},
+    params_parser::ParamParser,
+    tools::ledger::{Ledger, LedgerHelpers, Response},
+};
+
+use serde_json::Value as JsonValue;
+
+use super::common::{handle_transaction_response, print_transaction_response};
+
+pub mod get_txn_command {
+    use super::*;
+
+    command!(CommandMetadata::build("get-txn", "Get transaction by sequence number from Ledger.")
+                .add_required_param("seq_no", "Sequence number of the transaction")
+                .add_optional_param("ledger", "Ledger to read: DOMAIN (default), POOL or CONFIG")
+                .add_optional_param("send","Send the request to the Ledger (True by default). If false then created request will be printed and stored into CLI context.")
+                .add_example("ledger get-txn seq_no=10")
+                .add_example("ledger get-txn ledger=POOL seq_no=1")
+                .finalize()
+    );
+
+    fn execute(ctx: &CommandContext, params: &CommandParams) -> Result<(), ()> {
+        trace!("execute >> ctx {:?} params {:?}", ctx, params);
+
+        let pool = ctx.get_connected_pool();
+
+        let seq_no = ParamParser::get_number_param::<i32>("seq_no", params)?;
+        let ledger = ParamParser::get_opt_str_param(ctx, "ledger", params)?;
+
+        let ledger_type = parse_ledger_type(ctx, ledger.map(str::to_lowercase).as_deref())?;
+
+        let mut request = Ledger::build_get_txn_request(pool.as_deref(), ledger_type, seq_no)
+            .map_err(|err| err.print(ctx, None))?;
+
+        let (_, response) = send_read_request!(&ctx, params, &mut request);
+
+        let mut result = handle_transaction_response(ctx, response)?;
+
+        let txn = match result["data"].take() {
+            JsonValue::Null => {
+                println_err!(
+                    ctx,
+                    "Transaction with sequence number {} is not found on {} ledger",
+                    seq_no,
+                    ledger.unwrap_or("DOMAIN")
+                );
+                return Err(());
+            }
+            txn => txn,
+        };
+
+        let txn_type = LedgerHelpers::get_txn_title(&txn["txn"]["type"]);
+        // Fields of the transaction data depend on its type, so all of them are shown
+        let data_fields = txn["txn"]["data"]
+            .as_object()
+            .map(|data| data.keys().cloned().collect::<Vec<String>>())
+            .unwrap_or_default();
+        let data_headers = data_fields
+            .iter()
+            .map(|field| (field.as_str(), field.as_str()))
+            .collect::<Vec<(&str, &str)>>();
+
+        print_transaction_response(
+            ctx,
+            txn,
+            &format!(
+                "Following {} transaction has been received.",
+                txn_type.as_str().unwrap_or_default()
+            ),
+            None,
+            &data_headers,
+            true,
+        );
+
+        print_txn_notes(ctx, ledger_type, seq_no);
+
+        trace!("execute <<");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::commands::{setup_with_wallet_and_pool, tear_down_with_wallet_and_pool};
+
+    mod get_txn {
+        use super::*;
+
+        #[test]
+        pub fn get_txn_works() {
+            let ctx = setup_with_wallet_and_pool();
+            {
+                let cmd = get_txn_command::new();
+                let mut params = CommandParams::new();
+                params.insert("seq_no", "1".to_string());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            tear_down_with_wallet_and_pool(&ctx);
+        }
+
+        #[test]
+        pub fn get_txn_works_for_pool_ledger() {
+            let ctx = setup_with_wallet_and_pool();
+            {
+                let cmd = get_txn_command::new();
+                let mut params = CommandParams::new();
+                params.insert("seq_no", "1".to_string());
+                params.insert("ledger", "POOL".to_string());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            tear_down_with_wallet_and_pool(&ctx);
+        }
+
+        #[test]
+        pub fn get_txn_works_for_not_written() {
+            let ctx = setup_with_wallet_and_pool();
+            {
+                let cmd = get_txn_command::new();
+                let mut params = CommandParams::new();
+                params.insert("seq_no", "100000000".to_string());
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            tear_down_with_wallet_and_pool(&ctx);
+        }
+
+        #[test]
+        pub fn get_txn_works_for_unknown_ledger() {
+            let ctx = setup_with_wallet_and_pool();
+            {
+                let cmd = get_txn_command::new();
+                let mut params = CommandParams::new();
+                params.insert("seq_no", "1".to_string());
+                params.insert("ledger", "AUDIT".to_string());
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            tear_down_with_wallet_and_pool(&ctx);
+        }
+    }
+}
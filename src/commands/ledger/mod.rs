@@ -8,26 +8,38 @@ use crate::command_executor::{CommandGroup, CommandGroupMetadata};
 pub mod common;
 pub mod attrib;
 pub mod auth_rule;
+pub mod batch;
 pub mod constants;
 pub mod cred_def;
 pub mod custom;
+pub mod digest;
 pub mod endorser;
+pub mod flag;
 pub mod frozen_ledger;
+pub mod get_txn;
 pub mod node;
 pub mod nym;
 pub mod pool_config;
 pub mod pool_restart;
 pub mod pool_upgrade;
+pub mod prefetch;
+pub mod revoc_reg_def;
+pub mod revocation_status;
+pub mod revoke_roles;
 pub mod schema;
+pub mod search;
 pub mod sign_multi;
 pub mod transaction;
 pub mod transaction_author_agreement;
 pub mod validator_info;
+pub mod verify_signatures;
 
 pub use self::{
-    attrib::*, auth_rule::*, common::*, cred_def::*, custom::*, endorser::*, frozen_ledger::*,
-    node::*, nym::*, pool_config::*, pool_restart::*, pool_upgrade::*, schema::*, sign_multi::*,
-    transaction::*, transaction_author_agreement::*, validator_info::*,
+    attrib::*, auth_rule::*, batch::*, common::*, cred_def::*, custom::*, digest::*, endorser::*,
+    flag::*, frozen_ledger::*, get_txn::*, node::*, nym::*, pool_config::*, pool_restart::*,
+    pool_upgrade::*, prefetch::*, revoc_reg_def::*, revocation_status::*, revoke_roles::*,
+    schema::*, search::*, sign_multi::*, transaction::*, transaction_author_agreement::*,
+    validator_info::*, verify_signatures::*,
 };
 
 pub mod group {
@@ -42,14 +42,14 @@ pub mod node_command {
         let submitter_did = ctx.ensure_active_did()?;
         let pool = ctx.get_connected_pool();
 
-        let target_did = ParamParser::get_did_param("target", params)?;
-        let alias = ParamParser::get_str_param("alias", params)?;
-        let node_ip = ParamParser::get_opt_str_param("node_ip", params)?;
+        let target_did = ParamParser::get_did_param(ctx, "target", params)?;
+        let alias = ParamParser::get_str_param(ctx, "alias", params)?;
+        let node_ip = ParamParser::get_opt_str_param(ctx, "node_ip", params)?;
         let node_port = ParamParser::get_opt_number_param::<i32>("node_port", params)?;
-        let client_ip = ParamParser::get_opt_str_param("client_ip", params)?;
+        let client_ip = ParamParser::get_opt_str_param(ctx, "client_ip", params)?;
         let client_port = ParamParser::get_opt_number_param::<i32>("client_port", params)?;
-        let blskey = ParamParser::get_opt_str_param("blskey", params)?;
-        let blskey_pop = ParamParser::get_opt_str_param("blskey_pop", params)?;
+        let blskey = ParamParser::get_opt_str_param(ctx, "blskey", params)?;
+        let blskey_pop = ParamParser::get_opt_str_param(ctx, "blskey_pop", params)?;
         let services = ParamParser::get_opt_str_array_param("services", params)?;
 
         let services = match services {
@@ -60,7 +60,7 @@ pub mod node_command {
                         "VALIDATOR" => Ok(Services::VALIDATOR),
                         "OBSERVER" => Ok(Services::OBSERVER),
                         service => {
-                            println_err!("Unsupported service \"{}\"!", service);
+                            println_err!(ctx, "Unsupported service \"{}\"!", service);
                             Err(())
                         }
                     })
@@ -82,13 +82,14 @@ pub mod node_command {
 
         let mut request =
             Ledger::build_node_request(pool.as_deref(), &submitter_did, &target_did, node_data)
-                .map_err(|err| println_err!("{}", err.message(None)))?;
+                .map_err(|err| err.print(ctx, None))?;
 
         let (_, response): (String, Response<JsonValue>) =
             send_write_request!(ctx, params, &mut request, &wallet, &submitter_did);
 
-        handle_transaction_response(response).map(|result| {
+        handle_transaction_response(ctx, response).map(|result| {
             print_transaction_response(
+                ctx,
                 result,
                 "NodeConfig request has been sent to Ledger.",
                 Some("data"),
@@ -3,7 +3,10 @@
     https://digital.gov.bc.ca/digital-trust
 */
 use crate::{
-    command_executor::{Command, CommandContext, CommandMetadata, CommandParams},
+    command_executor::{
+        Command, CommandContext, CommandMetadata, CommandParams, ContextEvent,
+        DynamicCompletionType,
+    },
     params_parser::ParamParser,
     tools::ledger::{Ledger, Response},
 };
@@ -11,28 +14,43 @@ use crate::{
 use serde_json::Value as JsonValue;
 
 use super::common::{
-    handle_transaction_response, print_transaction_response, set_author_agreement,
+    get_custom_roles, handle_transaction_response, print_transaction_response, set_author_agreement,
 };
 
 pub mod nym_command {
     use super::*;
-    use crate::tools::{did::Did, ledger::LedgerHelpers};
+    use crate::tools::{
+        did::{alias::NymAlias, Did},
+        ledger::LedgerHelpers,
+    };
+
+    // did:indy self-certification is the latest one
+    const MAX_NYM_VERSION: u8 = 2;
 
     command!(
     CommandMetadata::build("nym", r#"Send NYM transaction to the Ledger."#)
         .add_required_param("did", "DID of new identity")
         .add_optional_param("verkey", "Verification key of new identity")
-        .add_optional_param("role", "Role of identity. One of: STEWARD, TRUSTEE, TRUST_ANCHOR, ENDORSER, NETWORK_MONITOR or associated number, or empty in case of blacklisting NYM")
+        .add_optional_param_with_dynamic_completion("role", "Role of identity. One of: STEWARD, TRUSTEE, TRUST_ANCHOR, ENDORSER, NETWORK_MONITOR, custom role of the connected pool or associated number, or empty in case of blacklisting NYM", DynamicCompletionType::Role)
+        .add_optional_param("alias", "Human-friendly alias of identity")
+        .add_optional_param("diddoc_content", "JSON object with DID Document content to add to the document assembled from the NYM (indy-node 1.13 or newer)")
+        .add_optional_param("version", "Self-certification of the DID (indy-node 1.13 or newer). One of: 0 - not checked, 1 - did:sov, 2 - did:indy")
         .add_optional_param("sign","Sign the request (True by default)")
         .add_optional_param("send","Send the request to the Ledger (True by default). If false then created request will be printed and stored into CLI context.")
+        .add_optional_param("queue","Spool the request to the local queue if the pool cannot be reached (False by default). Queued requests are sent by `queue flush` command.")
         .add_optional_param("endorser","DID of the Endorser that will submit the transaction to the ledger. \
             Note that specifying of this parameter implies send=false so the transaction will be prepared to pass to the endorser instead of sending to the ledger.\
             The created request will be printed and stored into CLI context.")
         .add_example("ledger nym did=VsKV7grR1BUE29mG2Fm2kX")
         .add_example("ledger nym did=VsKV7grR1BUE29mG2Fm2kX verkey=GjZWsBLgZCR18aL468JAT7w9CZRiBnpxUPPgyQxh4voa")
         .add_example("ledger nym did=VsKV7grR1BUE29mG2Fm2kX role=TRUSTEE")
+        .add_example("ledger nym did=VsKV7grR1BUE29mG2Fm2kX role=ENDORSER")
         .add_example("ledger nym did=VsKV7grR1BUE29mG2Fm2kX role=")
+        .add_example("ledger nym did=VsKV7grR1BUE29mG2Fm2kX alias=alice")
+        .add_example(r##"ledger nym did=VsKV7grR1BUE29mG2Fm2kX diddoc_content={"service":[{"id":"#linked-domain","type":"LinkedDomains","serviceEndpoint":"https://example.com"}]}"##)
+        .add_example("ledger nym did=VsKV7grR1BUE29mG2Fm2kX verkey=GjZWsBLgZCR18aL468JAT7w9CZRiBnpxUPPgyQxh4voa version=1")
         .add_example("ledger nym did=VsKV7grR1BUE29mG2Fm2kX send=false")
+        .add_example("ledger nym did=VsKV7grR1BUE29mG2Fm2kX queue=true")
         .finalize()
     );
 
@@ -43,9 +61,46 @@ pub mod nym_command {
         let pool = ctx.get_connected_pool();
         let submitter_did = ctx.ensure_active_did()?;
 
-        let target_did = ParamParser::get_did_param("did", params)?;
-        let verkey = ParamParser::get_opt_str_param("verkey", params)?;
+        let target_did = ParamParser::get_did_param(ctx, "did", params)?;
+        let verkey = ParamParser::get_opt_str_param(ctx, "verkey", params)?;
         let role = ParamParser::get_opt_empty_str_param("role", params)?;
+        let alias = ParamParser::get_opt_str_param(ctx, "alias", params)?;
+        let diddoc_content = ParamParser::get_opt_object_param(ctx, "diddoc_content", params)?;
+        let version = ParamParser::get_opt_number_param::<u8>("version", params)?;
+
+        if let Some(version) = version.filter(|version| *version > MAX_NYM_VERSION) {
+            println_err!(
+                ctx,
+                "Unsupported NYM version {}. Expected one of: 0, 1, 2",
+                version
+            );
+            return Err(());
+        }
+
+        if diddoc_content.is_some() || version.is_some() {
+            let capabilities = pool.as_ref().and_then(|pool| pool.capabilities());
+            if capabilities
+                .map(|capabilities| !capabilities.did_indy)
+                .unwrap_or(false)
+            {
+                println_err!(
+                    ctx,
+                    "The pool does not support did:indy: \"diddoc_content\" and \"version\" require indy-node 1.13 or newer."
+                );
+                return Err(());
+            }
+        }
+
+        let custom_roles = get_custom_roles(ctx);
+        let role = role
+            .map(|role| LedgerHelpers::get_role_code(role, &custom_roles))
+            .transpose()
+            .map_err(|err| err.print(ctx, None))?;
+        // Custom roles are not known by the request builder so set them into the request directly
+        let custom_role = role
+            .as_ref()
+            .filter(|role| !LedgerHelpers::is_standard_role_code(role))
+            .cloned();
 
         if let Some(target_verkey) = verkey {
             let did_info = Did::get(&wallet, &target_did);
@@ -56,10 +111,13 @@ pub mod nym_command {
 
                 if verkey_ != target_verkey {
                     println_warn!(
+                    ctx,
                     "There is the same `DID` stored in the wallet but with different Verkey: {:?}",
                     verkey_
                 );
-                    println_warn!("Do you really want to change Verkey on the ledger? (y/n)");
+                    crate::utils::term::print_prompt(
+                        "Do you really want to change Verkey on the ledger? (y/n)",
+                    );
 
                     let change_nym = crate::command_executor::wait_for_user_reply(ctx);
                     if !change_nym {
@@ -70,15 +128,21 @@ pub mod nym_command {
             }
         }
 
-        let mut request = Ledger::build_nym_request(
+        let mut request = Ledger::build_nym_request_with_diddoc(
             pool.as_deref(),
             &submitter_did,
             &target_did,
             verkey,
-            None,
-            role,
+            alias,
+            role.as_deref().filter(|_| custom_role.is_none()),
+            diddoc_content.as_ref(),
+            version,
         )
-        .map_err(|err| println_err!("{}", err.message(None)))?;
+        .map_err(|err| err.print(ctx, None))?;
+
+        if let Some(custom_role) = custom_role {
+            request.req_json["operation"]["role"] = JsonValue::String(custom_role);
+        }
 
         set_author_agreement(ctx, &mut request)?;
 
@@ -87,20 +151,37 @@ pub mod nym_command {
 
         if let Some(result) = response.result.as_mut() {
             result["txn"]["data"]["role"] =
-                LedgerHelpers::get_role_title(&result["txn"]["data"]["role"]);
-            result["role"] = LedgerHelpers::get_role_title(&result["role"]);
+                LedgerHelpers::get_role_title(&result["txn"]["data"]["role"], &custom_roles);
+            result["role"] = LedgerHelpers::get_role_title(&result["role"], &custom_roles);
         }
 
-        handle_transaction_response(response).map(|result| {
+        handle_transaction_response(ctx, response).map(|result| {
             print_transaction_response(
+                ctx,
                 result,
                 "Nym request has been sent to Ledger.",
                 None,
-                &[("dest", "Did"), ("verkey", "Verkey"), ("role", "Role")],
+                &[
+                    ("dest", "Did"),
+                    ("verkey", "Verkey"),
+                    ("role", "Role"),
+                    ("alias", "Alias"),
+                    ("diddocContent", "DIDDoc Content"),
+                    ("version", "Version"),
+                ],
                 true,
             )
         })?;
 
+        if let Some(alias) = alias {
+            let previous =
+                NymAlias::store(&wallet, alias, &target_did).map_err(|err| err.print(ctx, None))?;
+            ctx.add_context_event(ContextEvent::NymAlias {
+                alias: alias.to_string(),
+                previous,
+            });
+        }
+
         trace!("execute <<");
         Ok(())
     }
@@ -108,12 +189,21 @@ pub mod nym_command {
 
 pub mod get_nym_command {
     use super::*;
-    use crate::tools::ledger::LedgerHelpers;
+    use crate::{
+        tools::{did::alias::NymAlias, ledger::LedgerHelpers},
+        utils::time::timestamp_to_datetime,
+    };
 
     command!(CommandMetadata::build("get-nym", "Get NYM from Ledger.")
-                .add_required_param("did","DID of identity presented in Ledger")
+                .add_optional_param("did","DID of identity presented in Ledger")
+                .add_optional_param("alias","Alias of identity (instead of DID). Resolved using aliases of NYMs sent from the opened wallet")
+                .add_optional_param("seq_no","Sequence number of the transaction to get the state of the NYM after it was written")
+                .add_optional_param("timestamp","Time (in seconds since epoch) to get the state of the NYM at")
                 .add_optional_param("send","Send the request to the Ledger (True by default). If false then created request will be printed and stored into CLI context.")
                 .add_example("ledger get-nym did=VsKV7grR1BUE29mG2Fm2kX")
+                .add_example("ledger get-nym alias=alice")
+                .add_example("ledger get-nym did=VsKV7grR1BUE29mG2Fm2kX seq_no=12")
+                .add_example("ledger get-nym did=VsKV7grR1BUE29mG2Fm2kX timestamp=1700000000")
                 .finalize()
     );
 
@@ -123,30 +213,75 @@ pub mod get_nym_command {
         let submitter_did = ctx.get_active_did()?;
         let pool = ctx.get_connected_pool();
 
-        let target_did = ParamParser::get_did_param("did", params)?;
+        let target_did = ParamParser::get_opt_did_param(ctx, "did", params)?;
+        let alias = ParamParser::get_opt_str_param(ctx, "alias", params)?;
+        let seq_no = ParamParser::get_opt_number_param::<u64>("seq_no", params)?;
+        let timestamp = ParamParser::get_opt_number_param::<u64>("timestamp", params)?;
+
+        if seq_no.is_some() && timestamp.is_some() {
+            println_err!(
+                ctx,
+                "Only one of \"seq_no\" or \"timestamp\" parameters can be specified"
+            );
+            return Err(());
+        }
+
+        let target_did = match (target_did, alias) {
+            (Some(target_did), None) => target_did,
+            (None, Some(alias)) => {
+                let wallet = ctx.ensure_opened_wallet()?;
+                NymAlias::resolve(&wallet, alias).map_err(|err| err.print(ctx, None))?
+            }
+            (Some(_), Some(_)) => {
+                println_err!(
+                    ctx,
+                    "Only one of \"did\" or \"alias\" parameters can be specified"
+                );
+                return Err(());
+            }
+            (None, None) => {
+                println_err!(
+                    ctx,
+                    "Either \"did\" or \"alias\" parameter must be specified"
+                );
+                return Err(());
+            }
+        };
 
-        let request =
-            Ledger::build_get_nym_request(pool.as_deref(), submitter_did.as_deref(), &target_did)
-                .map_err(|err| println_err!("{}", err.message(None)))?;
+        let mut request = Ledger::build_get_nym_request_at(
+            pool.as_deref(),
+            submitter_did.as_deref(),
+            &target_did,
+            seq_no,
+            timestamp,
+        )
+        .map_err(|err| err.print(ctx, None))?;
 
-        let (_, mut response) = send_read_request!(&ctx, params, &request);
+        let (_, mut response) = send_read_request!(&ctx, params, &mut request);
 
         if let Some(result) = response.result.as_mut() {
             let data = serde_json::from_str::<JsonValue>(&result["data"].as_str().unwrap_or(""));
             match data {
                 Ok(mut data) => {
-                    data["role"] = LedgerHelpers::get_role_title(&data["role"]);
+                    data["role"] =
+                        LedgerHelpers::get_role_title(&data["role"], &get_custom_roles(ctx));
+                    // time the returned state of the NYM has been written
+                    data["txnTime"] = result["txnTime"]
+                        .as_i64()
+                        .map(|txn_time| json!(timestamp_to_datetime(ctx, txn_time)))
+                        .unwrap_or(JsonValue::Null);
                     result["data"] = data;
                 }
                 Err(_) => {
-                    println_err!("NYM not found");
+                    println_err!(ctx, "NYM not found");
                     return Err(());
                 }
             };
         };
 
-        handle_transaction_response(response).map(|result| {
+        handle_transaction_response(ctx, response).map(|result| {
             print_transaction_response(
+                ctx,
                 result,
                 "Following NYM has been received.",
                 Some("data"),
@@ -155,6 +290,10 @@ pub mod get_nym_command {
                     ("dest", "Dest"),
                     ("verkey", "Verkey"),
                     ("role", "Role"),
+                    ("alias", "Alias"),
+                    ("diddocContent", "DIDDoc Content"),
+                    ("version", "Version"),
+                    ("txnTime", "Transaction Time"),
                 ],
                 true,
             )
@@ -180,6 +319,7 @@ pub mod tests {
         },
         ledger::tests::{create_new_did, use_trustee, ReplyResult},
         pool::constants::DEFAULT_POOL_PROTOCOL_VERSION,
+        tools::did::alias::NymAlias,
     };
     use indy_utils::did::DidValue;
 
@@ -220,6 +360,45 @@ pub mod tests {
             tear_down_with_wallet_and_pool(&ctx);
         }
 
+        #[test]
+        pub fn nym_works_for_trust_anchor_role() {
+            let ctx = setup_with_wallet_and_pool();
+            use_trustee(&ctx);
+            let (did, verkey) = create_new_did(&ctx);
+            {
+                let cmd = nym_command::new();
+                let mut params = CommandParams::new();
+                params.insert("did", did.clone());
+                params.insert("verkey", verkey);
+                params.insert("role", "TRUST_ANCHOR".to_string());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            assert!(ensure_nym_added(&ctx, &did).is_ok());
+            tear_down_with_wallet_and_pool(&ctx);
+        }
+
+        #[test]
+        pub fn nym_works_for_alias() {
+            let ctx = setup_with_wallet_and_pool();
+            use_trustee(&ctx);
+            let (did, verkey) = create_new_did(&ctx);
+            {
+                let cmd = nym_command::new();
+                let mut params = CommandParams::new();
+                params.insert("did", did.clone());
+                params.insert("verkey", verkey);
+                params.insert("alias", "alice".to_string());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            assert!(ensure_nym_added(&ctx, &did).is_ok());
+            {
+                let wallet = ctx.ensure_opened_wallet().unwrap();
+                let resolved = NymAlias::resolve(&wallet, "alice").unwrap();
+                assert_eq!(resolved.to_string(), did);
+            }
+            tear_down_with_wallet_and_pool(&ctx);
+        }
+
         #[test]
         pub fn nym_works_for_wrong_role() {
             let ctx = setup_with_wallet_and_pool();
@@ -314,7 +493,7 @@ pub mod tests {
                 cmd.execute(&ctx, &params).unwrap();
             }
             assert!(ensure_nym_added(&ctx, &did).is_err());
-            assert!(ctx.get_context_transaction().is_some());
+            assert!(ctx.get_context_transaction(None).is_some());
             tear_down_with_wallet_and_pool(&ctx);
         }
 
@@ -332,12 +511,61 @@ pub mod tests {
                 params.insert("send", "false".to_string());
                 cmd.execute(&ctx, &params).unwrap();
             }
-            let transaction = ctx.get_context_transaction().unwrap();
+            let transaction = ctx.get_context_transaction(None).unwrap();
             let transaction: JsonValue = serde_json::from_str(&transaction).unwrap();
             assert!(transaction["signature"].is_null());
             tear_down_with_wallet_and_pool(&ctx);
         }
 
+        #[test]
+        pub fn nym_works_for_diddoc_content_and_version() {
+            let ctx = setup_with_wallet_and_pool();
+            use_trustee(&ctx);
+            let (did, verkey) = create_new_did(&ctx);
+            let diddoc_content = json!({
+                "service": [{
+                    "id": "#linked-domain",
+                    "type": "LinkedDomains",
+                    "serviceEndpoint": "https://example.com"
+                }]
+            });
+            {
+                let cmd = nym_command::new();
+                let mut params = CommandParams::new();
+                params.insert("did", did.clone());
+                params.insert("verkey", verkey);
+                params.insert("diddoc_content", diddoc_content.to_string());
+                params.insert("version", "1".to_string());
+                params.insert("send", "false".to_string());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            let transaction = ctx.get_context_transaction(None).unwrap();
+            let transaction: JsonValue = serde_json::from_str(&transaction).unwrap();
+            let content = transaction["operation"]["diddocContent"].as_str().unwrap();
+            assert_eq!(
+                diddoc_content,
+                serde_json::from_str::<JsonValue>(content).unwrap()
+            );
+            assert_eq!(1, transaction["operation"]["version"]);
+            tear_down_with_wallet_and_pool(&ctx);
+        }
+
+        #[test]
+        pub fn nym_works_for_unsupported_version() {
+            let ctx = setup_with_wallet_and_pool();
+            use_trustee(&ctx);
+            let (did, verkey) = create_new_did(&ctx);
+            {
+                let cmd = nym_command::new();
+                let mut params = CommandParams::new();
+                params.insert("did", did.clone());
+                params.insert("verkey", verkey);
+                params.insert("version", "3".to_string());
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            tear_down_with_wallet_and_pool(&ctx);
+        }
+
         #[test]
         pub fn nym_works_for_disconnected_pool_and_specific_protocol_version() {
             let ctx = setup_with_wallet();
@@ -388,6 +616,35 @@ pub mod tests {
             tear_down_with_wallet_and_pool(&ctx);
         }
 
+        #[test]
+        pub fn get_nym_works_for_timestamp() {
+            let ctx = setup_with_wallet_and_pool();
+            use_trustee(&ctx);
+            {
+                let cmd = get_nym_command::new();
+                let mut params = CommandParams::new();
+                params.insert("did", DID_TRUSTEE.to_string());
+                params.insert("timestamp", chrono::Utc::now().timestamp().to_string());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            tear_down_with_wallet_and_pool(&ctx);
+        }
+
+        #[test]
+        pub fn get_nym_works_for_seq_no_and_timestamp() {
+            let ctx = setup_with_wallet_and_pool();
+            use_trustee(&ctx);
+            {
+                let cmd = get_nym_command::new();
+                let mut params = CommandParams::new();
+                params.insert("did", DID_TRUSTEE.to_string());
+                params.insert("seq_no", "1".to_string());
+                params.insert("timestamp", "1700000000".to_string());
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            tear_down_with_wallet_and_pool(&ctx);
+        }
+
         #[test]
         pub fn get_nym_works_for_no_active_did() {
             let ctx = setup_with_wallet_and_pool();
@@ -400,6 +657,54 @@ pub mod tests {
             tear_down_with_wallet_and_pool(&ctx);
         }
 
+        #[test]
+        pub fn get_nym_works_for_alias() {
+            let ctx = setup_with_wallet_and_pool();
+            use_trustee(&ctx);
+            let (did, verkey) = create_new_did(&ctx);
+            {
+                let cmd = nym_command::new();
+                let mut params = CommandParams::new();
+                params.insert("did", did.clone());
+                params.insert("verkey", verkey);
+                params.insert("alias", "bob".to_string());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            assert!(ensure_nym_added(&ctx, &did).is_ok());
+            {
+                let cmd = get_nym_command::new();
+                let mut params = CommandParams::new();
+                params.insert("alias", "bob".to_string());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            tear_down_with_wallet_and_pool(&ctx);
+        }
+
+        #[test]
+        pub fn get_nym_works_for_unknown_alias() {
+            let ctx = setup_with_wallet_and_pool();
+            use_trustee(&ctx);
+            {
+                let cmd = get_nym_command::new();
+                let mut params = CommandParams::new();
+                params.insert("alias", "unknown".to_string());
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            tear_down_with_wallet_and_pool(&ctx);
+        }
+
+        #[test]
+        pub fn get_nym_works_for_missed_did_and_alias() {
+            let ctx = setup_with_wallet_and_pool();
+            use_trustee(&ctx);
+            {
+                let cmd = get_nym_command::new();
+                let params = CommandParams::new();
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            tear_down_with_wallet_and_pool(&ctx);
+        }
+
         #[test]
         pub fn get_nym_works_for_unknown_did() {
             let ctx = setup_with_wallet_and_pool();
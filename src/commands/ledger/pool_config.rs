@@ -32,18 +32,19 @@ pub mod pool_config_command {
         let submitter_did = ctx.ensure_active_did()?;
         let pool = ctx.get_connected_pool();
 
-        let writes = ParamParser::get_bool_param("writes", params)?;
-        let force = ParamParser::get_opt_bool_param("force", params)?.unwrap_or(false);
+        let writes = ParamParser::get_bool_param(ctx, "writes", params)?;
+        let force = ParamParser::get_opt_bool_param(ctx, "force", params)?.unwrap_or(false);
 
         let mut request =
             Ledger::indy_build_pool_config_request(pool.as_deref(), &submitter_did, writes, force)
-                .map_err(|err| println_err!("{}", err.message(None)))?;
+                .map_err(|err| err.print(ctx, None))?;
 
         let (_, response): (String, Response<JsonValue>) =
             send_write_request!(ctx, params, &mut request, &wallet, &submitter_did);
 
-        handle_transaction_response(response).map(|result| {
+        handle_transaction_response(ctx, response).map(|result| {
             print_transaction_response(
+                ctx,
                 result,
                 "NodeConfig request has been sent to Ledger.",
                 None,
@@ -36,45 +36,48 @@ pub mod pool_restart_command {
         let wallet = ctx.ensure_opened_wallet()?;
         let submitter_did = ctx.ensure_active_did()?;
 
-        let action = ParamParser::get_str_param("action", params)?;
-        let datetime = ParamParser::get_opt_str_param("datetime", params)?;
+        let action = ParamParser::get_str_param(ctx, "action", params)?;
+        let datetime = ParamParser::get_opt_str_param(ctx, "datetime", params)?;
         let nodes = ParamParser::get_opt_str_array_param("nodes", params)?;
         let timeout = ParamParser::get_opt_number_param::<i64>("timeout", params)?;
 
         let mut request =
             Ledger::indy_build_pool_restart_request(Some(&pool), &submitter_did, action, datetime)
-                .map_err(|err| println_err!("{}", err.message(Some(&pool.name))))?;
+                .map_err(|err| err.print(ctx, Some(&pool.name)))?;
 
         let response = if nodes.is_some() || timeout.is_some() {
             sign_and_submit_action(&wallet, &pool, &submitter_did, &mut request, nodes, timeout)
-                .map_err(|err| println_err!("{}", err.message(None)))?
+                .map_err(|err| err.print(ctx, None))?
         } else {
             Ledger::sign_and_submit_request(&pool, &wallet, &submitter_did, &mut request)
-                .map_err(|err| println_err!("{}", err.message(None)))?
+                .map_err(|err| err.print(ctx, None))?
         };
 
         let responses = match serde_json::from_str::<HashMap<String, String>>(&response) {
             Ok(responses) => responses,
             Err(_) => {
-                let response = serde_json::from_str::<Response<JsonValue>>(&response)
-                    .map_err(|err| println_err!("Invalid data has been received: {:?}", err))?;
-                return handle_transaction_response(response)
-                    .map(|result| println_succ!("{}", result));
+                let response =
+                    serde_json::from_str::<Response<JsonValue>>(&response).map_err(|err| {
+                        println_err!(ctx, "Invalid data has been received: {:?}", err)
+                    })?;
+                return handle_transaction_response(ctx, response)
+                    .map(|result| println_succ!(ctx, "{}", result));
             }
         };
 
         for (node, response) in responses {
             if response.eq("timeout") {
-                println_err!("Restart pool node {} timeout.", node);
+                println_err!(ctx, "Restart pool node {} timeout.", node);
                 continue;
             }
 
             let response = serde_json::from_str::<Response<JsonValue>>(&response)
-                .map_err(|err| println_err!("Invalid data has been received: {:?}", err))?;
+                .map_err(|err| println_err!(ctx, "Invalid data has been received: {:?}", err))?;
 
-            println_succ!("Restart pool response for node {}:", node);
-            let _res = handle_transaction_response(response).map(|result| {
+            println_succ!(ctx, "Restart pool response for node {}:", node);
+            let _res = handle_transaction_response(ctx, response).map(|result| {
                 print_table(
+                    ctx,
                     &result,
                     &[
                         ("identifier", "From"),
@@ -6,6 +6,7 @@ use crate::{
     command_executor::{Command, CommandContext, CommandMetadata, CommandParams},
     params_parser::ParamParser,
     tools::ledger::{Ledger, Response},
+    utils::time::rfc3339_to_datetime,
 };
 
 use serde_json::Value as JsonValue;
@@ -45,16 +46,16 @@ pub mod pool_upgrade_command {
         let submitter_did = ctx.ensure_active_did()?;
         let pool = ctx.get_connected_pool();
 
-        let name = ParamParser::get_str_param("name", params)?;
-        let version = ParamParser::get_str_param("version", params)?;
-        let action = ParamParser::get_str_param("action", params)?;
-        let sha256 = ParamParser::get_str_param("sha256", params)?;
+        let name = ParamParser::get_str_param(ctx, "name", params)?;
+        let version = ParamParser::get_str_param(ctx, "version", params)?;
+        let action = ParamParser::get_str_param(ctx, "action", params)?;
+        let sha256 = ParamParser::get_str_param(ctx, "sha256", params)?;
         let timeout = ParamParser::get_opt_number_param::<u32>("timeout", params)?;
-        let schedule = ParamParser::get_opt_str_param("schedule", params)?;
-        let justification = ParamParser::get_opt_str_param("justification", params)?;
-        let reinstall = ParamParser::get_opt_bool_param("reinstall", params)?.unwrap_or(false);
-        let force = ParamParser::get_opt_bool_param("force", params)?.unwrap_or(false);
-        let package = ParamParser::get_opt_str_param("package", params)?;
+        let schedule = ParamParser::get_opt_str_param(ctx, "schedule", params)?;
+        let justification = ParamParser::get_opt_str_param(ctx, "justification", params)?;
+        let reinstall = ParamParser::get_opt_bool_param(ctx, "reinstall", params)?.unwrap_or(false);
+        let force = ParamParser::get_opt_bool_param(ctx, "force", params)?.unwrap_or(false);
+        let package = ParamParser::get_opt_str_param(ctx, "package", params)?;
 
         let mut request = Ledger::indy_build_pool_upgrade_request(
             pool.as_deref(),
@@ -70,7 +71,7 @@ pub mod pool_upgrade_command {
             force,
             package,
         )
-        .map_err(|err| println_err!("{}", err.message(None)))?;
+        .map_err(|err| err.print(ctx, None))?;
 
         let (_, response): (String, Response<JsonValue>) =
             send_write_request!(ctx, params, &mut request, &wallet, &submitter_did);
@@ -85,7 +86,10 @@ pub mod pool_upgrade_command {
                         .map(|(key, value)| format!(
                             "\n    {:?}:{:?}",
                             key,
-                            value.as_str().unwrap_or("")
+                            value
+                                .as_str()
+                                .map(|value| rfc3339_to_datetime(ctx, value))
+                                .unwrap_or_default()
                         ))
                         .collect::<Vec<String>>()
                         .join(",")
@@ -95,8 +99,9 @@ pub mod pool_upgrade_command {
             hash = res["sha256"].as_str().map(|h| h.to_string());
         };
 
-        handle_transaction_response(response).map(|result| {
+        handle_transaction_response(ctx, response).map(|result| {
             print_transaction_response(
+                ctx,
                 result,
                 "NodeConfig request has been sent to Ledger.",
                 None,
@@ -114,11 +119,11 @@ pub mod pool_upgrade_command {
             )
         })?;
         if let Some(h) = hash {
-            println_succ!("Hash:");
+            println_succ!(ctx, "Hash:");
             println!("{}", h);
         }
         if let Some(s) = schedule {
-            println_succ!("Schedule:");
+            println_succ!(ctx, "Schedule:");
             println!("{}", s);
         }
         trace!("execute <<");
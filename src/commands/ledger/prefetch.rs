@@ -0,0 +1,300 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::{
+    command_executor::{Command, CommandContext, CommandMetadata, CommandParams},
+    error::{CliError, CliResult},
+    params_parser::ParamParser,
+    tools::{
+        anoncreds_id::{AnonCredsId, AnonCredsObject},
+        ledger::{Ledger, Response, ResponseType},
+        pool::Pool,
+    },
+    utils::{file::write_file, table::print_list_table},
+};
+
+use indy_utils::did::DidValue;
+use indy_vdr::{
+    ledger::identifiers::{CredentialDefinitionId, SchemaId},
+    pool::PreparedRequest,
+};
+use serde_json::Value as JsonValue;
+use std::path::Path;
+
+use super::common::{cred_def_to_anoncreds, schema_to_anoncreds};
+
+pub mod prefetch_command {
+    use super::*;
+
+    command!(CommandMetadata::build(
+        "prefetch",
+        "Fetch schemas and credential definitions concurrently and write them into a directory in the AnonCreds object format."
+    )
+    .add_optional_param("schema_ids", "Comma-separated schema ids (legacy or did:indy form)")
+    .add_optional_param("cred_def_ids", "Comma-separated credential definition ids (legacy or did:indy form)")
+    .add_required_param("out_dir", "The path to directory to write the objects into. Every object is written into `<id>.json` file")
+    .add_example("ledger prefetch schema_ids=VsKV7grR1BUE29mG2Fm2kX:2:gvt:1.0 out_dir=./objects")
+    .add_example("ledger prefetch schema_ids=VsKV7grR1BUE29mG2Fm2kX:2:gvt:1.0 cred_def_ids=VsKV7grR1BUE29mG2Fm2kX:3:CL:10:tag out_dir=./objects")
+    .finalize());
+
+    fn execute(ctx: &CommandContext, params: &CommandParams) -> Result<(), ()> {
+        trace!("execute >> ctx {:?} params {:?}", ctx, params);
+
+        let schema_ids =
+            ParamParser::get_opt_str_array_param("schema_ids", params)?.unwrap_or_default();
+        let cred_def_ids =
+            ParamParser::get_opt_str_array_param("cred_def_ids", params)?.unwrap_or_default();
+        let out_dir = ParamParser::get_str_param(ctx, "out_dir", params)?;
+
+        let ids = schema_ids
+            .iter()
+            .chain(cred_def_ids.iter())
+            .map(|id| AnonCredsId::parse(id.trim()))
+            .collect::<CliResult<Vec<AnonCredsId>>>()
+            .map_err(|err| err.print(ctx, None))?;
+        if ids.is_empty() {
+            println_err!(
+                ctx,
+                "Either `schema_ids` or `cred_def_ids` must be specified"
+            );
+            return Err(());
+        }
+        if let Some(id) = schema_ids.iter().zip(&ids).find_map(|(id, parsed)| {
+            (!matches!(parsed.object, AnonCredsObject::Schema { .. })).then_some(id)
+        }) {
+            println_err!(ctx, "\"{}\" is not a schema id", id);
+            return Err(());
+        }
+        if let Some(id) =
+            cred_def_ids
+                .iter()
+                .zip(&ids[schema_ids.len()..])
+                .find_map(|(id, parsed)| {
+                    (!matches!(parsed.object, AnonCredsObject::CredDef { .. })).then_some(id)
+                })
+        {
+            println_err!(ctx, "\"{}\" is not a credential definition id", id);
+            return Err(());
+        }
+
+        let pool = ctx.ensure_connected_pool()?;
+        let submitter_did = ctx.get_active_did()?;
+
+        let requests = ids
+            .iter()
+            .map(|id| build_request(&pool, submitter_did.as_deref(), id))
+            .collect::<CliResult<Vec<PreparedRequest>>>()
+            .map_err(|err| err.print(ctx, None))?;
+        let responses = Ledger::submit_requests(&pool, &requests);
+        ctx.set_pool_last_activity();
+
+        let mut failed = 0;
+        let rows = ids
+            .iter()
+            .zip(responses)
+            .map(|(id, response)| {
+                let legacy_id = id.to_legacy();
+                let (status, file) = match response
+                    .map_err(|err| err.message(Some(&pool.name)))
+                    .and_then(|response| write_object(out_dir, id, &response))
+                {
+                    Ok(file) => ("Written".to_string(), file),
+                    Err(err) => {
+                        failed += 1;
+                        (err, String::new())
+                    }
+                };
+                json!({
+                    "id": legacy_id,
+                    "type": id.type_name(),
+                    "status": status,
+                    "file": file,
+                })
+            })
+            .collect::<Vec<JsonValue>>();
+
+        print_list_table(
+            ctx,
+            &rows,
+            &[
+                ("id", "Id"),
+                ("type", "Type"),
+                ("status", "Status"),
+                ("file", "File"),
+            ],
+            "",
+        );
+
+        let res = if failed == 0 {
+            println_succ!(
+                ctx,
+                "{} object(s) have been written into \"{}\" directory",
+                rows.len(),
+                out_dir
+            );
+            Ok(())
+        } else {
+            println_err!(
+                ctx,
+                "{} of {} object(s) have not been fetched",
+                failed,
+                rows.len()
+            );
+            Err(())
+        };
+
+        trace!("execute << {:?}", res);
+        res
+    }
+}
+
+/// GET_SCHEMA or GET_CLAIM_DEF request of the object with the id.
+pub fn build_request(
+    pool: &Pool,
+    submitter_did: Option<&DidValue>,
+    id: &AnonCredsId,
+) -> CliResult<PreparedRequest> {
+    match id.object {
+        AnonCredsObject::Schema { .. } => Ledger::build_get_schema_request(
+            Some(pool),
+            submitter_did,
+            &SchemaId::from(id.to_legacy()),
+        ),
+        _ => Ledger::build_get_cred_def_request(
+            Some(pool),
+            submitter_did,
+            &CredentialDefinitionId::from(id.to_legacy()),
+        ),
+    }
+}
+
+// Writes the object into `<out_dir>/<id>.json`. Returns the path to the file or the reason of the failure.
+fn write_object(out_dir: &str, id: &AnonCredsId, response: &str) -> Result<String, String> {
+    let object = parse_object(id, response)?;
+    let content = serde_json::to_string_pretty(&object)
+        .map_err(|err| format!("Invalid data has been received: {}", err))?;
+
+    let file = Path::new(out_dir).join(format!("{}.json", file_name(&id.to_legacy())));
+    write_file(&file, &content)?;
+    Ok(file.to_string_lossy().to_string())
+}
+
+/// Object in the AnonCreds object format from the reply to the request built by `build_request`.
+pub fn parse_object(id: &AnonCredsId, response: &str) -> Result<JsonValue, String> {
+    let response = serde_json::from_str::<Response<JsonValue>>(response)
+        .map_err(|err| format!("Invalid data has been received: {}", err))?;
+    if let Some(result) = response.result.as_ref() {
+        if !result["seqNo"].is_i64() {
+            return Err("Not found".to_string());
+        }
+    }
+    let result = match response {
+        Response {
+            op: ResponseType::REPLY,
+            result: Some(result),
+            ..
+        } => result,
+        Response {
+            reason: Some(reason),
+            ..
+        } => return Err(CliError::rejected(&reason).message(None)),
+        _ => return Err("Invalid data has been received".to_string()),
+    };
+
+    Ok(match id.object {
+        AnonCredsObject::Schema { .. } => schema_to_anoncreds(&id.did, &result),
+        _ => cred_def_to_anoncreds(&id.did, &result),
+    })
+}
+
+// `:` is not allowed in Windows file names
+fn file_name(id: &str) -> String {
+    id.replace(|c: char| c == ':' || c == '/', "_")
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::{
+        commands::{setup_with_wallet_and_pool, tear_down_with_wallet_and_pool},
+        ledger::{
+            schema_command,
+            tests::{use_new_endorser, use_trustee},
+        },
+        utils::{environment::EnvironmentUtils, file::read_file},
+    };
+
+    mod prefetch {
+        use super::*;
+        use crate::ledger::schema::tests::ensure_schema_added;
+
+        #[test]
+        pub fn prefetch_works() {
+            let ctx = setup_with_wallet_and_pool();
+            let (did, _) = use_new_endorser(&ctx);
+            {
+                let cmd = schema_command::new();
+                let mut params = CommandParams::new();
+                params.insert("name", "gvt".to_string());
+                params.insert("version", "1.0".to_string());
+                params.insert("attr_names", "name,age".to_string());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            assert!(ensure_schema_added(&ctx, &did).is_ok());
+            let schema_id = format!("{}:2:gvt:1.0", did);
+            let out_dir = EnvironmentUtils::tmp_file_path("objects");
+            {
+                let cmd = prefetch_command::new();
+                let mut params = CommandParams::new();
+                params.insert("schema_ids", schema_id.clone());
+                params.insert("out_dir", out_dir.to_str().unwrap().to_string());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            let file = out_dir.join(format!("{}.json", file_name(&schema_id)));
+            let schema = serde_json::from_str::<JsonValue>(&read_file(&file).unwrap()).unwrap();
+            assert_eq!(schema["issuerId"], did);
+            assert_eq!(schema["name"], "gvt");
+            tear_down_with_wallet_and_pool(&ctx);
+        }
+
+        #[test]
+        pub fn prefetch_works_for_unknown_schema() {
+            let ctx = setup_with_wallet_and_pool();
+            use_trustee(&ctx);
+            {
+                let cmd = prefetch_command::new();
+                let mut params = CommandParams::new();
+                params.insert(
+                    "schema_ids",
+                    "V4SGRU86Z58d6TV7PBUe6f:2:unknown_schema_name:1.0".to_string(),
+                );
+                params.insert(
+                    "out_dir",
+                    EnvironmentUtils::tmp_file_path("objects")
+                        .to_str()
+                        .unwrap()
+                        .to_string(),
+                );
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            tear_down_with_wallet_and_pool(&ctx);
+        }
+
+        #[test]
+        pub fn prefetch_works_for_mismatched_id_type() {
+            let ctx = setup_with_wallet_and_pool();
+            {
+                let cmd = prefetch_command::new();
+                let mut params = CommandParams::new();
+                params.insert(
+                    "cred_def_ids",
+                    "V4SGRU86Z58d6TV7PBUe6f:2:gvt:1.0".to_string(),
+                );
+                params.insert("out_dir", "objects".to_string());
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            tear_down_with_wallet_and_pool(&ctx);
+        }
+    }
+}
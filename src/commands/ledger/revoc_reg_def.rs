@@ -0,0 +1,488 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::{
+    command_executor::{Command, CommandContext, CommandMetadata, CommandParams},
+    error::CliError,
+    params_parser::ParamParser,
+    tools::{
+        anoncreds::{
+            tails::{self, RevocationRegistryKeys},
+            CredentialDefinitionKeys,
+        },
+        anoncreds_id::{AnonCredsId, AnonCredsObject},
+        ledger::{Ledger, Response},
+        wallet::Wallet,
+    },
+    utils::environment::EnvironmentUtils,
+};
+
+use indy_vdr::ledger::{
+    identifiers::{CredentialDefinitionId, RevocationRegistryId},
+    requests::rev_reg_def::RevocationRegistryDefinition,
+};
+use serde_json::Value as JsonValue;
+use std::path::{Path, PathBuf};
+
+use super::common::{
+    handle_transaction_response, print_transaction_response, set_author_agreement,
+};
+
+const CL_ACCUM: &str = "CL_ACCUM";
+const ISSUANCE_BY_DEFAULT: &str = "ISSUANCE_BY_DEFAULT";
+const ISSUANCE_ON_DEMAND: &str = "ISSUANCE_ON_DEMAND";
+
+const REVOC_REG_DEF_HEADERS: [(&str, &str); 5] = [
+    ("id", "Id"),
+    ("credDefId", "Credential Definition"),
+    ("revocDefType", "Type"),
+    ("tag", "Tag"),
+    ("value", "Value"),
+];
+
+pub mod revoc_reg_def_command {
+    use super::*;
+
+    command!(CommandMetadata::build("revoc-reg-def", r#"Send Revocation Registry Definition transaction to the Ledger."#)
+                .add_required_param("cred_def_id", "Id of the credential definition the registry is created for (legacy or did:indy form)")
+                .add_required_param("tag", "Allows to distinct between revocation registries of the same credential definition")
+                .add_optional_param("revoc_def_type", "Revocation registry type (only CL_ACCUM supported now)")
+                .add_optional_param("issuance_type", "Issuance type: ISSUANCE_BY_DEFAULT (default) or ISSUANCE_ON_DEMAND")
+                .add_required_param("max_cred_num", "Maximum number of credentials the registry can hold")
+                .add_optional_param("accum_key", "Public accumulator key in json format. \
+                    Skip to generate the registry keys and the tails file: the private key is stored in the opened wallet \
+                    (the credential definition keys must be stored there too)")
+                .add_optional_param("tails_hash", "Hash of the tails file (required with `accum_key`)")
+                .add_optional_param("tails_location", "Location (URL) of the tails file. Set to the uploaded file if `tails_server` is specified")
+                .add_optional_param("tails_dir", "Directory to write the generated tails file to (`~/.indy_client/tails` by default)")
+                .add_optional_param("tails_server", "URL of the tails server to upload the generated tails file to")
+                .add_optional_param("sign","Sign the request (True by default)")
+                .add_optional_param("send","Send the request to the Ledger (True by default). If false then created request will be printed and stored into CLI context.")
+                .add_optional_param("queue","Spool the request to the local queue if the pool cannot be reached (False by default). Queued requests are sent by `queue flush` command.")
+                .add_optional_param("endorser","DID of the Endorser that will submit the transaction to the ledger later. \
+                    Note that specifying of this parameter implies send=false so the transaction will be prepared to pass to the endorser instead of sending to the ledger.\
+                    The created request will be printed and stored into CLI context.")
+                .add_example(r#"ledger revoc-reg-def cred_def_id=VsKV7grR1BUE29mG2Fm2kX:3:CL:10:tag tag=default max_cred_num=100 accum_key={"z":"1 0000 1 0000 1 0000"} tails_hash=5sSVCxLTYHtAJvRKJXTaGY5qAsHn6TpzBpBdJQ2dAqD tails_location=https://tails.example.com/5sSVCxLTYHtAJvRKJXTaGY5qAsHn6TpzBpBdJQ2dAqD"#)
+                .add_example(r#"ledger revoc-reg-def cred_def_id=VsKV7grR1BUE29mG2Fm2kX:3:CL:10:tag tag=default max_cred_num=100 accum_key={"z":"1 0000 1 0000 1 0000"} tails_hash=5sSVCxLTYHtAJvRKJXTaGY5qAsHn6TpzBpBdJQ2dAqD tails_location=https://tails.example.com/5sSVCxLTYHtAJvRKJXTaGY5qAsHn6TpzBpBdJQ2dAqD endorser=V4SGRU86Z58d6TV7PBUe6f"#)
+                .add_example("ledger revoc-reg-def cred_def_id=VsKV7grR1BUE29mG2Fm2kX:3:CL:10:tag tag=default max_cred_num=100 tails_server=https://tails.example.com")
+                .finalize()
+    );
+
+    fn execute(ctx: &CommandContext, params: &CommandParams) -> Result<(), ()> {
+        trace!("execute >> ctx {:?} params {:?}", ctx, params);
+
+        let wallet = ctx.ensure_opened_wallet()?;
+        let submitter_did = ctx.ensure_active_did()?;
+        let pool = ctx.get_connected_pool();
+
+        let cred_def_id = ParamParser::get_str_param(ctx, "cred_def_id", params)?;
+        let tag = ParamParser::get_str_param(ctx, "tag", params)?;
+        let revoc_def_type =
+            ParamParser::get_opt_str_param(ctx, "revoc_def_type", params)?.unwrap_or(CL_ACCUM);
+        let issuance_type = ParamParser::get_opt_str_param(ctx, "issuance_type", params)?
+            .unwrap_or(ISSUANCE_BY_DEFAULT);
+        let max_cred_num = ParamParser::get_number_param::<u32>("max_cred_num", params)?;
+        let accum_key = ParamParser::get_opt_object_param(ctx, "accum_key", params)?;
+        let tails_hash = ParamParser::get_opt_str_param(ctx, "tails_hash", params)?;
+        let tails_location = ParamParser::get_opt_str_param(ctx, "tails_location", params)?;
+        let tails_dir = ParamParser::get_opt_str_param(ctx, "tails_dir", params)?;
+        let tails_server = ParamParser::get_opt_str_param(ctx, "tails_server", params)?;
+
+        if revoc_def_type != CL_ACCUM {
+            println_err!(ctx, "Unsupported revoc_def_type {}", revoc_def_type);
+            return Err(());
+        }
+        if issuance_type != ISSUANCE_BY_DEFAULT && issuance_type != ISSUANCE_ON_DEMAND {
+            println_err!(ctx, "Unsupported issuance_type {}", issuance_type);
+            return Err(());
+        }
+
+        let cred_def_id = parse_cred_def_id(ctx, cred_def_id)?;
+        let id = RevocationRegistryId::new(&submitter_did, &cred_def_id, revoc_def_type, tag);
+
+        let (accum_key, tails_hash, tails_location) = match accum_key {
+            Some(accum_key) => {
+                let tails_hash = tails_hash.ok_or_else(|| {
+                    println_err!(
+                        ctx,
+                        "\"accum_key\" parameter requires \"tails_hash\" to be specified"
+                    )
+                })?;
+                let tails_location = tails_location.ok_or_else(|| {
+                    println_err!(
+                        ctx,
+                        "\"accum_key\" parameter requires \"tails_location\" to be specified"
+                    )
+                })?;
+                (
+                    accum_key,
+                    tails_hash.to_string(),
+                    tails_location.to_string(),
+                )
+            }
+            None => {
+                if tails_hash.is_some() {
+                    println_err!(
+                        ctx,
+                        "\"tails_hash\" parameter requires \"accum_key\" to be specified"
+                    );
+                    return Err(());
+                }
+                if tails_location.is_none() && tails_server.is_none() {
+                    println_err!(ctx, "Either \"tails_location\" or \"tails_server\" must be specified for the generated tails file");
+                    return Err(());
+                }
+                let tails_dir = tails_dir
+                    .map(PathBuf::from)
+                    .unwrap_or_else(EnvironmentUtils::tails_path);
+                let keys = get_or_generate_keys(
+                    ctx,
+                    &wallet,
+                    &id.0,
+                    &cred_def_id.0,
+                    tag,
+                    max_cred_num,
+                    &tails_dir,
+                )?;
+                let tails_location = match tails_server {
+                    Some(server) => {
+                        let location = tails::upload(server, Path::new(keys.tails_file()))
+                            .map_err(|err| err.print(ctx, None))?;
+                        println_succ!(ctx, "Tails file has been uploaded to \"{}\"", location);
+                        location
+                    }
+                    None => tails_location.unwrap_or_default().to_string(),
+                };
+                (
+                    keys.accum_key().clone(),
+                    keys.tails_hash().to_string(),
+                    tails_location,
+                )
+            }
+        };
+
+        let revoc_reg_def = serde_json::from_value::<RevocationRegistryDefinition>(json!({
+            "ver": "1.0",
+            "id": id,
+            "revocDefType": revoc_def_type,
+            "tag": tag,
+            "credDefId": cred_def_id,
+            "value": {
+                "issuanceType": issuance_type,
+                "maxCredNum": max_cred_num,
+                "publicKeys": {
+                    "accumKey": accum_key,
+                },
+                "tailsHash": tails_hash,
+                "tailsLocation": tails_location,
+            }
+        }))
+        .map_err(|err| println_err!(ctx, "Invalid revocation registry definition: {}", err))?;
+
+        let mut request =
+            Ledger::build_revoc_reg_def_request(pool.as_deref(), &submitter_did, revoc_reg_def)
+                .map_err(|err| err.print(ctx, None))?;
+
+        set_author_agreement(ctx, &mut request)?;
+
+        let (_, response): (String, Response<JsonValue>) =
+            send_write_request!(ctx, params, &mut request, &wallet, &submitter_did);
+
+        handle_transaction_response(ctx, response).map(|result| {
+            print_transaction_response(
+                ctx,
+                result,
+                "Revocation Registry Definition request has been sent to Ledger.",
+                None,
+                &REVOC_REG_DEF_HEADERS,
+                true,
+            )
+        })?;
+
+        trace!("execute <<");
+        Ok(())
+    }
+
+    // Keys stored by a previous attempt are reused together with their tails file
+    fn get_or_generate_keys(
+        ctx: &CommandContext,
+        wallet: &Wallet,
+        id: &str,
+        cred_def_id: &str,
+        tag: &str,
+        max_cred_num: u32,
+        tails_dir: &Path,
+    ) -> Result<RevocationRegistryKeys, ()> {
+        match RevocationRegistryKeys::get(wallet, id) {
+            Ok(keys) => {
+                println_warn!(
+                    ctx,
+                    "Keys of revocation registry \"{}\" already exist in the wallet and will be used",
+                    id
+                );
+                Ok(keys)
+            }
+            Err(CliError::NotFound(_)) => {
+                let cred_def = CredentialDefinitionKeys::get(wallet, cred_def_id)
+                    .map_err(|err| err.print(ctx, None))?;
+                let keys = RevocationRegistryKeys::generate(
+                    &cred_def.definition,
+                    cred_def_id,
+                    tag,
+                    max_cred_num,
+                    tails_dir,
+                )
+                .map_err(|err| err.print(ctx, None))?;
+                keys.store(wallet, id).map_err(|err| err.print(ctx, None))?;
+                println_succ!(
+                    ctx,
+                    "Keys of revocation registry \"{}\" have been generated and stored in the wallet. Tails file: \"{}\"",
+                    id,
+                    keys.tails_file()
+                );
+                Ok(keys)
+            }
+            Err(err) => Err(err.print(ctx, None)),
+        }
+    }
+
+    fn parse_cred_def_id(
+        ctx: &CommandContext,
+        cred_def_id: &str,
+    ) -> Result<CredentialDefinitionId, ()> {
+        let id = AnonCredsId::parse(cred_def_id).map_err(|err| err.print(ctx, None))?;
+        if !matches!(id.object, AnonCredsObject::CredDef { .. }) {
+            println_err!(ctx, "\"{}\" is not a credential definition id", cred_def_id);
+            return Err(());
+        }
+        Ok(CredentialDefinitionId::from(id.to_legacy()))
+    }
+}
+
+pub mod get_revoc_reg_def_command {
+    use super::*;
+
+    command!(CommandMetadata::build("get-revoc-reg-def", "Get Revocation Registry Definition from Ledger.")
+                .add_required_param("id", "Revocation registry id (legacy or did:indy form)")
+                .add_optional_param("send","Send the request to the Ledger (True by default). If false then created request will be printed and stored into CLI context.")
+                .add_example("ledger get-revoc-reg-def id=VsKV7grR1BUE29mG2Fm2kX:4:VsKV7grR1BUE29mG2Fm2kX:3:CL:10:tag:CL_ACCUM:default")
+                .finalize()
+    );
+
+    fn execute(ctx: &CommandContext, params: &CommandParams) -> Result<(), ()> {
+        trace!("execute >> ctx {:?} params {:?}", ctx, params);
+
+        let submitter_did = ctx.get_active_did()?;
+        let pool = ctx.get_connected_pool();
+
+        let id = ParamParser::get_str_param(ctx, "id", params)?;
+
+        let parsed_id = AnonCredsId::parse(id).map_err(|err| err.print(ctx, None))?;
+        if !matches!(parsed_id.object, AnonCredsObject::RevReg { .. }) {
+            println_err!(ctx, "\"{}\" is not a revocation registry id", id);
+            return Err(());
+        }
+        let id = RevocationRegistryId::from(parsed_id.to_legacy());
+
+        let mut request =
+            Ledger::build_get_revoc_reg_def_request(pool.as_deref(), submitter_did.as_deref(), &id)
+                .map_err(|err| err.print(ctx, None))?;
+
+        let (_, response) = send_read_request!(&ctx, params, &mut request);
+
+        if let Some(result) = response.result.as_ref() {
+            if !result["seqNo"].is_i64() {
+                println_err!(ctx, "Revocation Registry Definition not found");
+                return Err(());
+            }
+        };
+
+        handle_transaction_response(ctx, response).map(|result| {
+            print_transaction_response(
+                ctx,
+                result,
+                "Following Revocation Registry Definition has been received.",
+                Some("data"),
+                &REVOC_REG_DEF_HEADERS,
+                true,
+            )
+        })?;
+
+        trace!("execute <<");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::{
+        commands::{setup_with_wallet_and_pool, tear_down_with_wallet_and_pool},
+        ledger::tests::use_trustee,
+        utils::environment::EnvironmentUtils,
+    };
+    use std::fs;
+
+    const CRED_DEF_ID: &str = "V4SGRU86Z58d6TV7PBUe6f:3:CL:10:tag";
+    const ACCUM_KEY: &str = r#"{"z":"1 0000 1 0000 1 0000"}"#;
+    const TAILS_HASH: &str = "5sSVCxLTYHtAJvRKJXTaGY5qAsHn6TpzBpBdJQ2dAqD";
+
+    fn revoc_reg_def_params(cred_def_id: &str) -> CommandParams {
+        let mut params = CommandParams::new();
+        params.insert("cred_def_id", cred_def_id.to_string());
+        params.insert("tag", "default".to_string());
+        params.insert("max_cred_num", "100".to_string());
+        params.insert("accum_key", ACCUM_KEY.to_string());
+        params.insert("tails_hash", TAILS_HASH.to_string());
+        params.insert(
+            "tails_location",
+            format!("https://tails.example.com/{}", TAILS_HASH),
+        );
+        params
+    }
+
+    mod revoc_reg_def {
+        use super::*;
+
+        #[test]
+        pub fn revoc_reg_def_works_for_send_false() {
+            let ctx = setup_with_wallet_and_pool();
+            use_trustee(&ctx);
+            {
+                let cmd = revoc_reg_def_command::new();
+                let mut params = revoc_reg_def_params(CRED_DEF_ID);
+                params.insert("send", "false".to_string());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            let request =
+                serde_json::from_str::<JsonValue>(&ctx.get_context_transaction(None).unwrap())
+                    .unwrap();
+            assert_eq!("113", request["operation"]["type"]);
+            assert_eq!(CRED_DEF_ID, request["operation"]["credDefId"]);
+            assert_eq!(100, request["operation"]["value"]["maxCredNum"]);
+            tear_down_with_wallet_and_pool(&ctx);
+        }
+
+        #[test]
+        pub fn revoc_reg_def_works_for_generated_keys() {
+            let ctx = setup_with_wallet_and_pool();
+            use_trustee(&ctx);
+            let schema = json!({
+                "issuerId": "V4SGRU86Z58d6TV7PBUe6f",
+                "name": "gvt",
+                "version": "1.0",
+                "attrNames": ["name", "age"]
+            });
+            CredentialDefinitionKeys::generate(
+                "V4SGRU86Z58d6TV7PBUe6f",
+                "V4SGRU86Z58d6TV7PBUe6f:2:gvt:1.0",
+                &schema,
+                "tag",
+                true,
+            )
+            .unwrap()
+            .store(&ctx.ensure_opened_wallet().unwrap(), CRED_DEF_ID)
+            .unwrap();
+            let tails_dir = EnvironmentUtils::tmp_file_path("tails");
+            {
+                let cmd = revoc_reg_def_command::new();
+                let mut params = CommandParams::new();
+                params.insert("cred_def_id", CRED_DEF_ID.to_string());
+                params.insert("tag", "default".to_string());
+                params.insert("max_cred_num", "10".to_string());
+                params.insert("tails_dir", tails_dir.display().to_string());
+                params.insert(
+                    "tails_location",
+                    "https://tails.example.com/default".to_string(),
+                );
+                params.insert("send", "false".to_string());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            let request =
+                serde_json::from_str::<JsonValue>(&ctx.get_context_transaction(None).unwrap())
+                    .unwrap();
+            let value = &request["operation"]["value"];
+            assert!(value["publicKeys"]["accumKey"]["z"].is_string());
+            let tails_file = tails_dir.join(value["tailsHash"].as_str().unwrap());
+            assert_eq!(
+                value["tailsHash"].as_str().unwrap(),
+                tails::tails_hash(&tails_file).unwrap()
+            );
+            fs::remove_dir_all(&tails_dir).unwrap();
+            tear_down_with_wallet_and_pool(&ctx);
+        }
+
+        #[test]
+        pub fn revoc_reg_def_works_for_accum_key_without_tails_hash() {
+            let ctx = setup_with_wallet_and_pool();
+            use_trustee(&ctx);
+            {
+                let cmd = revoc_reg_def_command::new();
+                let mut params = revoc_reg_def_params(CRED_DEF_ID);
+                params.remove("tails_hash");
+                params.insert("send", "false".to_string());
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            tear_down_with_wallet_and_pool(&ctx);
+        }
+
+        #[test]
+        pub fn revoc_reg_def_works_for_invalid_cred_def_id() {
+            let ctx = setup_with_wallet_and_pool();
+            use_trustee(&ctx);
+            {
+                let cmd = revoc_reg_def_command::new();
+                let mut params = revoc_reg_def_params("V4SGRU86Z58d6TV7PBUe6f:2:gvt:1.0");
+                params.insert("send", "false".to_string());
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            tear_down_with_wallet_and_pool(&ctx);
+        }
+
+        #[test]
+        pub fn revoc_reg_def_works_for_unsupported_issuance_type() {
+            let ctx = setup_with_wallet_and_pool();
+            use_trustee(&ctx);
+            {
+                let cmd = revoc_reg_def_command::new();
+                let mut params = revoc_reg_def_params(CRED_DEF_ID);
+                params.insert("issuance_type", "ISSUANCE_ALWAYS".to_string());
+                params.insert("send", "false".to_string());
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            tear_down_with_wallet_and_pool(&ctx);
+        }
+    }
+
+    mod get_revoc_reg_def {
+        use super::*;
+
+        #[test]
+        pub fn get_revoc_reg_def_works_for_unknown() {
+            let ctx = setup_with_wallet_and_pool();
+            {
+                let cmd = get_revoc_reg_def_command::new();
+                let mut params = CommandParams::new();
+                params.insert(
+                    "id",
+                    format!("V4SGRU86Z58d6TV7PBUe6f:4:{}:CL_ACCUM:unknown", CRED_DEF_ID),
+                );
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            tear_down_with_wallet_and_pool(&ctx);
+        }
+
+        #[test]
+        pub fn get_revoc_reg_def_works_for_invalid_id() {
+            let ctx = setup_with_wallet_and_pool();
+            {
+                let cmd = get_revoc_reg_def_command::new();
+                let mut params = CommandParams::new();
+                params.insert("id", CRED_DEF_ID.to_string());
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            tear_down_with_wallet_and_pool(&ctx);
+        }
+    }
+}
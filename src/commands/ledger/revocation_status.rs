@@ -0,0 +1,205 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::{
+    command_executor::{Command, CommandContext, CommandMetadata, CommandParams},
+    params_parser::ParamParser,
+    tools::{
+        anoncreds_id::{AnonCredsId, AnonCredsObject},
+        ledger::{Ledger, Response},
+    },
+    utils::{table::print_table, time::timestamp_to_datetime},
+};
+
+use chrono::Utc;
+use indy_vdr::{ledger::identifiers::RevocationRegistryId, pool::PreparedRequest};
+use serde_json::Value as JsonValue;
+
+use super::common::{handle_transaction_response, submit_request};
+
+const ISSUANCE_BY_DEFAULT: &str = "ISSUANCE_BY_DEFAULT";
+
+pub mod revocation_status_command {
+    use super::*;
+
+    command!(CommandMetadata::build(
+        "revocation-status",
+        "Audit a revocation registry: read its definition and the delta over a time range and report issued/revoked counts and the latest accumulator."
+    )
+    .add_required_param("rev_reg_id", "Revocation registry id (legacy or did:indy form)")
+    .add_optional_param("from", "Start of the time range (Unix timestamp). The whole registry history is used by default")
+    .add_optional_param("to", "End of the time range (Unix timestamp, now by default)")
+    .add_example("ledger revocation-status rev_reg_id=VsKV7grR1BUE29mG2Fm2kX:4:VsKV7grR1BUE29mG2Fm2kX:3:CL:10:tag:CL_ACCUM:default")
+    .add_example("ledger revocation-status rev_reg_id=VsKV7grR1BUE29mG2Fm2kX:4:VsKV7grR1BUE29mG2Fm2kX:3:CL:10:tag:CL_ACCUM:default from=1700000000 to=1702592000")
+    .finalize());
+
+    fn execute(ctx: &CommandContext, params: &CommandParams) -> Result<(), ()> {
+        trace!("execute >> ctx {:?} params {:?}", ctx, params);
+
+        let rev_reg_id = ParamParser::get_str_param(ctx, "rev_reg_id", params)?;
+        let from = ParamParser::get_opt_number_param::<i64>("from", params)?;
+        let to = ParamParser::get_opt_number_param::<i64>("to", params)?
+            .unwrap_or_else(|| Utc::now().timestamp());
+
+        let id = AnonCredsId::parse(rev_reg_id).map_err(|err| err.print(ctx, None))?;
+        if !matches!(id.object, AnonCredsObject::RevReg { .. }) {
+            println_err!(ctx, "\"{}\" is not a revocation registry id", rev_reg_id);
+            return Err(());
+        }
+        if from.map(|from| from > to).unwrap_or(false) {
+            println_err!(ctx, "`from` must not be later than `to`");
+            return Err(());
+        }
+
+        let pool = ctx.ensure_connected_pool()?;
+        let submitter_did = ctx.get_active_did()?;
+        let id = RevocationRegistryId::from(id.to_legacy());
+
+        let request =
+            Ledger::build_get_revoc_reg_def_request(Some(&pool), submitter_did.as_deref(), &id)
+                .map_err(|err| err.print(ctx, None))?;
+        let definition = read_object(ctx, &request)
+            .ok_or_else(|| println_err!(ctx, "Revocation registry definition not found"))?;
+
+        let request = Ledger::build_get_revoc_reg_delta_request(
+            Some(&pool),
+            submitter_did.as_deref(),
+            &id,
+            from,
+            to,
+        )
+        .map_err(|err| err.print(ctx, None))?;
+        let delta = read_object(ctx, &request).unwrap_or(JsonValue::Null);
+
+        let status = revocation_status(ctx, &definition, &delta["value"]);
+        print_table(
+            ctx,
+            &status,
+            &[
+                ("id", "Revocation Registry"),
+                ("credDefId", "Credential Definition"),
+                ("issuanceType", "Issuance Type"),
+                ("maxCredNum", "Max Credentials"),
+                ("issued", "Issued"),
+                ("revoked", "Revoked"),
+                ("accumFrom", "Accumulator At Range Start"),
+                ("accumTo", "Latest Accumulator"),
+            ],
+        );
+
+        if definition["value"]["issuanceType"] == ISSUANCE_BY_DEFAULT {
+            println!("Credentials are issued by default in this registry: `Issued` counts re-activated credentials only.");
+        }
+        match delta["value"]["accum_to"]["txnTime"].as_i64() {
+            None => println_warn!(
+                ctx,
+                "No accumulator has been published for the registry up to {}",
+                timestamp_to_datetime(ctx, to)
+            ),
+            Some(time) if from.map(|from| time < from).unwrap_or(false) => println_warn!(
+                ctx,
+                "No accumulator update has been published within the range: the latest one is at {}",
+                timestamp_to_datetime(ctx, time)
+            ),
+            Some(_) => {}
+        }
+
+        trace!("execute <<");
+        Ok(())
+    }
+}
+
+// Returns `data` of the reply or `None` if the object is not written on the ledger.
+fn read_object(ctx: &CommandContext, request: &PreparedRequest) -> Option<JsonValue> {
+    let response = submit_request(ctx, request).ok()?;
+    let response = serde_json::from_str::<Response<JsonValue>>(&response)
+        .map_err(|err| println_err!(ctx, "Invalid data has been received: {:?}", err))
+        .ok()?;
+    let result = handle_transaction_response(ctx, response).ok()?;
+    match result["data"] {
+        JsonValue::Null => None,
+        ref data => Some(data.clone()),
+    }
+}
+
+fn revocation_status(ctx: &CommandContext, definition: &JsonValue, delta: &JsonValue) -> JsonValue {
+    let count = |list: &JsonValue| list.as_array().map(Vec::len).unwrap_or(0);
+    let accum_time = |accum: &JsonValue| {
+        accum["txnTime"].as_i64().map(|time| {
+            format!(
+                "{} (seqNo {})",
+                timestamp_to_datetime(ctx, time),
+                accum["seqNo"]
+            )
+        })
+    };
+    json!({
+        "id": definition["id"],
+        "credDefId": definition["credDefId"],
+        "issuanceType": definition["value"]["issuanceType"],
+        "maxCredNum": definition["value"]["maxCredNum"],
+        "issued": count(&delta["issued"]),
+        "revoked": count(&delta["revoked"]),
+        "accumFrom": accum_time(&delta["accum_from"]),
+        "accumTo": accum_time(&delta["accum_to"]),
+    })
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::commands::{setup_with_wallet_and_pool, tear_down_with_wallet_and_pool};
+
+    const REV_REG_ID: &str =
+        "V4SGRU86Z58d6TV7PBUe6f:4:V4SGRU86Z58d6TV7PBUe6f:3:CL:10:tag:CL_ACCUM:default";
+
+    mod revocation_status {
+        use super::*;
+
+        #[test]
+        pub fn revocation_status_works_for_unknown_registry() {
+            let ctx = setup_with_wallet_and_pool();
+            {
+                let cmd = revocation_status_command::new();
+                let mut params = CommandParams::new();
+                params.insert("rev_reg_id", REV_REG_ID.to_string());
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            tear_down_with_wallet_and_pool(&ctx);
+        }
+
+        #[test]
+        pub fn revocation_status_works_for_invalid_range() {
+            let ctx = setup_with_wallet_and_pool();
+            {
+                let cmd = revocation_status_command::new();
+                let mut params = CommandParams::new();
+                params.insert("rev_reg_id", REV_REG_ID.to_string());
+                params.insert("from", "1700000000".to_string());
+                params.insert("to", "1600000000".to_string());
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            tear_down_with_wallet_and_pool(&ctx);
+        }
+
+        #[test]
+        pub fn revocation_status_works_for_delta() {
+            let definition = json!({
+                "id": REV_REG_ID,
+                "credDefId": "V4SGRU86Z58d6TV7PBUe6f:3:CL:10:tag",
+                "value": {"issuanceType": "ISSUANCE_ON_DEMAND", "maxCredNum": 100}
+            });
+            let delta = json!({
+                "accum_to": {"seqNo": 20, "txnTime": 1700000000, "value": {}},
+                "issued": [1, 2, 3],
+                "revoked": [2]
+            });
+            let status = revocation_status(&CommandContext::new(), &definition, &delta);
+            assert_eq!(status["issued"], 3);
+            assert_eq!(status["revoked"], 1);
+            assert_eq!(status["accumFrom"], JsonValue::Null);
+            assert!(status["accumTo"].as_str().unwrap().contains("seqNo 20"));
+        }
+    }
+}
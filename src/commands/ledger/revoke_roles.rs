@@ -0,0 +1,261 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::{
+    command_executor::{
+        wait_for_user_reply, Command, CommandContext, CommandMetadata, CommandParams,
+    },
+    params_parser::ParamParser,
+    tools::{
+        ledger::{permissions::get_role, Ledger, LedgerHelpers},
+        wallet::Wallet,
+    },
+    utils::{file::read_file, progress::ProgressBar, table::print_list_table, term::print_prompt},
+};
+
+use super::{
+    batch::send_with_status,
+    common::{confirm_short_did, get_custom_roles, set_author_agreement},
+};
+
+use indy_utils::did::DidValue;
+use serde_json::Value as JsonValue;
+use std::str::FromStr;
+
+pub mod revoke_roles_command {
+    use super::*;
+
+    command!(CommandMetadata::build(
+        "revoke-roles",
+        "Remove the roles of the DIDs listed in a file by sending a NYM with empty role for each of them. \
+        The file contains one DID per line, empty lines and lines starting with `#` are ignored."
+    )
+    .add_required_param("file", "Path to the file with the DIDs to remove the roles of")
+    .add_example("ledger revoke-roles file=/home/indy/endorsers.txt")
+    .finalize());
+
+    fn execute(ctx: &CommandContext, params: &CommandParams) -> Result<(), ()> {
+        trace!("execute >> ctx {:?} params {:?}", ctx, params);
+
+        let file = ParamParser::get_str_param(ctx, "file", params)?;
+
+        let wallet = ctx.ensure_opened_wallet()?;
+        let pool = ctx.ensure_connected_pool()?;
+        let submitter_did = ctx.ensure_active_did()?;
+
+        if ctx.is_ledger_batch_started() {
+            println_err!(
+                ctx,
+                "Roles cannot be revoked inside a batch. Use `ledger batch commit` or `ledger batch abort` to finish it."
+            );
+            return Err(());
+        }
+
+        let dids = parse_dids(
+            ctx,
+            &read_file(file).map_err(|err| println_err!(ctx, "{}", err))?,
+        )?;
+        if dids.is_empty() {
+            println_err!(ctx, "There are no DIDs in the file \"{}\"", file);
+            return Err(());
+        }
+
+        print_prompt(&format!(
+            "The roles of {} DID(s) will be removed on the ledger. Would you like to continue? (y/n)",
+            dids.len()
+        ));
+        if !wait_for_user_reply(ctx) {
+            println!("The transactions have not been sent.");
+            return Ok(());
+        }
+        let identifier = match confirm_short_did(ctx) {
+            Ok(short_did) => short_did.unwrap_or_else(|| submitter_did.clone()),
+            Err(()) => {
+                println!("The transactions have not been sent.");
+                return Ok(());
+            }
+        };
+        crate::wallet::ensure_key_rotated(ctx, &wallet)?;
+
+        let custom_roles = get_custom_roles(ctx);
+        let mut progress = ProgressBar::new("nym", Some(dids.len()), 0);
+        let mut rows = Vec::new();
+        for did in dids.iter() {
+            // DIDs missing on the ledger or having no role already are not written again
+            let (role, status) = match get_role(&pool, did) {
+                Ok(None) => (
+                    JsonValue::Null,
+                    "Skipped: DID is not on the ledger".to_string(),
+                ),
+                Ok(Some(role)) if role.is_empty() => {
+                    (JsonValue::Null, "Skipped: DID has no role".to_string())
+                }
+                Ok(Some(role)) => {
+                    let role =
+                        LedgerHelpers::get_role_title(&JsonValue::String(role), &custom_roles);
+                    (
+                        role,
+                        revoke_role(ctx, &wallet, &submitter_did, &identifier, did),
+                    )
+                }
+                Err(err) => (JsonValue::Null, format!("Failed: {}", err.message(None))),
+            };
+            rows.push(json!({
+                "did": did.0,
+                "role": role,
+                "status": status,
+            }));
+            progress.inc(1);
+        }
+        progress.finish();
+
+        print_list_table(
+            ctx,
+            &rows,
+            &[
+                ("did", "Did"),
+                ("role", "Previous Role"),
+                ("status", "Status"),
+            ],
+            "",
+        );
+
+        let failed = rows
+            .iter()
+            .filter(|row| {
+                row["status"]
+                    .as_str()
+                    .map(|status| {
+                        !status.starts_with("Committed") && !status.starts_with("Skipped")
+                    })
+                    .unwrap_or(true)
+            })
+            .count();
+        if failed == 0 {
+            println_succ!(ctx, "Roles of the listed DIDs have been removed.");
+            trace!("execute <<");
+            Ok(())
+        } else {
+            println_err!(
+                ctx,
+                "Roles of {} of {} DID(s) have not been removed.",
+                failed,
+                dids.len()
+            );
+            Err(())
+        }
+    }
+}
+
+fn parse_dids(ctx: &CommandContext, content: &str) -> Result<Vec<DidValue>, ()> {
+    let mut dids: Vec<DidValue> = Vec::new();
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let did = DidValue::from_str(line)
+            .map_err(|_| println_err!(ctx, "Invalid DID {} provided", line))?;
+        if !dids.contains(&did) {
+            dids.push(did);
+        }
+    }
+    Ok(dids)
+}
+
+fn revoke_role(
+    ctx: &CommandContext,
+    wallet: &Wallet,
+    submitter_did: &DidValue,
+    identifier: &DidValue,
+    did: &DidValue,
+) -> String {
+    let pool = ctx.get_connected_pool();
+    let mut request =
+        match Ledger::build_nym_request(pool.as_deref(), identifier, did, None, None, Some("")) {
+            Ok(request) => request,
+            Err(err) => return format!("Failed: {}", err.message(None)),
+        };
+    if set_author_agreement(ctx, &mut request).is_err() {
+        return "Failed: transaction author agreement has not been set".to_string();
+    }
+    if let Err(err) = Ledger::sign_request(pool.as_deref(), wallet, submitter_did, &mut request) {
+        return format!("Failed: {}", err.message(None));
+    }
+    send_with_status(ctx, &request)
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::{
+        commands::{setup_with_wallet_and_pool, tear_down_with_wallet_and_pool},
+        ledger::tests::{create_new_did, send_nym, use_trustee},
+        utils::environment::EnvironmentUtils,
+    };
+
+    fn write_dids(name: &str, content: &str) -> String {
+        let path = EnvironmentUtils::tmp_file_path(name);
+        std::fs::create_dir_all(EnvironmentUtils::tmp_path()).unwrap();
+        std::fs::write(&path, content).unwrap();
+        path.to_string_lossy().to_string()
+    }
+
+    mod revoke_roles {
+        use super::*;
+
+        #[test]
+        pub fn parse_dids_works() {
+            let dids = parse_dids(
+                &CommandContext::new(),
+                "# endorsers\nVsKV7grR1BUE29mG2Fm2kX\n\n  5Uu7YveFSGcT3dSzjpvPab \nVsKV7grR1BUE29mG2Fm2kX\n",
+            )
+            .unwrap();
+            assert_eq!(
+                dids,
+                vec![
+                    DidValue("VsKV7grR1BUE29mG2Fm2kX".to_string()),
+                    DidValue("5Uu7YveFSGcT3dSzjpvPab".to_string())
+                ]
+            );
+        }
+
+        #[test]
+        pub fn revoke_roles_works() {
+            let ctx = setup_with_wallet_and_pool();
+            use_trustee(&ctx);
+            let (did_1, verkey_1) = create_new_did(&ctx);
+            let (did_2, verkey_2) = create_new_did(&ctx);
+            send_nym(&ctx, &did_1, &verkey_1, Some("ENDORSER"));
+            send_nym(&ctx, &did_2, &verkey_2, None);
+            let file = write_dids("revoke_roles.txt", &format!("{}\n{}\n", did_1, did_2));
+            {
+                let cmd = revoke_roles_command::new();
+                let mut params = CommandParams::new();
+                params.insert("file", file);
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            let pool = ctx.ensure_connected_pool().unwrap();
+            assert_eq!(
+                get_role(&pool, &DidValue(did_1)).unwrap(),
+                Some(String::new())
+            );
+            tear_down_with_wallet_and_pool(&ctx);
+        }
+
+        #[test]
+        pub fn revoke_roles_works_for_empty_file() {
+            let ctx = setup_with_wallet_and_pool();
+            use_trustee(&ctx);
+            let file = write_dids("revoke_roles_empty.txt", "# nothing\n");
+            {
+                let cmd = revoke_roles_command::new();
+                let mut params = CommandParams::new();
+                params.insert("file", file);
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            tear_down_with_wallet_and_pool(&ctx);
+        }
+    }
+}
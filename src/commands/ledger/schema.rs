@@ -15,7 +15,8 @@ use indy_vdr::ledger::{
 use serde_json::Value as JsonValue;
 
 use super::common::{
-    handle_transaction_response, print_transaction_response, set_author_agreement,
+    export_anoncreds_object, find_identical_object, handle_transaction_response,
+    print_transaction_response, schema_to_anoncreds, set_author_agreement,
 };
 
 pub mod schema_command {
@@ -27,11 +28,13 @@ pub mod schema_command {
                 .add_required_param("attr_names", "Schema attributes split by comma (the number of attributes should be less or equal than 125)")
                 .add_optional_param("sign","Sign the request (True by default)")
                 .add_optional_param("send","Send the request to the Ledger (True by default). If false then created request will be printed and stored into CLI context.")
+                .add_optional_param("queue","Spool the request to the local queue if the pool cannot be reached (False by default). Queued requests are sent by `queue flush` command.")
                 .add_optional_param("endorser","DID of the Endorser that will submit the transaction to the ledger later. \
                     Note that specifying of this parameter implies send=false so the transaction will be prepared to pass to the endorser instead of sending to the ledger.\
                     The created request will be printed and stored into CLI context.")
                 .add_example("ledger schema name=gvt version=1.0 attr_names=name,age")
                 .add_example("ledger schema name=gvt version=1.0 attr_names=name,age send=false")
+                .add_example("ledger schema name=gvt version=1.0 attr_names=name,age queue=true")
                 .finalize()
     );
 
@@ -42,11 +45,28 @@ pub mod schema_command {
         let submitter_did = ctx.ensure_active_did()?;
         let pool = ctx.get_connected_pool();
 
-        let name = ParamParser::get_str_param("name", params)?;
-        let version = ParamParser::get_str_param("version", params)?;
-        let attr_names = ParamParser::get_str_array_param("attr_names", params)?;
+        let name = ParamParser::get_str_param(ctx, "name", params)?;
+        let version = ParamParser::get_str_param(ctx, "version", params)?;
+        let attr_names = ParamParser::get_str_array_param(ctx, "attr_names", params)?;
 
         let id = SchemaId::new(&submitter_did, name, version);
+
+        let identical = find_identical_object(
+            ctx,
+            params,
+            |pool| Ledger::build_get_schema_request(Some(pool), None, &id),
+            |data| same_attr_names(&data["attr_names"], &attr_names),
+        )?;
+        if let Some(seq_no) = identical {
+            println_succ!(
+                ctx,
+                "Schema \"{}\" is already present on the Ledger (seqNo {}). The transaction has not been sent.",
+                id.0,
+                seq_no
+            );
+            return Ok(());
+        }
+
         let schema = Schema::SchemaV1(SchemaV1 {
             id,
             name: name.to_string(),
@@ -56,15 +76,16 @@ pub mod schema_command {
         });
 
         let mut request = Ledger::build_schema_request(pool.as_deref(), &submitter_did, schema)
-            .map_err(|err| println_err!("{}", err.message(None)))?;
+            .map_err(|err| err.print(ctx, None))?;
 
         set_author_agreement(ctx, &mut request)?;
 
         let (_, response): (String, Response<JsonValue>) =
             send_write_request!(ctx, params, &mut request, &wallet, &submitter_did);
 
-        handle_transaction_response(response).map(|result| {
+        handle_transaction_response(ctx, response).map(|result| {
             print_transaction_response(
+                ctx,
                 result,
                 "Schema request has been sent to Ledger.",
                 Some("data"),
@@ -80,6 +101,18 @@ pub mod schema_command {
         trace!("execute <<");
         Ok(())
     }
+
+    // Attribute names are compared regardless of their order
+    fn same_attr_names(existing: &JsonValue, requested: &[&str]) -> bool {
+        let mut existing: Vec<&str> = existing
+            .as_array()
+            .map(|names| names.iter().filter_map(JsonValue::as_str).collect())
+            .unwrap_or_default();
+        let mut requested = requested.to_vec();
+        existing.sort_unstable();
+        requested.sort_unstable();
+        existing == requested
+    }
 }
 
 pub mod get_schema_command {
@@ -90,7 +123,9 @@ pub mod get_schema_command {
                 .add_required_param("name", "Schema name")
                 .add_required_param("version", "Schema version")
                 .add_optional_param("send","Send the request to the Ledger (True by default). If false then created request will be printed and stored into CLI context.")
+                .add_optional_param("export", "Path to file to write the received Schema to in AnonCreds object format (as consumed by ACA-Py and AFJ)")
                 .add_example("ledger get-schema did=VsKV7grR1BUE29mG2Fm2kX name=gvt version=1.0")
+                .add_example("ledger get-schema did=VsKV7grR1BUE29mG2Fm2kX name=gvt version=1.0 export=/home/schema.json")
                 .finalize()
     );
 
@@ -100,38 +135,45 @@ pub mod get_schema_command {
         let submitter_did = ctx.get_active_did()?;
         let pool = ctx.get_connected_pool();
 
-        let target_did = ParamParser::get_did_param("did", params)?;
-        let name = ParamParser::get_str_param("name", params)?;
-        let version = ParamParser::get_str_param("version", params)?;
+        let target_did = ParamParser::get_did_param(ctx, "did", params)?;
+        let name = ParamParser::get_str_param(ctx, "name", params)?;
+        let version = ParamParser::get_str_param(ctx, "version", params)?;
+        let export = ParamParser::get_opt_str_param(ctx, "export", params)?;
 
         let id = SchemaId::new(&target_did, name, version);
 
-        let request =
+        let mut request =
             Ledger::build_get_schema_request(pool.as_deref(), submitter_did.as_deref(), &id)
-                .map_err(|err| println_err!("{}", err.message(None)))?;
+                .map_err(|err| err.print(ctx, None))?;
 
-        let (_, response) = send_read_request!(&ctx, params, &request);
+        let (_, response) = send_read_request!(&ctx, params, &mut request);
 
         if let Some(result) = response.result.as_ref() {
             if !result["seqNo"].is_i64() {
-                println_err!("Schema not found");
+                println_err!(ctx, "Schema not found");
                 return Err(());
             }
         };
 
-        handle_transaction_response(response).map(|result| {
-            print_transaction_response(
-                result,
-                "Following Schema has been received.",
-                Some("data"),
-                &[
-                    ("name", "Name"),
-                    ("version", "Version"),
-                    ("attr_names", "Attributes"),
-                ],
-                true,
-            )
-        })?;
+        let result = handle_transaction_response(ctx, response)?;
+
+        if let Some(file) = export {
+            let schema = schema_to_anoncreds(&target_did.to_string(), &result);
+            export_anoncreds_object(ctx, file, &id.0, &schema)?;
+        }
+
+        print_transaction_response(
+            ctx,
+            result,
+            "Following Schema has been received.",
+            Some("data"),
+            &[
+                ("name", "Name"),
+                ("version", "Version"),
+                ("attr_names", "Attributes"),
+            ],
+            true,
+        );
 
         trace!("execute <<");
         Ok(())
@@ -148,9 +190,13 @@ pub mod tests {
             wallet::tests::{close_wallet, open_wallet},
         },
         ledger::{
-            endorse_transaction_command,
+            custom_command, endorse_transaction_command,
             tests::{create_new_did, send_nym, use_new_endorser, use_trustee},
         },
+        utils::{
+            environment::EnvironmentUtils,
+            file::{read_file, write_file},
+        },
     };
     use indy_utils::did::DidValue;
 
@@ -173,6 +219,22 @@ pub mod tests {
             tear_down_with_wallet_and_pool(&ctx);
         }
 
+        #[test]
+        pub fn schema_works_for_already_present_schema() {
+            let ctx = setup_with_wallet_and_pool();
+            let (did, _) = use_new_endorser(&ctx);
+            for _ in 0..2 {
+                let cmd = schema_command::new();
+                let mut params = CommandParams::new();
+                params.insert("name", "gvt".to_string());
+                params.insert("version", "1.0".to_string());
+                params.insert("attr_names", "age,name".to_string());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            assert!(ensure_schema_added(&ctx, &did).is_ok());
+            tear_down_with_wallet_and_pool(&ctx);
+        }
+
         #[test]
         pub fn schema_works_for_missed_required_params() {
             let ctx = setup_with_wallet_and_pool();
@@ -230,7 +292,7 @@ pub mod tests {
                 cmd.execute(&ctx, &params).unwrap();
             }
             assert!(ensure_schema_added(&ctx, &did).is_err());
-            assert!(ctx.get_context_transaction().is_some());
+            assert!(ctx.get_context_transaction(None).is_some());
             tear_down_with_wallet_and_pool(&ctx);
         }
 
@@ -248,7 +310,7 @@ pub mod tests {
                 params.insert("send", "false".to_string());
                 cmd.execute(&ctx, &params).unwrap();
             }
-            let transaction = ctx.get_context_transaction().unwrap();
+            let transaction = ctx.get_context_transaction(None).unwrap();
             let transaction: JsonValue = serde_json::from_str(&transaction).unwrap();
             assert!(transaction["signature"].is_null());
             tear_down_with_wallet_and_pool(&ctx);
@@ -282,6 +344,51 @@ pub mod tests {
             assert!(ensure_schema_added(&ctx, &did).is_ok());
             tear_down_with_wallet_and_pool(&ctx);
         }
+
+        #[test]
+        pub fn schema_works_for_endorser_with_files() {
+            let ctx = setup_with_wallet_and_pool();
+            let (endorser_did, _) = use_new_endorser(&ctx);
+
+            let (did, verkey) = create_new_did(&ctx);
+            send_nym(&ctx, &did, &verkey, None);
+            use_did(&ctx, &did);
+
+            {
+                let cmd = schema_command::new();
+                let mut params = CommandParams::new();
+                params.insert("name", "gvt".to_string());
+                params.insert("version", "1.0".to_string());
+                params.insert("attr_names", "name,age".to_string());
+                params.insert("endorser", endorser_did.to_string());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            std::fs::create_dir_all(EnvironmentUtils::tmp_path()).unwrap();
+            let author_file = EnvironmentUtils::tmp_file_path("author_txn.json");
+            let endorsed_file = EnvironmentUtils::tmp_file_path("endorsed_txn.json");
+            write_file(&author_file, &ctx.get_context_transaction(None).unwrap()).unwrap();
+
+            use_did(&ctx, &endorser_did);
+            {
+                let cmd = endorse_transaction_command::new();
+                let mut params = CommandParams::new();
+                params.insert("file", author_file.to_string_lossy().to_string());
+                params.insert("outfile", endorsed_file.to_string_lossy().to_string());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+
+            let endorsed = read_file(&endorsed_file).unwrap();
+            let endorsed_json: JsonValue = serde_json::from_str(&endorsed).unwrap();
+            assert!(endorsed_json["signatures"][&endorser_did].is_string());
+            {
+                let cmd = custom_command::new();
+                let mut params = CommandParams::new();
+                params.insert("txn", endorsed);
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            assert!(ensure_schema_added(&ctx, &did).is_ok());
+            tear_down_with_wallet_and_pool(&ctx);
+        }
     }
 
     mod get_schema {
@@ -311,6 +418,36 @@ pub mod tests {
             tear_down_with_wallet_and_pool(&ctx);
         }
 
+        #[test]
+        pub fn get_schema_works_for_export() {
+            let ctx = setup_with_wallet_and_pool();
+            let (did, _) = use_new_endorser(&ctx);
+            {
+                let cmd = schema_command::new();
+                let mut params = CommandParams::new();
+                params.insert("name", "gvt".to_string());
+                params.insert("version", "1.0".to_string());
+                params.insert("attr_names", "name,age".to_string());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            assert!(ensure_schema_added(&ctx, &did).is_ok());
+            let path = EnvironmentUtils::tmp_file_path("schema.json");
+            {
+                let cmd = get_schema_command::new();
+                let mut params = CommandParams::new();
+                params.insert("did", did.clone());
+                params.insert("name", "gvt".to_string());
+                params.insert("version", "1.0".to_string());
+                params.insert("export", path.to_str().unwrap().to_string());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            let schema = serde_json::from_str::<JsonValue>(&read_file(&path).unwrap()).unwrap();
+            assert_eq!(schema["issuerId"], did);
+            assert_eq!(schema["name"], "gvt");
+            assert_eq!(schema["attrNames"].as_array().unwrap().len(), 2);
+            tear_down_with_wallet_and_pool(&ctx);
+        }
+
         #[test]
         pub fn schema_works_for_unknown_schema() {
             let ctx = setup_with_wallet_and_pool();
@@ -0,0 +1,228 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::{
+    command_executor::{Command, CommandContext, CommandMetadata, CommandParams},
+    commands::explore::{fetch_txns, get_ledger_size, has_txn_type, parse_ledger_type, print_txns},
+    params_parser::ParamParser,
+    utils::json_path,
+};
+
+use serde_json::Value as JsonValue;
+
+pub mod search_command {
+    use super::*;
+
+    const DEFAULT_SCAN_DEPTH: i32 = 1000;
+    const DEFAULT_LIMIT: usize = 20;
+    // Number of transactions read between progress reports
+    const PROGRESS_STEP: usize = 100;
+
+    command!(CommandMetadata::build(
+        "search",
+        "Scan a range of ledger transactions and show the ones matching the filters."
+    )
+    .add_optional_param("type", "Transaction type name or code (e.g. ATTRIB, NYM, 100)")
+    .add_optional_param(
+        "key",
+        "Field to look for at any depth of transaction data (ATTRIB raw values included) \
+        or JSON path starting with `$` (e.g. $.txn.metadata.from)"
+    )
+    .add_optional_param(
+        "contains",
+        "Substring the field value must contain. The whole transaction is searched if `key` is not set"
+    )
+    .add_optional_param(
+        "start",
+        "First sequence number to scan (the latest 1000 transactions are scanned by default)"
+    )
+    .add_optional_param("end", "Last sequence number to scan (the ledger end by default)")
+    .add_optional_param("limit", "Maximum number of transactions to show (20 by default)")
+    .add_optional_param("ledger", "Ledger to read: domain (default), pool or config")
+    .add_example("ledger search type=ATTRIB key=endpoint contains=agent.example.com")
+    .add_example("ledger search key=$.txn.metadata.from contains=V4SGRU86Z58d6TV7PBUe6f start=1 end=5000")
+    .add_example("ledger search contains=gvt limit=5")
+    .finalize());
+
+    fn execute(ctx: &CommandContext, params: &CommandParams) -> Result<(), ()> {
+        trace!("execute >> ctx {:?} params {:?}", ctx, params);
+
+        let txn_type = ParamParser::get_opt_str_param(ctx, "type", params)?;
+        let key = ParamParser::get_opt_str_param(ctx, "key", params)?;
+        let contains = ParamParser::get_opt_str_param(ctx, "contains", params)?;
+        let start = ParamParser::get_opt_number_param::<i32>("start", params)?;
+        let end = ParamParser::get_opt_number_param::<i32>("end", params)?;
+        let limit =
+            ParamParser::get_opt_number_param::<usize>("limit", params)?.unwrap_or(DEFAULT_LIMIT);
+        let ledger = ParamParser::get_opt_str_param(ctx, "ledger", params)?;
+
+        if txn_type.is_none() && key.is_none() && contains.is_none() {
+            println_err!(
+                ctx,
+                "At least one of `type`, `key` or `contains` filters must be specified"
+            );
+            return Err(());
+        }
+
+        if let Some(path) = key.filter(|key| key.starts_with('$')) {
+            json_path::select(&JsonValue::Null, path)
+                .map_err(|err| println_err!(ctx, "{}", err))?;
+        }
+
+        let ledger_type = parse_ledger_type(ctx, ledger)?;
+
+        let end = match end {
+            Some(end) => end,
+            None => get_ledger_size(ctx, ledger_type)?,
+        };
+        let start = start.unwrap_or_else(|| (end - DEFAULT_SCAN_DEPTH + 1).max(1));
+
+        if start < 1 || start > end {
+            println_err!(ctx, "Invalid sequence numbers range: {}-{}", start, end);
+            return Err(());
+        }
+
+        let seq_nos = (start..=end).collect::<Vec<i32>>();
+
+        let mut found: Vec<JsonValue> = Vec::new();
+        let mut scanned = 0;
+        for chunk in seq_nos.chunks(PROGRESS_STEP) {
+            let txns = fetch_txns(ctx, ledger_type, chunk)?;
+            scanned += chunk.len();
+
+            found.extend(
+                txns.into_iter()
+                    .flatten()
+                    .filter(|txn| matches(txn, txn_type, key, contains)),
+            );
+            println!(
+                "Scanned {}/{} transactions, {} matched",
+                scanned,
+                seq_nos.len(),
+                found.len()
+            );
+
+            if found.len() >= limit {
+                found.truncate(limit);
+                if scanned < seq_nos.len() {
+                    println_warn!(
+                        ctx,
+                        "Limit of {} transactions has been reached. Use `limit` or `start` to see more.",
+                        limit
+                    );
+                }
+                break;
+            }
+        }
+
+        print_txns(ctx, &found, "No matching transactions found");
+
+        trace!("execute <<");
+        Ok(())
+    }
+
+    fn matches(
+        txn: &JsonValue,
+        txn_type: Option<&str>,
+        key: Option<&str>,
+        contains: Option<&str>,
+    ) -> bool {
+        if let Some(txn_type) = txn_type {
+            if !has_txn_type(txn, txn_type) {
+                return false;
+            }
+        }
+
+        let txn = expand_raw_attrib(txn);
+
+        let values = match key {
+            Some(path) if path.starts_with('$') => {
+                json_path::select(&txn, path).unwrap_or_default()
+            }
+            Some(field) => json_path::find_field(&txn["txn"]["data"], field),
+            None => vec![&txn],
+        };
+
+        match contains {
+            Some(contains) => values.iter().any(|value| match value {
+                JsonValue::String(value) => value.contains(contains),
+                value => value.to_string().contains(contains),
+            }),
+            None => !values.is_empty(),
+        }
+    }
+
+    // ATTRIB raw value is stored on the ledger as JSON string
+    fn expand_raw_attrib(txn: &JsonValue) -> JsonValue {
+        let mut txn = txn.clone();
+        if let Some(raw) = txn["txn"]["data"]["raw"]
+            .as_str()
+            .and_then(|raw| serde_json::from_str::<JsonValue>(raw).ok())
+        {
+            txn["txn"]["data"]["raw"] = raw;
+        }
+        txn
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::{
+        commands::{setup_with_wallet_and_pool, tear_down_with_wallet_and_pool},
+        ledger::{attrib_command, tests::use_new_endorser},
+    };
+
+    mod search {
+        use super::*;
+
+        #[test]
+        pub fn search_works_for_attrib() {
+            let ctx = setup_with_wallet_and_pool();
+            let (did, _) = use_new_endorser(&ctx);
+            {
+                let cmd = attrib_command::new();
+                let mut params = CommandParams::new();
+                params.insert("did", did.clone());
+                params.insert(
+                    "raw",
+                    r#"{"endpoint":{"endpoint":"https://agent.example.com"}}"#.to_string(),
+                );
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            {
+                let cmd = search_command::new();
+                let mut params = CommandParams::new();
+                params.insert("type", "ATTRIB".to_string());
+                params.insert("key", "endpoint".to_string());
+                params.insert("contains", "agent.example.com".to_string());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            tear_down_with_wallet_and_pool(&ctx);
+        }
+
+        #[test]
+        pub fn search_works_for_no_filters() {
+            let ctx = setup_with_wallet_and_pool();
+            {
+                let cmd = search_command::new();
+                let params = CommandParams::new();
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            tear_down_with_wallet_and_pool(&ctx);
+        }
+
+        #[test]
+        pub fn search_works_for_invalid_json_path() {
+            let ctx = setup_with_wallet_and_pool();
+            {
+                let cmd = search_command::new();
+                let mut params = CommandParams::new();
+                params.insert("key", "$txn".to_string());
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            tear_down_with_wallet_and_pool(&ctx);
+        }
+    }
+}
@@ -6,13 +6,18 @@ use crate::{
     command_executor::{Command, CommandContext, CommandMetadata, CommandParams},
     params_parser::ParamParser,
     tools::ledger::Ledger,
+    wallet::ensure_key_rotated,
 };
 
+use indy_utils::did::DidValue;
 use indy_vdr::pool::PreparedRequest;
 
 pub mod sign_multi_command {
     use super::*;
-    use crate::error::CliError;
+    use crate::{
+        commands::ledger::endorser::{check_author_signature, warn_own_did_changes},
+        error::CliError,
+    };
     use indy_vdr::common::error::VdrErrorKind;
 
     command!(CommandMetadata::build(
@@ -23,7 +28,16 @@ pub mod sign_multi_command {
         "txn",
         "Transaction to sign. Skip to use a transaction stored into CLI context."
     )
+    .add_optional_param(
+        "name",
+        "Name of the context transaction to sign. The signed transaction is stored back under it."
+    )
+    .add_optional_param(
+        "force",
+        "Sign as endorser even if the author signature does not match the author verkey on the ledger (False by default)"
+    )
     .add_example(r#"ledger sign-multi txn={"reqId":123456789,"type":"100"}"#)
+    .add_example("ledger sign-multi name=schema")
     .finalize());
 
     fn execute(ctx: &CommandContext, params: &CommandParams) -> Result<(), ()> {
@@ -31,28 +45,45 @@ pub mod sign_multi_command {
 
         let wallet = ctx.ensure_opened_wallet()?;
         let submitter_did = ctx.ensure_active_did()?;
+        ensure_key_rotated(ctx, &wallet)?;
 
-        let param_txn = ParamParser::get_opt_str_param("txn", params)?;
+        let param_txn = ParamParser::get_opt_str_param(ctx, "txn", params)?;
+        let name = ParamParser::get_opt_str_param(ctx, "name", params)?;
+        let force = ParamParser::get_opt_bool_param(ctx, "force", params)?.unwrap_or(false);
 
-        let mut txn = get_transaction_to_use!(ctx, param_txn);
+        let mut txn = get_transaction_to_use!(ctx, param_txn, name);
+
+        let is_endorser = txn.req_json["endorser"]
+            .as_str()
+            .map(|endorser| DidValue(endorser.to_string()).to_short() == submitter_did.to_short())
+            .unwrap_or(false);
+        if is_endorser {
+            check_author_signature(ctx, &txn, force)?;
+        }
+        warn_own_did_changes(ctx, &wallet, &txn);
 
-        match Ledger::multi_sign_request(&wallet, &submitter_did, &mut txn) {
+        match Ledger::multi_sign_request(
+            ctx.get_connected_pool().as_deref(),
+            &wallet,
+            &submitter_did,
+            &mut txn,
+        ) {
             Ok(_) => {
-                println_succ!("Transaction has been signed:");
-                println_succ!("{:?}", txn.req_json.to_string());
-                ctx.set_context_transaction(Some(txn.req_json.to_string()));
+                println_succ!(ctx, "Transaction has been signed:");
+                println_succ!(ctx, "{:?}", txn.req_json.to_string());
+                ctx.set_context_transaction(name, Some(txn.req_json.to_string()));
             }
             Err(err) => match err {
                 CliError::VdrError(ref vdr_err) => match vdr_err.kind() {
                     VdrErrorKind::Unexpected => {
-                        println_err!("Signer DID: \"{}\" not found", submitter_did);
+                        println_err!(ctx, "Signer DID: \"{}\" not found", submitter_did);
                     }
                     _ => {
-                        println_err!("{}", err.message(None));
+                        err.print(ctx, None);
                     }
                 },
                 _ => {
-                    println_err!("{}", err.message(None));
+                    err.print(ctx, None);
                 }
             },
         };
@@ -5,7 +5,11 @@
 use crate::{
     command_executor::{Command, CommandContext, CommandMetadata, CommandParams},
     params_parser::ParamParser,
-    utils::file::{read_file, write_file},
+    utils::{
+        clipboard::copy_value,
+        file::{read_file, write_file},
+        pager,
+    },
 };
 
 use serde_json::Value as JsonValue;
@@ -18,18 +22,24 @@ pub mod save_transaction_command {
         "Save transaction from CLI context into a file."
     )
     .add_required_param("file", "The path to file.")
+    .add_optional_param(
+        "name",
+        "Name of the context transaction to save. Skip to use the default one."
+    )
     .add_example(r#"ledger save-transaction /home/transaction.txt"#)
+    .add_example(r#"ledger save-transaction /home/transaction.txt name=schema"#)
     .finalize());
 
     fn execute(ctx: &CommandContext, params: &CommandParams) -> Result<(), ()> {
         trace!("execute >> ctx {:?} params {:?}", ctx, params);
 
-        let file = ParamParser::get_str_param("file", params)?;
+        let file = ParamParser::get_str_param(ctx, "file", params)?;
+        let name = ParamParser::get_opt_str_param(ctx, "name", params)?;
 
-        let transaction = ctx.ensure_context_transaction()?;
+        let transaction = ctx.ensure_context_transaction(name)?;
 
         println!("Transaction: {}.", transaction);
-        println!("Would you like to save it? (y/n)");
+        crate::utils::term::print_prompt("Would you like to save it? (y/n)");
 
         let save_transaction = crate::command_executor::wait_for_user_reply(ctx);
 
@@ -39,9 +49,9 @@ pub mod save_transaction_command {
         }
 
         write_file(file, &transaction)
-            .map_err(|err| println_err!("Cannot save transaction into the file: {:?}", err))?;
+            .map_err(|err| println_err!(ctx, "Cannot save transaction into the file: {:?}", err))?;
 
-        println_succ!("The transaction has been saved.");
+        println_succ!(ctx, "The transaction has been saved.");
 
         trace!("execute <<");
         Ok(())
@@ -64,22 +74,65 @@ pub mod load_transaction_command {
         "Read transaction from a file and store it into CLI context."
     )
     .add_required_param("file", "The path to file containing a transaction to load.")
+    .add_optional_param(
+        "name",
+        "Name to store the transaction under. Skip to use the default one."
+    )
     .add_example(r#"ledger load-transaction /home/transaction.txt"#)
+    .add_example(r#"ledger load-transaction /home/transaction.txt name=schema"#)
     .finalize());
 
     fn execute(ctx: &CommandContext, params: &CommandParams) -> Result<(), ()> {
         trace!("execute >> ctx {:?} params {:?}", ctx, params);
 
-        let file = ParamParser::get_str_param("file", params)?;
+        let file = ParamParser::get_str_param(ctx, "file", params)?;
+        let name = ParamParser::get_opt_str_param(ctx, "name", params)?;
 
-        let transaction = read_file(file).map_err(|err| println_err!("{}", err))?;
+        let transaction = read_file(file).map_err(|err| println_err!(ctx, "{}", err))?;
 
         serde_json::from_str::<Request>(&transaction)
-            .map_err(|err| println_err!("File contains invalid transaction: {:?}", err))?;
+            .map_err(|err| println_err!(ctx, "File contains invalid transaction: {:?}", err))?;
 
         println!("Transaction has been loaded: {}", transaction);
 
-        ctx.set_context_transaction(Some(transaction));
+        ctx.set_context_transaction(name, Some(transaction));
+
+        trace!("execute <<");
+        Ok(())
+    }
+}
+
+pub mod last_transaction_command {
+    use super::*;
+
+    command!(CommandMetadata::build(
+        "last",
+        "Show transaction stored in CLI context (the latest built or loaded one)."
+    )
+    .add_optional_param(
+        "copy",
+        "Copy the transaction to the clipboard (false by default)"
+    )
+    .add_example("ledger last")
+    .add_example("ledger last copy=true")
+    .finalize());
+
+    fn execute(ctx: &CommandContext, params: &CommandParams) -> Result<(), ()> {
+        trace!("execute >> ctx {:?} params {:?}", ctx, params);
+
+        let copy = ParamParser::get_opt_bool_param(ctx, "copy", params)?.unwrap_or(false);
+
+        let transaction = ctx.ensure_context_transaction(None)?;
+
+        let output = serde_json::from_str::<JsonValue>(&transaction)
+            .ok()
+            .and_then(|json| serde_json::to_string_pretty(&json).ok())
+            .unwrap_or_else(|| transaction.clone());
+        pager::print(&output);
+
+        if copy {
+            copy_value(ctx, &transaction, "Transaction")?;
+        }
 
         trace!("execute <<");
         Ok(())
@@ -119,6 +172,33 @@ pub mod tests {
         }
     }
 
+    mod last_transaction {
+        use super::*;
+
+        #[test]
+        pub fn last_transaction_works() {
+            let ctx = setup();
+            ctx.set_context_transaction(None, Some(TRANSACTION.to_string()));
+            {
+                let cmd = last_transaction_command::new();
+                let params = CommandParams::new();
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            tear_down();
+        }
+
+        #[test]
+        pub fn last_transaction_works_for_no_txn_into_context() {
+            let ctx = setup();
+            {
+                let cmd = last_transaction_command::new();
+                let params = CommandParams::new();
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            tear_down();
+        }
+    }
+
     mod load_transaction {
         use super::*;
 
@@ -136,13 +216,37 @@ pub mod tests {
                 cmd.execute(&ctx, &params).unwrap();
             }
 
-            let context_txn = ctx.get_context_transaction().unwrap();
+            let context_txn = ctx.get_context_transaction(None).unwrap();
 
             assert_eq!(TRANSACTION.to_string(), context_txn);
 
             tear_down();
         }
 
+        #[test]
+        pub fn load_transaction_works_for_name() {
+            let ctx = setup();
+
+            let (_, path_str) = path();
+            write_file(&path_str, TRANSACTION).unwrap();
+
+            {
+                let cmd = load_transaction_command::new();
+                let mut params = CommandParams::new();
+                params.insert("file", path_str);
+                params.insert("name", "schema".to_string());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+
+            assert_eq!(
+                TRANSACTION.to_string(),
+                ctx.get_context_transaction(Some("schema")).unwrap()
+            );
+            assert!(ctx.get_context_transaction(None).is_none());
+
+            tear_down();
+        }
+
         #[test]
         pub fn load_transaction_works_for_invalid_transaction() {
             let ctx = setup();
@@ -184,7 +288,7 @@ pub mod tests {
             // Write long
             let (_, path_str) = path();
             {
-                ctx.set_context_transaction(Some(long_request));
+                ctx.set_context_transaction(None, Some(long_request));
 
                 let cmd = save_transaction_command::new();
                 let mut params = CommandParams::new();
@@ -195,7 +299,7 @@ pub mod tests {
             // Write short
             let (_, path_str) = path();
             {
-                ctx.set_context_transaction(Some(short_request));
+                ctx.set_context_transaction(None, Some(short_request));
 
                 let cmd = save_transaction_command::new();
                 let mut params = CommandParams::new();
@@ -5,13 +5,25 @@
 use crate::{
     command_executor::{Command, CommandContext, CommandMetadata, CommandParams},
     params_parser::ParamParser,
-    tools::ledger::{Ledger, Response},
-    utils::{file::read_file, table::print_list_table},
+    tools::{
+        ledger::{Ledger, Response},
+        pool::Pool,
+    },
+    utils::{
+        diff::{count_words, diff_words, Change},
+        file::read_file,
+        renderer::{print_value, OutputFormat},
+        table::print_list_table,
+        term::is_term,
+    },
 };
 
+use ansi_term::Color;
 use serde_json::Value as JsonValue;
 
-use super::common::{handle_transaction_response, print_transaction_response};
+use super::common::{
+    get_transaction_author_agreement, handle_transaction_response, print_transaction_response,
+};
 
 pub mod taa_command {
     use super::*;
@@ -57,8 +69,8 @@ pub mod taa_command {
         let pool = ctx.get_connected_pool();
 
         let text = ParamParser::get_opt_empty_str_param("text", params)?;
-        let file = ParamParser::get_opt_str_param("file", params)?;
-        let version = ParamParser::get_str_param("version", params)?;
+        let file = ParamParser::get_opt_str_param(ctx, "file", params)?;
+        let version = ParamParser::get_str_param(ctx, "version", params)?;
         let ratification_ts =
             ParamParser::get_opt_number_param::<u64>("ratification-timestamp", params)?;
         let retirement_ts =
@@ -66,9 +78,14 @@ pub mod taa_command {
 
         let text: Option<String> = match (text, file) {
             (Some(text_), None) => Some(text_.to_string()),
-            (None, Some(file_)) => Some(read_file(file_).map_err(|err| println_err!("{}", err))?),
+            (None, Some(file_)) => {
+                Some(read_file(file_).map_err(|err| println_err!(ctx, "{}", err))?)
+            }
             (Some(_), Some(_)) => {
-                println_err!("Only one of the parameters `text` and `file` can be specified");
+                println_err!(
+                    ctx,
+                    "Only one of the parameters `text` and `file` can be specified"
+                );
                 return Err(());
             }
             (None, None) => None,
@@ -82,15 +99,16 @@ pub mod taa_command {
             ratification_ts,
             retirement_ts,
         )
-        .map_err(|err| println_err!("{}", err.message(None)))?;
+        .map_err(|err| err.print(ctx, None))?;
 
         let (_, response): (String, Response<JsonValue>) =
             send_write_request!(ctx, params, &mut request, &wallet, &submitter_did);
 
-        handle_transaction_response(response).map(|result| {
+        handle_transaction_response(ctx, response).map(|result| {
             // TODO support multiply active TAA on the ledger IS-1441
             if let Some(text) = text {
                 print_transaction_response(
+                    ctx,
                     result,
                     "Transaction Author Agreement has been sent to Ledger.",
                     None,
@@ -133,14 +151,17 @@ pub mod taa_disable_all_command {
             pool.as_deref(),
             &submitter_did,
         )
-        .map_err(|err| println_err!("{}", err.message(None)))?;
+        .map_err(|err| err.print(ctx, None))?;
 
         let (_, response): (String, Response<JsonValue>) =
             send_write_request!(ctx, params, &mut request, &wallet, &submitter_did);
 
-        handle_transaction_response(response).map(|_| {
+        handle_transaction_response(ctx, response).map(|_| {
             ctx.set_transaction_author_info(None);
-            println_succ!("All Transaction Author Agreements on the Ledger have been disabled");
+            println_succ!(
+                ctx,
+                "All Transaction Author Agreements on the Ledger have been disabled"
+            );
         })?;
 
         trace!("execute <<");
@@ -172,20 +193,23 @@ pub mod aml_command {
         let submitter_did = ctx.ensure_active_did()?;
         let pool = ctx.get_connected_pool();
 
-        let aml = ParamParser::get_opt_str_param("aml", params)?;
-        let file = ParamParser::get_opt_str_param("file", params)?;
-        let version = ParamParser::get_str_param("version", params)?;
-        let context = ParamParser::get_opt_str_param("context", params)?;
+        let aml = ParamParser::get_opt_str_param(ctx, "aml", params)?;
+        let file = ParamParser::get_opt_str_param(ctx, "file", params)?;
+        let version = ParamParser::get_str_param(ctx, "version", params)?;
+        let context = ParamParser::get_opt_str_param(ctx, "context", params)?;
 
         let aml = match (aml, file) {
             (Some(aml_), None) => aml_.to_string(),
-            (None, Some(file_)) => read_file(file_).map_err(|err| println_err!("{}", err))?,
+            (None, Some(file_)) => read_file(file_).map_err(|err| println_err!(ctx, "{}", err))?,
             (Some(_), Some(_)) => {
-                println_err!("Only one of the parameters `aml` and `file` can be specified");
+                println_err!(
+                    ctx,
+                    "Only one of the parameters `aml` and `file` can be specified"
+                );
                 return Err(());
             }
             (None, None) => {
-                println_err!("Either `aml` or `file` parameter must be specified");
+                println_err!(ctx, "Either `aml` or `file` parameter must be specified");
                 return Err(());
             }
         };
@@ -197,13 +221,14 @@ pub mod aml_command {
             &version,
             context,
         )
-        .map_err(|err| println_err!("{}", err.message(None)))?;
+        .map_err(|err| err.print(ctx, None))?;
 
         let (_, response): (String, Response<JsonValue>) =
             send_write_request!(ctx, params, &mut request, &wallet, &submitter_did);
 
-        handle_transaction_response(response).map(|result| {
+        handle_transaction_response(ctx, response).map(|result| {
             print_transaction_response(
+                ctx,
                 result,
                 "Acceptance Mechanisms have been sent to Ledger.",
                 None,
@@ -242,23 +267,23 @@ pub mod get_acceptance_mechanisms_command {
         let pool = ctx.get_connected_pool();
 
         let timestamp = ParamParser::get_opt_number_param::<u64>("timestamp", params)?;
-        let version = ParamParser::get_opt_str_param("version", params)?;
+        let version = ParamParser::get_opt_str_param(ctx, "version", params)?;
 
-        let request = Ledger::build_get_acceptance_mechanisms_request(
+        let mut request = Ledger::build_get_acceptance_mechanisms_request(
             pool.as_deref(),
             submitter_did.as_deref(),
             timestamp,
             version,
         )
-        .map_err(|err| println_err!("{}", err.message(None)))?;
+        .map_err(|err| err.print(ctx, None))?;
 
-        let (_, response) = send_read_request!(&ctx, params, &request);
+        let (_, response) = send_read_request!(&ctx, params, &mut request);
 
-        match handle_transaction_response(response) {
+        match handle_transaction_response(ctx, response) {
             Ok(result) => {
                 let aml = result["data"]["aml"]
                     .as_object()
-                    .ok_or_else(|| println_err!("Wrong data has been received"))?;
+                    .ok_or_else(|| println_err!(ctx, "Wrong data has been received"))?;
 
                 let aml = aml
                     .iter()
@@ -275,6 +300,7 @@ pub mod get_acceptance_mechanisms_command {
                 }
 
                 print_list_table(
+                    ctx,
                     &aml,
                     &[("label", "Label"), ("description", "Description")],
                     "There are no acceptance mechanisms",
@@ -298,6 +324,127 @@ pub mod get_acceptance_mechanisms_command {
     }
 }
 
+pub mod taa_diff_command {
+    use super::*;
+
+    command!(CommandMetadata::build("taa-diff", "Show word-level changes between two Transaction Author Agreement versions set on the ledger, or between a version and a file")
+                .add_optional_param("from", "The version to compare. Skip to use the active agreement")
+                .add_optional_param("to", "The version set on the ledger to compare with")
+                .add_optional_param("file", "The path to file containing a new agreement to compare with (an alternative to the `to` parameter)")
+                .add_example("ledger taa-diff from=1.0 to=2.0")
+                .add_example("ledger taa-diff file=/home/agreement_v3.txt")
+                .finalize()
+    );
+
+    fn execute(ctx: &CommandContext, params: &CommandParams) -> Result<(), ()> {
+        trace!("execute >> ctx {:?} params {:?}", ctx, params);
+
+        let pool = ctx.ensure_connected_pool()?;
+
+        let from = ParamParser::get_opt_str_param(ctx, "from", params)?;
+        let to = ParamParser::get_opt_str_param(ctx, "to", params)?;
+        let file = ParamParser::get_opt_str_param(ctx, "file", params)?;
+
+        let (new_label, new_text) = match (to, file) {
+            (Some(version), None) => {
+                let (text, version, _) = fetch_agreement(ctx, &pool, Some(version))?;
+                (format!("version \"{}\"", version), text)
+            }
+            (None, Some(file)) => (
+                format!("file \"{}\"", file),
+                read_file(file).map_err(|err| println_err!(ctx, "{}", err))?,
+            ),
+            (Some(_), Some(_)) => {
+                println_err!(
+                    ctx,
+                    "Only one of the parameters `to` and `file` can be specified"
+                );
+                return Err(());
+            }
+            (None, None) => {
+                println_err!(ctx, "Either `to` or `file` parameter must be specified");
+                return Err(());
+            }
+        };
+        let (old_text, old_version, _) = fetch_agreement(ctx, &pool, from)?;
+        let old_label = format!("version \"{}\"", old_version);
+
+        let changes = diff_words(&old_text, &new_text);
+        let (removed, added) = count_words(&changes);
+
+        if ctx.get_output_format() == OutputFormat::Json {
+            let changes = changes
+                .iter()
+                .map(|change| match change {
+                    Change::Equal(text) => json!({ "op": "equal", "text": text }),
+                    Change::Removed(text) => json!({ "op": "removed", "text": text }),
+                    Change::Added(text) => json!({ "op": "added", "text": text }),
+                })
+                .collect::<Vec<JsonValue>>();
+            print_value(
+                ctx,
+                &json!({
+                    "from": old_label,
+                    "to": new_label,
+                    "removed": removed,
+                    "added": added,
+                    "changes": changes,
+                }),
+            );
+        } else if removed == 0 && added == 0 {
+            println_succ!(
+                ctx,
+                "Agreement texts of {} and {} are identical",
+                old_label,
+                new_label
+            );
+        } else {
+            println_succ!(ctx, "Changes from {} to {}:", old_label, new_label);
+            println!("{}", render_changes(&changes, is_term()));
+            println_succ!(ctx, "{} word(s) removed, {} word(s) added", removed, added);
+        }
+
+        trace!("execute <<");
+        Ok(())
+    }
+
+    fn fetch_agreement(
+        ctx: &CommandContext,
+        pool: &Pool,
+        version: Option<&str>,
+    ) -> Result<(String, String, Option<String>), ()> {
+        get_transaction_author_agreement(ctx, pool, version)?.ok_or_else(|| match version {
+            Some(version) => println_err!(
+                ctx,
+                "Transaction Author Agreement version \"{}\" not found on the ledger",
+                version
+            ),
+            None => println_err!(
+                ctx,
+                "There is no active Transaction Author Agreement on the ledger"
+            ),
+        })
+    }
+
+    // Word diff markers as in `git diff --word-diff`, colored in a terminal
+    pub fn render_changes(changes: &[Change], colored: bool) -> String {
+        changes
+            .iter()
+            .map(|change| match change {
+                Change::Equal(text) => text.to_string(),
+                Change::Removed(text) if colored => {
+                    Color::Red.paint(format!("[-{}-]", text)).to_string()
+                }
+                Change::Removed(text) => format!("[-{}-]", text),
+                Change::Added(text) if colored => {
+                    Color::Green.paint(format!("{{+{}+}}", text)).to_string()
+                }
+                Change::Added(text) => format!("{{+{}+}}", text),
+            })
+            .collect()
+    }
+}
+
 #[cfg(test)]
 pub mod tests {
     use super::*;
@@ -342,6 +489,33 @@ pub mod tests {
         }
     }
 
+    mod taa_diff {
+        use super::*;
+        use crate::utils::diff::diff_words;
+
+        #[test]
+        pub fn render_changes_works() {
+            let changes = diff_words("pay the fee now", "pay nothing now");
+            assert_eq!(
+                "pay [-the fee-]{+nothing+} now",
+                taa_diff_command::render_changes(&changes, false)
+            );
+        }
+
+        #[test]
+        pub fn taa_diff_works_for_unknown_version() {
+            let ctx = setup_with_wallet_and_pool();
+            {
+                let cmd = taa_diff_command::new();
+                let mut params = CommandParams::new();
+                params.insert("from", "unknown-version-1".to_string());
+                params.insert("to", "unknown-version-2".to_string());
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            tear_down_with_wallet_and_pool(&ctx);
+        }
+    }
+
     mod taa {
         use super::*;
 
@@ -38,27 +38,30 @@ pub mod get_validator_info_command {
         let timeout = ParamParser::get_opt_number_param::<i64>("timeout", params)?;
 
         let mut request = Ledger::build_get_validator_info_request(Some(&pool), &submitter_did)
-            .map_err(|err| println_err!("{}", err.message(None)))?;
+            .map_err(|err| err.print(ctx, None))?;
 
         let response = if nodes.is_some() || timeout.is_some() {
             sign_and_submit_action(&wallet, &pool, &submitter_did, &mut request, nodes, timeout)
-                .map_err(|err| println_err!("{}", err.message(None)))?
+                .map_err(|err| err.print(ctx, None))?
         } else {
             Ledger::sign_and_submit_request(&pool, &wallet, &submitter_did, &mut request)
-                .map_err(|err| println_err!("{}", err.message(None)))?
+                .map_err(|err| err.print(ctx, None))?
         };
+        ctx.set_last_response(Some(response.clone()));
 
         let responses = match serde_json::from_str::<BTreeMap<String, String>>(&response) {
             Ok(responses) => responses,
             Err(_) => {
-                let response = serde_json::from_str::<Response<JsonValue>>(&response)
-                    .map_err(|err| println_err!("Invalid data has been received: {:?}", err))?;
-                return handle_transaction_response(response)
-                    .map(|result| println_succ!("{}", result));
+                let response =
+                    serde_json::from_str::<Response<JsonValue>>(&response).map_err(|err| {
+                        println_err!(ctx, "Invalid data has been received: {:?}", err)
+                    })?;
+                return handle_transaction_response(ctx, response)
+                    .map(|result| println_succ!(ctx, "{}", result));
             }
         };
 
-        println_succ!("Validator Info:");
+        println_succ!(ctx, "Validator Info:");
 
         let mut lines: Vec<String> = Vec::new();
 
@@ -78,13 +81,13 @@ pub mod get_validator_info_command {
                 }
             };
 
-            match handle_transaction_response(response) {
+            match handle_transaction_response(ctx, response) {
                 Ok(result) => lines.push(format!("\t{:?}: {}", node, result)),
                 Err(_) => {}
             };
         }
 
-        println!("{{\n{}\n}}", lines.join(",\n"));
+        crate::utils::pager::print(&format!("{{\n{}\n}}", lines.join(",\n")));
 
         trace!("execute <<");
         Ok(())
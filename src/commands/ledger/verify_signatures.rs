@@ -0,0 +1,288 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::{
+    command_executor::{Command, CommandContext, CommandMetadata, CommandParams},
+    params_parser::ParamParser,
+    tools::{
+        did::Did,
+        ledger::{Ledger, Response, SignatureScheme},
+    },
+    utils::table::print_list_table,
+};
+
+use super::common::handle_transaction_response;
+use indy_utils::{base58, did::DidValue};
+use indy_vdr::pool::PreparedRequest;
+use serde_json::Value as JsonValue;
+
+pub mod verify_signatures_command {
+    use super::*;
+
+    command!(CommandMetadata::build(
+        "verify-signatures",
+        "Verify signatures and multi signatures of transaction against signers' verkeys."
+    )
+    .add_optional_param(
+        "txn",
+        "Transaction to verify. Skip to use a transaction stored into CLI context."
+    )
+    .add_example(r#"ledger verify-signatures txn={"reqId":123456789,"type":"100"}"#)
+    .add_example("ledger verify-signatures")
+    .finalize());
+
+    fn execute(ctx: &CommandContext, params: &CommandParams) -> Result<(), ()> {
+        trace!("execute >> ctx {:?} params {:?}", ctx, params);
+
+        let param_txn = ParamParser::get_opt_str_param(ctx, "txn", params)?;
+
+        let txn = get_transaction_to_use!(ctx, param_txn);
+
+        let scheme = Ledger::signature_scheme(ctx.get_connected_pool().as_deref());
+        let signature_input = scheme.preimage(&txn).map_err(|err| {
+            println_err!(
+                ctx,
+                "Unable to serialize transaction: {}",
+                err.message(None)
+            );
+        })?;
+
+        let mut signers: Vec<(String, Option<String>)> = Vec::new();
+        let mut add_signer = |did: &str, signature: Option<&str>| match signers
+            .iter_mut()
+            .find(|(signer, _)| signer == did)
+        {
+            Some((_, existing)) => {
+                if existing.is_none() {
+                    *existing = signature.map(String::from)
+                }
+            }
+            None => signers.push((did.to_string(), signature.map(String::from))),
+        };
+
+        // Author and endorser are expected to sign the transaction
+        if let Some(identifier) = txn.req_json["identifier"].as_str() {
+            add_signer(identifier, txn.req_json["signature"].as_str());
+        }
+        if let Some(endorser) = txn.req_json["endorser"].as_str() {
+            add_signer(endorser, None);
+        }
+        if let Some(signatures) = txn.req_json["signatures"].as_object() {
+            for (did, signature) in signatures {
+                add_signer(did, signature.as_str());
+            }
+        }
+
+        if signers.is_empty() {
+            println_err!(ctx, "Transaction does not contain any signer.");
+            return Err(());
+        }
+
+        let mut valid = true;
+        let rows = signers
+            .iter()
+            .map(|(did, signature)| {
+                let verkey = resolve_verkey(ctx, did);
+                let status = verify_signature(
+                    scheme,
+                    verkey.as_deref(),
+                    signature.as_deref(),
+                    &signature_input,
+                );
+                valid &= status == SignatureStatus::Valid;
+                json!({ "did": did, "status": status.to_string() })
+            })
+            .collect::<Vec<JsonValue>>();
+
+        print_list_table(ctx, &rows, &[("did", "Did"), ("status", "Status")], "");
+
+        if !valid {
+            println_err!(ctx, "Transaction is not completely signed.");
+            return Err(());
+        }
+
+        println_succ!(ctx, "All transaction signatures are valid.");
+
+        trace!("execute <<");
+        Ok(())
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum SignatureStatus {
+    Valid,
+    Missing,
+    InvalidEncoding,
+    UnknownVerkey,
+    Invalid(Option<String>),
+}
+
+impl std::fmt::Display for SignatureStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SignatureStatus::Valid => write!(f, "Valid"),
+            SignatureStatus::Missing => write!(f, "Missing"),
+            SignatureStatus::InvalidEncoding => write!(f, "Invalid signature encoding"),
+            SignatureStatus::UnknownVerkey => write!(f, "Unknown verkey"),
+            SignatureStatus::Invalid(None) => write!(f, "Invalid"),
+            SignatureStatus::Invalid(Some(err)) => write!(f, "Invalid: {}", err),
+        }
+    }
+}
+
+/// Check the signature of the transaction author (`identifier`) against the author's verkey set on the ledger.
+/// The author may have signed the transaction either with a single signature or with a multi signature.
+pub fn verify_author_signature(
+    ctx: &CommandContext,
+    txn: &PreparedRequest,
+) -> Result<SignatureStatus, ()> {
+    let author = txn.req_json["identifier"].as_str().ok_or_else(|| {
+        println_err!(
+            ctx,
+            "Transaction does not contain the author DID (\"identifier\" field)."
+        );
+    })?;
+    let signature = txn.req_json["signatures"][author]
+        .as_str()
+        .or_else(|| txn.req_json["signature"].as_str());
+    let scheme = Ledger::signature_scheme(ctx.get_connected_pool().as_deref());
+    let signature_input = scheme.preimage(txn).map_err(|err| {
+        println_err!(
+            ctx,
+            "Unable to serialize transaction: {}",
+            err.message(None)
+        );
+    })?;
+
+    let did = DidValue(author.to_string());
+    let verkey =
+        get_ledger_verkey(ctx, &did).and_then(|verkey| Did::full_verkey(author, &verkey).ok());
+    Ok(verify_signature(
+        scheme,
+        verkey.as_deref(),
+        signature,
+        &signature_input,
+    ))
+}
+
+/// Check of the signatures put on the transaction by the given DIDs.
+/// The signer verkeys are resolved in the same way as `ledger verify-signatures` does.
+pub fn signature_checker<'a>(
+    ctx: &'a CommandContext,
+    txn: &JsonValue,
+) -> Result<impl Fn(&str, &str) -> bool + 'a, ()> {
+    let txn = PreparedRequest::from_request_json(txn.to_string())
+        .map_err(|_| println_err!(ctx, "Invalid formatted transaction provided."))?;
+    let scheme = Ledger::signature_scheme(ctx.get_connected_pool().as_deref());
+    let signature_input = scheme.preimage(&txn).map_err(|err| {
+        println_err!(
+            ctx,
+            "Unable to serialize transaction: {}",
+            err.message(None)
+        );
+    })?;
+    Ok(move |did: &str, signature: &str| {
+        let verkey = resolve_verkey(ctx, did);
+        verify_signature(scheme, verkey.as_deref(), Some(signature), &signature_input)
+            == SignatureStatus::Valid
+    })
+}
+
+fn verify_signature(
+    scheme: SignatureScheme,
+    verkey: Option<&str>,
+    signature: Option<&str>,
+    signature_input: &[u8],
+) -> SignatureStatus {
+    let signature = match signature {
+        Some(signature) => signature,
+        None => return SignatureStatus::Missing,
+    };
+
+    let signature = match base58::decode(signature) {
+        Ok(signature) => signature,
+        Err(_) => return SignatureStatus::InvalidEncoding,
+    };
+
+    let verkey = match verkey {
+        Some(verkey) => verkey,
+        None => return SignatureStatus::UnknownVerkey,
+    };
+
+    match scheme.verify(verkey, signature_input, &signature) {
+        Ok(true) => SignatureStatus::Valid,
+        Ok(false) => SignatureStatus::Invalid(None),
+        Err(err) => SignatureStatus::Invalid(Some(err.message(None))),
+    }
+}
+
+/// Full verkey of the DID taken from the opened wallet or the ledger of the connected pool.
+pub fn resolve_verkey(ctx: &CommandContext, did: &str) -> Option<String> {
+    let did = DidValue(did.to_string());
+
+    let verkey = ctx
+        .get_opened_wallet()
+        .and_then(|wallet| Did::get(&wallet, &did).ok())
+        .map(|did_info| did_info.verkey)
+        .or_else(|| get_ledger_verkey(ctx, &did))?;
+
+    Did::full_verkey(&did.0, &verkey).ok()
+}
+
+fn get_ledger_verkey(ctx: &CommandContext, did: &DidValue) -> Option<String> {
+    let pool = ctx.get_connected_pool()?;
+    let response_json = Ledger::build_get_nym_request(Some(&pool), None, did)
+        .and_then(|request| Ledger::submit_request(&pool, &request))
+        .ok()?;
+    let response = serde_json::from_str::<Response<JsonValue>>(&response_json).ok()?;
+    let result = handle_transaction_response(ctx, response).ok()?;
+    let data = serde_json::from_str::<JsonValue>(result["data"].as_str()?).ok()?;
+    data["verkey"].as_str().map(String::from)
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::{
+        commands::{setup_with_wallet, tear_down_with_wallet},
+        did::tests::{new_did, SEED_TRUSTEE},
+        ledger::tests::TRANSACTION,
+    };
+
+    mod verify_signatures {
+        use super::*;
+
+        #[test]
+        pub fn verify_signatures_works() {
+            let ctx = setup_with_wallet();
+            new_did(&ctx, SEED_TRUSTEE);
+            {
+                let wallet = ctx.ensure_opened_wallet().unwrap();
+                let mut request = PreparedRequest::from_request_json(TRANSACTION).unwrap();
+                let did = DidValue(request.req_json["identifier"].as_str().unwrap().to_string());
+                Ledger::sign_request(None, &wallet, &did, &mut request).unwrap();
+                ctx.set_context_transaction(None, Some(request.req_json.to_string()));
+            }
+            {
+                let cmd = verify_signatures_command::new();
+                let params = CommandParams::new();
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            tear_down_with_wallet(&ctx);
+        }
+
+        #[test]
+        pub fn verify_signatures_works_for_missed_signature() {
+            let ctx = setup_with_wallet();
+            new_did(&ctx, SEED_TRUSTEE);
+            {
+                let cmd = verify_signatures_command::new();
+                let mut params = CommandParams::new();
+                params.insert("txn", TRANSACTION.to_string());
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            tear_down_with_wallet(&ctx);
+        }
+    }
+}
@@ -2,18 +2,40 @@
     Copyright © 2023 Province of British Columbia
     https://digital.gov.bc.ca/digital-trust
 */
+pub mod anoncreds;
 pub mod common;
 pub mod did;
+pub mod explore;
+pub mod fixtures;
 pub mod ledger;
+pub mod monitor;
+pub mod multisig;
+pub mod note;
 pub mod pool;
+pub mod queue;
+pub mod set;
+pub mod template;
+pub mod txn;
+pub mod util;
 pub mod wallet;
 
-use crate::command_executor::CommandContext;
+use crate::{
+    command_executor::CommandContext,
+    tools::{crypto_policy::CryptoPolicy, wallet::key_rotation::KeyRotationPolicy},
+    utils::renderer::{Output, OutputFormat},
+};
 
 use self::pool::constants::DEFAULT_POOL_PROTOCOL_VERSION;
 
 use indy_utils::did::DidValue;
-use std::rc::Rc;
+use std::{
+    collections::BTreeMap,
+    rc::Rc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// Name of the context transaction slot used when no name is given.
+pub const DEFAULT_TRANSACTION_NAME: &str = "default";
 
 impl CommandContext {
     pub fn set_active_did(&self, did: DidValue) {
@@ -32,7 +54,7 @@ impl CommandContext {
         match self.get_active_did() {
             Ok(Some(did)) => Ok(did.clone()),
             _ => {
-                println_err!("There is no active did");
+                println_err!(self, "There is no active did");
                 Err(())
             }
         }
@@ -59,7 +81,7 @@ impl CommandContext {
         match self.get_wallet() {
             Some(wallet) => Ok(wallet),
             None => {
-                println_err!("There is no opened wallet now");
+                println_err!(self, "There is no opened wallet now");
                 Err(())
             }
         }
@@ -73,7 +95,7 @@ impl CommandContext {
         let wallet = self.take_wallet();
         if let Some(wallet) = wallet {
             let wallet = Rc::try_unwrap(wallet)
-                .map_err(|_| println_err!("Unable to take the wallet ownership"))?;
+                .map_err(|_| println_err!(self, "Unable to take the wallet ownership"))?;
             Ok(Some(wallet))
         } else {
             Ok(None)
@@ -88,13 +110,14 @@ impl CommandContext {
     pub fn set_connected_pool(&self, pool: Pool) {
         self.set_sub_prompt(1, Some(format!("pool({})", pool.name)));
         self.set_pool(Some(pool));
+        self.set_pool_last_activity();
     }
 
     pub fn ensure_connected_pool(&self) -> Result<Rc<Pool>, ()> {
         match self.get_pool() {
             Some(pool) => Ok(pool),
             None => {
-                println_err!("There is no opened pool now");
+                println_err!(self, "There is no opened pool now");
                 Err(())
             }
         }
@@ -107,26 +130,103 @@ impl CommandContext {
     pub fn reset_connected_pool(&self) {
         self.set_sub_prompt(1, None);
         self.set_pool(None);
+        self.set_uint_value("POOL_LAST_ACTIVITY", None);
+    }
+
+    pub fn set_pool_last_activity(&self) {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        self.set_uint_value("POOL_LAST_ACTIVITY", Some(now));
     }
 
-    pub fn set_context_transaction(&self, request: Option<String>) {
-        self.set_string_value("LEDGER_TRANSACTION", request.clone());
+    pub fn get_pool_idle_time(&self) -> Option<Duration> {
+        let last_activity = self.get_uint_value("POOL_LAST_ACTIVITY")?;
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH + Duration::from_secs(last_activity))
+            .ok()
+    }
+
+    /// Store the transaction into the named slot (the default one for `None`) or remove it.
+    pub fn set_context_transaction(&self, name: Option<&str>, request: Option<String>) {
+        let mut transactions = self.get_context_transactions();
+        let name = name.unwrap_or(DEFAULT_TRANSACTION_NAME).to_string();
+        match request {
+            Some(request) => transactions.insert(name, request),
+            None => transactions.remove(&name),
+        };
+        self.set_string_value(
+            "LEDGER_TRANSACTIONS",
+            (!transactions.is_empty()).then(|| json!(transactions).to_string()),
+        );
     }
 
-    pub fn get_context_transaction(&self) -> Option<String> {
-        self.get_string_value("LEDGER_TRANSACTION")
+    pub fn get_context_transaction(&self, name: Option<&str>) -> Option<String> {
+        self.get_context_transactions()
+            .remove(name.unwrap_or(DEFAULT_TRANSACTION_NAME))
     }
 
-    pub fn ensure_context_transaction(&self) -> Result<String, ()> {
-        match self.get_string_value("LEDGER_TRANSACTION") {
+    pub fn ensure_context_transaction(&self, name: Option<&str>) -> Result<String, ()> {
+        match self.get_context_transaction(name) {
             Some(transaction) => Ok(transaction),
             None => {
-                println_err!("There is no transaction stored into context");
+                match name {
+                    Some(name) => {
+                        println_err!(
+                            self,
+                            "There is no transaction \"{}\" stored into context",
+                            name
+                        )
+                    }
+                    None => println_err!(self, "There is no transaction stored into context"),
+                }
                 Err(())
             }
         }
     }
 
+    /// Transactions stored into context by slot name.
+    pub fn get_context_transactions(&self) -> BTreeMap<String, String> {
+        self.get_string_value("LEDGER_TRANSACTIONS")
+            .and_then(|transactions| serde_json::from_str(&transactions).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn start_ledger_batch(&self) {
+        self.set_string_value("LEDGER_BATCH", Some(json!([]).to_string()));
+    }
+
+    pub fn is_ledger_batch_started(&self) -> bool {
+        self.get_string_value("LEDGER_BATCH").is_some()
+    }
+
+    /// Queue the request into the started batch. Returns the number of queued requests.
+    pub fn add_to_ledger_batch(&self, request: String) -> usize {
+        let mut requests = self.get_ledger_batch();
+        requests.push(request);
+        self.set_string_value("LEDGER_BATCH", Some(json!(requests).to_string()));
+        requests.len()
+    }
+
+    pub fn get_ledger_batch(&self) -> Vec<String> {
+        self.get_string_value("LEDGER_BATCH")
+            .and_then(|requests| serde_json::from_str(&requests).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn reset_ledger_batch(&self) {
+        self.set_string_value("LEDGER_BATCH", None);
+    }
+
+    pub fn set_last_response(&self, response: Option<String>) {
+        self.set_string_value("LAST_RESPONSE", response);
+    }
+
+    pub fn get_last_response(&self) -> Option<String> {
+        self.get_string_value("LAST_RESPONSE")
+    }
+
     pub fn set_transaction_author_info(&self, value: Option<(String, String, u64)>) {
         self.set_string_value(
             "AGREEMENT_TEXT",
@@ -167,6 +267,188 @@ impl CommandContext {
             None => DEFAULT_POOL_PROTOCOL_VERSION,
         }
     }
+
+    pub fn set_otel_endpoint(&self, endpoint: Option<String>) {
+        self.set_string_value("OTEL_ENDPOINT", endpoint);
+    }
+
+    pub fn get_otel_endpoint(&self) -> Option<String> {
+        self.get_string_value("OTEL_ENDPOINT")
+    }
+
+    pub fn set_ledger_request_info(&self, txn_type: &str, latency: Duration) {
+        self.set_string_value("LEDGER_REQUEST_TXN_TYPE", Some(txn_type.to_string()));
+        self.set_uint_value("LEDGER_REQUEST_LATENCY", Some(latency.as_millis() as u64));
+    }
+
+    pub fn get_ledger_request_info(&self) -> Option<(String, u64)> {
+        let txn_type = self.get_string_value("LEDGER_REQUEST_TXN_TYPE");
+        let latency = self.get_uint_value("LEDGER_REQUEST_LATENCY");
+
+        if let (Some(txn_type), Some(latency)) = (txn_type, latency) {
+            Some((txn_type, latency))
+        } else {
+            None
+        }
+    }
+
+    pub fn reset_ledger_request_info(&self) {
+        self.set_string_value("LEDGER_REQUEST_TXN_TYPE", None);
+        self.set_uint_value("LEDGER_REQUEST_LATENCY", None);
+    }
+
+    pub fn set_history_encryption(&self, enabled: bool) {
+        self.set_uint_value("ENCRYPT_HISTORY", Some(enabled as u64));
+    }
+
+    pub fn is_history_encryption_enabled(&self) -> bool {
+        self.get_uint_value("ENCRYPT_HISTORY").unwrap_or(0) == 1
+    }
+
+    pub fn set_secure_mode(&self, enabled: bool) {
+        self.set_uint_value("SECURE_MODE", Some(enabled as u64));
+    }
+
+    pub fn is_secure_mode(&self) -> bool {
+        self.get_uint_value("SECURE_MODE").unwrap_or(0) == 1
+    }
+
+    /// Run scripts written for libindy based indy-cli: unsupported commands are skipped instead of failing.
+    pub fn set_indy_cli_compat(&self, enabled: bool) {
+        self.set_uint_value("INDY_CLI_COMPAT", Some(enabled as u64));
+    }
+
+    pub fn is_indy_cli_compat(&self) -> bool {
+        self.get_uint_value("INDY_CLI_COMPAT").unwrap_or(0) == 1
+    }
+
+    /// Accept the Transaction Author Agreement of the connected pool without prompting.
+    pub fn set_taa_auto_accept(&self, enabled: bool) {
+        self.set_uint_value("TAA_AUTO_ACCEPT", Some(enabled as u64));
+    }
+
+    pub fn is_taa_auto_accept(&self) -> bool {
+        self.get_uint_value("TAA_AUTO_ACCEPT").unwrap_or(0) == 1
+    }
+
+    pub fn set_freshness_threshold(&self, seconds: Option<u64>, strict: bool) {
+        self.set_uint_value("FRESHNESS_THRESHOLD", seconds);
+        self.set_uint_value("FRESHNESS_STRICT", Some(strict as u64));
+    }
+
+    pub fn get_freshness_threshold(&self) -> Option<u64> {
+        self.get_uint_value("FRESHNESS_THRESHOLD")
+    }
+
+    pub fn is_freshness_strict(&self) -> bool {
+        self.get_uint_value("FRESHNESS_STRICT").unwrap_or(0) == 1
+    }
+
+    /// Maximum number of write requests per minute, `None` for no limit.
+    pub fn set_write_rate(&self, max_per_minute: Option<u64>) {
+        self.set_uint_value("WRITE_RATE", max_per_minute);
+    }
+
+    pub fn get_write_rate(&self) -> Option<u64> {
+        self.get_uint_value("WRITE_RATE")
+    }
+
+    pub fn set_last_write_time(&self, millis: u64) {
+        self.set_uint_value("LAST_WRITE_TIME", Some(millis));
+    }
+
+    pub fn get_last_write_time(&self) -> Option<u64> {
+        self.get_uint_value("LAST_WRITE_TIME")
+    }
+
+    pub fn set_key_rotation_policy(&self, policy: Option<KeyRotationPolicy>) {
+        self.set_uint_value(
+            "KEY_MAX_AGE_DAYS",
+            policy.as_ref().map(|policy| policy.max_age_days),
+        );
+        self.set_uint_value(
+            "KEY_ROTATION_ENFORCE",
+            policy.as_ref().map(|policy| policy.enforce as u64),
+        );
+    }
+
+    pub fn get_key_rotation_policy(&self) -> Option<KeyRotationPolicy> {
+        let max_age_days = self.get_uint_value("KEY_MAX_AGE_DAYS")?;
+        let enforce = self.get_uint_value("KEY_ROTATION_ENFORCE").unwrap_or(0) == 1;
+        Some(KeyRotationPolicy {
+            max_age_days,
+            enforce,
+        })
+    }
+
+    pub fn set_prompt_warnings(&self, warnings: &[String]) {
+        self.set_string_value("PROMPT_WARNINGS", Some(json!(warnings).to_string()));
+    }
+
+    pub fn is_prompt_warning_enabled(&self, warning: &str) -> bool {
+        self.get_string_value("PROMPT_WARNINGS")
+            .and_then(|warnings| serde_json::from_str::<Vec<String>>(&warnings).ok())
+            .map(|warnings| warnings.iter().any(|enabled| enabled == warning))
+            .unwrap_or(false)
+    }
+
+    pub fn set_production_pools(&self, pools: &[String]) {
+        self.set_string_value("PRODUCTION_POOLS", Some(json!(pools).to_string()));
+    }
+
+    pub fn is_production_pool(&self, name: &str) -> bool {
+        self.get_string_value("PRODUCTION_POOLS")
+            .and_then(|pools| serde_json::from_str::<Vec<String>>(&pools).ok())
+            .map(|pools| pools.iter().any(|pool| pool == name))
+            .unwrap_or(false)
+    }
+
+    /// Cache the ledger role of the active DID. `key` identifies the pool and DID the role has been requested for.
+    pub fn set_active_did_role(&self, key: &str, role: Option<String>) {
+        self.set_string_value("ACTIVE_DID_ROLE_KEY", Some(key.to_string()));
+        self.set_string_value("ACTIVE_DID_ROLE", role);
+    }
+
+    pub fn get_active_did_role(&self, key: &str) -> Option<Option<String>> {
+        match self.get_string_value("ACTIVE_DID_ROLE_KEY") {
+            Some(cached_key) if cached_key == key => Some(self.get_string_value("ACTIVE_DID_ROLE")),
+            _ => None,
+        }
+    }
+
+    pub fn set_output_format(&self, format: OutputFormat) {
+        self.set_uint_value("OUTPUT_FORMAT", Some(format as u64));
+    }
+
+    pub fn get_output_format(&self) -> OutputFormat {
+        self.get_uint_value("OUTPUT_FORMAT")
+            .map(|format| OutputFormat::from_u8(format as u8))
+            .unwrap_or(OutputFormat::Human)
+    }
+
+    pub fn set_crypto_policy(&self, policy: CryptoPolicy) {
+        self.set_uint_value("CRYPTO_POLICY", Some(policy as u64));
+    }
+
+    pub fn get_crypto_policy(&self) -> CryptoPolicy {
+        self.get_uint_value("CRYPTO_POLICY")
+            .map(|policy| CryptoPolicy::from_u8(policy as u8))
+            .unwrap_or_default()
+    }
+
+    pub fn set_pager(&self, enabled: bool) {
+        self.set_uint_value("PAGER", Some(enabled as u64));
+    }
+
+    pub fn is_pager_enabled(&self) -> bool {
+        self.get_uint_value("PAGER").unwrap_or(1) == 1
+    }
+}
+
+impl Output for CommandContext {
+    fn output_format(&self) -> OutputFormat {
+        self.get_output_format()
+    }
 }
 
 #[cfg(test)]
@@ -203,7 +485,7 @@ where
 }
 
 #[cfg(test)]
-use crate::utils::test::TestUtils;
+use crate::test_utils::TestUtils;
 
 #[cfg(test)]
 fn setup() -> CommandContext {
@@ -0,0 +1,18 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::command_executor::{CommandGroup, CommandGroupMetadata};
+
+pub mod start;
+
+pub use self::start::*;
+
+pub mod group {
+    use super::*;
+
+    command_group!(CommandGroupMetadata::new(
+        "monitor",
+        "Periodic collection of validator info and pool status"
+    ));
+}
@@ -0,0 +1,397 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::{
+    command_executor::{Command, CommandContext, CommandMetadata, CommandParams},
+    params_parser::ParamParser,
+    tools::{
+        ledger::{Ledger, Response, ResponseType},
+        pool::{health, Pool},
+        wallet::Wallet,
+    },
+    utils::{
+        metrics::{Metrics, MetricsServer},
+        time::format_duration,
+    },
+};
+
+use chrono::{DateTime, Utc};
+use indy_utils::did::DidValue;
+use serde_json::Value as JsonValue;
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    rc::Rc,
+    thread,
+    time::Duration,
+};
+
+const DEFAULT_INTERVAL: u64 = 300;
+const NODE_CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+const SNAPSHOT_PREFIX: &str = "validator-info-";
+
+pub mod start_command {
+    use super::*;
+
+    command!(CommandMetadata::build(
+        "start",
+        "Periodically collect validator info of all nodes and reachability of the connected pool into timestamped JSON files. \
+        Runs until the CLI is interrupted or the requested number of collections is done. \
+        Validator info requires the active DID to have TRUSTEE, STEWARD or NETWORK_MONITOR role."
+    )
+    .add_required_param("out_dir", "Directory to write the collected snapshots to")
+    .add_optional_param("interval", "Seconds between collections (300 by default)")
+    .add_optional_param("retention", "The number of the latest snapshots to keep in the directory (all are kept by default)")
+    .add_optional_param("count", "Stop after the given number of collections (run until interrupted by default)")
+    .add_optional_param("metrics", "Address to serve Prometheus metrics at `/metrics` path, e.g. 0.0.0.0:9100 (the `--serve` endpoint by default)")
+    .add_example("monitor start out_dir=/var/lib/indy-monitor")
+    .add_example("monitor start out_dir=/var/lib/indy-monitor interval=60 retention=1440")
+    .add_example("monitor start out_dir=/var/lib/indy-monitor metrics=0.0.0.0:9100")
+    .finalize());
+
+    fn execute(ctx: &CommandContext, params: &CommandParams) -> Result<(), ()> {
+        trace!("execute >> ctx {:?} params {:?}", ctx, params);
+
+        let out_dir = ParamParser::get_str_param(ctx, "out_dir", params)?;
+        let interval = ParamParser::get_opt_number_param::<u64>("interval", params)?
+            .unwrap_or(DEFAULT_INTERVAL);
+        let retention = ParamParser::get_opt_number_param::<usize>("retention", params)?;
+        let count = ParamParser::get_opt_number_param::<u64>("count", params)?;
+        let metrics_address = ParamParser::get_opt_str_param(ctx, "metrics", params)?;
+
+        let pool = ctx.ensure_connected_pool()?;
+        let wallet = ctx.ensure_opened_wallet()?;
+        let submitter_did = ctx.ensure_active_did()?;
+
+        if interval == 0 {
+            println_err!(ctx, "Interval must be greater than 0 seconds");
+            return Err(());
+        }
+        if retention == Some(0) {
+            println_err!(ctx, "Retention must keep at least 1 snapshot");
+            return Err(());
+        }
+
+        let out_dir = PathBuf::from(out_dir);
+        fs::create_dir_all(&out_dir).map_err(|err| {
+            println_err!(
+                ctx,
+                "Unable to create directory \"{}\": {}",
+                out_dir.display(),
+                err
+            )
+        })?;
+
+        // The endpoint started with `--serve` option is used unless another address is requested
+        let metrics_server = match metrics_address {
+            Some(address) => {
+                let server =
+                    MetricsServer::start(address).map_err(|err| println_err!(ctx, "{}", err))?;
+                Some(Rc::new(server))
+            }
+            None => ctx.get_metrics_server(),
+        };
+        if let Some(ref server) = metrics_server {
+            println!(
+                "Prometheus metrics are served at http://{}/metrics",
+                server.address
+            );
+        }
+
+        println!(
+            "Collecting validator info of pool \"{}\" every {} into \"{}\"",
+            pool.name,
+            format_duration(interval),
+            out_dir.display()
+        );
+
+        let mut collected: u64 = 0;
+        let mut failed: u64 = 0;
+        loop {
+            let time = Utc::now();
+            let snapshot = collect_snapshot(&pool, &wallet, &submitter_did, time);
+            let path = write_snapshot(ctx, &out_dir, time, &snapshot)?;
+            println!(
+                "{}: {} of {} nodes are reachable, snapshot has been saved to \"{}\"",
+                time.to_rfc3339(),
+                snapshot["reachable"],
+                snapshot["total"],
+                path.display()
+            );
+
+            if let Some(retention) = retention {
+                remove_old_snapshots(ctx, &out_dir, retention)?;
+            }
+
+            collected += 1;
+            if snapshot["validatorInfo"]["error"].is_string() {
+                failed += 1;
+            }
+            if let Some(ref server) = metrics_server {
+                server.publish(
+                    "monitor",
+                    build_metrics(&pool, &snapshot, collected, failed),
+                );
+                server.publish("ledger", pool.latency_stats().metrics(&pool.name));
+            }
+
+            if count.map(|count| collected >= count).unwrap_or(false) {
+                break;
+            }
+            thread::sleep(Duration::from_secs(interval));
+        }
+
+        println_succ!(ctx, "{} snapshot(s) have been collected", collected);
+
+        trace!("execute <<");
+        Ok(())
+    }
+}
+
+// Failures are recorded into the snapshot so that a single unavailable collection does not stop monitoring
+fn collect_snapshot(
+    pool: &Pool,
+    wallet: &Wallet,
+    submitter_did: &DidValue,
+    time: DateTime<Utc>,
+) -> JsonValue {
+    let (nodes, nodes_error) = match Pool::check_nodes(&pool.name, NODE_CHECK_TIMEOUT) {
+        Ok(nodes) => (nodes, None),
+        Err(err) => (Vec::new(), Some(err.message(Some(&pool.name)))),
+    };
+    let reachable = nodes.iter().filter(|node| node.is_reachable()).count();
+
+    let validator_info = Ledger::build_get_validator_info_request(Some(pool), submitter_did)
+        .and_then(|mut request| {
+            Ledger::sign_and_submit_request(pool, wallet, submitter_did, &mut request)
+        })
+        .map(|response| parse_validator_info(&response))
+        .unwrap_or_else(|err| json!({ "error": err.message(Some(&pool.name)) }));
+
+    json!({
+        "time": time.to_rfc3339(),
+        "timestamp": time.timestamp(),
+        "pool": pool.name,
+        "total": nodes.len(),
+        "reachable": reachable,
+        "consensus": !nodes.is_empty() && reachable >= health::consensus_quorum(nodes.len()),
+        "nodes": nodes.iter().map(|node| json!({
+            "alias": node.alias,
+            "address": node.address,
+            "reachable": node.is_reachable(),
+            "error": node.error,
+        })).collect::<Vec<JsonValue>>(),
+        "nodesError": nodes_error,
+        "validatorInfo": validator_info,
+    })
+}
+
+fn build_metrics(pool: &Pool, snapshot: &JsonValue, collected: u64, failed: u64) -> Metrics {
+    let name = pool.name.as_str();
+    let pool_label = || vec![("pool", name)];
+    let as_f64 = |value: &JsonValue| value.as_f64().unwrap_or_default();
+
+    let mut metrics = Metrics::new();
+    metrics.counter(
+        "indy_cli_monitor_collections_total",
+        "Number of monitor collections",
+        &[(pool_label(), collected as f64)],
+    );
+    metrics.counter(
+        "indy_cli_monitor_collection_errors_total",
+        "Number of monitor collections which failed to get validator info",
+        &[(pool_label(), failed as f64)],
+    );
+    metrics.gauge(
+        "indy_cli_monitor_last_collection_timestamp_seconds",
+        "Time of the latest monitor collection",
+        &[(pool_label(), as_f64(&snapshot["timestamp"]))],
+    );
+    metrics.gauge(
+        "indy_cli_pool_nodes",
+        "Number of validator nodes in the pool",
+        &[(pool_label(), as_f64(&snapshot["total"]))],
+    );
+    metrics.gauge(
+        "indy_cli_pool_nodes_reachable",
+        "Number of validator nodes responding on their client endpoints",
+        &[(pool_label(), as_f64(&snapshot["reachable"]))],
+    );
+    metrics.gauge(
+        "indy_cli_pool_consensus",
+        "Whether enough nodes are reachable to reach consensus",
+        &[(
+            pool_label(),
+            snapshot["consensus"].as_bool().unwrap_or(false) as u8 as f64,
+        )],
+    );
+
+    let nodes = snapshot["nodes"].as_array().cloned().unwrap_or_default();
+    metrics.gauge(
+        "indy_cli_node_reachable",
+        "Whether the node responds on its client endpoint",
+        &nodes
+            .iter()
+            .map(|node| {
+                (
+                    vec![
+                        ("pool", name),
+                        ("node", node["alias"].as_str().unwrap_or_default()),
+                    ],
+                    node["reachable"].as_bool().unwrap_or(false) as u8 as f64,
+                )
+            })
+            .collect::<Vec<_>>(),
+    );
+
+    let validator_info = snapshot["validatorInfo"]
+        .as_object()
+        .cloned()
+        .unwrap_or_default();
+    metrics.gauge(
+        "indy_cli_node_validator_info_up",
+        "Whether the node has replied to the latest validator info request",
+        &validator_info
+            .iter()
+            .filter(|(node, _)| node.as_str() != "error")
+            .map(|(node, info)| {
+                (
+                    vec![("pool", name), ("node", node.as_str())],
+                    info["error"].is_null() as u8 as f64,
+                )
+            })
+            .collect::<Vec<_>>(),
+    );
+    metrics
+}
+
+// Validator info response is a map of node names to their raw replies
+fn parse_validator_info(response: &str) -> JsonValue {
+    let responses = match serde_json::from_str::<BTreeMap<String, String>>(response) {
+        Ok(responses) => responses,
+        Err(_) => return json!({ "error": response }),
+    };
+
+    let nodes = responses
+        .into_iter()
+        .map(|(node, response)| {
+            let info = match serde_json::from_str::<Response<JsonValue>>(&response) {
+                _ if response == "timeout" => json!({ "error": "timeout" }),
+                Ok(Response {
+                    op: ResponseType::REPLY,
+                    result: Some(result),
+                    ..
+                }) => result,
+                Ok(Response {
+                    reason: Some(reason),
+                    ..
+                }) => json!({ "error": reason }),
+                _ => json!({ "error": "Invalid data has been received" }),
+            };
+            (node, info)
+        })
+        .collect::<serde_json::Map<String, JsonValue>>();
+    JsonValue::Object(nodes)
+}
+
+fn write_snapshot(
+    ctx: &CommandContext,
+    out_dir: &Path,
+    time: DateTime<Utc>,
+    snapshot: &JsonValue,
+) -> Result<PathBuf, ()> {
+    // Names sort in the collection order
+    let path = out_dir.join(format!(
+        "{}{}.json",
+        SNAPSHOT_PREFIX,
+        time.format("%Y%m%dT%H%M%SZ")
+    ));
+    let content = serde_json::to_string_pretty(snapshot)
+        .map_err(|err| println_err!(ctx, "Unable to serialize snapshot: {}", err))?;
+    fs::write(&path, content)
+        .map_err(|err| println_err!(ctx, "Unable to write \"{}\": {}", path.display(), err))?;
+    Ok(path)
+}
+
+fn remove_old_snapshots(ctx: &CommandContext, out_dir: &Path, retention: usize) -> Result<(), ()> {
+    let mut snapshots = list_snapshots(out_dir)
+        .map_err(|err| println_err!(ctx, "Unable to read \"{}\": {}", out_dir.display(), err))?;
+    snapshots.sort();
+
+    let outdated = snapshots.len().saturating_sub(retention);
+    for path in snapshots.into_iter().take(outdated) {
+        fs::remove_file(&path)
+            .map_err(|err| println_err!(ctx, "Unable to remove \"{}\": {}", path.display(), err))?;
+    }
+    Ok(())
+}
+
+fn list_snapshots(out_dir: &Path) -> std::io::Result<Vec<PathBuf>> {
+    let mut snapshots = Vec::new();
+    for entry in fs::read_dir(out_dir)? {
+        let path = entry?.path();
+        let is_snapshot = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .map(|name| name.starts_with(SNAPSHOT_PREFIX) && name.ends_with(".json"))
+            .unwrap_or(false);
+        if is_snapshot {
+            snapshots.push(path);
+        }
+    }
+    Ok(snapshots)
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::{
+        commands::{setup_with_wallet_and_pool, tear_down_with_wallet_and_pool},
+        ledger::tests::use_trustee,
+        utils::environment::EnvironmentUtils,
+    };
+
+    mod start {
+        use super::*;
+
+        #[test]
+        pub fn start_works() {
+            let ctx = setup_with_wallet_and_pool();
+            use_trustee(&ctx);
+            let out_dir = EnvironmentUtils::tmp_file_path("monitor");
+            {
+                let cmd = start_command::new();
+                let mut params = CommandParams::new();
+                params.insert("out_dir", out_dir.display().to_string());
+                params.insert("interval", "1".to_string());
+                params.insert("count", "3".to_string());
+                params.insert("retention", "2".to_string());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            let snapshots = list_snapshots(&out_dir).unwrap();
+            assert_eq!(2, snapshots.len());
+            let snapshot: JsonValue =
+                serde_json::from_str(&fs::read_to_string(&snapshots[0]).unwrap()).unwrap();
+            assert_eq!(snapshot["total"], snapshot["reachable"]);
+            assert!(snapshot["validatorInfo"].as_object().unwrap().len() > 0);
+            fs::remove_dir_all(out_dir).unwrap();
+            tear_down_with_wallet_and_pool(&ctx);
+        }
+
+        #[test]
+        pub fn parse_validator_info_works() {
+            let response = json!({
+                "Node1": json!({"op": "REPLY", "result": {"data": {"alias": "Node1"}}}).to_string(),
+                "Node2": "timeout",
+                "Node3": json!({"op": "REJECT", "reason": "Not enough roles"}).to_string(),
+            })
+            .to_string();
+            let info = parse_validator_info(&response);
+            assert_eq!(info["Node1"]["data"]["alias"], "Node1");
+            assert_eq!(info["Node2"]["error"], "timeout");
+            assert_eq!(info["Node3"]["error"], "Not enough roles");
+        }
+    }
+}
@@ -0,0 +1,167 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::{
+    command_executor::{Command, CommandContext, CommandMetadata, CommandParams},
+    ledger::verify_signatures::signature_checker,
+    params_parser::ParamParser,
+    tools::multisig::MultisigRequest,
+    utils::file::read_file,
+};
+
+use super::sign::print_progress;
+
+pub mod collect_command {
+    use super::*;
+
+    command!(CommandMetadata::build(
+        "collect",
+        "Take over the signatures from a transaction signed by other parties into a multi-signature request."
+    )
+    .add_main_param("name", "The name of the request")
+    .add_required_param("file", "The path to file containing the signed transaction")
+    .add_example("multisig collect rotate-steward file=/home/rotate-steward-signed.txt")
+    .finalize());
+
+    fn execute(ctx: &CommandContext, params: &CommandParams) -> Result<(), ()> {
+        trace!("execute >> ctx {:?} params {:?}", ctx, params);
+
+        let store = ctx.ensure_opened_wallet()?;
+
+        let name = ParamParser::get_str_param(ctx, "name", params)?;
+        let file = ParamParser::get_str_param(ctx, "file", params)?;
+
+        let transaction = read_file(file).map_err(|err| println_err!(ctx, "{}", err))?;
+
+        let mut request = MultisigRequest::get(&store, name).map_err(|err| err.print(ctx, None))?;
+        let is_valid = signature_checker(ctx, &request.request)?;
+        let (added, rejected) = request
+            .merge(&transaction, is_valid)
+            .map_err(|err| err.print(ctx, None))?;
+
+        if !rejected.is_empty() {
+            println_warn!(
+                ctx,
+                "Signatures have been skipped as they do not match the signers' verkeys: {}",
+                rejected.join(", ")
+            );
+        }
+        if added.is_empty() {
+            println!("The file does not contain new signatures.");
+        } else {
+            request.update(&store).map_err(|err| err.print(ctx, None))?;
+            println_succ!(ctx, "Signatures have been added: {}", added.join(", "));
+            let unexpected = added
+                .iter()
+                .filter(|did| !request.is_signer(did))
+                .map(String::as_str)
+                .collect::<Vec<&str>>();
+            if !unexpected.is_empty() {
+                println_warn!(
+                    ctx,
+                    "DIDs are not listed as signers of the request: {}",
+                    unexpected.join(", ")
+                );
+            }
+        }
+        print_progress(ctx, &request)?;
+
+        trace!("execute <<");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::{
+        commands::{setup_with_wallet, tear_down_with_wallet},
+        did::tests::{new_did, DID_MY1, SEED_MY1},
+        ledger::tests::TRANSACTION,
+        multisig::tests::{create_request, REQUEST},
+        tools::ledger::Ledger,
+        utils::{environment::EnvironmentUtils, file::write_file},
+    };
+    use indy_utils::did::DidValue;
+    use indy_vdr::pool::PreparedRequest;
+    use serde_json::Value as JsonValue;
+
+    mod collect {
+        use super::*;
+
+        #[test]
+        pub fn collect_works() {
+            let ctx = setup_with_wallet();
+            new_did(&ctx, SEED_MY1);
+            create_request(&ctx);
+            let store = ctx.ensure_opened_wallet().unwrap();
+            let path = EnvironmentUtils::tmp_file_path("multisig-signed.txt");
+            let mut transaction = PreparedRequest::from_request_json(TRANSACTION).unwrap();
+            Ledger::multi_sign_request(
+                None,
+                &store,
+                &DidValue(DID_MY1.to_string()),
+                &mut transaction,
+            )
+            .unwrap();
+            write_file(&path, &transaction.req_json.to_string()).unwrap();
+            {
+                let cmd = collect_command::new();
+                let mut params = CommandParams::new();
+                params.insert("name", REQUEST.to_string());
+                params.insert("file", path.display().to_string());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            let request = MultisigRequest::get(&store, REQUEST).unwrap();
+            let signed_by = request.signed_by(signature_checker(&ctx, &request.request).unwrap());
+            assert_eq!(vec![DID_MY1], request.approvals(&signed_by));
+            std::fs::remove_file(&path).unwrap();
+            tear_down_with_wallet(&ctx);
+        }
+
+        #[test]
+        pub fn collect_works_for_forged_signature() {
+            let ctx = setup_with_wallet();
+            new_did(&ctx, SEED_MY1);
+            create_request(&ctx);
+            let path = EnvironmentUtils::tmp_file_path("multisig-forged.txt");
+            let mut transaction = serde_json::from_str::<JsonValue>(TRANSACTION).unwrap();
+            transaction["signatures"][DID_MY1] =
+                json!("3PHbWL6UhPSA5Hk4ig7JLfhmtqSnvQFTjqoWyBkLXJW7");
+            write_file(&path, &transaction.to_string()).unwrap();
+            {
+                let cmd = collect_command::new();
+                let mut params = CommandParams::new();
+                params.insert("name", REQUEST.to_string());
+                params.insert("file", path.display().to_string());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            let store = ctx.ensure_opened_wallet().unwrap();
+            let request = MultisigRequest::get(&store, REQUEST).unwrap();
+            assert!(request.request["signatures"].get(DID_MY1).is_none());
+            std::fs::remove_file(&path).unwrap();
+            tear_down_with_wallet(&ctx);
+        }
+
+        #[test]
+        pub fn collect_works_for_other_transaction() {
+            let ctx = setup_with_wallet();
+            create_request(&ctx);
+            let path = EnvironmentUtils::tmp_file_path("multisig-other.txt");
+            let mut transaction = serde_json::from_str::<JsonValue>(TRANSACTION).unwrap();
+            transaction["reqId"] = json!(2);
+            transaction["signatures"][DID_MY1] = json!("signature");
+            write_file(&path, &transaction.to_string()).unwrap();
+            {
+                let cmd = collect_command::new();
+                let mut params = CommandParams::new();
+                params.insert("name", REQUEST.to_string());
+                params.insert("file", path.display().to_string());
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            std::fs::remove_file(&path).unwrap();
+            tear_down_with_wallet(&ctx);
+        }
+    }
+}
@@ -0,0 +1,119 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::{
+    command_executor::{Command, CommandContext, CommandMetadata, CommandParams},
+    params_parser::ParamParser,
+    tools::multisig::MultisigRequest,
+    utils::file::read_file,
+};
+
+pub mod create_command {
+    use super::*;
+
+    const LAST: &str = "last";
+
+    command!(CommandMetadata::build(
+        "create",
+        "Start collecting the signatures of several DIDs for a transaction. The request is tracked in the opened wallet."
+    )
+    .add_main_param("name", "The name of the request")
+    .add_required_param("signers", "Comma-separated DIDs which have to sign the transaction")
+    .add_optional_param(
+        "threshold",
+        "How many of the signers have to sign the transaction before it can be sent (all by default)"
+    )
+    .add_optional_param(
+        "from",
+        "`last` to use the transaction stored in CLI context (default) or the path to file containing a transaction"
+    )
+    .add_example("multisig create rotate-steward signers=V4SGRU86Z58d6TV7PBUe6f,VsKV7grR1BUE29mG2Fm2kX")
+    .add_example("multisig create upgrade signers=V4SGRU86Z58d6TV7PBUe6f,VsKV7grR1BUE29mG2Fm2kX,5Uu7YveFSGcT3dSzjpvPab threshold=2 from=/home/upgrade.txt")
+    .finalize());
+
+    fn execute(ctx: &CommandContext, params: &CommandParams) -> Result<(), ()> {
+        trace!("execute >> ctx {:?} params {:?}", ctx, params);
+
+        let store = ctx.ensure_opened_wallet()?;
+
+        let name = ParamParser::get_str_param(ctx, "name", params)?;
+        let signers = ParamParser::get_str_array_param(ctx, "signers", params)?;
+        let threshold = ParamParser::get_opt_number_param::<usize>("threshold", params)?;
+        let from = ParamParser::get_opt_str_param(ctx, "from", params)?.unwrap_or(LAST);
+
+        let transaction = match from {
+            LAST => ctx.ensure_context_transaction(None)?,
+            file => read_file(file).map_err(|err| println_err!(ctx, "{}", err))?,
+        };
+
+        let request = MultisigRequest::new(name, &transaction, &signers, threshold)
+            .map_err(|err| err.print(ctx, None))?;
+        request.store(&store).map_err(|err| err.print(ctx, None))?;
+
+        println_succ!(
+            ctx,
+            "Multi-signature request \"{}\" has been created: {} of {} signatures are required",
+            name,
+            request.threshold,
+            request.signers.len()
+        );
+        println!(
+            "Use `multisig export` to hand the transaction over to the signers and `multisig collect` to take over their signatures."
+        );
+
+        trace!("execute <<");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::{
+        commands::{setup_with_wallet, tear_down_with_wallet},
+        did::tests::{DID_MY1, DID_TRUSTEE},
+        ledger::tests::TRANSACTION,
+        multisig::tests::{create_request, REQUEST},
+    };
+
+    mod create {
+        use super::*;
+
+        #[test]
+        pub fn create_works() {
+            let ctx = setup_with_wallet();
+            create_request(&ctx);
+            tear_down_with_wallet(&ctx);
+        }
+
+        #[test]
+        pub fn create_works_for_duplicate() {
+            let ctx = setup_with_wallet();
+            create_request(&ctx);
+            {
+                let cmd = create_command::new();
+                let mut params = CommandParams::new();
+                params.insert("name", REQUEST.to_string());
+                params.insert("signers", DID_TRUSTEE.to_string());
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            tear_down_with_wallet(&ctx);
+        }
+
+        #[test]
+        pub fn create_works_for_invalid_threshold() {
+            let ctx = setup_with_wallet();
+            ctx.set_context_transaction(None, Some(TRANSACTION.to_string()));
+            {
+                let cmd = create_command::new();
+                let mut params = CommandParams::new();
+                params.insert("name", REQUEST.to_string());
+                params.insert("signers", format!("{},{}", DID_TRUSTEE, DID_MY1));
+                params.insert("threshold", "3".to_string());
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            tear_down_with_wallet(&ctx);
+        }
+    }
+}
@@ -0,0 +1,70 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::{
+    command_executor::{Command, CommandContext, CommandMetadata, CommandParams},
+    params_parser::ParamParser,
+    tools::multisig::MultisigRequest,
+};
+
+pub mod delete_command {
+    use super::*;
+
+    command!(CommandMetadata::build(
+        "delete",
+        "Stop tracking a multi-signature request. The collected signatures are discarded."
+    )
+    .add_main_param("name", "The name of the request")
+    .add_example("multisig delete rotate-steward")
+    .finalize());
+
+    fn execute(ctx: &CommandContext, params: &CommandParams) -> Result<(), ()> {
+        trace!("execute >> ctx {:?} params {:?}", ctx, params);
+
+        let store = ctx.ensure_opened_wallet()?;
+
+        let name = ParamParser::get_str_param(ctx, "name", params)?;
+
+        MultisigRequest::get(&store, name)
+            .and_then(|request| request.remove(&store))
+            .map_err(|err| err.print(ctx, None))?;
+
+        println_succ!(ctx, "Multi-signature request \"{}\" has been deleted", name);
+
+        trace!("execute <<");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::{
+        commands::{setup_with_wallet, tear_down_with_wallet},
+        multisig::tests::{create_request, REQUEST},
+    };
+
+    mod delete {
+        use super::*;
+
+        #[test]
+        pub fn delete_works() {
+            let ctx = setup_with_wallet();
+            create_request(&ctx);
+            {
+                let cmd = delete_command::new();
+                let mut params = CommandParams::new();
+                params.insert("name", REQUEST.to_string());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            {
+                let cmd = delete_command::new();
+                let mut params = CommandParams::new();
+                params.insert("name", REQUEST.to_string());
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            tear_down_with_wallet(&ctx);
+        }
+    }
+}
@@ -0,0 +1,86 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::{
+    command_executor::{Command, CommandContext, CommandMetadata, CommandParams},
+    ledger::verify_signatures::signature_checker,
+    params_parser::ParamParser,
+    tools::multisig::MultisigRequest,
+    utils::file::write_file,
+};
+
+pub mod export_command {
+    use super::*;
+
+    command!(CommandMetadata::build(
+        "export",
+        "Write the transaction of a multi-signature request with the signatures collected so far into a file to pass it to the signers. \
+        A signer loads it with `ledger load-transaction`, signs with `ledger sign-multi` and returns the file saved with `ledger save-transaction`."
+    )
+    .add_main_param("name", "The name of the request")
+    .add_required_param("file", "The path to file")
+    .add_example("multisig export rotate-steward file=/home/rotate-steward.txt")
+    .finalize());
+
+    fn execute(ctx: &CommandContext, params: &CommandParams) -> Result<(), ()> {
+        trace!("execute >> ctx {:?} params {:?}", ctx, params);
+
+        let store = ctx.ensure_opened_wallet()?;
+
+        let name = ParamParser::get_str_param(ctx, "name", params)?;
+        let file = ParamParser::get_str_param(ctx, "file", params)?;
+
+        let request = MultisigRequest::get(&store, name).map_err(|err| err.print(ctx, None))?;
+        let signed_by = request.signed_by(signature_checker(ctx, &request.request)?);
+
+        write_file(file, &request.request.to_string())
+            .map_err(|err| println_err!(ctx, "Cannot save transaction into the file: {:?}", err))?;
+
+        println_succ!(
+            ctx,
+            "Signing request \"{}\" has been written to \"{}\". Signatures are awaited from: {}",
+            name,
+            file,
+            request.outstanding(&signed_by).join(", ")
+        );
+
+        trace!("execute <<");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::{
+        commands::{setup_with_wallet, tear_down_with_wallet},
+        multisig::tests::{create_request, REQUEST},
+        utils::{environment::EnvironmentUtils, file::read_file},
+    };
+
+    mod export {
+        use super::*;
+
+        #[test]
+        pub fn export_works() {
+            let ctx = setup_with_wallet();
+            create_request(&ctx);
+            let path = EnvironmentUtils::tmp_file_path("multisig-request.txt");
+            {
+                let cmd = export_command::new();
+                let mut params = CommandParams::new();
+                params.insert("name", REQUEST.to_string());
+                params.insert("file", path.display().to_string());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            let transaction = read_file(&path).unwrap();
+            assert!(
+                MultisigRequest::new(REQUEST, &transaction, &["V4SGRU86Z58d6TV7PBUe6f"], None)
+                    .is_ok()
+            );
+            std::fs::remove_file(&path).unwrap();
+            tear_down_with_wallet(&ctx);
+        }
+    }
+}
@@ -0,0 +1,47 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::command_executor::{CommandGroup, CommandGroupMetadata};
+
+pub mod collect;
+pub mod create;
+pub mod delete;
+pub mod export;
+pub mod send;
+pub mod sign;
+pub mod status;
+
+pub use self::{collect::*, create::*, delete::*, export::*, send::*, sign::*, status::*};
+
+pub mod group {
+    use super::*;
+
+    command_group!(CommandGroupMetadata::new(
+        "multisig",
+        "Multi-signature request management commands: collect the approvals of several DIDs for a transaction"
+    ));
+}
+
+#[cfg(test)]
+pub mod tests {
+    use crate::{
+        command_executor::{CommandContext, CommandParams},
+        commands::{
+            did::tests::{DID_MY1, DID_TRUSTEE},
+            multisig::create_command,
+        },
+        ledger::tests::TRANSACTION,
+    };
+
+    pub const REQUEST: &str = "get-nym";
+
+    pub fn create_request(ctx: &CommandContext) {
+        ctx.set_context_transaction(None, Some(TRANSACTION.to_string()));
+        let cmd = create_command::new();
+        let mut params = CommandParams::new();
+        params.insert("name", REQUEST.to_string());
+        params.insert("signers", format!("{},{}", DID_TRUSTEE, DID_MY1));
+        cmd.execute(&ctx, &params).unwrap();
+    }
+}
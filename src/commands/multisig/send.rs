@@ -0,0 +1,97 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::{
+    command_executor::{Command, CommandContext, CommandMetadata, CommandParams},
+    commands::ledger::{
+        handle_transaction_response, submit_request, verify_signatures::signature_checker,
+    },
+    params_parser::ParamParser,
+    tools::{ledger::Response, multisig::MultisigRequest},
+    utils::renderer::print_value,
+};
+
+use indy_vdr::pool::PreparedRequest;
+use serde_json::Value as JsonValue;
+
+pub mod send_command {
+    use super::*;
+
+    command!(CommandMetadata::build(
+        "send",
+        "Send a multi-signature request which has collected the required signatures to the Ledger. \
+        The request stops being tracked once the Ledger accepts it."
+    )
+    .add_main_param("name", "The name of the request")
+    .add_example("multisig send rotate-steward")
+    .finalize());
+
+    fn execute(ctx: &CommandContext, params: &CommandParams) -> Result<(), ()> {
+        trace!("execute >> ctx {:?} params {:?}", ctx, params);
+
+        let store = ctx.ensure_opened_wallet()?;
+        ctx.ensure_connected_pool()?;
+
+        let name = ParamParser::get_str_param(ctx, "name", params)?;
+
+        let request = MultisigRequest::get(&store, name).map_err(|err| err.print(ctx, None))?;
+        let signed_by = request.signed_by(signature_checker(ctx, &request.request)?);
+        if !request.is_complete(&signed_by) {
+            println_err!(
+                ctx,
+                "Request \"{}\" has {} of {} required valid signatures. Awaiting: {}",
+                name,
+                request.approvals(&signed_by).len(),
+                request.threshold,
+                request.outstanding(&signed_by).join(", ")
+            );
+            return Err(());
+        }
+
+        let transaction =
+            PreparedRequest::from_request_json(request.request.to_string()).map_err(|_| {
+                println_err!(ctx, "Invalid formatted transaction stored in the request.")
+            })?;
+
+        let response_json = submit_request(ctx, &transaction)?;
+        let response = serde_json::from_str::<Response<JsonValue>>(&response_json)
+            .map_err(|err| println_err!(ctx, "Invalid data has been received: {:?}", err))?;
+        let result = handle_transaction_response(ctx, response)?;
+
+        request.remove(&store).map_err(|err| err.print(ctx, None))?;
+
+        println_succ!(ctx, "Request \"{}\" has been accepted by the Ledger", name);
+        println_acc!(ctx, "Response:");
+        print_value(ctx, &result);
+
+        trace!("execute <<");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::{
+        commands::{setup_with_wallet_and_pool, tear_down_with_wallet_and_pool},
+        multisig::tests::{create_request, REQUEST},
+    };
+
+    mod send {
+        use super::*;
+
+        #[test]
+        pub fn send_works_for_missing_signatures() {
+            let ctx = setup_with_wallet_and_pool();
+            create_request(&ctx);
+            {
+                let cmd = send_command::new();
+                let mut params = CommandParams::new();
+                params.insert("name", REQUEST.to_string());
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            tear_down_with_wallet_and_pool(&ctx);
+        }
+    }
+}
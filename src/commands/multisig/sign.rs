@@ -0,0 +1,138 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::{
+    command_executor::{Command, CommandContext, CommandMetadata, CommandParams},
+    ledger::{endorser::warn_own_did_changes, verify_signatures::signature_checker},
+    params_parser::ParamParser,
+    tools::{ledger::Ledger, multisig::MultisigRequest},
+    wallet::ensure_key_rotated,
+};
+
+use indy_vdr::pool::PreparedRequest;
+
+pub mod sign_command {
+    use super::*;
+
+    command!(CommandMetadata::build(
+        "sign",
+        "Add the signature of the current DID to a multi-signature request."
+    )
+    .add_main_param("name", "The name of the request")
+    .add_example("multisig sign rotate-steward")
+    .finalize());
+
+    fn execute(ctx: &CommandContext, params: &CommandParams) -> Result<(), ()> {
+        trace!("execute >> ctx {:?} params {:?}", ctx, params);
+
+        let store = ctx.ensure_opened_wallet()?;
+        let did = ctx.ensure_active_did()?;
+        ensure_key_rotated(ctx, &store)?;
+
+        let name = ParamParser::get_str_param(ctx, "name", params)?;
+
+        let mut request = MultisigRequest::get(&store, name).map_err(|err| err.print(ctx, None))?;
+        if !request.is_signer(&did.to_string()) {
+            println_err!(
+                ctx,
+                "DID \"{}\" is not a signer of the request \"{}\". Expected signers: {}",
+                did,
+                name,
+                request.signers.join(", ")
+            );
+            return Err(());
+        }
+
+        let mut transaction = PreparedRequest::from_request_json(request.request.to_string())
+            .map_err(|_| {
+                println_err!(ctx, "Invalid formatted transaction stored in the request.")
+            })?;
+        warn_own_did_changes(ctx, &store, &transaction);
+        Ledger::multi_sign_request(
+            ctx.get_connected_pool().as_deref(),
+            &store,
+            &did,
+            &mut transaction,
+        )
+        .map_err(|err| err.print(ctx, None))?;
+        request.request = transaction.req_json;
+        request.update(&store).map_err(|err| err.print(ctx, None))?;
+
+        println_succ!(ctx, "Request \"{}\" has been signed by \"{}\"", name, did);
+        print_progress(ctx, &request)?;
+
+        trace!("execute <<");
+        Ok(())
+    }
+}
+
+pub fn print_progress(ctx: &CommandContext, request: &MultisigRequest) -> Result<(), ()> {
+    let signed_by = request.signed_by(signature_checker(ctx, &request.request)?);
+    let approvals = request.approvals(&signed_by).len();
+    if request.is_complete(&signed_by) {
+        println_succ!(
+            ctx,
+            "{} of {} required signatures have been collected. The request can be sent with `multisig send {}`",
+            approvals,
+            request.threshold,
+            request.name
+        );
+    } else {
+        println!(
+            "{} of {} required signatures have been collected. Awaiting: {}",
+            approvals,
+            request.threshold,
+            request.outstanding(&signed_by).join(", ")
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::{
+        commands::{setup_with_wallet, tear_down_with_wallet},
+        did::tests::{new_did, use_did, DID_MY3, DID_TRUSTEE, SEED_MY3, SEED_TRUSTEE},
+        multisig::tests::{create_request, REQUEST},
+    };
+
+    mod sign {
+        use super::*;
+
+        #[test]
+        pub fn sign_works() {
+            let ctx = setup_with_wallet();
+            new_did(&ctx, SEED_TRUSTEE);
+            use_did(&ctx, DID_TRUSTEE);
+            create_request(&ctx);
+            {
+                let cmd = sign_command::new();
+                let mut params = CommandParams::new();
+                params.insert("name", REQUEST.to_string());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            let store = ctx.ensure_opened_wallet().unwrap();
+            let request = MultisigRequest::get(&store, REQUEST).unwrap();
+            let signed_by = request.signed_by(signature_checker(&ctx, &request.request).unwrap());
+            assert_eq!(vec![DID_TRUSTEE], request.approvals(&signed_by));
+            tear_down_with_wallet(&ctx);
+        }
+
+        #[test]
+        pub fn sign_works_for_not_signer() {
+            let ctx = setup_with_wallet();
+            new_did(&ctx, SEED_MY3);
+            use_did(&ctx, DID_MY3);
+            create_request(&ctx);
+            {
+                let cmd = sign_command::new();
+                let mut params = CommandParams::new();
+                params.insert("name", REQUEST.to_string());
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            tear_down_with_wallet(&ctx);
+        }
+    }
+}
@@ -0,0 +1,154 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::{
+    command_executor::{Command, CommandContext, CommandMetadata, CommandParams},
+    ledger::verify_signatures::signature_checker,
+    params_parser::ParamParser,
+    tools::{ledger::LedgerHelpers, multisig::MultisigRequest},
+    utils::{table::print_list_table, time::timestamp_to_datetime},
+};
+
+use serde_json::Value as JsonValue;
+
+pub mod status_command {
+    use super::*;
+
+    command!(CommandMetadata::build(
+        "status",
+        "Show the multi-signature requests tracked in the opened wallet and the approvals they are waiting for."
+    )
+    .add_optional_param("name", "Show the signers of a single request")
+    .add_example("multisig status")
+    .add_example("multisig status name=rotate-steward")
+    .finalize());
+
+    fn execute(ctx: &CommandContext, params: &CommandParams) -> Result<(), ()> {
+        trace!("execute >> ctx {:?} params {:?}", ctx, params);
+
+        let store = ctx.ensure_opened_wallet()?;
+
+        let name = ParamParser::get_opt_str_param(ctx, "name", params)?;
+
+        match name {
+            Some(name) => {
+                let request =
+                    MultisigRequest::get(&store, name).map_err(|err| err.print(ctx, None))?;
+                print_signers(ctx, &request)?;
+            }
+            None => {
+                let mut requests =
+                    MultisigRequest::list(&store).map_err(|err| err.print(ctx, None))?;
+                requests.sort_by(|request, other| request.created.cmp(&other.created));
+                print_requests(ctx, &requests)?;
+            }
+        }
+
+        trace!("execute <<");
+        Ok(())
+    }
+
+    fn print_requests(ctx: &CommandContext, requests: &[MultisigRequest]) -> Result<(), ()> {
+        let rows = requests
+            .iter()
+            .map(|request| {
+                let signed_by = request.signed_by(signature_checker(ctx, &request.request)?);
+                Ok(json!({
+                    "name": request.name,
+                    "type": LedgerHelpers::get_txn_title(&request.request["operation"]["type"]),
+                    "approvals": format!("{}/{}", request.approvals(&signed_by).len(), request.threshold),
+                    "outstanding": if request.is_complete(&signed_by) {
+                        "-".to_string()
+                    } else {
+                        request.outstanding(&signed_by).join(", ")
+                    },
+                    "created": timestamp_to_datetime(ctx, request.created),
+                }))
+            })
+            .collect::<Result<Vec<JsonValue>, ()>>()?;
+        print_list_table(
+            ctx,
+            &rows,
+            &[
+                ("name", "Name"),
+                ("type", "Type"),
+                ("approvals", "Approvals"),
+                ("outstanding", "Awaiting"),
+                ("created", "Created"),
+            ],
+            "There are no multi-signature requests",
+        );
+        Ok(())
+    }
+
+    fn print_signers(ctx: &CommandContext, request: &MultisigRequest) -> Result<(), ()> {
+        let signed_by = request.signed_by(signature_checker(ctx, &request.request)?);
+        let approvals = request.approvals(&signed_by);
+        let rows = request
+            .signers
+            .iter()
+            .map(|signer| {
+                json!({
+                    "signer": signer,
+                    "status": if approvals.contains(&signer.as_str()) { "Signed" } else { "Awaiting" },
+                })
+            })
+            .collect::<Vec<JsonValue>>();
+        print_list_table(
+            ctx,
+            &rows,
+            &[("signer", "Signer"), ("status", "Status")],
+            "",
+        );
+        println!(
+            "{} of {} required signatures have been collected.",
+            approvals.len(),
+            request.threshold
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::{
+        commands::{setup_with_wallet, tear_down_with_wallet},
+        multisig::tests::{create_request, REQUEST},
+    };
+
+    mod status {
+        use super::*;
+
+        #[test]
+        pub fn status_works() {
+            let ctx = setup_with_wallet();
+            create_request(&ctx);
+            {
+                let cmd = status_command::new();
+                let params = CommandParams::new();
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            {
+                let cmd = status_command::new();
+                let mut params = CommandParams::new();
+                params.insert("name", REQUEST.to_string());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            tear_down_with_wallet(&ctx);
+        }
+
+        #[test]
+        pub fn status_works_for_unknown_request() {
+            let ctx = setup_with_wallet();
+            {
+                let cmd = status_command::new();
+                let mut params = CommandParams::new();
+                params.insert("name", REQUEST.to_string());
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            tear_down_with_wallet(&ctx);
+        }
+    }
+}
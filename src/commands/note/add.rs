@@ -0,0 +1,111 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::{
+    command_executor::{Command, CommandContext, CommandMetadata, CommandParams},
+    commands::explore::{ledger_name, parse_ledger_type},
+    params_parser::ParamParser,
+    tools::note::TransactionNote,
+};
+
+pub mod add_command {
+    use super::*;
+
+    command!(CommandMetadata::build(
+        "add",
+        "Attach a note to the ledger transaction. The note is stored in the opened wallet \
+                 and shown together with the transaction by `ledger get-txn` and `explore txn`."
+    )
+    .add_required_param("seq_no", "Sequence number of the transaction")
+    .add_required_param("text", "Text of the note")
+    .add_optional_param(
+        "ledger",
+        "Ledger of the transaction: domain (default), pool or config"
+    )
+    .add_example(r#"note add seq_no=10 text="Endorser for the issuer team, see ticket 42""#)
+    .add_example(r#"note add seq_no=5 ledger=pool text="Node added during migration""#)
+    .finalize());
+
+    fn execute(ctx: &CommandContext, params: &CommandParams) -> Result<(), ()> {
+        trace!("execute >> ctx {:?} params {:?}", ctx, params);
+
+        let store = ctx.ensure_opened_wallet()?;
+
+        let seq_no = ParamParser::get_number_param::<i32>("seq_no", params)?;
+        let text = ParamParser::get_str_param(ctx, "text", params)?;
+        let ledger = ParamParser::get_opt_str_param(ctx, "ledger", params)?;
+
+        let ledger_type = parse_ledger_type(ctx, ledger)?;
+
+        if seq_no < 1 {
+            println_err!(ctx, "Invalid sequence number: {}", seq_no);
+            return Err(());
+        }
+
+        let author = ctx.get_active_did()?.map(|did| did.to_string());
+        let note = TransactionNote::new(ledger_type, seq_no, text, author.as_deref());
+        note.store(&store).map_err(|err| err.print(ctx, None))?;
+
+        println_succ!(
+            ctx,
+            "Note has been added to transaction {} of {} ledger",
+            seq_no,
+            ledger_name(ledger_type)
+        );
+
+        trace!("execute <<");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::commands::{setup, setup_with_wallet, tear_down, tear_down_with_wallet};
+
+    mod add {
+        use super::*;
+        use crate::note::tests::{add_note, TEXT};
+
+        #[test]
+        pub fn add_works() {
+            let ctx = setup_with_wallet();
+            add_note(&ctx);
+            {
+                let wallet = ctx.ensure_opened_wallet().unwrap();
+                let notes = TransactionNote::list_for_txn(&wallet, 1, 1).unwrap();
+                assert_eq!(1, notes.len());
+                assert_eq!(TEXT, notes[0].text);
+            }
+            tear_down_with_wallet(&ctx);
+        }
+
+        #[test]
+        pub fn add_works_for_unknown_ledger() {
+            let ctx = setup_with_wallet();
+            {
+                let cmd = add_command::new();
+                let mut params = CommandParams::new();
+                params.insert("seq_no", "1".to_string());
+                params.insert("text", TEXT.to_string());
+                params.insert("ledger", "audit".to_string());
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            tear_down_with_wallet(&ctx);
+        }
+
+        #[test]
+        pub fn add_works_for_no_opened_wallet() {
+            let ctx = setup();
+            {
+                let cmd = add_command::new();
+                let mut params = CommandParams::new();
+                params.insert("seq_no", "1".to_string());
+                params.insert("text", TEXT.to_string());
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            tear_down();
+        }
+    }
+}
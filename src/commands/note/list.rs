@@ -0,0 +1,112 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::{
+    command_executor::{Command, CommandContext, CommandMetadata, CommandParams},
+    commands::explore::{ledger_name, parse_ledger_type},
+    params_parser::ParamParser,
+    tools::note::TransactionNote,
+    utils::{table::print_list_table, time::timestamp_to_datetime},
+};
+
+use serde_json::Value as JsonValue;
+
+pub mod list_command {
+    use super::*;
+
+    command!(CommandMetadata::build(
+        "list",
+        "List notes of ledger transactions stored in the opened wallet."
+    )
+    .add_optional_param(
+        "seq_no",
+        "Show only the notes of the transaction with this sequence number"
+    )
+    .add_optional_param(
+        "ledger",
+        "Show only the notes of transactions of the ledger: domain, pool or config"
+    )
+    .add_example("note list")
+    .add_example("note list seq_no=10")
+    .add_example("note list ledger=pool")
+    .finalize());
+
+    fn execute(ctx: &CommandContext, params: &CommandParams) -> Result<(), ()> {
+        trace!("execute >> ctx {:?} params {:?}", ctx, params);
+
+        let store = ctx.ensure_opened_wallet()?;
+
+        let seq_no = ParamParser::get_opt_number_param::<i32>("seq_no", params)?;
+        let ledger = ParamParser::get_opt_str_param(ctx, "ledger", params)?;
+
+        // A sequence number alone refers to the domain ledger
+        let ledger_type = match (ledger, seq_no) {
+            (None, None) => None,
+            (ledger, _) => Some(parse_ledger_type(ctx, ledger)?),
+        };
+
+        let notes = TransactionNote::list(&store).map_err(|err| err.print(ctx, None))?;
+
+        let rows = notes
+            .iter()
+            .filter(|note| ledger_type.map_or(true, |ledger_type| note.ledger_type == ledger_type))
+            .filter(|note| seq_no.map_or(true, |seq_no| note.seq_no == seq_no))
+            .map(|note| {
+                json!({
+                    "ledger": ledger_name(note.ledger_type),
+                    "seq_no": note.seq_no,
+                    "text": note.text,
+                    "author": note.author,
+                    "created": timestamp_to_datetime(ctx, note.created),
+                })
+            })
+            .collect::<Vec<JsonValue>>();
+
+        print_list_table(
+            ctx,
+            &rows,
+            &[
+                ("ledger", "Ledger"),
+                ("seq_no", "Seq No"),
+                ("text", "Note"),
+                ("author", "Author"),
+                ("created", "Created"),
+            ],
+            "There are no transaction notes",
+        );
+
+        trace!("execute <<");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::commands::{setup_with_wallet, tear_down_with_wallet};
+
+    mod list {
+        use super::*;
+        use crate::note::tests::{add_note, SEQ_NO};
+
+        #[test]
+        pub fn list_works() {
+            let ctx = setup_with_wallet();
+            add_note(&ctx);
+            {
+                let cmd = list_command::new();
+                let params = CommandParams::new();
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            {
+                let cmd = list_command::new();
+                let mut params = CommandParams::new();
+                params.insert("seq_no", SEQ_NO.to_string());
+                params.insert("ledger", "pool".to_string());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            tear_down_with_wallet(&ctx);
+        }
+    }
+}
@@ -0,0 +1,76 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::{
+    command_executor::{CommandContext, CommandGroup, CommandGroupMetadata},
+    tools::note::TransactionNote,
+    utils::time::timestamp_to_datetime,
+};
+
+pub mod add;
+pub mod list;
+
+pub use self::{add::*, list::*};
+
+pub mod group {
+    use super::*;
+
+    command_group!(CommandGroupMetadata::new(
+        "note",
+        "Local annotations of ledger transactions stored in the wallet"
+    ));
+}
+
+/// Print the notes attached to the transaction if a wallet is opened.
+pub fn print_txn_notes(ctx: &CommandContext, ledger_type: i32, seq_no: i32) {
+    let store = match ctx.get_opened_wallet() {
+        Some(store) => store,
+        None => return,
+    };
+    let notes = match TransactionNote::list_for_txn(&store, ledger_type, seq_no) {
+        Ok(notes) => notes,
+        Err(err) => {
+            println_warn!(
+                ctx,
+                "Unable to read transaction notes: {}",
+                err.message(None)
+            );
+            return;
+        }
+    };
+    if notes.is_empty() {
+        return;
+    }
+
+    println!("Notes:");
+    for note in notes {
+        println!(
+            "  [{}{}] {}",
+            timestamp_to_datetime(ctx, note.created),
+            note.author
+                .map(|author| format!(" {}", author))
+                .unwrap_or_default(),
+            note.text
+        );
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use crate::{
+        command_executor::{CommandContext, CommandParams},
+        commands::note::add_command,
+    };
+
+    pub const SEQ_NO: &str = "1";
+    pub const TEXT: &str = "Genesis trustee";
+
+    pub fn add_note(ctx: &CommandContext) {
+        let cmd = add_command::new();
+        let mut params = CommandParams::new();
+        params.insert("seq_no", SEQ_NO.to_string());
+        params.insert("text", TEXT.to_string());
+        cmd.execute(&ctx, &params).unwrap();
+    }
+}
@@ -0,0 +1,227 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::{
+    command_executor::{Command, CommandContext, CommandMetadata, CommandParams},
+    params_parser::ParamParser,
+    tools::pool::Pool,
+    utils::{file::read_file, json_schema, table::print_list_table},
+};
+
+use serde_json::Value as JsonValue;
+
+pub mod set_attrib_schema_command {
+    use super::*;
+
+    command!(CommandMetadata::build(
+        "set-attrib-schema",
+        "Register JSON Schema which raw values of the ATTRIB attribute must match on the pool. \
+                 `ledger attrib` refuses values which do not match it."
+    )
+    .add_main_param("name", "The name of pool ledger config")
+    .add_required_param("key", "Attribute name, e.g. `endpoint`")
+    .add_optional_param("schema", "JSON Schema of the attribute value")
+    .add_optional_param(
+        "file",
+        "The path to file containing JSON Schema (an alternative to the `schema` parameter)"
+    )
+    .add_example(
+        r#"pool set-attrib-schema sovrin key=endpoint schema={"type":"object","required":["endpoint"],"properties":{"endpoint":{"type":"string","format":"uri"}}}"#
+    )
+    .add_example("pool set-attrib-schema sovrin key=service file=/home/service_schema.json")
+    .add_example("pool set-attrib-schema sovrin key=endpoint")
+    .finalize());
+
+    fn execute(ctx: &CommandContext, params: &CommandParams) -> Result<(), ()> {
+        trace!("execute >> ctx {:?} params {:?}", ctx, params);
+
+        let name = ParamParser::get_str_param(ctx, "name", params)?;
+        let key = ParamParser::get_str_param(ctx, "key", params)?;
+        let schema = ParamParser::get_opt_object_param(ctx, "schema", params)?;
+        let file = ParamParser::get_opt_str_param(ctx, "file", params)?;
+
+        let schema = match (schema, file) {
+            (Some(_), Some(_)) => {
+                println_err!(
+                    ctx,
+                    "Only one of \"schema\" or \"file\" parameters can be specified"
+                );
+                return Err(());
+            }
+            (Some(schema), None) => Some(schema),
+            (None, Some(file)) => {
+                let content = read_file(file).map_err(|err| println_err!(ctx, "{}", err))?;
+                Some(serde_json::from_str::<JsonValue>(&content).map_err(|err| {
+                    println_err!(ctx, "File \"{}\" does not contain JSON: {}", file, err)
+                })?)
+            }
+            (None, None) => None,
+        };
+
+        if let Some(schema) = schema.as_ref() {
+            json_schema::check_schema(schema)
+                .map_err(|err| println_err!(ctx, "Invalid JSON Schema: {}", err))?;
+        }
+
+        let removed = schema.is_none();
+        Pool::set_attrib_schema(name, key, schema).map_err(|err| err.print(ctx, Some(name)))?;
+
+        if removed {
+            println_succ!(
+                ctx,
+                "Schema of \"{}\" attribute has been removed from pool \"{}\"",
+                key,
+                name
+            );
+        } else {
+            println_succ!(
+                ctx,
+                "Schema of \"{}\" attribute has been registered for pool \"{}\"",
+                key,
+                name
+            );
+        }
+
+        trace!("execute <<");
+        Ok(())
+    }
+}
+
+pub mod attrib_schemas_command {
+    use super::*;
+
+    command!(CommandMetadata::build(
+        "attrib-schemas",
+        "Show JSON Schemas of ATTRIB values registered for the pool."
+    )
+    .add_main_param("name", "The name of pool ledger config")
+    .add_example("pool attrib-schemas sovrin")
+    .finalize());
+
+    fn execute(ctx: &CommandContext, params: &CommandParams) -> Result<(), ()> {
+        trace!("execute >> ctx {:?} params {:?}", ctx, params);
+
+        let name = ParamParser::get_str_param(ctx, "name", params)?;
+
+        let schemas = Pool::attrib_schemas(name).map_err(|err| err.print(ctx, Some(name)))?;
+
+        let mut rows = schemas
+            .iter()
+            .map(|(key, schema)| {
+                json!({
+                    "key": key,
+                    "schema": serde_json::to_string_pretty(schema).unwrap_or_default(),
+                })
+            })
+            .collect::<Vec<JsonValue>>();
+        rows.sort_by(|a, b| a["key"].as_str().cmp(&b["key"].as_str()));
+
+        print_list_table(
+            ctx,
+            &rows,
+            &[("key", "Attribute"), ("schema", "Schema")],
+            &format!(
+                "There are no attribute schemas registered for pool \"{}\"",
+                name
+            ),
+        );
+
+        trace!("execute <<");
+        Ok(())
+    }
+}
+
+/// Validate the raw value of ATTRIB against the schemas registered for the pool.
+/// Every attribute of the value which has a schema is checked; the others are accepted as is.
+pub fn validate_raw_attrib(
+    ctx: &CommandContext,
+    pool_name: &str,
+    raw: &JsonValue,
+) -> Result<(), ()> {
+    let schemas = Pool::attrib_schemas(pool_name).map_err(|err| err.print(ctx, Some(pool_name)))?;
+
+    let mut valid = true;
+    for (key, value) in raw.as_object().into_iter().flatten() {
+        if let Some(schema) = schemas.get(key) {
+            for error in json_schema::validate(schema, value) {
+                println_err!(ctx, "Attribute \"{}\": {}", key, error);
+                valid = false;
+            }
+        }
+    }
+    if !valid {
+        println_err!(
+            ctx,
+            "The value does not match the attribute schema registered for pool \"{}\".",
+            pool_name
+        );
+        return Err(());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::commands::{setup, tear_down};
+
+    pub const ENDPOINT_SCHEMA: &str = r#"{"type":"object","required":["ha"],"properties":{"ha":{"type":"string","format":"hostname-port"}}}"#;
+
+    pub fn set_endpoint_schema(ctx: &CommandContext) {
+        let cmd = set_attrib_schema_command::new();
+        let mut params = CommandParams::new();
+        params.insert("name", crate::pool::tests::POOL.to_string());
+        params.insert("key", "endpoint".to_string());
+        params.insert("schema", ENDPOINT_SCHEMA.to_string());
+        cmd.execute(ctx, &params).unwrap();
+    }
+
+    mod set_attrib_schema {
+        use super::*;
+        use crate::pool::tests::{create_pool, delete_pool, POOL};
+
+        #[test]
+        pub fn set_attrib_schema_works() {
+            let ctx = setup();
+            create_pool(&ctx);
+            set_endpoint_schema(&ctx);
+            assert!(Pool::attrib_schemas(POOL).unwrap().contains_key("endpoint"));
+
+            validate_raw_attrib(&ctx, POOL, &json!({"endpoint": {"ha": "127.0.0.1:5555"}}))
+                .unwrap();
+            validate_raw_attrib(&ctx, POOL, &json!({"endpoint": {"ha": "127.0.0.1"}})).unwrap_err();
+            validate_raw_attrib(&ctx, POOL, &json!({"url": "127.0.0.1"})).unwrap();
+            {
+                let cmd = set_attrib_schema_command::new();
+                let mut params = CommandParams::new();
+                params.insert("name", POOL.to_string());
+                params.insert("key", "endpoint".to_string());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            assert!(Pool::attrib_schemas(POOL).unwrap().is_empty());
+            delete_pool(&ctx);
+            tear_down();
+        }
+
+        #[test]
+        pub fn set_attrib_schema_works_for_unsupported_schema() {
+            let ctx = setup();
+            create_pool(&ctx);
+            {
+                let cmd = set_attrib_schema_command::new();
+                let mut params = CommandParams::new();
+                params.insert("name", POOL.to_string());
+                params.insert("key", "endpoint".to_string());
+                params.insert(
+                    "schema",
+                    r#"{"type":"string","pattern":"^http"}"#.to_string(),
+                );
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            assert!(Pool::attrib_schemas(POOL).unwrap().is_empty());
+            delete_pool(&ctx);
+            tear_down();
+        }
+    }
+}
@@ -7,13 +7,18 @@ use crate::{
         wait_for_user_reply, Command, CommandContext, CommandMetadata, CommandParams,
         DynamicCompletionType,
     },
-    ledger::get_active_transaction_author_agreement,
+    ledger::{check_did_namespace, get_active_transaction_author_agreement},
     params_parser::ParamParser,
-    tools::pool::Pool,
+    tools::pool::{health::consensus_quorum, Pool},
+    utils::{table::print_list_table, term::print_prompt},
 };
 
 use chrono::prelude::*;
+use indy_utils::hash::SHA256;
 use indy_vdr::{config::PoolConfig, pool::ProtocolVersion};
+use std::time::Duration;
+
+const NODE_CHECK_TIMEOUT: Duration = Duration::from_secs(5);
 
 pub mod connect_command {
     use super::*;
@@ -41,17 +46,27 @@ pub mod connect_command {
         "number-read-nodes",
         "The number of nodes to send read requests (2 by default)"
     )
+    .add_optional_param(
+        "partial",
+        "Connect even if less than a consensus quorum of nodes is reachable. Reachable nodes get a priority (False by default)"
+    )
+    .add_optional_param(
+        "expected-root-hash",
+        "Base58 merkle root hash the pool ledger must have after catch up. The connection is aborted if it differs"
+    )
     .add_example("pool connect pool1")
     .add_example("pool connect pool1 protocol-version=2")
     .add_example("pool connect pool1 protocol-version=2 timeout=100")
     .add_example("pool connect pool1 protocol-version=2 extended-timeout=100")
     .add_example("pool connect pool1 protocol-version=2 pre-ordered-nodes=Node2,Node1")
+    .add_example("pool connect pool1 partial=true")
+    .add_example("pool connect pool1 expected-root-hash=DvTefBCg1ssAf3kbtd4zqDjGCUZXGaQ6gqWEXLoUf4bV")
     .finalize());
 
     fn execute(ctx: &CommandContext, params: &CommandParams) -> Result<(), ()> {
         trace!("execute >> ctx {:?} params {:?}", ctx, params);
 
-        let name = ParamParser::get_str_param("name", params)?;
+        let name = ParamParser::get_str_param(ctx, "name", params)?;
         let protocol_version =
             ParamParser::get_opt_number_param::<usize>("protocol-version", params)?
                 .unwrap_or(ctx.get_pool_protocol_version());
@@ -61,8 +76,14 @@ pub mod connect_command {
         let pre_ordered_nodes = ParamParser::get_opt_str_array_param("pre-ordered-nodes", params)?;
         let number_read_nodes =
             ParamParser::get_opt_number_param::<usize>("number-read-nodes", params)?;
+        let partial = ParamParser::get_opt_bool_param(ctx, "partial", params)?.unwrap_or(false);
+        let expected_root_hash = ParamParser::get_opt_str_param(ctx, "expected-root-hash", params)?;
         let protocol_version = ProtocolVersion::from_id(protocol_version as i64).map_err(|_| {
-            println_err!("Unexpected Pool protocol version \"{}\".", protocol_version)
+            println_err!(
+                ctx,
+                "Unexpected Pool protocol version \"{}\".",
+                protocol_version
+            )
         })?;
 
         let config = PoolConfig {
@@ -74,23 +95,149 @@ pub mod connect_command {
             ..PoolConfig::default()
         };
 
+        let check_timeout = timeout
+            .map(|timeout| Duration::from_secs(timeout as u64))
+            .unwrap_or(NODE_CHECK_TIMEOUT);
+        let reachable_nodes = check_pool_health(ctx, name, check_timeout, partial)?;
+        let pre_ordered_nodes = pre_ordered_nodes.or_else(|| {
+            reachable_nodes
+                .as_ref()
+                .map(|nodes| nodes.iter().map(String::as_str).collect())
+        });
+
         if let Some(pool) = ctx.get_connected_pool() {
             close_pool(ctx, &pool)?;
         }
 
         let pool = Pool::open(name, config, pre_ordered_nodes)
-            .map_err(|err| println_err!("{}", err.message(Some(&name))))?;
+            .map_err(|err| err.print(ctx, Some(&name)))?;
+
+        if let Some(expected_root_hash) = expected_root_hash {
+            check_root_hash(ctx, &pool, expected_root_hash)?;
+        }
 
         ctx.set_connected_pool(pool);
-        println_succ!("Pool \"{}\" has been connected", name);
+        println_succ!(ctx, "Pool \"{}\" has been connected", name);
+
+        check_did_namespace(ctx);
 
         let pool = ctx.ensure_connected_pool()?;
+        discover_capabilities(ctx, &pool);
         set_transaction_author_agreement(ctx, &pool, true)?;
 
         trace!("execute <<");
         Ok(())
     }
 
+    // Commands fall back to reading the ledger when the capabilities are unknown, so a failure is not fatal
+    fn discover_capabilities(ctx: &CommandContext, pool: &Pool) {
+        if pool.capabilities().is_some() {
+            return;
+        }
+        if let Err(err) = pool.refresh_capabilities() {
+            println_warn!(
+                ctx,
+                "Unable to read capabilities of pool \"{}\": {}",
+                pool.name,
+                err.message(None)
+            );
+        }
+    }
+
+    // A different root means the pool transactions (starting from the genesis file) are not the expected ones
+    fn check_root_hash(
+        ctx: &CommandContext,
+        pool: &Pool,
+        expected_root_hash: &str,
+    ) -> Result<(), ()> {
+        let (root_hash, size) = pool.merkle_tree_info();
+        if root_hash != expected_root_hash {
+            println_err!(
+                ctx,
+                "Pool \"{}\" ledger root hash \"{}\" (size {}) does not match the expected \"{}\". \
+                The connection has been aborted: check that the genesis transactions come from a trusted source.",
+                pool.name,
+                root_hash,
+                size,
+                expected_root_hash
+            );
+            return Err(());
+        }
+        println_succ!(
+            ctx,
+            "Pool ledger root hash \"{}\" (size {}) matches the expected one",
+            root_hash,
+            size
+        );
+        Ok(())
+    }
+
+    /// Returns reachable nodes if connection proceeds with a part of the pool only.
+    fn check_pool_health(
+        ctx: &CommandContext,
+        name: &str,
+        timeout: Duration,
+        partial: bool,
+    ) -> Result<Option<Vec<String>>, ()> {
+        let nodes = Pool::check_nodes(name, timeout).map_err(|err| err.print(ctx, Some(&name)))?;
+
+        let reachable_nodes = nodes
+            .iter()
+            .filter(|node| node.is_reachable())
+            .map(|node| node.alias.to_string())
+            .collect::<Vec<String>>();
+        let quorum = consensus_quorum(nodes.len());
+
+        if reachable_nodes.len() >= quorum {
+            return Ok(None);
+        }
+
+        let rows = nodes
+            .iter()
+            .map(|node| {
+                json!({
+                    "alias": node.alias,
+                    "address": node.address,
+                    "status": node.error.as_deref().unwrap_or("OK"),
+                })
+            })
+            .collect::<Vec<serde_json::Value>>();
+        print_list_table(
+            ctx,
+            &rows,
+            &[
+                ("alias", "Node"),
+                ("address", "Address"),
+                ("status", "Status"),
+            ],
+            "There are no validator nodes in the pool transactions",
+        );
+
+        if partial && !reachable_nodes.is_empty() {
+            println_warn!(
+                ctx,
+                "Only {} of {} nodes are reachable ({} required for consensus). Connecting to the reachable nodes.",
+                reachable_nodes.len(),
+                nodes.len(),
+                quorum
+            );
+            return Ok(Some(reachable_nodes));
+        }
+
+        println_err!(
+            ctx,
+            "Unable to connect to pool \"{}\": only {} of {} nodes are reachable ({} required for consensus).",
+            name,
+            reachable_nodes.len(),
+            nodes.len(),
+            quorum
+        );
+        if !reachable_nodes.is_empty() {
+            println!("Use `partial=true` to connect to the reachable nodes only.");
+        }
+        Err(())
+    }
+
     pub fn cleanup(ctx: &CommandContext) {
         trace!("cleanup >> ctx {:?}", ctx);
 
@@ -103,18 +250,21 @@ pub mod connect_command {
 }
 
 pub fn accept_transaction_author_agreement(ctx: &CommandContext, text: &str, version: &str) {
-    println!("Would you like to accept it? (y/n)");
+    print_prompt("Would you like to accept it? (y/n)");
 
     let accept_agreement = wait_for_user_reply(ctx);
 
     if !accept_agreement {
-        println_warn!("The Transaction Author Agreement has NOT been Accepted.");
+        println_warn!(
+            ctx,
+            "The Transaction Author Agreement has NOT been Accepted."
+        );
         println!("Use `pool show-taa` command to accept the Agreement.");
         println!();
         return;
     }
 
-    println_succ!("Transaction Author Agreement has been accepted.");
+    println_succ!(ctx, "Transaction Author Agreement has been accepted.");
 
     let time_of_acceptance = Utc::now().timestamp() as u64;
 
@@ -125,23 +275,79 @@ pub fn accept_transaction_author_agreement(ctx: &CommandContext, text: &str, ver
     )));
 }
 
+/// Accept the Agreement without prompting as configured by `taaAutoAccept`.
+/// The accepted digest is logged to the `audit` log target.
+pub fn auto_accept_transaction_author_agreement(
+    ctx: &CommandContext,
+    pool_name: &str,
+    text: &str,
+    version: &str,
+    digest: Option<&str>,
+) {
+    let digest = digest
+        .map(String::from)
+        .unwrap_or_else(|| transaction_author_agreement_digest(text, version));
+    let time_of_acceptance = Utc::now().timestamp() as u64;
+
+    ctx.set_transaction_author_info(Some((
+        text.to_string(),
+        version.to_string(),
+        time_of_acceptance,
+    )));
+
+    info!(
+        target: "audit",
+        "Transaction Author Agreement accepted automatically: pool \"{}\", version \"{}\", digest {}, mechanism \"{}\", time {}",
+        pool_name,
+        version,
+        digest,
+        ctx.get_taa_acceptance_mechanism(),
+        time_of_acceptance
+    );
+    println_succ!(
+        ctx,
+        "Transaction Author Agreement (version \"{}\", digest {}) has been accepted automatically.",
+        version,
+        digest
+    );
+}
+
+// Digest of the Agreement as defined by the ledger: SHA-256 of the version concatenated with the text
+pub fn transaction_author_agreement_digest(text: &str, version: &str) -> String {
+    hex::encode(SHA256::digest(format!("{}{}", version, text).as_bytes()))
+}
+
 pub fn set_transaction_author_agreement(
     ctx: &CommandContext,
     pool: &Pool,
     ask_for_showing: bool,
 ) -> Result<Option<()>, ()> {
-    if let Some((text, version, digest)) = get_active_transaction_author_agreement(pool)? {
+    if let Some((text, version, digest)) = get_active_transaction_author_agreement(ctx, pool)? {
+        if ask_for_showing && ctx.is_taa_auto_accept() {
+            auto_accept_transaction_author_agreement(
+                ctx,
+                &pool.name,
+                &text,
+                &version,
+                digest.as_deref(),
+            );
+            return Ok(Some(()));
+        }
+
         if ask_for_showing {
             println!();
             println!("There is a Transaction Author Agreement set on the connected Pool.");
             println!("You should read and accept it to be able to send transactions to the Pool.");
             println!("You can postpone accepting the Agreement. Accept it later by calling `pool show-taa` command");
-            println!("Would you like to read it? (y/n)");
+            print_prompt("Would you like to read it? (y/n)");
 
             let read_agreement = wait_for_user_reply(ctx);
 
             if !read_agreement {
-                println_warn!("The Transaction Author Agreement has NOT been Accepted.");
+                println_warn!(
+                    ctx,
+                    "The Transaction Author Agreement has NOT been Accepted."
+                );
                 println!("Use `pool show-taa` command to accept the Agreement.");
                 println!();
                 return Ok(Some(()));
@@ -203,6 +409,48 @@ pub mod tests {
             tear_down();
         }
 
+        #[test]
+        pub fn connect_works_for_expected_root_hash() {
+            let ctx = setup();
+            create_pool(&ctx);
+            let root_hash = {
+                let cmd = connect_command::new();
+                let mut params = CommandParams::new();
+                params.insert("name", POOL.to_string());
+                cmd.execute(&ctx, &params).unwrap();
+                ctx.ensure_connected_pool().unwrap().merkle_tree_info().0
+            };
+            {
+                let cmd = connect_command::new();
+                let mut params = CommandParams::new();
+                params.insert("name", POOL.to_string());
+                params.insert("expected-root-hash", root_hash);
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            ctx.ensure_connected_pool().unwrap();
+            disconnect_and_delete_pool(&ctx);
+            tear_down();
+        }
+
+        #[test]
+        pub fn connect_works_for_unexpected_root_hash() {
+            let ctx = setup();
+            create_pool(&ctx);
+            {
+                let cmd = connect_command::new();
+                let mut params = CommandParams::new();
+                params.insert("name", POOL.to_string());
+                params.insert(
+                    "expected-root-hash",
+                    "DvTefBCg1ssAf3kbtd4zqDjGCUZXGaQ6gqWEXLoUf4bV".to_string(),
+                );
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            assert!(ctx.get_connected_pool().is_none());
+            delete_pool(&ctx);
+            tear_down();
+        }
+
         #[test]
         pub fn connect_works_for_not_created() {
             let ctx = setup();
@@ -291,4 +539,31 @@ pub mod tests {
             tear_down();
         }
     }
+
+    mod transaction_author_agreement {
+        use super::*;
+
+        #[test]
+        pub fn auto_accept_transaction_author_agreement_works() {
+            let ctx = setup();
+            ctx.set_taa_acceptance_mechanism("service_agreement");
+            ctx.set_taa_auto_accept(true);
+
+            auto_accept_transaction_author_agreement(&ctx, "pool", "indy agreement", "1.0", None);
+
+            let (text, version, acc_mech_type, _) = ctx.get_transaction_author_info().unwrap();
+            assert_eq!(text, "indy agreement");
+            assert_eq!(version, "1.0");
+            assert_eq!(acc_mech_type, "service_agreement");
+            tear_down();
+        }
+
+        #[test]
+        pub fn transaction_author_agreement_digest_works() {
+            assert_eq!(
+                transaction_author_agreement_digest("indy agreement", "1.0"),
+                "a7f0752ba35b283b47e70f683572340e7f1d78d7dae632e6425b5ef7848dfa74"
+            );
+        }
+    }
 }
@@ -2,4 +2,8 @@
     Copyright © 2023 Province of British Columbia
     https://digital.gov.bc.ca/digital-trust
 */
+use std::time::Duration;
+
 pub const DEFAULT_POOL_PROTOCOL_VERSION: usize = 2;
+// Pool connection is rebuilt before sending a request if it has not been used for this period
+pub const POOL_IDLE_TIMEOUT: Duration = Duration::from_secs(600);
@@ -10,40 +10,257 @@ use crate::{
 
 pub mod create_command {
     use super::*;
-    use crate::tools::pool::pool_config::PoolConfig;
+    use crate::{
+        error::CliError,
+        tools::{
+            docker::{Docker, GENESIS_PATHS},
+            pool::pool_config::PoolConfig,
+        },
+        utils::encoding::Encoding,
+    };
+    use serde_json::Value as JsonValue;
+    use std::{collections::HashMap, fs};
+
+    const SIGNATURE_LENGTH: usize = 64;
 
     command!(CommandMetadata::build(
         "create",
         "Create new pool ledger config with specified name"
     )
     .add_main_param("name", "The name of new pool ledger config")
-    .add_required_param("gen_txn_file", "Path to file with genesis transactions")
+    .add_optional_param("gen_txn_file", "Path to file with genesis transactions")
+    .add_optional_param(
+        "container",
+        "Name of a running local network container to take the genesis transactions from (an alternative to the `gen_txn_file` parameter)"
+    )
+    .add_optional_param(
+        "container_path",
+        "Path to the genesis file inside the container. Skip to look in the locations used by von-network and indy-node images"
+    )
+    .add_optional_param(
+        "namespace",
+        "did:indy namespace of the network (for example `sovrin:staging`)"
+    )
+    .add_optional_param(
+        "custom_roles",
+        "JSON object mapping network-specific role names to their codes"
+    )
+    .add_optional_param(
+        "endorser",
+        "DID to prepare write requests for endorsement by when the active DID is not allowed to send them on its own"
+    )
+    .add_optional_param(
+        "gen_txn_sig",
+        "Path to file with the detached Ed25519 signature of the genesis transactions (raw bytes, base58, base64 or hex)"
+    )
+    .add_optional_param(
+        "signer_verkey",
+        "Verkey of the network publisher to verify the genesis transactions signature with"
+    )
     .add_example("pool create pool1 gen_txn_file=/home/pool_genesis_transactions")
+    .add_example(
+        "pool create pool1 gen_txn_file=/home/pool_genesis_transactions namespace=sovrin:staging"
+    )
+    .add_example(
+        r#"pool create pool1 gen_txn_file=/home/pool_genesis_transactions custom_roles={"AUDITOR":"301"}"#
+    )
+    .add_example("pool create local container=von_webserver")
+    .add_example(
+        "pool create pool1 gen_txn_file=/home/pool_genesis_transactions gen_txn_sig=/home/pool_genesis_transactions.sig signer_verkey=GJ1SzoWzavQYfNL9XkaJdrQejfztN4XqdsiV4ct3LXKL"
+    )
     .finalize());
 
     fn execute(ctx: &CommandContext, params: &CommandParams) -> Result<(), ()> {
         trace!("execute >> ctx {:?} params {:?}", ctx, params);
 
-        let name = ParamParser::get_str_param("name", params)?;
-        let gen_txn_file = ParamParser::get_str_param("gen_txn_file", params)?;
+        let name = ParamParser::get_str_param(ctx, "name", params)?;
+        let gen_txn_file = ParamParser::get_opt_str_param(ctx, "gen_txn_file", params)?;
+        let container = ParamParser::get_opt_str_param(ctx, "container", params)?;
+        let container_path = ParamParser::get_opt_str_param(ctx, "container_path", params)?;
+        let namespace = ParamParser::get_opt_str_param(ctx, "namespace", params)?;
+        let endorser = ParamParser::get_opt_did_param(ctx, "endorser", params)?;
+        let gen_txn_sig = ParamParser::get_opt_str_param(ctx, "gen_txn_sig", params)?;
+        let signer_verkey = ParamParser::get_opt_str_param(ctx, "signer_verkey", params)?;
+        let custom_roles = ParamParser::get_opt_object_param(ctx, "custom_roles", params)?
+            .map(|roles| parse_custom_roles(ctx, roles))
+            .transpose()?
+            .unwrap_or_default();
 
         trace!(
-            r#"Pool::create_pool_ledger_config try: name {}, gen_txn_file {:?}"#,
+            r#"Pool::create_pool_ledger_config try: name {}, gen_txn_file {:?}, container {:?}"#,
             name,
-            gen_txn_file
+            gen_txn_file,
+            container
         );
 
-        let config = PoolConfig {
-            genesis_txn: gen_txn_file.to_string(),
+        let signature = match (gen_txn_sig, signer_verkey) {
+            (Some(gen_txn_sig), Some(signer_verkey)) => {
+                Some((read_genesis_signature(ctx, gen_txn_sig)?, signer_verkey))
+            }
+            (None, None) => None,
+            _ => {
+                println_err!(
+                    ctx,
+                    "\"gen_txn_sig\" and \"signer_verkey\" parameters must be specified together"
+                );
+                return Err(());
+            }
         };
 
-        Pool::create(name, &config).map_err(|err| println_err!("{}", err.message(Some(&name))))?;
+        let mut config = PoolConfig {
+            genesis_txn: String::new(),
+            namespace: namespace.map(String::from),
+            custom_roles,
+            endorser: endorser.map(|endorser| endorser.to_string()),
+            genesis_signer: signature.as_ref().map(|(_, verkey)| verkey.to_string()),
+            ..PoolConfig::default()
+        };
 
-        println_succ!("Pool config \"{}\" has been created", name);
+        match (gen_txn_file, container) {
+            (Some(gen_txn_file), None) if signature.is_some() => {
+                // the verified content is stored rather than copied from the file once more
+                let genesis = fs::read(gen_txn_file).map_err(|err| {
+                    println_err!(ctx, "Unable to read \"{}\": {}", gen_txn_file, err)
+                })?;
+                verify_genesis(ctx, &genesis, signature.as_ref())?;
+                let genesis = String::from_utf8(genesis).map_err(|_| {
+                    println_err!(
+                        ctx,
+                        "File \"{}\" is not a genesis transactions file",
+                        gen_txn_file
+                    )
+                })?;
+                Pool::create_with_genesis(name, &config, &genesis)
+                    .map_err(|err| err.print(ctx, Some(&name)))?;
+                println_succ!(ctx, "Pool config \"{}\" has been created", name);
+            }
+            (Some(gen_txn_file), None) => {
+                config.genesis_txn = gen_txn_file.to_string();
+                Pool::create(name, &config).map_err(|err| err.print(ctx, Some(&name)))?;
+                println_succ!(ctx, "Pool config \"{}\" has been created", name);
+            }
+            (None, Some(container)) => {
+                let (path, genesis) = read_container_genesis(ctx, container, container_path)?;
+                verify_genesis(ctx, genesis.as_bytes(), signature.as_ref())?;
+                Pool::create_with_genesis(name, &config, &genesis)
+                    .map_err(|err| err.print(ctx, Some(&name)))?;
+                println_succ!(
+                    ctx,
+                    "Pool config \"{}\" has been created from \"{}\" of container \"{}\"",
+                    name,
+                    path,
+                    container
+                );
+            }
+            _ => {
+                println_err!(
+                    ctx,
+                    "Either \"gen_txn_file\" or \"container\" parameter must be specified"
+                );
+                return Err(());
+            }
+        }
 
         trace!("execute <<");
         Ok(())
     }
+
+    fn verify_genesis(
+        ctx: &CommandContext,
+        genesis: &[u8],
+        signature: Option<&(Vec<u8>, &str)>,
+    ) -> Result<(), ()> {
+        if let Some((signature, signer_verkey)) = signature {
+            Pool::verify_genesis(genesis, signature, signer_verkey)
+                .map_err(|err| err.print(ctx, None))?;
+            println_succ!(
+                ctx,
+                "Genesis transactions signature of \"{}\" is valid",
+                signer_verkey
+            );
+        }
+        Ok(())
+    }
+
+    // Raw signature bytes or their text form as printed by `did sign`
+    fn read_genesis_signature(ctx: &CommandContext, path: &str) -> Result<Vec<u8>, ()> {
+        let content = fs::read(path)
+            .map_err(|err| println_err!(ctx, "Unable to read \"{}\": {}", path, err))?;
+        if content.len() == SIGNATURE_LENGTH {
+            return Ok(content);
+        }
+        let content = String::from_utf8_lossy(&content);
+        [Encoding::Base58, Encoding::Base64, Encoding::Hex]
+            .iter()
+            .filter_map(|encoding| encoding.decode(content.trim()).ok())
+            .find(|signature| signature.len() == SIGNATURE_LENGTH)
+            .ok_or_else(|| {
+                println_err!(
+                    ctx,
+                    "File \"{}\" does not contain an Ed25519 signature",
+                    path
+                )
+            })
+    }
+
+    fn read_container_genesis(
+        ctx: &CommandContext,
+        container: &str,
+        container_path: Option<&str>,
+    ) -> Result<(String, String), ()> {
+        let docker = Docker::from_env().map_err(|err| err.print(ctx, None))?;
+        let paths = match container_path {
+            Some(path) => vec![path],
+            None => GENESIS_PATHS.to_vec(),
+        };
+
+        let mut last_error = None;
+        for path in paths {
+            match docker.read_file(container, path) {
+                Ok(genesis) => {
+                    let genesis = String::from_utf8(genesis).map_err(|_| {
+                        println_err!(ctx, "File \"{}\" is not a genesis transactions file", path)
+                    })?;
+                    return Ok((path.to_string(), genesis));
+                }
+                Err(err @ CliError::NotFound(_)) => last_error = Some(err),
+                // Docker is not available: no point to try other paths
+                Err(err) => return Err(err.print(ctx, None)),
+            }
+        }
+        if let Some(err) = last_error {
+            err.print(ctx, None);
+        }
+        Err(())
+    }
+
+    fn parse_custom_roles(
+        ctx: &CommandContext,
+        roles: JsonValue,
+    ) -> Result<HashMap<String, String>, ()> {
+        let roles = roles.as_object().ok_or_else(|| {
+            println_err!(
+                ctx,
+                "Invalid format of \"custom_roles\" parameter: JSON object expected"
+            )
+        })?;
+
+        roles
+            .iter()
+            .map(|(name, code)| {
+                let code = match code {
+                    JsonValue::String(code) => code.to_string(),
+                    JsonValue::Number(code) => code.to_string(),
+                    _ => String::new(),
+                };
+                if code.is_empty() || !code.chars().all(|c| c.is_ascii_digit()) {
+                    println_err!(ctx, "Invalid code of \"{}\" role: number expected", name);
+                    return Err(());
+                }
+                Ok((name.to_uppercase(), code))
+            })
+            .collect()
+    }
 }
 
 #[cfg(test)]
@@ -53,7 +270,14 @@ pub mod tests {
 
     mod create {
         use super::*;
-        use crate::pool::tests::{create_pool, delete_pool, get_pools, POOL};
+        use crate::{
+            did::tests::{SEED_TRUSTEE, VERKEY_MY1, VERKEY_TRUSTEE},
+            pool::tests::{create_pool, delete_pool, get_pools, POOL},
+            utils::environment::EnvironmentUtils,
+        };
+        use aries_askar::kms::{KeyAlg, LocalKey};
+        use indy_utils::base58;
+        use std::fs;
 
         #[test]
         pub fn create_works() {
@@ -108,6 +332,97 @@ pub mod tests {
             tear_down();
         }
 
+        #[test]
+        pub fn create_works_for_both_gen_txn_file_and_container() {
+            let ctx = setup();
+            {
+                let cmd = create_command::new();
+                let mut params = CommandParams::new();
+                params.insert("name", POOL.to_string());
+                params.insert(
+                    "gen_txn_file",
+                    "docker_pool_transactions_genesis".to_string(),
+                );
+                params.insert("container", "von_webserver".to_string());
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            assert!(get_pools().is_empty());
+            tear_down();
+        }
+
+        fn sign_genesis(file_name: &str) -> String {
+            let genesis = fs::read("docker_pool_transactions_genesis").unwrap();
+            let key = LocalKey::from_seed(KeyAlg::Ed25519, SEED_TRUSTEE.as_bytes(), None).unwrap();
+            let signature = key.sign_message(&genesis, None).unwrap();
+            let path = EnvironmentUtils::tmp_file_path(file_name);
+            fs::write(&path, base58::encode(signature)).unwrap();
+            path.display().to_string()
+        }
+
+        #[test]
+        pub fn create_works_for_genesis_signature() {
+            let ctx = setup();
+            let signature = sign_genesis("genesis_signature.sig");
+            {
+                let cmd = create_command::new();
+                let mut params = CommandParams::new();
+                params.insert("name", POOL.to_string());
+                params.insert(
+                    "gen_txn_file",
+                    "docker_pool_transactions_genesis".to_string(),
+                );
+                params.insert("gen_txn_sig", signature.clone());
+                params.insert("signer_verkey", VERKEY_TRUSTEE.to_string());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            let pools = get_pools();
+            assert_eq!(VERKEY_TRUSTEE, pools[0]["genesis_signer"]);
+            fs::remove_file(&signature).unwrap();
+            delete_pool(&ctx);
+            tear_down();
+        }
+
+        #[test]
+        pub fn create_works_for_invalid_genesis_signature() {
+            let ctx = setup();
+            let signature = sign_genesis("genesis_signature_invalid.sig");
+            {
+                let cmd = create_command::new();
+                let mut params = CommandParams::new();
+                params.insert("name", POOL.to_string());
+                params.insert(
+                    "gen_txn_file",
+                    "docker_pool_transactions_genesis".to_string(),
+                );
+                params.insert("gen_txn_sig", signature.clone());
+                params.insert("signer_verkey", VERKEY_MY1.to_string());
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            assert!(get_pools().is_empty());
+            fs::remove_file(&signature).unwrap();
+            tear_down();
+        }
+
+        #[test]
+        pub fn create_works_for_missed_signer_verkey() {
+            let ctx = setup();
+            let signature = sign_genesis("genesis_signature_no_signer.sig");
+            {
+                let cmd = create_command::new();
+                let mut params = CommandParams::new();
+                params.insert("name", POOL.to_string());
+                params.insert(
+                    "gen_txn_file",
+                    "docker_pool_transactions_genesis".to_string(),
+                );
+                params.insert("gen_txn_sig", signature.clone());
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            assert!(get_pools().is_empty());
+            fs::remove_file(&signature).unwrap();
+            tear_down();
+        }
+
         #[test]
         pub fn create_works_for_unknown_txn_file() {
             let ctx = setup();
@@ -28,7 +28,7 @@ pub mod delete_command {
     fn execute(ctx: &CommandContext, params: &CommandParams) -> Result<(), ()> {
         trace!("execute >> ctx {:?} params {:?}", ctx, params);
 
-        let name = ParamParser::get_str_param("name", params)?;
+        let name = ParamParser::get_str_param(ctx, "name", params)?;
 
         trace!(r#"Pool::delete try: name {}"#, name);
 
@@ -36,9 +36,9 @@ pub mod delete_command {
             close_pool(ctx, &pool)?;
         }
 
-        Pool::delete(name).map_err(|err| println_err!("{}", err.message(Some(&name))))?;
+        Pool::delete(name).map_err(|err| err.print(ctx, Some(&name)))?;
 
-        println_succ!("Pool \"{}\" has been deleted.", name);
+        println_succ!(ctx, "Pool \"{}\" has been deleted.", name);
 
         trace!("execute <<");
         Ok(())
@@ -29,9 +29,9 @@ pub fn close_pool(ctx: &CommandContext, pool: &Pool) -> Result<(), ()> {
         .map(|_| {
             ctx.reset_connected_pool();
             ctx.set_transaction_author_info(None);
-            println_succ!("Pool \"{}\" has been disconnected", pool.name)
+            println_succ!(ctx, "Pool \"{}\" has been disconnected", pool.name)
         })
-        .map_err(|err| println_err!("{}", err.message(Some(&pool.name))))
+        .map_err(|err| err.print(ctx, Some(&pool.name)))
 }
 
 #[cfg(test)]
@@ -0,0 +1,146 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::{
+    command_executor::{Command, CommandContext, CommandMetadata, CommandParams},
+    params_parser::ParamParser,
+    utils::table::print_list_table,
+};
+
+pub mod latency_stats_command {
+    use super::*;
+
+    command!(CommandMetadata::build(
+        "latency-stats",
+        "Show ledger request latency per node collected since the pool has been connected."
+    )
+    .add_optional_param(
+        "reset",
+        "Reset collected statistics after showing (false by default)"
+    )
+    .add_example("pool latency-stats")
+    .add_example("pool latency-stats reset=true")
+    .finalize());
+
+    fn execute(ctx: &CommandContext, params: &CommandParams) -> Result<(), ()> {
+        trace!("execute >> ctx {:?} params {:?}", ctx, params);
+
+        let reset = ParamParser::get_opt_bool_param(ctx, "reset", params)?.unwrap_or(false);
+
+        let pool = ctx.ensure_connected_pool()?;
+
+        {
+            let stats = pool.latency_stats();
+
+            let rows = stats
+                .nodes()
+                .into_iter()
+                .map(|node| {
+                    json!({
+                        "node": node.node,
+                        "replies": node.replies,
+                        "p50": format!("{:.1}", node.p50),
+                        "p95": format!("{:.1}", node.p95),
+                        "max": format!("{:.1}", node.max),
+                        "errors": node.errors,
+                    })
+                })
+                .collect::<Vec<serde_json::Value>>();
+
+            print_list_table(
+                ctx,
+                &rows,
+                &[
+                    ("node", "Node"),
+                    ("replies", "Replies"),
+                    ("p50", "p50, ms"),
+                    ("p95", "p95, ms"),
+                    ("max", "Max, ms"),
+                    ("errors", "Errors"),
+                ],
+                "There are no ledger requests sent yet",
+            );
+            println!(
+                "Requests sent to pool \"{}\": {}, failed: {}",
+                pool.name, stats.requests, stats.failures
+            );
+        }
+
+        if reset {
+            pool.reset_latency_stats();
+            println_succ!(ctx, "Latency statistics have been reset");
+        }
+
+        trace!("execute <<");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::{
+        commands::{setup, setup_with_wallet_and_pool, tear_down, tear_down_with_wallet_and_pool},
+        ledger::tests::use_trustee,
+        tools::pool::stats::LatencyStats,
+    };
+    use std::collections::HashMap;
+
+    mod latency_stats {
+        use super::*;
+        use crate::{did::tests::DID_TRUSTEE, ledger::get_nym_command};
+
+        #[test]
+        pub fn latency_stats_works() {
+            let ctx = setup_with_wallet_and_pool();
+            use_trustee(&ctx);
+            {
+                let cmd = get_nym_command::new();
+                let mut params = CommandParams::new();
+                params.insert("did", DID_TRUSTEE.to_string());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            {
+                let cmd = latency_stats_command::new();
+                let mut params = CommandParams::new();
+                params.insert("reset", "true".to_string());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            let pool = ctx.ensure_connected_pool().unwrap();
+            assert_eq!(pool.latency_stats().requests, 0);
+            tear_down_with_wallet_and_pool(&ctx);
+        }
+
+        #[test]
+        pub fn latency_stats_works_for_not_connected() {
+            let ctx = setup();
+            {
+                let cmd = latency_stats_command::new();
+                let params = CommandParams::new();
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            tear_down();
+        }
+
+        #[test]
+        pub fn latency_stats_record_works() {
+            let mut stats = LatencyStats::default();
+            for elapsed in [0.1, 0.2, 0.3, 0.4] {
+                let timing = HashMap::from([("Node1".to_string(), elapsed)]);
+                stats.record("105", Some(&timing), false);
+            }
+            let timing = HashMap::from([("Node1".to_string(), 1.0)]);
+            stats.record("1", Some(&timing), true);
+            stats.record("1", None, true);
+
+            let nodes = stats.nodes();
+            assert_eq!(stats.requests, 6);
+            assert_eq!(stats.failures, 2);
+            assert_eq!(nodes[0].replies, 5);
+            assert_eq!(nodes[0].p50.round(), 300.0);
+            assert_eq!(nodes[0].max.round(), 1000.0);
+            assert_eq!(nodes[0].errors, 1);
+        }
+    }
+}
@@ -5,7 +5,7 @@
 use crate::{
     command_executor::{Command, CommandContext, CommandMetadata, CommandParams},
     tools::pool::Pool,
-    utils::table::print_list_table,
+    utils::{table::print_list_table, time::timestamp_to_datetime},
 };
 
 pub mod list_command {
@@ -16,15 +16,35 @@ pub mod list_command {
     fn execute(ctx: &CommandContext, params: &CommandParams) -> Result<(), ()> {
         trace!("execute >> ctx {:?} params {:?}", ctx, params);
 
-        let pools = Pool::list().map_err(|err| println_err!("{}", err.message(None)))?;
+        let pools = Pool::list().map_err(|err| err.print(ctx, None))?;
 
-        let pools: Vec<serde_json::Value> = serde_json::from_str(&pools)
-            .map_err(|_| println_err!("Wrong data has been received"))?;
+        let mut pools: Vec<serde_json::Value> = serde_json::from_str(&pools)
+            .map_err(|_| println_err!(ctx, "Wrong data has been received"))?;
 
-        print_list_table(&pools, &[("pool", "Pool")], "There are no pools defined");
+        let connected_pool = ctx.get_connected_pool().map(|pool| pool.name.to_string());
+        for pool in pools.iter_mut() {
+            pool["connected"] = json!(pool["pool"].as_str() == connected_pool.as_deref());
+            if let Some(last_refresh) = pool["last_refresh"].as_i64() {
+                pool["last_refresh"] = json!(timestamp_to_datetime(ctx, last_refresh));
+            }
+        }
+
+        print_list_table(
+            ctx,
+            &pools,
+            &[
+                ("pool", "Pool"),
+                ("connected", "Connected"),
+                ("nodes", "Nodes"),
+                ("checksum", "Genesis SHA-256"),
+                ("last_refresh", "Last Refresh"),
+                ("genesis_signer", "Genesis Signer"),
+            ],
+            "There are no pools defined",
+        );
 
         if let Some(pool) = ctx.get_connected_pool() {
-            println_succ!("Current pool \"{}\"", pool.name);
+            println_succ!(ctx, "Current pool \"{}\"", pool.name);
         }
 
         trace!("execute <<");
@@ -54,7 +74,7 @@ pub mod tests {
 
     mod list {
         use super::*;
-        use crate::pool::tests::{create_pool, delete_pool};
+        use crate::pool::tests::{create_pool, delete_pool, get_pools};
 
         #[test]
         pub fn list_works() {
@@ -65,6 +85,9 @@ pub mod tests {
                 let params = CommandParams::new();
                 cmd.execute(&ctx, &params).unwrap();
             }
+            let pools = get_pools();
+            assert_eq!(4, pools[0]["nodes"].as_u64().unwrap());
+            assert_eq!(64, pools[0]["checksum"].as_str().unwrap().len());
             delete_pool(&ctx);
             tear_down();
         }
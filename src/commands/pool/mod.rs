@@ -4,19 +4,25 @@
 */
 use crate::command_executor::{CommandGroup, CommandGroupMetadata};
 
+pub mod attrib_schema;
 pub mod connect;
 pub mod constants;
 pub mod create;
 pub mod delete;
 pub mod disconnect;
+pub mod latency_stats;
 pub mod list;
 pub mod refresh;
+pub mod refresh_capabilities;
+pub mod set_endorser;
 pub mod set_protocol_version;
+pub mod set_signature_scheme;
 pub mod show_taa;
 
 pub use self::{
-    connect::*, create::*, delete::*, disconnect::*, list::*, refresh::*, set_protocol_version::*,
-    show_taa::*,
+    attrib_schema::*, connect::*, create::*, delete::*, disconnect::*, latency_stats::*, list::*,
+    refresh::*, refresh_capabilities::*, set_endorser::*, set_protocol_version::*,
+    set_signature_scheme::*, show_taa::*,
 };
 
 pub mod group {
@@ -18,10 +18,11 @@ pub mod refresh_command {
 
         let pool = ctx.ensure_connected_pool()?;
 
-        pool.refresh()
-            .map_err(|err| println_err!("Unable to refresh pool. Reason: {}", err.message(None)))?;
+        pool.refresh().map_err(|err| {
+            println_err!(ctx, "Unable to refresh pool. Reason: {}", err.message(None))
+        })?;
 
-        println_succ!("Pool \"{}\"  has been refreshed", pool.name);
+        println_succ!(ctx, "Pool \"{}\"  has been refreshed", pool.name);
 
         trace!("execute <<");
         Ok(())
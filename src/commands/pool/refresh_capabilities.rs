@@ -0,0 +1,117 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::{
+    command_executor::{Command, CommandContext, CommandMetadata, CommandParams},
+    tools::pool::capabilities::Capabilities,
+    utils::table::print_table,
+};
+
+pub mod refresh_capabilities_command {
+    use super::*;
+
+    command!(CommandMetadata::build(
+        "refresh-capabilities",
+        "Read the capabilities of the connected network (Transaction Author Agreement, acceptance mechanisms, \
+                 did:indy support, auth rules) from the ledger and update the copy cached in the pool directory."
+    )
+    .add_example("pool refresh-capabilities")
+    .finalize());
+
+    fn execute(ctx: &CommandContext, params: &CommandParams) -> Result<(), ()> {
+        trace!("execute >> ctx {:?} params {:?}", ctx, params);
+
+        let pool = ctx.ensure_connected_pool()?;
+
+        let capabilities = pool.refresh_capabilities().map_err(|err| {
+            println_err!(
+                ctx,
+                "Unable to read capabilities of pool \"{}\". Reason: {}",
+                pool.name,
+                err.message(None)
+            )
+        })?;
+
+        println_succ!(
+            ctx,
+            "Capabilities of pool \"{}\" have been refreshed",
+            pool.name
+        );
+        print_capabilities(ctx, &capabilities);
+
+        trace!("execute <<");
+        Ok(())
+    }
+}
+
+fn print_capabilities(ctx: &CommandContext, capabilities: &Capabilities) {
+    print_table(
+        ctx,
+        &json!({
+            "taa_required": capabilities.taa_required,
+            "taa_version": capabilities.taa_version,
+            "aml_version": capabilities.aml_version,
+            "acceptance_mechanisms": capabilities.acceptance_mechanisms.join(", "),
+            "did_indy": capabilities.did_indy,
+            "auth_rules": capabilities.auth_rules.len(),
+        }),
+        &[
+            ("taa_required", "TAA Required"),
+            ("taa_version", "TAA Version"),
+            ("aml_version", "AML Version"),
+            ("acceptance_mechanisms", "Acceptance Mechanisms"),
+            ("did_indy", "did:indy"),
+            ("auth_rules", "Auth Rules"),
+        ],
+    );
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::{
+        commands::{setup, tear_down},
+        tools::pool::pool_config::PoolDirectory,
+    };
+
+    mod refresh_capabilities {
+        use super::*;
+        use crate::pool::tests::{
+            create_and_connect_pool, create_pool, delete_pool, disconnect_and_delete_pool, POOL,
+        };
+
+        #[test]
+        pub fn refresh_capabilities_works() {
+            let ctx = setup();
+            create_and_connect_pool(&ctx);
+            {
+                let cmd = refresh_capabilities_command::new();
+                let params = CommandParams::new();
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            let capabilities = PoolDirectory::from(POOL).read_capabilities().unwrap();
+            assert!(!capabilities.auth_rules.is_empty());
+            assert!(ctx
+                .ensure_connected_pool()
+                .unwrap()
+                .capabilities()
+                .is_some());
+            disconnect_and_delete_pool(&ctx);
+            tear_down();
+        }
+
+        #[test]
+        pub fn refresh_capabilities_works_for_not_connected() {
+            let ctx = setup();
+            create_pool(&ctx);
+            {
+                let cmd = refresh_capabilities_command::new();
+                let params = CommandParams::new();
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            delete_pool(&ctx);
+            tear_down();
+        }
+    }
+}
@@ -0,0 +1,115 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::{
+    command_executor::{Command, CommandContext, CommandMetadata, CommandParams},
+    params_parser::ParamParser,
+    tools::pool::Pool,
+};
+
+pub mod set_endorser_command {
+    use super::*;
+
+    command!(CommandMetadata::build(
+        "set-endorser",
+        "Set default endorser of the pool. Write requests which the active DID is not allowed to send \
+                 on its own (according to the ledger auth rules) are prepared for endorsement by this DID."
+    )
+    .add_main_param("name", "The name of pool ledger config")
+    .add_optional_param("did", "Endorser DID. Skip to remove the default endorser")
+    .add_example("pool set-endorser sovrin did=VsKV7grR1BUE29mG2Fm2kX")
+    .add_example("pool set-endorser sovrin")
+    .finalize());
+
+    fn execute(ctx: &CommandContext, params: &CommandParams) -> Result<(), ()> {
+        trace!("execute >> ctx {:?} params {:?}", ctx, params);
+
+        let name = ParamParser::get_str_param(ctx, "name", params)?;
+        let endorser = ParamParser::get_opt_did_param(ctx, "did", params)?;
+
+        Pool::set_endorser(name, endorser.as_ref()).map_err(|err| err.print(ctx, Some(name)))?;
+
+        match endorser {
+            Some(endorser) => println_succ!(
+                ctx,
+                "Default endorser of pool \"{}\" has been set to \"{}\"",
+                name,
+                endorser
+            ),
+            None => println_succ!(
+                ctx,
+                "Default endorser of pool \"{}\" has been removed",
+                name
+            ),
+        }
+        if ctx
+            .get_connected_pool()
+            .map(|pool| pool.name == name)
+            .unwrap_or(false)
+        {
+            println_warn!(
+                ctx,
+                "The change will be applied on the next connection to the pool"
+            );
+        }
+
+        trace!("execute <<");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::{
+        commands::{setup, tear_down},
+        did::tests::DID_MY1,
+        tools::pool::pool_config::PoolDirectory,
+    };
+
+    mod set_endorser {
+        use super::*;
+        use crate::pool::tests::{create_pool, delete_pool, POOL};
+
+        fn get_endorser() -> Option<String> {
+            PoolDirectory::from(POOL).read_config().unwrap().endorser
+        }
+
+        #[test]
+        pub fn set_endorser_works() {
+            let ctx = setup();
+            create_pool(&ctx);
+            {
+                let cmd = set_endorser_command::new();
+                let mut params = CommandParams::new();
+                params.insert("name", POOL.to_string());
+                params.insert("did", DID_MY1.to_string());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            assert_eq!(Some(DID_MY1.to_string()), get_endorser());
+            {
+                let cmd = set_endorser_command::new();
+                let mut params = CommandParams::new();
+                params.insert("name", POOL.to_string());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            assert_eq!(None, get_endorser());
+            delete_pool(&ctx);
+            tear_down();
+        }
+
+        #[test]
+        pub fn set_endorser_works_for_unknown_pool() {
+            let ctx = setup();
+            {
+                let cmd = set_endorser_command::new();
+                let mut params = CommandParams::new();
+                params.insert("name", POOL.to_string());
+                params.insert("did", DID_MY1.to_string());
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            tear_down();
+        }
+    }
+}
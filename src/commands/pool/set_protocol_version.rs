@@ -25,7 +25,11 @@ pub mod set_protocol_version_command {
         let protocol_version = ParamParser::get_number_param::<usize>("protocol-version", params)?;
 
         ctx.set_pool_protocol_version(protocol_version);
-        println_succ!("Protocol Version has been set: \"{}\".", protocol_version);
+        println_succ!(
+            ctx,
+            "Protocol Version has been set: \"{}\".",
+            protocol_version
+        );
 
         trace!("execute <<");
         Ok(())
@@ -0,0 +1,122 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::{
+    command_executor::{Command, CommandContext, CommandMetadata, CommandParams},
+    params_parser::ParamParser,
+    tools::{ledger::SignatureScheme, pool::Pool},
+};
+
+pub mod set_signature_scheme_command {
+    use super::*;
+
+    command!(CommandMetadata::build(
+        "set-signature-scheme",
+        "Set the scheme requests sent to the pool are signed with. \
+                 Needed only for networks which do not accept the default Ed25519 signatures of the request data."
+    )
+    .add_main_param("name", "The name of pool ledger config")
+    .add_optional_param(
+        "scheme",
+        "Signature scheme. One of:
+                 ed25519 - Ed25519 signature of the request data (default)
+                 ed25519ph - Ed25519ph (RFC 8032) signature of the SHA-512 digest of the request data
+                 Skip to reset to the default"
+    )
+    .add_example("pool set-signature-scheme sovrin scheme=ed25519ph")
+    .add_example("pool set-signature-scheme sovrin")
+    .finalize());
+
+    fn execute(ctx: &CommandContext, params: &CommandParams) -> Result<(), ()> {
+        trace!("execute >> ctx {:?} params {:?}", ctx, params);
+
+        let name = ParamParser::get_str_param(ctx, "name", params)?;
+        let scheme = ParamParser::get_opt_str_param(ctx, "scheme", params)?
+            .map(SignatureScheme::from_str)
+            .transpose()
+            .map_err(|err| err.print(ctx, None))?;
+
+        Pool::set_signature_scheme(name, scheme).map_err(|err| err.print(ctx, Some(name)))?;
+
+        println_succ!(
+            ctx,
+            "Signature scheme of pool \"{}\" has been set to \"{}\"",
+            name,
+            scheme.unwrap_or_default().as_str()
+        );
+        if ctx
+            .get_connected_pool()
+            .map(|pool| pool.name == name)
+            .unwrap_or(false)
+        {
+            println_warn!(
+                ctx,
+                "The change will be applied on the next connection to the pool"
+            );
+        }
+
+        trace!("execute <<");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::{
+        commands::{setup, tear_down},
+        tools::pool::pool_config::PoolDirectory,
+    };
+
+    mod set_signature_scheme {
+        use super::*;
+        use crate::pool::tests::{create_pool, delete_pool, POOL};
+
+        fn get_signature_scheme() -> Option<String> {
+            PoolDirectory::from(POOL)
+                .read_config()
+                .unwrap()
+                .signature_scheme
+        }
+
+        #[test]
+        pub fn set_signature_scheme_works() {
+            let ctx = setup();
+            create_pool(&ctx);
+            {
+                let cmd = set_signature_scheme_command::new();
+                let mut params = CommandParams::new();
+                params.insert("name", POOL.to_string());
+                params.insert("scheme", "ed25519ph".to_string());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            assert_eq!(Some("ed25519ph".to_string()), get_signature_scheme());
+            {
+                let cmd = set_signature_scheme_command::new();
+                let mut params = CommandParams::new();
+                params.insert("name", POOL.to_string());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            assert_eq!(None, get_signature_scheme());
+            delete_pool(&ctx);
+            tear_down();
+        }
+
+        #[test]
+        pub fn set_signature_scheme_works_for_unknown_scheme() {
+            let ctx = setup();
+            create_pool(&ctx);
+            {
+                let cmd = set_signature_scheme_command::new();
+                let mut params = CommandParams::new();
+                params.insert("name", POOL.to_string());
+                params.insert("scheme", "rsa".to_string());
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            assert_eq!(None, get_signature_scheme());
+            delete_pool(&ctx);
+            tear_down();
+        }
+    }
+}
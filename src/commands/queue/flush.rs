@@ -0,0 +1,211 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::{
+    command_executor::{Command, CommandContext, CommandMetadata, CommandParams},
+    ledger::{get_active_transaction_author_agreement, send_with_status, set_author_agreement},
+    pool::transaction_author_agreement_digest,
+    tools::{ledger::Ledger, queue::QueuedRequest},
+    wallet::ensure_key_rotated,
+};
+
+use super::list::print_queue;
+
+use indy_utils::did::DidValue;
+use indy_vdr::pool::PreparedRequest;
+
+pub mod flush_command {
+    use super::*;
+
+    command!(CommandMetadata::build(
+        "flush",
+        "Send transactions spooled to the local queue to the connected pool in the order they have been queued. \
+        Sent transactions are removed from the queue, the first one that cannot be sent stops flushing. \
+        If the pool Transaction Author Agreement has changed since a transaction has been queued, \
+        the acceptance of the Agreement accepted in the current session is put into the transaction and it is signed again."
+    )
+    .add_example("queue flush")
+    .finalize());
+
+    fn execute(ctx: &CommandContext, params: &CommandParams) -> Result<(), ()> {
+        trace!("execute >> ctx {:?} params {:?}", ctx, params);
+
+        let pool = ctx.ensure_connected_pool()?;
+
+        let (requests, skipped): (Vec<QueuedRequest>, Vec<QueuedRequest>) = QueuedRequest::list()
+            .map_err(|err| err.print(ctx, None))?
+            .into_iter()
+            .partition(|request| {
+                request.pool.is_none() || request.pool.as_deref() == Some(pool.name.as_str())
+            });
+
+        if !skipped.is_empty() {
+            println!(
+                "{} queued transaction(s) belong to other pools and have been skipped.",
+                skipped.len()
+            );
+        }
+        if requests.is_empty() {
+            println!(
+                "There are no queued transactions to send to pool \"{}\".",
+                pool.name
+            );
+            return Ok(());
+        }
+
+        let agreement = get_active_transaction_author_agreement(ctx, &pool)?;
+
+        let mut statuses = Vec::new();
+        for queued in requests.iter() {
+            let status = match prepare(ctx, queued, agreement.as_ref()) {
+                Ok(request) => send_with_status(ctx, &request),
+                Err(status) => status,
+            };
+            let committed = status.starts_with("Committed");
+            // Resending a rejected transaction gives the same result, so it is dropped as well
+            if committed || status.starts_with("Rejected") {
+                queued.remove().map_err(|err| err.print(ctx, None))?;
+            }
+            statuses.push(status);
+            if !committed {
+                break;
+            }
+        }
+
+        print_queue(ctx, &requests, &statuses);
+        let committed = statuses
+            .iter()
+            .filter(|status| status.starts_with("Committed"))
+            .count();
+        if committed != requests.len() {
+            println_err!(
+                ctx,
+                "{} of {} queued transaction(s) have been committed. The rest are kept in the queue.",
+                committed,
+                requests.len()
+            );
+            return Err(());
+        }
+        println_succ!(
+            ctx,
+            "All {} queued transaction(s) have been committed.",
+            committed
+        );
+
+        trace!("execute <<");
+        Ok(())
+    }
+}
+
+// The ledger rejects transactions carrying the acceptance of an Agreement other than the active one
+fn prepare(
+    ctx: &CommandContext,
+    queued: &QueuedRequest,
+    agreement: Option<&(String, String, Option<String>)>,
+) -> Result<PreparedRequest, String> {
+    let mut request = queued
+        .prepared_request()
+        .map_err(|_| "Failed: invalid transaction".to_string())?;
+
+    let (text, version, digest) = match agreement {
+        Some(agreement) => agreement,
+        None => return Ok(request),
+    };
+    let digest = digest
+        .clone()
+        .unwrap_or_else(|| transaction_author_agreement_digest(text, version));
+    if request.req_json["taaAcceptance"]["taaDigest"].as_str() == Some(digest.as_str()) {
+        return Ok(request);
+    }
+
+    match ctx.get_transaction_author_info() {
+        Some((accepted_text, accepted_version, _, _))
+            if &accepted_text == text && &accepted_version == version => {}
+        _ => {
+            return Err(format!(
+            "Failed: Agreement version \"{}\" is not accepted. Use `pool show-taa` to accept it",
+            version
+        ))
+        }
+    }
+    let wallet = ctx
+        .ensure_opened_wallet()
+        .map_err(|_| "Failed: wallet is required to sign the transaction again".to_string())?;
+    ensure_key_rotated(ctx, &wallet).map_err(|_| {
+        "Failed: wallet key must be rotated to sign the transaction again".to_string()
+    })?;
+
+    set_author_agreement(ctx, &mut request)
+        .map_err(|_| "Failed: Agreement acceptance cannot be set".to_string())?;
+    Ledger::sign_request(
+        ctx.get_connected_pool().as_deref(),
+        &wallet,
+        &DidValue(queued.submitter.to_string()),
+        &mut request,
+    )
+    .map_err(|err| format!("Failed: {}", err.message(Some(&wallet.name))))?;
+    Ok(request)
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::{
+        commands::{
+            setup_with_wallet, setup_with_wallet_and_pool, tear_down_with_wallet,
+            tear_down_with_wallet_and_pool,
+        },
+        did::tests::DID_TRUSTEE,
+        ledger::{
+            nym::tests::ensure_nym_added,
+            nym_command,
+            tests::{create_new_did, use_trustee},
+        },
+        pool::tests::POOL,
+    };
+
+    mod flush {
+        use super::*;
+
+        #[test]
+        pub fn flush_works() {
+            let ctx = setup_with_wallet_and_pool();
+            use_trustee(&ctx);
+            let (did, verkey) = create_new_did(&ctx);
+            {
+                let cmd = nym_command::new();
+                let mut params = CommandParams::new();
+                params.insert("did", did.clone());
+                params.insert("verkey", verkey);
+                params.insert("send", "false".to_string());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            let request =
+                PreparedRequest::from_request_json(ctx.get_context_transaction(None).unwrap())
+                    .unwrap();
+            QueuedRequest::new(&request, DID_TRUSTEE, Some(POOL))
+                .save()
+                .unwrap();
+            {
+                let cmd = flush_command::new();
+                let params = CommandParams::new();
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            assert!(ensure_nym_added(&ctx, &did).is_ok());
+            assert!(QueuedRequest::list().unwrap().is_empty());
+            tear_down_with_wallet_and_pool(&ctx);
+        }
+
+        #[test]
+        pub fn flush_works_for_no_connected_pool() {
+            let ctx = setup_with_wallet();
+            {
+                let cmd = flush_command::new();
+                let params = CommandParams::new();
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            tear_down_with_wallet(&ctx);
+        }
+    }
+}
@@ -0,0 +1,114 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::{
+    command_executor::{Command, CommandContext, CommandMetadata, CommandParams},
+    tools::{ledger::LedgerHelpers, queue::QueuedRequest},
+    utils::{table::print_list_table, time::timestamp_to_datetime},
+};
+
+use serde_json::Value as JsonValue;
+
+pub mod list_command {
+    use super::*;
+
+    command!(CommandMetadata::build(
+        "list",
+        "List ledger transactions spooled to the local queue with `queue=true` parameter."
+    )
+    .add_example("queue list")
+    .finalize());
+
+    fn execute(ctx: &CommandContext, params: &CommandParams) -> Result<(), ()> {
+        trace!("execute >> ctx {:?} params {:?}", ctx, params);
+
+        let requests = QueuedRequest::list().map_err(|err| err.print(ctx, None))?;
+        print_queue(ctx, &requests, &[]);
+
+        trace!("execute <<");
+        Ok(())
+    }
+}
+
+pub fn print_queue(ctx: &CommandContext, requests: &[QueuedRequest], statuses: &[String]) {
+    let rows = requests
+        .iter()
+        .enumerate()
+        .map(|(index, request)| {
+            json!({
+                "reqId": request.id,
+                "type": LedgerHelpers::get_txn_title(&JsonValue::String(request.txn_type.to_string())),
+                "submitter": request.submitter,
+                "pool": request.pool.as_deref().unwrap_or("-"),
+                "queued": timestamp_to_datetime(ctx, request.queued),
+                "status": statuses.get(index).map(String::as_str).unwrap_or("Queued"),
+            })
+        })
+        .collect::<Vec<JsonValue>>();
+
+    print_list_table(
+        ctx,
+        &rows,
+        &[
+            ("reqId", "Request ID"),
+            ("type", "Type"),
+            ("submitter", "Submitter"),
+            ("pool", "Pool"),
+            ("queued", "Queued"),
+            ("status", "Status"),
+        ],
+        "There are no queued transactions",
+    );
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::{
+        commands::{setup_with_wallet, tear_down_with_wallet},
+        ledger::{
+            nym_command,
+            tests::{create_new_did, use_trustee},
+        },
+    };
+
+    mod list {
+        use super::*;
+
+        #[test]
+        pub fn list_works() {
+            let ctx = setup_with_wallet();
+            {
+                let cmd = list_command::new();
+                let params = CommandParams::new();
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            tear_down_with_wallet(&ctx);
+        }
+
+        #[test]
+        pub fn list_works_for_request_queued_without_pool() {
+            let ctx = setup_with_wallet();
+            use_trustee(&ctx);
+            let (did, _) = create_new_did(&ctx);
+            {
+                let cmd = nym_command::new();
+                let mut params = CommandParams::new();
+                params.insert("did", did.clone());
+                params.insert("queue", "true".to_string());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            let requests = QueuedRequest::list().unwrap();
+            assert_eq!(1, requests.len());
+            assert_eq!("1", requests[0].txn_type);
+            assert_eq!(None, requests[0].pool);
+            {
+                let cmd = list_command::new();
+                let params = CommandParams::new();
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            tear_down_with_wallet(&ctx);
+        }
+    }
+}
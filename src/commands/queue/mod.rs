@@ -0,0 +1,19 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::command_executor::{CommandGroup, CommandGroupMetadata};
+
+pub mod flush;
+pub mod list;
+
+pub use self::{flush::*, list::*};
+
+pub mod group {
+    use super::*;
+
+    command_group!(CommandGroupMetadata::new(
+        "queue",
+        "Queue of ledger transactions spooled while the pool was unreachable"
+    ));
+}
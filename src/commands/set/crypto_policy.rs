@@ -0,0 +1,80 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::{
+    command_executor::{Command, CommandContext, CommandMetadata, CommandParams, CommandResult},
+    params_parser::ParamParser,
+    tools::crypto_policy::CryptoPolicy,
+};
+
+pub mod crypto_policy_command {
+    use super::*;
+
+    command!(CommandMetadata::build(
+        "crypto-policy",
+        "Set cryptography policy. `strict` policy allows only approved key algorithms and key derivation methods."
+    )
+    .add_main_param("policy", "Policy to use: default or strict")
+    .add_example("set crypto-policy strict")
+    .add_example("set crypto-policy default")
+    .finalize());
+
+    fn execute(ctx: &CommandContext, params: &CommandParams) -> CommandResult {
+        trace!("execute >> ctx: {:?}, params: {:?}", ctx, params);
+
+        let policy = ParamParser::get_str_param(ctx, "policy", params)?;
+
+        let policy = CryptoPolicy::from_str(policy).map_err(|err| err.print(ctx, None))?;
+        ctx.set_crypto_policy(policy);
+
+        println_succ!(ctx, "Crypto policy has been set to \"{}\"", policy.name());
+        let res = Ok(());
+
+        trace!("execute << {:?}", res);
+        res
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::commands::{setup, tear_down};
+
+    mod crypto_policy {
+        use super::*;
+
+        #[test]
+        #[cfg(not(feature = "fips"))]
+        pub fn crypto_policy_works() {
+            let ctx = setup();
+            {
+                let cmd = crypto_policy_command::new();
+                let mut params = CommandParams::new();
+                params.insert("policy", "strict".to_string());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            assert_eq!(ctx.get_crypto_policy(), CryptoPolicy::Strict);
+            {
+                let cmd = crypto_policy_command::new();
+                let mut params = CommandParams::new();
+                params.insert("policy", "default".to_string());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            assert_eq!(ctx.get_crypto_policy(), CryptoPolicy::Default);
+            tear_down();
+        }
+
+        #[test]
+        pub fn crypto_policy_works_for_unknown_policy() {
+            let ctx = setup();
+            {
+                let cmd = crypto_policy_command::new();
+                let mut params = CommandParams::new();
+                params.insert("policy", "fips".to_string());
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            tear_down();
+        }
+    }
+}
@@ -0,0 +1,126 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::{
+    command_executor::{Command, CommandContext, CommandMetadata, CommandParams, CommandResult},
+    params_parser::ParamParser,
+};
+
+pub mod freshness_threshold_command {
+    use super::*;
+
+    command!(CommandMetadata::build(
+        "freshness-threshold",
+        "Set the maximum age of the ledger state returned in read replies. \
+        Replies with an older state proof produce a warning (or an error in strict mode)."
+    )
+    .add_required_param(
+        "seconds",
+        "Maximum age of the state proof in seconds. 0 disables the check"
+    )
+    .add_optional_param(
+        "strict",
+        "Reject stale replies instead of warning (false by default)"
+    )
+    .add_example("set freshness-threshold seconds=300")
+    .add_example("set freshness-threshold seconds=60 strict=true")
+    .add_example("set freshness-threshold seconds=0")
+    .finalize());
+
+    fn execute(ctx: &CommandContext, params: &CommandParams) -> CommandResult {
+        trace!("execute >> ctx: {:?}, params: {:?}", ctx, params);
+
+        let seconds = ParamParser::get_number_param::<u64>("seconds", params)?;
+        let strict = ParamParser::get_opt_bool_param(ctx, "strict", params)?.unwrap_or(false);
+
+        if seconds == 0 {
+            ctx.set_freshness_threshold(None, false);
+            println_succ!(ctx, "Freshness check has been disabled");
+        } else {
+            ctx.set_freshness_threshold(Some(seconds), strict);
+            println_succ!(
+                ctx,
+                "Freshness threshold has been set to {} seconds. Stale replies will be {}",
+                seconds,
+                if strict { "rejected" } else { "reported" }
+            );
+        }
+        let res = Ok(());
+
+        trace!("execute << {:?}", res);
+        res
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::{
+        commands::{setup, tear_down},
+        ledger::check_freshness,
+    };
+
+    fn reply(timestamp: u64) -> String {
+        json!({
+            "op": "REPLY",
+            "result": {
+                "state_proof": {
+                    "multi_signature": {
+                        "value": { "timestamp": timestamp }
+                    }
+                }
+            }
+        })
+        .to_string()
+    }
+
+    fn set_threshold(ctx: &CommandContext, seconds: &str, strict: Option<&str>) {
+        let cmd = freshness_threshold_command::new();
+        let mut params = CommandParams::new();
+        params.insert("seconds", seconds.to_string());
+        if let Some(strict) = strict {
+            params.insert("strict", strict.to_string());
+        }
+        cmd.execute(ctx, &params).unwrap();
+    }
+
+    mod freshness_threshold {
+        use super::*;
+
+        #[test]
+        pub fn freshness_threshold_works() {
+            let ctx = setup();
+            set_threshold(&ctx, "300", None);
+            assert_eq!(ctx.get_freshness_threshold(), Some(300));
+            assert!(!ctx.is_freshness_strict());
+            check_freshness(&ctx, &reply(1)).unwrap();
+            set_threshold(&ctx, "0", None);
+            assert_eq!(ctx.get_freshness_threshold(), None);
+            tear_down();
+        }
+
+        #[test]
+        pub fn freshness_threshold_works_for_strict() {
+            let ctx = setup();
+            set_threshold(&ctx, "300", Some("true"));
+            check_freshness(&ctx, &reply(1)).unwrap_err();
+            let now = chrono::Utc::now().timestamp() as u64;
+            check_freshness(&ctx, &reply(now)).unwrap();
+            check_freshness(&ctx, r#"{"op":"REPLY","result":{}}"#).unwrap();
+            tear_down();
+        }
+
+        #[test]
+        pub fn freshness_threshold_works_for_invalid_seconds() {
+            let ctx = setup();
+            {
+                let cmd = freshness_threshold_command::new();
+                let mut params = CommandParams::new();
+                params.insert("seconds", "-1".to_string());
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            tear_down();
+        }
+    }
+}
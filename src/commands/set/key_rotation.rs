@@ -0,0 +1,103 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::{
+    command_executor::{Command, CommandContext, CommandMetadata, CommandParams, CommandResult},
+    params_parser::ParamParser,
+    tools::wallet::key_rotation::KeyRotationPolicy,
+    wallet::warn_key_rotation,
+};
+
+pub mod key_rotation_command {
+    use super::*;
+
+    command!(CommandMetadata::build(
+        "key-rotation",
+        "Set the maximum age of wallet keys. Opening a wallet with an older key (or a key of unknown age) \
+        produces a warning; in enforced mode transactions cannot be signed with the wallet until `wallet rekey` is run."
+    )
+    .add_required_param(
+        "max_age_days",
+        "Maximum age of the wallet key in days. 0 disables the policy"
+    )
+    .add_optional_param(
+        "enforce",
+        "Block signing transactions with a wallet whose key must be rotated (false by default)"
+    )
+    .add_example("set key-rotation max_age_days=90")
+    .add_example("set key-rotation max_age_days=90 enforce=true")
+    .add_example("set key-rotation max_age_days=0")
+    .finalize());
+
+    fn execute(ctx: &CommandContext, params: &CommandParams) -> CommandResult {
+        trace!("execute >> ctx: {:?}, params: {:?}", ctx, params);
+
+        let max_age_days = ParamParser::get_number_param::<u64>("max_age_days", params)?;
+        let enforce = ParamParser::get_opt_bool_param(ctx, "enforce", params)?.unwrap_or(false);
+
+        if max_age_days == 0 {
+            ctx.set_key_rotation_policy(None);
+            println_succ!(ctx, "Key rotation policy has been disabled");
+        } else {
+            ctx.set_key_rotation_policy(Some(KeyRotationPolicy {
+                max_age_days,
+                enforce,
+            }));
+            println_succ!(
+                ctx,
+                "Wallet keys must be rotated every {} days. Wallets with older keys will be {}",
+                max_age_days,
+                if enforce {
+                    "blocked from signing"
+                } else {
+                    "reported"
+                }
+            );
+            if let Some(wallet) = ctx.get_opened_wallet() {
+                warn_key_rotation(ctx, &wallet);
+            }
+        }
+        let res = Ok(());
+
+        trace!("execute << {:?}", res);
+        res
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::commands::{setup, tear_down};
+
+    mod key_rotation {
+        use super::*;
+
+        #[test]
+        pub fn key_rotation_works() {
+            let ctx = setup();
+            {
+                let cmd = key_rotation_command::new();
+                let mut params = CommandParams::new();
+                params.insert("max_age_days", "90".to_string());
+                params.insert("enforce", "true".to_string());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            assert_eq!(
+                Some(KeyRotationPolicy {
+                    max_age_days: 90,
+                    enforce: true
+                }),
+                ctx.get_key_rotation_policy()
+            );
+            {
+                let cmd = key_rotation_command::new();
+                let mut params = CommandParams::new();
+                params.insert("max_age_days", "0".to_string());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            assert_eq!(None, ctx.get_key_rotation_policy());
+            tear_down();
+        }
+    }
+}
@@ -0,0 +1,21 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::command_executor::{CommandGroup, CommandGroupMetadata};
+
+pub mod crypto_policy;
+pub mod freshness_threshold;
+pub mod key_rotation;
+pub mod output_format;
+pub mod write_rate;
+
+pub use self::{
+    crypto_policy::*, freshness_threshold::*, key_rotation::*, output_format::*, write_rate::*,
+};
+
+pub mod group {
+    use super::*;
+
+    command_group!(CommandGroupMetadata::new("set", "CLI settings commands"));
+}
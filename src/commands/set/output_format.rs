@@ -0,0 +1,88 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::{
+    command_executor::{Command, CommandContext, CommandMetadata, CommandParams, CommandResult},
+    params_parser::ParamParser,
+    utils::renderer::OutputFormat,
+};
+
+pub mod output_format_command {
+    use super::*;
+
+    command!(CommandMetadata::build(
+        "output-format",
+        "Set the format of command output: messages, tables and errors."
+    )
+    .add_main_param(
+        "format",
+        "One of: human (colored text and tables, default), json (a JSON document per line), \
+        csv (tables as CSV, messages in stderr), quiet (errors only)"
+    )
+    .add_example("set output-format json")
+    .add_example("set output-format human")
+    .finalize());
+
+    fn execute(ctx: &CommandContext, params: &CommandParams) -> CommandResult {
+        trace!("execute >> ctx: {:?}, params: {:?}", ctx, params);
+
+        let format = ParamParser::get_str_param(ctx, "format", params)?;
+        let format = OutputFormat::from_str(format).ok_or_else(|| {
+            println_err!(
+                ctx,
+                "Unsupported output format \"{}\". Supported formats: {}",
+                format,
+                OutputFormat::NAMES.join(", ")
+            )
+        })?;
+
+        ctx.set_output_format(format);
+        println_succ!(ctx, "Output format has been set to \"{}\"", format.name());
+        let res = Ok(());
+
+        trace!("execute << {:?}", res);
+        res
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::{
+        commands::{setup, tear_down},
+        utils::renderer::Output,
+    };
+
+    mod output_format {
+        use super::*;
+
+        #[test]
+        pub fn output_format_works() {
+            let ctx = setup();
+            {
+                let cmd = output_format_command::new();
+                let mut params = CommandParams::new();
+                params.insert("format", "quiet".to_string());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            assert_eq!(ctx.get_output_format(), OutputFormat::Quiet);
+            assert_eq!(ctx.output_format(), OutputFormat::Quiet);
+            ctx.set_output_format(OutputFormat::Human);
+            tear_down();
+        }
+
+        #[test]
+        pub fn output_format_works_for_unknown_format() {
+            let ctx = setup();
+            {
+                let cmd = output_format_command::new();
+                let mut params = CommandParams::new();
+                params.insert("format", "xml".to_string());
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            assert_eq!(ctx.get_output_format(), OutputFormat::Human);
+            tear_down();
+        }
+    }
+}
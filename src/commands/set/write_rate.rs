@@ -0,0 +1,100 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::{
+    command_executor::{Command, CommandContext, CommandMetadata, CommandParams, CommandResult},
+    params_parser::ParamParser,
+};
+
+pub mod write_rate_command {
+    use super::*;
+
+    command!(CommandMetadata::build(
+        "write-rate",
+        "Limit the number of write transactions sent to the pool per minute. \
+        Writes of ledger commands, `ledger batch commit` and `queue flush` wait for their turn, \
+        so large provisioning runs do not overload validator nodes."
+    )
+    .add_required_param(
+        "max_per_minute",
+        "Maximum number of write transactions per minute. 0 removes the limit"
+    )
+    .add_example("set write-rate max_per_minute=30")
+    .add_example("set write-rate max_per_minute=0")
+    .finalize());
+
+    fn execute(ctx: &CommandContext, params: &CommandParams) -> CommandResult {
+        trace!("execute >> ctx: {:?}, params: {:?}", ctx, params);
+
+        let max_per_minute = ParamParser::get_number_param::<u64>("max_per_minute", params)?;
+
+        if max_per_minute == 0 {
+            ctx.set_write_rate(None);
+            println_succ!(ctx, "Write rate limit has been removed");
+        } else {
+            ctx.set_write_rate(Some(max_per_minute));
+            println_succ!(
+                ctx,
+                "Write transactions will be sent at most {} per minute",
+                max_per_minute
+            );
+        }
+        let res = Ok(());
+
+        trace!("execute << {:?}", res);
+        res
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::{
+        commands::{setup, tear_down},
+        ledger::throttle_write,
+    };
+    use std::time::Instant;
+
+    fn set_write_rate(ctx: &CommandContext, max_per_minute: &str) {
+        let cmd = write_rate_command::new();
+        let mut params = CommandParams::new();
+        params.insert("max_per_minute", max_per_minute.to_string());
+        cmd.execute(ctx, &params).unwrap();
+    }
+
+    mod write_rate {
+        use super::*;
+
+        #[test]
+        pub fn write_rate_works() {
+            let ctx = setup();
+            set_write_rate(&ctx, "600");
+            assert_eq!(ctx.get_write_rate(), Some(600));
+
+            // 600 writes per minute leave 100 ms between writes
+            let started = Instant::now();
+            throttle_write(&ctx);
+            throttle_write(&ctx);
+            throttle_write(&ctx);
+            assert!(started.elapsed().as_millis() >= 200);
+
+            set_write_rate(&ctx, "0");
+            assert_eq!(ctx.get_write_rate(), None);
+            tear_down();
+        }
+
+        #[test]
+        pub fn write_rate_works_for_invalid_value() {
+            let ctx = setup();
+            {
+                let cmd = write_rate_command::new();
+                let mut params = CommandParams::new();
+                params.insert("max_per_minute", "fast".to_string());
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            assert_eq!(ctx.get_write_rate(), None);
+            tear_down();
+        }
+    }
+}
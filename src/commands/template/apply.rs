@@ -0,0 +1,119 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::{
+    command_executor::{Command, CommandContext, CommandMetadata, CommandParams},
+    params_parser::ParamParser,
+    tools::template::TransactionTemplate,
+};
+
+use serde_json::Map as JsonMap;
+
+pub mod apply_command {
+    use super::*;
+
+    command!(CommandMetadata::build(
+        "apply",
+        "Build a transaction from the template and store it into CLI context."
+    )
+    .add_main_param("name", "The name of the template")
+    .add_optional_param(
+        "values",
+        "JSON object with the values of the template params. Other operation fields can be overridden as well"
+    )
+    .add_example(r#"template apply monthly-taa values={"text":"New agreement","version":"2.0"}"#)
+    .add_example(r#"template apply node-rotation values={"alias":"Node5","services":[]}"#)
+    .finalize());
+
+    fn execute(ctx: &CommandContext, params: &CommandParams) -> Result<(), ()> {
+        trace!("execute >> ctx {:?} params {:?}", ctx, params);
+
+        let store = ctx.ensure_opened_wallet()?;
+
+        let name = ParamParser::get_str_param(ctx, "name", params)?;
+        let values = match ParamParser::get_opt_object_param(ctx, "values", params)? {
+            Some(serde_json::Value::Object(values)) => values,
+            Some(_) => {
+                println_err!(ctx, "Invalid \"values\" parameter: JSON object expected");
+                return Err(());
+            }
+            None => JsonMap::new(),
+        };
+        let identifier = ctx.get_active_did()?.map(|did| did.to_string());
+
+        let transaction = TransactionTemplate::get(&store, name)
+            .and_then(|template| template.apply(&values, identifier.as_deref()))
+            .map_err(|err| err.print(ctx, None))?;
+
+        println!("Transaction: {}", transaction);
+        ctx.set_context_transaction(None, Some(transaction));
+        println_succ!(
+            ctx,
+            "Transaction has been built from template \"{}\" and stored into CLI context",
+            name
+        );
+        println!("Use `ledger custom context sign=true` to send it or `ledger sign-multi` to collect signatures.");
+
+        trace!("execute <<");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::{
+        commands::{setup_with_wallet, tear_down_with_wallet},
+        template::tests::{save_template, TEMPLATE},
+    };
+
+    const DEST: &str = "VsKV7grR1BUE29mG2Fm2kX";
+
+    mod apply {
+        use super::*;
+
+        #[test]
+        pub fn apply_works() {
+            let ctx = setup_with_wallet();
+            save_template(&ctx);
+            {
+                let cmd = apply_command::new();
+                let mut params = CommandParams::new();
+                params.insert("name", TEMPLATE.to_string());
+                params.insert("values", format!(r#"{{"dest":"{}"}}"#, DEST));
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            let transaction = ctx.get_context_transaction(None).unwrap();
+            let transaction = serde_json::from_str::<serde_json::Value>(&transaction).unwrap();
+            assert_eq!(transaction["operation"]["dest"], DEST);
+            assert!(transaction["reqId"].is_u64());
+            tear_down_with_wallet(&ctx);
+        }
+
+        #[test]
+        pub fn apply_works_for_missing_param() {
+            let ctx = setup_with_wallet();
+            save_template(&ctx);
+            {
+                let cmd = apply_command::new();
+                let mut params = CommandParams::new();
+                params.insert("name", TEMPLATE.to_string());
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            tear_down_with_wallet(&ctx);
+        }
+
+        #[test]
+        pub fn apply_works_for_unknown_template() {
+            let ctx = setup_with_wallet();
+            {
+                let cmd = apply_command::new();
+                let mut params = CommandParams::new();
+                params.insert("name", "unknown".to_string());
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            tear_down_with_wallet(&ctx);
+        }
+    }
+}
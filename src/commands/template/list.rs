@@ -0,0 +1,82 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::{
+    command_executor::{Command, CommandContext, CommandMetadata, CommandParams},
+    tools::{ledger::LedgerHelpers, template::TransactionTemplate},
+    utils::{table::print_list_table, time::timestamp_to_datetime},
+};
+
+use serde_json::Value as JsonValue;
+
+pub mod list_command {
+    use super::*;
+
+    command!(CommandMetadata::build(
+        "list",
+        "List transaction templates stored in the opened wallet."
+    )
+    .finalize());
+
+    fn execute(ctx: &CommandContext, params: &CommandParams) -> Result<(), ()> {
+        trace!("execute >> ctx {:?} params {:?}", ctx, params);
+
+        let store = ctx.ensure_opened_wallet()?;
+
+        let mut templates =
+            TransactionTemplate::list(&store).map_err(|err| err.print(ctx, None))?;
+        templates.sort_by(|template, other| template.name.cmp(&other.name));
+
+        let rows = templates
+            .iter()
+            .map(|template| {
+                json!({
+                    "name": template.name,
+                    "type": LedgerHelpers::get_txn_title(&template.request["operation"]["type"]),
+                    "params": template.params.join(","),
+                    "created": timestamp_to_datetime(ctx, template.created),
+                })
+            })
+            .collect::<Vec<JsonValue>>();
+        print_list_table(
+            ctx,
+            &rows,
+            &[
+                ("name", "Name"),
+                ("type", "Type"),
+                ("params", "Params"),
+                ("created", "Created"),
+            ],
+            "There are no templates",
+        );
+
+        trace!("execute <<");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::{
+        commands::{setup_with_wallet, tear_down_with_wallet},
+        template::tests::save_template,
+    };
+
+    mod list {
+        use super::*;
+
+        #[test]
+        pub fn list_works() {
+            let ctx = setup_with_wallet();
+            save_template(&ctx);
+            {
+                let cmd = list_command::new();
+                let params = CommandParams::new();
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            tear_down_with_wallet(&ctx);
+        }
+    }
+}
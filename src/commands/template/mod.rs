@@ -0,0 +1,40 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::command_executor::{CommandGroup, CommandGroupMetadata};
+
+pub mod apply;
+pub mod list;
+pub mod save;
+
+pub use self::{apply::*, list::*, save::*};
+
+pub mod group {
+    use super::*;
+
+    command_group!(CommandGroupMetadata::new(
+        "template",
+        "Transaction template management commands"
+    ));
+}
+
+#[cfg(test)]
+pub mod tests {
+    use crate::{
+        command_executor::{CommandContext, CommandParams},
+        commands::template::save_command,
+        ledger::tests::TRANSACTION,
+    };
+
+    pub const TEMPLATE: &str = "get-nym";
+
+    pub fn save_template(ctx: &CommandContext) {
+        ctx.set_context_transaction(None, Some(TRANSACTION.to_string()));
+        let cmd = save_command::new();
+        let mut params = CommandParams::new();
+        params.insert("name", TEMPLATE.to_string());
+        params.insert("params", "dest".to_string());
+        cmd.execute(&ctx, &params).unwrap();
+    }
+}
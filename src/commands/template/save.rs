@@ -0,0 +1,107 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::{
+    command_executor::{Command, CommandContext, CommandMetadata, CommandParams},
+    params_parser::ParamParser,
+    tools::template::TransactionTemplate,
+    utils::file::read_file,
+};
+
+pub mod save_command {
+    use super::*;
+
+    const LAST: &str = "last";
+
+    command!(CommandMetadata::build(
+        "save",
+        "Save a transaction as a reusable template in the opened wallet."
+    )
+    .add_main_param("name", "The name of the template")
+    .add_optional_param(
+        "from",
+        "`last` to use the transaction stored in CLI context (default) or the path to file containing a transaction"
+    )
+    .add_optional_param(
+        "params",
+        "Comma-separated names of the operation fields which must be given on every apply. Their saved values are cleared"
+    )
+    .add_example("template save monthly-taa")
+    .add_example("template save node-rotation params=alias,dest,blskey,blskey_pop")
+    .add_example("template save monthly-taa from=/home/taa_transaction.txt params=text,version")
+    .finalize());
+
+    fn execute(ctx: &CommandContext, params: &CommandParams) -> Result<(), ()> {
+        trace!("execute >> ctx {:?} params {:?}", ctx, params);
+
+        let store = ctx.ensure_opened_wallet()?;
+
+        let name = ParamParser::get_str_param(ctx, "name", params)?;
+        let from = ParamParser::get_opt_str_param(ctx, "from", params)?.unwrap_or(LAST);
+        let template_params =
+            ParamParser::get_opt_str_array_param("params", params)?.unwrap_or_default();
+
+        let transaction = match from {
+            LAST => ctx.ensure_context_transaction(None)?,
+            file => read_file(file).map_err(|err| println_err!(ctx, "{}", err))?,
+        };
+
+        TransactionTemplate::from_request(name, &transaction, &template_params)
+            .and_then(|template| template.store(&store))
+            .map_err(|err| err.print(ctx, None))?;
+
+        println_succ!(ctx, "Template \"{}\" has been saved", name);
+
+        trace!("execute <<");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::{
+        commands::{setup_with_wallet, tear_down_with_wallet},
+        ledger::tests::TRANSACTION,
+        template::tests::save_template,
+    };
+
+    mod save {
+        use super::*;
+
+        #[test]
+        pub fn save_works() {
+            let ctx = setup_with_wallet();
+            save_template(&ctx);
+            tear_down_with_wallet(&ctx);
+        }
+
+        #[test]
+        pub fn save_works_for_unknown_param() {
+            let ctx = setup_with_wallet();
+            ctx.set_context_transaction(None, Some(TRANSACTION.to_string()));
+            {
+                let cmd = save_command::new();
+                let mut params = CommandParams::new();
+                params.insert("name", "template".to_string());
+                params.insert("params", "alias".to_string());
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            tear_down_with_wallet(&ctx);
+        }
+
+        #[test]
+        pub fn save_works_for_duplicate() {
+            let ctx = setup_with_wallet();
+            save_template(&ctx);
+            {
+                let cmd = save_command::new();
+                let mut params = CommandParams::new();
+                params.insert("name", crate::template::tests::TEMPLATE.to_string());
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            tear_down_with_wallet(&ctx);
+        }
+    }
+}
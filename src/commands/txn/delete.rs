@@ -0,0 +1,77 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::{
+    command_executor::{Command, CommandContext, CommandMetadata, CommandParams},
+    params_parser::ParamParser,
+};
+
+pub mod delete_command {
+    use super::*;
+
+    command!(
+        CommandMetadata::build("delete", "Remove transaction stored into CLI context.")
+            .add_main_param("name", "The name of the transaction")
+            .add_example("txn delete schema")
+            .finalize()
+    );
+
+    fn execute(ctx: &CommandContext, params: &CommandParams) -> Result<(), ()> {
+        trace!("execute >> ctx {:?} params {:?}", ctx, params);
+
+        let name = ParamParser::get_str_param(ctx, "name", params)?;
+
+        ctx.ensure_context_transaction(Some(name))?;
+        ctx.set_context_transaction(Some(name), None);
+
+        println_succ!(
+            ctx,
+            "Transaction \"{}\" has been deleted from context",
+            name
+        );
+
+        trace!("execute <<");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::{
+        commands::{setup, tear_down},
+        txn::tests::{store_transactions, NAME},
+    };
+
+    mod delete {
+        use super::*;
+
+        #[test]
+        pub fn delete_works() {
+            let ctx = setup();
+            store_transactions(&ctx);
+            {
+                let cmd = delete_command::new();
+                let mut params = CommandParams::new();
+                params.insert("name", NAME.to_string());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            assert!(ctx.get_context_transaction(Some(NAME)).is_none());
+            assert!(ctx.get_context_transaction(None).is_some());
+            tear_down();
+        }
+
+        #[test]
+        pub fn delete_works_for_unknown_name() {
+            let ctx = setup();
+            {
+                let cmd = delete_command::new();
+                let mut params = CommandParams::new();
+                params.insert("name", NAME.to_string());
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            tear_down();
+        }
+    }
+}
@@ -0,0 +1,91 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::{
+    command_executor::{Command, CommandContext, CommandMetadata, CommandParams},
+    tools::ledger::LedgerHelpers,
+    utils::table::print_list_table,
+};
+
+use serde_json::Value as JsonValue;
+
+pub mod list_command {
+    use super::*;
+
+    command!(
+        CommandMetadata::build("list", "List transactions stored into CLI context.")
+            .add_example("txn list")
+            .finalize()
+    );
+
+    fn execute(ctx: &CommandContext, params: &CommandParams) -> Result<(), ()> {
+        trace!("execute >> ctx {:?} params {:?}", ctx, params);
+
+        let rows = ctx
+            .get_context_transactions()
+            .into_iter()
+            .map(|(name, transaction)| {
+                let transaction =
+                    serde_json::from_str::<JsonValue>(&transaction).unwrap_or(JsonValue::Null);
+                json!({
+                    "name": name,
+                    "type": LedgerHelpers::get_txn_title(&transaction["operation"]["type"]),
+                    "identifier": transaction["identifier"],
+                    "reqId": transaction["reqId"],
+                })
+            })
+            .collect::<Vec<JsonValue>>();
+
+        print_list_table(
+            ctx,
+            &rows,
+            &[
+                ("name", "Name"),
+                ("type", "Type"),
+                ("identifier", "Submitter"),
+                ("reqId", "Request ID"),
+            ],
+            "There are no transactions stored into context",
+        );
+
+        trace!("execute <<");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::{
+        commands::{setup, tear_down},
+        txn::tests::store_transactions,
+    };
+
+    mod list {
+        use super::*;
+
+        #[test]
+        pub fn list_works() {
+            let ctx = setup();
+            store_transactions(&ctx);
+            {
+                let cmd = list_command::new();
+                let params = CommandParams::new();
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            tear_down();
+        }
+
+        #[test]
+        pub fn list_works_for_empty_context() {
+            let ctx = setup();
+            {
+                let cmd = list_command::new();
+                let params = CommandParams::new();
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            tear_down();
+        }
+    }
+}
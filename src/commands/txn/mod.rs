@@ -0,0 +1,65 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::command_executor::{CommandContext, CommandGroup, CommandGroupMetadata};
+
+pub mod delete;
+pub mod list;
+pub mod show;
+
+pub use self::{delete::*, list::*, show::*};
+
+pub mod group {
+    use super::*;
+
+    command_group!(CommandGroupMetadata::new(
+        "txn",
+        "Transactions stored into CLI context by name"
+    ));
+}
+
+#[cfg(test)]
+pub mod tests {
+    use crate::{
+        commands::{setup, tear_down, DEFAULT_TRANSACTION_NAME},
+        ledger::tests::TRANSACTION,
+    };
+
+    use super::*;
+
+    pub const NAME: &str = "schema";
+
+    pub fn store_transactions(ctx: &CommandContext) {
+        ctx.set_context_transaction(None, Some(TRANSACTION.to_string()));
+        ctx.set_context_transaction(Some(NAME), Some(TRANSACTION.to_string()));
+    }
+
+    mod context {
+        use super::*;
+
+        #[test]
+        pub fn context_transactions_work() {
+            let ctx = setup();
+            store_transactions(&ctx);
+            assert_eq!(
+                ctx.get_context_transaction(Some(DEFAULT_TRANSACTION_NAME)),
+                Some(TRANSACTION.to_string())
+            );
+            assert_eq!(
+                ctx.get_context_transaction(Some(NAME)),
+                Some(TRANSACTION.to_string())
+            );
+            assert_eq!(ctx.get_context_transactions().len(), 2);
+
+            ctx.set_context_transaction(None, None);
+            assert_eq!(ctx.get_context_transaction(None), None);
+            assert!(ctx.ensure_context_transaction(None).is_err());
+            ctx.ensure_context_transaction(Some(NAME)).unwrap();
+
+            ctx.set_context_transaction(Some(NAME), None);
+            assert!(ctx.get_context_transactions().is_empty());
+            tear_down();
+        }
+    }
+}
@@ -0,0 +1,79 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::{
+    command_executor::{Command, CommandContext, CommandMetadata, CommandParams},
+    params_parser::ParamParser,
+    utils::pager,
+};
+
+use serde_json::Value as JsonValue;
+
+pub mod show_command {
+    use super::*;
+
+    command!(
+        CommandMetadata::build("show", "Show transaction stored into CLI context.")
+            .add_main_param("name", "The name of the transaction")
+            .add_example("txn show schema")
+            .add_example("txn show default")
+            .finalize()
+    );
+
+    fn execute(ctx: &CommandContext, params: &CommandParams) -> Result<(), ()> {
+        trace!("execute >> ctx {:?} params {:?}", ctx, params);
+
+        let name = ParamParser::get_str_param(ctx, "name", params)?;
+
+        let transaction = ctx.ensure_context_transaction(Some(name))?;
+
+        let output = serde_json::from_str::<JsonValue>(&transaction)
+            .ok()
+            .and_then(|json| serde_json::to_string_pretty(&json).ok())
+            .unwrap_or(transaction);
+        pager::print(&output);
+
+        trace!("execute <<");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::{
+        commands::{setup, tear_down},
+        txn::tests::{store_transactions, NAME},
+    };
+
+    mod show {
+        use super::*;
+
+        #[test]
+        pub fn show_works() {
+            let ctx = setup();
+            store_transactions(&ctx);
+            {
+                let cmd = show_command::new();
+                let mut params = CommandParams::new();
+                params.insert("name", NAME.to_string());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            tear_down();
+        }
+
+        #[test]
+        pub fn show_works_for_unknown_name() {
+            let ctx = setup();
+            store_transactions(&ctx);
+            {
+                let cmd = show_command::new();
+                let mut params = CommandParams::new();
+                params.insert("name", "unknown".to_string());
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            tear_down();
+        }
+    }
+}
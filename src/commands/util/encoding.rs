@@ -0,0 +1,201 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::{
+    command_executor::{Command, CommandContext, CommandMetadata, CommandParams},
+    params_parser::ParamParser,
+    utils::{encoding::Encoding, renderer::print_value},
+};
+
+use std::fs;
+
+pub mod encode_command {
+    use super::*;
+
+    command!(CommandMetadata::build("encode", "Encode a value or file content.")
+        .add_optional_param("value", "Text to encode (UTF-8)")
+        .add_optional_param("file", "Path to file with bytes to encode")
+        .add_required_param(
+            "encoding",
+            "Encoding to use: base58, base58check, base64, base64url, hex or multibase (base58btc)"
+        )
+        .add_example("util encode value=000000000000000000000000Trustee1 encoding=hex")
+        .add_example("util encode file=/home/key.bin encoding=base58")
+        .finalize());
+
+    fn execute(ctx: &CommandContext, params: &CommandParams) -> Result<(), ()> {
+        trace!("execute >> ctx {:?} params {:?}", ctx, secret!(params));
+
+        let encoding = get_encoding_param(ctx, "encoding", params)?;
+
+        let bytes = match (
+            ParamParser::get_opt_str_param(ctx, "value", params)?,
+            ParamParser::get_opt_str_param(ctx, "file", params)?,
+        ) {
+            (Some(value), None) => value.as_bytes().to_vec(),
+            (None, Some(file)) => fs::read(file)
+                .map_err(|err| println_err!(ctx, "Can't read the file \"{}\": {}", file, err))?,
+            _ => {
+                println_err!(ctx, "Either `value` or `file` must be specified");
+                return Err(());
+            }
+        };
+
+        print_value(ctx, &json!(encoding.encode(&bytes)));
+
+        trace!("execute <<");
+        Ok(())
+    }
+}
+
+pub mod decode_command {
+    use super::*;
+
+    command!(CommandMetadata::build("decode", "Decode a value or file content.")
+        .add_optional_param("value", "Encoded value")
+        .add_optional_param("file", "Path to file containing encoded value")
+        .add_required_param(
+            "encoding",
+            "Encoding of the value: base58, base58check, base64, base64url, hex or multibase"
+        )
+        .add_optional_param(
+            "to",
+            "Encoding to convert the decoded bytes to. \
+            Decoded bytes are printed as text if they are valid UTF-8 and as hex otherwise by default"
+        )
+        .add_optional_param("output", "Path to file to write the decoded bytes to")
+        .add_example("util decode value=GJ1SzoWzavQYfNL9XkaJdrQejfztN4XqdsiV4ct3LXKL encoding=base58 to=hex")
+        .add_example("util decode value=z6MkukGVb3mRvTu1msArDKY9UwxeZFGjmwnCKtdQttr4Fk6i encoding=multibase to=base58")
+        .add_example("util decode file=/home/key.b64 encoding=base64 output=/home/key.bin")
+        .finalize());
+
+    fn execute(ctx: &CommandContext, params: &CommandParams) -> Result<(), ()> {
+        trace!("execute >> ctx {:?} params {:?}", ctx, secret!(params));
+
+        let encoding = get_encoding_param(ctx, "encoding", params)?;
+        let to = match params.get("to") {
+            Some(_) => Some(get_encoding_param(ctx, "to", params)?),
+            None => None,
+        };
+        let output = ParamParser::get_opt_str_param(ctx, "output", params)?;
+
+        let value = match (
+            ParamParser::get_opt_str_param(ctx, "value", params)?,
+            ParamParser::get_opt_str_param(ctx, "file", params)?,
+        ) {
+            (Some(value), None) => value.to_string(),
+            (None, Some(file)) => fs::read_to_string(file)
+                .map_err(|err| println_err!(ctx, "Can't read the file \"{}\": {}", file, err))?,
+            _ => {
+                println_err!(ctx, "Either `value` or `file` must be specified");
+                return Err(());
+            }
+        };
+
+        let bytes = encoding
+            .decode(&value)
+            .map_err(|err| println_err!(ctx, "{}", err))?;
+
+        if let Some(output) = output {
+            fs::write(output, &bytes)
+                .map_err(|err| println_err!(ctx, "Can't write the file \"{}\": {}", output, err))?;
+            println_succ!(
+                ctx,
+                "{} decoded bytes have been written into \"{}\" file",
+                bytes.len(),
+                output
+            );
+            return Ok(());
+        }
+
+        match (to, String::from_utf8(bytes.clone())) {
+            (Some(to), _) => print_value(ctx, &json!(to.encode(&bytes))),
+            (None, Ok(text)) if !text.contains(|c: char| c.is_control() && c != '\n') => {
+                print_value(ctx, &json!(text))
+            }
+            (None, _) => print_value(ctx, &json!(Encoding::Hex.encode(&bytes))),
+        }
+
+        trace!("execute <<");
+        Ok(())
+    }
+}
+
+fn get_encoding_param(
+    ctx: &CommandContext,
+    name: &str,
+    params: &CommandParams,
+) -> Result<Encoding, ()> {
+    let encoding = ParamParser::get_str_param(ctx, name, params)?;
+    Encoding::from_str(encoding).map_err(|err| println_err!(ctx, "{}", err))
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::commands::{setup, tear_down};
+
+    const VERKEY: &str = "GJ1SzoWzavQYfNL9XkaJdrQejfztN4XqdsiV4ct3LXKL";
+
+    mod encode {
+        use super::*;
+
+        #[test]
+        pub fn encode_works() {
+            let ctx = setup();
+            {
+                let cmd = encode_command::new();
+                let mut params = CommandParams::new();
+                params.insert("value", "indy".to_string());
+                params.insert("encoding", "base58check".to_string());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            tear_down();
+        }
+
+        #[test]
+        pub fn encode_works_for_unknown_encoding() {
+            let ctx = setup();
+            {
+                let cmd = encode_command::new();
+                let mut params = CommandParams::new();
+                params.insert("value", "indy".to_string());
+                params.insert("encoding", "base32".to_string());
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            tear_down();
+        }
+    }
+
+    mod decode {
+        use super::*;
+
+        #[test]
+        pub fn decode_works_for_conversion() {
+            let ctx = setup();
+            {
+                let cmd = decode_command::new();
+                let mut params = CommandParams::new();
+                params.insert("value", VERKEY.to_string());
+                params.insert("encoding", "base58".to_string());
+                params.insert("to", "multibase".to_string());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            tear_down();
+        }
+
+        #[test]
+        pub fn decode_works_for_invalid_value() {
+            let ctx = setup();
+            {
+                let cmd = decode_command::new();
+                let mut params = CommandParams::new();
+                params.insert("value", "0OIl".to_string());
+                params.insert("encoding", "base58".to_string());
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            tear_down();
+        }
+    }
+}
@@ -0,0 +1,16 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::command_executor::{CommandGroup, CommandGroupMetadata};
+
+pub mod encoding;
+pub mod verkey_from_seed;
+
+pub use self::{encoding::*, verkey_from_seed::*};
+
+pub mod group {
+    use super::*;
+
+    command_group!(CommandGroupMetadata::new("util", "Utility commands"));
+}
@@ -0,0 +1,92 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::{
+    command_executor::{Command, CommandContext, CommandMetadata, CommandParams},
+    params_parser::ParamParser,
+    tools::did::{key::Key, Did},
+    utils::table::print_table,
+};
+
+pub mod verkey_from_seed_command {
+    use super::*;
+
+    command!(CommandMetadata::build(
+        "verkey-from-seed",
+        "Show DID and verkey derived from the seed. Nothing is stored in the wallet."
+    )
+    .add_required_deferred_param("seed", "Seed for DID key-pair (UTF-8, base64 or hex)")
+    .add_example("util verkey-from-seed")
+    .add_example("util verkey-from-seed seed=00000000000000000000000000000My1")
+    .finalize());
+
+    fn execute(ctx: &CommandContext, params: &CommandParams) -> Result<(), ()> {
+        trace!("execute >> ctx {:?} params {:?}", ctx, secret!(params));
+
+        let seed = ParamParser::get_str_param(ctx, "seed", params)?;
+
+        let verkey = Key::verkey_from_seed(seed).map_err(|err| err.print(ctx, None))?;
+        let did = Did::from_verkey(&verkey).map_err(|err| err.print(ctx, None))?;
+        let abbreviated_verkey =
+            Did::abbreviate_verkey(&did, &verkey).map_err(|err| err.print(ctx, None))?;
+        let did_key = Did::to_did_key(&verkey).map_err(|err| err.print(ctx, None))?;
+
+        print_table(
+            ctx,
+            &json!({
+                "did": did,
+                "verkey": verkey,
+                "abbreviatedVerkey": abbreviated_verkey,
+                "didKey": did_key,
+            }),
+            &[
+                ("did", "Did"),
+                ("verkey", "Verkey"),
+                ("abbreviatedVerkey", "Abbreviated Verkey"),
+                ("didKey", "did:key"),
+            ],
+        );
+
+        trace!("execute <<");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::commands::{setup, tear_down};
+
+    mod verkey_from_seed {
+        use super::*;
+        use crate::did::tests::{DID_MY1, SEED_MY1, VERKEY_MY1};
+
+        #[test]
+        pub fn verkey_from_seed_works() {
+            let ctx = setup();
+            {
+                let cmd = verkey_from_seed_command::new();
+                let mut params = CommandParams::new();
+                params.insert("seed", SEED_MY1.to_string());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            let verkey = Key::verkey_from_seed(SEED_MY1).unwrap();
+            assert_eq!(verkey, VERKEY_MY1);
+            assert_eq!(Did::from_verkey(&verkey).unwrap(), DID_MY1);
+            tear_down();
+        }
+
+        #[test]
+        pub fn verkey_from_seed_works_for_invalid_seed() {
+            let ctx = setup();
+            {
+                let cmd = verkey_from_seed_command::new();
+                let mut params = CommandParams::new();
+                params.insert("seed", "short".to_string());
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            tear_down();
+        }
+    }
+}
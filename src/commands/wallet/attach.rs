@@ -26,10 +26,10 @@ pub mod attach_command {
     fn execute(ctx: &CommandContext, params: &CommandParams) -> Result<(), ()> {
         trace!("execute >> ctx {:?} params {:?}", ctx, secret!(params));
 
-        let id = ParamParser::get_str_param("name", params)?;
+        let id = ParamParser::get_str_param(ctx, "name", params)?;
         let storage_type =
-            ParamParser::get_opt_str_param("storage_type", params)?.unwrap_or("default");
-        let storage_config = ParamParser::get_opt_object_param("storage_config", params)?;
+            ParamParser::get_opt_str_param(ctx, "storage_type", params)?.unwrap_or("default");
+        let storage_config = ParamParser::get_opt_object_param(ctx, "storage_config", params)?;
 
         let config = WalletConfig {
             id: id.to_string(),
@@ -38,15 +38,15 @@ pub mod attach_command {
         };
 
         if config.exists() {
-            println_err!("Wallet \"{}\" is already attached to CLI", id);
+            println_err!(ctx, "Wallet \"{}\" is already attached to CLI", id);
             return Err(());
         }
 
-        config
-            .store()
-            .map_err(|err| println_err!("Cannot store wallet \"{}\" config file: {:?}", id, err))?;
+        config.store().map_err(|err| {
+            println_err!(ctx, "Cannot store wallet \"{}\" config file: {:?}", id, err)
+        })?;
 
-        println_succ!("Wallet \"{}\" has been attached", id);
+        println_succ!(ctx, "Wallet \"{}\" has been attached", id);
 
         trace!("execute << ");
         Ok(())
@@ -18,7 +18,7 @@ pub mod close_command {
         if let Some(wallet) = ctx.take_opened_wallet()? {
             close_wallet(ctx, wallet)?;
         } else {
-            println_err!("There is no opened wallet now");
+            println_err!(ctx, "There is no opened wallet now");
             return Err(());
         }
 
@@ -34,9 +34,9 @@ pub fn close_wallet(ctx: &CommandContext, wallet: Wallet) -> Result<(), ()> {
         .map(|_| {
             ctx.reset_opened_wallet();
             ctx.reset_active_did();
-            println_succ!("Wallet \"{}\" has been closed", name);
+            println_succ!(ctx, "Wallet \"{}\" has been closed", name);
         })
-        .map_err(|err| println_err!("{}", err.message(Some(&name))))
+        .map_err(|err| err.print(ctx, Some(&name)))
 }
 
 #[cfg(test)]
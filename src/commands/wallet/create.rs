@@ -19,11 +19,12 @@ pub mod create_command {
                                     argon2m - derive secured wallet key (used by default)
                                     argon2i - derive secured wallet key (less secured but faster)
                                     raw - raw wallet key provided (skip derivation)")
-                .add_optional_param("storage_type", "Type of the wallet storage.")
+                .add_optional_param("storage_type", "Type of the wallet storage. `memory` keeps the wallet in memory only: it vanishes on exit")
                 .add_optional_param("storage_config", "The list of key:value pairs defined by storage type.")
                 .add_optional_param("storage_credentials", "The list of key:value pairs defined by storage type.")
                 .add_example("wallet create wallet1 key")
                 .add_example("wallet create wallet1 key storage_type=default")
+                .add_example("wallet create tmp key storage_type=memory")
                 .add_example(r#"wallet create wallet1 key storage_type=default storage_config={"key1":"value1","key2":"value2"}"#)
                 .finalize()
     );
@@ -31,14 +32,15 @@ pub mod create_command {
     fn execute(ctx: &CommandContext, params: &CommandParams) -> Result<(), ()> {
         trace!("execute >> ctx {:?} params {:?}", ctx, secret!(params));
 
-        let id = ParamParser::get_str_param("name", params)?;
-        let key = ParamParser::get_str_param("key", params)?;
+        let id = ParamParser::get_str_param(ctx, "name", params)?;
+        let key = ParamParser::get_str_param(ctx, "key", params)?;
         let key_derivation_method =
-            ParamParser::get_opt_str_param("key_derivation_method", params)?;
+            ParamParser::get_opt_str_param(ctx, "key_derivation_method", params)?;
         let storage_type =
-            ParamParser::get_opt_str_param("storage_type", params)?.unwrap_or("default");
-        let storage_config = ParamParser::get_opt_object_param("storage_config", params)?;
-        let storage_credentials = ParamParser::get_opt_object_param("storage_credentials", params)?;
+            ParamParser::get_opt_str_param(ctx, "storage_type", params)?.unwrap_or("default");
+        let storage_config = ParamParser::get_opt_object_param(ctx, "storage_config", params)?;
+        let storage_credentials =
+            ParamParser::get_opt_object_param(ctx, "storage_credentials", params)?;
 
         let config = WalletConfig {
             id: id.to_string(),
@@ -48,20 +50,29 @@ pub mod create_command {
         let credentials = Credentials {
             key: key.to_string(),
             key_derivation_method: key_derivation_method.map(String::from),
+            rekey: None,
+            rekey_derivation_method: None,
             storage_credentials,
-            ..Credentials::default()
         };
 
         trace!("Wallet::create_wallet try: config {:?}", config);
 
-        Wallet::create(&config, &credentials)
-            .map_err(|err| println_err!("{}", err.message(Some(&id))))?;
+        Wallet::create(&config, &credentials, ctx.get_crypto_policy())
+            .map_err(|err| err.print(ctx, Some(&id)))?;
 
-        config
-            .store()
-            .map_err(|err| println_err!("Cannot store wallet \"{}\" config file: {:?}", id, err))?;
+        config.store().map_err(|err| {
+            println_err!(ctx, "Cannot store wallet \"{}\" config file: {:?}", id, err)
+        })?;
 
-        println_succ!("Wallet \"{}\" has been created", id);
+        if config.is_memory() {
+            println_succ!(
+                ctx,
+                "Wallet \"{}\" has been created in memory. It will be lost on exit",
+                id
+            );
+        } else {
+            println_succ!(ctx, "Wallet \"{}\" has been created", id);
+        }
 
         trace!("execute << {:?}", ());
         Ok(())
@@ -75,9 +86,15 @@ pub mod tests {
 
     mod create {
         use super::*;
-        use crate::wallet::{
-            delete_command,
-            tests::{delete_wallet, WALLET, WALLET_KEY, WALLET_KEY_RAW},
+        use crate::{
+            did::tests::{get_did_info, new_did, DID_MY1, SEED_MY1},
+            utils::environment::EnvironmentUtils,
+            wallet::{
+                delete_command,
+                tests::{
+                    close_wallet, delete_wallet, open_wallet, WALLET, WALLET_KEY, WALLET_KEY_RAW,
+                },
+            },
         };
 
         #[test]
@@ -207,6 +224,38 @@ pub mod tests {
             tear_down();
         }
 
+        #[test]
+        pub fn create_works_for_memory_storage() {
+            let ctx = setup();
+            {
+                let cmd = create_command::new();
+                let mut params = CommandParams::new();
+                params.insert("name", WALLET.to_string());
+                params.insert("key", WALLET_KEY_RAW.to_string());
+                params.insert("key_derivation_method", "raw".to_string());
+                params.insert("storage_type", "memory".to_string());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            assert!(!EnvironmentUtils::wallet_path(WALLET).exists());
+
+            let wallets = Wallet::list();
+            assert_eq!(1, wallets.len());
+            assert_eq!(wallets[0]["storage_type"].as_str().unwrap(), "memory");
+
+            // records survive closing of the wallet
+            open_wallet(&ctx);
+            new_did(&ctx, SEED_MY1);
+            close_wallet(&ctx);
+            open_wallet(&ctx);
+            get_did_info(&ctx, DID_MY1);
+            close_wallet(&ctx);
+            assert!(!EnvironmentUtils::wallet_path(WALLET).exists());
+
+            delete_wallet(&ctx);
+            assert!(Wallet::list().is_empty());
+            tear_down();
+        }
+
         #[test]
         pub fn create_works_for_wrong_key_derivation_method() {
             let ctx = setup();
@@ -30,34 +30,35 @@ pub mod delete_command {
     fn execute(ctx: &CommandContext, params: &CommandParams) -> Result<(), ()> {
         trace!("execute >> ctx: {:?} params {:?}", ctx, secret!(params));
 
-        let id = ParamParser::get_str_param("name", params)?;
-        let key = ParamParser::get_str_param("key", params)?;
+        let id = ParamParser::get_str_param(ctx, "name", params)?;
+        let key = ParamParser::get_str_param(ctx, "key", params)?;
         let key_derivation_method =
-            ParamParser::get_opt_str_param("key_derivation_method", params)?;
-        let storage_credentials = ParamParser::get_opt_object_param("storage_credentials", params)?;
+            ParamParser::get_opt_str_param(ctx, "key_derivation_method", params)?;
+        let storage_credentials =
+            ParamParser::get_opt_object_param(ctx, "storage_credentials", params)?;
 
         let config = WalletConfig::read(id)
-            .map_err(|_| println_err!("Wallet \"{}\" isn't attached to CLI", id))?;
+            .map_err(|_| println_err!(ctx, "Wallet \"{}\" isn't attached to CLI", id))?;
 
         let credentials = Credentials {
             key: key.to_string(),
             key_derivation_method: key_derivation_method.map(String::from),
+            rekey: None,
+            rekey_derivation_method: None,
             storage_credentials,
-            ..Credentials::default()
         };
 
         if let Some(wallet) = ctx.take_opened_wallet()? {
             close_wallet(ctx, wallet)?;
         }
 
-        Wallet::delete(&config, &credentials)
-            .map_err(|err| println_err!("{}", err.message(Some(id))))?;
+        Wallet::delete(&config, &credentials).map_err(|err| err.print(ctx, Some(id)))?;
 
         config
             .delete()
-            .map_err(|err| println_err!("Cannot delete \"{}\" config file: {:?}", id, err))?;
+            .map_err(|err| println_err!(ctx, "Cannot delete \"{}\" config file: {:?}", id, err))?;
 
-        println_succ!("Wallet \"{}\" has been deleted", id);
+        println_succ!(ctx, "Wallet \"{}\" has been deleted", id);
 
         trace!("execute <<");
         Ok(())
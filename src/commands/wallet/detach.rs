@@ -27,23 +27,23 @@ pub mod detach_command {
     fn execute(ctx: &CommandContext, params: &CommandParams) -> Result<(), ()> {
         trace!("execute >> ctx: {:?} params {:?}", ctx, secret!(params));
 
-        let id = ParamParser::get_str_param("name", params)?;
+        let id = ParamParser::get_str_param(ctx, "name", params)?;
 
         let config = WalletConfig::read(id)
-            .map_err(|_| println_err!("Wallet \"{}\" isn't attached to CLI", id))?;
+            .map_err(|_| println_err!(ctx, "Wallet \"{}\" isn't attached to CLI", id))?;
 
         if let Some(wallet) = ctx.get_opened_wallet() {
             if wallet.name == id {
-                println_err!("Wallet \"{}\" is opened", id);
+                println_err!(ctx, "Wallet \"{}\" is opened", id);
                 return Err(());
             }
         }
 
         config
             .delete()
-            .map_err(|err| println_err!("Cannot delete \"{}\" config file: {:?}", id, err))?;
+            .map_err(|err| println_err!(ctx, "Cannot delete \"{}\" config file: {:?}", id, err))?;
 
-        println_succ!("Wallet \"{}\" has been detached", id);
+        println_succ!(ctx, "Wallet \"{}\" has been detached", id);
 
         trace!("execute << ");
         Ok(())
@@ -5,21 +5,42 @@
 use crate::{
     command_executor::{Command, CommandContext, CommandMetadata, CommandParams},
     params_parser::ParamParser,
+    tools::wallet::{resume::ResumeMarker, Wallet},
 };
 
+use super::{print_record_counts, warn_resume};
+
 pub mod export_command {
     use super::*;
-    use crate::tools::wallet::ExportConfig;
+    use crate::{
+        error::CliError,
+        tools::{
+            did::Did,
+            wallet::{
+                backup::BackupRecord, universal::UniversalWallet, ExportConfig,
+                DEFAULT_KEY_DERIVATION_METHOD,
+            },
+        },
+        utils::file::write_private_file,
+    };
+    use indy_utils::did::DidValue;
 
-    command!(CommandMetadata::build("export", "Export opened wallet to the file")
+    command!(CommandMetadata::build("export", "Export opened wallet to the file. Run the command again with the same path to resume an interrupted export")
                 .add_required_param("export_path", "Path to the export file")
-                .add_required_deferred_param("export_key", "Key or passphrase used for export wallet key derivation.
+                .add_optional_deferred_param("export_key", "Key or passphrase used for export wallet key derivation (required for askar format).
                                                Look to key_derivation_method param for information about supported key derivation methods.")
                 .add_optional_param("export_key_derivation_method", "Algorithm to use for export key derivation. One of:
                                     argon2m - derive secured export key (used by default)
                                     argon2i - derive secured export key (less secured but faster)
                                     raw - raw export key provided (skip derivation)")
+                .add_optional_param("format", "Format of the export file. One of:
+                                    askar - encrypted wallet backup which can be imported by `wallet import` (used by default)
+                                    universal - DIDs and keys as Universal Wallet 2020 JSON-LD for non-Indy wallets")
+                .add_optional_param("include_private_keys", "Write the private keys of DIDs into the universal format file (False by default)")
+                .add_optional_param("dids", "DIDs split by comma to export with their keys and aliases only. Skip to export the whole wallet")
                 .add_example("wallet export export_path=/home/indy/export_wallet export_key")
+                .add_example("wallet export export_path=/home/indy/steward_wallet export_key dids=VsKV7grR1BUE29mG2Fm2kX")
+                .add_example("wallet export export_path=/home/indy/wallet.jsonld format=universal include_private_keys=true")
                 .finalize()
     );
 
@@ -28,15 +49,42 @@ pub mod export_command {
 
         let wallet = ctx.ensure_opened_wallet()?;
 
-        let export_path = ParamParser::get_str_param("export_path", params)?;
-        let export_key = ParamParser::get_str_param("export_key", params)?;
+        let export_path = ParamParser::get_str_param(ctx, "export_path", params)?;
+        let format = ParamParser::get_opt_str_param(ctx, "format", params)?.unwrap_or(FORMAT_ASKAR);
+        let dids = match ParamParser::get_opt_str_array_param("dids", params)? {
+            Some(dids) => Some(get_dids(ctx, &wallet, &dids)?),
+            None => None,
+        };
+
+        match format {
+            FORMAT_ASKAR => {}
+            FORMAT_UNIVERSAL => {
+                let include_private_keys =
+                    ParamParser::get_opt_bool_param(ctx, "include_private_keys", params)?
+                        .unwrap_or(false);
+                return export_universal(ctx, &wallet, export_path, include_private_keys, dids);
+            }
+            format => {
+                println_err!(
+                    ctx,
+                    "Unsupported export format \"{}\". One of: {}, {}",
+                    format,
+                    FORMAT_ASKAR,
+                    FORMAT_UNIVERSAL
+                );
+                return Err(());
+            }
+        }
+
+        let export_key = ParamParser::get_str_param(ctx, "export_key", params)?;
         let export_key_derivation_method =
-            ParamParser::get_opt_str_param("export_key_derivation_method", params)?;
+            ParamParser::get_opt_str_param(ctx, "export_key_derivation_method", params)?;
 
         let export_config = ExportConfig {
             path: export_path.to_string(),
             key: export_key.to_string(),
             key_derivation_method: export_key_derivation_method.map(String::from),
+            dids,
         };
 
         trace!(
@@ -45,11 +93,35 @@ pub mod export_command {
             export_path
         );
 
-        wallet
-            .export(&export_config)
-            .map_err(|err| println_err!("{}", err.message(Some(&wallet.name))))?;
+        warn_resume(
+            ctx,
+            ResumeMarker::path_for(std::path::Path::new(export_path)),
+            &wallet.name,
+            "export",
+        );
+
+        let counts = wallet
+            .export(&export_config, ctx.get_crypto_policy())
+            .map_err(|err| err.print(ctx, Some(&wallet.name)))?;
+
+        print_record_counts(ctx, &counts);
 
+        // a file holding a part of the DIDs cannot restore the wallet
+        if export_config.dids.is_none() {
+            let backup = BackupRecord::new(
+                export_path,
+                export_key_derivation_method.unwrap_or(DEFAULT_KEY_DERIVATION_METHOD),
+            );
+            if let Err(err) = backup.store(&wallet) {
+                println_warn!(
+                    ctx,
+                    "Backup location has not been recorded in the wallet: {}",
+                    err.message(Some(&wallet.name))
+                );
+            }
+        }
         println_succ!(
+            ctx,
             "Wallet \"{}\" has been exported to the file \"{}\"",
             wallet.name,
             export_path
@@ -58,6 +130,91 @@ pub mod export_command {
         trace!("execute <<");
         Ok(())
     }
+
+    const FORMAT_ASKAR: &str = "askar";
+    const FORMAT_UNIVERSAL: &str = "universal";
+
+    // DIDs as they are stored in the wallet, so both short and qualified forms can be given
+    fn get_dids(ctx: &CommandContext, wallet: &Wallet, dids: &[&str]) -> Result<Vec<String>, ()> {
+        if dids.is_empty() {
+            println_err!(ctx, "No DIDs to export are given");
+            return Err(());
+        }
+        let mut stored = Vec::new();
+        for did in dids {
+            let did_info = Did::get(wallet, &DidValue(did.to_string())).map_err(|err| {
+                println_err!(
+                    ctx,
+                    "DID \"{}\" cannot be exported: {}",
+                    did,
+                    err.message(None)
+                )
+            })?;
+            if !stored.contains(&did_info.did) {
+                stored.push(did_info.did);
+            }
+        }
+        Ok(stored)
+    }
+
+    fn export_universal(
+        ctx: &CommandContext,
+        wallet: &Wallet,
+        export_path: &str,
+        include_private_keys: bool,
+        selected: Option<Vec<String>>,
+    ) -> Result<(), ()> {
+        if std::path::Path::new(export_path).exists() {
+            println_err!(ctx, "Export file \"{}\" already exists", export_path);
+            return Err(());
+        }
+
+        let mut dids = Did::list(wallet).map_err(|err| err.print(ctx, None))?;
+        if let Some(selected) = selected {
+            dids.retain(|did_info| selected.contains(&did_info.did));
+        }
+        let mut seeds = Vec::new();
+        for did_info in dids.iter() {
+            // keys of hardware wallets never leave the device
+            let seed = if include_private_keys && did_info.signer.is_none() {
+                let did = DidValue(did_info.did.to_string());
+                let (_, seed) =
+                    Did::export_seed(wallet, &did).map_err(|err| err.print(ctx, None))?;
+                Some(seed)
+            } else {
+                None
+            };
+            seeds.push(seed);
+        }
+
+        let universal = UniversalWallet {
+            name: &wallet.name,
+            dids: dids
+                .into_iter()
+                .zip(seeds.iter())
+                .map(|(did_info, seed)| (did_info, seed.as_ref().map(|seed| seed.as_ref())))
+                .collect(),
+        };
+        let content = universal
+            .to_json()
+            .and_then(|content| serde_json::to_string_pretty(&content).map_err(CliError::from))
+            .map_err(|err| err.print(ctx, None))?;
+
+        write_private_file(export_path, content.as_bytes())
+            .map_err(|err| println_err!(ctx, "Unable to write \"{}\": {}", export_path, err))?;
+
+        println_succ!(
+            ctx,
+            "{} DIDs of wallet \"{}\" have been exported to the file \"{}\" in Universal Wallet format",
+            universal.dids.len(),
+            wallet.name,
+            export_path
+        );
+        if include_private_keys {
+            println_warn!(ctx, "The file contains private keys in plain text. Keep it as secret as the wallet key.");
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -67,8 +224,13 @@ pub mod tests {
 
     mod export {
         use super::*;
-        use crate::wallet::tests::{
-            export_wallet, export_wallet_path, EXPORT_KEY, EXPORT_KEY_DERIVATION_METHOD,
+        use crate::{
+            did::tests::{new_did, DID_MY1, DID_MY3, SEED_MY1, SEED_MY3, VERKEY_MY1},
+            tools::wallet::{resume::ResumeMarker, ExportConfig},
+            utils::{environment::EnvironmentUtils, file::read_file},
+            wallet::tests::{
+                export_wallet, export_wallet_path, EXPORT_KEY, EXPORT_KEY_DERIVATION_METHOD, WALLET,
+            },
         };
 
         #[test]
@@ -92,6 +254,47 @@ pub mod tests {
             tear_down_with_wallet(&ctx);
         }
 
+        #[test]
+        pub fn export_works_for_universal_format() {
+            let ctx = setup_with_wallet();
+            new_did(&ctx, SEED_MY1);
+
+            let path = EnvironmentUtils::tmp_file_path("wallet.jsonld");
+            let _ = std::fs::remove_file(&path);
+            {
+                let cmd = export_command::new();
+                let mut params = CommandParams::new();
+                params.insert("export_path", path.display().to_string());
+                params.insert("format", "universal".to_string());
+                params.insert("include_private_keys", "true".to_string());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+
+            let content: serde_json::Value =
+                serde_json::from_str(&read_file(&path).unwrap()).unwrap();
+            let entry = &content["contents"][0];
+            assert_eq!(format!("did:sov:{}", DID_MY1), entry["controller"]);
+            assert_eq!(VERKEY_MY1, entry["publicKeyBase58"]);
+            assert!(entry["privateKeyBase58"].is_string());
+            std::fs::remove_file(&path).unwrap();
+            tear_down_with_wallet(&ctx);
+        }
+
+        #[test]
+        pub fn export_works_for_unknown_format() {
+            let ctx = setup_with_wallet();
+            let (path, path_str) = export_wallet_path();
+            {
+                let cmd = export_command::new();
+                let mut params = CommandParams::new();
+                params.insert("export_path", path_str);
+                params.insert("format", "jwe".to_string());
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            assert!(!path.exists());
+            tear_down_with_wallet(&ctx);
+        }
+
         #[test]
         pub fn export_works_for_file_already_exists() {
             let ctx = setup_with_wallet();
@@ -112,5 +315,105 @@ pub mod tests {
             }
             tear_down_with_wallet(&ctx);
         }
+
+        #[test]
+        pub fn export_works_for_record_counts() {
+            let ctx = setup_with_wallet();
+            new_did(&ctx, SEED_MY1);
+
+            let (_, path_str) = export_wallet_path();
+            let export_config = ExportConfig {
+                path: path_str,
+                key: EXPORT_KEY.to_string(),
+                key_derivation_method: Some(EXPORT_KEY_DERIVATION_METHOD.to_string()),
+                dids: None,
+            };
+            let counts = ctx
+                .ensure_opened_wallet()
+                .unwrap()
+                .export(&export_config, ctx.get_crypto_policy())
+                .unwrap();
+            assert_eq!(counts["did"], 1);
+            assert_eq!(counts["key"], 1);
+            assert_eq!(counts["txn_template"], 0);
+            tear_down_with_wallet(&ctx);
+        }
+
+        #[test]
+        pub fn export_works_for_dids() {
+            let ctx = setup_with_wallet();
+            new_did(&ctx, SEED_MY1);
+            new_did(&ctx, SEED_MY3);
+
+            let (path, path_str) = export_wallet_path();
+            {
+                let cmd = export_command::new();
+                let mut params = CommandParams::new();
+                params.insert("export_path", path_str.clone());
+                params.insert("export_key", EXPORT_KEY.to_string());
+                params.insert(
+                    "export_key_derivation_method",
+                    EXPORT_KEY_DERIVATION_METHOD.to_string(),
+                );
+                params.insert("dids", DID_MY1.to_string());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            assert!(path.exists());
+
+            let path_str = EnvironmentUtils::tmp_file_path("export_file_selected")
+                .to_string_lossy()
+                .to_string();
+            let export_config = ExportConfig {
+                path: path_str,
+                key: EXPORT_KEY.to_string(),
+                key_derivation_method: Some(EXPORT_KEY_DERIVATION_METHOD.to_string()),
+                dids: Some(vec![DID_MY3.to_string()]),
+            };
+            let counts = ctx
+                .ensure_opened_wallet()
+                .unwrap()
+                .export(&export_config, ctx.get_crypto_policy())
+                .unwrap();
+            assert_eq!(counts["did"], 1);
+            assert_eq!(counts["key"], 1);
+            tear_down_with_wallet(&ctx);
+        }
+
+        #[test]
+        pub fn export_works_for_unknown_did() {
+            let ctx = setup_with_wallet();
+            new_did(&ctx, SEED_MY1);
+
+            let (path, path_str) = export_wallet_path();
+            {
+                let cmd = export_command::new();
+                let mut params = CommandParams::new();
+                params.insert("export_path", path_str);
+                params.insert("export_key", EXPORT_KEY.to_string());
+                params.insert("dids", format!("{},{}", DID_MY1, DID_MY3));
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            assert!(!path.exists());
+            tear_down_with_wallet(&ctx);
+        }
+
+        #[test]
+        pub fn export_works_for_interrupted_export() {
+            let ctx = setup_with_wallet();
+            new_did(&ctx, SEED_MY1);
+
+            let (path, path_str) = export_wallet_path();
+            export_wallet(&ctx, &path_str);
+
+            // marker left by an export interrupted before the first page has been committed
+            let marker_path = ResumeMarker::path_for(&path);
+            ResumeMarker::new(marker_path.clone(), WALLET)
+                .save()
+                .unwrap();
+
+            export_wallet(&ctx, &path_str);
+            assert!(!marker_path.exists());
+            tear_down_with_wallet(&ctx);
+        }
     }
 }
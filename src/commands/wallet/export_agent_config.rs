@@ -0,0 +1,167 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::{
+    command_executor::{
+        Command, CommandContext, CommandMetadata, CommandParams, DynamicCompletionType,
+    },
+    params_parser::ParamParser,
+    tools::{
+        did::Did,
+        pool::Pool,
+        wallet::{
+            agent_config::{AgentConfig, AgentFramework},
+            wallet_config::WalletConfig,
+        },
+    },
+    utils::file::write_private_file,
+};
+
+pub mod export_agent_config_command {
+    use super::*;
+
+    command!(CommandMetadata::build("export-agent-config", "Write the configuration an Aries agent needs to start with the opened wallet: \
+                                    the wallet key and storage, the genesis transactions of the pool and optionally the seed of the active DID.")
+                .add_required_param("framework", "Agent framework to write the configuration for. One of:
+                                    acapy - YAML file to pass with `--arg-file`
+                                    afj - JSON agent config")
+                .add_required_param("out", "Path to the file to create")
+                .add_required_deferred_param("key", "Key or passphrase the opened wallet has been opened with")
+                .add_optional_param("storage_credentials", "Credentials of the wallet storage (Postgres only)")
+                .add_optional_param_with_dynamic_completion("pool", "Pool to take the genesis transactions of (connected pool by default)", DynamicCompletionType::Pool)
+                .add_optional_param("include_seed", "Write the seed of the active DID so that the agent uses it as the public DID (False by default)")
+                .add_example("wallet export-agent-config framework=acapy out=/home/agent/acapy.yml key")
+                .add_example("wallet export-agent-config framework=afj out=/home/agent/afj.json key pool=sandbox include_seed=true")
+                .finalize()
+    );
+
+    fn execute(ctx: &CommandContext, params: &CommandParams) -> Result<(), ()> {
+        trace!("execute >> ctx {:?} params {:?}", ctx, secret!(params));
+
+        let wallet = ctx.ensure_opened_wallet()?;
+
+        let framework = ParamParser::get_str_param(ctx, "framework", params)?;
+        let out = ParamParser::get_str_param(ctx, "out", params)?;
+        let key = ParamParser::get_str_param(ctx, "key", params)?;
+        let storage_credentials =
+            ParamParser::get_opt_object_param(ctx, "storage_credentials", params)?;
+        let pool = ParamParser::get_opt_str_param(ctx, "pool", params)?;
+        let include_seed =
+            ParamParser::get_opt_bool_param(ctx, "include_seed", params)?.unwrap_or(false);
+
+        let framework = AgentFramework::from_str(framework).map_err(|err| err.print(ctx, None))?;
+
+        let wallet_config = WalletConfig::read(&wallet.name)
+            .map_err(|_| println_err!(ctx, "Wallet \"{}\" isn't attached to CLI", wallet.name))?;
+
+        let pool_name = pool
+            .map(String::from)
+            .or_else(|| ctx.get_connected_pool().map(|pool| pool.name.to_string()));
+        let genesis = match pool_name.as_ref() {
+            Some(name) => {
+                Some(Pool::genesis_txn_path(name).map_err(|err| err.print(ctx, Some(name)))?)
+            }
+            None => {
+                println_warn!(
+                    ctx,
+                    "No pool connected: genesis transactions are not included"
+                );
+                None
+            }
+        };
+
+        let seed = if include_seed {
+            let did = ctx.ensure_active_did()?;
+            let (_, seed) = Did::export_seed(&wallet, &did).map_err(|err| err.print(ctx, None))?;
+            Some(seed)
+        } else {
+            None
+        };
+
+        let config = AgentConfig {
+            wallet: &wallet_config,
+            key,
+            key_derivation_method: &wallet.key_derivation_method,
+            storage_credentials: storage_credentials.as_ref(),
+            pool: pool_name.as_deref().zip(genesis.as_deref()),
+            seed: seed.as_ref().map(|seed| seed.as_ref()),
+        };
+        let content = config
+            .render(framework)
+            .map_err(|err| err.print(ctx, None))?;
+
+        write_private_file(out, content.as_bytes())
+            .map_err(|err| println_err!(ctx, "Unable to write \"{}\": {}", out, err))?;
+
+        println_succ!(
+            ctx,
+            "Agent configuration of wallet \"{}\" has been written to \"{}\"",
+            wallet.name,
+            out
+        );
+        println_warn!(
+            ctx,
+            "The file contains the wallet key. Keep it as secret as the key itself."
+        );
+
+        trace!("execute <<");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::{
+        commands::{setup_with_wallet, tear_down_with_wallet},
+        did::tests::{new_did, use_did, DID_TRUSTEE, SEED_TRUSTEE},
+        utils::{environment::EnvironmentUtils, file::read_file},
+        wallet::tests::WALLET_KEY,
+    };
+    use serde_json::Value as JsonValue;
+    use std::fs;
+
+    mod export_agent_config {
+        use super::*;
+
+        #[test]
+        pub fn export_agent_config_works() {
+            let ctx = setup_with_wallet();
+            new_did(&ctx, SEED_TRUSTEE);
+            use_did(&ctx, DID_TRUSTEE);
+            let path = EnvironmentUtils::tmp_file_path("afj.json");
+            let _ = fs::remove_file(&path);
+            {
+                let cmd = export_agent_config_command::new();
+                let mut params = CommandParams::new();
+                params.insert("framework", "afj".to_string());
+                params.insert("out", path.display().to_string());
+                params.insert("key", WALLET_KEY.to_string());
+                params.insert("include_seed", "true".to_string());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            let config: JsonValue = serde_json::from_str(&read_file(&path).unwrap()).unwrap();
+            assert_eq!(WALLET_KEY, config["walletConfig"]["key"]);
+            assert_eq!(SEED_TRUSTEE, config["publicDidSeed"]);
+            fs::remove_file(&path).unwrap();
+            tear_down_with_wallet(&ctx);
+        }
+
+        #[test]
+        pub fn export_agent_config_works_for_unknown_framework() {
+            let ctx = setup_with_wallet();
+            let path = EnvironmentUtils::tmp_file_path("agent.conf");
+            {
+                let cmd = export_agent_config_command::new();
+                let mut params = CommandParams::new();
+                params.insert("framework", "unknown".to_string());
+                params.insert("out", path.display().to_string());
+                params.insert("key", WALLET_KEY.to_string());
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            assert!(!path.exists());
+            tear_down_with_wallet(&ctx);
+        }
+    }
+}
@@ -7,14 +7,17 @@ use crate::{
         Command, CommandContext, CommandMetadata, CommandParams, DynamicCompletionType,
     },
     params_parser::ParamParser,
-    tools::wallet::{wallet_config::WalletConfig, Credentials, Wallet},
+    tools::wallet::{resume::ResumeMarker, wallet_config::WalletConfig, Credentials, Wallet},
 };
 
+use super::{print_record_counts, warn_resume};
+
 pub mod import_command {
     use super::*;
-    use crate::tools::wallet::ImportConfig;
+    use crate::tools::wallet::{ConflictPolicy, ImportConfig};
 
-    command!(CommandMetadata::build("import", "Create new wallet, attach to Indy CLI and then import content from the specified file")
+    command!(CommandMetadata::build("import", "Create new wallet, attach to Indy CLI and then import content from the specified file. \
+                With merge=true the content is copied into the existing wallet instead. Run the command again to resume an interrupted import")
                 .add_main_param_with_dynamic_completion("name", "The name of new wallet", DynamicCompletionType::Wallet)
                 .add_required_deferred_param("key", "Key or passphrase used for wallet key derivation.
                                                Look to key_derivation_method param for information about supported key derivation methods.")
@@ -28,7 +31,13 @@ pub mod import_command {
                 .add_required_param("export_path", "Path to the file that contains exported wallet content")
                 .add_required_deferred_param("export_key", "Key used for export of the wallet")
                 .add_required_deferred_param("export_key_derivation_method", "Algorithm to use for export key derivation")
+                .add_optional_param("merge", "Copy the content into the existing (closed) wallet instead of creating a new one (False by default)")
+                .add_optional_param("conflict", "What to do with the records the wallet already has on merge. One of:
+                                    skip - keep the records of the wallet (used by default)
+                                    overwrite - replace them with the records of the file
+                                    fail - import nothing if any record conflicts")
                 .add_example("wallet import wallet1 key export_path=/home/indy/export_wallet export_key")
+                .add_example("wallet import wallet1 key export_path=/home/indy/export_wallet export_key merge=true conflict=overwrite")
                 .add_example(r#"wallet import wallet1 key export_path=/home/indy/export_wallet export_key storage_type=default storage_config={"key1":"value1","key2":"value2"}"#)
                 .finalize()
     );
@@ -36,29 +45,70 @@ pub mod import_command {
     fn execute(ctx: &CommandContext, params: &CommandParams) -> Result<(), ()> {
         trace!("execute >> ctx {:?} params {:?}", ctx, secret!(params));
 
-        let id = ParamParser::get_str_param("name", params)?;
-        let key = ParamParser::get_str_param("key", params)?;
+        let id = ParamParser::get_str_param(ctx, "name", params)?;
+        let key = ParamParser::get_str_param(ctx, "key", params)?;
         let key_derivation_method =
-            ParamParser::get_opt_str_param("key_derivation_method", params)?;
-        let export_path = ParamParser::get_str_param("export_path", params)?;
-        let export_key = ParamParser::get_str_param("export_key", params)?;
+            ParamParser::get_opt_str_param(ctx, "key_derivation_method", params)?;
+        let export_path = ParamParser::get_str_param(ctx, "export_path", params)?;
+        let export_key = ParamParser::get_str_param(ctx, "export_key", params)?;
         let export_key_derivation_method =
-            ParamParser::get_opt_str_param("export_key_derivation_method", params)?;
+            ParamParser::get_opt_str_param(ctx, "export_key_derivation_method", params)?;
         let storage_type =
-            ParamParser::get_opt_str_param("storage_type", params)?.unwrap_or("default");
-        let storage_config = ParamParser::get_opt_object_param("storage_config", params)?;
-        let storage_credentials = ParamParser::get_opt_object_param("storage_credentials", params)?;
-
-        let config = WalletConfig {
-            id: id.to_string(),
-            storage_type: storage_type.to_string(),
-            storage_config,
+            ParamParser::get_opt_str_param(ctx, "storage_type", params)?.unwrap_or("default");
+        let storage_config = ParamParser::get_opt_object_param(ctx, "storage_config", params)?;
+        let storage_credentials =
+            ParamParser::get_opt_object_param(ctx, "storage_credentials", params)?;
+        let merge = ParamParser::get_opt_bool_param(ctx, "merge", params)?.unwrap_or(false);
+        let conflict = ParamParser::get_opt_str_param(ctx, "conflict", params)?;
+
+        let merge = match (merge, conflict) {
+            (false, None) => None,
+            (false, Some(_)) => {
+                println_err!(ctx, "\"conflict\" can be used with merge=true only");
+                return Err(());
+            }
+            (true, None | Some("skip")) => Some(ConflictPolicy::Skip),
+            (true, Some("overwrite")) => Some(ConflictPolicy::Overwrite),
+            (true, Some("fail")) => Some(ConflictPolicy::Fail),
+            (true, Some(conflict)) => {
+                println_err!(
+                    ctx,
+                    "Unsupported conflict policy \"{}\". One of: skip, overwrite, fail",
+                    conflict
+                );
+                return Err(());
+            }
+        };
+
+        // the wallet keeps its storage on merge
+        let config = if merge.is_some() {
+            if ctx
+                .get_opened_wallet()
+                .map(|wallet| wallet.name == id)
+                .unwrap_or(false)
+            {
+                println_err!(
+                    ctx,
+                    "Wallet \"{}\" is opened. Close it with `wallet close` before merging into it",
+                    id
+                );
+                return Err(());
+            }
+            WalletConfig::read(id)
+                .map_err(|_| println_err!(ctx, "Wallet \"{}\" isn't attached to CLI", id))?
+        } else {
+            WalletConfig {
+                id: id.to_string(),
+                storage_type: storage_type.to_string(),
+                storage_config,
+            }
         };
 
         let import_config = ImportConfig {
             path: export_path.to_string(),
             key: export_key.to_string(),
             key_derivation_method: export_key_derivation_method.map(String::from),
+            merge,
         };
 
         let credentials = Credentials {
@@ -69,8 +119,8 @@ pub mod import_command {
             storage_credentials,
         };
 
-        if config.exists() {
-            println_err!("Wallet \"{}\" is already attached to CLI", id);
+        if merge.is_none() && config.exists() {
+            println_err!(ctx, "Wallet \"{}\" is already attached to CLI", id);
             return Err(());
         }
 
@@ -80,14 +130,34 @@ pub mod import_command {
             secret!(&import_config)
         );
 
-        Wallet::import(&config, &credentials, &import_config)
-            .map_err(|err| println_err!("{}", err.message(Some(id))))?;
+        warn_resume(ctx, ResumeMarker::path_in_wallet(id), export_path, "import");
+
+        let counts = Wallet::import(
+            &config,
+            &credentials,
+            &import_config,
+            ctx.get_crypto_policy(),
+        )
+        .map_err(|err| err.print(ctx, Some(id)))?;
+
+        if merge.is_some() {
+            print_record_counts(ctx, &counts);
+            println_succ!(
+                ctx,
+                "The content of \"{}\" has been merged into wallet \"{}\"",
+                export_path,
+                id
+            );
+            trace!("execute <<");
+            return Ok(());
+        }
 
         config
             .store()
-            .map_err(|err| println_err!("Cannot store \"{}\" config file: {:?}", id, err))?;
+            .map_err(|err| println_err!(ctx, "Cannot store \"{}\" config file: {:?}", id, err))?;
 
-        println_succ!("Wallet \"{}\" has been created", id);
+        print_record_counts(ctx, &counts);
+        println_succ!(ctx, "Wallet \"{}\" has been created", id);
 
         trace!("execute <<");
         Ok(())
@@ -103,7 +173,7 @@ pub mod tests {
         use super::*;
         use crate::{
             commands::setup_with_wallet,
-            did::tests::{new_did, use_did, DID_MY1, SEED_MY1},
+            did::tests::{get_did_info, new_did, use_did, DID_MY1, SEED_MY1},
             wallet::{
                 close_command, create_command, delete_command, export_command, open_command,
                 tests::{
@@ -243,6 +313,108 @@ pub mod tests {
             tear_down();
         }
 
+        fn merge_wallet(
+            ctx: &CommandContext,
+            name: &str,
+            path: &str,
+            conflict: &str,
+        ) -> Result<(), ()> {
+            let cmd = import_command::new();
+            let mut params = CommandParams::new();
+            params.insert("name", name.to_string());
+            params.insert("key", WALLET_KEY_RAW.to_string());
+            params.insert("key_derivation_method", "raw".to_string());
+            params.insert("export_path", path.to_string());
+            params.insert("export_key", EXPORT_KEY.to_string());
+            params.insert(
+                "export_key_derivation_method",
+                EXPORT_KEY_DERIVATION_METHOD.to_string(),
+            );
+            params.insert("merge", "true".to_string());
+            params.insert("conflict", conflict.to_string());
+            cmd.execute(ctx, &params)
+        }
+
+        #[test]
+        pub fn import_works_for_merge() {
+            let ctx = setup();
+
+            create_and_open_wallet(&ctx);
+            new_did(&ctx, SEED_MY1);
+
+            let (_, path_str) = export_wallet_path();
+            export_wallet(&ctx, &path_str);
+
+            let wallet_name = "merged_wallet";
+            {
+                let cmd = create_command::new();
+                let mut params = CommandParams::new();
+                params.insert("name", wallet_name.to_string());
+                params.insert("key", WALLET_KEY_RAW.to_string());
+                params.insert("key_derivation_method", "raw".to_string());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+
+            merge_wallet(&ctx, wallet_name, &path_str, "skip").unwrap();
+            // the DID is in the wallet now
+            merge_wallet(&ctx, wallet_name, &path_str, "fail").unwrap_err();
+            merge_wallet(&ctx, wallet_name, &path_str, "overwrite").unwrap();
+            // the opened wallet cannot be merged into
+            merge_wallet(&ctx, WALLET, &path_str, "skip").unwrap_err();
+
+            close_and_delete_wallet(&ctx);
+            {
+                let cmd = open_command::new();
+                let mut params = CommandParams::new();
+                params.insert("name", wallet_name.to_string());
+                params.insert("key", WALLET_KEY_RAW.to_string());
+                params.insert("key_derivation_method", "raw".to_string());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            assert_eq!(get_did_info(&ctx, DID_MY1).did, DID_MY1);
+            {
+                let cmd = close_command::new();
+                let params = CommandParams::new();
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            {
+                let cmd = delete_command::new();
+                let mut params = CommandParams::new();
+                params.insert("name", wallet_name.to_string());
+                params.insert("key", WALLET_KEY_RAW.to_string());
+                params.insert("key_derivation_method", "raw".to_string());
+                cmd.execute(&CommandContext::new(), &params).unwrap();
+            }
+            tear_down();
+        }
+
+        #[test]
+        pub fn import_works_for_merge_into_not_existing_wallet() {
+            let ctx = setup_with_wallet();
+            let (_, path_str) = export_wallet_path();
+            export_wallet(&ctx, &path_str);
+            merge_wallet(&ctx, "unknown_wallet", &path_str, "skip").unwrap_err();
+            close_and_delete_wallet(&ctx);
+            tear_down();
+        }
+
+        #[test]
+        pub fn import_works_for_conflict_without_merge() {
+            let ctx = setup();
+            let (_, path_str) = export_wallet_path();
+            {
+                let cmd = import_command::new();
+                let mut params = CommandParams::new();
+                params.insert("name", WALLET.to_string());
+                params.insert("key", WALLET_KEY.to_string());
+                params.insert("export_path", path_str);
+                params.insert("export_key", EXPORT_KEY.to_string());
+                params.insert("conflict", "skip".to_string());
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            tear_down();
+        }
+
         #[test]
         pub fn import_works_for_config() {
             let ctx = setup();
@@ -19,13 +19,14 @@ pub mod list_command {
         let wallets = Wallet::list();
 
         print_list_table(
+            ctx,
             &wallets,
             &[("id", "Name"), ("storage_type", "Type")],
             "There are no wallets",
         );
 
         if let Some(wallet) = ctx.get_opened_wallet() {
-            println_succ!("Current wallet \"{}\"", wallet.name);
+            println_succ!(ctx, "Current wallet \"{}\"", wallet.name);
         }
 
         trace!("execute << ");
@@ -0,0 +1,169 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::{
+    command_executor::{
+        Command, CommandContext, CommandMetadata, CommandParams, DynamicCompletionType,
+    },
+    params_parser::ParamParser,
+    tools::wallet::{
+        resume::ResumeMarker, wallet_config::WalletConfig, Credentials, MigrateConfig, Wallet,
+    },
+    utils::environment::EnvironmentUtils,
+};
+
+use super::{print_record_counts, warn_resume};
+
+pub mod migrate_command {
+    use super::*;
+
+    command!(CommandMetadata::build("migrate", "Create new wallet, attach to Indy CLI and then copy DIDs and keys from the wallet created by the old Indy-CLI (libindy SQLite wallet). \
+                The old wallet is not changed. Run the command again to resume an interrupted migration")
+                .add_main_param_with_dynamic_completion("name", "The name of new wallet", DynamicCompletionType::Wallet)
+                .add_required_deferred_param("key", "Key or passphrase used for wallet key derivation.
+                                               Look to key_derivation_method param for information about supported key derivation methods.")
+                .add_optional_param("key_derivation_method", "Algorithm to use for wallet key derivation. One of:
+                                    argon2m - derive secured wallet key (used by default)
+                                    argon2i - derive secured wallet key (less secured but faster)
+                                    raw - raw key provided (skip derivation)")
+                .add_optional_param("storage_type", "Type of the wallet storage.")
+                .add_optional_param("storage_config", "The list of key:value pairs defined by storage type.")
+                .add_optional_param("storage_credentials", "The list of key:value pairs defined by storage type.")
+                .add_optional_param("legacy_path", "Path to the libindy wallet directory or its sqlite.db file (the old Indy-CLI wallet with the same name by default)")
+                .add_required_deferred_param("legacy_key", "Key the libindy wallet has been created with")
+                .add_optional_param("legacy_key_derivation_method", "Algorithm the libindy wallet key is derived with (argon2m by default)")
+                .add_example("wallet migrate wallet1 key legacy_key")
+                .add_example("wallet migrate wallet2 key legacy_path=/home/indy/.indy_client/wallet/wallet1 legacy_key legacy_key_derivation_method=argon2i")
+                .finalize()
+    );
+
+    fn execute(ctx: &CommandContext, params: &CommandParams) -> Result<(), ()> {
+        trace!("execute >> ctx {:?} params {:?}", ctx, secret!(params));
+
+        let id = ParamParser::get_str_param(ctx, "name", params)?;
+        let key = ParamParser::get_str_param(ctx, "key", params)?;
+        let key_derivation_method =
+            ParamParser::get_opt_str_param(ctx, "key_derivation_method", params)?;
+        let legacy_path = ParamParser::get_opt_str_param(ctx, "legacy_path", params)?;
+        let legacy_key = ParamParser::get_str_param(ctx, "legacy_key", params)?;
+        let legacy_key_derivation_method =
+            ParamParser::get_opt_str_param(ctx, "legacy_key_derivation_method", params)?;
+        let storage_type =
+            ParamParser::get_opt_str_param(ctx, "storage_type", params)?.unwrap_or("default");
+        let storage_config = ParamParser::get_opt_object_param(ctx, "storage_config", params)?;
+        let storage_credentials =
+            ParamParser::get_opt_object_param(ctx, "storage_credentials", params)?;
+
+        let config = WalletConfig {
+            id: id.to_string(),
+            storage_type: storage_type.to_string(),
+            storage_config,
+        };
+
+        let migrate_config = MigrateConfig {
+            path: legacy_path.map(String::from).unwrap_or_else(|| {
+                EnvironmentUtils::wallet_path(id)
+                    .to_string_lossy()
+                    .to_string()
+            }),
+            key: legacy_key.to_string(),
+            key_derivation_method: legacy_key_derivation_method.map(String::from),
+        };
+
+        let credentials = Credentials {
+            key: key.to_string(),
+            key_derivation_method: key_derivation_method.map(String::from),
+            rekey: None,
+            rekey_derivation_method: None,
+            storage_credentials,
+        };
+
+        // the wallet left by an interrupted migration is completed
+        if config.exists() && !ResumeMarker::path_in_wallet(id).exists() {
+            println_err!(ctx, "Wallet \"{}\" is already attached to CLI", id);
+            return Err(());
+        }
+
+        trace!(
+            "Wallet::migrate try: config {:?}, migrate_config {:?}",
+            config,
+            secret!(&migrate_config)
+        );
+
+        warn_resume(
+            ctx,
+            ResumeMarker::path_in_wallet(id),
+            &migrate_config.path,
+            "migration",
+        );
+
+        let counts = Wallet::migrate(
+            &config,
+            &credentials,
+            &migrate_config,
+            ctx.get_crypto_policy(),
+        )
+        .map_err(|err| err.print(ctx, Some(id)))?;
+
+        config
+            .store()
+            .map_err(|err| println_err!(ctx, "Cannot store \"{}\" config file: {:?}", id, err))?;
+
+        print_record_counts(ctx, &counts);
+        println_succ!(
+            ctx,
+            "Wallet \"{}\" has been created from the libindy wallet \"{}\"",
+            id,
+            migrate_config.path
+        );
+
+        trace!("execute <<");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::commands::{setup, tear_down};
+
+    mod migrate {
+        use super::*;
+        use crate::wallet::tests::{WALLET, WALLET_KEY};
+
+        #[test]
+        pub fn migrate_works_for_not_found_wallet() {
+            let ctx = setup();
+            {
+                let cmd = migrate_command::new();
+                let mut params = CommandParams::new();
+                params.insert("name", WALLET.to_string());
+                params.insert("key", WALLET_KEY.to_string());
+                params.insert("legacy_key", WALLET_KEY.to_string());
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            assert!(!EnvironmentUtils::wallet_config_path(WALLET).exists());
+            tear_down();
+        }
+
+        #[test]
+        pub fn migrate_works_for_not_libindy_wallet() {
+            let ctx = setup();
+            let path = EnvironmentUtils::tmp_file_path("not_a_wallet.db");
+            std::fs::create_dir_all(EnvironmentUtils::tmp_path()).unwrap();
+            std::fs::write(&path, "not a wallet").unwrap();
+            {
+                let cmd = migrate_command::new();
+                let mut params = CommandParams::new();
+                params.insert("name", WALLET.to_string());
+                params.insert("key", WALLET_KEY.to_string());
+                params.insert("legacy_path", path.to_string_lossy().to_string());
+                params.insert("legacy_key", WALLET_KEY.to_string());
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            assert!(!EnvironmentUtils::wallet_config_path(WALLET).exists());
+            tear_down();
+        }
+    }
+}
@@ -3,22 +3,37 @@
     https://digital.gov.bc.ca/digital-trust
 */
 use crate::{
-    command_executor::{CommandGroup, CommandGroupMetadata},
-    tools::wallet::Wallet,
+    command_executor::{CommandContext, CommandGroup, CommandGroupMetadata},
+    tools::wallet::{
+        key_rotation::{KeyRotationPolicy, KeyRotationRecord},
+        resume::{RecordCounts, ResumeMarker},
+        Wallet,
+    },
+    utils::table::print_list_table,
 };
 
+use chrono::Utc;
+use serde_json::Value as JsonValue;
+use std::path::PathBuf;
+
 pub mod attach;
 pub mod close;
 pub mod create;
 pub mod delete;
 pub mod detach;
 pub mod export;
+pub mod export_agent_config;
 pub mod import;
 pub mod list;
+pub mod migrate;
 pub mod open;
+pub mod reindex;
+pub mod rekey;
+pub mod security_report;
 
 pub use self::{
-    attach::*, close::*, create::*, delete::*, detach::*, export::*, import::*, list::*, open::*,
+    attach::*, close::*, create::*, delete::*, detach::*, export::*, export_agent_config::*,
+    import::*, list::*, migrate::*, open::*, reindex::*, rekey::*, security_report::*,
 };
 
 pub mod group {
@@ -42,6 +57,73 @@ pub fn wallet_names() -> Vec<String> {
         .collect()
 }
 
+pub fn print_record_counts(ctx: &CommandContext, counts: &RecordCounts) {
+    let rows = counts
+        .iter()
+        .map(|(category, count)| json!({ "category": category, "records": count }))
+        .collect::<Vec<JsonValue>>();
+    print_list_table(
+        ctx,
+        &rows,
+        &[("category", "Category"), ("records", "Records")],
+        "",
+    );
+}
+
+/// Tell the user that the copying continues from the position saved by an interrupted run.
+pub fn warn_resume(ctx: &CommandContext, marker_path: PathBuf, source: &str, operation: &str) {
+    if let Ok(Some(marker)) = ResumeMarker::load(marker_path, source) {
+        println_warn!(
+            ctx,
+            "Resuming interrupted {}: {} record(s) have already been copied",
+            operation,
+            marker.total()
+        );
+    }
+}
+
+/// Reason to rotate the wallet key according to the key rotation policy set in CLI.
+fn key_rotation_due(ctx: &CommandContext, wallet: &Wallet) -> Option<(KeyRotationPolicy, String)> {
+    let policy = ctx.get_key_rotation_policy()?;
+    let record = KeyRotationRecord::get(wallet).ok().flatten();
+    policy
+        .check(record.as_ref(), Utc::now().timestamp())
+        .map(|reason| (policy, reason))
+}
+
+pub fn warn_key_rotation(ctx: &CommandContext, wallet: &Wallet) {
+    if let Some((policy, reason)) = key_rotation_due(ctx, wallet) {
+        println_warn!(
+            ctx,
+            "Key of wallet \"{}\" must be rotated: {}. Set a new key with `wallet rekey`.",
+            wallet.name,
+            reason
+        );
+        if policy.enforce {
+            println_warn!(
+                ctx,
+                "Transactions cannot be signed with the wallet until the key is rotated."
+            );
+        }
+    }
+}
+
+/// Block signing transactions with the wallet while the enforced key rotation is due.
+pub fn ensure_key_rotated(ctx: &CommandContext, wallet: &Wallet) -> Result<(), ()> {
+    match key_rotation_due(ctx, wallet) {
+        Some((policy, reason)) if policy.enforce => {
+            println_err!(
+                ctx,
+                "Key of wallet \"{}\" must be rotated before signing transactions: {}. Set a new key with `wallet rekey`.",
+                wallet.name,
+                reason
+            );
+            Err(())
+        }
+        _ => Ok(()),
+    }
+}
+
 #[cfg(test)]
 pub mod tests {
     use super::*;
@@ -6,11 +6,19 @@ use crate::{
     command_executor::{
         Command, CommandContext, CommandMetadata, CommandParams, DynamicCompletionType,
     },
+    error::CliError,
     params_parser::ParamParser,
     tools::wallet::{wallet_config::WalletConfig, Credentials, Wallet},
-    wallet::close_wallet,
+    wallet::{close_wallet, warn_key_rotation},
 };
 
+use std::{
+    thread,
+    time::{Duration, Instant},
+};
+
+const WAIT_INTERVAL: Duration = Duration::from_secs(1);
+
 pub mod open_command {
     use super::*;
 
@@ -28,24 +36,28 @@ pub mod open_command {
                                                 argon2i - derive secured wallet key (less secured but faster)
                                                 raw - raw key provided (skip derivation)")
                             .add_optional_param("storage_credentials", "The list of key:value pairs defined by storage type.")
+                            .add_optional_param("wait", "Seconds to wait for the wallet to be released if it is locked by another process (0 by default)")
                             .add_example("wallet open wallet1 key")
                             .add_example("wallet open wallet1 key rekey")
+                            .add_example("wallet open wallet1 key wait=30")
                             .finalize());
 
     fn execute(ctx: &CommandContext, params: &CommandParams) -> Result<(), ()> {
         trace!("execute >> ctx {:?} params {:?}", ctx, secret!(params));
 
-        let id = ParamParser::get_str_param("name", params)?;
-        let key = ParamParser::get_str_param("key", params)?;
-        let rekey = ParamParser::get_opt_str_param("rekey", params)?;
+        let id = ParamParser::get_str_param(ctx, "name", params)?;
+        let key = ParamParser::get_str_param(ctx, "key", params)?;
+        let rekey = ParamParser::get_opt_str_param(ctx, "rekey", params)?;
         let key_derivation_method =
-            ParamParser::get_opt_str_param("key_derivation_method", params)?;
+            ParamParser::get_opt_str_param(ctx, "key_derivation_method", params)?;
         let rekey_derivation_method =
-            ParamParser::get_opt_str_param("rekey_derivation_method", params)?;
-        let storage_credentials = ParamParser::get_opt_object_param("storage_credentials", params)?;
+            ParamParser::get_opt_str_param(ctx, "rekey_derivation_method", params)?;
+        let storage_credentials =
+            ParamParser::get_opt_object_param(ctx, "storage_credentials", params)?;
+        let wait = ParamParser::get_opt_number_param::<u64>("wait", params)?.unwrap_or(0);
 
         let config = WalletConfig::read(id)
-            .map_err(|_| println_err!("Wallet \"{}\" isn't attached to CLI", id))?;
+            .map_err(|_| println_err!(ctx, "Wallet \"{}\" isn't attached to CLI", id))?;
 
         let credentials = Credentials {
             key: key.to_string(),
@@ -59,7 +71,7 @@ pub mod open_command {
 
         if let Some(wallet) = ctx.get_opened_wallet() {
             if wallet.name == config.id {
-                println_err!("Wallet \"{}\" already opened.", wallet.name);
+                println_err!(ctx, "Wallet \"{}\" already opened.", wallet.name);
                 return Err(());
             }
         }
@@ -67,16 +79,43 @@ pub mod open_command {
             close_wallet(ctx, wallet)?;
         }
 
-        let wallet = Wallet::open(&config, &credentials)
-            .map_err(|err| println_err!("{}", err.message(Some(&id))))?;
+        let wallet = open_wallet(ctx, &config, &credentials, wait)
+            .map_err(|err| err.print(ctx, Some(&id)))?;
 
+        println_succ!(ctx, "Wallet \"{}\" has been opened", id);
+        warn_key_rotation(ctx, &wallet);
         ctx.set_opened_wallet(wallet);
-        println_succ!("Wallet \"{}\" has been opened", id);
 
         trace!("execute << {:?}", ());
         Ok(())
     }
 
+    fn open_wallet(
+        ctx: &CommandContext,
+        config: &WalletConfig,
+        credentials: &Credentials,
+        wait: u64,
+    ) -> Result<Wallet, CliError> {
+        let deadline = Instant::now() + Duration::from_secs(wait);
+        let mut waiting = false;
+        loop {
+            match Wallet::open(config, credentials, ctx.get_crypto_policy()) {
+                Err(CliError::Locked(message)) if Instant::now() < deadline => {
+                    if !waiting {
+                        println_warn!(ctx, "{}", message);
+                        println!(
+                            "Waiting up to {} second(s) for the wallet to be released...",
+                            wait
+                        );
+                        waiting = true;
+                    }
+                    thread::sleep(WAIT_INTERVAL);
+                }
+                res => return res,
+            }
+        }
+    }
+
     pub fn cleanup(ctx: &CommandContext) {
         trace!("cleanup >> ctx {:?}", ctx);
 
@@ -132,6 +171,31 @@ pub mod tests {
             tear_down_with_wallet(&ctx);
         }
 
+        #[test]
+        pub fn open_works_for_locked_by_other_process() {
+            let ctx = setup();
+            create_wallet(&ctx);
+            let lock_path = crate::tools::wallet::lock::WalletLock::path(WALLET);
+            std::fs::write(
+                &lock_path,
+                json!({"pid": 1, "host": "other-host", "openedAt": "2023-01-01 00:00:00 UTC"})
+                    .to_string(),
+            )
+            .unwrap();
+            {
+                let cmd = open_command::new();
+                let mut params = CommandParams::new();
+                params.insert("name", WALLET.to_string());
+                params.insert("key", WALLET_KEY_RAW.to_string());
+                params.insert("key_derivation_method", "raw".to_string());
+                params.insert("wait", "1".to_string());
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            std::fs::remove_file(&lock_path).unwrap();
+            delete_wallet(&ctx);
+            tear_down();
+        }
+
         #[test]
         pub fn open_works_for_not_created() {
             let ctx = setup();
@@ -0,0 +1,116 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::{
+    command_executor::{Command, CommandContext, CommandMetadata, CommandParams},
+    tools::did::Did,
+};
+
+pub mod reindex_command {
+    use super::*;
+
+    command!(CommandMetadata::build(
+        "reindex",
+        "Rebuild search tags of DID records in the opened wallet (verkey, method, network and metadata). Use it for wallets created by older CLI releases."
+    )
+    .add_example("wallet reindex")
+    .finalize());
+
+    fn execute(ctx: &CommandContext, params: &CommandParams) -> Result<(), ()> {
+        trace!("execute >> ctx {:?} params {:?}", ctx, params);
+
+        let wallet = ctx.ensure_opened_wallet()?;
+
+        let stats = Did::reindex(&wallet).map_err(|err| err.print(ctx, Some(&wallet.name)))?;
+
+        for did in stats.invalid.iter() {
+            println_warn!(
+                ctx,
+                "DID record \"{}\" cannot be parsed and has been skipped",
+                did
+            );
+        }
+        println_succ!(
+            ctx,
+            "Tags of {} of {} DID record(s) have been rebuilt in wallet \"{}\"",
+            stats.updated,
+            stats.total,
+            wallet.name
+        );
+
+        trace!("execute <<");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::{
+        commands::{setup, setup_with_wallet, tear_down, tear_down_with_wallet},
+        tools::did::{constants::CATEGORY_DID, DidInfo},
+        utils::futures::block_on,
+    };
+    use aries_askar::EntryTag;
+
+    const DID: &str = "did:indy:sovrin:staging:VsKV7grR1BUE29mG2Fm2kX";
+    const VERKEY: &str = "GjZWsBLgZCR18aL468JAT7w9CZRiBnpxUPPgyQxh4voa";
+
+    mod reindex {
+        use super::*;
+
+        #[test]
+        pub fn reindex_works() {
+            let ctx = setup_with_wallet();
+            let wallet = ctx.ensure_opened_wallet().unwrap();
+
+            // record written by an older release: only the verkey tags are set
+            let did_info = DidInfo {
+                did: DID.to_string(),
+                verkey: VERKEY.to_string(),
+                verkey_type: "ed25519".to_string(),
+                metadata: Some(r#"{"role":"issuer"}"#.to_string()),
+                ..DidInfo::default()
+            };
+            block_on(wallet.store_record(
+                CATEGORY_DID,
+                DID,
+                &did_info.to_bytes().unwrap(),
+                Some(&did_info.tags()[..2]),
+                true,
+            ))
+            .unwrap();
+            {
+                let cmd = reindex_command::new();
+                let params = CommandParams::new();
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            let entry = block_on(wallet.fetch_record(CATEGORY_DID, DID, false))
+                .unwrap()
+                .unwrap();
+            assert!(entry.tags.contains(&EntryTag::Encrypted(
+                "network".to_string(),
+                "sovrin:staging".to_string()
+            )));
+            assert!(entry.tags.contains(&EntryTag::Encrypted(
+                "metadata.role".to_string(),
+                "issuer".to_string()
+            )));
+
+            assert_eq!(Did::reindex(&wallet).unwrap().updated, 0);
+            tear_down_with_wallet(&ctx);
+        }
+
+        #[test]
+        pub fn reindex_works_for_no_opened_wallet() {
+            let ctx = setup();
+            {
+                let cmd = reindex_command::new();
+                let params = CommandParams::new();
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            tear_down();
+        }
+    }
+}
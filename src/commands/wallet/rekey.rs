@@ -0,0 +1,157 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::{
+    command_executor::{Command, CommandContext, CommandMetadata, CommandParams},
+    params_parser::ParamParser,
+};
+
+pub mod rekey_command {
+    use super::*;
+
+    command!(CommandMetadata::build(
+        "rekey",
+        "Replace the key of the opened wallet. The time of the rotation is recorded in the wallet \
+        and checked against the key rotation policy (see `set key-rotation`)."
+    )
+    .add_required_deferred_param(
+        "key",
+        "New key or passphrase used for wallet key derivation"
+    )
+    .add_optional_param(
+        "key_derivation_method",
+        "Algorithm to use for wallet key derivation. One of:
+                    argon2m - derive secured wallet key (used by default)
+                    argon2i - derive secured wallet key (less secured but faster)
+                    raw - raw key provided (skip derivation)"
+    )
+    .add_example("wallet rekey key")
+    .add_example("wallet rekey key key_derivation_method=argon2i")
+    .finalize());
+
+    fn execute(ctx: &CommandContext, params: &CommandParams) -> Result<(), ()> {
+        trace!("execute >> ctx {:?} params {:?}", ctx, secret!(params));
+
+        let key = ParamParser::get_str_param(ctx, "key", params)?;
+        let key_derivation_method =
+            ParamParser::get_opt_str_param(ctx, "key_derivation_method", params)?;
+
+        let mut wallet = match ctx.take_opened_wallet()? {
+            Some(wallet) => wallet,
+            None => {
+                println_err!(ctx, "There is no opened wallet now");
+                return Err(());
+            }
+        };
+
+        // the wallet stays opened with the previous key if the rotation fails
+        let res = wallet
+            .rekey(key, key_derivation_method, ctx.get_crypto_policy())
+            .map_err(|err| err.print(ctx, Some(&wallet.name)));
+        if res.is_ok() {
+            info!(
+                target: "audit",
+                "Key of wallet \"{}\" has been rotated: key derivation method \"{}\"",
+                wallet.name,
+                wallet.key_derivation_method
+            );
+            println_succ!(ctx, "Key of wallet \"{}\" has been rotated", wallet.name);
+        }
+        ctx.set_opened_wallet(wallet);
+
+        trace!("execute << {:?}", res);
+        res
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::{
+        commands::{setup, setup_with_wallet, tear_down, tear_down_with_wallet},
+        tools::wallet::key_rotation::{KeyRotationPolicy, KeyRotationRecord},
+        wallet::{
+            ensure_key_rotated, open_command,
+            tests::{close_wallet, WALLET, WALLET_KEY_RAW},
+        },
+    };
+
+    const NEW_WALLET_KEY_RAW: &str = "6nxtSiXFvBd593Y2DCed2dYvRY1PGK9WMtxCBjLzKgbx";
+
+    mod rekey {
+        use super::*;
+
+        #[test]
+        pub fn rekey_works() {
+            let ctx = setup_with_wallet();
+            {
+                let cmd = rekey_command::new();
+                let mut params = CommandParams::new();
+                params.insert("key", NEW_WALLET_KEY_RAW.to_string());
+                params.insert("key_derivation_method", "raw".to_string());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            let wallet = ctx.ensure_opened_wallet().unwrap();
+            assert_eq!(WALLET, wallet.name);
+            assert_eq!("raw", wallet.key_derivation_method);
+            drop(wallet);
+            close_wallet(&ctx);
+            {
+                let cmd = open_command::new();
+                let mut params = CommandParams::new();
+                params.insert("name", WALLET.to_string());
+                params.insert("key", NEW_WALLET_KEY_RAW.to_string());
+                params.insert("key_derivation_method", "raw".to_string());
+                params.insert("rekey", WALLET_KEY_RAW.to_string());
+                params.insert("rekey_derivation_method", "raw".to_string());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            tear_down_with_wallet(&ctx);
+        }
+
+        #[test]
+        pub fn rekey_works_for_no_opened_wallet() {
+            let ctx = setup();
+            {
+                let cmd = rekey_command::new();
+                let mut params = CommandParams::new();
+                params.insert("key", NEW_WALLET_KEY_RAW.to_string());
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            tear_down();
+        }
+
+        #[test]
+        pub fn rekey_works_for_enforced_key_rotation() {
+            let ctx = setup_with_wallet();
+            let wallet = ctx.ensure_opened_wallet().unwrap();
+            assert!(KeyRotationRecord::get(&wallet).unwrap().is_some());
+
+            ctx.set_key_rotation_policy(Some(KeyRotationPolicy {
+                max_age_days: 1,
+                enforce: true,
+            }));
+            ensure_key_rotated(&ctx, &wallet).unwrap();
+
+            let mut record = KeyRotationRecord::new("raw");
+            record.rotated -= 2 * 24 * 60 * 60;
+            record.store(&wallet).unwrap();
+            ensure_key_rotated(&ctx, &wallet).unwrap_err();
+            drop(wallet);
+
+            {
+                let cmd = rekey_command::new();
+                let mut params = CommandParams::new();
+                params.insert("key", NEW_WALLET_KEY_RAW.to_string());
+                params.insert("key_derivation_method", "raw".to_string());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            let wallet = ctx.ensure_opened_wallet().unwrap();
+            ensure_key_rotated(&ctx, &wallet).unwrap();
+            drop(wallet);
+
+            tear_down_with_wallet(&ctx);
+        }
+    }
+}
@@ -0,0 +1,176 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::{
+    command_executor::{Command, CommandContext, CommandMetadata, CommandParams},
+    params_parser::ParamParser,
+    tools::wallet::security::{describe_key_derivation_method, SecurityPolicy, SecurityReport},
+    utils::{
+        table::{print_list_table, print_table},
+        time::timestamp_to_datetime,
+    },
+};
+
+use serde_json::Value as JsonValue;
+
+pub mod security_report_command {
+    use super::*;
+
+    command!(CommandMetadata::build(
+        "security-report",
+        "Show how the opened wallet is protected at rest (key derivation, key algorithms, backups) and flag the settings weaker than the policy."
+    )
+    .add_optional_param(
+        "policy",
+        "Path to a JSON file with the policy to check against. Skip to use the default policy: \
+        {\"key_derivation_methods\":[\"argon2m\"],\"key_algorithms\":[\"ed25519\"],\"backup_key_derivation_methods\":[\"argon2m\"],\"require_backup\":true,\"max_backup_age_days\":90}"
+    )
+    .add_example("wallet security-report")
+    .add_example("wallet security-report policy=/home/security-policy.json")
+    .finalize());
+
+    fn execute(ctx: &CommandContext, params: &CommandParams) -> Result<(), ()> {
+        trace!("execute >> ctx {:?} params {:?}", ctx, params);
+
+        let wallet = ctx.ensure_opened_wallet()?;
+
+        let policy = match ParamParser::get_opt_str_param(ctx, "policy", params)? {
+            Some(path) => SecurityPolicy::read(path).map_err(|err| err.print(ctx, None))?,
+            None => SecurityPolicy::default(),
+        };
+
+        let report = SecurityReport::build(&wallet, &policy)
+            .map_err(|err| err.print(ctx, Some(&wallet.name)))?;
+
+        println_succ!(ctx, "Wallet \"{}\" security report", report.wallet);
+        print_table(
+            ctx,
+            &json!({
+                "storage_type": report.storage_type,
+                "key_derivation_method": report.key_derivation_method,
+                "kdf": describe_key_derivation_method(&report.key_derivation_method),
+                "key_rotated": report
+                    .key_rotated
+                    .map(|time| timestamp_to_datetime(ctx, time))
+                    .unwrap_or_else(|| "Unknown".to_string()),
+            }),
+            &[
+                ("storage_type", "Storage"),
+                ("key_derivation_method", "Key derivation method"),
+                ("kdf", "KDF"),
+                ("key_rotated", "Key rotated"),
+            ],
+        );
+
+        println_succ!(ctx, "Keys:");
+        let keys = report
+            .key_algorithms
+            .iter()
+            .map(|(algorithm, count)| json!({ "algorithm": algorithm, "count": count }))
+            .collect::<Vec<JsonValue>>();
+        print_list_table(
+            ctx,
+            &keys,
+            &[("algorithm", "Algorithm"), ("count", "Keys")],
+            "There are no keys",
+        );
+
+        println_succ!(ctx, "Backups:");
+        let backups = report
+            .backups
+            .iter()
+            .map(|backup| {
+                json!({
+                    "path": backup.path,
+                    "key_derivation_method": backup.key_derivation_method,
+                    "created": timestamp_to_datetime(ctx, backup.created),
+                    "status": if backup.exists() { "Available" } else { "Missing" },
+                })
+            })
+            .collect::<Vec<JsonValue>>();
+        print_list_table(
+            ctx,
+            &backups,
+            &[
+                ("path", "Path"),
+                ("key_derivation_method", "Key derivation method"),
+                ("created", "Created"),
+                ("status", "Status"),
+            ],
+            "There are no backups made with `wallet export`",
+        );
+
+        if report.findings.is_empty() {
+            println_succ!(ctx, "Wallet settings comply with the policy");
+        } else {
+            for finding in &report.findings {
+                println_warn!(ctx, "{}", finding);
+            }
+        }
+
+        trace!("execute <<");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::{
+        commands::{setup, setup_with_wallet, tear_down, tear_down_with_wallet},
+        utils::{environment::EnvironmentUtils, file::write_file},
+    };
+
+    mod security_report {
+        use super::*;
+
+        #[test]
+        pub fn security_report_works() {
+            let ctx = setup_with_wallet();
+            {
+                let cmd = security_report_command::new();
+                let params = CommandParams::new();
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            tear_down_with_wallet(&ctx);
+        }
+
+        #[test]
+        pub fn security_report_works_for_policy() {
+            let ctx = setup_with_wallet();
+            let path = EnvironmentUtils::tmp_file_path("security-policy.json");
+            write_file(
+                &path,
+                r#"{"key_derivation_methods":["raw"],"require_backup":false}"#,
+            )
+            .unwrap();
+            {
+                let cmd = security_report_command::new();
+                let mut params = CommandParams::new();
+                params.insert("policy", path.display().to_string());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            {
+                write_file(&path, r#"{"key_derivation_methods":"raw"}"#).unwrap();
+                let cmd = security_report_command::new();
+                let mut params = CommandParams::new();
+                params.insert("policy", path.display().to_string());
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            std::fs::remove_file(&path).unwrap();
+            tear_down_with_wallet(&ctx);
+        }
+
+        #[test]
+        pub fn security_report_works_for_no_opened_wallet() {
+            let ctx = setup();
+            {
+                let cmd = security_report_command::new();
+                let params = CommandParams::new();
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            tear_down();
+        }
+    }
+}
@@ -5,6 +5,11 @@ use serde_json::Error as SerdeError;
 use std::io::Error as FileSystemError;
 use thiserror::Error;
 
+use crate::{
+    tools::ledger::rejection::{reason_message, Rejection},
+    utils::renderer::Output,
+};
+
 #[derive(Error, Debug)]
 pub enum CliError {
     #[error("`{0}`")]
@@ -15,6 +20,8 @@ pub enum CliError {
     InvalidEntityState(String),
     #[error("Invalid input parameter provided `{0}`")]
     InvalidInput(String),
+    #[error("`{0}`")]
+    Locked(String),
     #[error("Aries Askar error occurred `{0}`")]
     AskarError(AskarError),
     #[error("Aries Askar error occurred `{0}`")]
@@ -67,7 +74,8 @@ impl CliError {
             CliError::InvalidInput(message)
             | CliError::InvalidEntityState(message)
             | CliError::NotFound(message)
-            | CliError::Duplicate(message) => message.to_string(),
+            | CliError::Duplicate(message)
+            | CliError::Locked(message) => message.to_string(),
             CliError::VdrError(vdr_error) => match vdr_error.kind() {
                 VdrErrorKind::Config => "Pool configuration is invalid.".to_string(),
                 VdrErrorKind::Connection => format!(
@@ -94,11 +102,15 @@ impl CliError {
                 }
                 VdrErrorKind::PoolTimeout => format!("Transaction response has not been received"),
                 VdrErrorKind::PoolRequestFailed(reason) => {
-                    let reason = serde_json::from_str::<serde_json::Value>(&reason)
-                        .ok()
-                        .and_then(|value| value["reason"].as_str().map(String::from))
-                        .unwrap_or(reason.to_string());
-                    format!("Transaction has been rejected: {}", reason)
+                    let reason = rejection_reason(reason);
+                    match Rejection::explain(&reason) {
+                        Some(rejection) => format!(
+                            "Transaction has been rejected: {}\nLedger reason: {}",
+                            rejection.explanation,
+                            reason_message(&reason)
+                        ),
+                        None => format!("Transaction has been rejected: {}", reason),
+                    }
                 }
             },
             CliError::AskarError(askar_error) => match askar_error.kind() {
@@ -129,4 +141,150 @@ impl CliError {
             CliError::FileSystemError(fs_error) => fs_error.to_string(),
         }
     }
+
+    /// Stable error code to key support runbooks on (does not depend on the message text).
+    pub fn code(&self) -> &'static str {
+        match self {
+            CliError::Duplicate(_) => "CLI-101",
+            CliError::NotFound(_) => "CLI-102",
+            CliError::InvalidEntityState(_) => "CLI-103",
+            CliError::InvalidInput(_) => "CLI-104",
+            CliError::Locked(_) => "CLI-105",
+            CliError::AskarError(askar_error) => match askar_error.kind() {
+                AskarErrorKind::Backend => "CLI-201",
+                AskarErrorKind::Busy => "CLI-202",
+                AskarErrorKind::Duplicate => "CLI-203",
+                AskarErrorKind::Encryption => "CLI-204",
+                AskarErrorKind::Input => "CLI-205",
+                AskarErrorKind::NotFound => "CLI-206",
+                AskarErrorKind::Custom | AskarErrorKind::Unexpected => "CLI-207",
+                AskarErrorKind::Unsupported => "CLI-208",
+            },
+            CliError::VdrError(vdr_error) => match vdr_error.kind() {
+                VdrErrorKind::Config => "CLI-301",
+                VdrErrorKind::Connection => "CLI-302",
+                VdrErrorKind::FileSystem(_) => "CLI-303",
+                VdrErrorKind::Input => "CLI-304",
+                VdrErrorKind::Resource => "CLI-305",
+                VdrErrorKind::Unavailable => "CLI-306",
+                VdrErrorKind::Unexpected => "CLI-307",
+                VdrErrorKind::Incompatible => "CLI-308",
+                VdrErrorKind::PoolNoConsensus => "CLI-309",
+                VdrErrorKind::PoolTimeout => "CLI-310",
+                VdrErrorKind::PoolRequestFailed(_) => "CLI-311",
+            },
+            CliError::FileSystemError(_) => "CLI-401",
+        }
+    }
+
+    pub fn hint(&self) -> Option<&'static str> {
+        match self {
+            CliError::Duplicate(_) => Some("Use another name or remove the existing entity first."),
+            CliError::NotFound(_) => Some("Check the name or identifier. Use the corresponding `list` command to see available entities."),
+            CliError::InvalidEntityState(_) => None,
+            CliError::InvalidInput(_) => Some("Check the command parameters. Type \"<command> help\" to see the expected format."),
+            CliError::Locked(_) => Some("Close the wallet in the other process or use `wait=<secs>` parameter to wait until it is released."),
+            CliError::AskarError(askar_error) => match askar_error.kind() {
+                AskarErrorKind::Backend => Some("Check that the wallet storage is available and not corrupted."),
+                AskarErrorKind::Busy => Some("The wallet is used by another process. Close it there and try again."),
+                AskarErrorKind::Duplicate => None,
+                AskarErrorKind::Encryption => Some("Check the wallet key and the key derivation method used on wallet creation."),
+                AskarErrorKind::Input => Some("Check the wallet configuration and credentials."),
+                AskarErrorKind::NotFound => None,
+                AskarErrorKind::Custom | AskarErrorKind::Unexpected => None,
+                AskarErrorKind::Unsupported => None,
+            },
+            CliError::VdrError(vdr_error) => match vdr_error.kind() {
+                VdrErrorKind::Config => Some("Check the pool genesis transactions file."),
+                VdrErrorKind::Connection => Some("Connect to the pool using `pool connect` command."),
+                VdrErrorKind::FileSystem(_) => Some("Recreate the pool configuration using `pool create` command."),
+                VdrErrorKind::Input => None,
+                VdrErrorKind::Resource | VdrErrorKind::Unavailable => Some("Check the network connection to the pool nodes and try again."),
+                VdrErrorKind::Unexpected => None,
+                VdrErrorKind::Incompatible => Some("Change the protocol version using `pool set-protocol-version` command."),
+                VdrErrorKind::PoolNoConsensus => Some("Pool nodes may be out of sync. Try again later or use `pool refresh` command."),
+                VdrErrorKind::PoolTimeout => Some("The transaction may still be written. Check the ledger before sending it again."),
+                VdrErrorKind::PoolRequestFailed(reason) => Some(
+                    Rejection::explain(&rejection_reason(reason))
+                        .map(|rejection| rejection.hint)
+                        .unwrap_or("Check the permissions of the submitter DID and the transaction author agreement acceptance."),
+                ),
+            },
+            CliError::FileSystemError(_) => Some("Check that the path exists and the CLI has permissions to access it."),
+        }
+    }
+
+    /// Error for a transaction rejected by the ledger (REJECT or REQNACK reply).
+    pub fn rejected(reason: &str) -> CliError {
+        CliError::VdrError(VdrErrorKind::PoolRequestFailed(reason.to_string()).into())
+    }
+
+    /// Print the error to the user together with its code and remediation hint.
+    pub fn print(&self, output: &dyn Output, extra: Option<&str>) {
+        output
+            .renderer()
+            .error(self.code(), &self.message(extra), self.hint())
+    }
+}
+
+// Failed request carries either the whole ledger reply or the reason only
+fn rejection_reason(reason: &str) -> String {
+    serde_json::from_str::<serde_json::Value>(reason)
+        .ok()
+        .and_then(|value| value["reason"].as_str().map(String::from))
+        .unwrap_or(reason.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn code_works() {
+        assert_eq!(CliError::Duplicate(String::new()).code(), "CLI-101");
+        assert_eq!(CliError::InvalidInput(String::new()).code(), "CLI-104");
+        assert_eq!(CliError::Locked(String::new()).code(), "CLI-105");
+        assert_eq!(
+            CliError::VdrError(VdrErrorKind::PoolTimeout.into()).code(),
+            "CLI-310"
+        );
+        assert_eq!(CliError::rejected("reason").code(), "CLI-311");
+        assert_eq!(
+            CliError::from(FileSystemError::from(std::io::ErrorKind::NotFound)).code(),
+            "CLI-401"
+        );
+    }
+
+    #[test]
+    fn code_does_not_depend_on_message() {
+        assert_eq!(
+            CliError::NotFound("first".to_string()).code(),
+            CliError::NotFound("second".to_string()).code()
+        );
+        assert_eq!(
+            CliError::rejected("first").code(),
+            CliError::rejected("second").code()
+        );
+    }
+
+    #[test]
+    fn hint_works() {
+        assert!(CliError::InvalidInput(String::new()).hint().is_some());
+        assert!(CliError::InvalidEntityState(String::new()).hint().is_none());
+        assert!(CliError::VdrError(VdrErrorKind::Input.into())
+            .hint()
+            .is_none());
+        assert_eq!(
+            CliError::VdrError(VdrErrorKind::Connection.into()).hint(),
+            Some("Connect to the pool using `pool connect` command.")
+        );
+    }
+
+    #[test]
+    fn hint_works_for_rejected_transaction() {
+        assert_eq!(
+            CliError::rejected("unknown reason").hint(),
+            Some("Check the permissions of the submitter DID and the transaction author agreement acceptance.")
+        );
+    }
 }
@@ -0,0 +1,68 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+//! Wallet, DID and ledger request logic of Indy CLI for the use from other Rust tools.
+//!
+//! The binary is built on top of the same modules:
+//! * [`tools::wallet`] - create, open and manage Askar wallets the way CLI does (`~/.indy_client/wallets`).
+//! * [`tools::did`] - create DIDs, rotate keys and sign data with the keys stored in a wallet.
+//! * [`tools::ledger`] - build, sign and submit ledger requests and parse the replies.
+//! * [`tools::pool`] - pool configurations and connections the ledger requests are sent to.
+//!
+//! Functions return [`error::CliResult`]; the interactive parts (prompts, tables, confirmations)
+//! stay in the binary.
+//! Builders take the connected pool as `Option<&Pool>` to apply the network settings
+//! (protocol version, signature scheme); pass `None` to use the defaults.
+//!
+//! ```no_run
+//! use indy_cli_rs::tools::{
+//!     crypto_policy::CryptoPolicy,
+//!     did::Did,
+//!     ledger::Ledger,
+//!     pool::Pool,
+//!     wallet::{wallet_config::WalletConfig, Credentials, Wallet},
+//! };
+//! use indy_utils::did::DidValue;
+//! use indy_vdr::config::PoolConfig;
+//!
+//! let config = WalletConfig::read("steward").unwrap();
+//! let credentials = Credentials {
+//!     key: "wallet key".to_string(),
+//!     key_derivation_method: None,
+//!     rekey: None,
+//!     rekey_derivation_method: None,
+//!     storage_credentials: None,
+//! };
+//! let wallet = Wallet::open(&config, &credentials, CryptoPolicy::default()).unwrap();
+//! let pool = Pool::open("sandbox", PoolConfig::default(), None).unwrap();
+//!
+//! let (did, verkey) = Did::create(&wallet, None, None, None, None).unwrap();
+//! let submitter = DidValue("Th7MpTaRZVRYnPiabds81Y".to_string());
+//! let mut request = Ledger::build_nym_request(
+//!     Some(&pool),
+//!     &submitter,
+//!     &DidValue(did),
+//!     Some(&verkey),
+//!     None,
+//!     None,
+//! )
+//! .unwrap();
+//! let response = Ledger::sign_and_submit_request(&pool, &wallet, &submitter, &mut request).unwrap();
+//! println!("{}", response);
+//!
+//! wallet.close().unwrap();
+//! ```
+#![cfg_attr(feature = "fatal_warnings", deny(warnings))]
+
+#[macro_use]
+extern crate log;
+#[macro_use]
+extern crate serde_derive;
+#[macro_use]
+extern crate serde_json;
+
+#[macro_use]
+pub mod utils;
+pub mod error;
+pub mod tools;
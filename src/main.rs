@@ -6,20 +6,28 @@ extern crate log;
 extern crate serde_derive;
 #[macro_use]
 extern crate serde_json;
-
 #[macro_use]
-mod utils;
+extern crate indy_cli_rs;
+
 mod command_executor;
 mod params_parser;
 #[macro_use]
 mod commands;
-mod error;
-mod tools;
+#[cfg(test)]
+mod test_utils;
+
+use indy_cli_rs::{error, tools, utils};
 
 use crate::{
-    command_executor::CommandExecutor,
-    commands::{common, did, ledger, pool, wallet},
-    utils::history,
+    command_executor::{CommandExecutor, CommandExecutorBuilder, Deprecation},
+    commands::{
+        anoncreds, common, did, explore, fixtures, ledger, monitor, multisig, note, pool, queue,
+        set, template, txn, util, wallet,
+    },
+    tools::wallet::key_rotation::KeyRotationPolicy,
+    utils::{
+        environment::EnvironmentUtils, history, metrics::MetricsServer, renderer::OutputFormat,
+    },
 };
 
 use linefeed::{
@@ -28,10 +36,10 @@ use linefeed::{
 };
 
 use std::{env, fs::File, io::BufReader, sync::Arc};
+use zeroize::Zeroizing;
 
 fn main() {
-    #[cfg(target_os = "windows")]
-    let _ = ansi_term::enable_ansi_support().is_ok();
+    EnvironmentUtils::init_console();
 
     let mut args = env::args();
     args.next(); // skip library
@@ -40,36 +48,89 @@ fn main() {
 
     while let Some(arg) = args.next() {
         match arg.as_str() {
-            "-h" | "--help" => return _print_help(),
+            "-h" | "--help" => return _print_help(&command_executor),
             "--config" => {
                 let file = unwrap_or_return!(
                     args.next(),
-                    println_err!("CLI configuration file is not specified")
+                    println_err!(
+                        command_executor.ctx(),
+                        "CLI configuration file is not specified"
+                    )
                 );
 
                 match CliConfig::read_from_file(&file)
                     .and_then(|config| config.handle(&command_executor))
                 {
                     Ok(()) => {}
-                    Err(err) => return println_err!("{}", err),
+                    Err(err) => return println_err!(command_executor.ctx(), "{}", err),
                 }
             }
             "--logger-config" => {
                 let file = unwrap_or_return!(
                     args.next(),
-                    println_err!("Logger config file is not specified")
+                    println_err!(
+                        command_executor.ctx(),
+                        "Logger config file is not specified"
+                    )
                 );
                 match utils::logger::IndyCliLogger::init(&file) {
                     Ok(()) => println_succ!(
+                        command_executor.ctx(),
                         "Logger has been initialized according to the config file: \"{}\"",
                         file
                     ),
-                    Err(err) => return println_err!("{}", err),
+                    Err(err) => return println_err!(command_executor.ctx(), "{}", err),
+                }
+            }
+            "--otel-endpoint" => {
+                let endpoint = unwrap_or_return!(
+                    args.next(),
+                    println_err!(
+                        command_executor.ctx(),
+                        "OpenTelemetry endpoint is not specified"
+                    )
+                );
+                match set_otel_endpoint(&command_executor, &endpoint) {
+                    Ok(()) => {}
+                    Err(err) => return println_err!(command_executor.ctx(), "{}", err),
+                }
+            }
+            "--serve" => {
+                let address = unwrap_or_return!(
+                    args.next(),
+                    println_err!(command_executor.ctx(), "Metrics address is not specified")
+                );
+                match MetricsServer::start(&address) {
+                    Ok(server) => {
+                        println_succ!(
+                            command_executor.ctx(),
+                            "Prometheus metrics are served at http://{}/metrics",
+                            server.address
+                        );
+                        command_executor.ctx().set_metrics_server(Some(server));
+                    }
+                    Err(err) => return println_err!(command_executor.ctx(), "{}", err),
+                }
+            }
+            "--compat" => {
+                let mode = unwrap_or_return!(
+                    args.next(),
+                    println_err!(
+                        command_executor.ctx(),
+                        "Compatibility mode is not specified"
+                    )
+                );
+                match set_compat_mode(&command_executor, &mode) {
+                    Ok(()) => {}
+                    Err(err) => return println_err!(command_executor.ctx(), "{}", err),
                 }
             }
             "--plugins" => {
-                unwrap_or_return!(args.next(), println_err!("Plugins are not specified"));
-                println_warn!("Option DEPRECATED!");
+                unwrap_or_return!(
+                    args.next(),
+                    println_err!(command_executor.ctx(), "Plugins are not specified")
+                );
+                println_warn!(command_executor.ctx(), "Option DEPRECATED!");
             }
             _ if args.len() == 0 => {
                 execute_batch(&command_executor, Some(&arg));
@@ -79,8 +140,8 @@ fn main() {
                 }
             }
             _ => {
-                println_err!("Unknown option");
-                return _print_help();
+                println_err!(command_executor.ctx(), "Unknown option");
+                return _print_help(&command_executor);
             }
         }
     }
@@ -93,6 +154,17 @@ fn main() {
 pub struct CliConfig {
     pub logger_config: Option<String>,
     pub taa_acceptance_mechanism: Option<String>,
+    pub taa_auto_accept: Option<bool>,
+    pub encrypt_history: Option<bool>,
+    pub otel_endpoint: Option<String>,
+    pub output_format: Option<String>,
+    pub pager: Option<bool>,
+    pub secure_mode: Option<bool>,
+    pub prompt_warnings: Option<Vec<String>>,
+    pub production_pools: Option<Vec<String>>,
+    pub wallet_key_max_age_days: Option<u64>,
+    pub wallet_key_rotation_enforce: Option<bool>,
+    pub compat: Option<String>,
 }
 
 impl CliConfig {
@@ -107,6 +179,7 @@ impl CliConfig {
         if let Some(ref logger_config) = self.logger_config {
             utils::logger::IndyCliLogger::init(logger_config)?;
             println_succ!(
+                command_executor.ctx(),
                 "Logger has been initialized according to the config file: \"{}\"",
                 logger_config
             );
@@ -116,82 +189,347 @@ impl CliConfig {
                 .ctx()
                 .set_taa_acceptance_mechanism(taa_acceptance_mechanism);
             println_succ!(
+                command_executor.ctx(),
                 "\"{}\" is used as transaction author agreement acceptance mechanism",
                 taa_acceptance_mechanism
             );
         }
+        if let Some(true) = self.taa_auto_accept {
+            if self.taa_acceptance_mechanism.is_none() {
+                return Err(
+                    "\"taaAutoAccept\" requires \"taaAcceptanceMechanism\" to be set".to_string(),
+                );
+            }
+            command_executor.ctx().set_taa_auto_accept(true);
+            println_succ!(
+                command_executor.ctx(),
+                "Transaction author agreement will be accepted automatically on pool connection"
+            );
+        }
+        if let Some(true) = self.encrypt_history {
+            command_executor.ctx().set_history_encryption(true);
+            println_succ!(
+                command_executor.ctx(),
+                "CLI history will be stored encrypted"
+            );
+        }
+        if let Some(ref otel_endpoint) = self.otel_endpoint {
+            set_otel_endpoint(command_executor, otel_endpoint)?;
+        }
+        if let Some(ref output_format) = self.output_format {
+            let format = OutputFormat::from_str(output_format).ok_or_else(|| {
+                format!(
+                    "Unsupported output format \"{}\". Supported formats: {}",
+                    output_format,
+                    OutputFormat::NAMES.join(", ")
+                )
+            })?;
+            command_executor.ctx().set_output_format(format);
+        }
+        if let Some(pager) = self.pager {
+            command_executor.ctx().set_pager(pager);
+        }
+        if let Some(true) = self.secure_mode {
+            command_executor.ctx().set_secure_mode(true);
+            println_succ!(
+                command_executor.ctx(),
+                "Commands accepting secrets will not be stored in CLI history"
+            );
+        }
+        if let Some(ref prompt_warnings) = self.prompt_warnings {
+            if let Some(warning) = prompt_warnings
+                .iter()
+                .find(|warning| !common::PROMPT_WARNINGS.contains(&warning.as_str()))
+            {
+                return Err(format!(
+                    "Unsupported prompt warning \"{}\". Supported warnings: {}",
+                    warning,
+                    common::PROMPT_WARNINGS.join(", ")
+                ));
+            }
+            command_executor.ctx().set_prompt_warnings(prompt_warnings);
+        }
+        if let Some(ref production_pools) = self.production_pools {
+            command_executor
+                .ctx()
+                .set_production_pools(production_pools);
+        }
+        if let Some(max_age_days) = self.wallet_key_max_age_days {
+            let enforce = self.wallet_key_rotation_enforce.unwrap_or(false);
+            command_executor
+                .ctx()
+                .set_key_rotation_policy(Some(KeyRotationPolicy {
+                    max_age_days,
+                    enforce,
+                }));
+            println_succ!(
+                command_executor.ctx(),
+                "Wallet keys older than {} days will be {}",
+                max_age_days,
+                if enforce {
+                    "blocked from signing"
+                } else {
+                    "reported"
+                }
+            );
+        } else if self.wallet_key_rotation_enforce.is_some() {
+            return Err(
+                "\"walletKeyRotationEnforce\" requires \"walletKeyMaxAgeDays\" to be set"
+                    .to_string(),
+            );
+        }
+        if let Some(ref compat) = self.compat {
+            set_compat_mode(command_executor, compat)?;
+        }
         Ok(())
     }
 }
 
+fn set_compat_mode(command_executor: &CommandExecutor, mode: &str) -> Result<(), String> {
+    match mode {
+        "indy-cli" => {
+            command_executor.ctx().set_indy_cli_compat(true);
+            println_succ!(
+                command_executor.ctx(),
+                "Scripts written for indy-cli are run in compatibility mode"
+            );
+            Ok(())
+        }
+        _ => Err(format!(
+            "Unsupported compatibility mode \"{}\". Supported modes: indy-cli",
+            mode
+        )),
+    }
+}
+
+fn set_otel_endpoint(command_executor: &CommandExecutor, endpoint: &str) -> Result<(), String> {
+    utils::telemetry::validate_endpoint(endpoint)?;
+    command_executor
+        .ctx()
+        .set_otel_endpoint(Some(endpoint.to_string()));
+    println_succ!(
+        command_executor.ctx(),
+        "Command execution traces will be exported to \"{}\"",
+        endpoint
+    );
+    Ok(())
+}
+
 fn build_executor() -> CommandExecutor {
-    CommandExecutor::build()
+    let executor = CommandExecutor::build()
         .add_command(common::about_command::new())
         .add_command(common::exit_command::new())
         .add_command(common::prompt_command::new())
         .add_command(common::show_command::new())
+        .add_command(common::whoami_command::new())
+        .add_command(common::sleep_command::new())
         .add_command(common::load_plugin_command::new())
         .add_command(common::init_logger_command::new())
+        .add_command(common::doctor_command::new())
+        .add_command(common::self_update_command::new())
+        .add_command(common::selftest_command::new())
         .add_group(did::group::new())
         .add_command(did::new_command::new())
         .add_command(did::set_metadata_command::new())
         .add_command(did::import_command::new())
+        .add_command(did::import_hardware_command::new())
         .add_command(did::use_command::new())
         .add_command(did::rotate_key_command::new())
         .add_command(did::list_command::new())
+        .add_command(did::show_command::new())
+        .add_command(did::sign_command::new())
+        .add_command(did::verify_command::new())
+        .add_command(did::resolve_command::new())
+        .add_command(did::qr_command::new())
+        .add_command(did::export_paper_command::new())
+        .add_command(did::import_paper_command::new())
         .add_command(did::qualify_command::new())
+        .add_command(did::qualify_all_command::new())
         .finalize_group()
         .add_group(pool::group::new())
         .add_command(pool::create_command::new())
         .add_command(pool::connect_command::new())
         .add_command(pool::refresh_command::new())
+        .add_command(pool::refresh_capabilities_command::new())
         .add_command(pool::list_command::new())
         .add_command(pool::disconnect_command::new())
         .add_command(pool::delete_command::new())
         .add_command(pool::show_taa_command::new())
         .add_command(pool::set_protocol_version_command::new())
+        .add_command(pool::set_endorser_command::new())
+        .add_command(pool::set_signature_scheme_command::new())
+        .add_command(pool::set_attrib_schema_command::new())
+        .add_command(pool::attrib_schemas_command::new())
+        .add_command(pool::latency_stats_command::new())
         .finalize_group()
         .add_group(wallet::group::new())
         .add_command(wallet::create_command::new())
         .add_command(wallet::attach_command::new())
         .add_command(wallet::open_command::new())
+        .add_command(wallet::rekey_command::new())
         .add_command(wallet::list_command::new())
         .add_command(wallet::close_command::new())
         .add_command(wallet::delete_command::new())
         .add_command(wallet::detach_command::new())
         .add_command(wallet::export_command::new())
+        .add_command(wallet::export_agent_config_command::new())
         .add_command(wallet::import_command::new())
+        .add_command(wallet::migrate_command::new())
+        .add_command(wallet::reindex_command::new())
+        .add_command(wallet::security_report_command::new())
         .finalize_group()
         .add_group(ledger::group::new())
         .add_command(ledger::nym::nym_command::new())
         .add_command(ledger::nym::get_nym_command::new())
+        .add_command(ledger::get_txn::get_txn_command::new())
         .add_command(ledger::attrib::attrib_command::new())
         .add_command(ledger::attrib::get_attrib_command::new())
+        .add_command(ledger::attrib::register_agent_command::new())
+        .add_command(ledger::attrib::clear_attrib_command::new())
         .add_command(ledger::schema::schema_command::new())
         .add_command(ledger::schema::get_schema_command::new())
         .add_command(ledger::validator_info::get_validator_info_command::new())
         .add_command(ledger::cred_def::cred_def_command::new())
         .add_command(ledger::cred_def::get_cred_def_command::new())
+        .add_command(ledger::revoc_reg_def::revoc_reg_def_command::new())
+        .add_command(ledger::revoc_reg_def::get_revoc_reg_def_command::new())
+        .add_command(ledger::prefetch_command::new())
+        .add_command(ledger::revocation_status_command::new())
         .add_command(ledger::node::node_command::new())
         .add_command(ledger::pool_config::pool_config_command::new())
         .add_command(ledger::pool_restart::pool_restart_command::new())
         .add_command(ledger::pool_upgrade::pool_upgrade_command::new())
         .add_command(ledger::custom::custom_command::new())
         .add_command(ledger::sign_multi::sign_multi_command::new())
+        .add_command(ledger::digest::digest_command::new())
+        .add_command(ledger::verify_signatures::verify_signatures_command::new())
+        .add_command(ledger::search::search_command::new())
         .add_command(ledger::auth_rule::auth_rule_command::new())
         .add_command(ledger::auth_rule::auth_rules_command::new())
         .add_command(ledger::auth_rule::get_auth_rule_command::new())
         .add_command(ledger::transaction::save_transaction_command::new())
         .add_command(ledger::transaction::load_transaction_command::new())
+        .add_command(ledger::transaction::last_transaction_command::new())
+        .add_command(ledger::batch::batch_command::new())
+        .add_command(ledger::revoke_roles::revoke_roles_command::new())
         .add_command(ledger::transaction_author_agreement::taa_command::new())
         .add_command(ledger::transaction_author_agreement::aml_command::new())
         .add_command(ledger::transaction_author_agreement::get_acceptance_mechanisms_command::new())
         .add_command(ledger::endorser::endorse_transaction_command::new())
         .add_command(ledger::transaction_author_agreement::taa_disable_all_command::new())
+        .add_command(ledger::transaction_author_agreement::taa_diff_command::new())
         .add_command(ledger::frozen_ledger::ledgers_freeze_command::new())
         .add_command(ledger::frozen_ledger::get_frozen_ledgers_command::new())
+        .add_command(ledger::flag::flag_command::new())
+        .add_command(ledger::flag::get_flag_command::new())
+        .finalize_group()
+        .add_group(explore::group::new())
+        .add_command(explore::txn_command::new())
+        .add_command(explore::domain_command::new())
+        .add_command(explore::pool_command::new())
+        .add_command(explore::config_command::new())
+        .add_command(explore::search_command::new())
+        .finalize_group()
+        .add_group(multisig::group::new())
+        .add_command(multisig::create_command::new())
+        .add_command(multisig::export_command::new())
+        .add_command(multisig::sign_command::new())
+        .add_command(multisig::collect_command::new())
+        .add_command(multisig::status_command::new())
+        .add_command(multisig::send_command::new())
+        .add_command(multisig::delete_command::new())
+        .finalize_group()
+        .add_group(note::group::new())
+        .add_command(note::add_command::new())
+        .add_command(note::list_command::new())
+        .finalize_group()
+        .add_group(template::group::new())
+        .add_command(template::save_command::new())
+        .add_command(template::list_command::new())
+        .add_command(template::apply_command::new())
+        .finalize_group()
+        .add_group(txn::group::new())
+        .add_command(txn::list_command::new())
+        .add_command(txn::show_command::new())
+        .add_command(txn::delete_command::new())
+        .finalize_group()
+        .add_group(queue::group::new())
+        .add_command(queue::list_command::new())
+        .add_command(queue::flush_command::new())
+        .finalize_group()
+        .add_group(fixtures::group::new())
+        .add_command(fixtures::generate_command::new())
+        .finalize_group()
+        .add_group(monitor::group::new())
+        .add_command(monitor::start_command::new())
+        .finalize_group()
+        .add_group(set::group::new())
+        .add_command(set::crypto_policy_command::new())
+        .add_command(set::freshness_threshold_command::new())
+        .add_command(set::key_rotation_command::new())
+        .add_command(set::output_format_command::new())
+        .add_command(set::write_rate_command::new())
+        .finalize_group()
+        .add_group(anoncreds::group::new())
+        .add_command(anoncreds::id_command::new())
+        .add_command(anoncreds::create_link_secret_command::new())
+        .add_command(anoncreds::create_offer_command::new())
+        .add_command(anoncreds::create_request_command::new())
+        .add_command(anoncreds::issue_command::new())
+        .add_command(anoncreds::store_credential_command::new())
+        .add_command(anoncreds::create_proof_command::new())
+        .add_command(anoncreds::verify_proof_command::new())
+        .add_command(anoncreds::tails_hash_command::new())
+        .add_command(anoncreds::upload_tails_command::new())
         .finalize_group()
-        .finalize()
+        .add_group(util::group::new())
+        .add_command(util::encode_command::new())
+        .add_command(util::decode_command::new())
+        .add_command(util::verkey_from_seed_command::new())
+        .finalize_group();
+
+    add_deprecations(executor).finalize()
+}
+
+// Syntax of libindy based indy-cli which is not supported anymore
+fn add_deprecations(mut executor: CommandExecutorBuilder) -> CommandExecutorBuilder {
+    const PAYMENTS_NOTE: &str = "Payment plugins are not supported by indy-cli-rs.";
+
+    for command in [
+        "payment-address create",
+        "payment-address list",
+        "payment-address sign",
+        "payment-address verify",
+        "ledger get-payment-sources",
+        "ledger payment",
+        "ledger get-fees",
+        "ledger mint-prepare",
+        "ledger set-fees-prepare",
+        "ledger verify-payment-receipt",
+    ] {
+        executor = executor.add_deprecation(Deprecation::Removed {
+            command,
+            note: PAYMENTS_NOTE,
+        });
+    }
+
+    for command in [
+        "ledger nym",
+        "ledger attrib",
+        "ledger schema",
+        "ledger cred-def",
+    ] {
+        for old in ["fees_inputs", "fees_outputs", "extra"] {
+            executor = executor.add_deprecation(Deprecation::Param {
+                command,
+                old,
+                new: None,
+                note: "The transaction is sent without fees as payment plugins are not supported by indy-cli-rs.",
+            });
+        }
+    }
+
+    executor
 }
 
 fn execute_stdin(command_executor: CommandExecutor) {
@@ -207,23 +545,69 @@ where
 {
     let command_executor = Arc::new(command_executor);
     reader.set_completer(command_executor.clone());
+    common::update_prompt_warnings(command_executor.ctx());
     reader.set_prompt(&command_executor.ctx().get_prompt()).ok();
-    history::load(&mut reader).ok();
+    utils::pager::set_enabled(command_executor.ctx().is_pager_enabled());
+
+    let history_passphrase = if command_executor.ctx().is_history_encryption_enabled() {
+        match history::read_passphrase().and_then(|passphrase| {
+            history::load(&mut reader, Some(&passphrase)).map(|_| passphrase)
+        }) {
+            Ok(passphrase) => Some(passphrase),
+            Err(err) => {
+                println_err!(command_executor.ctx(), "{}", err);
+                println_warn!(
+                    command_executor.ctx(),
+                    "CLI history will not be stored for this session"
+                );
+                None
+            }
+        }
+    } else if history::is_encrypted() {
+        // Plain history of this session would overwrite the encrypted one
+        println_warn!(
+            command_executor.ctx(),
+            "CLI history is encrypted. Enable \"encryptHistory\" in the CLI config to use it"
+        );
+        println_warn!(
+            command_executor.ctx(),
+            "CLI history will not be stored for this session"
+        );
+        None
+    } else {
+        history::load(&mut reader, None).ok();
+        None
+    };
+    let persist_history = |reader: &Interface<T>| match history_passphrase {
+        Some(ref passphrase) => history::persist(reader, Some(passphrase)).ok(),
+        None if command_executor.ctx().is_history_encryption_enabled() => None,
+        None if history::is_encrypted() => None,
+        None => history::persist(reader, None).ok(),
+    };
 
     while let Ok(read_result) = reader.read_line() {
         match read_result {
             ReadResult::Input(line) => {
-                let line = line.trim();
+                let input = Zeroizing::new(line);
+                let line = input.trim();
                 if line.is_empty() {
                     continue;
                 }
 
                 let _ = command_executor.execute(&line).is_ok();
-                history::add(line, &reader).ok();
+                // Secrets typed on the line are never kept, secure mode also skips the commands
+                // whose secrets have been entered on prompt
+                let is_secret = CommandExecutor::has_inline_secret(line)
+                    || (command_executor.ctx().is_secure_mode()
+                        && command_executor.is_secret_bearing(line));
+                if !is_secret {
+                    history::add(line, &reader).ok();
+                }
+                common::update_prompt_warnings(command_executor.ctx());
                 reader.set_prompt(&command_executor.ctx().get_prompt()).ok();
 
                 if command_executor.ctx().is_exit() {
-                    history::persist(&reader).ok();
+                    persist_history(&reader);
                     break;
                 }
             }
@@ -231,7 +615,7 @@ where
             | ReadResult::Signal(Signal::Quit)
             | ReadResult::Signal(Signal::Break)
             | ReadResult::Signal(Signal::Interrupt) => {
-                history::persist(&reader).ok();
+                persist_history(&reader);
                 break;
             }
             _ => break,
@@ -241,11 +625,18 @@ where
 
 fn execute_batch(command_executor: &CommandExecutor, script_path: Option<&str>) {
     command_executor.ctx().set_batch_mode();
+    let pager_enabled = utils::pager::is_enabled();
+    utils::pager::set_enabled(false);
     if let Some(script_path) = script_path {
         let file = match File::open(script_path) {
             Ok(file) => file,
             Err(err) => {
-                return println_err!("Can't open script file {}\nError: {}", script_path, err)
+                return println_err!(
+                    command_executor.ctx(),
+                    "Can't open script file {}\nError: {}",
+                    script_path,
+                    err
+                )
             }
         };
         _iter_batch(command_executor, BufReader::new(file));
@@ -253,37 +644,80 @@ fn execute_batch(command_executor: &CommandExecutor, script_path: Option<&str>)
         let stdin = std::io::stdin();
         _iter_batch(command_executor, stdin.lock());
     };
+    utils::pager::set_enabled(pager_enabled);
     command_executor.ctx().set_not_batch_mode();
 }
 
-fn _print_help() {
-    println_acc!("Hyperledger Indy CLI");
+fn _print_help(command_executor: &CommandExecutor) {
+    println_acc!(command_executor.ctx(), "Hyperledger Indy CLI");
     println!();
-    println_acc!("CLI supports 2 execution modes:");
+    println_acc!(command_executor.ctx(), "CLI supports 2 execution modes:");
     println_acc!(
+        command_executor.ctx(),
         "\tInteractive - reads commands from terminal. To start just run indy-cli-rs without params."
     );
-    println_acc!("\tUsage: indy-cli-rs");
+    println_acc!(command_executor.ctx(), "\tUsage: indy-cli-rs");
     println!();
     println_acc!(
+        command_executor.ctx(),
         "\tBatch - all commands will be read from text file or pipe and executed in series."
     );
-    println_acc!("\tUsage: indy-cli-rs <path-to-text-file>");
+    println_acc!(
+        command_executor.ctx(),
+        "\tUsage: indy-cli-rs <path-to-text-file>"
+    );
     println!();
-    println_acc!("Options:");
-    println_acc!("\tLoad plugins in Libindy.");
-    println_acc!("\tUsage: indy-cli-rs --plugins <lib-1-name>:<init-func-1-name>,...,<lib-n-name>:<init-func-n-name>");
+    println_acc!(command_executor.ctx(), "Options:");
+    println_acc!(command_executor.ctx(), "\tLoad plugins in Libindy.");
+    println_acc!(command_executor.ctx(), "\tUsage: indy-cli-rs --plugins <lib-1-name>:<init-func-1-name>,...,<lib-n-name>:<init-func-n-name>");
     println!();
-    println_acc!("\tInit logger according to a config file. \n\tIndy Cli uses `log4rs` logging framework: https://crates.io/crates/log4rs");
-    println_acc!("\tUsage: indy-cli-rs --logger-config <path-to-config-file>");
+    println_acc!(command_executor.ctx(), "\tInit logger according to a config file. \n\tIndy Cli uses `log4rs` logging framework: https://crates.io/crates/log4rs");
+    println_acc!(
+        command_executor.ctx(),
+        "\tUsage: indy-cli-rs --logger-config <path-to-config-file>"
+    );
     println!();
     println_acc!(
+        command_executor.ctx(),
+        "\tExport command execution traces to an OpenTelemetry collector (OTLP/HTTP)."
+    );
+    println_acc!(
+        command_executor.ctx(),
+        "\tUsage: indy-cli-rs --otel-endpoint <http://collector-host:4318>"
+    );
+    println!();
+    println_acc!(
+        command_executor.ctx(),
+        "\tServe Prometheus metrics of executed commands, ledger requests and `monitor start` collections at `/metrics` path."
+    );
+    println_acc!(
+        command_executor.ctx(),
+        "\tUsage: indy-cli-rs --serve <0.0.0.0:9100>"
+    );
+    println!();
+    println_acc!(command_executor.ctx(), "\tRun scripts written for libindy based indy-cli: payment commands are skipped with a warning instead of failing.");
+    println_acc!(
+        command_executor.ctx(),
+        "\tUsage: indy-cli-rs --compat indy-cli <path-to-text-file>"
+    );
+    println!();
+    println_acc!(
+        command_executor.ctx(),
         "\tUse config file for CLI initialization. A config file can contain the following fields:"
     );
-    println_acc!("\t\tplugins - a list of plugins to load in Libindy (is equal to usage of \"--plugins\" option).");
-    println_acc!("\t\tloggerConfig - path to a logger config file (is equal to usage of \"--logger-config\" option).");
-    println_acc!("\t\ttaaAcceptanceMechanism - transaction author agreement acceptance mechanism to use for sending write transactions to the Ledger.");
-    println_acc!("\tUsage: indy-cli-rs --config <path-to-config-json-file>");
+    println_acc!(command_executor.ctx(), "\t\tplugins - a list of plugins to load in Libindy (is equal to usage of \"--plugins\" option).");
+    println_acc!(command_executor.ctx(), "\t\tloggerConfig - path to a logger config file (is equal to usage of \"--logger-config\" option).");
+    println_acc!(command_executor.ctx(), "\t\ttaaAcceptanceMechanism - transaction author agreement acceptance mechanism to use for sending write transactions to the Ledger.");
+    println_acc!(command_executor.ctx(), "\t\tencryptHistory - encrypt the persisted CLI history with a passphrase requested on start (the wallet key can be used as the passphrase).");
+    println_acc!(command_executor.ctx(), "\t\totelEndpoint - OpenTelemetry collector to export command execution traces to (is equal to usage of \"--otel-endpoint\" option).");
+    println_acc!(command_executor.ctx(), "\t\toutputFormat - format of command output: \"human\" (default), \"json\", \"csv\" or \"quiet\". Errors carry stable codes.");
+    println_acc!(command_executor.ctx(), "\t\tpager - show output exceeding the terminal height through $PAGER (or the internal pager) in interactive mode (default true).");
+    println_acc!(command_executor.ctx(), "\t\tcompat - compatibility mode to run scripts of other CLIs: \"indy-cli\" (is equal to usage of \"--compat\" option).");
+    println_acc!(command_executor.ctx(), "\t\tsecureMode - do not store commands accepting secrets (seeds, wallet keys) in CLI history even if secrets are entered on prompt.");
+    println_acc!(
+        command_executor.ctx(),
+        "\tUsage: indy-cli-rs --config <path-to-config-json-file>"
+    );
     println!();
 }
 
@@ -294,9 +728,9 @@ where
     let mut line_num = 1;
     for line in reader.lines() {
         let line = if let Ok(line) = line {
-            line
+            Zeroizing::new(line)
         } else {
-            return println_err!("Can't parse line #{}", line_num);
+            return println_err!(command_executor.ctx(), "Can't parse line #{}", line_num);
         };
 
         if line.starts_with('#') || line.is_empty() {
@@ -311,7 +745,11 @@ where
             (line[0..].as_ref(), false)
         };
         if command_executor.execute(line).is_err() && !force {
-            return println_err!("Batch execution failed at line #{}", line_num);
+            return println_err!(
+                command_executor.ctx(),
+                "Batch execution failed at line #{}",
+                line_num
+            );
         }
         println!();
         line_num += 1;
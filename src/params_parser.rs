@@ -2,7 +2,7 @@
     Copyright © 2023 Province of British Columbia
     https://digital.gov.bc.ca/digital-trust
 */
-use crate::command_executor::CommandParams;
+use crate::command_executor::{CommandContext, CommandParams};
 
 use indy_utils::{did::DidValue, Qualifiable};
 use std::{fmt::Display, str::FromStr};
@@ -10,27 +10,32 @@ use std::{fmt::Display, str::FromStr};
 pub struct ParamParser;
 
 impl ParamParser {
-    pub fn get_str_param<'a>(name: &'a str, params: &'a CommandParams) -> Result<&'a str, ()> {
+    pub fn get_str_param<'a>(
+        ctx: &CommandContext,
+        name: &'a str,
+        params: &'a CommandParams,
+    ) -> Result<&'a str, ()> {
         match params.get(name) {
             Some(v) if v == "" => {
-                println_err!("Required \"{}\" parameter is empty.", name);
+                println_err!(ctx, "Required \"{}\" parameter is empty.", name);
                 Err(())
             }
             Some(v) => Ok(v.as_str()),
             None => {
-                println_err!("No required \"{}\" parameter present.", name);
+                println_err!(ctx, "No required \"{}\" parameter present.", name);
                 Err(())
             }
         }
     }
 
     pub fn get_opt_str_param<'a>(
+        ctx: &CommandContext,
         key: &'a str,
         params: &'a CommandParams,
     ) -> Result<Option<&'a str>, ()> {
         match params.get(key) {
             Some(v) if v == "" => {
-                println_err!("Optional parameter \"{}\" is empty.", key);
+                println_err!(ctx, "Optional parameter \"{}\" is empty.", key);
                 Err(())
             }
             Some(v) => Ok(Some(v.as_str())),
@@ -45,23 +50,36 @@ impl ParamParser {
         Ok(params.get(key).map(|v| v.as_str()))
     }
 
-    pub fn _get_int_param<T>(name: &str, params: &CommandParams) -> Result<T, ()>
+    pub fn _get_int_param<T>(
+        ctx: &CommandContext,
+        name: &str,
+        params: &CommandParams,
+    ) -> Result<T, ()>
     where
         T: FromStr,
         <T as FromStr>::Err: Display,
     {
         match params.get(name) {
             Some(v) => Ok(v.parse::<T>().map_err(|err| {
-                println_err!("Can't parse integer parameter \"{}\": err {}", name, err)
+                println_err!(
+                    ctx,
+                    "Can't parse integer parameter \"{}\": err {}",
+                    name,
+                    err
+                )
             })?),
             None => {
-                println_err!("No required \"{}\" parameter present", name);
+                println_err!(ctx, "No required \"{}\" parameter present", name);
                 Err(())
             }
         }
     }
 
-    pub fn get_number_param<T>(key: &str, params: &CommandParams) -> Result<T, ()>
+    pub fn get_number_param<T>(
+        ctx: &CommandContext,
+        key: &str,
+        params: &CommandParams,
+    ) -> Result<T, ()>
     where
         T: FromStr,
         <T as FromStr>::Err: Display,
@@ -69,6 +87,7 @@ impl ParamParser {
         match params.get(key) {
             Some(value) => value.parse::<T>().map_err(|err| {
                 println_err!(
+                    ctx,
                     "Can't parse number parameter \"{}\": value: \"{}\", err \"{}\"",
                     key,
                     value,
@@ -76,13 +95,17 @@ impl ParamParser {
                 )
             }),
             None => {
-                println_err!("No required \"{}\" parameter present", key);
+                println_err!(ctx, "No required \"{}\" parameter present", key);
                 Err(())
             }
         }
     }
 
-    pub fn get_opt_number_param<T>(key: &str, params: &CommandParams) -> Result<Option<T>, ()>
+    pub fn get_opt_number_param<T>(
+        ctx: &CommandContext,
+        key: &str,
+        params: &CommandParams,
+    ) -> Result<Option<T>, ()>
     where
         T: FromStr,
         <T as FromStr>::Err: Display,
@@ -90,6 +113,7 @@ impl ParamParser {
         let res = match params.get(key) {
             Some(value) => Some(value.parse::<T>().map_err(|err| {
                 println_err!(
+                    ctx,
                     "Can't parse number parameter \"{}\": value: \"{}\", err \"{}\"",
                     key,
                     value,
@@ -101,38 +125,47 @@ impl ParamParser {
         Ok(res)
     }
 
-    pub fn get_bool_param(key: &str, params: &CommandParams) -> Result<bool, ()> {
+    pub fn get_bool_param(
+        ctx: &CommandContext,
+        key: &str,
+        params: &CommandParams,
+    ) -> Result<bool, ()> {
         match params.get(key) {
             Some(value) => Ok(value.parse::<bool>().map_err(|err| {
-                println_err!("Can't parse bool parameter \"{}\": err {}", key, err)
+                println_err!(ctx, "Can't parse bool parameter \"{}\": err {}", key, err)
             })?),
             None => {
-                println_err!("No required \"{}\" parameter present", key);
+                println_err!(ctx, "No required \"{}\" parameter present", key);
                 Err(())
             }
         }
     }
 
-    pub fn get_opt_bool_param(key: &str, params: &CommandParams) -> Result<Option<bool>, ()> {
+    pub fn get_opt_bool_param(
+        ctx: &CommandContext,
+        key: &str,
+        params: &CommandParams,
+    ) -> Result<Option<bool>, ()> {
         match params.get(key) {
             Some(value) => Ok(Some(value.parse::<bool>().map_err(|err| {
-                println_err!("Can't parse bool parameter \"{}\": err {}", key, err)
+                println_err!(ctx, "Can't parse bool parameter \"{}\": err {}", key, err)
             })?)),
             None => Ok(None),
         }
     }
 
     pub fn get_str_array_param<'a>(
+        ctx: &CommandContext,
         name: &'a str,
         params: &'a CommandParams,
     ) -> Result<Vec<&'a str>, ()> {
         match params.get(name) {
             None => {
-                println_err!("No required \"{}\" parameter present", name);
+                println_err!(ctx, "No required \"{}\" parameter present", name);
                 Err(())
             }
             Some(v) if v.is_empty() => {
-                println_err!("No required \"{}\" parameter present", name);
+                println_err!(ctx, "No required \"{}\" parameter present", name);
                 Err(())
             }
             Some(v) => Ok(v.split(',').collect::<Vec<&'a str>>()),
@@ -156,31 +189,39 @@ impl ParamParser {
     }
 
     pub fn get_object_param<'a>(
+        ctx: &CommandContext,
         name: &'a str,
         params: &'a CommandParams,
     ) -> Result<serde_json::Value, ()> {
         match params.get(name) {
             Some(v) => Ok(serde_json::from_str(v).map_err(|err| {
-                println_err!("Can't parse object parameter \"{}\": err {}", name, err)
+                println_err!(
+                    ctx,
+                    "Can't parse object parameter \"{}\": err {}",
+                    name,
+                    err
+                )
             })?),
             None => {
-                println_err!("No required \"{}\" parameter present", name);
+                println_err!(ctx, "No required \"{}\" parameter present", name);
                 Err(())
             }
         }
     }
 
     pub fn get_opt_object_param<'a>(
+        ctx: &CommandContext,
         name: &'a str,
         params: &'a CommandParams,
     ) -> Result<Option<serde_json::Value>, ()> {
         match params.get(name) {
-            Some(_) => Ok(Some(ParamParser::get_object_param(name, params)?)),
+            Some(_) => Ok(Some(ParamParser::get_object_param(ctx, name, params)?)),
             None => Ok(None),
         }
     }
 
     pub fn get_number_tuple_array_param<'a>(
+        ctx: &CommandContext,
         name: &'a str,
         params: &'a CommandParams,
     ) -> Result<Vec<u64>, ()> {
@@ -188,7 +229,7 @@ impl ParamParser {
             Some(v) if !v.is_empty() => {
                 let tuples: Vec<&str> = v.split(",").collect();
                 if tuples.is_empty() {
-                    println_err!("Parameter \"{}\" has invalid format", name);
+                    println_err!(ctx, "Parameter \"{}\" has invalid format", name);
                     Err(())
                 } else {
                     let mut result: Vec<u64> = Vec::new();
@@ -196,6 +237,7 @@ impl ParamParser {
                         println!("{:?}", item);
                         result.push(item.parse::<u64>().map_err(|err| {
                             println_err!(
+                                ctx,
                                 "Can't parse number parameter \"{}\": value: \"{}\", err \"{}\"",
                                 name,
                                 item,
@@ -207,26 +249,32 @@ impl ParamParser {
                 }
             }
             _ => {
-                println_err!("No required \"{}\" parameter present", name);
+                println_err!(ctx, "No required \"{}\" parameter present", name);
                 Err(())
             }
         }
     }
 
-    pub fn get_did_param<'a>(name: &'a str, params: &'a CommandParams) -> Result<DidValue, ()> {
-        let did_str = ParamParser::get_str_param(name, params)?;
-        DidValue::from_str(did_str).map_err(|_| println_err!("Invalid DID {} provided", did_str))
+    pub fn get_did_param<'a>(
+        ctx: &CommandContext,
+        name: &'a str,
+        params: &'a CommandParams,
+    ) -> Result<DidValue, ()> {
+        let did_str = ParamParser::get_str_param(ctx, name, params)?;
+        DidValue::from_str(did_str)
+            .map_err(|_| println_err!(ctx, "Invalid DID {} provided", did_str))
     }
 
     pub fn get_opt_did_param<'a>(
+        ctx: &CommandContext,
         name: &'a str,
         params: &'a CommandParams,
     ) -> Result<Option<DidValue>, ()> {
-        let did_str = ParamParser::get_opt_str_param(name, params)?;
+        let did_str = ParamParser::get_opt_str_param(ctx, name, params)?;
         match did_str {
             Some(did_str) => {
                 Ok(Some(DidValue::from_str(did_str).map_err(|_| {
-                    println_err!("Invalid DID {} provided", did_str)
+                    println_err!(ctx, "Invalid DID {} provided", did_str)
                 })?))
             }
             None => Ok(None),
@@ -0,0 +1,742 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+pub mod tails;
+
+use crate::{
+    error::{CliError, CliResult},
+    tools::{
+        anoncreds_id::{AnonCredsId, AnonCredsObject},
+        wallet::Wallet,
+    },
+    utils::futures::block_on,
+};
+
+use anoncreds::{
+    data_types::{cred_def::CredentialDefinitionId, schema::SchemaId},
+    issuer::{create_credential, create_credential_definition, create_credential_offer},
+    prover::{
+        create_credential_request, create_link_secret, create_presentation, process_credential,
+    },
+    types::{
+        Credential, CredentialDefinition, CredentialDefinitionConfig, CredentialDefinitionPrivate,
+        CredentialKeyCorrectnessProof, CredentialOffer, CredentialRequest,
+        CredentialRequestMetadata, LinkSecret, MakeCredentialValues, PresentCredentials,
+        Presentation, PresentationRequest, Schema, SignatureType,
+    },
+    verifier::verify_presentation,
+};
+use serde::de::DeserializeOwned;
+use serde_json::Value as JsonValue;
+use std::{
+    collections::{BTreeMap, HashMap},
+    convert::{TryFrom, TryInto},
+    hash::Hash,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+pub const CATEGORY_CRED_DEF_PRIVATE: &str = "cred_def_private";
+pub const CATEGORY_LINK_SECRET: &str = "link_secret";
+pub const CATEGORY_CRED_REQUEST_METADATA: &str = "cred_request_metadata";
+pub const CATEGORY_CREDENTIAL: &str = "credential";
+
+/// Keys of a credential definition generated locally.
+/// Only the public keys (`value` of the definition) are published to the ledger, the rest stays in the wallet of the issuer.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CredentialDefinitionKeys {
+    pub definition: JsonValue,
+    pub private: JsonValue,
+    pub key_correctness_proof: JsonValue,
+}
+
+impl CredentialDefinitionKeys {
+    /// Generate CL keys for the attributes of the schema.
+    /// `schema` is the schema in the AnonCreds object format (`issuerId`, `name`, `version`, `attrNames`).
+    pub fn generate(
+        issuer_did: &str,
+        schema_id: &str,
+        schema: &JsonValue,
+        tag: &str,
+        support_revocation: bool,
+    ) -> CliResult<CredentialDefinitionKeys> {
+        let schema: Schema = from_json("schema", schema)?;
+
+        let (cred_def, private, key_correctness_proof) = create_credential_definition(
+            schema_id,
+            &schema,
+            issuer_did,
+            tag,
+            SignatureType::CL,
+            CredentialDefinitionConfig { support_revocation },
+        )
+        .map_err(|err| anoncreds_error("generate credential definition keys", err))?;
+
+        Ok(CredentialDefinitionKeys {
+            definition: serde_json::to_value(cred_def)?,
+            private: serde_json::to_value(private)?,
+            key_correctness_proof: serde_json::to_value(key_correctness_proof)?,
+        })
+    }
+
+    /// Store the keys with the credential definition id. The keys of a known id are never replaced,
+    /// as credentials issued with them would become unverifiable.
+    pub fn store(&self, store: &Wallet, cred_def_id: &str) -> CliResult<()> {
+        let value = serde_json::to_vec(self)?;
+        block_on(async move {
+            if store
+                .fetch_record(CATEGORY_CRED_DEF_PRIVATE, cred_def_id, false)
+                .await?
+                .is_some()
+            {
+                return Err(CliError::Duplicate(format!(
+                    "Keys of credential definition \"{}\" already exist in the wallet.",
+                    cred_def_id
+                )));
+            }
+            store
+                .store_record(CATEGORY_CRED_DEF_PRIVATE, cred_def_id, &value, None, true)
+                .await
+        })
+    }
+
+    pub fn public(&self) -> &JsonValue {
+        &self.definition["value"]
+    }
+
+    pub fn get(store: &Wallet, cred_def_id: &str) -> CliResult<CredentialDefinitionKeys> {
+        block_on(async move {
+            let entry = store
+                .fetch_record(CATEGORY_CRED_DEF_PRIVATE, cred_def_id, false)
+                .await?
+                .ok_or_else(|| {
+                    CliError::NotFound(format!(
+                        "Keys of credential definition \"{}\" not found.",
+                        cred_def_id
+                    ))
+                })?;
+            serde_json::from_slice(&entry.value).map_err(CliError::from)
+        })
+    }
+
+    /// Credential offer for the credential definition the keys belong to.
+    pub fn create_offer(&self, cred_def_id: &str) -> CliResult<JsonValue> {
+        let schema_id = self.definition["schemaId"].as_str().unwrap_or_default();
+        let key_correctness_proof: CredentialKeyCorrectnessProof =
+            from_json("key correctness proof", &self.key_correctness_proof)?;
+        let offer = create_credential_offer(schema_id, cred_def_id, &key_correctness_proof)
+            .map_err(|err| anoncreds_error("create credential offer", err))?;
+        serde_json::to_value(offer).map_err(CliError::from)
+    }
+
+    /// Issue the credential with the raw attribute values given as `{"<name>": "<value>"}` object.
+    pub fn issue(
+        &self,
+        offer: &JsonValue,
+        request: &JsonValue,
+        values: &JsonValue,
+    ) -> CliResult<JsonValue> {
+        let cred_def: CredentialDefinition = from_json("credential definition", &self.definition)?;
+        let private: CredentialDefinitionPrivate =
+            from_json("credential definition private keys", &self.private)?;
+        let offer: CredentialOffer = from_json("credential offer", offer)?;
+        let request: CredentialRequest = from_json("credential request", request)?;
+
+        let values = values.as_object().ok_or_else(|| {
+            CliError::InvalidInput("Credential values must be a JSON object".to_string())
+        })?;
+        let mut cred_values = MakeCredentialValues::default();
+        for (name, value) in values {
+            // Numbers are encoded as integers, so they are passed in their text form
+            let raw = value
+                .as_str()
+                .map(String::from)
+                .unwrap_or_else(|| value.to_string());
+            cred_values
+                .add_raw(name, &raw)
+                .map_err(|err| anoncreds_error("encode credential values", err))?;
+        }
+
+        let credential = create_credential(
+            &cred_def,
+            &private,
+            &offer,
+            &request,
+            cred_values.into(),
+            None,
+        )
+        .map_err(|err| anoncreds_error("issue credential", err))?;
+        serde_json::to_value(credential).map_err(CliError::from)
+    }
+}
+
+/// Holder side of the credential issuance: link secrets, credential requests and received credentials.
+pub struct Prover {}
+
+impl Prover {
+    pub fn create_link_secret(store: &Wallet, id: &str) -> CliResult<()> {
+        let link_secret =
+            create_link_secret().map_err(|err| anoncreds_error("create link secret", err))?;
+        let value: String = link_secret.try_into().map_err(|err| {
+            CliError::InvalidEntityState(format!("Invalid link secret created: {:?}", err))
+        })?;
+        block_on(async move {
+            if store
+                .fetch_record(CATEGORY_LINK_SECRET, id, false)
+                .await?
+                .is_some()
+            {
+                return Err(CliError::Duplicate(format!(
+                    "Link secret \"{}\" already exists in the wallet.",
+                    id
+                )));
+            }
+            store
+                .store_record(CATEGORY_LINK_SECRET, id, value.as_bytes(), None, true)
+                .await
+        })
+    }
+
+    /// Credential request for the offer. The request metadata needed to store the issued credential
+    /// is kept in the wallet with the credential definition id.
+    pub fn create_request(
+        store: &Wallet,
+        prover_did: Option<&str>,
+        cred_def: &JsonValue,
+        offer: &JsonValue,
+        link_secret_id: &str,
+    ) -> CliResult<JsonValue> {
+        let cred_def_id = offer["cred_def_id"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string();
+        let cred_def: CredentialDefinition = from_json("credential definition", cred_def)?;
+        let offer: CredentialOffer = from_json("credential offer", offer)?;
+        let link_secret = Self::get_link_secret(store, link_secret_id)?;
+
+        let (request, metadata) = create_credential_request(
+            None,
+            prover_did,
+            &cred_def,
+            &link_secret,
+            link_secret_id,
+            &offer,
+        )
+        .map_err(|err| anoncreds_error("create credential request", err))?;
+
+        let metadata = serde_json::to_vec(&metadata)?;
+        block_on(async move {
+            let exists = store
+                .fetch_record(CATEGORY_CRED_REQUEST_METADATA, &cred_def_id, true)
+                .await?
+                .is_some();
+            store
+                .store_record(
+                    CATEGORY_CRED_REQUEST_METADATA,
+                    &cred_def_id,
+                    &metadata,
+                    None,
+                    !exists,
+                )
+                .await
+        })?;
+
+        serde_json::to_value(request).map_err(CliError::from)
+    }
+
+    /// Process the issued credential with the link secret and store it in the wallet. Returns the credential id.
+    pub fn store_credential(
+        store: &Wallet,
+        id: Option<&str>,
+        cred_def: &JsonValue,
+        credential: &JsonValue,
+    ) -> CliResult<String> {
+        let cred_def_id = credential["cred_def_id"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string();
+        let cred_def: CredentialDefinition = from_json("credential definition", cred_def)?;
+        let mut credential: Credential = from_json("credential", credential)?;
+
+        let metadata: CredentialRequestMetadata = block_on(async {
+            let entry = store
+                .fetch_record(CATEGORY_CRED_REQUEST_METADATA, &cred_def_id, false)
+                .await?
+                .ok_or_else(|| {
+                    CliError::NotFound(format!(
+                        "No credential request has been made for credential definition \"{}\".",
+                        cred_def_id
+                    ))
+                })?;
+            serde_json::from_slice(&entry.value).map_err(CliError::from)
+        })?;
+        let link_secret = Self::get_link_secret(store, &metadata.link_secret_name)?;
+
+        process_credential(&mut credential, &metadata, &link_secret, &cred_def, None)
+            .map_err(|err| anoncreds_error("process credential", err))?;
+
+        let id = match id {
+            Some(id) => id.to_string(),
+            None => {
+                let nonce = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_nanos();
+                format!("{}:{}", cred_def_id, nonce)
+            }
+        };
+        let value = serde_json::to_vec(&credential)?;
+        block_on(async {
+            if store
+                .fetch_record(CATEGORY_CREDENTIAL, &id, false)
+                .await?
+                .is_some()
+            {
+                return Err(CliError::Duplicate(format!(
+                    "Credential \"{}\" already exists in the wallet.",
+                    id
+                )));
+            }
+            store
+                .store_record(CATEGORY_CREDENTIAL, &id, &value, None, true)
+                .await?;
+            store
+                .remove_record(CATEGORY_CRED_REQUEST_METADATA, &cred_def_id)
+                .await
+        })?;
+        Ok(id)
+    }
+
+    /// Stored credentials sorted by id.
+    pub fn list_credentials(store: &Wallet) -> CliResult<Vec<(String, JsonValue)>> {
+        let mut credentials = block_on(async move {
+            store
+                .fetch_all_records(CATEGORY_CREDENTIAL)
+                .await?
+                .into_iter()
+                .map(|entry| {
+                    serde_json::from_slice(&entry.value)
+                        .map(|credential| (entry.name.to_string(), credential))
+                        .map_err(CliError::from)
+                })
+                .collect::<CliResult<Vec<(String, JsonValue)>>>()
+        })?;
+        credentials.sort_by(|(id1, _), (id2, _)| id1.cmp(id2));
+        Ok(credentials)
+    }
+
+    /// Choose the credentials answering the referents of the proof request.
+    /// `chosen` credential ids are taken as is, the rest of the referents get the first credential
+    /// which contains the attributes, satisfies the restrictions and, for predicates, the predicate itself.
+    /// Attribute referents with `self_attested` values need no credential.
+    pub fn select_credentials(
+        credentials: &[(String, JsonValue)],
+        proof_request: &JsonValue,
+        chosen: &HashMap<String, String>,
+        self_attested: &HashMap<String, String>,
+    ) -> CliResult<ProofCredentials> {
+        let find = |referent: &str, names: Vec<&str>, info: &JsonValue| -> CliResult<String> {
+            if let Some(id) = chosen.get(referent) {
+                if !credentials.iter().any(|(id_, _)| id_ == id) {
+                    return Err(CliError::NotFound(format!(
+                        "Credential \"{}\" chosen for \"{}\" referent not found.",
+                        id, referent
+                    )));
+                }
+                return Ok(id.to_string());
+            }
+            credentials
+                .iter()
+                .find(|(_, credential)| {
+                    let tags = credential_tags(credential);
+                    names
+                        .iter()
+                        .all(|name| tags.contains_key(&attr_tag(name, "marker")))
+                        && matches_restrictions(&info["restrictions"], &tags)
+                        && satisfies_predicate(info, &tags)
+                })
+                .map(|(id, _)| id.to_string())
+                .ok_or_else(|| {
+                    CliError::NotFound(format!(
+                        "No stored credential satisfies \"{}\" referent of the proof request.",
+                        referent
+                    ))
+                })
+        };
+
+        let mut selected = ProofCredentials::default();
+        if let Some(attributes) = proof_request["requested_attributes"].as_object() {
+            for (referent, info) in attributes {
+                if self_attested.contains_key(referent) {
+                    continue;
+                }
+                let names = match info["names"].as_array() {
+                    Some(names) => names.iter().filter_map(|name| name.as_str()).collect(),
+                    None => info["name"].as_str().into_iter().collect(),
+                };
+                let id = find(referent, names, info)?;
+                selected.attributes.insert(referent.to_string(), id);
+            }
+        }
+        if let Some(predicates) = proof_request["requested_predicates"].as_object() {
+            for (referent, info) in predicates {
+                let names = info["name"].as_str().into_iter().collect();
+                let id = find(referent, names, info)?;
+                selected.predicates.insert(referent.to_string(), id);
+            }
+        }
+        Ok(selected)
+    }
+
+    /// Presentation for the proof request revealing all requested attributes.
+    /// `schemas` and `cred_defs` are the objects the selected credentials refer to, keyed by id.
+    pub fn create_presentation(
+        store: &Wallet,
+        proof_request: &JsonValue,
+        selected: &ProofCredentials,
+        self_attested: HashMap<String, String>,
+        link_secret_id: &str,
+        schemas: &JsonValue,
+        cred_defs: &JsonValue,
+    ) -> CliResult<JsonValue> {
+        let proof_request: PresentationRequest = from_json("proof request", proof_request)?;
+        let schemas = object_map("schema", schemas, SchemaId::new_unchecked)?;
+        let cred_defs = object_map(
+            "credential definition",
+            cred_defs,
+            CredentialDefinitionId::new_unchecked,
+        )?;
+        let link_secret = Self::get_link_secret(store, link_secret_id)?;
+
+        let mut credentials: BTreeMap<&str, Credential> = BTreeMap::new();
+        for id in selected.credential_ids() {
+            let credential = Self::get_credential(store, id)?;
+            credentials.insert(id, credential);
+        }
+
+        let mut present = PresentCredentials::default();
+        for (id, credential) in credentials.iter() {
+            let mut entry = present.add_credential(credential, None, None);
+            for (referent, id_) in selected.attributes.iter() {
+                if id_.as_str() == *id {
+                    entry.add_requested_attribute(referent.to_string(), true);
+                }
+            }
+            for (referent, id_) in selected.predicates.iter() {
+                if id_.as_str() == *id {
+                    entry.add_requested_predicate(referent.to_string());
+                }
+            }
+        }
+
+        let self_attested = if self_attested.is_empty() {
+            None
+        } else {
+            Some(self_attested)
+        };
+        let presentation = create_presentation(
+            &proof_request,
+            present,
+            self_attested,
+            &link_secret,
+            &schemas,
+            &cred_defs,
+        )
+        .map_err(|err| anoncreds_error("create presentation", err))?;
+        serde_json::to_value(presentation).map_err(CliError::from)
+    }
+
+    fn get_credential(store: &Wallet, id: &str) -> CliResult<Credential> {
+        block_on(async move {
+            let entry = store
+                .fetch_record(CATEGORY_CREDENTIAL, id, false)
+                .await?
+                .ok_or_else(|| CliError::NotFound(format!("Credential \"{}\" not found.", id)))?;
+            serde_json::from_slice(&entry.value).map_err(CliError::from)
+        })
+    }
+
+    fn get_link_secret(store: &Wallet, id: &str) -> CliResult<LinkSecret> {
+        let value = block_on(async move {
+            store
+                .fetch_record(CATEGORY_LINK_SECRET, id, false)
+                .await?
+                .map(|entry| String::from_utf8_lossy(&entry.value).to_string())
+                .ok_or_else(|| CliError::NotFound(format!("Link secret \"{}\" not found.", id)))
+        })?;
+        LinkSecret::try_from(value.as_str()).map_err(|err| {
+            CliError::InvalidEntityState(format!("Invalid link secret \"{}\": {:?}", id, err))
+        })
+    }
+}
+
+/// Credentials chosen for the referents of a proof request, as referent to credential id.
+#[derive(Debug, Default)]
+pub struct ProofCredentials {
+    pub attributes: BTreeMap<String, String>,
+    pub predicates: BTreeMap<String, String>,
+}
+
+impl ProofCredentials {
+    pub fn credential_ids(&self) -> Vec<&str> {
+        let mut ids: Vec<&str> = self
+            .attributes
+            .values()
+            .chain(self.predicates.values())
+            .map(String::as_str)
+            .collect();
+        ids.sort_unstable();
+        ids.dedup();
+        ids
+    }
+}
+
+pub struct Verifier {}
+
+impl Verifier {
+    /// Verify the presentation against the proof request. Revocation is not checked.
+    pub fn verify(
+        presentation: &JsonValue,
+        proof_request: &JsonValue,
+        schemas: &JsonValue,
+        cred_defs: &JsonValue,
+    ) -> CliResult<bool> {
+        let presentation: Presentation = from_json("proof", presentation)?;
+        let proof_request: PresentationRequest = from_json("proof request", proof_request)?;
+        let schemas = object_map("schema", schemas, SchemaId::new_unchecked)?;
+        let cred_defs = object_map(
+            "credential definition",
+            cred_defs,
+            CredentialDefinitionId::new_unchecked,
+        )?;
+        verify_presentation(
+            &presentation,
+            &proof_request,
+            &schemas,
+            &cred_defs,
+            None,
+            None,
+            None,
+        )
+        .map_err(|err| anoncreds_error("verify proof", err))
+    }
+}
+
+// WQL tags Indy wallets kept for credentials, which proof request restrictions are written against
+fn credential_tags(credential: &JsonValue) -> HashMap<String, String> {
+    let mut tags = HashMap::new();
+    if let Some(schema_id) = credential["schema_id"].as_str() {
+        tags.insert("schema_id".to_string(), schema_id.to_string());
+        if let Ok(AnonCredsId {
+            did,
+            object: AnonCredsObject::Schema { name, version },
+            ..
+        }) = AnonCredsId::parse(schema_id)
+        {
+            tags.insert("schema_issuer_did".to_string(), did);
+            tags.insert("schema_name".to_string(), name);
+            tags.insert("schema_version".to_string(), version);
+        }
+    }
+    if let Some(cred_def_id) = credential["cred_def_id"].as_str() {
+        tags.insert("cred_def_id".to_string(), cred_def_id.to_string());
+        if let Ok(id) = AnonCredsId::parse(cred_def_id) {
+            tags.insert("issuer_did".to_string(), id.did);
+        }
+    }
+    if let Some(values) = credential["values"].as_object() {
+        for (name, value) in values {
+            tags.insert(attr_tag(name, "marker"), "1".to_string());
+            if let Some(raw) = value["raw"].as_str() {
+                tags.insert(attr_tag(name, "value"), raw.to_string());
+            }
+        }
+    }
+    tags
+}
+
+// Attribute names are compared case insensitive and ignoring spaces
+fn attr_tag(name: &str, kind: &str) -> String {
+    format!("attr::{}::{}", name.replace(' ', "").to_lowercase(), kind)
+}
+
+fn matches_restrictions(restrictions: &JsonValue, tags: &HashMap<String, String>) -> bool {
+    match restrictions {
+        JsonValue::Null => true,
+        JsonValue::Array(queries) => {
+            queries.is_empty() || queries.iter().any(|query| matches_query(query, tags))
+        }
+        query => matches_query(query, tags),
+    }
+}
+
+fn matches_query(query: &JsonValue, tags: &HashMap<String, String>) -> bool {
+    let query = match query.as_object() {
+        Some(query) => query,
+        None => return false,
+    };
+    query.iter().all(|(key, value)| match key.as_str() {
+        "$or" => value
+            .as_array()
+            .map(|queries| queries.iter().any(|query| matches_query(query, tags)))
+            .unwrap_or(false),
+        "$and" => value
+            .as_array()
+            .map(|queries| queries.iter().all(|query| matches_query(query, tags)))
+            .unwrap_or(false),
+        "$not" => !matches_query(value, tags),
+        key => {
+            let key = match key
+                .strip_prefix("attr::")
+                .and_then(|key| key.rsplit_once("::"))
+            {
+                Some((name, kind)) => attr_tag(name, kind),
+                None => key.to_string(),
+            };
+            let tag = tags.get(&key);
+            match value {
+                JsonValue::String(value) => tag == Some(value),
+                JsonValue::Object(operator) => match operator.get("$in") {
+                    Some(JsonValue::Array(values)) => tag
+                        .map(|tag| values.iter().any(|value| value.as_str() == Some(tag)))
+                        .unwrap_or(false),
+                    _ => false,
+                },
+                _ => false,
+            }
+        }
+    })
+}
+
+// Requested attributes carry no predicate and are satisfied by any value
+fn satisfies_predicate(info: &JsonValue, tags: &HashMap<String, String>) -> bool {
+    let (name, p_type, p_value) = match (
+        info["name"].as_str(),
+        info["p_type"].as_str(),
+        info["p_value"].as_i64(),
+    ) {
+        (Some(name), Some(p_type), Some(p_value)) => (name, p_type, p_value),
+        _ => return true,
+    };
+    let value = match tags
+        .get(&attr_tag(name, "value"))
+        .and_then(|value| value.parse::<i64>().ok())
+    {
+        Some(value) => value,
+        None => return false,
+    };
+    match p_type {
+        ">=" => value >= p_value,
+        ">" => value > p_value,
+        "<=" => value <= p_value,
+        "<" => value < p_value,
+        _ => false,
+    }
+}
+
+fn object_map<K: Eq + Hash, T: DeserializeOwned>(
+    name: &str,
+    objects: &JsonValue,
+    id: impl Fn(String) -> K,
+) -> CliResult<HashMap<K, T>> {
+    let objects = objects.as_object().ok_or_else(|| {
+        CliError::InvalidInput(format!(
+            "Invalid {} objects: JSON object keyed by id is expected",
+            name
+        ))
+    })?;
+    objects
+        .iter()
+        .map(|(key, value)| Ok((id(key.to_string()), from_json(name, value)?)))
+        .collect()
+}
+
+fn from_json<T: DeserializeOwned>(name: &str, value: &JsonValue) -> CliResult<T> {
+    serde_json::from_value(value.clone())
+        .map_err(|err| CliError::InvalidInput(format!("Invalid {}: {}", name, err)))
+}
+
+fn anoncreds_error(action: &str, err: anoncreds::Error) -> CliError {
+    CliError::InvalidEntityState(format!("Unable to {}: {}", action, err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_works() {
+        let schema = json!({
+            "issuerId": "V4SGRU86Z58d6TV7PBUe6f",
+            "name": "gvt",
+            "version": "1.0",
+            "attrNames": ["name", "age"]
+        });
+        let keys = CredentialDefinitionKeys::generate(
+            "V4SGRU86Z58d6TV7PBUe6f",
+            "V4SGRU86Z58d6TV7PBUe6f:2:gvt:1.0",
+            &schema,
+            "tag",
+            false,
+        )
+        .unwrap();
+        assert!(keys.public()["primary"]["n"].is_string());
+        assert!(keys.public()["primary"]["r"]["age"].is_string());
+        assert!(keys.public()["revocation"].is_null());
+        assert!(keys.private.is_object());
+    }
+
+    #[test]
+    fn select_credentials_works() {
+        let credentials = vec![
+            (
+                "1".to_string(),
+                json!({
+                    "schema_id": "V4SGRU86Z58d6TV7PBUe6f:2:gvt:1.0",
+                    "cred_def_id": "V4SGRU86Z58d6TV7PBUe6f:3:CL:10:TAG",
+                    "values": {"name": {"raw": "Alex"}, "age": {"raw": "17"}}
+                }),
+            ),
+            (
+                "2".to_string(),
+                json!({
+                    "schema_id": "V4SGRU86Z58d6TV7PBUe6f:2:gvt:1.0",
+                    "cred_def_id": "V4SGRU86Z58d6TV7PBUe6f:3:CL:10:TAG",
+                    "values": {"name": {"raw": "Alex"}, "age": {"raw": "28"}}
+                }),
+            ),
+        ];
+        let proof_request = json!({
+            "requested_attributes": {
+                "attr1_referent": {
+                    "name": "Name",
+                    "restrictions": [{"schema_name": "gvt", "issuer_did": "V4SGRU86Z58d6TV7PBUe6f"}]
+                },
+                "attr2_referent": {"name": "phone"}
+            },
+            "requested_predicates": {
+                "predicate1_referent": {"name": "age", "p_type": ">=", "p_value": 18}
+            }
+        });
+        let self_attested = HashMap::from([("attr2_referent".to_string(), "8-800".to_string())]);
+
+        let selected = Prover::select_credentials(
+            &credentials,
+            &proof_request,
+            &HashMap::new(),
+            &self_attested,
+        )
+        .unwrap();
+        assert_eq!("1", selected.attributes["attr1_referent"]);
+        assert_eq!("2", selected.predicates["predicate1_referent"]);
+        assert!(!selected.attributes.contains_key("attr2_referent"));
+
+        Prover::select_credentials(
+            &credentials,
+            &proof_request,
+            &HashMap::new(),
+            &HashMap::new(),
+        )
+        .unwrap_err();
+    }
+}
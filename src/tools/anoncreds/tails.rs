@@ -0,0 +1,197 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::{
+    error::{CliError, CliResult},
+    tools::wallet::Wallet,
+    utils::futures::block_on,
+};
+
+use super::{anoncreds_error, from_json};
+
+use anoncreds::{
+    issuer::create_revocation_registry_def,
+    tails::TailsFileWriter,
+    types::{CredentialDefinition, RegistryType},
+};
+use indy_utils::{base58, hash::SHA256};
+use serde_json::Value as JsonValue;
+use std::{fs, path::Path, time::Duration};
+
+pub const CATEGORY_REV_REG_DEF_PRIVATE: &str = "rev_reg_def_private";
+
+const UPLOAD_TIMEOUT: u64 = 120;
+
+/// Revocation registry generated locally together with its tails file.
+/// The private key of the registry stays in the wallet of the issuer, the tails file is shared with the holders.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RevocationRegistryKeys {
+    pub definition: JsonValue,
+    pub private: JsonValue,
+}
+
+impl RevocationRegistryKeys {
+    /// Generate the registry for the credential definition (in the AnonCreds object format, created with revocation support)
+    /// and write the tails file named by its hash into `tails_dir`.
+    pub fn generate(
+        cred_def: &JsonValue,
+        cred_def_id: &str,
+        tag: &str,
+        max_cred_num: u32,
+        tails_dir: &Path,
+    ) -> CliResult<RevocationRegistryKeys> {
+        let cred_def: CredentialDefinition = from_json("credential definition", cred_def)?;
+
+        fs::create_dir_all(tails_dir)?;
+        let mut tails_writer = TailsFileWriter::new(Some(tails_dir.to_string_lossy().to_string()));
+
+        let (definition, private) = create_revocation_registry_def(
+            &cred_def,
+            cred_def_id,
+            tag,
+            RegistryType::CL_ACCUM,
+            max_cred_num,
+            &mut tails_writer,
+        )
+        .map_err(|err| anoncreds_error("generate revocation registry", err))?;
+
+        Ok(RevocationRegistryKeys {
+            definition: serde_json::to_value(definition)?,
+            private: serde_json::to_value(private)?,
+        })
+    }
+
+    /// Store the keys with the revocation registry id. As for credential definitions, the keys of a known id are never replaced.
+    pub fn store(&self, store: &Wallet, rev_reg_id: &str) -> CliResult<()> {
+        let value = serde_json::to_vec(self)?;
+        block_on(async move {
+            if store
+                .fetch_record(CATEGORY_REV_REG_DEF_PRIVATE, rev_reg_id, false)
+                .await?
+                .is_some()
+            {
+                return Err(CliError::Duplicate(format!(
+                    "Keys of revocation registry \"{}\" already exist in the wallet.",
+                    rev_reg_id
+                )));
+            }
+            store
+                .store_record(CATEGORY_REV_REG_DEF_PRIVATE, rev_reg_id, &value, None, true)
+                .await
+        })
+    }
+
+    pub fn get(store: &Wallet, rev_reg_id: &str) -> CliResult<RevocationRegistryKeys> {
+        block_on(async move {
+            let entry = store
+                .fetch_record(CATEGORY_REV_REG_DEF_PRIVATE, rev_reg_id, false)
+                .await?
+                .ok_or_else(|| {
+                    CliError::NotFound(format!(
+                        "Keys of revocation registry \"{}\" not found.",
+                        rev_reg_id
+                    ))
+                })?;
+            serde_json::from_slice(&entry.value).map_err(CliError::from)
+        })
+    }
+
+    pub fn accum_key(&self) -> &JsonValue {
+        &self.definition["value"]["publicKeys"]["accumKey"]
+    }
+
+    pub fn tails_hash(&self) -> &str {
+        self.definition["value"]["tailsHash"]
+            .as_str()
+            .unwrap_or_default()
+    }
+
+    /// Path to the tails file written on generation.
+    pub fn tails_file(&self) -> &str {
+        self.definition["value"]["tailsLocation"]
+            .as_str()
+            .unwrap_or_default()
+    }
+}
+
+/// Hash of the tails file as published in the revocation registry definition: base58 encoded SHA-256 of the file.
+pub fn tails_hash(path: &Path) -> CliResult<String> {
+    let content = fs::read(path)?;
+    Ok(base58::encode(SHA256::digest(&content)))
+}
+
+/// Upload the tails file to the tails server and return the location to publish on the ledger.
+/// The file is put by its hash (`<server>/hash/<tails hash>`), so the server can check the content.
+pub fn upload(server: &str, path: &Path) -> CliResult<String> {
+    let hash = tails_hash(path)?;
+    let content = fs::read(path)?;
+    let location = format!("{}/hash/{}", server.trim_end_matches('/'), hash);
+
+    let boundary = format!("indy-cli-{}", hash);
+    let mut body = format!(
+        "--{}\r\nContent-Disposition: form-data; name=\"tails\"; filename=\"{}\"\r\nContent-Type: application/octet-stream\r\n\r\n",
+        boundary, hash
+    )
+    .into_bytes();
+    body.extend_from_slice(&content);
+    body.extend_from_slice(format!("\r\n--{}--\r\n", boundary).as_bytes());
+
+    attohttpc::put(&location)
+        .header(
+            "Content-Type",
+            format!("multipart/form-data; boundary={}", boundary),
+        )
+        .timeout(Duration::from_secs(UPLOAD_TIMEOUT))
+        .bytes(body)
+        .send()
+        .and_then(|response| response.error_for_status())
+        .map_err(|err| {
+            CliError::InvalidEntityState(format!(
+                "Unable to upload tails file to \"{}\": {}",
+                location, err
+            ))
+        })?;
+    Ok(location)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{tools::anoncreds::CredentialDefinitionKeys, utils::environment::EnvironmentUtils};
+
+    #[test]
+    fn generate_works() {
+        let schema = json!({
+            "issuerId": "V4SGRU86Z58d6TV7PBUe6f",
+            "name": "gvt",
+            "version": "1.0",
+            "attrNames": ["name", "age"]
+        });
+        let cred_def_id = "V4SGRU86Z58d6TV7PBUe6f:3:CL:10:TAG";
+        let cred_def = CredentialDefinitionKeys::generate(
+            "V4SGRU86Z58d6TV7PBUe6f",
+            "V4SGRU86Z58d6TV7PBUe6f:2:gvt:1.0",
+            &schema,
+            "TAG",
+            true,
+        )
+        .unwrap();
+        let tails_dir = EnvironmentUtils::tmp_file_path("tails");
+
+        let keys = RevocationRegistryKeys::generate(
+            &cred_def.definition,
+            cred_def_id,
+            "default",
+            10,
+            &tails_dir,
+        )
+        .unwrap();
+
+        assert!(keys.accum_key()["z"].is_string());
+        let tails_file = Path::new(keys.tails_file());
+        assert!(tails_file.starts_with(&tails_dir));
+        assert_eq!(keys.tails_hash(), tails_hash(tails_file).unwrap());
+        fs::remove_dir_all(&tails_dir).unwrap();
+    }
+}
@@ -0,0 +1,258 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::{
+    error::{CliError, CliResult},
+    tools::did::constants::INDY_DID_PREFIX,
+};
+
+use indy_utils::base58;
+
+const SCHEMA_MARKER: &str = "2";
+const CRED_DEF_MARKER: &str = "3";
+const REV_REG_MARKER: &str = "4";
+const SIGNATURE_TYPE: &str = "CL";
+const REV_REG_TYPE: &str = "CL_ACCUM";
+
+const OBJECT_PATH: &str = "anoncreds/v0";
+const SCHEMA_PATH: &str = "SCHEMA";
+const CRED_DEF_PATH: &str = "CLAIM_DEF";
+const REV_REG_PATH: &str = "REV_REG_DEF";
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum AnonCredsObject {
+    Schema {
+        name: String,
+        version: String,
+    },
+    CredDef {
+        schema_seq_no: u64,
+        tag: String,
+    },
+    RevReg {
+        schema_seq_no: u64,
+        cred_def_tag: String,
+        tag: String,
+    },
+}
+
+/// Identifier of an AnonCreds object written to an Indy ledger.
+/// Both legacy (`<did>:2:<name>:<version>`) and `did:indy` (`did:indy:<namespace>:<did>/anoncreds/v0/SCHEMA/<name>/<version>`) forms are supported.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnonCredsId {
+    pub did: String,
+    pub namespace: Option<String>,
+    pub object: AnonCredsObject,
+}
+
+impl AnonCredsId {
+    pub fn new(
+        did: &str,
+        namespace: Option<&str>,
+        object: AnonCredsObject,
+    ) -> CliResult<AnonCredsId> {
+        let id = AnonCredsId {
+            did: did.to_string(),
+            namespace: namespace.map(String::from),
+            object,
+        };
+        id.validate()?;
+        Ok(id)
+    }
+
+    pub fn parse(id: &str) -> CliResult<AnonCredsId> {
+        match id.strip_prefix(INDY_DID_PREFIX) {
+            Some(rest) => Self::parse_did_indy(id, rest),
+            None => Self::parse_legacy(id),
+        }
+    }
+
+    fn parse_legacy(id: &str) -> CliResult<AnonCredsId> {
+        let parts = id.split(':').collect::<Vec<&str>>();
+        let object = match parts.as_slice() {
+            [_, SCHEMA_MARKER, name, version] => AnonCredsObject::Schema {
+                name: name.to_string(),
+                version: version.to_string(),
+            },
+            [_, CRED_DEF_MARKER, SIGNATURE_TYPE, schema_seq_no, tag] => AnonCredsObject::CredDef {
+                schema_seq_no: parse_seq_no(schema_seq_no)?,
+                tag: tag.to_string(),
+            },
+            [did, REV_REG_MARKER, cred_def_did, CRED_DEF_MARKER, SIGNATURE_TYPE, schema_seq_no, cred_def_tag, REV_REG_TYPE, tag] =>
+            {
+                if did != cred_def_did {
+                    return Err(invalid_id(
+                        id,
+                        "revocation registry and credential definition issuers differ",
+                    ));
+                }
+                AnonCredsObject::RevReg {
+                    schema_seq_no: parse_seq_no(schema_seq_no)?,
+                    cred_def_tag: cred_def_tag.to_string(),
+                    tag: tag.to_string(),
+                }
+            }
+            _ => return Err(invalid_id(id, "unknown identifier format")),
+        };
+        AnonCredsId::new(parts[0], None, object)
+    }
+
+    fn parse_did_indy(id: &str, rest: &str) -> CliResult<AnonCredsId> {
+        let (did, path) = rest
+            .split_once('/')
+            .ok_or_else(|| invalid_id(id, "object path is missing"))?;
+        let (namespace, did) = did
+            .rsplit_once(':')
+            .ok_or_else(|| invalid_id(id, "namespace is missing"))?;
+        let path = path
+            .strip_prefix(OBJECT_PATH)
+            .and_then(|path| path.strip_prefix('/'))
+            .ok_or_else(|| invalid_id(id, "object path must start with anoncreds/v0"))?;
+
+        let parts = path.split('/').collect::<Vec<&str>>();
+        let object = match parts.as_slice() {
+            [SCHEMA_PATH, name, version] => AnonCredsObject::Schema {
+                name: name.to_string(),
+                version: version.to_string(),
+            },
+            [CRED_DEF_PATH, schema_seq_no, tag] => AnonCredsObject::CredDef {
+                schema_seq_no: parse_seq_no(schema_seq_no)?,
+                tag: tag.to_string(),
+            },
+            [REV_REG_PATH, schema_seq_no, cred_def_tag, tag] => AnonCredsObject::RevReg {
+                schema_seq_no: parse_seq_no(schema_seq_no)?,
+                cred_def_tag: cred_def_tag.to_string(),
+                tag: tag.to_string(),
+            },
+            _ => return Err(invalid_id(id, "unknown object type")),
+        };
+        AnonCredsId::new(did, Some(namespace), object)
+    }
+
+    fn validate(&self) -> CliResult<()> {
+        let did_bytes = base58::decode(&self.did).map_err(|_| {
+            CliError::InvalidInput(format!("Invalid DID \"{}\": not a base58 string", self.did))
+        })?;
+        if did_bytes.len() != 16 {
+            return Err(CliError::InvalidInput(format!(
+                "Invalid DID \"{}\": 16 bytes expected",
+                self.did
+            )));
+        }
+
+        if let Some(namespace) = self.namespace.as_ref() {
+            if namespace.is_empty() || namespace.contains(|c: char| c == '/' || c.is_whitespace()) {
+                return Err(CliError::InvalidInput(format!(
+                    "Invalid namespace \"{}\"",
+                    namespace
+                )));
+            }
+        }
+
+        let parts = match &self.object {
+            AnonCredsObject::Schema { name, version } => {
+                vec![("name", name.as_str()), ("version", version.as_str())]
+            }
+            AnonCredsObject::CredDef { tag, .. } => vec![("tag", tag.as_str())],
+            AnonCredsObject::RevReg {
+                cred_def_tag, tag, ..
+            } => vec![
+                ("credential definition tag", cred_def_tag.as_str()),
+                ("tag", tag.as_str()),
+            ],
+        };
+        for (name, value) in parts {
+            if value.is_empty() || value.contains(|c| c == ':' || c == '/') {
+                return Err(CliError::InvalidInput(format!(
+                    "Invalid {} \"{}\": must be non-empty and must not contain `:` or `/`",
+                    name, value
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn type_name(&self) -> &'static str {
+        match self.object {
+            AnonCredsObject::Schema { .. } => "Schema",
+            AnonCredsObject::CredDef { .. } => "Credential Definition",
+            AnonCredsObject::RevReg { .. } => "Revocation Registry",
+        }
+    }
+
+    pub fn to_legacy(&self) -> String {
+        match &self.object {
+            AnonCredsObject::Schema { name, version } => {
+                format!("{}:{}:{}:{}", self.did, SCHEMA_MARKER, name, version)
+            }
+            AnonCredsObject::CredDef { schema_seq_no, tag } => format!(
+                "{}:{}:{}:{}:{}",
+                self.did, CRED_DEF_MARKER, SIGNATURE_TYPE, schema_seq_no, tag
+            ),
+            AnonCredsObject::RevReg {
+                schema_seq_no,
+                cred_def_tag,
+                tag,
+            } => format!(
+                "{}:{}:{}:{}:{}:{}:{}:{}:{}",
+                self.did,
+                REV_REG_MARKER,
+                self.did,
+                CRED_DEF_MARKER,
+                SIGNATURE_TYPE,
+                schema_seq_no,
+                cred_def_tag,
+                REV_REG_TYPE,
+                tag
+            ),
+        }
+    }
+
+    /// `did:indy` form of the identifier. Returns `None` if the network namespace is unknown.
+    pub fn to_did_indy(&self) -> Option<String> {
+        let path = match &self.object {
+            AnonCredsObject::Schema { name, version } => {
+                format!("{}/{}/{}", SCHEMA_PATH, name, version)
+            }
+            AnonCredsObject::CredDef { schema_seq_no, tag } => {
+                format!("{}/{}/{}", CRED_DEF_PATH, schema_seq_no, tag)
+            }
+            AnonCredsObject::RevReg {
+                schema_seq_no,
+                cred_def_tag,
+                tag,
+            } => format!(
+                "{}/{}/{}/{}",
+                REV_REG_PATH, schema_seq_no, cred_def_tag, tag
+            ),
+        };
+        self.namespace.as_ref().map(|namespace| {
+            format!(
+                "{}{}:{}/{}/{}",
+                INDY_DID_PREFIX, namespace, self.did, OBJECT_PATH, path
+            )
+        })
+    }
+}
+
+fn parse_seq_no(seq_no: &str) -> CliResult<u64> {
+    seq_no
+        .parse::<u64>()
+        .ok()
+        .filter(|seq_no| *seq_no > 0)
+        .ok_or_else(|| {
+            CliError::InvalidInput(format!(
+                "Invalid schema sequence number \"{}\": positive integer expected",
+                seq_no
+            ))
+        })
+}
+
+fn invalid_id(id: &str, reason: &str) -> CliError {
+    CliError::InvalidInput(format!(
+        "Invalid AnonCreds identifier \"{}\": {}",
+        id, reason
+    ))
+}
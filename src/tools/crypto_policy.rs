@@ -0,0 +1,118 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::error::{CliError, CliResult};
+
+use aries_askar::kms::KeyAlg;
+
+const APPROVED_KEY_ALGS: [KeyAlg; 1] = [KeyAlg::Ed25519];
+// Wallet storage derives the store key with Argon2i (or takes a raw key) and encrypts records with
+// ChaCha20-Poly1305. Neither is an approved algorithm, so no wallet key method satisfies the strict policy.
+const APPROVED_KEY_DERIVATION_METHODS: [&str; 0] = [];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CryptoPolicy {
+    Default,
+    Strict,
+}
+
+impl Default for CryptoPolicy {
+    // Builds with `fips` feature start in the strict mode and cannot leave it
+    fn default() -> Self {
+        if cfg!(feature = "fips") {
+            CryptoPolicy::Strict
+        } else {
+            CryptoPolicy::Default
+        }
+    }
+}
+
+impl CryptoPolicy {
+    pub fn from_str(policy: &str) -> CliResult<CryptoPolicy> {
+        let policy = match policy {
+            "default" => CryptoPolicy::Default,
+            "strict" => CryptoPolicy::Strict,
+            _ => {
+                return Err(CliError::InvalidInput(format!(
+                    "Unsupported crypto policy \"{}\". Supported policies: default, strict",
+                    policy
+                )))
+            }
+        };
+        if cfg!(feature = "fips") && policy != CryptoPolicy::Strict {
+            return Err(CliError::InvalidEntityState(
+                "CLI is built with \"fips\" feature: crypto policy cannot be relaxed".to_string(),
+            ));
+        }
+        Ok(policy)
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            CryptoPolicy::Default => "default",
+            CryptoPolicy::Strict => "strict",
+        }
+    }
+
+    pub fn from_u8(value: u8) -> CryptoPolicy {
+        match value {
+            1 => CryptoPolicy::Strict,
+            _ => CryptoPolicy::default(),
+        }
+    }
+
+    pub fn check_key_alg(&self, alg: KeyAlg) -> CliResult<()> {
+        if *self == CryptoPolicy::Strict && !APPROVED_KEY_ALGS.contains(&alg) {
+            return Err(CliError::InvalidInput(format!(
+                "Key algorithm \"{}\" is not allowed by the strict crypto policy. Approved algorithms: {}",
+                alg.as_str(),
+                APPROVED_KEY_ALGS
+                    .iter()
+                    .map(|alg| alg.as_str())
+                    .collect::<Vec<&str>>()
+                    .join(", ")
+            )));
+        }
+        Ok(())
+    }
+
+    pub fn check_key_derivation_method(&self, method: &str) -> CliResult<()> {
+        if *self == CryptoPolicy::Strict && !APPROVED_KEY_DERIVATION_METHODS.contains(&method) {
+            return Err(CliError::InvalidInput(format!(
+                "Key derivation method \"{}\" is not allowed by the strict crypto policy. \
+                Wallet storage uses Argon2i key derivation and ChaCha20-Poly1305 encryption which are not approved algorithms.",
+                method
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strict_policy_works() {
+        let policy = CryptoPolicy::Strict;
+        policy.check_key_alg(KeyAlg::Ed25519).unwrap();
+        policy.check_key_alg(KeyAlg::X25519).unwrap_err();
+        for method in ["argon2m", "argon2i", "raw"] {
+            policy.check_key_derivation_method(method).unwrap_err();
+        }
+    }
+
+    #[test]
+    #[cfg(not(feature = "fips"))]
+    fn default_policy_works() {
+        let policy = CryptoPolicy::default();
+        assert_eq!(CryptoPolicy::Default, policy);
+        policy.check_key_alg(KeyAlg::X25519).unwrap();
+        policy.check_key_derivation_method("raw").unwrap();
+        assert_eq!(
+            CryptoPolicy::Strict,
+            CryptoPolicy::from_u8(CryptoPolicy::Strict as u8)
+        );
+    }
+}
@@ -0,0 +1,61 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::{
+    error::{CliError, CliResult},
+    tools::{did::constants::CATEGORY_NYM_ALIAS, wallet::Wallet},
+    utils::futures::block_on,
+};
+
+use indy_utils::did::DidValue;
+
+/// Local `alias -> DID` mapping for NYMs published with an alias.
+///
+/// The ledger does not index NYMs by alias, so lookups by alias are resolved
+/// against the aliases recorded in the wallet when the NYM was sent.
+pub struct NymAlias {}
+
+impl NymAlias {
+    /// Map the alias to the DID. Returns the DID the alias has been mapped to before.
+    pub fn store(store: &Wallet, alias: &str, did: &DidValue) -> CliResult<Option<DidValue>> {
+        block_on(async move {
+            let previous = store
+                .fetch_record(CATEGORY_NYM_ALIAS, alias, true)
+                .await?
+                .map(|entry| DidValue(String::from_utf8_lossy(&entry.value).to_string()));
+            store
+                .store_record(
+                    CATEGORY_NYM_ALIAS,
+                    alias,
+                    did.to_string().as_bytes(),
+                    None,
+                    previous.is_none(),
+                )
+                .await?;
+            Ok(previous)
+        })
+    }
+
+    pub fn remove(store: &Wallet, alias: &str) -> CliResult<()> {
+        block_on(async move { store.remove_record(CATEGORY_NYM_ALIAS, alias).await })
+    }
+
+    pub fn resolve(store: &Wallet, alias: &str) -> CliResult<DidValue> {
+        block_on(async move {
+            let entry = store
+                .fetch_record(CATEGORY_NYM_ALIAS, alias, false)
+                .await?
+                .ok_or_else(|| {
+                    CliError::NotFound(format!(
+                        "NYM with alias \"{}\" is not known in the wallet.",
+                        alias
+                    ))
+                })?;
+            let did = String::from_utf8(entry.value.to_vec()).map_err(|_| {
+                CliError::InvalidEntityState(format!("Invalid DID stored for alias \"{}\"", alias))
+            })?;
+            Ok(DidValue(did))
+        })
+    }
+}
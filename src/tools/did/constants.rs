@@ -4,3 +4,9 @@
 */
 pub const KEY_TYPE: &'static str = "ed25519";
 pub const CATEGORY_DID: &'static str = "did";
+pub const CATEGORY_NYM_ALIAS: &'static str = "nym_alias";
+pub const INDY_DID_PREFIX: &'static str = "did:indy:";
+pub const DID_KEY_PREFIX: &'static str = "did:key:z";
+// Multicodec prefix of Ed25519 public key (0xed varint encoded)
+pub const ED25519_MULTICODEC_PREFIX: [u8; 2] = [0xed, 0x01];
+pub const ED25519_PUBLIC_KEY_LENGTH: usize = 32;
@@ -0,0 +1,221 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::error::{CliError, CliResult};
+
+use aries_askar::kms::{KeyAlg, LocalKey};
+use indy_utils::base58;
+use serde_json::Value as JsonValue;
+
+const DID_CONTEXT: &str = "https://www.w3.org/ns/did/v1";
+const ED25519_CONTEXT: &str = "https://w3id.org/security/suites/ed25519-2018/v1";
+const X25519_CONTEXT: &str = "https://w3id.org/security/suites/x25519-2019/v1";
+// Types of the services of an `endpoint` ATTRIB which does not list them
+const DEFAULT_ENDPOINT_TYPES: [&str; 2] = ["endpoint", "did-communication"];
+
+/// W3C DID Document of an Indy DID assembled from the ledger data as defined by the `did:indy` method:
+/// the NYM verkey, and either the NYM `diddocContent` or the services of the legacy `endpoint` ATTRIB.
+pub struct DidDocument<'a> {
+    pub id: &'a str,
+    /// Full (not abbreviated) verkey of the DID
+    pub verkey: &'a str,
+    /// Value of the `endpoint` ATTRIB: `{"endpoint": <url>, "routingKeys": [..], "types": [..]}`
+    pub endpoint: Option<&'a JsonValue>,
+    pub diddoc_content: Option<&'a JsonValue>,
+}
+
+impl<'a> DidDocument<'a> {
+    pub fn to_json(&self) -> CliResult<JsonValue> {
+        let verkey_id = format!("{}#verkey", self.id);
+        let mut document = json!({
+            "@context": [DID_CONTEXT, ED25519_CONTEXT],
+            "id": self.id,
+            "verificationMethod": [{
+                "id": verkey_id,
+                "type": "Ed25519VerificationKey2018",
+                "controller": self.id,
+                "publicKeyBase58": self.verkey,
+            }],
+            "authentication": [verkey_id],
+        });
+
+        // the endpoint ATTRIB is superseded by the content set in the NYM
+        match (self.diddoc_content, self.endpoint) {
+            (Some(content), _) => merge_content(&mut document, content)?,
+            (None, Some(endpoint)) => self.add_services(&mut document, endpoint)?,
+            (None, None) => {}
+        }
+        Ok(document)
+    }
+
+    fn add_services(&self, document: &mut JsonValue, endpoint: &JsonValue) -> CliResult<()> {
+        let url = endpoint["endpoint"].as_str().ok_or_else(|| {
+            CliError::InvalidEntityState(format!(
+                "Endpoint ATTRIB of {} has no service endpoint URL",
+                self.id
+            ))
+        })?;
+        let routing_keys = match endpoint["routingKeys"] {
+            JsonValue::Null => json!([]),
+            ref routing_keys => routing_keys.clone(),
+        };
+        let types = match endpoint["types"].as_array() {
+            Some(types) => types.iter().filter_map(JsonValue::as_str).collect(),
+            None => DEFAULT_ENDPOINT_TYPES.to_vec(),
+        };
+
+        // DIDComm messages are encrypted for the X25519 counterpart of the verkey
+        let key_agreement_id = format!("{}#key-agreement", self.id);
+        push(document, "@context", json!(X25519_CONTEXT));
+        push(
+            document,
+            "verificationMethod",
+            json!({
+                "id": key_agreement_id,
+                "type": "X25519KeyAgreementKey2019",
+                "controller": self.id,
+                "publicKeyBase58": x25519_public_key(self.verkey)?,
+            }),
+        );
+        document["keyAgreement"] = json!([key_agreement_id]);
+
+        let mut services = Vec::new();
+        for endpoint_type in types {
+            match endpoint_type {
+                "endpoint" => services.push(json!({
+                    "id": format!("{}#endpoint", self.id),
+                    "type": "endpoint",
+                    "serviceEndpoint": url,
+                })),
+                "did-communication" => services.push(json!({
+                    "id": format!("{}#did-communication", self.id),
+                    "type": "did-communication",
+                    "serviceEndpoint": url,
+                    "recipientKeys": [key_agreement_id],
+                    "routingKeys": routing_keys,
+                    "accept": ["didcomm/aip2;env=rfc19"],
+                    "priority": 0,
+                })),
+                "DIDComm" => services.push(json!({
+                    "id": format!("{}#didcomm-1", self.id),
+                    "type": "DIDComm",
+                    "serviceEndpoint": url,
+                    "routingKeys": routing_keys,
+                    "accept": ["didcomm/v2"],
+                })),
+                _ => {}
+            }
+        }
+        document["service"] = json!(services);
+        Ok(())
+    }
+}
+
+/// Extend the base document by `diddocContent`: lists are appended, other properties are set.
+fn merge_content(document: &mut JsonValue, content: &JsonValue) -> CliResult<()> {
+    let content = content.as_object().ok_or_else(|| {
+        CliError::InvalidEntityState("NYM diddocContent is not a JSON object".to_string())
+    })?;
+    for (key, value) in content {
+        match (key.as_str(), value) {
+            // the DID is defined by the ledger only
+            ("id", _) => {}
+            ("@context", JsonValue::Array(contexts)) => {
+                for context in contexts {
+                    push(document, key, context.clone());
+                }
+            }
+            ("@context", context) => push(document, key, context.clone()),
+            (_, JsonValue::Array(values)) if document[key].is_array() => {
+                for value in values {
+                    push(document, key, value.clone());
+                }
+            }
+            _ => document[key] = value.clone(),
+        }
+    }
+    Ok(())
+}
+
+fn push(document: &mut JsonValue, key: &str, value: JsonValue) {
+    if let Some(values) = document[key].as_array_mut() {
+        if !values.contains(&value) {
+            values.push(value);
+        }
+    }
+}
+
+fn x25519_public_key(verkey: &str) -> CliResult<String> {
+    let verkey = base58::decode(verkey)
+        .map_err(|_| CliError::InvalidInput(format!("Invalid verkey {} provided.", verkey)))?;
+    let key = LocalKey::from_public_bytes(KeyAlg::Ed25519, &verkey)?.convert_key(KeyAlg::X25519)?;
+    Ok(base58::encode(key.to_public_bytes()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ID: &str = "did:indy:sandbox:V4SGRU86Z58d6TV7PBUe6f";
+    const VERKEY: &str = "GJ1SzoWzavQYfNL9XkaJdrQejfztN4XqdsiV4ct3LXKL";
+
+    fn document(endpoint: Option<&JsonValue>, diddoc_content: Option<&JsonValue>) -> JsonValue {
+        DidDocument {
+            id: ID,
+            verkey: VERKEY,
+            endpoint,
+            diddoc_content,
+        }
+        .to_json()
+        .unwrap()
+    }
+
+    #[test]
+    fn did_document_works() {
+        let document = document(None, None);
+        assert_eq!(ID, document["id"]);
+        assert_eq!(VERKEY, document["verificationMethod"][0]["publicKeyBase58"]);
+        assert_eq!(
+            json!([format!("{}#verkey", ID)]),
+            document["authentication"]
+        );
+        assert!(document["service"].is_null());
+    }
+
+    #[test]
+    fn did_document_works_for_endpoint() {
+        let endpoint = json!({ "endpoint": "https://agent.example.com", "routingKeys": [] });
+        let document = document(Some(&endpoint), None);
+        let services = document["service"].as_array().unwrap();
+        assert_eq!(2, services.len());
+        assert_eq!("endpoint", services[0]["type"]);
+        assert_eq!("https://agent.example.com", services[1]["serviceEndpoint"]);
+        assert_eq!(
+            json!([format!("{}#key-agreement", ID)]),
+            services[1]["recipientKeys"]
+        );
+        let key_agreement = &document["verificationMethod"][1];
+        assert_eq!("X25519KeyAgreementKey2019", key_agreement["type"]);
+        assert_ne!(VERKEY, key_agreement["publicKeyBase58"]);
+    }
+
+    #[test]
+    fn did_document_works_for_diddoc_content() {
+        let endpoint = json!({ "endpoint": "https://agent.example.com" });
+        let content = json!({
+            "@context": ["https://identity.foundation/linked-vp/contexts/v1"],
+            "id": "did:example:other",
+            "service": [{
+                "id": format!("{}#whois", ID),
+                "type": "LinkedVerifiablePresentation",
+                "serviceEndpoint": "https://example.com/whois.vp",
+            }],
+        });
+        let document = document(Some(&endpoint), Some(&content));
+        assert_eq!(ID, document["id"]);
+        assert_eq!(3, document["@context"].as_array().unwrap().len());
+        assert_eq!(1, document["service"].as_array().unwrap().len());
+        assert!(document["keyAgreement"].is_null());
+    }
+}
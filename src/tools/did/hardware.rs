@@ -0,0 +1,318 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::error::{CliError, CliResult};
+
+pub const DEVICES: [&str; 1] = ["ledger"];
+
+// Hardened derivation flag of BIP32 path components
+const HARDENED: u32 = 0x8000_0000;
+const MAX_PATH_LENGTH: usize = 10;
+
+/// Key held by a hardware wallet. The DID record keeps it as `<device>:<BIP32 path>` locator
+/// instead of a wallet key, so the secret key never leaves the device.
+///
+/// Experimental: the device must run an app implementing Ed25519 signing of raw messages
+/// with `GET_PUBLIC_KEY` (INS 0x02) and `SIGN` (INS 0x04) APDUs of CLA 0xE0.
+#[derive(Debug, PartialEq)]
+pub struct HardwareKey {
+    pub device: String,
+    pub path: Vec<u32>,
+}
+
+impl HardwareKey {
+    pub fn new(device: &str, path: &str) -> CliResult<HardwareKey> {
+        if !DEVICES.contains(&device) {
+            return Err(CliError::InvalidInput(format!(
+                "Unsupported hardware wallet \"{}\". Supported devices: {}",
+                device,
+                DEVICES.join(", ")
+            )));
+        }
+        Ok(HardwareKey {
+            device: device.to_string(),
+            path: parse_path(path)?,
+        })
+    }
+
+    /// Parse the locator stored in the DID record: `ledger:44'/1'/0'`.
+    pub fn from_locator(locator: &str) -> CliResult<HardwareKey> {
+        let (device, path) = locator.split_once(':').ok_or_else(|| {
+            CliError::InvalidEntityState(format!("Invalid hardware key locator \"{}\"", locator))
+        })?;
+        HardwareKey::new(device, path)
+    }
+
+    pub fn locator(&self) -> String {
+        let path = self
+            .path
+            .iter()
+            .map(|index| format!("{}'", index & !HARDENED))
+            .collect::<Vec<String>>()
+            .join("/");
+        format!("{}:{}", self.device, path)
+    }
+
+    pub fn public_key(&self) -> CliResult<Vec<u8>> {
+        ledger::get_public_key(&self.path)
+    }
+
+    /// Sign the message on the device. Blocks until the signing is confirmed on the device.
+    pub fn sign(&self, message: &[u8]) -> CliResult<Vec<u8>> {
+        ledger::sign(&self.path, message)
+    }
+}
+
+// `m/44'/1'/0'` or `44'/1'/0'`. Ed25519 keys (SLIP-0010) are derived with hardened components only.
+fn parse_path(path: &str) -> CliResult<Vec<u32>> {
+    let invalid = || {
+        CliError::InvalidInput(format!(
+            "Invalid BIP32 path \"{}\". Expected hardened components, e.g. 44'/1'/0'",
+            path
+        ))
+    };
+    let components = path
+        .trim()
+        .trim_start_matches("m/")
+        .split('/')
+        .map(|component| {
+            let index = component
+                .strip_suffix('\'')
+                .or_else(|| component.strip_suffix('h'))
+                .ok_or_else(invalid)?
+                .parse::<u32>()
+                .map_err(|_| invalid())?;
+            if index >= HARDENED {
+                return Err(invalid());
+            }
+            Ok(index | HARDENED)
+        })
+        .collect::<CliResult<Vec<u32>>>()?;
+    if components.is_empty() || components.len() > MAX_PATH_LENGTH {
+        return Err(invalid());
+    }
+    Ok(components)
+}
+
+#[cfg(feature = "hardware_wallet")]
+mod ledger {
+    use super::*;
+    use hidapi::{HidApi, HidDevice};
+
+    const VENDOR_ID: u16 = 0x2c97;
+    const USAGE_PAGE: u16 = 0xffa0;
+    const CHANNEL: u16 = 0x0101;
+    const TAG_APDU: u8 = 0x05;
+    const PACKET_SIZE: usize = 64;
+    const READ_TIMEOUT_MS: i32 = 60_000;
+
+    const CLA: u8 = 0xE0;
+    const INS_GET_PUBLIC_KEY: u8 = 0x02;
+    const INS_SIGN: u8 = 0x04;
+    const P1_FIRST: u8 = 0x00;
+    const P1_MORE: u8 = 0x80;
+    const MAX_CHUNK: usize = 255;
+    const SW_OK: u16 = 0x9000;
+    const SW_DENIED: u16 = 0x6985;
+
+    pub fn get_public_key(path: &[u32]) -> CliResult<Vec<u8>> {
+        let device = open()?;
+        exchange(&device, INS_GET_PUBLIC_KEY, P1_FIRST, &serialize_path(path))
+    }
+
+    pub fn sign(path: &[u32], message: &[u8]) -> CliResult<Vec<u8>> {
+        let device = open()?;
+
+        // The path goes first, the message is split over as many APDUs as needed
+        let mut data = serialize_path(path);
+        data.extend_from_slice(message);
+        let chunks = data.chunks(MAX_CHUNK).collect::<Vec<&[u8]>>();
+        let mut response = Vec::new();
+        for (index, chunk) in chunks.iter().enumerate() {
+            let p1 = if index == 0 { P1_FIRST } else { P1_MORE };
+            response = exchange(&device, INS_SIGN, p1, chunk)?;
+        }
+        Ok(response)
+    }
+
+    fn open() -> CliResult<HidDevice> {
+        let api = HidApi::new().map_err(hid_error)?;
+        let info = api
+            .device_list()
+            .find(|info| info.vendor_id() == VENDOR_ID && info.usage_page() == USAGE_PAGE)
+            .ok_or_else(|| {
+                CliError::NotFound(
+                    "Ledger device is not connected or is locked. Unlock it and open the signing app."
+                        .to_string(),
+                )
+            })?;
+        info.open_device(&api).map_err(hid_error)
+    }
+
+    fn exchange(device: &HidDevice, ins: u8, p1: u8, data: &[u8]) -> CliResult<Vec<u8>> {
+        let mut apdu = vec![CLA, ins, p1, 0x00, data.len() as u8];
+        apdu.extend_from_slice(data);
+
+        for packet in wrap_apdu(&apdu) {
+            // hidapi expects the report ID before the packet
+            let mut report = vec![0x00];
+            report.extend_from_slice(&packet);
+            device.write(&report).map_err(hid_error)?;
+        }
+
+        let mut packets = Vec::new();
+        loop {
+            let mut packet = [0u8; PACKET_SIZE];
+            let read = device
+                .read_timeout(&mut packet, READ_TIMEOUT_MS)
+                .map_err(hid_error)?;
+            if read == 0 {
+                return Err(CliError::InvalidEntityState(
+                    "Ledger device has not responded".to_string(),
+                ));
+            }
+            packets.push(packet.to_vec());
+            if let Some(response) = unwrap_response(&packets)? {
+                return check_status(response);
+            }
+        }
+    }
+
+    fn check_status(mut response: Vec<u8>) -> CliResult<Vec<u8>> {
+        if response.len() < 2 {
+            return Err(CliError::InvalidEntityState(
+                "Ledger device has sent an invalid response".to_string(),
+            ));
+        }
+        let status = response.split_off(response.len() - 2);
+        match u16::from_be_bytes([status[0], status[1]]) {
+            SW_OK => Ok(response),
+            SW_DENIED => Err(CliError::InvalidEntityState(
+                "Signing has been rejected on the Ledger device".to_string(),
+            )),
+            status => Err(CliError::InvalidEntityState(format!(
+                "Ledger device has returned error 0x{:04x}. Check that the signing app is open",
+                status
+            ))),
+        }
+    }
+
+    fn serialize_path(path: &[u32]) -> Vec<u8> {
+        let mut data = vec![path.len() as u8];
+        for index in path {
+            data.extend_from_slice(&index.to_be_bytes());
+        }
+        data
+    }
+
+    // HID packets: channel, tag, sequence number; the first packet also carries the APDU length
+    fn wrap_apdu(apdu: &[u8]) -> Vec<Vec<u8>> {
+        let mut data = (apdu.len() as u16).to_be_bytes().to_vec();
+        data.extend_from_slice(apdu);
+
+        data.chunks(PACKET_SIZE - 5)
+            .enumerate()
+            .map(|(sequence, chunk)| {
+                let mut packet = CHANNEL.to_be_bytes().to_vec();
+                packet.push(TAG_APDU);
+                packet.extend_from_slice(&(sequence as u16).to_be_bytes());
+                packet.extend_from_slice(chunk);
+                packet.resize(PACKET_SIZE, 0);
+                packet
+            })
+            .collect()
+    }
+
+    // `None` while more packets are expected
+    fn unwrap_response(packets: &[Vec<u8>]) -> CliResult<Option<Vec<u8>>> {
+        let invalid =
+            || CliError::InvalidEntityState("Ledger device has sent an invalid packet".to_string());
+        let mut data = Vec::new();
+        for (sequence, packet) in packets.iter().enumerate() {
+            if packet.len() < 5
+                || packet[..2] != CHANNEL.to_be_bytes()
+                || packet[2] != TAG_APDU
+                || packet[3..5] != (sequence as u16).to_be_bytes()
+            {
+                return Err(invalid());
+            }
+            data.extend_from_slice(&packet[5..]);
+        }
+        if data.len() < 2 {
+            return Err(invalid());
+        }
+        let length = u16::from_be_bytes([data[0], data[1]]) as usize;
+        if data.len() - 2 < length {
+            return Ok(None);
+        }
+        Ok(Some(data[2..2 + length].to_vec()))
+    }
+
+    fn hid_error(err: hidapi::HidError) -> CliError {
+        CliError::InvalidEntityState(format!("Hardware wallet communication has failed: {}", err))
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn wrap_apdu_works() {
+            let apdu = vec![0xAB; 100];
+            let packets = wrap_apdu(&apdu);
+            assert_eq!(2, packets.len());
+            assert_eq!(&packets[0][..7], &[0x01, 0x01, 0x05, 0x00, 0x00, 0x00, 100]);
+            assert_eq!(&packets[1][..5], &[0x01, 0x01, 0x05, 0x00, 0x01]);
+            assert_eq!(apdu, unwrap_response(&packets).unwrap().unwrap());
+            assert_eq!(None, unwrap_response(&packets[..1]).unwrap());
+        }
+
+        #[test]
+        fn check_status_works() {
+            assert_eq!(vec![1, 2], check_status(vec![1, 2, 0x90, 0x00]).unwrap());
+            check_status(vec![0x69, 0x85]).unwrap_err();
+        }
+    }
+}
+
+#[cfg(not(feature = "hardware_wallet"))]
+mod ledger {
+    use super::*;
+
+    fn unsupported() -> CliError {
+        CliError::InvalidEntityState(
+            "CLI has been built without hardware wallet support. Rebuild it with `hardware_wallet` feature"
+                .to_string(),
+        )
+    }
+
+    pub fn get_public_key(_path: &[u32]) -> CliResult<Vec<u8>> {
+        Err(unsupported())
+    }
+
+    pub fn sign(_path: &[u32], _message: &[u8]) -> CliResult<Vec<u8>> {
+        Err(unsupported())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hardware_key_locator_works() {
+        let key = HardwareKey::new("ledger", "m/44'/1'/0h").unwrap();
+        assert_eq!(vec![44 | HARDENED, 1 | HARDENED, HARDENED], key.path);
+        assert_eq!("ledger:44'/1'/0'", key.locator());
+        assert_eq!(key, HardwareKey::from_locator(&key.locator()).unwrap());
+    }
+
+    #[test]
+    fn hardware_key_works_for_invalid_input() {
+        HardwareKey::new("trezor", "44'/1'").unwrap_err();
+        HardwareKey::new("ledger", "44'/1").unwrap_err();
+        HardwareKey::new("ledger", "").unwrap_err();
+        HardwareKey::from_locator("44'/1'").unwrap_err();
+    }
+}
@@ -4,11 +4,12 @@
 */
 use crate::{
     error::{CliError, CliResult},
-    tools::did::seed::Seed,
+    tools::{crypto_policy::CryptoPolicy, did::seed::Seed},
 };
 
 use crate::tools::wallet::Wallet;
-use aries_askar::kms::{KeyAlg, LocalKey, SecretBytes};
+use aries_askar::kms::{crypto_box_seal_open, KeyAlg, LocalKey, SecretBytes};
+use ed25519_dalek::{Digest, Keypair, PublicKey, SecretKey, Sha512, Signature, SignatureError};
 use indy_utils::base58;
 
 pub struct Key(LocalKey);
@@ -36,6 +37,12 @@ impl Key {
         Ok(key)
     }
 
+    pub fn verkey_from_seed(seed: &str) -> CliResult<String> {
+        let seed = Seed::from_str(seed)?;
+        let keypair = LocalKey::from_secret_bytes(KeyAlg::Ed25519, seed.value())?;
+        Key(keypair).verkey()
+    }
+
     fn value(&self) -> &LocalKey {
         &self.0
     }
@@ -56,4 +63,59 @@ impl Key {
             .sign_message(bytes, None)
             .map_err(CliError::from)
     }
+
+    pub fn verify(verkey: &str, message: &[u8], signature: &[u8]) -> CliResult<bool> {
+        let verkey = base58::decode(verkey)
+            .map_err(|_| CliError::InvalidInput(format!("Invalid verkey {} provided.", verkey)))?;
+        LocalKey::from_public_bytes(KeyAlg::Ed25519, &verkey)?
+            .verify_signature(message, signature, None)
+            .map_err(CliError::from)
+    }
+
+    /// Ed25519ph (RFC 8032) signature: the message is hashed with SHA-512 and signed with empty context.
+    pub async fn sign_prehashed(store: &Wallet, id: &str, message: &[u8]) -> CliResult<Vec<u8>> {
+        let seed = Self::secret_bytes(store, id).await?;
+        let secret = SecretKey::from_bytes(&seed).map_err(signature_error)?;
+        let public = PublicKey::from(&secret);
+        let signature = Keypair { secret, public }
+            .sign_prehashed(Sha512::new().chain(message), None)
+            .map_err(signature_error)?;
+        Ok(signature.to_bytes().to_vec())
+    }
+
+    pub fn verify_prehashed(verkey: &str, message: &[u8], signature: &[u8]) -> CliResult<bool> {
+        let verkey = base58::decode(verkey)
+            .map_err(|_| CliError::InvalidInput(format!("Invalid verkey {} provided.", verkey)))?;
+        let public = PublicKey::from_bytes(&verkey).map_err(signature_error)?;
+        let signature = Signature::try_from(signature).map_err(signature_error)?;
+        Ok(public
+            .verify_prehashed(Sha512::new().chain(message), None, &signature)
+            .is_ok())
+    }
+
+    /// Ed25519 secret key bytes, which are the seed the key has been created from.
+    pub async fn secret_bytes(store: &Wallet, id: &str) -> CliResult<SecretBytes> {
+        store
+            .fetch_key(id)
+            .await?
+            .to_secret_bytes()
+            .map_err(CliError::from)
+    }
+
+    pub async fn decrypt_sealed(
+        store: &Wallet,
+        id: &str,
+        ciphertext: &[u8],
+        policy: CryptoPolicy,
+    ) -> CliResult<Vec<u8>> {
+        policy.check_key_alg(KeyAlg::X25519)?;
+        let key = store.fetch_key(id).await?.convert_key(KeyAlg::X25519)?;
+        crypto_box_seal_open(&key, ciphertext)
+            .map(|message| message.to_vec())
+            .map_err(CliError::from)
+    }
+}
+
+fn signature_error(err: SignatureError) -> CliError {
+    CliError::InvalidInput(format!("Ed25519ph signature error: {}", err))
 }
@@ -2,8 +2,12 @@
     Copyright © 2023 Province of British Columbia
     https://digital.gov.bc.ca/digital-trust
 */
+pub mod alias;
 pub mod constants;
+pub mod document;
+pub mod hardware;
 pub mod key;
+pub mod paper;
 pub mod seed;
 
 use crate::{
@@ -11,17 +15,29 @@ use crate::{
     utils::futures::block_on,
 };
 
-use crate::tools::wallet::Wallet;
-use aries_askar::{Entry, EntryTag};
+use crate::tools::{crypto_policy::CryptoPolicy, wallet::Wallet};
+use aries_askar::{kms::SecretBytes, Entry, EntryTag};
 use indy_utils::{base58, did::DidValue, keys::EncodedVerKey, Qualifiable};
+use serde_json::Value as JsonValue;
 
 use self::{
-    constants::{CATEGORY_DID, KEY_TYPE},
+    constants::{
+        CATEGORY_DID, DID_KEY_PREFIX, ED25519_MULTICODEC_PREFIX, ED25519_PUBLIC_KEY_LENGTH,
+        INDY_DID_PREFIX, KEY_TYPE,
+    },
+    hardware::HardwareKey,
     key::Key,
 };
 
 pub struct Did {}
 
+#[derive(Debug, Default)]
+pub struct ReindexStats {
+    pub total: usize,
+    pub updated: usize,
+    pub invalid: Vec<String>,
+}
+
 #[derive(Debug, Default, Serialize, Deserialize)]
 pub struct DidInfo {
     pub did: String,
@@ -30,6 +46,8 @@ pub struct DidInfo {
     pub method: Option<String>,
     pub metadata: Option<String>,
     pub next_verkey: Option<String>,
+    /// Locator of the key held by a hardware wallet, the wallet keeps no key for such DIDs.
+    pub signer: Option<String>,
 }
 
 impl Did {
@@ -42,45 +60,85 @@ impl Did {
     ) -> CliResult<(String, String)> {
         block_on(async move {
             let key = Key::create(store, seed, metadata).await?;
-
-            let verkey = key.verkey()?;
             let verkey_bytes = key.verkey_bytes()?;
-            let mut did = match did {
-                Some(did) => did.to_string(),
-                None => base58::encode(&verkey_bytes[0..16]),
-            };
 
-            let existing_did = Self::get_opt_record(store, &did, false).await?;
-            if existing_did.is_some() {
-                return Err(CliError::Duplicate(format!(
-                    "DID already exits in the wallet"
-                )));
-            }
-            if let Some(method) = method {
-                did = DidValue(did.to_string()).to_qualified(method)?.to_string();
-            }
+            Self::store_new(store, did, &verkey_bytes, metadata, method, None).await
+        })
+    }
 
-            let did_info = DidInfo {
-                did: did.clone(),
-                verkey: verkey.clone(),
-                verkey_type: KEY_TYPE.to_string(),
-                method: method.map(String::from),
-                metadata: metadata.map(String::from),
-                next_verkey: None,
-            };
+    /// Add DID controlled by the key held by a hardware wallet. Only the public key is stored.
+    pub fn create_hardware(
+        store: &Wallet,
+        did: Option<&str>,
+        hardware_key: &HardwareKey,
+        metadata: Option<&str>,
+        method: Option<&str>,
+    ) -> CliResult<(String, String)> {
+        let verkey_bytes = hardware_key.public_key()?;
+        if verkey_bytes.len() != ED25519_PUBLIC_KEY_LENGTH {
+            return Err(CliError::InvalidEntityState(format!(
+                "Hardware wallet has returned an invalid Ed25519 public key of {} bytes",
+                verkey_bytes.len()
+            )));
+        }
+        block_on(async move {
+            Self::store_new(
+                store,
+                did,
+                &verkey_bytes,
+                metadata,
+                method,
+                Some(hardware_key.locator()),
+            )
+            .await
+        })
+    }
 
-            store
-                .store_record(
-                    CATEGORY_DID,
-                    &did_info.did,
-                    &did_info.to_bytes()?,
-                    Some(&did_info.tags()),
-                    true,
-                )
-                .await?;
+    async fn store_new(
+        store: &Wallet,
+        did: Option<&str>,
+        verkey_bytes: &[u8],
+        metadata: Option<&str>,
+        method: Option<&str>,
+        signer: Option<String>,
+    ) -> CliResult<(String, String)> {
+        let verkey = base58::encode(verkey_bytes);
+        let mut did = match did {
+            Some(did) => did.to_string(),
+            None => base58::encode(&verkey_bytes[0..16]),
+        };
+
+        let existing_did = Self::get_opt_record(store, &did, false).await?;
+        if existing_did.is_some() {
+            return Err(CliError::Duplicate(format!(
+                "DID already exits in the wallet"
+            )));
+        }
+        if let Some(method) = method {
+            did = DidValue(did.to_string()).to_qualified(method)?.to_string();
+        }
 
-            Ok((did, verkey))
-        })
+        let did_info = DidInfo {
+            did: did.clone(),
+            verkey: verkey.clone(),
+            verkey_type: KEY_TYPE.to_string(),
+            method: method.map(String::from),
+            metadata: metadata.map(String::from),
+            next_verkey: None,
+            signer,
+        };
+
+        store
+            .store_record(
+                CATEGORY_DID,
+                &did_info.did,
+                &did_info.to_bytes()?,
+                Some(&did_info.tags()),
+                true,
+            )
+            .await?;
+
+        Ok((did, verkey))
     }
 
     pub fn replace_keys_start(store: &Wallet, did: &str, seed: Option<&str>) -> CliResult<String> {
@@ -109,14 +167,16 @@ impl Did {
 
     pub fn replace_keys_apply(store: &Wallet, did: &str) -> CliResult<()> {
         block_on(async move {
-            let (did_entry, mut did_info) = Self::get_record(store, &did, true).await?;
+            let (_, mut did_info) = Self::get_record(store, &did, true).await?;
 
             let next_verkey = did_info.next_verkey.ok_or_else(|| {
                 CliError::InvalidEntityState(format!("Next key is not set for the DID {}.", did))
             })?;
 
+            // The next key is always created in the wallet
             did_info.verkey = next_verkey;
             did_info.next_verkey = None;
+            did_info.signer = None;
 
             let value = serde_json::to_vec(&did_info)?;
             store
@@ -124,7 +184,7 @@ impl Did {
                     CATEGORY_DID,
                     &did_info.did,
                     &value,
-                    Some(&did_entry.tags),
+                    Some(&did_info.tags()),
                     false,
                 )
                 .await?;
@@ -135,7 +195,7 @@ impl Did {
 
     pub fn set_metadata(store: &Wallet, did: &str, metadata: &str) -> CliResult<()> {
         block_on(async move {
-            let (did_entry, mut did_info) = Self::get_record(store, &did, true).await?;
+            let (_, mut did_info) = Self::get_record(store, &did, true).await?;
 
             did_info.metadata = Some(metadata.to_string());
 
@@ -145,7 +205,7 @@ impl Did {
                     CATEGORY_DID,
                     &did_info.did,
                     &value,
-                    Some(&did_entry.tags),
+                    Some(&did_info.tags()),
                     false,
                 )
                 .await?;
@@ -172,6 +232,61 @@ impl Did {
         })
     }
 
+    /// Default (unqualified) DID for the verkey: first 16 bytes of the key in base58.
+    pub fn from_verkey(verkey: &str) -> CliResult<String> {
+        let verkey_bytes = base58::decode(verkey)
+            .map_err(|_| CliError::InvalidInput(format!("Invalid verkey {} provided.", verkey)))?;
+        if verkey_bytes.len() < 16 {
+            return Err(CliError::InvalidInput(format!(
+                "Invalid verkey {} provided.",
+                verkey
+            )));
+        }
+        Ok(base58::encode(&verkey_bytes[0..16]))
+    }
+
+    /// `did:key` representation of the full Ed25519 verkey: multicodec prefixed key in base58btc.
+    pub fn to_did_key(verkey: &str) -> CliResult<String> {
+        let verkey_bytes = base58::decode(verkey)
+            .map_err(|_| CliError::InvalidInput(format!("Invalid verkey {} provided.", verkey)))?;
+        let mut bytes = ED25519_MULTICODEC_PREFIX.to_vec();
+        bytes.extend(verkey_bytes);
+        Ok(format!("{}{}", DID_KEY_PREFIX, base58::encode(bytes)))
+    }
+
+    /// Namespace of a `did:indy` DID: `sovrin:staging` for `did:indy:sovrin:staging:<id>`.
+    pub fn namespace(did: &DidValue) -> Option<String> {
+        did.0
+            .strip_prefix(INDY_DID_PREFIX)
+            .and_then(|rest| rest.rsplit_once(':'))
+            .map(|(namespace, _)| namespace.to_string())
+    }
+
+    /// Short form of the DID as it is stored on Indy ledgers.
+    pub fn short_form(did: &DidValue) -> DidValue {
+        match did.0.rsplit_once(':') {
+            Some((_, id)) if did.is_fully_qualified() => DidValue(id.to_string()),
+            _ => did.clone(),
+        }
+    }
+
+    /// Expand abbreviated (`~`-prefixed) verkey to the full form.
+    pub fn full_verkey(did: &str, verkey: &str) -> CliResult<String> {
+        let abbreviated = match verkey.strip_prefix('~') {
+            Some(abbreviated) => abbreviated,
+            None => return Ok(verkey.to_string()),
+        };
+        let did = Self::short_form(&DidValue(did.to_string()));
+        let mut verkey_bytes = base58::decode(&did.0)
+            .map_err(|_| CliError::InvalidInput(format!("Invalid DID {} provided.", did)))?;
+        verkey_bytes.extend(
+            base58::decode(abbreviated).map_err(|_| {
+                CliError::InvalidInput(format!("Invalid verkey {} provided.", verkey))
+            })?,
+        );
+        Ok(base58::encode(verkey_bytes))
+    }
+
     pub fn abbreviate_verkey(did: &str, verkey: &str) -> CliResult<String> {
         let did = DidValue(did.to_string()).to_short().to_string();
         EncodedVerKey::from_did_and_verkey(&did, verkey)?
@@ -181,7 +296,7 @@ impl Did {
 
     pub fn qualify(store: &Wallet, did: &DidValue, method: &str) -> CliResult<DidValue> {
         block_on(async {
-            let (entry, did_info) = Self::get_opt_record(store, &did.to_string(), true)
+            let (_, did_info) = Self::get_opt_record(store, &did.to_string(), true)
                 .await?
                 .ok_or_else(|| {
                     CliError::NotFound(format!("DID {} does not exits in the wallet!", did))
@@ -195,22 +310,96 @@ impl Did {
 
             let did_info = DidInfo {
                 did: qualified_did.to_string(),
+                method: Some(method.to_string()),
                 ..did_info
             };
 
             let value = serde_json::to_vec(&did_info)?;
             store
-                .store_record(CATEGORY_DID, &did_info.did, &value, Some(&entry.tags), true)
+                .store_record(
+                    CATEGORY_DID,
+                    &did_info.did,
+                    &value,
+                    Some(&did_info.tags()),
+                    true,
+                )
                 .await?;
 
             Ok(qualified_did)
         })
     }
 
+    /// Rebuild tags of all DID records from their values.
+    /// Wallets created by older releases miss the tags added later, so tag based search skips their DIDs.
+    pub fn reindex(store: &Wallet) -> CliResult<ReindexStats> {
+        block_on(async move {
+            let mut stats = ReindexStats::default();
+            for entry in store.fetch_all_records(CATEGORY_DID).await? {
+                stats.total += 1;
+                let did_info = match DidInfo::from_bytes(&entry.value) {
+                    Ok(did_info) => did_info,
+                    Err(_) => {
+                        stats.invalid.push(entry.name.clone());
+                        continue;
+                    }
+                };
+
+                let tags = did_info.tags();
+                if tags_equal(&tags, &entry.tags) {
+                    continue;
+                }
+                store
+                    .store_record(CATEGORY_DID, &entry.name, &entry.value, Some(&tags), false)
+                    .await?;
+                stats.updated += 1;
+            }
+            Ok(stats)
+        })
+    }
+
     pub async fn sign(store: &Wallet, did: &str, bytes: &[u8]) -> CliResult<Vec<u8>> {
         let (_, did_info) = Self::get_record(store, &did, true).await?;
 
-        Key::sign(store, &did_info.verkey, bytes).await
+        match did_info.signer {
+            Some(ref signer) => HardwareKey::from_locator(signer)?.sign(bytes),
+            None => Key::sign(store, &did_info.verkey, bytes).await,
+        }
+    }
+
+    /// Ed25519ph signature of the bytes with the current key of the DID.
+    /// Hardware wallets sign pure Ed25519 only.
+    pub async fn sign_prehashed(store: &Wallet, did: &str, bytes: &[u8]) -> CliResult<Vec<u8>> {
+        let (_, did_info) = Self::get_record(store, &did, true).await?;
+        did_info.ensure_software_key()?;
+        Key::sign_prehashed(store, &did_info.verkey, bytes).await
+    }
+
+    /// Sign arbitrary bytes with the current key of the DID.
+    pub fn sign_message(store: &Wallet, did: &DidValue, message: &[u8]) -> CliResult<Vec<u8>> {
+        block_on(Self::sign(store, did, message))
+    }
+
+    /// Seed of the current key of the DID.
+    pub fn export_seed(store: &Wallet, did: &DidValue) -> CliResult<(DidInfo, SecretBytes)> {
+        block_on(async move {
+            let (_, did_info) = Self::get_record(store, did, false).await?;
+            did_info.ensure_software_key()?;
+            let seed = Key::secret_bytes(store, &did_info.verkey).await?;
+            Ok((did_info, seed))
+        })
+    }
+
+    pub fn decrypt_sealed(
+        store: &Wallet,
+        did: &DidValue,
+        ciphertext: &[u8],
+        policy: CryptoPolicy,
+    ) -> CliResult<Vec<u8>> {
+        block_on(async move {
+            let (_, did_info) = Self::get_record(store, did, false).await?;
+            did_info.ensure_software_key()?;
+            Key::decrypt_sealed(store, &did_info.verkey, ciphertext, policy).await
+        })
     }
 
     async fn remove(store: &Wallet, name: &str) -> CliResult<()> {
@@ -246,6 +435,16 @@ impl Did {
 }
 
 impl DidInfo {
+    fn ensure_software_key(&self) -> CliResult<()> {
+        match self.signer {
+            Some(ref signer) => Err(CliError::InvalidEntityState(format!(
+                "The key of DID {} is held by hardware wallet \"{}\" and cannot be used for this operation.",
+                self.did, signer
+            ))),
+            None => Ok(()),
+        }
+    }
+
     pub fn from_bytes(bytes: &[u8]) -> CliResult<Self> {
         serde_json::from_slice(bytes)
             .map_err(|_| CliError::InvalidInput("Unable to parse did info".to_string()))
@@ -255,6 +454,8 @@ impl DidInfo {
         serde_json::to_vec(self).map_err(CliError::from)
     }
 
+    /// Tags of the DID record: verkey, method, `did:indy` network and metadata.
+    /// Top level scalar fields of JSON object metadata are indexed as `metadata.<field>` tags.
     pub fn tags(&self) -> Vec<EntryTag> {
         let mut tags = vec![
             EntryTag::Encrypted("verkey".to_string(), self.verkey.to_string()),
@@ -266,6 +467,86 @@ impl DidInfo {
                 method.to_string(),
             ))
         }
+        if let Some(network) = Did::namespace(&DidValue(self.did.to_string())) {
+            tags.push(EntryTag::Encrypted("network".to_string(), network))
+        }
+        if let Some(ref metadata) = self.metadata {
+            tags.push(EntryTag::Encrypted(
+                "metadata".to_string(),
+                metadata.to_string(),
+            ));
+            if let Ok(JsonValue::Object(fields)) = serde_json::from_str::<JsonValue>(metadata) {
+                for (name, value) in fields {
+                    let value = match value {
+                        JsonValue::String(value) => value,
+                        JsonValue::Number(_) | JsonValue::Bool(_) => value.to_string(),
+                        _ => continue,
+                    };
+                    tags.push(EntryTag::Encrypted(format!("metadata.{}", name), value))
+                }
+            }
+        }
         tags
     }
 }
+
+// Tags are compared regardless of their order
+fn tags_equal(left: &[EntryTag], right: &[EntryTag]) -> bool {
+    fn sorted(tags: &[EntryTag]) -> Vec<(bool, &str, &str)> {
+        let mut tags = tags
+            .iter()
+            .map(|tag| match tag {
+                EntryTag::Encrypted(name, value) => (true, name.as_str(), value.as_str()),
+                EntryTag::Plaintext(name, value) => (false, name.as_str(), value.as_str()),
+            })
+            .collect::<Vec<_>>();
+        tags.sort();
+        tags
+    }
+    sorted(left) == sorted(right)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn namespace_works() {
+        assert_eq!(
+            Did::namespace(&DidValue(
+                "did:indy:sovrin:staging:V4SGRU86Z58d6TV7PBUe6f".to_string()
+            )),
+            Some("sovrin:staging".to_string())
+        );
+        assert_eq!(
+            Did::namespace(&DidValue(
+                "did:indy:idunion:V4SGRU86Z58d6TV7PBUe6f".to_string()
+            )),
+            Some("idunion".to_string())
+        );
+    }
+
+    #[test]
+    fn namespace_works_for_not_indy_did() {
+        assert_eq!(
+            Did::namespace(&DidValue("V4SGRU86Z58d6TV7PBUe6f".to_string())),
+            None
+        );
+        assert_eq!(
+            Did::namespace(&DidValue("did:sov:V4SGRU86Z58d6TV7PBUe6f".to_string())),
+            None
+        );
+    }
+
+    #[test]
+    fn short_form_works() {
+        let short = DidValue("V4SGRU86Z58d6TV7PBUe6f".to_string());
+        assert_eq!(
+            Did::short_form(&DidValue(
+                "did:indy:sovrin:staging:V4SGRU86Z58d6TV7PBUe6f".to_string()
+            )),
+            short
+        );
+        assert_eq!(Did::short_form(&short), short);
+    }
+}
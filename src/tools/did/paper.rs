@@ -0,0 +1,261 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::{
+    error::{CliError, CliResult},
+    utils::{
+        page::{Font, Page, PAGE_HEIGHT},
+        pdf::PdfPage,
+        raster::RasterPage,
+    },
+};
+
+use chrono::Utc;
+use indy_utils::hash::SHA256;
+use qrcode::{Color, QrCode};
+use zeroize::Zeroizing;
+
+const SEED_LENGTH: usize = 32;
+const CHECKSUM_LENGTH: usize = 4;
+const WORDS_PER_LINE: usize = 6;
+const QR_MODULE_SIZE: f64 = 5.0;
+const MARGIN: f64 = 50.0;
+
+// One word per byte, so that the seed can be read out and typed without ambiguity
+const WORDS: [&str; 256] = [
+    "acid", "acorn", "actor", "adult", "agent", "album", "alarm", "alley", "amber", "angel",
+    "angle", "ankle", "apple", "april", "apron", "arena", "argue", "armor", "arrow", "atlas",
+    "attic", "audio", "aunt", "autumn", "avoid", "awake", "badge", "bagel", "baker", "bamboo",
+    "banjo", "barn", "basil", "basin", "beach", "beard", "beast", "bench", "berry", "bike", "bird",
+    "blade", "blank", "blend", "bloom", "board", "boat", "bonus", "boost", "boot", "brain",
+    "brave", "bread", "brick", "bride", "brief", "broom", "brush", "bucket", "buddy", "bulb",
+    "bunny", "cabin", "cable", "cactus", "camel", "camp", "canal", "candy", "canoe", "canvas",
+    "cargo", "carpet", "carrot", "castle", "cedar", "chair", "chalk", "chess", "chief", "chili",
+    "cider", "cigar", "circle", "civic", "claim", "clerk", "cliff", "clock", "cloud", "clown",
+    "coach", "cobra", "cocoa", "comet", "coral", "couch", "cousin", "crane", "crater", "crow",
+    "crown", "cube", "curve", "daisy", "dance", "delta", "denim", "depot", "desk", "diary",
+    "diesel", "dingo", "disco", "dock", "dolphin", "donkey", "dough", "dragon", "drama", "dream",
+    "drum", "eagle", "earth", "easel", "echo", "eclipse", "elbow", "elder", "ember", "empty",
+    "engine", "envoy", "epic", "equal", "eraser", "event", "fable", "fabric", "falcon", "family",
+    "fancy", "fawn", "ferry", "fever", "fiber", "field", "filter", "final", "flame", "flute",
+    "focus", "forest", "fossil", "fox", "frost", "fruit", "galaxy", "garden", "garlic", "gecko",
+    "genie", "giant", "ginger", "glove", "goat", "gold", "goose", "gorilla", "grape", "gravel",
+    "guitar", "habit", "hammer", "harbor", "hazel", "heart", "helmet", "hero", "hockey", "honey",
+    "hotel", "humor", "hunter", "husky", "igloo", "index", "input", "iris", "island", "ivory",
+    "jacket", "jaguar", "jelly", "jewel", "jockey", "judge", "juice", "jungle", "kayak", "kettle",
+    "kidney", "kitten", "koala", "label", "ladder", "lagoon", "lamp", "laser", "lava", "lemon",
+    "lens", "lilac", "lion", "lizard", "llama", "lobster", "locket", "lotus", "lunar", "magnet",
+    "mango", "maple", "marble", "meadow", "melon", "mirror", "mobile", "monkey", "moose", "motor",
+    "mouse", "muffin", "museum", "nectar", "needle", "nickel", "noble", "noodle", "north", "nurse",
+    "oasis", "ocean", "olive", "onion", "opera", "orange", "orbit", "otter", "oven", "owl",
+    "oyster", "paddle", "panda", "paper", "parrot",
+];
+
+/// Printable backup of a DID key seed: QR code, word list and checksum of the seed.
+/// The key is restored from the words with `did import-paper` or from the hex with `did new`.
+pub struct PaperBackup {
+    pub did: String,
+    pub verkey: String,
+    seed: Zeroizing<Vec<u8>>,
+}
+
+impl PaperBackup {
+    pub fn new(did: &str, verkey: &str, seed: &[u8]) -> PaperBackup {
+        PaperBackup {
+            did: did.to_string(),
+            verkey: verkey.to_string(),
+            seed: Zeroizing::new(seed.to_vec()),
+        }
+    }
+
+    pub fn words(&self) -> Vec<&'static str> {
+        to_words(&self.seed)
+    }
+
+    /// First bytes of SHA-256 of the seed written as words, to check the transcription.
+    pub fn checksum(&self) -> Vec<&'static str> {
+        to_words(&SHA256::digest(self.seed.as_slice())[..CHECKSUM_LENGTH])
+    }
+
+    /// Seed written as words on the page.
+    /// The checksum words, if given, must match the seed to catch a mistyped word.
+    pub fn seed_from_words(words: &str, checksum: Option<&str>) -> CliResult<Zeroizing<Vec<u8>>> {
+        let seed = Zeroizing::new(from_words(words)?);
+        if seed.len() != SEED_LENGTH {
+            return Err(CliError::InvalidInput(format!(
+                "Expected {} seed words but got {}.",
+                SEED_LENGTH,
+                seed.len()
+            )));
+        }
+
+        if let Some(checksum) = checksum {
+            if from_words(checksum)? != SHA256::digest(seed.as_slice())[..CHECKSUM_LENGTH] {
+                return Err(CliError::InvalidInput(
+                    "Seed words do not match the checksum. Check the words for typos.".to_string(),
+                ));
+            }
+        }
+
+        Ok(seed)
+    }
+
+    pub fn to_pdf(&self) -> Result<Vec<u8>, String> {
+        self.draw(PdfPage::new())
+    }
+
+    pub fn to_png(&self) -> Result<Vec<u8>, String> {
+        self.draw(RasterPage::new())
+    }
+
+    fn draw<P: Page>(&self, mut page: P) -> Result<Vec<u8>, String> {
+        let seed = Zeroizing::new(hex::encode(self.seed.as_slice()));
+        let code =
+            QrCode::new(seed.as_bytes()).map_err(|err| format!("Can't build QR code: {}", err))?;
+
+        let mut y = PAGE_HEIGHT - MARGIN - 20.0;
+
+        page.text(MARGIN, y, 20.0, Font::Bold, "Indy DID key backup");
+        y -= 20.0;
+        page.text(
+            MARGIN,
+            y,
+            10.0,
+            Font::Regular,
+            &format!("Created {}", Utc::now().format("%Y-%m-%d %H:%M UTC")),
+        );
+        y -= 30.0;
+        for (label, value) in [("DID", &self.did), ("Verkey", &self.verkey)] {
+            page.text(MARGIN, y, 11.0, Font::Bold, label);
+            page.text(MARGIN + 60.0, y, 11.0, Font::Monospace, value);
+            y -= 18.0;
+        }
+
+        y -= 10.0;
+        let width = code.width();
+        let colors = code.to_colors();
+        let top = y;
+        for (index, color) in colors.iter().enumerate() {
+            if *color == Color::Dark {
+                let (column, row) = (index % width, index / width);
+                page.rect(
+                    MARGIN + column as f64 * QR_MODULE_SIZE,
+                    top - (row + 1) as f64 * QR_MODULE_SIZE,
+                    QR_MODULE_SIZE,
+                    QR_MODULE_SIZE,
+                );
+            }
+        }
+        y = top - width as f64 * QR_MODULE_SIZE - 30.0;
+
+        page.text(MARGIN, y, 11.0, Font::Bold, "Seed words");
+        y -= 18.0;
+        for (line, words) in self.words().chunks(WORDS_PER_LINE).enumerate() {
+            let words = words
+                .iter()
+                .enumerate()
+                .map(|(index, word)| {
+                    format!("{:>2}. {:<8}", line * WORDS_PER_LINE + index + 1, word)
+                })
+                .collect::<Vec<String>>();
+            page.text(MARGIN, y, 10.0, Font::Monospace, &words.join(" "));
+            y -= 15.0;
+        }
+
+        y -= 10.0;
+        page.text(MARGIN, y, 11.0, Font::Bold, "Checksum");
+        page.text(
+            MARGIN + 60.0,
+            y,
+            10.0,
+            Font::Monospace,
+            &self.checksum().join(" "),
+        );
+        y -= 18.0;
+        page.text(MARGIN, y, 11.0, Font::Bold, "Seed");
+        page.text(MARGIN + 60.0, y, 9.0, Font::Monospace, &seed);
+
+        y -= 40.0;
+        for line in [
+            "The QR code encodes the seed in hex. Each word stands for one byte of the seed.",
+            "Restore the key with: did import-paper <DID> seed=\"<seed words>\" checksum=\"<checksum>\"",
+            "Anyone holding this page controls the DID. Keep it offline in a safe place.",
+        ] {
+            page.text(MARGIN, y, 9.0, Font::Regular, line);
+            y -= 13.0;
+        }
+
+        page.to_bytes()
+    }
+}
+
+fn to_words(bytes: &[u8]) -> Vec<&'static str> {
+    bytes.iter().map(|byte| WORDS[*byte as usize]).collect()
+}
+
+fn from_words(words: &str) -> CliResult<Vec<u8>> {
+    words
+        .split_whitespace()
+        .enumerate()
+        .map(|(index, word)| {
+            WORDS
+                .iter()
+                .position(|known| known.eq_ignore_ascii_case(word))
+                .map(|byte| byte as u8)
+                .ok_or_else(|| CliError::InvalidInput(format!("Unknown seed word #{}.", index + 1)))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn words_are_unique() {
+        let mut words = WORDS.to_vec();
+        words.sort();
+        words.dedup();
+        assert_eq!(WORDS.len(), words.len());
+    }
+
+    #[test]
+    fn paper_backup_works() {
+        let seed = [0u8, 1, 255].repeat(11)[..32].to_vec();
+        let backup = PaperBackup::new("V4SGRU86Z58d6TV7PBUe6f", "GJ1SzoWz", &seed);
+        let words = backup.words();
+        assert_eq!(32, words.len());
+        assert_eq!(&words[..3], &[WORDS[0], WORDS[1], WORDS[255]]);
+        assert_eq!(CHECKSUM_LENGTH, backup.checksum().len());
+
+        let pdf = String::from_utf8(backup.to_pdf().unwrap()).unwrap();
+        assert!(pdf.contains(&hex::encode(&seed)));
+        assert!(pdf.contains("V4SGRU86Z58d6TV7PBUe6f"));
+
+        let png = backup.to_png().unwrap();
+        assert!(png.starts_with(b"\x89PNG"));
+    }
+
+    #[test]
+    fn seed_from_words_works() {
+        let seed = (0..32).map(|byte| byte * 8).collect::<Vec<u8>>();
+        let backup = PaperBackup::new("V4SGRU86Z58d6TV7PBUe6f", "GJ1SzoWz", &seed);
+        let words = backup.words().join(" ");
+        let checksum = backup.checksum().join(" ");
+
+        let restored = PaperBackup::seed_from_words(&words, Some(&checksum)).unwrap();
+        assert_eq!(seed, restored.as_slice());
+        let restored = PaperBackup::seed_from_words(&words.to_uppercase(), None).unwrap();
+        assert_eq!(seed, restored.as_slice());
+
+        // Swapped words are caught by the checksum
+        let mut swapped = backup.words();
+        swapped.swap(0, 1);
+        PaperBackup::seed_from_words(&swapped.join(" "), Some(&checksum)).unwrap_err();
+
+        PaperBackup::seed_from_words(&words, Some("acid acid acid acid")).unwrap_err();
+        PaperBackup::seed_from_words("acid acorn", None).unwrap_err();
+        PaperBackup::seed_from_words(&words.replace(WORDS[0], "bitcoin"), None).unwrap_err();
+    }
+}
@@ -6,10 +6,12 @@ use crate::error::{CliError, CliResult};
 
 use hex::FromHex;
 use indy_utils::base64;
+use zeroize::Zeroizing;
 
 const SEED_BYTES: usize = 32;
 
-pub struct Seed(Vec<u8>);
+// Seed bytes are wiped from memory as soon as the seed is dropped
+pub struct Seed(Zeroizing<Vec<u8>>);
 
 impl Seed {
     pub fn value(&self) -> &[u8] {
@@ -19,11 +21,13 @@ impl Seed {
     pub fn from_str(seed: &str) -> CliResult<Seed> {
         if seed.as_bytes().len() == SEED_BYTES {
             // is acceptable seed length
-            Ok(Seed(seed.as_bytes().to_vec()))
+            Ok(Seed(Zeroizing::new(seed.as_bytes().to_vec())))
         } else if seed.ends_with('=') {
             // is base64 string
-            let decoded = base64::decode(&seed)
-                .map_err(|_| CliError::InvalidInput(format!("Invalid seed provided.")))?;
+            let decoded = Zeroizing::new(
+                base64::decode(&seed)
+                    .map_err(|_| CliError::InvalidInput(format!("Invalid seed provided.")))?,
+            );
             if decoded.len() == SEED_BYTES {
                 Ok(Seed(decoded))
             } else {
@@ -35,8 +39,10 @@ impl Seed {
             }
         } else if seed.as_bytes().len() == SEED_BYTES * 2 {
             // is hex string
-            let decoded = Vec::from_hex(seed)
-                .map_err(|_| CliError::InvalidInput(format!("Seed is invalid hex")))?;
+            let decoded = Zeroizing::new(
+                Vec::from_hex(seed)
+                    .map_err(|_| CliError::InvalidInput(format!("Seed is invalid hex")))?,
+            );
             Ok(Seed(decoded))
         } else {
             Err(CliError::InvalidInput(format!(
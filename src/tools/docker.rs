@@ -0,0 +1,275 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::error::{CliError, CliResult};
+
+use std::{
+    env,
+    io::Read,
+    path::{Path, PathBuf},
+};
+
+pub const DEFAULT_DOCKER_HOST: &str = "unix:///var/run/docker.sock";
+const API_VERSION: &str = "v1.41";
+const MAX_LINKS: usize = 5;
+
+/// Locations of the genesis file in the containers of common local networks:
+/// von-network (`von_webserver`, `von_node1`, ...) and indy-node `indy_pool`.
+pub const GENESIS_PATHS: [&str; 2] = [
+    "/home/indy/ledger/sandbox/pool_transactions_genesis",
+    "/var/lib/indy/sandbox/pool_transactions_genesis",
+];
+
+/// Minimal client of the Docker Engine API reached through `DOCKER_HOST`
+/// (unix socket by default, or plain `tcp://host:port`).
+#[derive(Debug, PartialEq)]
+pub enum Docker {
+    Unix(String),
+    Tcp(String),
+}
+
+impl Docker {
+    pub fn from_env() -> CliResult<Docker> {
+        let host = env::var("DOCKER_HOST").unwrap_or_else(|_| DEFAULT_DOCKER_HOST.to_string());
+        Docker::new(&host)
+    }
+
+    pub fn new(host: &str) -> CliResult<Docker> {
+        if let Some(socket) = host.strip_prefix("unix://") {
+            Ok(Docker::Unix(socket.to_string()))
+        } else if let Some(address) = host.strip_prefix("tcp://") {
+            Ok(Docker::Tcp(address.trim_end_matches('/').to_string()))
+        } else {
+            Err(CliError::InvalidInput(format!(
+                "Unsupported Docker host \"{}\". Expected unix:///path/to/docker.sock or tcp://host:port",
+                host
+            )))
+        }
+    }
+
+    /// Content of a file in the container. Symbolic links are followed.
+    pub fn read_file(&self, container: &str, path: &str) -> CliResult<Vec<u8>> {
+        let mut path = PathBuf::from(path);
+        for _ in 0..MAX_LINKS {
+            let uri = format!(
+                "/{}/containers/{}/archive?path={}",
+                API_VERSION,
+                urlencoding::encode(container),
+                urlencoding::encode(&path.to_string_lossy())
+            );
+            let (status, body) = self.get(&uri)?;
+            match status {
+                200 => {}
+                404 => {
+                    return Err(CliError::NotFound(format!(
+                        "Container \"{}\" or file \"{}\" in it not found: {}",
+                        container,
+                        path.display(),
+                        error_message(&body)
+                    )))
+                }
+                _ => {
+                    return Err(CliError::InvalidEntityState(format!(
+                        "Docker has returned error {}: {}",
+                        status,
+                        error_message(&body)
+                    )))
+                }
+            }
+            match extract_file(&body)? {
+                ArchivedFile::Content(content) => return Ok(content),
+                ArchivedFile::Link(target) => {
+                    path = path.parent().unwrap_or_else(|| Path::new("/")).join(target)
+                }
+            }
+        }
+        Err(CliError::InvalidEntityState(format!(
+            "Too many levels of symbolic links in container \"{}\"",
+            container
+        )))
+    }
+
+    fn get(&self, uri: &str) -> CliResult<(u16, Vec<u8>)> {
+        match self {
+            Docker::Unix(socket) => get_unix(socket, uri),
+            Docker::Tcp(address) => {
+                let url = format!("http://{}{}", address, uri);
+                let response = attohttpc::get(&url)
+                    .send()
+                    .map_err(|err| connection_error(address, &err.to_string()))?;
+                let status = response.status().as_u16();
+                let body = response
+                    .bytes()
+                    .map_err(|err| connection_error(address, &err.to_string()))?;
+                Ok((status, body))
+            }
+        }
+    }
+}
+
+#[cfg(unix)]
+fn get_unix(socket: &str, uri: &str) -> CliResult<(u16, Vec<u8>)> {
+    use std::{io::Write, os::unix::net::UnixStream};
+
+    let mut stream =
+        UnixStream::connect(socket).map_err(|err| connection_error(socket, &err.to_string()))?;
+    // HTTP/1.0 so that the body is neither chunked nor kept open after the response
+    write!(stream, "GET {} HTTP/1.0\r\nHost: docker\r\n\r\n", uri)?;
+    let mut response = Vec::new();
+    stream.read_to_end(&mut response)?;
+    parse_response(&response)
+}
+
+#[cfg(not(unix))]
+fn get_unix(socket: &str, _uri: &str) -> CliResult<(u16, Vec<u8>)> {
+    Err(connection_error(
+        socket,
+        "unix sockets are not supported on this platform, set DOCKER_HOST to tcp://host:port",
+    ))
+}
+
+fn connection_error(host: &str, err: &str) -> CliError {
+    CliError::InvalidEntityState(format!(
+        "Unable to reach Docker at \"{}\": {}. Check that Docker is running and accessible by the current user",
+        host, err
+    ))
+}
+
+fn parse_response(response: &[u8]) -> CliResult<(u16, Vec<u8>)> {
+    let invalid =
+        || CliError::InvalidEntityState("Docker has sent an invalid response".to_string());
+    let header_end = response
+        .windows(4)
+        .position(|window| window == b"\r\n\r\n")
+        .ok_or_else(invalid)?;
+    let status = String::from_utf8_lossy(&response[..header_end])
+        .lines()
+        .next()
+        .and_then(|status_line| status_line.split_whitespace().nth(1))
+        .and_then(|status| status.parse::<u16>().ok())
+        .ok_or_else(invalid)?;
+    Ok((status, response[header_end + 4..].to_vec()))
+}
+
+// Errors of the Docker API come as `{"message": "..."}`
+fn error_message(body: &[u8]) -> String {
+    serde_json::from_slice::<serde_json::Value>(body)
+        .ok()
+        .and_then(|error| error["message"].as_str().map(String::from))
+        .unwrap_or_else(|| String::from_utf8_lossy(body).trim().to_string())
+}
+
+#[derive(Debug, PartialEq)]
+enum ArchivedFile {
+    Content(Vec<u8>),
+    Link(PathBuf),
+}
+
+// The archive endpoint returns a tar with the requested path as the first entry
+fn extract_file(archive: &[u8]) -> CliResult<ArchivedFile> {
+    let to_error = |err: std::io::Error| {
+        CliError::InvalidEntityState(format!("Can't unpack the file sent by Docker: {}", err))
+    };
+
+    let mut archive = tar::Archive::new(archive);
+    let mut entry = archive
+        .entries()
+        .map_err(to_error)?
+        .next()
+        .ok_or_else(|| {
+            CliError::InvalidEntityState("Docker has sent an empty archive".to_string())
+        })?
+        .map_err(to_error)?;
+
+    let entry_type = entry.header().entry_type();
+    if entry_type.is_symlink() {
+        let target = entry
+            .link_name()
+            .map_err(to_error)?
+            .ok_or_else(|| {
+                CliError::InvalidEntityState("Symbolic link without target".to_string())
+            })?
+            .to_path_buf();
+        return Ok(ArchivedFile::Link(target));
+    }
+    if !entry_type.is_file() {
+        return Err(CliError::InvalidInput(format!(
+            "\"{}\" is not a file",
+            entry.path().map_err(to_error)?.display()
+        )));
+    }
+
+    let mut content = Vec::new();
+    entry.read_to_end(&mut content).map_err(to_error)?;
+    Ok(ArchivedFile::Content(content))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn archive(name: &str, entry_type: tar::EntryType, link: Option<&str>, data: &[u8]) -> Vec<u8> {
+        let mut builder = tar::Builder::new(Vec::new());
+        let mut header = tar::Header::new_gnu();
+        header.set_entry_type(entry_type);
+        header.set_size(data.len() as u64);
+        header.set_mode(0o644);
+        if let Some(link) = link {
+            header.set_link_name(link).unwrap();
+        }
+        builder.append_data(&mut header, name, data).unwrap();
+        builder.into_inner().unwrap()
+    }
+
+    #[test]
+    fn docker_new_works() {
+        assert_eq!(
+            Docker::Unix("/var/run/docker.sock".to_string()),
+            Docker::new(DEFAULT_DOCKER_HOST).unwrap()
+        );
+        assert_eq!(
+            Docker::Tcp("127.0.0.1:2375".to_string()),
+            Docker::new("tcp://127.0.0.1:2375/").unwrap()
+        );
+        Docker::new("npipe:////./pipe/docker_engine").unwrap_err();
+    }
+
+    #[test]
+    fn parse_response_works() {
+        let response = b"HTTP/1.0 404 Not Found\r\nContent-Type: application/json\r\n\r\n{\"message\":\"No such container: von\"}";
+        let (status, body) = parse_response(response).unwrap();
+        assert_eq!(404, status);
+        assert_eq!("No such container: von", error_message(&body));
+
+        parse_response(b"HTTP/1.0 200 OK\r\n").unwrap_err();
+    }
+
+    #[test]
+    fn extract_file_works() {
+        let genesis = archive(
+            "pool_transactions_genesis",
+            tar::EntryType::Regular,
+            None,
+            b"{}\n",
+        );
+        assert_eq!(
+            ArchivedFile::Content(b"{}\n".to_vec()),
+            extract_file(&genesis).unwrap()
+        );
+
+        let link = archive(
+            "pool_transactions_genesis",
+            tar::EntryType::Symlink,
+            Some("../genesis"),
+            b"",
+        );
+        assert_eq!(
+            ArchivedFile::Link(PathBuf::from("../genesis")),
+            extract_file(&link).unwrap()
+        );
+
+        let directory = archive("sandbox", tar::EntryType::Directory, None, b"");
+        extract_file(&directory).unwrap_err();
+    }
+}
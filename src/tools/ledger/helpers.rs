@@ -2,23 +2,78 @@
     Copyright © 2023 Province of British Columbia
     https://digital.gov.bc.ca/digital-trust
 */
+use crate::{
+    error::{CliError, CliResult},
+    tools::ledger::{FLAG, GET_FLAG},
+};
 use indy_vdr::ledger::constants::*;
 use serde_json::Value as JsonValue;
+use std::collections::HashMap;
+
+// TRUST_ANCHOR is the legacy name of ENDORSER role
+const ROLES: [(&str, &str); 5] = [
+    ("TRUSTEE", TRUSTEE),
+    ("STEWARD", STEWARD),
+    ("ENDORSER", ENDORSER),
+    ("TRUST_ANCHOR", ENDORSER),
+    ("NETWORK_MONITOR", NETWORK_MONITOR),
+];
 
 pub struct LedgerHelpers;
 
 impl LedgerHelpers {
-    pub fn get_role_title(role: &JsonValue) -> JsonValue {
-        JsonValue::String(
-            match role.as_str() {
-                Some(TRUSTEE) => "TRUSTEE",
-                Some(STEWARD) => "STEWARD",
-                Some(ENDORSER) => "ENDORSER",
-                Some(NETWORK_MONITOR) => "NETWORK_MONITOR",
-                _ => "-",
-            }
-            .to_string(),
-        )
+    pub fn get_role_title(role: &JsonValue, custom_roles: &HashMap<String, String>) -> JsonValue {
+        JsonValue::String(match role.as_str() {
+            Some(TRUSTEE) => "TRUSTEE".to_string(),
+            Some(STEWARD) => "STEWARD".to_string(),
+            Some(ENDORSER) => "ENDORSER".to_string(),
+            Some(NETWORK_MONITOR) => "NETWORK_MONITOR".to_string(),
+            Some(code) if !code.is_empty() => custom_roles
+                .iter()
+                .find(|(_, custom_code)| custom_code.as_str() == code)
+                .map(|(name, _)| name.to_string())
+                .unwrap_or_else(|| code.to_string()),
+            _ => "-".to_string(),
+        })
+    }
+
+    /// Resolve role name (or numeric code) to the code used on the ledger.
+    /// Empty role stays empty as it means role removal.
+    pub fn get_role_code(role: &str, custom_roles: &HashMap<String, String>) -> CliResult<String> {
+        if role.is_empty() || role.chars().all(|c| c.is_ascii_digit()) {
+            return Ok(role.to_string());
+        }
+
+        let name = role.to_uppercase();
+        ROLES
+            .iter()
+            .find(|(role_name, _)| *role_name == name)
+            .map(|(_, code)| code.to_string())
+            .or_else(|| custom_roles.get(&name).cloned())
+            .ok_or_else(|| {
+                CliError::InvalidInput(format!(
+                    "Unknown role \"{}\". Expected one of: {}",
+                    role,
+                    Self::get_role_names(custom_roles).join(", ")
+                ))
+            })
+    }
+
+    pub fn is_standard_role_code(code: &str) -> bool {
+        code.is_empty() || ROLES.iter().any(|(_, role_code)| *role_code == code)
+    }
+
+    pub fn get_role_names(custom_roles: &HashMap<String, String>) -> Vec<String> {
+        ROLES
+            .iter()
+            .map(|(name, _)| name.to_string())
+            .chain(custom_roles.keys().cloned())
+            .collect()
+    }
+
+    /// Time (in seconds since epoch) the ledger state returned in a read reply has been signed by the pool.
+    pub fn get_state_proof_timestamp(response: &JsonValue) -> Option<u64> {
+        response["result"]["state_proof"]["multi_signature"]["value"]["timestamp"].as_u64()
     }
 
     pub fn get_txn_title(txn_type: &JsonValue) -> JsonValue {
@@ -52,6 +107,8 @@ impl LedgerHelpers {
                 Some(AUTH_RULE) => "AUTH_RULE",
                 Some(GET_AUTH_RULE) => "GET_AUTH_RULE",
                 Some(AUTH_RULES) => "AUTH_RULES",
+                Some(FLAG) => "FLAG",
+                Some(GET_FLAG) => "GET_FLAG",
                 Some(val) => val,
                 _ => "-",
             }
@@ -59,3 +116,35 @@ impl LedgerHelpers {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn get_role_title_works() {
+        let mut custom_roles = HashMap::new();
+        custom_roles.insert("AUDITOR".to_string(), "9001".to_string());
+
+        assert_eq!(
+            json!("ENDORSER"),
+            LedgerHelpers::get_role_title(&json!(ENDORSER), &custom_roles)
+        );
+        assert_eq!(
+            json!("AUDITOR"),
+            LedgerHelpers::get_role_title(&json!("9001"), &custom_roles)
+        );
+        assert_eq!(
+            json!("9002"),
+            LedgerHelpers::get_role_title(&json!("9002"), &custom_roles)
+        );
+        assert_eq!(
+            json!("-"),
+            LedgerHelpers::get_role_title(&json!(""), &custom_roles)
+        );
+        assert_eq!(
+            json!("-"),
+            LedgerHelpers::get_role_title(&JsonValue::Null, &custom_roles)
+        );
+    }
+}
@@ -3,7 +3,10 @@
     https://digital.gov.bc.ca/digital-trust
 */
 pub mod helpers;
+pub mod permissions;
+pub mod rejection;
 pub mod response;
+pub mod signature;
 
 use crate::{
     error::{CliError, CliResult},
@@ -12,16 +15,18 @@ use crate::{
 };
 
 use crate::tools::{pool::Pool, wallet::Wallet};
+use futures_util::future::join_all;
 use indy_utils::did::DidValue;
 use indy_vdr::{
     ledger::{
-        identifiers::{CredentialDefinitionId, SchemaId},
+        identifiers::{CredentialDefinitionId, RevocationRegistryId, SchemaId},
         requests::{
             auth_rule::{AddAuthRuleData, AuthRuleData, AuthRules, Constraint, EditAuthRuleData},
             author_agreement::{AcceptanceMechanisms, GetTxnAuthorAgreementData},
             cred_def::CredentialDefinition,
             node::NodeOperationData,
             pool::Schedule,
+            rev_reg_def::RevocationRegistryDefinition,
             schema::Schema,
         },
         RequestBuilder,
@@ -32,12 +37,18 @@ use indy_vdr::{
     },
 };
 use serde_json::Value as JsonValue;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 pub use self::{
     helpers::LedgerHelpers,
     response::{parse_transaction_response, Response, ResponseType},
+    signature::SignatureScheme,
 };
 
+// Config ledger feature flags of indy-node, there are no indy-vdr builders for them
+pub const FLAG: &str = "130";
+pub const GET_FLAG: &str = "131";
+
 pub struct Ledger {}
 
 impl Ledger {
@@ -48,7 +59,8 @@ impl Ledger {
         request: &mut PreparedRequest,
     ) -> CliResult<String> {
         block_on(async move {
-            let signature = Self::_sign(request, store, submitter_did).await?;
+            let signature =
+                Self::_sign(request, pool.signature_scheme, store, submitter_did).await?;
             request.set_signature(&signature)?;
             Self::_submit_request(request, pool).await
         })
@@ -58,6 +70,18 @@ impl Ledger {
         block_on(async { Self::_submit_request(request, pool).await })
     }
 
+    /// Submit a batch of requests concurrently. Results are returned in the order of requests.
+    pub fn submit_requests(pool: &Pool, requests: &[PreparedRequest]) -> Vec<CliResult<String>> {
+        block_on(async {
+            join_all(
+                requests
+                    .iter()
+                    .map(|request| Self::_submit_request(request, pool)),
+            )
+            .await
+        })
+    }
+
     pub fn submit_action(
         pool: &Pool,
         request: &PreparedRequest,
@@ -70,39 +94,45 @@ impl Ledger {
         };
 
         block_on(async {
-            let (request_result, _) = perform_ledger_action(
+            let response = perform_ledger_action(
                 &pool.pool,
                 request.req_id.to_string(),
                 request.req_json.to_string(),
                 nodes,
                 timeout,
             )
-            .await?;
-            match request_result {
-                RequestResult::Reply(message) => Ok(message),
-                RequestResult::Failed(error) => Err(error.into()),
-            }
+            .await;
+            let (result, timing): (CliResult<NodeReplies<String>>, _) = match response {
+                Ok((RequestResult::Reply(message), timing)) => (Ok(message), timing),
+                Ok((RequestResult::Failed(error), timing)) => (Err(error.into()), timing),
+                Err(error) => (Err(error.into()), None),
+            };
+            pool.record_request(&request.txn_type, timing.as_ref(), result.is_err());
+            result
         })
     }
 
+    /// Sign the request according to the signature scheme of the pool (the default one if there is no pool).
     pub fn sign_request(
+        pool: Option<&Pool>,
         store: &Wallet,
         did: &DidValue,
         request: &mut PreparedRequest,
     ) -> CliResult<()> {
         block_on(async move {
-            let signature = Self::_sign(request, store, did).await?;
+            let signature = Self::_sign(request, Self::signature_scheme(pool), store, did).await?;
             request.set_signature(&signature).map_err(CliError::from)
         })
     }
 
     pub fn multi_sign_request(
+        pool: Option<&Pool>,
         store: &Wallet,
         did: &DidValue,
         request: &mut PreparedRequest,
     ) -> CliResult<()> {
         block_on(async move {
-            let signature = Self::_sign(request, store, did).await?;
+            let signature = Self::_sign(request, Self::signature_scheme(pool), store, did).await?;
             request
                 .set_multi_signature(did, &signature)
                 .map_err(CliError::from)
@@ -114,7 +144,7 @@ impl Ledger {
         submitter_did: &DidValue,
         target_did: &DidValue,
         verkey: Option<&str>,
-        data: Option<&str>,
+        alias: Option<&str>,
         role: Option<&str>,
     ) -> CliResult<PreparedRequest> {
         Self::_request_builder(pool)
@@ -122,12 +152,35 @@ impl Ledger {
                 submitter_did,
                 target_did,
                 verkey.map(String::from),
-                data.map(String::from),
+                alias.map(String::from),
                 role.map(String::from),
             )
             .map_err(CliError::from)
     }
 
+    /// NYM request setting DID Document content and DID version (indy-node 1.13 or newer).
+    pub fn build_nym_request_with_diddoc(
+        pool: Option<&Pool>,
+        submitter_did: &DidValue,
+        target_did: &DidValue,
+        verkey: Option<&str>,
+        alias: Option<&str>,
+        role: Option<&str>,
+        diddoc_content: Option<&JsonValue>,
+        version: Option<u8>,
+    ) -> CliResult<PreparedRequest> {
+        let mut request =
+            Self::build_nym_request(pool, submitter_did, target_did, verkey, alias, role)?;
+        // Fields of indy-node 1.13 NYM: the ledger takes diddocContent as JSON encoded string
+        if let Some(diddoc_content) = diddoc_content {
+            request.req_json["operation"]["diddocContent"] = json!(diddoc_content.to_string());
+        }
+        if let Some(version) = version {
+            request.req_json["operation"]["version"] = json!(version);
+        }
+        Ok(request)
+    }
+
     pub fn build_get_nym_request(
         pool: Option<&Pool>,
         submitter_did: Option<&DidValue>,
@@ -138,6 +191,24 @@ impl Ledger {
             .map_err(CliError::from)
     }
 
+    /// State of the NYM after the transaction with `seq_no` or at `timestamp` (indy-node 1.13 or newer).
+    pub fn build_get_nym_request_at(
+        pool: Option<&Pool>,
+        submitter_did: Option<&DidValue>,
+        target_did: &DidValue,
+        seq_no: Option<u64>,
+        timestamp: Option<u64>,
+    ) -> CliResult<PreparedRequest> {
+        let mut request = Self::build_get_nym_request(pool, submitter_did, target_did)?;
+        if let Some(seq_no) = seq_no {
+            request.req_json["operation"]["seqNo"] = json!(seq_no);
+        }
+        if let Some(timestamp) = timestamp {
+            request.req_json["operation"]["timestamp"] = json!(timestamp);
+        }
+        Ok(request)
+    }
+
     pub fn build_attrib_request(
         pool: Option<&Pool>,
         submitter_did: &DidValue,
@@ -225,6 +296,38 @@ impl Ledger {
             .map_err(CliError::from)
     }
 
+    pub fn build_revoc_reg_def_request(
+        pool: Option<&Pool>,
+        submitter_did: &DidValue,
+        revoc_reg_def: RevocationRegistryDefinition,
+    ) -> CliResult<PreparedRequest> {
+        Self::_request_builder(pool)
+            .build_revoc_reg_def_request(submitter_did, revoc_reg_def)
+            .map_err(CliError::from)
+    }
+
+    pub fn build_get_revoc_reg_def_request(
+        pool: Option<&Pool>,
+        submitter_did: Option<&DidValue>,
+        id: &RevocationRegistryId,
+    ) -> CliResult<PreparedRequest> {
+        Self::_request_builder(pool)
+            .build_get_revoc_reg_def_request(submitter_did, id)
+            .map_err(CliError::from)
+    }
+
+    pub fn build_get_revoc_reg_delta_request(
+        pool: Option<&Pool>,
+        submitter_did: Option<&DidValue>,
+        id: &RevocationRegistryId,
+        from: Option<i64>,
+        to: i64,
+    ) -> CliResult<PreparedRequest> {
+        Self::_request_builder(pool)
+            .build_get_revoc_reg_delta_request(submitter_did, id, from, to)
+            .map_err(CliError::from)
+    }
+
     pub fn build_node_request(
         pool: Option<&Pool>,
         submitter_did: &DidValue,
@@ -484,6 +587,16 @@ impl Ledger {
             .map_err(CliError::from)
     }
 
+    pub fn build_get_txn_request(
+        pool: Option<&Pool>,
+        ledger_type: i32,
+        seq_no: i32,
+    ) -> CliResult<PreparedRequest> {
+        Self::_request_builder(pool)
+            .build_get_txn_request(None, ledger_type, seq_no)
+            .map_err(CliError::from)
+    }
+
     pub fn build_get_frozen_ledgers_request(
         pool: Option<&Pool>,
         submitter_did: &DidValue,
@@ -493,25 +606,92 @@ impl Ledger {
             .map_err(CliError::from)
     }
 
+    /// Set the flag to `value`, `None` unsets it.
+    pub fn build_flag_request(
+        pool: Option<&Pool>,
+        submitter_did: &DidValue,
+        name: &str,
+        value: Option<&str>,
+    ) -> CliResult<PreparedRequest> {
+        let operation = json!({
+            "type": FLAG,
+            "name": name,
+            "value": value,
+        });
+        Self::_build_request(pool, Some(submitter_did), operation)
+    }
+
+    /// Value of the flag, as it was at `timestamp` if it is given.
+    pub fn build_get_flag_request(
+        pool: Option<&Pool>,
+        submitter_did: Option<&DidValue>,
+        name: &str,
+        timestamp: Option<u64>,
+    ) -> CliResult<PreparedRequest> {
+        let mut operation = json!({
+            "type": GET_FLAG,
+            "name": name,
+        });
+        if let Some(timestamp) = timestamp {
+            operation["timestamp"] = json!(timestamp);
+        }
+        Self::_build_request(pool, submitter_did, operation)
+    }
+
+    fn _build_request(
+        pool: Option<&Pool>,
+        identifier: Option<&DidValue>,
+        operation: JsonValue,
+    ) -> CliResult<PreparedRequest> {
+        let req_id = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos() as u64;
+        let mut request = json!({
+            "reqId": req_id,
+            "operation": operation,
+            "protocolVersion": Self::_request_builder(pool).protocol_version.to_id(),
+        });
+        if let Some(identifier) = identifier {
+            request["identifier"] = json!(identifier.to_short());
+        }
+        PreparedRequest::from_request_json(request.to_string()).map_err(CliError::from)
+    }
+
     fn _request_builder(pool: Option<&Pool>) -> RequestBuilder {
         pool.map(|pool| pool.pool.get_request_builder())
             .unwrap_or_else(|| RequestBuilder::new(ProtocolVersion::Node1_4))
     }
 
     async fn _submit_request(request: &PreparedRequest, pool: &Pool) -> CliResult<String> {
-        let (request_result, _) = perform_ledger_request(&pool.pool, request).await?;
-        match request_result {
-            RequestResult::Reply(message) => Ok(message),
-            RequestResult::Failed(error) => Err(error.into()),
-        }
+        let (result, timing): (CliResult<String>, _) =
+            match perform_ledger_request(&pool.pool, request).await {
+                Ok((RequestResult::Reply(message), timing)) => (Ok(message), timing),
+                Ok((RequestResult::Failed(error), timing)) => (Err(error.into()), timing),
+                Err(error) => (Err(error.into()), None),
+            };
+        pool.record_request(&request.txn_type, timing.as_ref(), result.is_err());
+        result
+    }
+
+    pub fn signature_scheme(pool: Option<&Pool>) -> SignatureScheme {
+        pool.map(|pool| pool.signature_scheme).unwrap_or_default()
     }
 
     async fn _sign(
         request: &mut PreparedRequest,
+        scheme: SignatureScheme,
         store: &Wallet,
         submitter_did: &DidValue,
     ) -> CliResult<Vec<u8>> {
-        let sig_bytes = request.get_signature_input()?;
-        Did::sign(store, &submitter_did.to_string(), sig_bytes.as_bytes()).await
+        let sig_bytes = scheme.preimage(request)?;
+        match scheme {
+            SignatureScheme::Ed25519 => {
+                Did::sign(store, &submitter_did.to_string(), &sig_bytes).await
+            }
+            SignatureScheme::Ed25519ph => {
+                Did::sign_prehashed(store, &submitter_did.to_string(), &sig_bytes).await
+            }
+        }
     }
 }
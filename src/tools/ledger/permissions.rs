@@ -0,0 +1,437 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::{
+    error::{CliError, CliResult},
+    tools::{ledger::Ledger, pool::Pool},
+};
+
+use indy_utils::did::DidValue;
+use indy_vdr::{
+    ledger::{
+        constants::{ATTRIB, CRED_DEF, NYM, SCHEMA},
+        identifiers::{CredentialDefinitionId, SchemaId},
+    },
+    pool::PreparedRequest,
+};
+use serde_json::Value as JsonValue;
+use std::collections::BTreeMap;
+
+const ACTION_ADD: &str = "ADD";
+const ACTION_EDIT: &str = "EDIT";
+
+/// Ledger entry written by a transaction.
+#[derive(Debug, PartialEq)]
+pub struct Target {
+    /// Whether the submitter owns the entry.
+    pub is_owner: bool,
+    /// Changed fields of the existing entry as `(field, old value, new value)`, `None` for a new entry.
+    /// The fields are known only for NYM, any other change is reported as `("*", "*", "*")`.
+    pub changes: Option<Vec<(String, String, String)>>,
+}
+
+/// Whether the DID is allowed to send the transaction without endorser, according to the auth rules
+/// set on the ledger for the DID role: the EDIT rules of the changed fields when the transaction changes
+/// an existing entry, the ADD rule otherwise. The result is cached for the pool connection.
+pub fn can_write(pool: &Pool, did: &DidValue, request: &PreparedRequest) -> CliResult<bool> {
+    let txn_type = request.txn_type.as_str();
+    let target = get_target(pool, did, request)?;
+    let rule_keys = match target.changes {
+        Some(ref changes) => changes
+            .iter()
+            .map(|(field, old, new)| {
+                [
+                    ACTION_EDIT,
+                    txn_type,
+                    field.as_str(),
+                    old.as_str(),
+                    new.as_str(),
+                ]
+                .join("--")
+            })
+            .collect::<Vec<String>>(),
+        None => vec![[ACTION_ADD, txn_type, "*", "*", "*"].join("--")],
+    };
+    let cache_key = format!("{}--{}", rule_keys.join(","), target.is_owner);
+    if let Some(allowed) = pool.cached_write_permission(did, &cache_key) {
+        return Ok(allowed);
+    }
+
+    let rules = match pool.capabilities() {
+        Some(capabilities) => capabilities.auth_rules,
+        None => get_auth_rules(pool)?,
+    };
+    let mut role = None;
+    let mut allowed = true;
+    for rule_key in rule_keys.iter() {
+        // No rule: the ledger does not restrict the change
+        if let Some(constraint) = find_constraint(&rules, rule_key) {
+            if role.is_none() {
+                role = Some(get_role(pool, did)?);
+            }
+            let did_role = role.as_ref().and_then(Option::as_deref);
+            allowed &= is_satisfied(constraint, did_role, target.is_owner);
+        }
+    }
+    pool.cache_write_permission(did, &cache_key, allowed);
+    Ok(allowed)
+}
+
+/// Entry the transaction writes. The existing entries are looked up for NYM, ATTRIB, SCHEMA and CRED_DEF
+/// transactions, the other ones are considered as new entries of the submitter.
+pub fn get_target(pool: &Pool, did: &DidValue, request: &PreparedRequest) -> CliResult<Target> {
+    let operation = &request.req_json["operation"];
+    let any_change = || vec![("*".to_string(), "*".to_string(), "*".to_string())];
+    let target = match request.txn_type.as_str() {
+        NYM => {
+            let dest = DidValue(operation["dest"].as_str().unwrap_or_default().to_string());
+            match get_nym_data(pool, &dest)? {
+                Some(data) => Target {
+                    is_owner: is_nym_owner(&dest, &data, did),
+                    changes: Some(get_nym_changes(operation, &data)),
+                },
+                None => Target {
+                    is_owner: false,
+                    changes: None,
+                },
+            }
+        }
+        ATTRIB => {
+            let dest = DidValue(operation["dest"].as_str().unwrap_or_default().to_string());
+            let is_owner = get_nym_data(pool, &dest)?
+                .map(|data| is_nym_owner(&dest, &data, did))
+                .unwrap_or(false);
+            // The attribute is requested by its name, the raw value is a JSON object of a single field
+            let raw = operation["raw"]
+                .as_str()
+                .and_then(|raw| serde_json::from_str::<JsonValue>(raw).ok())
+                .and_then(|raw| raw.as_object()?.keys().next().cloned());
+            let request = Ledger::build_get_attrib_request(
+                Some(pool),
+                None,
+                &dest,
+                raw.as_deref(),
+                operation["hash"].as_str(),
+                operation["enc"].as_str(),
+            )?;
+            let result = read_result(&Ledger::submit_request(pool, &request)?)?;
+            Target {
+                is_owner,
+                changes: (!result["data"].is_null()).then(any_change),
+            }
+        }
+        // Identifiers of schemas and credential definitions include the author DID,
+        // so the submitter always owns the entry
+        SCHEMA => {
+            let id = SchemaId::new(
+                did,
+                operation["data"]["name"].as_str().unwrap_or_default(),
+                operation["data"]["version"].as_str().unwrap_or_default(),
+            );
+            let request = Ledger::build_get_schema_request(Some(pool), None, &id)?;
+            let result = read_result(&Ledger::submit_request(pool, &request)?)?;
+            Target {
+                is_owner: true,
+                changes: result["seqNo"].is_u64().then(any_change),
+            }
+        }
+        CRED_DEF => {
+            let id = CredentialDefinitionId::new(
+                did,
+                &SchemaId(operation["ref"].to_string()),
+                operation["signature_type"].as_str().unwrap_or("CL"),
+                operation["tag"].as_str().unwrap_or_default(),
+            );
+            let request = Ledger::build_get_cred_def_request(Some(pool), None, &id)?;
+            let result = read_result(&Ledger::submit_request(pool, &request)?)?;
+            Target {
+                is_owner: true,
+                changes: result["seqNo"].is_u64().then(any_change),
+            }
+        }
+        _ => Target {
+            is_owner: true,
+            changes: None,
+        },
+    };
+    Ok(target)
+}
+
+fn get_nym_data(pool: &Pool, did: &DidValue) -> CliResult<Option<JsonValue>> {
+    let request = Ledger::build_get_nym_request(Some(pool), None, did)?;
+    let result = read_result(&Ledger::submit_request(pool, &request)?)?;
+    match result["data"].as_str() {
+        Some(data) => Ok(Some(serde_json::from_str::<JsonValue>(data)?)),
+        None => Ok(None),
+    }
+}
+
+// The ledger considers the NYM itself as the owner if it has a verkey, its creator otherwise
+fn is_nym_owner(nym: &DidValue, data: &JsonValue, did: &DidValue) -> bool {
+    let owner = match data["verkey"].as_str() {
+        Some(_) => nym.to_short(),
+        None => DidValue(data["identifier"].as_str().unwrap_or_default().to_string()).to_short(),
+    };
+    owner == did.to_short()
+}
+
+// Fields of the existing NYM changed by the NYM transaction, the ones having EDIT rules on the ledger
+fn get_nym_changes(operation: &JsonValue, data: &JsonValue) -> Vec<(String, String, String)> {
+    let mut changes = Vec::new();
+    if let Some(role) = operation.get("role") {
+        let old = data["role"].as_str().unwrap_or_default();
+        let new = role.as_str().unwrap_or_default();
+        if old != new {
+            changes.push(("role".to_string(), old.to_string(), new.to_string()));
+        }
+    }
+    if operation["verkey"].is_string() && operation["verkey"] != data["verkey"] {
+        changes.push(("verkey".to_string(), "*".to_string(), "*".to_string()));
+    }
+    changes
+}
+
+/// Role of the DID on the ledger: `None` when the DID is not written on the ledger, empty for a DID without role.
+pub fn get_role(pool: &Pool, did: &DidValue) -> CliResult<Option<String>> {
+    let request = Ledger::build_get_nym_request(Some(pool), None, did)?;
+    let result = read_result(&Ledger::submit_request(pool, &request)?)?;
+    let data = match result["data"].as_str() {
+        Some(data) => serde_json::from_str::<JsonValue>(data)?,
+        None => return Ok(None),
+    };
+    Ok(Some(data["role"].as_str().unwrap_or_default().to_string()))
+}
+
+/// Constraints of all auth rules set on the ledger by key `<auth_action>--<auth_type>--<field>--<old_value>--<new_value>`.
+/// The ledger returns either one rule or all of them, so all rules are requested.
+pub fn get_auth_rules(pool: &Pool) -> CliResult<BTreeMap<String, JsonValue>> {
+    let request =
+        Ledger::build_get_auth_rule_request(Some(pool), None, None, None, None, None, None)?;
+    let mut result = read_result(&Ledger::submit_request(pool, &request)?)?;
+    let rules = match result["data"].take() {
+        JsonValue::Array(rules) => rules,
+        _ => Vec::new(),
+    };
+    Ok(rules
+        .into_iter()
+        .map(|mut rule| {
+            let key = [
+                rule["auth_action"].as_str(),
+                rule["auth_type"].as_str(),
+                rule["field"].as_str(),
+                rule["old_value"].as_str(),
+                rule["new_value"].as_str(),
+            ]
+            .iter()
+            .map(|part| part.unwrap_or("*"))
+            .collect::<Vec<&str>>()
+            .join("--");
+            (key, rule["constraint"].take())
+        })
+        .collect())
+}
+
+// Constraint of the rule by key `<auth_action>--<auth_type>--<field>--<old_value>--<new_value>`:
+// fields and values of the rules may be `*`, and ADD rules without new value
+// (for NYM it is a DID without role) match a new entry of the type.
+fn find_constraint<'a>(
+    rules: &'a BTreeMap<String, JsonValue>,
+    rule_key: &str,
+) -> Option<&'a JsonValue> {
+    let wanted = rule_key.split("--").collect::<Vec<&str>>();
+    let value_matches =
+        |value: &str, wanted: &str| value == "*" || wanted == "*" || value == wanted;
+    rules
+        .iter()
+        .find(|(key, _)| {
+            let parts = key.split("--").collect::<Vec<&str>>();
+            match (parts.as_slice(), wanted.as_slice()) {
+                ([ACTION_ADD, auth_type, _, _, "" | "*"], [ACTION_ADD, txn_type, ..]) => {
+                    auth_type == txn_type
+                }
+                (
+                    [ACTION_EDIT, auth_type, field, old, new],
+                    [ACTION_EDIT, txn_type, wanted_field, wanted_old, wanted_new],
+                ) => {
+                    auth_type == txn_type
+                        && value_matches(field, wanted_field)
+                        && value_matches(old, wanted_old)
+                        && value_matches(new, wanted_new)
+                }
+                _ => false,
+            }
+        })
+        .map(|(_, constraint)| constraint)
+}
+
+pub(crate) fn read_result(response: &str) -> CliResult<JsonValue> {
+    let mut response = serde_json::from_str::<JsonValue>(response)?;
+    if response["op"].as_str() != Some("REPLY") {
+        return Err(CliError::InvalidEntityState(format!(
+            "Ledger has rejected the request: {}",
+            response["reason"].as_str().unwrap_or("unknown reason")
+        )));
+    }
+    Ok(response["result"].take())
+}
+
+/// Whether a single signature of a DID with the role satisfies the constraint.
+/// `role` is `None` for a DID which is not written on the ledger.
+/// `is_owner` tells whether the DID owns the entry for the constraints requiring the owner signature.
+fn is_satisfied(constraint: &JsonValue, role: Option<&str>, is_owner: bool) -> bool {
+    let nested = || {
+        constraint["auth_constraints"]
+            .as_array()
+            .cloned()
+            .unwrap_or_default()
+    };
+    match constraint["constraint_id"].as_str() {
+        Some("ROLE") => {
+            let sig_count = constraint["sig_count"].as_u64().unwrap_or(1);
+            if sig_count == 0 {
+                return true;
+            }
+            if sig_count > 1 {
+                return false;
+            }
+            if constraint["need_to_be_owner"].as_bool().unwrap_or(false) && !is_owner {
+                return false;
+            }
+            match role {
+                Some(role) => constraint["role"]
+                    .as_str()
+                    .map(|required| required == "*" || required == role)
+                    .unwrap_or(false),
+                None => constraint["off_ledger_signature"]
+                    .as_bool()
+                    .unwrap_or(false),
+            }
+        }
+        Some("AND") => nested()
+            .iter()
+            .all(|constraint| is_satisfied(constraint, role, is_owner)),
+        Some("OR") => nested()
+            .iter()
+            .any(|constraint| is_satisfied(constraint, role, is_owner)),
+        // FORBIDDEN
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn is_satisfied_works() {
+        // Default rule of SCHEMA: TRUSTEE or STEWARD or ENDORSER
+        let constraint = json!({
+            "constraint_id": "OR",
+            "auth_constraints": [
+                {"constraint_id": "ROLE", "role": "0", "sig_count": 1, "need_to_be_owner": false},
+                {"constraint_id": "ROLE", "role": "2", "sig_count": 1, "need_to_be_owner": false},
+                {"constraint_id": "ROLE", "role": "101", "sig_count": 1, "need_to_be_owner": false}
+            ]
+        });
+        assert!(is_satisfied(&constraint, Some("101"), false));
+        assert!(!is_satisfied(&constraint, Some(""), false));
+        assert!(!is_satisfied(&constraint, None, false));
+    }
+
+    #[test]
+    fn find_constraint_works() {
+        let mut rules = BTreeMap::new();
+        rules.insert(
+            "ADD--1--role--*--101".to_string(),
+            json!({"constraint_id": "ROLE", "role": "0"}),
+        );
+        rules.insert(
+            "ADD--1--role--*--".to_string(),
+            json!({"constraint_id": "ROLE", "role": "101"}),
+        );
+        rules.insert(
+            "EDIT--1--role--0--".to_string(),
+            json!({"constraint_id": "ROLE", "role": "0"}),
+        );
+        rules.insert(
+            "EDIT--1--verkey--*--*".to_string(),
+            json!({"constraint_id": "ROLE", "role": "*", "need_to_be_owner": true}),
+        );
+        rules.insert(
+            "EDIT--101--*--*--*".to_string(),
+            json!({"constraint_id": "FORBIDDEN"}),
+        );
+        assert_eq!(
+            Some(&json!({"constraint_id": "ROLE", "role": "101"})),
+            find_constraint(&rules, "ADD--1--*--*--*")
+        );
+        assert_eq!(None, find_constraint(&rules, "ADD--101--*--*--*"));
+        assert_eq!(
+            Some(&json!({"constraint_id": "ROLE", "role": "*", "need_to_be_owner": true})),
+            find_constraint(&rules, "EDIT--1--verkey--*--*")
+        );
+        assert_eq!(
+            Some(&json!({"constraint_id": "ROLE", "role": "0"})),
+            find_constraint(&rules, "EDIT--1--role--0--")
+        );
+        assert_eq!(None, find_constraint(&rules, "EDIT--1--role--2--"));
+        assert_eq!(
+            Some(&json!({"constraint_id": "FORBIDDEN"})),
+            find_constraint(&rules, "EDIT--101--*--*--*")
+        );
+    }
+
+    #[test]
+    fn get_nym_changes_works() {
+        let data = json!({"identifier": "V4SGRU86Z58d6TV7PBUe6f", "role": "101", "verkey": "~key"});
+        let operation = json!({"type": "1", "dest": "VsKV7grR1BUE29mG2Fm2kX", "role": null, "verkey": "~other"});
+        assert_eq!(
+            vec![
+                ("role".to_string(), "101".to_string(), "".to_string()),
+                ("verkey".to_string(), "*".to_string(), "*".to_string())
+            ],
+            get_nym_changes(&operation, &data)
+        );
+        let operation = json!({"type": "1", "dest": "VsKV7grR1BUE29mG2Fm2kX", "alias": "alias"});
+        assert!(get_nym_changes(&operation, &data).is_empty());
+    }
+
+    #[test]
+    fn is_nym_owner_works() {
+        let nym = DidValue("VsKV7grR1BUE29mG2Fm2kX".to_string());
+        let creator = DidValue("V4SGRU86Z58d6TV7PBUe6f".to_string());
+        let data = json!({"identifier": creator.0, "verkey": "~key"});
+        assert!(is_nym_owner(&nym, &data, &nym));
+        assert!(!is_nym_owner(&nym, &data, &creator));
+        let data = json!({"identifier": creator.0, "verkey": null});
+        assert!(is_nym_owner(&nym, &data, &creator));
+    }
+
+    #[test]
+    fn is_satisfied_works_for_any_role() {
+        let constraint = json!({"constraint_id": "ROLE", "role": "*", "sig_count": 1});
+        assert!(is_satisfied(&constraint, Some(""), false));
+        assert!(!is_satisfied(&constraint, None, false));
+
+        let constraint = json!({"constraint_id": "ROLE", "role": "*", "sig_count": 1, "off_ledger_signature": true});
+        assert!(is_satisfied(&constraint, None, false));
+
+        let constraint = json!({"constraint_id": "ROLE", "role": "0", "sig_count": 3});
+        assert!(!is_satisfied(&constraint, Some("0"), true));
+        assert!(!is_satisfied(
+            &json!({"constraint_id": "FORBIDDEN"}),
+            Some("0"),
+            true
+        ));
+    }
+
+    #[test]
+    fn is_satisfied_works_for_owner() {
+        // Default rule of ATTRIB: any DID owning the target NYM
+        let constraint =
+            json!({"constraint_id": "ROLE", "role": "*", "sig_count": 1, "need_to_be_owner": true});
+        assert!(is_satisfied(&constraint, Some(""), true));
+        assert!(!is_satisfied(&constraint, Some("0"), false));
+    }
+}
@@ -0,0 +1,252 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+
+/// Explanation of a REJECT/REQNACK reason sent by indy-node together with the suggested next step.
+#[derive(Debug, PartialEq)]
+pub struct Rejection {
+    pub explanation: String,
+    pub hint: &'static str,
+}
+
+impl Rejection {
+    fn new(explanation: String, hint: &'static str) -> Option<Rejection> {
+        Some(Rejection { explanation, hint })
+    }
+
+    /// Recognize common indy-node rejection reasons. `None` if the reason is not known.
+    pub fn explain(reason: &str) -> Option<Rejection> {
+        let message = reason_message(reason);
+        let lowercase = message.to_lowercase();
+        let contains =
+            |patterns: &[&str]| patterns.iter().any(|pattern| lowercase.contains(pattern));
+
+        if contains(&["txn author agreement", "transaction author agreement"]) {
+            return explain_taa(&lowercase);
+        }
+
+        if let Some((name, version)) = duplicate_schema(message) {
+            return Rejection::new(
+                format!(
+                    "Schema \"{}\" version \"{}\" has already been written by the submitter DID.",
+                    name, version
+                ),
+                "Schemas cannot be changed. Send the schema with a new version.",
+            );
+        }
+
+        if contains(&["verkey for", "could not authenticate"])
+            && contains(&["cannot be found", "not found"])
+        {
+            return Rejection::new(
+                "Submitter DID has not been written to the ledger.".to_string(),
+                "Ask a Trustee, Steward or Endorser to write the DID with `ledger nym` command or use another DID.",
+            );
+        }
+
+        if contains(&[
+            "insufficient number of valid signatures",
+            "insufficientcorrectsignatures",
+        ]) {
+            return Rejection::new(
+                "Transaction signature is invalid.".to_string(),
+                "Check that the verkey of the submitter DID in the wallet matches the one on the ledger (`ledger get-nym`).",
+            );
+        }
+
+        if contains(&["can not touch", "only the owner can"])
+            || (contains(&["needs to be owner"]) && contains(&["not owner"]))
+        {
+            return Rejection::new(
+                format!("Only the owner is allowed to change the field{}.", rule(message)),
+                "Send the transaction with the DID that has created the object or ask its owner to make the change.",
+            );
+        }
+
+        if contains(&["'endorser' field must be explicitly set", "endorser must"]) {
+            return Rejection::new(
+                "The transaction must be endorsed.".to_string(),
+                "Build the transaction with `endorser=<DID>` parameter and pass it to the Endorser for `ledger endorse`.",
+            );
+        }
+
+        if contains(&["signatures are required"]) && contains(&["not enough"]) {
+            return Rejection::new(
+                format!(
+                    "The transaction does not have enough signatures{}.",
+                    rule(message)
+                ),
+                "Collect the signatures of the required DIDs with `ledger sign-multi` and send the transaction with `ledger custom`.",
+            );
+        }
+
+        if contains(&[
+            "signature is required",
+            "not enough",
+            "is not a steward",
+            "is neither trustee",
+            "only trustee",
+            "only steward",
+        ]) {
+            return Rejection::new(
+                format!(
+                    "Submitter DID does not have the role required for this action{}.",
+                    rule(message)
+                ),
+                "Use a DID with the required role or ask a Trustee to grant it. Check the rules with `ledger get-auth-rule`.",
+            );
+        }
+
+        None
+    }
+}
+
+fn explain_taa(reason: &str) -> Option<Rejection> {
+    let (explanation, hint) = if reason.contains("acceptance is required") {
+        (
+            "The ledger requires the Transaction Author Agreement to be accepted, but the transaction has no acceptance.",
+            "Accept the Agreement with `pool show-taa` command and build the transaction again.",
+        )
+    } else if reason.contains("acceptance is disabled") {
+        (
+            "The ledger has no Transaction Author Agreement, but the transaction carries an acceptance.",
+            "Reconnect to the pool with `pool refresh` and build the transaction again.",
+        )
+    } else if reason.contains("mechanism") {
+        (
+            "The Agreement acceptance mechanism is not allowed by the ledger.",
+            "Get the allowed mechanisms with `ledger get-acceptance-mechanisms` and set `taaAcceptanceMechanism` CLI config option.",
+        )
+    } else if reason.contains("acceptance time") {
+        (
+            "Time of the Agreement acceptance is out of the range allowed by the ledger.",
+            "Check the system clock, accept the Agreement again with `pool show-taa` and build the transaction again.",
+        )
+    } else if reason.contains("retired")
+        || reason.contains("incorrect")
+        || reason.contains("digest")
+    {
+        (
+            "The transaction accepts a Transaction Author Agreement which is not active on the ledger.",
+            "Accept the active Agreement with `pool show-taa` command and build the transaction again.",
+        )
+    } else {
+        return None;
+    };
+    Rejection::new(explanation.to_string(), hint)
+}
+
+/// Message of the rejection without the indy-node exception wrapping,
+/// e.g. `Not enough TRUSTEE signatures` for `client request invalid: UnauthorizedClientRequest('Not enough TRUSTEE signatures',)`.
+pub fn reason_message(reason: &str) -> &str {
+    let message = reason
+        .split_once("client request invalid: ")
+        .map(|(_, message)| message)
+        .unwrap_or(reason);
+    match message.split_once("('") {
+        Some((exception, rest)) if exception.chars().all(char::is_alphanumeric) => rest
+            .trim_end_matches(')')
+            .trim_end_matches(',')
+            .trim_end_matches('\''),
+        _ => message,
+    }
+}
+
+// indy-node: `<DID> can have one and only one SCHEMA with name <name> and version <version>`
+fn duplicate_schema(message: &str) -> Option<(&str, &str)> {
+    let (_, schema) = message.split_once("one and only one SCHEMA with name ")?;
+    let (name, version) = schema.split_once(" and version ")?;
+    Some((
+        name.trim(),
+        version.split_whitespace().next().unwrap_or_default(),
+    ))
+}
+
+// indy-node: `Rule for this action is: <constraint>\nFailed checks: ...`
+fn rule(message: &str) -> String {
+    message
+        .split_once("Rule for this action is: ")
+        .map(|(_, rule)| {
+            rule.split(|c| c == '\n' || c == '\\')
+                .next()
+                .unwrap_or(rule)
+                .trim()
+        })
+        .filter(|rule| !rule.is_empty())
+        .map(|rule| format!(" (rule: {})", rule))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reason_message_works() {
+        assert_eq!(
+            reason_message("client request invalid: UnauthorizedClientRequest('Not enough TRUSTEE signatures',)"),
+            "Not enough TRUSTEE signatures"
+        );
+        assert_eq!(reason_message("Some reason"), "Some reason");
+    }
+
+    #[test]
+    fn explain_works_for_missing_role() {
+        let rejection = Rejection::explain("client request invalid: UnauthorizedClientRequest('Rule for this action is: 1 TRUSTEE signature is required and needs to be owner OR 1 STEWARD signature is required\\nFailed checks:\\nConstraint: 1 TRUSTEE signature is required, Error: Not enough TRUSTEE signatures',)").unwrap();
+        assert_eq!(
+            rejection.explanation,
+            "Submitter DID does not have the role required for this action (rule: 1 TRUSTEE signature is required and needs to be owner OR 1 STEWARD signature is required)."
+        );
+    }
+
+    #[test]
+    fn explain_works_for_insufficient_signatures() {
+        let rejection = Rejection::explain("client request invalid: UnauthorizedClientRequest('Rule for this action is: 3 TRUSTEE signatures are required\\nFailed checks:\\nConstraint: 3 TRUSTEE signatures are required, Error: Not enough TRUSTEE signatures',)").unwrap();
+        assert!(rejection.hint.contains("sign-multi"));
+    }
+
+    #[test]
+    fn explain_works_for_taa() {
+        let rejection = Rejection::explain(
+            "client request invalid: InvalidClientTaaAcceptanceError('Txn Author Agreement acceptance is required for ledger with id 1',)",
+        )
+        .unwrap();
+        assert!(rejection.hint.contains("pool show-taa"));
+
+        let rejection = Rejection::explain(
+            "client request invalid: InvalidClientTaaAcceptanceError('Incorrect Txn Author Agreement(digest=abc) in the request',)",
+        )
+        .unwrap();
+        assert!(rejection.explanation.contains("not active"));
+    }
+
+    #[test]
+    fn explain_works_for_duplicate_schema() {
+        let rejection = Rejection::explain(
+            "client request invalid: InvalidClientRequest('VsKV7grR1BUE29mG2Fm2kX can have one and only one SCHEMA with name gvt and version 1.0',)",
+        )
+        .unwrap();
+        assert_eq!(
+            rejection.explanation,
+            "Schema \"gvt\" version \"1.0\" has already been written by the submitter DID."
+        );
+    }
+
+    #[test]
+    fn explain_works_for_unauthorized_field_edit() {
+        let rejection = Rejection::explain(
+            "client request invalid: UnauthorizedClientRequest('VsKV7grR1BUE29mG2Fm2kX can not touch verkey field since only the owner can modify it',)",
+        )
+        .unwrap();
+        assert!(rejection.explanation.starts_with("Only the owner"));
+    }
+
+    #[test]
+    fn explain_works_for_unknown_reason() {
+        assert_eq!(
+            Rejection::explain("client request invalid: some new check failed"),
+            None
+        );
+    }
+}
@@ -2,10 +2,13 @@
     Copyright © 2023 Province of British Columbia
     https://digital.gov.bc.ca/digital-trust
 */
-use crate::utils::time::timestamp_to_datetime;
+use crate::utils::{renderer::Output, time::timestamp_to_datetime};
 
 use serde_json::Value as JsonValue;
 
+// Transaction data fields holding timestamps (transaction author agreement)
+const TIMESTAMP_FIELDS: [&str; 2] = ["ratification_ts", "retirement_ts"];
+
 #[derive(Deserialize, Eq, PartialEq, Debug)]
 pub enum ResponseType {
     REQNACK,
@@ -22,12 +25,14 @@ pub struct Response<T> {
 }
 
 pub fn parse_transaction_response(
+    output: &dyn Output,
     mut result: JsonValue,
 ) -> Result<(Vec<(&'static str, &'static str)>, JsonValue, JsonValue), ()> {
     match result["ver"].clone().as_str() {
-        None => Ok(parse_transaction_response_v0(&mut result)),
-        Some("1") => Ok(parse_transaction_response_v1(&mut result)),
+        None => Ok(parse_transaction_response_v0(output, &mut result)),
+        Some("1") => Ok(parse_transaction_response_v1(output, &mut result)),
         ver => Err(println_err!(
+            output,
             "Unsupported transaction response format: {:?}",
             ver
         )),
@@ -35,11 +40,13 @@ pub fn parse_transaction_response(
 }
 
 fn parse_transaction_response_v0(
+    output: &dyn Output,
     result: &mut JsonValue,
 ) -> (Vec<(&'static str, &'static str)>, JsonValue, JsonValue) {
     if let Some(txn_time) = result["txnTime"].as_i64() {
-        result["txnTime"] = JsonValue::String(timestamp_to_datetime(txn_time))
+        result["txnTime"] = JsonValue::String(timestamp_to_datetime(output, txn_time))
     }
+    format_timestamps(output, result);
 
     let metadata_headers = vec![
         ("identifier", "Identifier"),
@@ -52,10 +59,12 @@ fn parse_transaction_response_v0(
 }
 
 fn parse_transaction_response_v1(
+    output: &dyn Output,
     result: &mut JsonValue,
 ) -> (Vec<(&'static str, &'static str)>, JsonValue, JsonValue) {
     if let Some(txn_time) = result["txnMetadata"]["txnTime"].as_i64() {
-        result["txnMetadata"]["txnTime"] = JsonValue::String(timestamp_to_datetime(txn_time))
+        result["txnMetadata"]["txnTime"] =
+            JsonValue::String(timestamp_to_datetime(output, txn_time))
     }
 
     let mut metadata_headers = vec![
@@ -85,7 +94,16 @@ fn parse_transaction_response_v1(
     }
 
     let metadata = JsonValue::Object(metadata_obj);
-    let data = result["txn"]["data"].clone();
+    let mut data = result["txn"]["data"].clone();
+    format_timestamps(output, &mut data);
 
     (metadata_headers, metadata, data)
 }
+
+fn format_timestamps(output: &dyn Output, data: &mut JsonValue) {
+    for field in TIMESTAMP_FIELDS {
+        if let Some(timestamp) = data[field].as_i64() {
+            data[field] = JsonValue::String(timestamp_to_datetime(output, timestamp))
+        }
+    }
+}
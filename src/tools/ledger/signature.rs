@@ -0,0 +1,99 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::{
+    error::{CliError, CliResult},
+    tools::did::key::Key,
+};
+
+use indy_vdr::pool::PreparedRequest;
+
+/// Rule of signing ledger requests used by a network.
+/// The signed message is always the signature input of the request as defined by Indy Node.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SignatureScheme {
+    /// Pure Ed25519 (RFC 8032)
+    Ed25519,
+    /// Ed25519ph (RFC 8032): Ed25519 signature of the SHA-512 digest of the message, with empty context
+    Ed25519ph,
+}
+
+impl Default for SignatureScheme {
+    fn default() -> Self {
+        SignatureScheme::Ed25519
+    }
+}
+
+impl SignatureScheme {
+    pub const NAMES: [&'static str; 2] = ["ed25519", "ed25519ph"];
+
+    pub fn from_str(scheme: &str) -> CliResult<SignatureScheme> {
+        match scheme.to_lowercase().as_str() {
+            "ed25519" => Ok(SignatureScheme::Ed25519),
+            "ed25519ph" => Ok(SignatureScheme::Ed25519ph),
+            _ => Err(CliError::InvalidInput(format!(
+                "Unsupported signature scheme \"{}\". One of: {}",
+                scheme,
+                Self::NAMES.join(", ")
+            ))),
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SignatureScheme::Ed25519 => "ed25519",
+            SignatureScheme::Ed25519ph => "ed25519ph",
+        }
+    }
+
+    /// Bytes to sign (or verify the signatures against) for the request.
+    pub fn preimage(&self, request: &PreparedRequest) -> CliResult<Vec<u8>> {
+        Ok(request.get_signature_input()?.into_bytes())
+    }
+
+    pub fn verify(&self, verkey: &str, message: &[u8], signature: &[u8]) -> CliResult<bool> {
+        match self {
+            SignatureScheme::Ed25519 => Key::verify(verkey, message, signature),
+            SignatureScheme::Ed25519ph => Key::verify_prehashed(verkey, message, signature),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indy_utils::base58;
+
+    #[test]
+    fn from_str_works() {
+        for name in SignatureScheme::NAMES.iter() {
+            assert_eq!(*name, SignatureScheme::from_str(name).unwrap().as_str());
+        }
+        SignatureScheme::from_str("rsa").unwrap_err();
+        SignatureScheme::from_str("ed25519-sha256").unwrap_err();
+    }
+
+    #[test]
+    fn verify_works_for_ed25519ph() {
+        // RFC 8032, 7.3. Test Vectors for Ed25519ph
+        let verkey = base58::encode(
+            hex::decode("ec172b93ad5e563bf4932c70e1245034c35467ef2efd4d64ebf819683467e2bf")
+                .unwrap(),
+        );
+        let signature = hex::decode(
+            "98a70222f0b8121aa9d30f813d683f809e462b469c7ff87639499bb94e6dae41\
+            31f85042463c2a355a2003d062adf5aaa10b8c61e636062aaad11c2a26083406",
+        )
+        .unwrap();
+        assert!(SignatureScheme::Ed25519ph
+            .verify(&verkey, b"abc", &signature)
+            .unwrap());
+        assert!(!SignatureScheme::Ed25519ph
+            .verify(&verkey, b"abd", &signature)
+            .unwrap());
+        assert!(!SignatureScheme::Ed25519
+            .verify(&verkey, b"abc", &signature)
+            .unwrap());
+    }
+}
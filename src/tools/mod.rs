@@ -2,7 +2,15 @@
     Copyright © 2023 Province of British Columbia
     https://digital.gov.bc.ca/digital-trust
 */
+pub mod anoncreds;
+pub mod anoncreds_id;
+pub mod crypto_policy;
 pub mod did;
+pub mod docker;
 pub mod ledger;
+pub mod multisig;
+pub mod note;
 pub mod pool;
+pub mod queue;
+pub mod template;
 pub mod wallet;
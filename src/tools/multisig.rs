@@ -0,0 +1,347 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::{
+    error::{CliError, CliResult},
+    tools::wallet::Wallet,
+    utils::futures::block_on,
+};
+
+use chrono::Utc;
+use serde_json::Value as JsonValue;
+
+pub const CATEGORY_MULTISIG_REQUEST: &str = "multisig_request";
+
+/// Ledger request which has to be approved by several DIDs before it is sent.
+/// The request collects the signatures of the co-signers until `threshold` of `signers` have signed it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MultisigRequest {
+    pub name: String,
+    pub request: JsonValue,
+    pub signers: Vec<String>,
+    pub threshold: usize,
+    pub created: i64,
+}
+
+impl MultisigRequest {
+    pub fn new(
+        name: &str,
+        request: &str,
+        signers: &[&str],
+        threshold: Option<usize>,
+    ) -> CliResult<MultisigRequest> {
+        let request = parse_request(request)?;
+
+        let mut unique_signers: Vec<String> = Vec::new();
+        for signer in signers {
+            if unique_signers.iter().any(|unique| unique == signer) {
+                return Err(CliError::InvalidInput(format!(
+                    "Signer \"{}\" is listed more than once",
+                    signer
+                )));
+            }
+            unique_signers.push(signer.to_string());
+        }
+
+        let threshold = threshold.unwrap_or(unique_signers.len());
+        if threshold == 0 || threshold > unique_signers.len() {
+            return Err(CliError::InvalidInput(format!(
+                "Threshold must be between 1 and the number of signers ({})",
+                unique_signers.len()
+            )));
+        }
+
+        Ok(MultisigRequest {
+            name: name.to_string(),
+            request,
+            signers: unique_signers,
+            threshold,
+            created: Utc::now().timestamp(),
+        })
+    }
+
+    /// DIDs whose signatures pass `is_valid` check, including the ones which are not listed as signers.
+    /// `is_valid` receives the DID and the signature it has put on the request.
+    pub fn signed_by(&self, is_valid: impl Fn(&str, &str) -> bool) -> Vec<String> {
+        signatures(&self.request)
+            .into_iter()
+            .filter(|(did, signature)| is_valid(did, signature))
+            .map(|(did, _)| did)
+            .collect()
+    }
+
+    pub fn approvals(&self, signed_by: &[String]) -> Vec<&str> {
+        self.signers
+            .iter()
+            .filter(|signer| signed_by.contains(*signer))
+            .map(String::as_str)
+            .collect()
+    }
+
+    pub fn outstanding(&self, signed_by: &[String]) -> Vec<&str> {
+        self.signers
+            .iter()
+            .filter(|signer| !signed_by.contains(*signer))
+            .map(String::as_str)
+            .collect()
+    }
+
+    pub fn is_complete(&self, signed_by: &[String]) -> bool {
+        self.approvals(signed_by).len() >= self.threshold
+    }
+
+    pub fn is_signer(&self, did: &str) -> bool {
+        self.signers.iter().any(|signer| signer == did)
+    }
+
+    /// Take over the signatures of the same request signed by other parties.
+    /// Only the signatures passing `is_valid` check are added.
+    /// Returns the DIDs whose signatures have been added and the DIDs whose signatures have been rejected.
+    pub fn merge(
+        &mut self,
+        request: &str,
+        is_valid: impl Fn(&str, &str) -> bool,
+    ) -> CliResult<(Vec<String>, Vec<String>)> {
+        let other = parse_request(request)?;
+        if unsigned(&other) != unsigned(&self.request) {
+            return Err(CliError::InvalidInput(format!(
+                "Transaction does not match the request \"{}\": it has been changed or belongs to another request",
+                self.name
+            )));
+        }
+
+        // Ledger does not accept a request carrying both single and multi signatures,
+        // so the single signature of the author is kept as one of multi signatures.
+        let existing = signatures(&self.request);
+        if let Some(fields) = self.request.as_object_mut() {
+            fields.remove("signature");
+        }
+        for (did, signature) in existing.iter() {
+            self.request["signatures"][did] = json!(signature);
+        }
+
+        let mut added = Vec::new();
+        let mut rejected = Vec::new();
+        for (did, signature) in signatures(&other) {
+            if existing.iter().any(|(existing, _)| existing == &did) {
+                continue;
+            }
+            if is_valid(&did, &signature) {
+                self.request["signatures"][&did] = json!(signature);
+                added.push(did);
+            } else {
+                rejected.push(did);
+            }
+        }
+        Ok((added, rejected))
+    }
+
+    pub fn store(&self, store: &Wallet) -> CliResult<()> {
+        let value = serde_json::to_vec(self)?;
+        block_on(async move {
+            if store
+                .fetch_record(CATEGORY_MULTISIG_REQUEST, &self.name, false)
+                .await?
+                .is_some()
+            {
+                return Err(CliError::Duplicate(format!(
+                    "Multi-signature request \"{}\" already exists.",
+                    self.name
+                )));
+            }
+            store
+                .store_record(CATEGORY_MULTISIG_REQUEST, &self.name, &value, None, true)
+                .await
+        })
+    }
+
+    pub fn update(&self, store: &Wallet) -> CliResult<()> {
+        let value = serde_json::to_vec(self)?;
+        block_on(async move {
+            store
+                .store_record(CATEGORY_MULTISIG_REQUEST, &self.name, &value, None, false)
+                .await
+        })
+    }
+
+    pub fn get(store: &Wallet, name: &str) -> CliResult<MultisigRequest> {
+        block_on(async move {
+            let entry = store
+                .fetch_record(CATEGORY_MULTISIG_REQUEST, name, false)
+                .await?
+                .ok_or_else(|| {
+                    CliError::NotFound(format!("Multi-signature request \"{}\" not found.", name))
+                })?;
+            serde_json::from_slice(&entry.value).map_err(CliError::from)
+        })
+    }
+
+    pub fn list(store: &Wallet) -> CliResult<Vec<MultisigRequest>> {
+        block_on(async move {
+            store
+                .fetch_all_records(CATEGORY_MULTISIG_REQUEST)
+                .await?
+                .iter()
+                .map(|entry| serde_json::from_slice(&entry.value).map_err(CliError::from))
+                .collect()
+        })
+    }
+
+    pub fn remove(&self, store: &Wallet) -> CliResult<()> {
+        block_on(async move {
+            store
+                .remove_record(CATEGORY_MULTISIG_REQUEST, &self.name)
+                .await
+        })
+    }
+}
+
+fn parse_request(request: &str) -> CliResult<JsonValue> {
+    let request = serde_json::from_str::<JsonValue>(request)
+        .map_err(|err| CliError::InvalidInput(format!("Invalid transaction provided: {}", err)))?;
+    if !matches!(request["operation"], JsonValue::Object(_)) || request["reqId"].is_null() {
+        return Err(CliError::InvalidInput(
+            "Invalid transaction provided: `operation` and `reqId` are required".to_string(),
+        ));
+    }
+    Ok(request)
+}
+
+// Signatures put on the request including the single signature of the author (`identifier`)
+fn signatures(request: &JsonValue) -> Vec<(String, String)> {
+    let mut signatures = request["signatures"]
+        .as_object()
+        .map(|signatures| {
+            signatures
+                .iter()
+                .filter_map(|(did, signature)| {
+                    Some((did.to_string(), signature.as_str()?.to_string()))
+                })
+                .collect::<Vec<(String, String)>>()
+        })
+        .unwrap_or_default();
+    if let (Some(identifier), Some(signature)) = (
+        request["identifier"].as_str(),
+        request["signature"].as_str(),
+    ) {
+        if !signatures.iter().any(|(did, _)| did == identifier) {
+            signatures.push((identifier.to_string(), signature.to_string()));
+        }
+    }
+    signatures
+}
+
+// The request as it is seen by the signers: every signature must cover exactly this content
+fn unsigned(request: &JsonValue) -> JsonValue {
+    let mut request = request.clone();
+    if let Some(fields) = request.as_object_mut() {
+        fields.remove("signature");
+        fields.remove("signatures");
+    }
+    request
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REQUEST: &str = r#"{"reqId":1,"identifier":"V4SGRU86Z58d6TV7PBUe6f","operation":{"type":"1","dest":"VsKV7grR1BUE29mG2Fm2kX"},"protocolVersion":2}"#;
+    const SIGNERS: [&str; 3] = [
+        "V4SGRU86Z58d6TV7PBUe6f",
+        "VsKV7grR1BUE29mG2Fm2kX",
+        "5Uu7YveFSGcT3dSzjpvPab",
+    ];
+
+    fn signed(request: &str, did: &str) -> String {
+        let mut request = serde_json::from_str::<JsonValue>(request).unwrap();
+        request["signatures"][did] = json!(signature(did));
+        request.to_string()
+    }
+
+    fn signature(did: &str) -> String {
+        format!("signature-{}", did)
+    }
+
+    // Accepts only the signatures produced by `signed`
+    fn is_valid(did: &str, signature: &str) -> bool {
+        signature == self::signature(did)
+    }
+
+    #[test]
+    fn multisig_request_merge_works() {
+        let mut request = MultisigRequest::new("nym", REQUEST, &SIGNERS, Some(2)).unwrap();
+        let signed_by = request.signed_by(is_valid);
+        assert!(request.approvals(&signed_by).is_empty());
+        assert!(!request.is_complete(&signed_by));
+
+        let (added, rejected) = request
+            .merge(&signed(REQUEST, SIGNERS[1]), is_valid)
+            .unwrap();
+        assert_eq!(vec![SIGNERS[1].to_string()], added);
+        assert!(rejected.is_empty());
+        let signed_by = request.signed_by(is_valid);
+        assert_eq!(
+            vec![SIGNERS[0], SIGNERS[2]],
+            request.outstanding(&signed_by)
+        );
+
+        // Already collected signatures are not added twice
+        let other = signed(&request.request.to_string(), SIGNERS[2]);
+        let (added, _) = request.merge(&other, is_valid).unwrap();
+        assert_eq!(vec![SIGNERS[2].to_string()], added);
+        let signed_by = request.signed_by(is_valid);
+        assert_eq!(vec![SIGNERS[1], SIGNERS[2]], request.approvals(&signed_by));
+        assert!(request.is_complete(&signed_by));
+    }
+
+    #[test]
+    fn multisig_request_merge_works_for_invalid_signature() {
+        let mut request = MultisigRequest::new("nym", REQUEST, &SIGNERS, None).unwrap();
+        let mut other = serde_json::from_str::<JsonValue>(REQUEST).unwrap();
+        other["signatures"][SIGNERS[1]] = json!("forged");
+        let (added, rejected) = request.merge(&other.to_string(), is_valid).unwrap();
+        assert!(added.is_empty());
+        assert_eq!(vec![SIGNERS[1].to_string()], rejected);
+        assert!(request.request["signatures"].get(SIGNERS[1]).is_none());
+    }
+
+    #[test]
+    fn multisig_request_merge_works_for_single_signature() {
+        let mut request = MultisigRequest::new("nym", REQUEST, &SIGNERS, None).unwrap();
+        let mut other = serde_json::from_str::<JsonValue>(REQUEST).unwrap();
+        other["signature"] = json!(signature(SIGNERS[0]));
+        let (added, _) = request.merge(&other.to_string(), is_valid).unwrap();
+        assert_eq!(vec![SIGNERS[0].to_string()], added);
+        assert_eq!(
+            json!(signature(SIGNERS[0])),
+            request.request["signatures"][SIGNERS[0]]
+        );
+
+        // The single signature is kept as multi signature once other signatures are added
+        let mut request = MultisigRequest::new("nym", &other.to_string(), &SIGNERS, None).unwrap();
+        request
+            .merge(&signed(REQUEST, SIGNERS[1]), is_valid)
+            .unwrap();
+        assert!(request.request.get("signature").is_none());
+        let signed_by = request.signed_by(is_valid);
+        assert_eq!(vec![SIGNERS[0], SIGNERS[1]], request.approvals(&signed_by));
+    }
+
+    #[test]
+    fn multisig_request_merge_works_for_changed_transaction() {
+        let mut request = MultisigRequest::new("nym", REQUEST, &SIGNERS, None).unwrap();
+        let changed = REQUEST.replace(r#""reqId":1"#, r#""reqId":2"#);
+        request
+            .merge(&signed(&changed, SIGNERS[1]), is_valid)
+            .unwrap_err();
+        assert!(request.signed_by(is_valid).is_empty());
+    }
+
+    #[test]
+    fn multisig_request_works_for_invalid_threshold() {
+        MultisigRequest::new("nym", REQUEST, &SIGNERS, Some(0)).unwrap_err();
+        MultisigRequest::new("nym", REQUEST, &SIGNERS, Some(4)).unwrap_err();
+        MultisigRequest::new("nym", REQUEST, &[SIGNERS[0], SIGNERS[0]], None).unwrap_err();
+    }
+}
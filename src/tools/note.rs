@@ -0,0 +1,77 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::{
+    error::{CliError, CliResult},
+    tools::wallet::Wallet,
+    utils::futures::block_on,
+};
+
+use chrono::Utc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub const CATEGORY_TXN_NOTE: &str = "txn_note";
+
+/// Local annotation of a ledger transaction stored in the wallet.
+/// Several notes can be attached to the same transaction.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransactionNote {
+    pub id: String,
+    pub ledger_type: i32,
+    pub seq_no: i32,
+    pub text: String,
+    pub author: Option<String>,
+    pub created: i64,
+}
+
+impl TransactionNote {
+    pub fn new(ledger_type: i32, seq_no: i32, text: &str, author: Option<&str>) -> Self {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos();
+        TransactionNote {
+            id: format!("{}:{}:{}", ledger_type, seq_no, nonce),
+            ledger_type,
+            seq_no,
+            text: text.to_string(),
+            author: author.map(String::from),
+            created: Utc::now().timestamp(),
+        }
+    }
+
+    pub fn store(&self, store: &Wallet) -> CliResult<()> {
+        let value = serde_json::to_vec(self)?;
+        block_on(async move {
+            store
+                .store_record(CATEGORY_TXN_NOTE, &self.id, &value, None, true)
+                .await
+        })
+    }
+
+    /// Notes ordered by the time they were added.
+    pub fn list(store: &Wallet) -> CliResult<Vec<TransactionNote>> {
+        let mut notes = block_on(async move {
+            store
+                .fetch_all_records(CATEGORY_TXN_NOTE)
+                .await?
+                .iter()
+                .map(|entry| serde_json::from_slice(&entry.value).map_err(CliError::from))
+                .collect::<CliResult<Vec<TransactionNote>>>()
+        })?;
+        notes.sort_by(|note, other| (note.created, &note.id).cmp(&(other.created, &other.id)));
+        Ok(notes)
+    }
+
+    pub fn list_for_txn(
+        store: &Wallet,
+        ledger_type: i32,
+        seq_no: i32,
+    ) -> CliResult<Vec<TransactionNote>> {
+        Ok(TransactionNote::list(store)?
+            .into_iter()
+            .filter(|note| note.ledger_type == ledger_type && note.seq_no == seq_no)
+            .collect())
+    }
+}
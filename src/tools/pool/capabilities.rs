@@ -0,0 +1,120 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::{
+    error::CliResult,
+    tools::{
+        ledger::{
+            permissions::{get_auth_rules, read_result},
+            Ledger,
+        },
+        pool::Pool,
+    },
+};
+
+use chrono::Utc;
+use indy_utils::did::DidValue;
+use serde_json::Value as JsonValue;
+use std::collections::BTreeMap;
+
+/// How long the discovered capabilities are used before they are read from the ledger again (1 day).
+pub const CAPABILITIES_TTL: i64 = 24 * 60 * 60;
+
+// Any DID will do: only whether the nodes accept the `timestamp` of GET_NYM matters
+const DID_INDY_PROBE: &str = "V4SGRU86Z58d6TV7PBUe6f";
+
+/// Protocol features of the network discovered from the ledger and cached in the pool directory.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Capabilities {
+    /// Time the capabilities have been read from the ledger
+    pub discovered_at: i64,
+    /// Whether the domain write requests must contain the Transaction Author Agreement acceptance
+    pub taa_required: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub taa_version: Option<String>,
+    /// Version of the active Acceptance Mechanisms List
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub aml_version: Option<String>,
+    #[serde(default)]
+    pub acceptance_mechanisms: Vec<String>,
+    /// Whether the nodes support `did:indy` (NYM versions, diddocContent and GET_NYM by time, indy-node 1.13+)
+    pub did_indy: bool,
+    /// Constraints of the auth rules by key `<auth_action>--<auth_type>--<field>--<old_value>--<new_value>`
+    #[serde(default)]
+    pub auth_rules: BTreeMap<String, JsonValue>,
+}
+
+impl Capabilities {
+    pub fn discover(pool: &Pool) -> CliResult<Capabilities> {
+        let request = Ledger::build_get_txn_author_agreement_request(Some(pool), None, None)?;
+        let taa = read_result(&Ledger::submit_request(pool, &request)?)?;
+        let taa_required = taa["data"]["text"]
+            .as_str()
+            .map(|text| !text.is_empty())
+            .unwrap_or(false);
+
+        let request =
+            Ledger::build_get_acceptance_mechanisms_request(Some(pool), None, None, None)?;
+        let aml = read_result(&Ledger::submit_request(pool, &request)?)?;
+        let acceptance_mechanisms = aml["data"]["aml"]
+            .as_object()
+            .map(|aml| aml.keys().cloned().collect())
+            .unwrap_or_default();
+
+        Ok(Capabilities {
+            discovered_at: Utc::now().timestamp(),
+            taa_required,
+            taa_version: taa_required
+                .then(|| taa["data"]["version"].as_str().map(String::from))
+                .flatten(),
+            aml_version: aml["data"]["version"].as_str().map(String::from),
+            acceptance_mechanisms,
+            did_indy: supports_did_indy(pool)?,
+            auth_rules: get_auth_rules(pool)?,
+        })
+    }
+
+    pub fn is_expired(&self, now: i64) -> bool {
+        now - self.discovered_at >= CAPABILITIES_TTL
+    }
+}
+
+// Nodes older than indy-node 1.13 reject GET_NYM with an unknown `timestamp` field
+fn supports_did_indy(pool: &Pool) -> CliResult<bool> {
+    let request = Ledger::build_get_nym_request_at(
+        Some(pool),
+        None,
+        &DidValue(DID_INDY_PROBE.to_string()),
+        None,
+        Some(Utc::now().timestamp() as u64),
+    )?;
+    let response = serde_json::from_str::<JsonValue>(&Ledger::submit_request(pool, &request)?)?;
+    Ok(response["op"].as_str() == Some("REPLY"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn is_expired_works() {
+        let capabilities = Capabilities {
+            discovered_at: 1000,
+            ..Capabilities::default()
+        };
+        assert!(!capabilities.is_expired(1000 + CAPABILITIES_TTL - 1));
+        assert!(capabilities.is_expired(1000 + CAPABILITIES_TTL));
+    }
+
+    #[test]
+    fn capabilities_deserialize_works_for_missed_optional_fields() {
+        let capabilities = serde_json::from_str::<Capabilities>(
+            r#"{"discovered_at":1,"taa_required":false,"did_indy":true}"#,
+        )
+        .unwrap();
+        assert!(capabilities.did_indy);
+        assert!(capabilities.acceptance_mechanisms.is_empty());
+        assert!(capabilities.auth_rules.is_empty());
+    }
+}
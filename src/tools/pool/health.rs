@@ -0,0 +1,126 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::error::{CliError, CliResult};
+
+use serde_json::Value as JsonValue;
+use std::{
+    collections::BTreeMap,
+    fs,
+    io::{ErrorKind, Read},
+    net::{TcpStream, ToSocketAddrs},
+    thread,
+    time::Duration,
+};
+
+// First byte of ZMTP greeting signature sent by a node right after the connection
+const ZMTP_SIGNATURE_START: u8 = 0xFF;
+
+pub struct NodeHealth {
+    pub alias: String,
+    pub address: String,
+    pub error: Option<String>,
+}
+
+impl NodeHealth {
+    pub fn is_reachable(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+/// Check that client endpoints of the validator nodes listed in the pool transactions respond.
+pub fn check_nodes(pool_transactions_file: &str, timeout: Duration) -> CliResult<Vec<NodeHealth>> {
+    let nodes = read_nodes(pool_transactions_file)?;
+
+    let handles = nodes
+        .into_iter()
+        .map(|(alias, address)| {
+            thread::spawn(move || {
+                let error = check_node(&address, timeout).err();
+                NodeHealth {
+                    alias,
+                    address,
+                    error,
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+
+    handles
+        .into_iter()
+        .map(|handle| {
+            handle
+                .join()
+                .map_err(|_| CliError::InvalidEntityState("Node check has failed".to_string()))
+        })
+        .collect()
+}
+
+/// Number of nodes which must be reachable to reach consensus: N - f, where f = (N - 1) / 3.
+pub fn consensus_quorum(nodes_count: usize) -> usize {
+    nodes_count - nodes_count.saturating_sub(1) / 3
+}
+
+/// Aliases and client addresses of the validator nodes listed in the pool transactions.
+pub fn read_nodes(pool_transactions_file: &str) -> CliResult<Vec<(String, String)>> {
+    let content = fs::read_to_string(pool_transactions_file)?;
+
+    // Later transactions update node data so they are merged by node DID
+    let mut nodes: BTreeMap<String, JsonValue> = BTreeMap::new();
+    for line in content.lines().filter(|line| !line.trim().is_empty()) {
+        let txn = serde_json::from_str::<JsonValue>(line)?;
+        let (dest, data) = match (
+            txn["txn"]["data"]["dest"].as_str(),
+            txn["txn"]["data"]["data"].as_object(),
+        ) {
+            (Some(dest), Some(data)) => (dest, data),
+            _ => continue,
+        };
+        let node = nodes.entry(dest.to_string()).or_insert_with(|| json!({}));
+        for (key, value) in data {
+            node[key] = value.clone();
+        }
+    }
+
+    Ok(nodes
+        .into_values()
+        .filter(|node| {
+            node["services"]
+                .as_array()
+                .map(|services| services.iter().any(|service| service == "VALIDATOR"))
+                .unwrap_or(false)
+        })
+        .filter_map(|node| {
+            let alias = node["alias"].as_str()?.to_string();
+            let address = format!(
+                "{}:{}",
+                node["client_ip"].as_str()?,
+                node["client_port"].as_u64()?
+            );
+            Some((alias, address))
+        })
+        .collect())
+}
+
+fn check_node(address: &str, timeout: Duration) -> Result<(), String> {
+    let socket_address = address
+        .to_socket_addrs()
+        .map_err(|err| format!("DNS failure: {}", err))?
+        .next()
+        .ok_or_else(|| "DNS failure: no address found".to_string())?;
+
+    let mut stream =
+        TcpStream::connect_timeout(&socket_address, timeout).map_err(|err| match err.kind() {
+            ErrorKind::TimedOut | ErrorKind::WouldBlock => "Connection timeout".to_string(),
+            _ => format!("Connection error: {}", err),
+        })?;
+    stream.set_read_timeout(Some(timeout)).ok();
+
+    let mut greeting = [0u8; 1];
+    match stream.read_exact(&mut greeting) {
+        Ok(_) if greeting[0] == ZMTP_SIGNATURE_START => Ok(()),
+        Ok(_) => Err("ZMQ handshake error: unexpected greeting".to_string()),
+        Err(err) => Err(format!("ZMQ handshake error: {}", err)),
+    }
+}
@@ -4,21 +4,45 @@
 */
 use crate::{
     error::{CliError, CliResult},
+    tools::{did::key::Key, ledger::SignatureScheme},
     utils::futures::block_on,
 };
-use std::collections::HashMap;
+use std::{
+    cell::{Ref, RefCell},
+    collections::HashMap,
+    rc::Rc,
+    time::Duration,
+};
 
+use capabilities::Capabilities;
+use chrono::Utc;
+use health::NodeHealth;
+use indy_utils::did::DidValue;
 use indy_vdr::{
     config::PoolConfig as OpenPoolConfig,
     pool::{helpers::perform_refresh, LocalPool, Pool as PoolImpl, PoolBuilder, PoolTransactions},
 };
 use pool_config::{PoolConfig, PoolDirectory};
+use serde_json::Value as JsonValue;
+use stats::LatencyStats;
 
+pub mod capabilities;
+pub mod health;
 pub mod pool_config;
+pub mod stats;
 
 pub struct Pool {
     pub pool: LocalPool,
     pub name: String,
+    pub namespace: Option<String>,
+    pub custom_roles: HashMap<String, String>,
+    pub endorser: Option<DidValue>,
+    pub signature_scheme: SignatureScheme,
+    node_weights: Option<HashMap<String, f32>>,
+    // Shared with the pool instances rebuilt on refresh so the stats cover the whole connection
+    stats: Rc<RefCell<LatencyStats>>,
+    // Whether a DID may send a transaction without endorser, by (DID, auth rules the transaction is checked against)
+    write_permissions: Rc<RefCell<HashMap<(String, String), bool>>>,
 }
 
 impl Pool {
@@ -28,15 +52,40 @@ impl Pool {
             .map_err(CliError::from)
     }
 
+    /// Create the pool config with the genesis transactions given as content instead of a file,
+    /// e.g. the ones extracted from a local network container.
+    pub fn create_with_genesis(name: &str, config: &PoolConfig, genesis: &str) -> CliResult<()> {
+        PoolTransactions::from_json(genesis).map_err(|err| {
+            CliError::InvalidInput(format!("Invalid genesis transactions: {}", err))
+        })?;
+        PoolDirectory::from(name).store_config_with_genesis(config, &mut genesis.as_bytes())
+    }
+
+    /// Check the detached Ed25519 signature the network publisher made over the genesis transactions file.
+    pub fn verify_genesis(genesis: &[u8], signature: &[u8], signer_verkey: &str) -> CliResult<()> {
+        if Key::verify(signer_verkey, genesis, signature)? {
+            Ok(())
+        } else {
+            Err(CliError::InvalidInput(format!(
+                "Signature of the genesis transactions is not valid for signer verkey {}",
+                signer_verkey
+            )))
+        }
+    }
+
     pub fn open(
         name: &str,
         config: OpenPoolConfig,
         pre_ordered_nodes: Option<Vec<&str>>,
     ) -> CliResult<Pool> {
-        let pool_transactions_file = PoolDirectory::from(name)
+        let pool_config = PoolDirectory::from(name)
             .read_config()
-            .map_err(|_| CliError::NotFound(format!("Pool \"{}\" does not exist.", name)))?
-            .genesis_txn;
+            .map_err(|_| CliError::NotFound(format!("Pool \"{}\" does not exist.", name)))?;
+        let pool_transactions_file = pool_config.genesis_txn;
+        let signature_scheme = match pool_config.signature_scheme {
+            Some(ref scheme) => SignatureScheme::from_str(scheme)?,
+            None => SignatureScheme::default(),
+        };
 
         let weight_nodes = pre_ordered_nodes.map(|pre_ordered_nodes| {
             pre_ordered_nodes
@@ -49,44 +98,188 @@ impl Pool {
 
         let pool = PoolBuilder::from(config)
             .transactions(pool_transactions)?
-            .node_weights(weight_nodes)
+            .node_weights(weight_nodes.clone())
             .into_local()?;
 
         let pool = Pool {
             pool,
             name: name.to_string(),
+            namespace: pool_config.namespace,
+            custom_roles: pool_config.custom_roles,
+            endorser: pool_config.endorser.map(DidValue),
+            signature_scheme,
+            node_weights: weight_nodes,
+            stats: Rc::new(RefCell::new(LatencyStats::default())),
+            write_permissions: Rc::new(RefCell::new(HashMap::new())),
         };
 
-        pool.refresh()
+        // The pool rebuilt from the caught up transactions if the genesis ones are outdated
+        let refreshed = pool
+            .refresh()
             .map_err(|_| CliError::NotFound(format!("Unable to connect to pool \"{}\"", name)))?;
 
-        Ok(pool)
+        Ok(refreshed.unwrap_or(pool))
+    }
+
+    /// Root hash (base58) and size of the pool ledger merkle tree known to the connection.
+    pub fn merkle_tree_info(&self) -> (String, usize) {
+        self.pool.get_merkle_tree_info()
+    }
+
+    pub fn check_nodes(name: &str, timeout: Duration) -> CliResult<Vec<NodeHealth>> {
+        health::check_nodes(&Self::genesis_txn_path(name)?, timeout)
+    }
+
+    pub fn genesis_txn_path(name: &str) -> CliResult<String> {
+        PoolDirectory::from(name)
+            .read_config()
+            .map(|config| config.genesis_txn)
+            .map_err(|_| CliError::NotFound(format!("Pool \"{}\" does not exist.", name)))
     }
 
     pub fn refresh(&self) -> CliResult<Option<Pool>> {
         let (transactions, _) = block_on(async move { perform_refresh(&self.pool).await })?;
+        // Informational only: the refresh result does not depend on it
+        PoolDirectory::from(&self.name)
+            .store_last_refresh(Utc::now().timestamp())
+            .ok();
 
         match transactions {
             Some(new_transactions) if new_transactions.len() > 0 => {
                 let mut transactions = PoolTransactions::from(self.pool.get_merkle_tree());
                 transactions.extend_from_json(new_transactions)?;
 
-                let pool = PoolBuilder::from(self.pool.get_config().to_owned())
-                    .transactions(transactions)?
-                    .into_local()?;
+                let pool = self.rebuild(transactions)?;
 
                 PoolDirectory::from(&self.name)
                     .store_pool_transactions(&self.pool.get_json_transactions()?)?;
 
-                Ok(Some(Pool {
-                    pool,
-                    name: self.name.to_string(),
-                }))
+                Ok(Some(pool))
             }
             _ => Ok(None),
         }
     }
 
+    /// Rebuild the pool connection from the known pool transactions and catch up the ledger.
+    pub fn reconnect(&self) -> CliResult<Pool> {
+        let pool = self.rebuild(PoolTransactions::from(self.pool.get_merkle_tree()))?;
+        Ok(pool.refresh()?.unwrap_or(pool))
+    }
+
+    // The rebuilt pool keeps the settings and the shared state of the current one
+    fn rebuild(&self, transactions: PoolTransactions) -> CliResult<Pool> {
+        let pool = PoolBuilder::from(self.pool.get_config().to_owned())
+            .transactions(transactions)?
+            .node_weights(self.node_weights.clone())
+            .into_local()?;
+
+        Ok(Pool {
+            pool,
+            name: self.name.to_string(),
+            namespace: self.namespace.clone(),
+            custom_roles: self.custom_roles.clone(),
+            endorser: self.endorser.clone(),
+            signature_scheme: self.signature_scheme,
+            node_weights: self.node_weights.clone(),
+            stats: self.stats.clone(),
+            write_permissions: self.write_permissions.clone(),
+        })
+    }
+
+    pub fn record_request(
+        &self,
+        txn_type: &str,
+        timing: Option<&HashMap<String, f32>>,
+        failed: bool,
+    ) {
+        self.stats.borrow_mut().record(txn_type, timing, failed);
+    }
+
+    pub fn latency_stats(&self) -> Ref<LatencyStats> {
+        self.stats.borrow()
+    }
+
+    pub fn reset_latency_stats(&self) {
+        self.stats.replace(LatencyStats::default());
+    }
+
+    /// Set or clear the default endorser of the pool. It is applied on the next connection.
+    pub fn set_endorser(name: &str, endorser: Option<&DidValue>) -> CliResult<()> {
+        let directory = PoolDirectory::from(name);
+        let mut config = directory
+            .read_config()
+            .map_err(|_| CliError::NotFound(format!("Pool \"{}\" does not exist.", name)))?;
+        config.endorser = endorser.map(|endorser| endorser.to_string());
+        directory.update_config(&config)
+    }
+
+    /// Set or reset to the default the signature scheme of the pool. It is applied on the next connection.
+    pub fn set_signature_scheme(name: &str, scheme: Option<SignatureScheme>) -> CliResult<()> {
+        let directory = PoolDirectory::from(name);
+        let mut config = directory
+            .read_config()
+            .map_err(|_| CliError::NotFound(format!("Pool \"{}\" does not exist.", name)))?;
+        config.signature_scheme = scheme.map(|scheme| scheme.as_str().to_string());
+        directory.update_config(&config)
+    }
+
+    /// Register the JSON Schema of ATTRIB raw value with the name, or remove it.
+    pub fn set_attrib_schema(name: &str, key: &str, schema: Option<JsonValue>) -> CliResult<()> {
+        let directory = PoolDirectory::from(name);
+        let mut config = directory
+            .read_config()
+            .map_err(|_| CliError::NotFound(format!("Pool \"{}\" does not exist.", name)))?;
+        match schema {
+            Some(schema) => {
+                config.attrib_schemas.insert(key.to_string(), schema);
+            }
+            None => {
+                config.attrib_schemas.remove(key).ok_or_else(|| {
+                    CliError::NotFound(format!(
+                        "There is no schema of \"{}\" attribute registered for pool \"{}\".",
+                        key, name
+                    ))
+                })?;
+            }
+        }
+        directory.update_config(&config)
+    }
+
+    pub fn attrib_schemas(name: &str) -> CliResult<HashMap<String, JsonValue>> {
+        PoolDirectory::from(name)
+            .read_config()
+            .map(|config| config.attrib_schemas)
+            .map_err(|_| CliError::NotFound(format!("Pool \"{}\" does not exist.", name)))
+    }
+
+    pub fn cached_write_permission(&self, did: &DidValue, rules: &str) -> Option<bool> {
+        self.write_permissions
+            .borrow()
+            .get(&(did.to_string(), rules.to_string()))
+            .copied()
+    }
+
+    pub fn cache_write_permission(&self, did: &DidValue, rules: &str, allowed: bool) {
+        self.write_permissions
+            .borrow_mut()
+            .insert((did.to_string(), rules.to_string()), allowed);
+    }
+
+    /// Capabilities of the network cached in the pool directory, `None` if they are not discovered yet or expired.
+    pub fn capabilities(&self) -> Option<Capabilities> {
+        PoolDirectory::from(&self.name)
+            .read_capabilities()
+            .ok()
+            .filter(|capabilities| !capabilities.is_expired(Utc::now().timestamp()))
+    }
+
+    /// Read the capabilities of the network from the ledger and store them in the pool directory.
+    pub fn refresh_capabilities(&self) -> CliResult<Capabilities> {
+        let capabilities = Capabilities::discover(self)?;
+        PoolDirectory::from(&self.name).store_capabilities(&capabilities)?;
+        Ok(capabilities)
+    }
+
     pub fn list() -> CliResult<String> {
         PoolDirectory::list_pools().map_err(CliError::from)
     }
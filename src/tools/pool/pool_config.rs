@@ -4,9 +4,12 @@
 */
 use crate::{
     error::{CliError, CliResult},
+    tools::pool::capabilities::Capabilities,
     utils::environment::EnvironmentUtils,
 };
-use std::path::PathBuf;
+use indy_utils::hash::SHA256;
+use serde_json::Value as JsonValue;
+use std::{collections::HashMap, path::PathBuf};
 use std::{
     fs,
     fs::File,
@@ -14,9 +17,28 @@ use std::{
     io::{Read, Write},
 };
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub struct PoolConfig {
     pub genesis_txn: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub namespace: Option<String>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub custom_roles: HashMap<String, String>,
+    /// Endorser of the write requests which the active DID is not allowed to send on its own
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub endorser: Option<String>,
+    /// Time of the last successful catch-up of the pool transactions
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_refresh: Option<i64>,
+    /// JSON Schemas of ATTRIB raw values by attribute name
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub attrib_schemas: HashMap<String, JsonValue>,
+    /// Signature scheme of the requests if the network does not use the default one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature_scheme: Option<String>,
+    /// Verkey the detached signature of the genesis transactions has been verified with on creation
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub genesis_signer: Option<String>,
 }
 
 pub struct PoolDirectory {
@@ -24,13 +46,23 @@ pub struct PoolDirectory {
 }
 
 impl PoolDirectory {
-    pub(crate) fn from(name: &str) -> Self {
+    pub fn from(name: &str) -> Self {
         PoolDirectory {
             name: name.to_string(),
         }
     }
 
     pub(crate) fn store_config(&self, config: &PoolConfig) -> CliResult<()> {
+        let mut genesis = File::open(&config.genesis_txn)?;
+        self.store_config_with_genesis(config, &mut genesis)
+    }
+
+    // `config.genesis_txn` is ignored: the genesis transactions are copied from `genesis`
+    pub(crate) fn store_config_with_genesis(
+        &self,
+        config: &PoolConfig,
+        genesis: &mut dyn Read,
+    ) -> CliResult<()> {
         let mut path = self.path();
 
         if path.as_path().exists() {
@@ -47,9 +79,8 @@ impl PoolDirectory {
             path.push(&self.name);
             path.set_extension("txn");
 
-            let mut gt_fin = File::open(&config.genesis_txn)?;
             let mut gt_fout = File::create(path.as_path())?;
-            io::copy(&mut gt_fin, &mut gt_fout)?;
+            io::copy(genesis, &mut gt_fout)?;
         }
         let txn_path = path.to_string_lossy().to_string();
 
@@ -60,17 +91,26 @@ impl PoolDirectory {
             path.push("config");
             path.set_extension("json");
 
-            let pool_config = json!({ "genesis_txn": txn_path });
+            let pool_config = PoolConfig {
+                genesis_txn: txn_path,
+                namespace: config.namespace.clone(),
+                custom_roles: config.custom_roles.clone(),
+                endorser: config.endorser.clone(),
+                last_refresh: None,
+                attrib_schemas: config.attrib_schemas.clone(),
+                signature_scheme: config.signature_scheme.clone(),
+                genesis_signer: config.genesis_signer.clone(),
+            };
 
             let mut f: File = File::create(path.as_path())?;
-            f.write_all(pool_config.to_string().as_bytes())?;
+            f.write_all(json!(pool_config).to_string().as_bytes())?;
             f.flush()?;
         }
 
         Ok(())
     }
 
-    pub(crate) fn read_config(&self) -> CliResult<PoolConfig> {
+    pub fn read_config(&self) -> CliResult<PoolConfig> {
         let path = EnvironmentUtils::pool_config_path(&self.name);
 
         let mut config_json = String::new();
@@ -82,6 +122,14 @@ impl PoolDirectory {
         Ok(config)
     }
 
+    pub(crate) fn update_config(&self, config: &PoolConfig) -> CliResult<()> {
+        let path = EnvironmentUtils::pool_config_path(&self.name);
+        let mut f: File = File::create(path)?;
+        f.write_all(json!(config).to_string().as_bytes())?;
+        f.flush()?;
+        Ok(())
+    }
+
     pub(crate) fn delete_config(&self) -> CliResult<()> {
         let path = self.path();
         if !path.as_path().exists() {
@@ -109,7 +157,17 @@ impl PoolDirectory {
                     .file_name()
                     .and_then(|os_str| os_str.to_str())
                 {
-                    let json = json!({ "pool": pool_name.to_owned() });
+                    let config = PoolDirectory::from(pool_name).read_config().ok();
+                    let genesis = config
+                        .as_ref()
+                        .and_then(|config| fs::read(&config.genesis_txn).ok());
+                    let json = json!({
+                        "pool": pool_name.to_owned(),
+                        "nodes": genesis.as_deref().map(count_nodes),
+                        "checksum": genesis.as_deref().map(|genesis| hex::encode(SHA256::digest(genesis))),
+                        "last_refresh": config.as_ref().and_then(|config| config.last_refresh),
+                        "genesis_signer": config.and_then(|config| config.genesis_signer),
+                    });
                     pools.push(json);
                 }
             }
@@ -119,6 +177,26 @@ impl PoolDirectory {
         Ok(pools)
     }
 
+    pub(crate) fn store_last_refresh(&self, timestamp: i64) -> CliResult<()> {
+        let mut config = self.read_config()?;
+        config.last_refresh = Some(timestamp);
+        self.update_config(&config)
+    }
+
+    pub fn read_capabilities(&self) -> CliResult<Capabilities> {
+        let path = EnvironmentUtils::pool_capabilities_path(&self.name);
+        let capabilities = fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&capabilities)?)
+    }
+
+    pub(crate) fn store_capabilities(&self, capabilities: &Capabilities) -> CliResult<()> {
+        let path = EnvironmentUtils::pool_capabilities_path(&self.name);
+        let mut f = File::create(path)?;
+        f.write_all(json!(capabilities).to_string().as_bytes())?;
+        f.flush()?;
+        Ok(())
+    }
+
     pub(crate) fn store_pool_transactions(&self, transactions: &Vec<String>) -> CliResult<()> {
         let path = EnvironmentUtils::pool_transactions_path(&self.name);
         let mut f = File::create(path.as_path())?;
@@ -130,3 +208,53 @@ impl PoolDirectory {
         EnvironmentUtils::pool_path(&self.name)
     }
 }
+
+// Validator nodes of the pool transactions: the latest NODE transaction of a node defines its services
+fn count_nodes(transactions: &[u8]) -> usize {
+    let mut nodes: HashMap<String, bool> = HashMap::new();
+    for line in String::from_utf8_lossy(transactions).lines() {
+        let transaction = match serde_json::from_str::<JsonValue>(line) {
+            Ok(transaction) => transaction,
+            Err(_) => continue,
+        };
+        // Transactions of protocol version 1 are not wrapped into `txn`
+        let txn = if transaction["txn"].is_object() {
+            &transaction["txn"]
+        } else {
+            &transaction
+        };
+        let (txn_type, data) = (txn["type"].as_str(), &txn["data"]);
+        let (dest, services) = if data["dest"].is_string() {
+            (data["dest"].as_str(), &data["data"]["services"])
+        } else {
+            (txn["dest"].as_str(), &data["services"])
+        };
+        if let (Some("0"), Some(dest)) = (txn_type, dest) {
+            let validator = match services.as_array() {
+                Some(services) => services.iter().any(|service| service == "VALIDATOR"),
+                // Update of other node fields keeps the services
+                None => nodes.get(dest).copied().unwrap_or(false),
+            };
+            nodes.insert(dest.to_string(), validator);
+        }
+    }
+    nodes.values().filter(|validator| **validator).count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn count_nodes_works() {
+        let transactions = [
+            r#"{"txn":{"data":{"data":{"alias":"Node1","services":["VALIDATOR"]},"dest":"Node1Dest"},"type":"0"}}"#,
+            r#"{"txn":{"data":{"data":{"alias":"Node2","services":["VALIDATOR"]},"dest":"Node2Dest"},"type":"0"}}"#,
+            r#"{"txn":{"data":{"data":{"alias":"Node2","services":[]},"dest":"Node2Dest"},"type":"0"}}"#,
+            r#"{"txn":{"data":{"data":{"alias":"Node1","node_port":9711},"dest":"Node1Dest"},"type":"0"}}"#,
+            r#"{"txn":{"data":{"dest":"TrusteeDid","role":"0"},"type":"1"}}"#,
+        ]
+        .join("\n");
+        assert_eq!(1, count_nodes(transactions.as_bytes()));
+    }
+}
@@ -0,0 +1,165 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::utils::metrics::Metrics;
+
+use indy_vdr::ledger::constants::{
+    GET_ATTR, GET_AUTH_RULE, GET_CRED_DEF, GET_FROZEN_LEDGERS, GET_NYM, GET_REVOC_REG,
+    GET_REVOC_REG_DEF, GET_REVOC_REG_DELTA, GET_SCHEMA, GET_TXN, GET_TXN_AUTHR_AGRMT,
+    GET_TXN_AUTHR_AGRMT_AML, GET_VALIDATOR_INFO,
+};
+use std::collections::{BTreeMap, HashMap};
+
+// Read requests are sent to a few nodes only, so missing replies cannot be attributed to a node
+const READ_REQUESTS: [&str; 13] = [
+    GET_TXN,
+    GET_ATTR,
+    GET_NYM,
+    GET_SCHEMA,
+    GET_CRED_DEF,
+    GET_AUTH_RULE,
+    GET_TXN_AUTHR_AGRMT,
+    GET_TXN_AUTHR_AGRMT_AML,
+    GET_REVOC_REG_DEF,
+    GET_REVOC_REG,
+    GET_REVOC_REG_DELTA,
+    GET_FROZEN_LEDGERS,
+    GET_VALIDATOR_INFO,
+];
+
+/// Latency of ledger requests collected since the pool has been connected.
+#[derive(Debug, Default)]
+pub struct LatencyStats {
+    pub requests: u64,
+    pub failures: u64,
+    nodes: BTreeMap<String, NodeStats>,
+}
+
+#[derive(Debug, Default)]
+struct NodeStats {
+    // Reply times in milliseconds
+    latencies: Vec<f64>,
+    errors: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct NodeLatency {
+    pub node: String,
+    pub replies: usize,
+    pub p50: f64,
+    pub p95: f64,
+    pub max: f64,
+    pub errors: u64,
+}
+
+impl LatencyStats {
+    /// Record the request outcome. `timing` contains reply time in seconds for every node which replied.
+    pub fn record(&mut self, txn_type: &str, timing: Option<&HashMap<String, f32>>, failed: bool) {
+        self.requests += 1;
+        if failed {
+            self.failures += 1;
+        }
+
+        if let Some(timing) = timing {
+            for (node, elapsed) in timing {
+                self.nodes
+                    .entry(node.to_string())
+                    .or_default()
+                    .latencies
+                    .push(*elapsed as f64 * 1000.0);
+            }
+        }
+
+        // Write requests are sent to every node: the nodes keeping silent are the ones to blame for the failure
+        if failed && !READ_REQUESTS.contains(&txn_type) {
+            for (node, stats) in self.nodes.iter_mut() {
+                if !timing
+                    .map(|timing| timing.contains_key(node))
+                    .unwrap_or(false)
+                {
+                    stats.errors += 1;
+                }
+            }
+        }
+    }
+
+    pub fn nodes(&self) -> Vec<NodeLatency> {
+        self.nodes
+            .iter()
+            .map(|(node, stats)| {
+                let mut latencies = stats.latencies.clone();
+                latencies.sort_by(|a, b| a.total_cmp(b));
+                NodeLatency {
+                    node: node.to_string(),
+                    replies: latencies.len(),
+                    p50: percentile(&latencies, 50.0),
+                    p95: percentile(&latencies, 95.0),
+                    max: latencies.last().cloned().unwrap_or_default(),
+                    errors: stats.errors,
+                }
+            })
+            .collect()
+    }
+
+    /// Request counters and node reply latencies of the pool for Prometheus.
+    pub fn metrics(&self, pool: &str) -> Metrics {
+        let mut metrics = Metrics::new();
+        metrics.counter(
+            "indy_cli_ledger_requests_total",
+            "Number of ledger requests sent since the pool has been connected",
+            &[(vec![("pool", pool)], self.requests as f64)],
+        );
+        metrics.counter(
+            "indy_cli_ledger_request_failures_total",
+            "Number of failed ledger requests since the pool has been connected",
+            &[(vec![("pool", pool)], self.failures as f64)],
+        );
+        let latencies = self.nodes();
+        let mut latency_samples = Vec::new();
+        for latency in latencies.iter() {
+            for (quantile, value) in [
+                ("0.5", latency.p50),
+                ("0.95", latency.p95),
+                ("1", latency.max),
+            ] {
+                latency_samples.push((
+                    vec![
+                        ("pool", pool),
+                        ("node", latency.node.as_str()),
+                        ("quantile", quantile),
+                    ],
+                    value,
+                ));
+            }
+        }
+        metrics.gauge(
+            "indy_cli_node_reply_latency_milliseconds",
+            "Node reply time to ledger requests",
+            &latency_samples,
+        );
+        metrics.counter(
+            "indy_cli_node_errors_total",
+            "Number of failed write requests the node has not replied to",
+            &latencies
+                .iter()
+                .map(|latency| {
+                    (
+                        vec![("pool", pool), ("node", latency.node.as_str())],
+                        latency.errors as f64,
+                    )
+                })
+                .collect::<Vec<_>>(),
+        );
+        metrics
+    }
+}
+
+// Nearest-rank percentile of sorted values
+fn percentile(sorted: &[f64], percentile: f64) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    let rank = (percentile / 100.0 * sorted.len() as f64).ceil() as usize;
+    sorted[rank.max(1) - 1]
+}
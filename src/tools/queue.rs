@@ -0,0 +1,87 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::{
+    error::{CliError, CliResult},
+    utils::environment::EnvironmentUtils,
+};
+
+use chrono::Utc;
+use indy_vdr::pool::PreparedRequest;
+use std::fs;
+
+/// Signed write request spooled to the local queue because the pool could not be reached.
+/// Queued requests are kept as files in the CLI home directory and sent by `queue flush`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueuedRequest {
+    pub id: String,
+    pub txn_type: String,
+    pub submitter: String,
+    pub pool: Option<String>,
+    pub queued: i64,
+    pub request: String,
+}
+
+impl QueuedRequest {
+    pub fn new(request: &PreparedRequest, submitter: &str, pool: Option<&str>) -> QueuedRequest {
+        QueuedRequest {
+            id: request.req_id.to_string(),
+            txn_type: request.txn_type.to_string(),
+            submitter: submitter.to_string(),
+            pool: pool.map(String::from),
+            queued: Utc::now().timestamp(),
+            request: json!(&request.req_json).to_string(),
+        }
+    }
+
+    /// Queued requests in the order they have been spooled.
+    pub fn list() -> CliResult<Vec<QueuedRequest>> {
+        let path = EnvironmentUtils::queue_path();
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut requests = Vec::new();
+        for entry in fs::read_dir(path)? {
+            let path = entry?.path();
+            if path.extension().and_then(|extension| extension.to_str()) != Some("json") {
+                continue;
+            }
+            let content = fs::read_to_string(&path)?;
+            let request = serde_json::from_str::<QueuedRequest>(&content).map_err(|err| {
+                CliError::InvalidEntityState(format!(
+                    "Queued request \"{}\" is corrupted: {}",
+                    path.display(),
+                    err
+                ))
+            })?;
+            requests.push(request);
+        }
+        requests.sort_by(|request, other| {
+            (request.queued, request.id.len(), &request.id).cmp(&(
+                other.queued,
+                other.id.len(),
+                &other.id,
+            ))
+        });
+        Ok(requests)
+    }
+
+    pub fn save(&self) -> CliResult<()> {
+        fs::create_dir_all(EnvironmentUtils::queue_path())?;
+        fs::write(
+            EnvironmentUtils::queued_request_path(&self.id),
+            serde_json::to_vec_pretty(self)?,
+        )
+        .map_err(CliError::from)
+    }
+
+    pub fn remove(&self) -> CliResult<()> {
+        fs::remove_file(EnvironmentUtils::queued_request_path(&self.id)).map_err(CliError::from)
+    }
+
+    pub fn prepared_request(&self) -> CliResult<PreparedRequest> {
+        PreparedRequest::from_request_json(&self.request).map_err(CliError::from)
+    }
+}
@@ -0,0 +1,175 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::{
+    error::{CliError, CliResult},
+    tools::wallet::Wallet,
+    utils::futures::block_on,
+};
+
+use chrono::Utc;
+use serde_json::{Map as JsonMap, Value as JsonValue};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub const CATEGORY_TXN_TEMPLATE: &str = "txn_template";
+
+// Request fields bound to a particular submission which are never stored in a template
+const SUBMISSION_FIELDS: [&str; 5] = [
+    "reqId",
+    "signature",
+    "signatures",
+    "taaAcceptance",
+    "endorser",
+];
+
+/// Reusable starting point for a recurring ledger request stored in the wallet.
+///
+/// Operation fields listed in `params` are cleared on save and must be given on every apply,
+/// the rest of the operation fields keep the saved values unless they are overridden.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransactionTemplate {
+    pub name: String,
+    pub request: JsonValue,
+    pub params: Vec<String>,
+    pub created: i64,
+}
+
+impl TransactionTemplate {
+    pub fn from_request(name: &str, request: &str, params: &[&str]) -> CliResult<Self> {
+        let mut request = serde_json::from_str::<JsonValue>(request).map_err(|err| {
+            CliError::InvalidInput(format!("Invalid transaction provided: {}", err))
+        })?;
+        let fields = request.as_object_mut().ok_or_else(|| {
+            CliError::InvalidInput("Invalid transaction provided: object expected".to_string())
+        })?;
+        if !matches!(fields.get("operation"), Some(JsonValue::Object(_))) {
+            return Err(CliError::InvalidInput(
+                "Invalid transaction provided: `operation` is missing".to_string(),
+            ));
+        }
+        for field in SUBMISSION_FIELDS {
+            fields.remove(field);
+        }
+
+        for param in params {
+            if !replace_field(&mut request["operation"], param, &JsonValue::Null) {
+                return Err(CliError::InvalidInput(format!(
+                    "Transaction operation has no \"{}\" field",
+                    param
+                )));
+            }
+        }
+
+        Ok(TransactionTemplate {
+            name: name.to_string(),
+            request,
+            params: params.iter().map(|param| param.to_string()).collect(),
+            created: Utc::now().timestamp(),
+        })
+    }
+
+    /// Build a request with a fresh request id from the template.
+    /// `values` maps operation field names to their new values, all the template params must be present.
+    pub fn apply(
+        &self,
+        values: &JsonMap<String, JsonValue>,
+        identifier: Option<&str>,
+    ) -> CliResult<String> {
+        let missing = self
+            .params
+            .iter()
+            .filter(|param| !values.contains_key(param.as_str()))
+            .map(String::as_str)
+            .collect::<Vec<&str>>();
+        if !missing.is_empty() {
+            return Err(CliError::InvalidInput(format!(
+                "Values for template \"{}\" params are missing: {}",
+                self.name,
+                missing.join(", ")
+            )));
+        }
+
+        let mut request = self.request.clone();
+        for (field, value) in values {
+            if !replace_field(&mut request["operation"], field, value) {
+                return Err(CliError::InvalidInput(format!(
+                    "Template \"{}\" operation has no \"{}\" field",
+                    self.name, field
+                )));
+            }
+        }
+
+        let req_id = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos() as u64;
+        request["reqId"] = json!(req_id);
+        if let Some(identifier) = identifier {
+            request["identifier"] = json!(identifier);
+        }
+        Ok(request.to_string())
+    }
+
+    pub fn store(&self, store: &Wallet) -> CliResult<()> {
+        let value = serde_json::to_vec(self)?;
+        block_on(async move {
+            if store
+                .fetch_record(CATEGORY_TXN_TEMPLATE, &self.name, false)
+                .await?
+                .is_some()
+            {
+                return Err(CliError::Duplicate(format!(
+                    "Template \"{}\" already exists.",
+                    self.name
+                )));
+            }
+            store
+                .store_record(CATEGORY_TXN_TEMPLATE, &self.name, &value, None, true)
+                .await
+        })
+    }
+
+    pub fn get(store: &Wallet, name: &str) -> CliResult<TransactionTemplate> {
+        block_on(async move {
+            let entry = store
+                .fetch_record(CATEGORY_TXN_TEMPLATE, name, false)
+                .await?
+                .ok_or_else(|| CliError::NotFound(format!("Template \"{}\" not found.", name)))?;
+            serde_json::from_slice(&entry.value).map_err(CliError::from)
+        })
+    }
+
+    pub fn list(store: &Wallet) -> CliResult<Vec<TransactionTemplate>> {
+        block_on(async move {
+            store
+                .fetch_all_records(CATEGORY_TXN_TEMPLATE)
+                .await?
+                .iter()
+                .map(|entry| serde_json::from_slice(&entry.value).map_err(CliError::from))
+                .collect()
+        })
+    }
+}
+
+// Replace the values of the field at any depth. Returns `false` if the field is not found.
+fn replace_field(value: &mut JsonValue, field: &str, new_value: &JsonValue) -> bool {
+    match value {
+        JsonValue::Object(map) => {
+            let mut found = false;
+            for (key, value) in map.iter_mut() {
+                if key == field {
+                    *value = new_value.clone();
+                    found = true;
+                } else {
+                    found |= replace_field(value, field, new_value);
+                }
+            }
+            found
+        }
+        JsonValue::Array(items) => items.iter_mut().fold(false, |found, item| {
+            replace_field(item, field, new_value) || found
+        }),
+        _ => false,
+    }
+}
@@ -0,0 +1,223 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::{
+    error::{CliError, CliResult},
+    tools::wallet::{
+        uri::{StorageType, WalletUri},
+        wallet_config::WalletConfig,
+    },
+    utils::environment::EnvironmentUtils,
+};
+
+use indy_utils::base64;
+use serde_json::Value as JsonValue;
+
+/// Aries agent frameworks the wallet configuration can be exported for.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AgentFramework {
+    AcaPy,
+    Afj,
+}
+
+impl AgentFramework {
+    pub fn from_str(framework: &str) -> CliResult<AgentFramework> {
+        match framework.to_lowercase().as_str() {
+            "acapy" | "aca-py" => Ok(AgentFramework::AcaPy),
+            "afj" | "credo" => Ok(AgentFramework::Afj),
+            _ => Err(CliError::InvalidInput(format!(
+                "Unsupported agent framework \"{}\". One of: acapy, afj",
+                framework
+            ))),
+        }
+    }
+}
+
+/// Settings of the opened wallet and the pool an agent needs to start with the identities provisioned by CLI.
+pub struct AgentConfig<'a> {
+    pub wallet: &'a WalletConfig,
+    pub key: &'a str,
+    pub key_derivation_method: &'a str,
+    pub storage_credentials: Option<&'a JsonValue>,
+    pub pool: Option<(&'a str, &'a str)>,
+    pub seed: Option<&'a [u8]>,
+}
+
+impl<'a> AgentConfig<'a> {
+    /// ACA-Py reads the configuration as YAML passed by `--arg-file`, AFJ as JSON agent config.
+    pub fn render(&self, framework: AgentFramework) -> CliResult<String> {
+        match framework {
+            AgentFramework::AcaPy => serde_yaml::to_string(&self.acapy()?)
+                .map_err(|err| CliError::InvalidEntityState(err.to_string())),
+            AgentFramework::Afj => {
+                serde_json::to_string_pretty(&self.afj()?).map_err(CliError::from)
+            }
+        }
+    }
+
+    fn acapy(&self) -> CliResult<JsonValue> {
+        let mut config = json!({
+            "wallet-type": "askar",
+            "wallet-name": self.wallet.id,
+            "wallet-key": self.key,
+            "wallet-key-derivation-method": self.derivation_method()?,
+        });
+        match self.storage_type()? {
+            StorageType::Sqlite => {
+                config["wallet-storage-type"] = json!("default");
+                // ACA-Py takes the directory and opens `sqlite.db` file in it
+                let directory = EnvironmentUtils::wallet_path(&self.wallet.id);
+                config["wallet-storage-config"] =
+                    json!(json!({ "path": directory.to_string_lossy() }).to_string());
+            }
+            StorageType::Postgres => {
+                config["wallet-storage-type"] = json!("postgres_storage");
+                config["wallet-storage-config"] = json!(self.postgres_config()?.to_string());
+                if let Some(credentials) = self.storage_credentials {
+                    config["wallet-storage-creds"] = json!(credentials.to_string());
+                }
+            }
+            StorageType::Memory => return Err(Self::memory_wallet_error()),
+        }
+        if let Some((_, genesis)) = self.pool {
+            config["genesis-file"] = json!(genesis);
+        }
+        if let Some(seed) = self.seed {
+            config["seed"] = json!(base64::encode(seed));
+        }
+        Ok(config)
+    }
+
+    fn afj(&self) -> CliResult<JsonValue> {
+        let storage = match self.storage_type()? {
+            StorageType::Sqlite => json!({
+                "type": "sqlite",
+                "config": { "path": self.sqlite_path() },
+            }),
+            StorageType::Postgres => json!({
+                "type": "postgres",
+                "config": self.postgres_config()?,
+                "credentials": self.storage_credentials,
+            }),
+            StorageType::Memory => return Err(Self::memory_wallet_error()),
+        };
+        let mut config = json!({
+            "label": self.wallet.id,
+            "walletConfig": {
+                "id": self.wallet.id,
+                "key": self.key,
+                "keyDerivationMethod": self.derivation_method()?,
+                "storage": storage,
+            },
+        });
+        if let Some((name, genesis)) = self.pool {
+            config["ledgers"] = json!([{
+                "id": name,
+                "indyNamespace": name,
+                "genesisPath": genesis,
+                "isProduction": false,
+            }]);
+        }
+        if let Some(seed) = self.seed {
+            // AFJ takes the seed as 32 characters string only
+            let seed = std::str::from_utf8(seed).map_err(|_| {
+                CliError::InvalidEntityState(
+                    "DID seed is not a printable string and cannot be passed to AFJ".to_string(),
+                )
+            })?;
+            config["publicDidSeed"] = json!(seed);
+        }
+        Ok(config)
+    }
+
+    fn memory_wallet_error() -> CliError {
+        CliError::InvalidEntityState(
+            "Memory wallets exist in the CLI process only and cannot be used by an agent"
+                .to_string(),
+        )
+    }
+
+    fn storage_type(&self) -> CliResult<StorageType> {
+        WalletUri::map_storage_type(&self.wallet.storage_type)
+    }
+
+    fn sqlite_path(&self) -> String {
+        WalletUri::sqlite_path(&self.wallet.id)
+            .to_string_lossy()
+            .to_string()
+    }
+
+    fn postgres_config(&self) -> CliResult<JsonValue> {
+        self.wallet.storage_config.clone().ok_or_else(|| {
+            CliError::InvalidEntityState("No 'storage_config' of postgres store".to_string())
+        })
+    }
+
+    // Both frameworks use the Askar names of the key derivation methods
+    fn derivation_method(&self) -> CliResult<&'static str> {
+        match self.key_derivation_method {
+            "argon2m" => Ok("ARGON2I_MOD"),
+            "argon2i" => Ok("ARGON2I_INT"),
+            "raw" => Ok("RAW"),
+            method => Err(CliError::InvalidInput(format!(
+                "Unsupported key derivation method \"{}\"",
+                method
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wallet_config() -> WalletConfig {
+        WalletConfig {
+            id: "agent".to_string(),
+            storage_type: "default".to_string(),
+            storage_config: None,
+        }
+    }
+
+    #[test]
+    fn render_works_for_acapy() {
+        let wallet = wallet_config();
+        let config = AgentConfig {
+            wallet: &wallet,
+            key: "key",
+            key_derivation_method: "argon2m",
+            storage_credentials: None,
+            pool: Some(("pool", "/tmp/genesis.txn")),
+            seed: Some(b"000000000000000000000000Trustee1"),
+        };
+        let config: JsonValue =
+            serde_yaml::from_str(&config.render(AgentFramework::AcaPy).unwrap()).unwrap();
+        assert_eq!("agent", config["wallet-name"]);
+        assert_eq!("ARGON2I_MOD", config["wallet-key-derivation-method"]);
+        assert_eq!("/tmp/genesis.txn", config["genesis-file"]);
+        assert_eq!(
+            "MDAwMDAwMDAwMDAwMDAwMDAwMDAwMDAwVHJ1c3RlZTE=",
+            config["seed"]
+        );
+    }
+
+    #[test]
+    fn render_works_for_afj() {
+        let wallet = wallet_config();
+        let config = AgentConfig {
+            wallet: &wallet,
+            key: "key",
+            key_derivation_method: "raw",
+            storage_credentials: None,
+            pool: None,
+            seed: None,
+        };
+        let config: JsonValue =
+            serde_json::from_str(&config.render(AgentFramework::Afj).unwrap()).unwrap();
+        assert_eq!("RAW", config["walletConfig"]["keyDerivationMethod"]);
+        assert_eq!("sqlite", config["walletConfig"]["storage"]["type"]);
+        assert!(config["ledgers"].is_null());
+        assert!(config["publicDidSeed"].is_null());
+    }
+}
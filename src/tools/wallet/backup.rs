@@ -2,8 +2,18 @@
     Copyright © 2023 Province of British Columbia
     https://digital.gov.bc.ca/digital-trust
 */
-use crate::error::{CliError, CliResult};
-use std::{ffi::OsStr, fs, path::PathBuf};
+use crate::{
+    error::{CliError, CliResult},
+    tools::wallet::Wallet,
+    utils::futures::block_on,
+};
+
+use chrono::Utc;
+use std::{
+    ffi::OsStr,
+    fs,
+    path::{Path, PathBuf},
+};
 
 pub struct WalletBackup {
     path: PathBuf,
@@ -16,6 +26,15 @@ pub enum BackupKind {
 }
 
 pub const DEFAULT_BACKUP_NAME: &'static str = "backup";
+pub const CATEGORY_WALLET_BACKUP: &str = "wallet_backup";
+
+/// Location of an export made from the wallet, kept in the wallet to report where its backups are.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupRecord {
+    pub path: String,
+    pub key_derivation_method: String,
+    pub created: i64,
+}
 
 impl WalletBackup {
     pub fn from_file(path: &str) -> CliResult<Self> {
@@ -65,3 +84,44 @@ impl WalletBackup {
         }
     }
 }
+
+impl BackupRecord {
+    pub fn new(path: &str, key_derivation_method: &str) -> BackupRecord {
+        BackupRecord {
+            path: path.to_string(),
+            key_derivation_method: key_derivation_method.to_string(),
+            created: Utc::now().timestamp(),
+        }
+    }
+
+    pub fn exists(&self) -> bool {
+        Path::new(&self.path).exists()
+    }
+
+    /// Store the record, an export to the same path replaces the previous one.
+    pub fn store(&self, wallet: &Wallet) -> CliResult<()> {
+        let value = serde_json::to_vec(self)?;
+        block_on(async move {
+            let new = wallet
+                .fetch_record(CATEGORY_WALLET_BACKUP, &self.path, false)
+                .await?
+                .is_none();
+            wallet
+                .store_record(CATEGORY_WALLET_BACKUP, &self.path, &value, None, new)
+                .await
+        })
+    }
+
+    pub fn list(wallet: &Wallet) -> CliResult<Vec<BackupRecord>> {
+        block_on(async move {
+            let mut records = wallet
+                .fetch_all_records(CATEGORY_WALLET_BACKUP)
+                .await?
+                .iter()
+                .map(|entry| serde_json::from_slice(&entry.value).map_err(CliError::from))
+                .collect::<CliResult<Vec<BackupRecord>>>()?;
+            records.sort_by(|record, other| record.created.cmp(&other.created));
+            Ok(records)
+        })
+    }
+}
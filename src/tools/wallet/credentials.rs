@@ -4,7 +4,10 @@
 */
 use crate::{
     error::{CliError, CliResult},
-    tools::wallet::Credentials,
+    tools::{
+        crypto_policy::CryptoPolicy,
+        wallet::{Credentials, DEFAULT_KEY_DERIVATION_METHOD},
+    },
 };
 
 use aries_askar::{Argon2Level, KdfMethod, PassKey, StoreKeyMethod};
@@ -17,19 +20,19 @@ pub struct WalletCredentials<'a> {
 }
 
 impl<'a> WalletCredentials<'a> {
-    pub fn build(credentials: &Credentials) -> CliResult<WalletCredentials> {
+    pub fn build(credentials: &Credentials, policy: CryptoPolicy) -> CliResult<WalletCredentials> {
         let key_method = Self::map_key_derivation_method(
             credentials
                 .key_derivation_method
                 .as_ref()
                 .map(String::as_str),
         )?;
-        let key = PassKey::from(credentials.key.to_string());
+        let key = PassKey::from(credentials.key.as_str());
 
         let rekey = credentials
             .rekey
             .as_ref()
-            .map(|rekey| PassKey::from(rekey.to_string()));
+            .map(|rekey| PassKey::from(rekey.as_str()));
 
         let rekey_method = match credentials.rekey {
             Some(_) => Some(Self::map_key_derivation_method(
@@ -37,6 +40,7 @@ impl<'a> WalletCredentials<'a> {
                     .rekey_derivation_method
                     .as_ref()
                     .map(String::as_str),
+                policy,
             )?),
             None => None,
         };
@@ -49,7 +53,11 @@ impl<'a> WalletCredentials<'a> {
         })
     }
 
-    fn map_key_derivation_method(key: Option<&str>) -> CliResult<StoreKeyMethod> {
+    pub fn map_key_derivation_method(
+        key: Option<&str>,
+        policy: CryptoPolicy,
+    ) -> CliResult<StoreKeyMethod> {
+        policy.check_key_derivation_method(key.unwrap_or(DEFAULT_KEY_DERIVATION_METHOD))?;
         match key {
             None | Some("argon2m") => Ok(StoreKeyMethod::DeriveKey(KdfMethod::Argon2i(
                 Argon2Level::Moderate,
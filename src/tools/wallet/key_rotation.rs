@@ -0,0 +1,120 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::{
+    error::{CliError, CliResult},
+    tools::wallet::Wallet,
+    utils::futures::block_on,
+};
+
+use chrono::Utc;
+
+pub const CATEGORY_WALLET_KEY_ROTATION: &str = "wallet_key_rotation";
+// The wallet keeps a single record replaced on every rotation
+pub(crate) const KEY_ROTATION_RECORD_ID: &str = "latest";
+const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
+/// Time the wallet key has been set, on wallet creation or rekey.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KeyRotationRecord {
+    pub key_derivation_method: String,
+    pub rotated: i64,
+}
+
+impl KeyRotationRecord {
+    pub fn new(key_derivation_method: &str) -> KeyRotationRecord {
+        KeyRotationRecord {
+            key_derivation_method: key_derivation_method.to_string(),
+            rotated: Utc::now().timestamp(),
+        }
+    }
+
+    pub fn to_bytes(&self) -> CliResult<Vec<u8>> {
+        serde_json::to_vec(self).map_err(CliError::from)
+    }
+
+    pub fn store(&self, wallet: &Wallet) -> CliResult<()> {
+        let value = self.to_bytes()?;
+        block_on(async move {
+            let new = wallet
+                .fetch_record(CATEGORY_WALLET_KEY_ROTATION, KEY_ROTATION_RECORD_ID, false)
+                .await?
+                .is_none();
+            wallet
+                .store_record(
+                    CATEGORY_WALLET_KEY_ROTATION,
+                    KEY_ROTATION_RECORD_ID,
+                    &value,
+                    None,
+                    new,
+                )
+                .await
+        })
+    }
+
+    /// The latest rotation. `None` for wallets created before rotations were recorded.
+    pub fn get(wallet: &Wallet) -> CliResult<Option<KeyRotationRecord>> {
+        block_on(async move {
+            wallet
+                .fetch_record(CATEGORY_WALLET_KEY_ROTATION, KEY_ROTATION_RECORD_ID, false)
+                .await?
+                .map(|entry| serde_json::from_slice(&entry.value).map_err(CliError::from))
+                .transpose()
+        })
+    }
+
+    pub fn age_days(&self, now: i64) -> i64 {
+        (now - self.rotated) / SECONDS_PER_DAY
+    }
+}
+
+/// Maximum age of the wallet key. With `enforce` the wallet cannot sign ledger writes once the key is too old.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct KeyRotationPolicy {
+    pub max_age_days: u64,
+    pub enforce: bool,
+}
+
+impl KeyRotationPolicy {
+    /// Reason to rotate the key, if the policy requires it.
+    /// Keys of unknown age are due as the policy cannot be proven to be met.
+    pub fn check(&self, record: Option<&KeyRotationRecord>, now: i64) -> Option<String> {
+        match record {
+            None => Some("the key age is unknown as no rotation has been recorded".to_string()),
+            Some(record) if record.age_days(now) > self.max_age_days as i64 => Some(format!(
+                "the key is {} days old, the policy allows {} days",
+                record.age_days(now),
+                self.max_age_days
+            )),
+            Some(_) => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: i64 = 1_700_000_000;
+
+    #[test]
+    fn key_rotation_policy_check_works() {
+        let policy = KeyRotationPolicy {
+            max_age_days: 90,
+            enforce: true,
+        };
+        let mut record = KeyRotationRecord {
+            key_derivation_method: "argon2m".to_string(),
+            rotated: NOW - 90 * SECONDS_PER_DAY,
+        };
+        assert_eq!(None, policy.check(Some(&record), NOW));
+
+        record.rotated = NOW - 91 * SECONDS_PER_DAY;
+        assert!(policy
+            .check(Some(&record), NOW)
+            .unwrap()
+            .contains("91 days"));
+        assert!(policy.check(None, NOW).is_some());
+    }
+}
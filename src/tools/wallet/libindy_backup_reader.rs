@@ -78,10 +78,10 @@ impl LibindyBackupReader {
 
         let mut record = vec![0u8; record_len];
         self.reader.read_exact(&mut record).map_err(|err| {
-            println_err!("{:?}", err);
-            CliError::InvalidInput(
-                "Invalid backup content: Unable to read backup record1".to_string(),
-            )
+            CliError::InvalidInput(format!(
+                "Invalid backup content: Unable to read backup record: {}",
+                err
+            ))
         })?;
 
         let record: BackupRecord = rmp_serde::from_slice(&record).map_err(|_| {
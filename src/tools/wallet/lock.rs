@@ -0,0 +1,273 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::{
+    error::{CliError, CliResult},
+    utils::environment::EnvironmentUtils,
+};
+
+use chrono::Utc;
+use std::{
+    collections::BTreeSet,
+    env, fs,
+    fs::OpenOptions,
+    io::{ErrorKind, Write},
+    path::{Path, PathBuf},
+    process,
+    sync::{Mutex, PoisonError},
+    thread,
+    time::Duration,
+};
+
+// Lock files held by this process. A lock file of the current process missing here
+// is left by a crashed process which had the same pid.
+static HELD_LOCKS: Mutex<BTreeSet<PathBuf>> = Mutex::new(BTreeSet::new());
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LockInfo {
+    pub pid: u32,
+    pub host: String,
+    pub opened_at: String,
+}
+
+impl LockInfo {
+    fn current() -> LockInfo {
+        LockInfo {
+            pid: process::id(),
+            host: hostname(),
+            opened_at: Utc::now().format("%Y-%m-%d %H:%M:%S UTC").to_string(),
+        }
+    }
+
+    fn is_current_process(&self) -> bool {
+        self.pid == process::id() && self.host == hostname()
+    }
+
+    // A process crashed without removing its lock file leaves a stale lock behind.
+    // The owner liveness can be checked only for locks taken on this host.
+    fn is_stale(&self) -> bool {
+        self.host == hostname() && !is_process_alive(self.pid)
+    }
+}
+
+/// Lock metadata file stored next to the wallet data while the wallet is opened by CLI.
+#[derive(Debug)]
+pub struct WalletLock {
+    path: PathBuf,
+}
+
+impl WalletLock {
+    /// Take the lock of an existing wallet. The wallet directory is never created here.
+    pub fn acquire(id: &str) -> CliResult<WalletLock> {
+        let path = Self::path(id);
+        if !path.parent().map(|parent| parent.is_dir()).unwrap_or(false) {
+            return Err(CliError::NotFound(format!(
+                "Wallet \"{}\" not found or unavailable.",
+                id
+            )));
+        }
+
+        // The file is created exclusively so that two processes cannot take the lock at once.
+        // A stale lock is removed and the creation is attempted once more.
+        for _ in 0..2 {
+            match OpenOptions::new().write(true).create_new(true).open(&path) {
+                Ok(mut file) => {
+                    let lock = WalletLock::hold(path);
+                    file.write_all(json!(LockInfo::current()).to_string().as_bytes())?;
+                    return Ok(lock);
+                }
+                Err(err) if err.kind() == ErrorKind::AlreadyExists => match Self::read_owner(id) {
+                    // the second lock would remove the file of the first one on drop
+                    Some(info) if info.is_current_process() && Self::is_held(&path) => {
+                        return Err(CliError::Locked(format!(
+                            "Wallet \"{}\" is already opened by this CLI.",
+                            id
+                        )))
+                    }
+                    Some(info) if !info.is_current_process() && !info.is_stale() => {
+                        return Err(Self::locked_error(id, &info))
+                    }
+                    _ => {
+                        fs::remove_file(&path).ok();
+                    }
+                },
+                Err(err) => return Err(err.into()),
+            }
+        }
+
+        match Self::read(id) {
+            Some(info) => Err(Self::locked_error(id, &info)),
+            None => Err(CliError::Locked(format!(
+                "Wallet \"{}\" is being locked by another process.",
+                id
+            ))),
+        }
+    }
+
+    fn hold(path: PathBuf) -> WalletLock {
+        HELD_LOCKS
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(path.clone());
+        WalletLock { path }
+    }
+
+    fn is_held(path: &Path) -> bool {
+        HELD_LOCKS
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .contains(path)
+    }
+
+    // The owner may have created the file but not written it yet
+    fn read_owner(id: &str) -> Option<LockInfo> {
+        Self::read(id).or_else(|| {
+            thread::sleep(Duration::from_millis(100));
+            Self::read(id)
+        })
+    }
+
+    pub fn read(id: &str) -> Option<LockInfo> {
+        let content = fs::read_to_string(Self::path(id)).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    pub fn locked_error(id: &str, info: &LockInfo) -> CliError {
+        CliError::Locked(format!(
+            "Wallet \"{}\" is locked by process {} on host \"{}\" (opened at {}). \
+            Remove \"{}\" file if the process does not exist anymore.",
+            id,
+            info.pid,
+            info.host,
+            info.opened_at,
+            Self::path(id).to_string_lossy()
+        ))
+    }
+
+    pub fn path(id: &str) -> PathBuf {
+        EnvironmentUtils::wallet_path(id).join("cli.lock")
+    }
+}
+
+impl Drop for WalletLock {
+    fn drop(&mut self) {
+        fs::remove_file(&self.path).ok();
+        HELD_LOCKS
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(&self.path);
+    }
+}
+
+fn hostname() -> String {
+    env::var("HOSTNAME")
+        .or_else(|_| env::var("COMPUTERNAME"))
+        .ok()
+        .or_else(|| fs::read_to_string("/etc/hostname").ok())
+        .map(|host| host.trim().to_string())
+        .filter(|host| !host.is_empty())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+#[cfg(unix)]
+fn is_process_alive(pid: u32) -> bool {
+    // 0 and negative values address process groups
+    let pid = match libc::pid_t::try_from(pid) {
+        Ok(pid) if pid > 0 => pid,
+        _ => return false,
+    };
+    // Signal 0 only checks that the process exists; EPERM means it belongs to another user
+    if unsafe { libc::kill(pid, 0) } == 0 {
+        return true;
+    }
+    std::io::Error::last_os_error().raw_os_error() == Some(libc::EPERM)
+}
+
+#[cfg(windows)]
+fn is_process_alive(pid: u32) -> bool {
+    use winapi::{
+        shared::{minwindef::FALSE, winerror::ERROR_ACCESS_DENIED},
+        um::{
+            errhandlingapi::GetLastError,
+            handleapi::CloseHandle,
+            minwinbase::STILL_ACTIVE,
+            processthreadsapi::{GetExitCodeProcess, OpenProcess},
+            winnt::PROCESS_QUERY_LIMITED_INFORMATION,
+        },
+    };
+
+    unsafe {
+        let handle = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, FALSE, pid);
+        if handle.is_null() {
+            return GetLastError() == ERROR_ACCESS_DENIED;
+        }
+        let mut exit_code = 0;
+        let alive = GetExitCodeProcess(handle, &mut exit_code) != 0 && exit_code == STILL_ACTIVE;
+        CloseHandle(handle);
+        alive
+    }
+}
+
+#[cfg(not(any(unix, windows)))]
+fn is_process_alive(_pid: u32) -> bool {
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WALLET: &str = "lock_test_wallet";
+
+    fn write_lock(pid: u32, host: &str) {
+        fs::write(
+            WalletLock::path(WALLET),
+            json!({"pid": pid, "host": host, "openedAt": "2023-01-01 00:00:00 UTC"}).to_string(),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn is_process_alive_works() {
+        assert!(is_process_alive(process::id()));
+        assert!(!is_process_alive(0));
+        assert!(!is_process_alive(u32::MAX));
+    }
+
+    #[test]
+    fn acquire_works() {
+        let dir = EnvironmentUtils::wallet_path(WALLET);
+        fs::remove_dir_all(&dir).ok();
+
+        // the directory of not existing wallet is not created
+        WalletLock::acquire(WALLET).unwrap_err();
+        assert!(!dir.exists());
+
+        fs::create_dir_all(&dir).unwrap();
+        {
+            let _lock = WalletLock::acquire(WALLET).unwrap();
+            assert!(WalletLock::read(WALLET).unwrap().is_current_process());
+
+            // the lock is not taken twice by the same process
+            WalletLock::acquire(WALLET).unwrap_err();
+            assert!(WalletLock::read(WALLET).is_some());
+        }
+        assert!(WalletLock::read(WALLET).is_none());
+
+        // lock of a crashed process with the same pid is taken over
+        write_lock(process::id(), &hostname());
+        WalletLock::acquire(WALLET).unwrap();
+
+        // stale lock of this host is taken over
+        write_lock(u32::MAX, &hostname());
+        WalletLock::acquire(WALLET).unwrap();
+
+        // lock of another host is kept
+        write_lock(1, "other-host");
+        WalletLock::acquire(WALLET).unwrap_err();
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,80 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::error::{CliError, CliResult};
+
+use aries_askar::any::AnyStore;
+use std::{cell::RefCell, collections::BTreeMap};
+use zeroize::Zeroize;
+
+/// Askar SQLite store without a file: every provisioned store is a separate database which lives until it is closed.
+pub const MEMORY_STORE_URI: &str = "sqlite://:memory:";
+
+thread_local! {
+    // Memory wallets created in this process by name. They vanish with the process.
+    static MEMORY_WALLETS: RefCell<BTreeMap<String, MemoryWallet>> = RefCell::new(BTreeMap::new());
+}
+
+struct MemoryWallet {
+    store: AnyStore,
+    // The store stays opened between `wallet open` calls, so the key is checked by CLI
+    key: String,
+    key_derivation_method: String,
+}
+
+impl Drop for MemoryWallet {
+    fn drop(&mut self) {
+        self.key.zeroize();
+    }
+}
+
+pub struct MemoryWallets;
+
+impl MemoryWallets {
+    pub fn register(id: &str, store: AnyStore, key: &str, key_derivation_method: &str) {
+        MEMORY_WALLETS.with(|wallets| {
+            wallets.borrow_mut().insert(
+                id.to_string(),
+                MemoryWallet {
+                    store,
+                    key: key.to_string(),
+                    key_derivation_method: key_derivation_method.to_string(),
+                },
+            )
+        });
+    }
+
+    pub fn contains(id: &str) -> bool {
+        MEMORY_WALLETS.with(|wallets| wallets.borrow().contains_key(id))
+    }
+
+    pub fn open(id: &str, key: &str, key_derivation_method: &str) -> CliResult<AnyStore> {
+        MEMORY_WALLETS.with(|wallets| {
+            let wallets = wallets.borrow();
+            let wallet = wallets.get(id).ok_or_else(|| {
+                CliError::NotFound(format!("Wallet \"{}\" not found or unavailable.", id))
+            })?;
+            if wallet.key != key || wallet.key_derivation_method != key_derivation_method {
+                return Err(CliError::InvalidInput(format!(
+                    "Invalid key provided for the wallet \"{}\"",
+                    id
+                )));
+            }
+            Ok(wallet.store.clone())
+        })
+    }
+
+    pub fn remove(id: &str) -> Option<AnyStore> {
+        MEMORY_WALLETS.with(|wallets| {
+            wallets
+                .borrow_mut()
+                .remove(id)
+                .map(|wallet| wallet.store.clone())
+        })
+    }
+
+    pub fn list() -> Vec<String> {
+        MEMORY_WALLETS.with(|wallets| wallets.borrow().keys().cloned().collect())
+    }
+}
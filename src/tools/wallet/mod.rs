@@ -2,20 +2,37 @@
     Copyright © 2023 Province of British Columbia
     https://digital.gov.bc.ca/digital-trust
 */
+pub mod agent_config;
 pub mod backup;
 mod credentials;
+pub mod key_rotation;
 pub mod libindy_backup_reader;
+pub mod lock;
+pub mod memory;
+pub mod resume;
+pub mod security;
+pub mod session_pool;
+pub mod universal;
 mod uri;
 pub mod wallet_config;
 
 use crate::{
     error::{CliError, CliResult},
-    tools::did::constants::CATEGORY_DID,
-    utils::futures::block_on,
+    tools::{
+        crypto_policy::CryptoPolicy,
+        did::constants::{CATEGORY_DID, CATEGORY_NYM_ALIAS},
+        template::CATEGORY_TXN_TEMPLATE,
+    },
+    utils::{futures::block_on, progress::ProgressBar},
 };
 
 use self::{
     credentials::WalletCredentials,
+    key_rotation::{KeyRotationRecord, CATEGORY_WALLET_KEY_ROTATION},
+    lock::WalletLock,
+    memory::MemoryWallets,
+    resume::{RecordCounts, ResumeMarker, PAGE_SIZE},
+    session_pool::{PooledSession, SessionPool, MAX_WALLET_SESSIONS},
     uri::{StorageType, WalletUri},
 };
 
@@ -25,23 +42,58 @@ use crate::tools::{
     wallet::{
         backup::BackupKind,
         libindy_backup_reader::{
-            DidMetadataRecord, DidRecord, KeyRecord, LibindyBackupReader, TemporaryDidRecord,
+            BackupRecord, DidMetadataRecord, DidRecord, KeyRecord, LibindyBackupReader,
+            TemporaryDidRecord,
         },
     },
 };
+use crate::utils::environment::EnvironmentUtils;
 use aries_askar::{
-    any::AnyStore,
+    any::{AnySession, AnyStore},
     kms::{KeyAlg, LocalKey},
-    Entry, EntryTag, Error as AskarError, ErrorKind as AskarErrorKind, ManageBackend,
+    migration::IndySdkToAriesAskarMigration,
+    Entry, EntryTag, Error as AskarError, ErrorKind as AskarErrorKind, ManageBackend, PassKey,
 };
 use backup::WalletBackup;
 use serde_json::Value as JsonValue;
+use std::{
+    collections::BTreeMap,
+    fs::{self, File},
+    io::Read,
+    path::{Path, PathBuf},
+};
 use wallet_config::{WalletConfig, WalletDirectory};
+use zeroize::Zeroize;
+
+const SQLITE_HEADER: &[u8; 16] = b"SQLite format 3\0";
+// Storage file of wallets created by libindy
+const LEGACY_STORAGE_FILE: &str = "sqlite.db";
+// Files SQLite keeps next to the database: records not checkpointed yet live in the WAL
+const SQLITE_SIDE_FILES: [&str; 3] = ["-wal", "-shm", "-journal"];
+// Record categories transferred by export and import
+const RECORD_CATEGORIES: [&str; 3] = [CATEGORY_DID, CATEGORY_NYM_ALIAS, CATEGORY_TXN_TEMPLATE];
+// Name under which keys are reported as they are not stored as regular records
+const KEYS_CATEGORY: &str = "key";
+pub const DEFAULT_KEY_DERIVATION_METHOD: &str = "argon2m";
+// Resume marker of an interrupted import kept in the new wallet directory
+// Record categories of libindy wallets in the order they are migrated: DIDs are referred to by the others
+const LEGACY_RECORD_CATEGORIES: [&str; 4] = [
+    KeyRecord::TYPE,
+    DidRecord::TYPE,
+    TemporaryDidRecord::TYPE,
+    DidMetadataRecord::TYPE,
+];
 
 #[derive(Debug)]
 pub struct Wallet {
     pub name: String,
+    // Method of the key the store has been opened (or rekeyed) with
+    pub key_derivation_method: String,
+    // Declared before the store so that pooled sessions are closed first
+    sessions: SessionPool,
     pub store: AnyStore,
+    // Memory wallets have no directory to keep the lock file in
+    _lock: Option<WalletLock>,
 }
 
 #[derive(Debug, Default, Serialize, Deserialize)]
@@ -58,6 +110,9 @@ pub struct ExportConfig {
     pub path: String,
     pub key: String,
     pub key_derivation_method: Option<String>,
+    /// DIDs (as stored in the wallet) to export with their keys and aliases, the whole wallet if `None`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dids: Option<Vec<String>>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -65,10 +120,63 @@ pub struct ImportConfig {
     pub path: String,
     pub key: String,
     pub key_derivation_method: Option<String>,
+    /// Copy the records into the existing wallet instead of creating a new one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub merge: Option<ConflictPolicy>,
+}
+
+/// How a record of the backup is handled when the wallet it is merged into already has a record with the same name.
+/// Keys are named by their verkey, so an existing key is always kept.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ConflictPolicy {
+    /// Keep the record of the wallet
+    Skip,
+    /// Replace the record of the wallet with the one of the backup
+    Overwrite,
+    /// Do not import anything if any record conflicts
+    Fail,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MigrateConfig {
+    /// Directory of the libindy wallet or its `sqlite.db` file
+    pub path: String,
+    pub key: String,
+    pub key_derivation_method: Option<String>,
+}
+
+impl Drop for Credentials {
+    fn drop(&mut self) {
+        self.key.zeroize();
+        self.rekey.zeroize();
+    }
+}
+
+impl Drop for ExportConfig {
+    fn drop(&mut self) {
+        self.key.zeroize();
+    }
+}
+
+impl Drop for ImportConfig {
+    fn drop(&mut self) {
+        self.key.zeroize();
+    }
+}
+
+impl Drop for MigrateConfig {
+    fn drop(&mut self) {
+        self.key.zeroize();
+    }
 }
 
 impl Wallet {
-    pub fn create(config: &WalletConfig, credentials: &Credentials) -> CliResult<()> {
+    pub fn create(
+        config: &WalletConfig,
+        credentials: &Credentials,
+        policy: CryptoPolicy,
+    ) -> CliResult<()> {
         block_on(async move {
             if config.exists() {
                 return Err(CliError::Duplicate(format!(
@@ -78,7 +186,13 @@ impl Wallet {
             }
 
             let wallet_uri = WalletUri::build(config, credentials, None)?;
-            let credentials = WalletCredentials::build(credentials)?;
+            let key_derivation_method = credentials
+                .key_derivation_method
+                .as_deref()
+                .unwrap_or(DEFAULT_KEY_DERIVATION_METHOD);
+            let rotation = KeyRotationRecord::new(key_derivation_method);
+            let key = credentials.key.as_str();
+            let credentials = WalletCredentials::build(credentials, policy)?;
 
             config.create_path()?;
 
@@ -92,6 +206,24 @@ impl Wallet {
                 )
                 .await?;
 
+            let mut session = store.session(None).await?;
+            session
+                .insert(
+                    CATEGORY_WALLET_KEY_ROTATION,
+                    key_rotation::KEY_ROTATION_RECORD_ID,
+                    &rotation.to_bytes()?,
+                    None,
+                    None,
+                )
+                .await?;
+            session.commit().await?;
+
+            // the database of a memory store is dropped on close, so it is kept opened until the wallet is deleted
+            if config.is_memory() {
+                MemoryWallets::register(&config.id, store, key, key_derivation_method);
+                return Ok(());
+            }
+
             // Askar: If there is any opened store when delete the wallet, function returns ok and deletes wallet file successfully
             // But next if we create wallet with the same again it will contain old records
             // So we have to close all store handles
@@ -101,10 +233,40 @@ impl Wallet {
         })
     }
 
-    pub fn open(config: &WalletConfig, credentials: &Credentials) -> CliResult<Wallet> {
-        block_on(async move {
+    pub fn open(
+        config: &WalletConfig,
+        credentials: &Credentials,
+        policy: CryptoPolicy,
+    ) -> CliResult<Wallet> {
+        let rekeyed = credentials.rekey.is_some();
+        let wallet = block_on(async move {
+            let key_derivation_method = match credentials.rekey {
+                Some(_) => credentials.rekey_derivation_method.clone(),
+                None => credentials.key_derivation_method.clone(),
+            }
+            .unwrap_or_else(|| DEFAULT_KEY_DERIVATION_METHOD.to_string());
+
+            if config.is_memory() {
+                if credentials.rekey.is_some() {
+                    return Err(CliError::InvalidInput(
+                        "Memory wallets cannot be rekeyed".to_string(),
+                    ));
+                }
+                let store =
+                    MemoryWallets::open(&config.id, &credentials.key, &key_derivation_method)?;
+                return Ok(Wallet {
+                    sessions: SessionPool::new(MAX_WALLET_SESSIONS),
+                    store,
+                    name: config.id.to_string(),
+                    key_derivation_method,
+                    _lock: None,
+                });
+            }
+
             let wallet_uri = WalletUri::build(config, credentials, None)?;
-            let credentials = WalletCredentials::build(credentials)?;
+            let credentials = WalletCredentials::build(credentials, policy)?;
+
+            let lock = WalletLock::acquire(&config.id)?;
 
             let mut store: AnyStore = wallet_uri
                 .value()
@@ -115,6 +277,17 @@ impl Wallet {
                         "Wallet \"{}\" not found or unavailable.",
                         config.id
                     )),
+                    // Store locked by a process which does not use CLI lock file
+                    AskarErrorKind::Busy => CliError::Locked(format!(
+                        "Wallet \"{}\" storage is locked by another process.",
+                        config.id
+                    )),
+                    AskarErrorKind::Backend if err.to_string().contains("database is locked") => {
+                        CliError::Locked(format!(
+                            "Wallet \"{}\" storage is locked by another process.",
+                            config.id
+                        ))
+                    }
                     _ => CliError::from(err),
                 })?;
 
@@ -124,18 +297,82 @@ impl Wallet {
             }
 
             Ok(Wallet {
+                sessions: SessionPool::new(MAX_WALLET_SESSIONS),
                 store,
                 name: config.id.to_string(),
+                key_derivation_method,
+                _lock: Some(lock),
             })
-        })
+        })?;
+
+        if rekeyed {
+            KeyRotationRecord::new(&wallet.key_derivation_method).store(&wallet)?;
+        }
+        Ok(wallet)
+    }
+
+    /// Replace the key of the opened wallet and record the rotation.
+    pub fn rekey(
+        &mut self,
+        key: &str,
+        key_derivation_method: Option<&str>,
+        policy: CryptoPolicy,
+    ) -> CliResult<()> {
+        if MemoryWallets::contains(&self.name) {
+            return Err(CliError::InvalidInput(
+                "Memory wallets cannot be rekeyed".to_string(),
+            ));
+        }
+        let key_method =
+            WalletCredentials::map_key_derivation_method(key_derivation_method, policy)?;
+        block_on(async {
+            self.sessions.clear();
+            self.store
+                .rekey(key_method, PassKey::from(key))
+                .await
+                .map_err(CliError::from)
+        })?;
+        self.key_derivation_method = key_derivation_method
+            .unwrap_or(DEFAULT_KEY_DERIVATION_METHOD)
+            .to_string();
+        KeyRotationRecord::new(&self.key_derivation_method).store(self)
     }
 
     pub fn close(self) -> CliResult<()> {
-        block_on(async move { self.store.close().await.map_err(CliError::from) })
+        block_on(async move {
+            // pooled sessions release their connections within the runtime
+            self.sessions.clear();
+            // closing would drop the database of a memory wallet
+            if MemoryWallets::contains(&self.name) {
+                return Ok(());
+            }
+            self.store.close().await.map_err(CliError::from)
+        })
+    }
+
+    /// Borrow a session from the wallet session pool.
+    /// Sessions are not transactional: every operation is committed immediately.
+    pub async fn session(&self) -> CliResult<PooledSession<'_>> {
+        self.sessions.acquire(&self.store).await
     }
 
     pub fn delete(config: &WalletConfig, credentials: &Credentials) -> CliResult<()> {
         block_on(async move {
+            if config.is_memory() {
+                MemoryWallets::open(
+                    &config.id,
+                    &credentials.key,
+                    credentials
+                        .key_derivation_method
+                        .as_deref()
+                        .unwrap_or(DEFAULT_KEY_DERIVATION_METHOD),
+                )?;
+                if let Some(store) = MemoryWallets::remove(&config.id) {
+                    store.close().await?;
+                }
+                return Ok(());
+            }
+
             let wallet_uri = WalletUri::build(config, credentials, None)?;
 
             let removed = wallet_uri.value().remove_backend().await?;
@@ -154,7 +391,63 @@ impl Wallet {
         WalletDirectory::list_wallets()
     }
 
-    pub fn export(&self, export_config: &ExportConfig) -> CliResult<()> {
+    /// Check that the wallet storage is readable without opening it.
+    /// Returns `false` if the storage type cannot be checked locally (Postgres).
+    pub fn check_storage(id: &str) -> CliResult<bool> {
+        let config = WalletConfig::read(id).map_err(|err| {
+            CliError::InvalidEntityState(format!("Wallet config cannot be read: {}", err))
+        })?;
+
+        match WalletUri::map_storage_type(&config.storage_type)? {
+            StorageType::Postgres => Ok(false),
+            StorageType::Memory => Ok(MemoryWallets::contains(id)),
+            StorageType::Sqlite => {
+                let path = WalletUri::sqlite_path(id);
+                let mut header = [0u8; SQLITE_HEADER.len()];
+                File::open(&path)
+                    .and_then(|mut file| file.read_exact(&mut header))
+                    .map_err(|err| {
+                        CliError::InvalidEntityState(format!(
+                            "Storage file \"{}\" cannot be read: {}",
+                            path.display(),
+                            err
+                        ))
+                    })?;
+                if &header != SQLITE_HEADER {
+                    return Err(CliError::InvalidEntityState(format!(
+                        "Storage file \"{}\" is not an SQLite database",
+                        path.display()
+                    )));
+                }
+                Ok(true)
+            }
+        }
+    }
+
+    /// Names of the wallets created by the old Indy-CLI: they have no CLI config and cannot be opened.
+    pub fn list_legacy() -> Vec<String> {
+        let entries = match fs::read_dir(EnvironmentUtils::wallet_home_path()) {
+            Ok(entries) => entries,
+            Err(_) => return Vec::new(),
+        };
+
+        let mut names = entries
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.path().join(LEGACY_STORAGE_FILE).exists())
+            .filter_map(|entry| entry.file_name().to_str().map(String::from))
+            .filter(|name| !EnvironmentUtils::wallet_config_path(name).exists())
+            .collect::<Vec<String>>();
+        names.sort();
+        names
+    }
+
+    /// Copy the wallet records into the backup.
+    /// An interrupted export continues from the last committed page when it is run again with the same path.
+    pub fn export(
+        &self,
+        export_config: &ExportConfig,
+        policy: CryptoPolicy,
+    ) -> CliResult<RecordCounts> {
         block_on(async move {
             let backup = WalletBackup::from_file(&export_config.path)?;
 
@@ -166,7 +459,9 @@ impl Wallet {
             let backup_credentials = Credentials {
                 key: export_config.key.clone(),
                 key_derivation_method: export_config.key_derivation_method.clone(),
-                ..Credentials::default()
+                rekey: None,
+                rekey_derivation_method: None,
+                storage_credentials: None,
             };
 
             let backup_uri = WalletUri::build(
@@ -174,33 +469,71 @@ impl Wallet {
                 &backup_credentials,
                 Some(&export_config.path),
             )?;
-            let backup_credentials = WalletCredentials::build(&backup_credentials)?;
-
-            backup.init_dir()?;
-
-            let backup_store = backup_uri
-                .value()
-                .provision_backend(
-                    backup_credentials.key_method,
-                    backup_credentials.key.as_ref(),
-                    None,
-                    false,
-                )
-                .await?;
-
-            Self::copy_records_from_askar_store(&self.store, &backup_store).await?;
+            let backup_credentials = WalletCredentials::build(&backup_credentials, policy)?;
+
+            let marker_path = ResumeMarker::path_for(Path::new(&export_config.path));
+            let (backup_store, mut marker) =
+                match ResumeMarker::load(marker_path.clone(), &self.name)? {
+                    Some(marker) => {
+                        let backup_store = backup_uri
+                            .value()
+                            .open_backend(
+                                Some(backup_credentials.key_method),
+                                backup_credentials.key.as_ref(),
+                                None,
+                            )
+                            .await?;
+                        (backup_store, marker)
+                    }
+                    None => {
+                        backup.init_dir()?;
+
+                        let backup_store = backup_uri
+                            .value()
+                            .provision_backend(
+                                backup_credentials.key_method,
+                                backup_credentials.key.as_ref(),
+                                None,
+                                false,
+                            )
+                            .await?;
+                        let marker = ResumeMarker::new(marker_path, &self.name);
+                        marker.save()?;
+                        (backup_store, marker)
+                    }
+                };
+
+            match export_config.dids {
+                Some(ref dids) => {
+                    Self::copy_did_records(&self.store, &backup_store, dids, &mut marker).await?
+                }
+                None => {
+                    Self::copy_records_from_askar_store(
+                        &self.store,
+                        &backup_store,
+                        &mut marker,
+                        ConflictPolicy::Skip,
+                    )
+                    .await?
+                }
+            }
 
             backup_store.close().await?;
 
-            Ok(())
+            let counts = marker.counts();
+            marker.remove()?;
+            Ok(counts)
         })
     }
 
+    /// Create a new wallet and copy the backup records into it.
+    /// An interrupted import continues from the last committed page when it is run again for the same wallet.
     pub fn import(
         config: &WalletConfig,
         credentials: &Credentials,
         import_config: &ImportConfig,
-    ) -> CliResult<()> {
+        policy: CryptoPolicy,
+    ) -> CliResult<RecordCounts> {
         block_on(async move {
             let backup = WalletBackup::from_file(&import_config.path)?;
             if !backup.exists() {
@@ -210,19 +543,47 @@ impl Wallet {
                 )));
             }
 
-            if config.exists() {
-                return Err(CliError::Duplicate(format!(
-                    "Wallet \"{}\" already exists",
-                    config.id
-                )));
+            if config.is_memory() {
+                return Err(CliError::InvalidInput(
+                    "Memory wallets can be created by `wallet create` only".to_string(),
+                ));
+            }
+
+            match (import_config.merge, config.exists()) {
+                (None, true) => {
+                    return Err(CliError::Duplicate(format!(
+                        "Wallet \"{}\" already exists",
+                        config.id
+                    )))
+                }
+                (Some(_), false) => {
+                    return Err(CliError::NotFound(format!(
+                        "Wallet \"{}\" does not exist",
+                        config.id
+                    )))
+                }
+                _ => {}
             }
 
             match backup.kind()? {
+                BackupKind::Libindy if import_config.merge.is_some() => {
+                    Err(CliError::InvalidInput(
+                        "Libindy backups cannot be merged into an existing wallet. Import it as a new wallet first."
+                            .to_string(),
+                    ))
+                }
                 BackupKind::Askar => {
-                    Self::import_askar_backup(&backup, &config, &credentials, &import_config).await
+                    Self::import_askar_backup(&backup, &config, &credentials, &import_config, policy)
+                        .await
                 }
                 BackupKind::Libindy => {
-                    Self::import_libindy_backup(&backup, &config, &credentials, &import_config)
+                    Self::import_libindy_backup(
+                        &backup,
+                        &config,
+                        &credentials,
+                        &import_config,
+                        policy,
+                    )
                         .await
                 }
             }
@@ -234,7 +595,8 @@ impl Wallet {
         config: &WalletConfig,
         credentials: &Credentials,
         import_config: &ImportConfig,
-    ) -> CliResult<()> {
+        policy: CryptoPolicy,
+    ) -> CliResult<RecordCounts> {
         // prepare config and credentials for backup and new wallet
         let backup_config = WalletConfig {
             id: backup.id(),
@@ -244,7 +606,9 @@ impl Wallet {
         let backup_credentials = Credentials {
             key: import_config.key.clone(),
             key_derivation_method: import_config.key_derivation_method.clone(),
-            ..Credentials::default()
+            rekey: None,
+            rekey_derivation_method: None,
+            storage_credentials: None,
         };
 
         let backup_wallet_uri = WalletUri::build(
@@ -252,10 +616,10 @@ impl Wallet {
             &backup_credentials,
             Some(&import_config.path),
         )?;
-        let backup_wallet_credentials = WalletCredentials::build(&backup_credentials)?;
+        let backup_wallet_credentials = WalletCredentials::build(&backup_credentials, policy)?;
 
         let new_wallet_uri = WalletUri::build(&config, &credentials, None)?;
-        let new_wallet_credentials = WalletCredentials::build(&credentials)?;
+        let new_wallet_credentials = WalletCredentials::build(&credentials, policy)?;
 
         // open backup storage
         let backup_store: AnyStore = backup_wallet_uri
@@ -271,27 +635,48 @@ impl Wallet {
                 _ => CliError::from(err),
             })?;
 
-        // create directory for new wallet and provision it
-        config.create_path()?;
+        // the merged wallet must not be opened by another process meanwhile
+        let _lock = match import_config.merge {
+            Some(_) => Some(WalletLock::acquire(&config.id)?),
+            None => None,
+        };
 
-        let new_store = new_wallet_uri
-            .value()
-            .provision_backend(
-                new_wallet_credentials.key_method,
-                new_wallet_credentials.key.as_ref(),
-                None,
-                false,
-            )
-            .await?;
+        // create new wallet (open the existing one on merge) or open the one left by an interrupted import
+        let (new_store, mut marker) = Self::open_import_target(
+            config,
+            &new_wallet_credentials,
+            &new_wallet_uri,
+            &import_config.path,
+            import_config.merge.is_some(),
+        )
+        .await?;
+
+        // on resume the conflicts are the records copied before the interruption
+        if import_config.merge == Some(ConflictPolicy::Fail) && marker.total() == 0 {
+            if let Err(err) = Self::ensure_no_conflicts(&backup_store, &new_store, &config.id).await
+            {
+                // nothing has been copied, so there is nothing to resume
+                marker.remove()?;
+                return Err(err);
+            }
+        }
 
         // copy all records from the backup into the new wallet
-        Self::copy_records_from_askar_store(&backup_store, &new_store).await?;
+        Self::copy_records_from_askar_store(
+            &backup_store,
+            &new_store,
+            &mut marker,
+            import_config.merge.unwrap_or(ConflictPolicy::Skip),
+        )
+        .await?;
 
         // finish
         backup_store.close().await?;
         new_store.close().await?;
 
-        Ok(())
+        let counts = marker.counts();
+        marker.remove()?;
+        Ok(counts)
     }
 
     async fn import_libindy_backup(
@@ -299,45 +684,357 @@ impl Wallet {
         config: &WalletConfig,
         credentials: &Credentials,
         import_config: &ImportConfig,
-    ) -> CliResult<()> {
+        policy: CryptoPolicy,
+    ) -> CliResult<RecordCounts> {
         // prepare config and credentials for new wallet
         let new_wallet_uri = WalletUri::build(&config, &credentials, None)?;
-        let new_wallet_credentials = WalletCredentials::build(&credentials)?;
+        let new_wallet_credentials = WalletCredentials::build(&credentials, policy)?;
 
         // init libindy backup reader
+        policy.check_key_derivation_method(
+            import_config
+                .key_derivation_method
+                .as_deref()
+                .unwrap_or(DEFAULT_KEY_DERIVATION_METHOD),
+        )?;
         let mut backup_reader = LibindyBackupReader::init(import_config)?;
 
+        // create new wallet or open the one left by an interrupted import
+        let (new_store, mut marker) = Self::open_import_target(
+            config,
+            &new_wallet_credentials,
+            &new_wallet_uri,
+            &import_config.path,
+            false,
+        )
+        .await?;
+
+        // copy all records from the backup into the new wallet
+        Self::copy_records_from_libindy_backup(&mut backup_reader, &new_store, &mut marker).await?;
+
+        // finish
+        new_store.close().await?;
+
+        let counts = marker.counts();
+        marker.remove()?;
+        Ok(counts)
+    }
+
+    /// Create a new wallet from the SQLite wallet of libindy (the old Indy-CLI and indy-sdk based agents).
+    /// The legacy wallet is not changed: it is converted into the Askar format in a temporary copy first.
+    pub fn migrate(
+        config: &WalletConfig,
+        credentials: &Credentials,
+        migrate_config: &MigrateConfig,
+        policy: CryptoPolicy,
+    ) -> CliResult<RecordCounts> {
+        block_on(async move {
+            let path = Path::new(&migrate_config.path);
+            let legacy_path = if path.is_dir() {
+                path.join(LEGACY_STORAGE_FILE)
+            } else {
+                path.to_path_buf()
+            };
+            if config.is_memory() {
+                return Err(CliError::InvalidInput(
+                    "Memory wallets can be created by `wallet create` only".to_string(),
+                ));
+            }
+            if !legacy_path.exists() {
+                return Err(CliError::NotFound(format!(
+                    "Libindy wallet storage \"{}\" does not exist",
+                    legacy_path.display()
+                )));
+            }
+
+            // the wallet left by an interrupted migration is completed
+            let unfinished = ResumeMarker::path_in_wallet(&config.id).exists();
+            if config.exists() && !unfinished {
+                return Err(CliError::Duplicate(format!(
+                    "Wallet \"{}\" already exists",
+                    config.id
+                )));
+            }
+
+            // the copy is removed together with the directory when it is dropped
+            let copy_dir = MigrationDirectory::create(&config.id)?;
+            let copy_path = copy_dir.copy_path();
+            copy_sqlite_files(&legacy_path, &copy_path)?;
+            Self::migrate_legacy_copy(config, credentials, migrate_config, &copy_path, policy).await
+        })
+    }
+
+    async fn migrate_legacy_copy(
+        config: &WalletConfig,
+        credentials: &Credentials,
+        migrate_config: &MigrateConfig,
+        copy_path: &Path,
+        policy: CryptoPolicy,
+    ) -> CliResult<RecordCounts> {
+        let kdf_level = match migrate_config.key_derivation_method.as_deref() {
+            None | Some("argon2m") => "ARGON2I_MOD",
+            Some("argon2i") => "ARGON2I_INT",
+            Some("raw") => "RAW",
+            Some(value) => {
+                return Err(CliError::InvalidInput(format!(
+                    "Unsupported key derivation method \"{}\" provided for the libindy wallet.",
+                    value
+                )))
+            }
+        };
+        let legacy_uri = format!("sqlite://{}", copy_path.to_string_lossy());
+
+        IndySdkToAriesAskarMigration::connect(
+            &legacy_uri,
+            &config.id,
+            &migrate_config.key,
+            kdf_level,
+        )
+        .await?
+        .migrate()
+        .await
+        .map_err(|err| {
+            CliError::InvalidEntityState(format!(
+                "Libindy wallet cannot be converted (check the key and key derivation method): {}",
+                err
+            ))
+        })?;
+
+        let legacy_credentials = Credentials {
+            key: migrate_config.key.clone(),
+            key_derivation_method: migrate_config.key_derivation_method.clone(),
+            rekey: None,
+            rekey_derivation_method: None,
+            storage_credentials: None,
+        };
+        let legacy_wallet_credentials = WalletCredentials::build(&legacy_credentials, policy)?;
+        let legacy_store: AnyStore = legacy_uri
+            .as_str()
+            .open_backend(
+                Some(legacy_wallet_credentials.key_method),
+                legacy_wallet_credentials.key.as_ref(),
+                None,
+            )
+            .await?;
+
+        let new_wallet_uri = WalletUri::build(config, credentials, None)?;
+        let new_wallet_credentials = WalletCredentials::build(credentials, policy)?;
+
+        // create new wallet or open the one left by an interrupted migration
+        let (new_store, mut marker) = Self::open_import_target(
+            config,
+            &new_wallet_credentials,
+            &new_wallet_uri,
+            &migrate_config.path,
+            false,
+        )
+        .await?;
+
+        Self::copy_records_from_legacy_store(&legacy_store, &new_store, &mut marker).await?;
+
+        // finish
+        legacy_store.close().await?;
+        new_store.close().await?;
+
+        let counts = marker.counts();
+        marker.remove()?;
+        Ok(counts)
+    }
+
+    async fn open_import_target(
+        config: &WalletConfig,
+        credentials: &WalletCredentials,
+        uri: &WalletUri,
+        source: &str,
+        existing: bool,
+    ) -> CliResult<(AnyStore, ResumeMarker)> {
+        let marker_path = ResumeMarker::path_in_wallet(&config.id);
+
+        let marker = match ResumeMarker::load(marker_path.clone(), source)? {
+            None if existing => Some(ResumeMarker::new(marker_path.clone(), source)),
+            marker => marker,
+        };
+
+        if let Some(marker) = marker {
+            let store = uri
+                .value()
+                .open_backend(
+                    Some(credentials.key_method.clone()),
+                    credentials.key.as_ref(),
+                    None,
+                )
+                .await?;
+            marker.save()?;
+            return Ok((store, marker));
+        }
+
         // create directory for new wallet and provision it
         config.create_path()?;
 
-        let new_store = new_wallet_uri
+        let store = uri
             .value()
             .provision_backend(
-                new_wallet_credentials.key_method,
-                new_wallet_credentials.key.as_ref(),
+                credentials.key_method.clone(),
+                credentials.key.as_ref(),
                 None,
                 false,
             )
             .await?;
 
-        // copy all records from the backup into the new wallet
-        Self::copy_records_from_libindy_backup(&mut backup_reader, &new_store).await?;
+        let marker = ResumeMarker::new(marker_path, source);
+        marker.save()?;
+        Ok((store, marker))
+    }
 
-        // finish
-        new_store.close().await?;
+    // Records are copied page by page, every committed page is saved into the resume marker.
+    // Already copied records are skipped on resume and inserting of existing ones is ignored
+    // unless they are overwritten on merge.
+    async fn copy_records_from_askar_store(
+        from: &AnyStore,
+        to: &AnyStore,
+        marker: &mut ResumeMarker,
+        conflict: ConflictPolicy,
+    ) -> CliResult<()> {
+        for category in RECORD_CATEGORIES {
+            let copied = marker.copied(category);
+            let total = from.session(None).await?.count(category, None).await?;
+            let mut progress = ProgressBar::new(category, Some(total as usize), copied);
+
+            let mut scan = from
+                .scan(None, category.to_string(), None, Some(copied as i64), None)
+                .await?;
+
+            while let Some(entries) = scan.fetch_next().await? {
+                for page in entries.chunks(PAGE_SIZE) {
+                    let mut to_session = to.session(None).await?;
+                    for entry in page {
+                        let exists = conflict == ConflictPolicy::Overwrite
+                            && to_session
+                                .fetch(&entry.category, &entry.name, true)
+                                .await?
+                                .is_some();
+                        if exists {
+                            to_session
+                                .replace(
+                                    &entry.category,
+                                    &entry.name,
+                                    &entry.value,
+                                    Some(&entry.tags),
+                                    None,
+                                )
+                                .await?;
+                        } else {
+                            to_session
+                                .insert(
+                                    &entry.category,
+                                    &entry.name,
+                                    &entry.value,
+                                    Some(&entry.tags),
+                                    None,
+                                )
+                                .await
+                                .ok();
+                        }
+                    }
+                    to_session.commit().await?;
+
+                    marker.add(category, page.len());
+                    marker.save()?;
+                    progress.inc(page.len());
+                }
+            }
+            marker.add(category, 0);
+            progress.finish();
+        }
+
+        let key_entries = from
+            .session(None)
+            .await?
+            .fetch_all_keys(None, None, None, None, false)
+            .await?;
+        let copied = marker.copied(KEYS_CATEGORY).min(key_entries.len());
+        let mut progress = ProgressBar::new(KEYS_CATEGORY, Some(key_entries.len()), copied);
+
+        for page in key_entries[copied..].chunks(PAGE_SIZE) {
+            let mut to_session = to.session(None).await?;
+            for entry in page {
+                to_session
+                    .insert_key(
+                        entry.name(),
+                        &entry.load_local_key()?,
+                        entry.metadata(),
+                        None,
+                        None,
+                    )
+                    .await
+                    .ok();
+            }
+            to_session.commit().await?;
+
+            marker.add(KEYS_CATEGORY, page.len());
+            marker.save()?;
+            progress.inc(page.len());
+        }
+        marker.add(KEYS_CATEGORY, 0);
+        progress.finish();
 
         Ok(())
     }
 
-    async fn copy_records_from_askar_store(from: &AnyStore, to: &AnyStore) -> CliResult<()> {
+    // Records of the wallet having the same names as the records of the backup, listed before anything is copied
+    async fn ensure_no_conflicts(from: &AnyStore, to: &AnyStore, id: &str) -> CliResult<()> {
         let mut from_session = from.session(None).await?;
         let mut to_session = to.session(None).await?;
+        let mut conflicts = Vec::new();
+        for category in RECORD_CATEGORIES {
+            for entry in from_session.fetch_all(category, None, None, false).await? {
+                if to_session
+                    .fetch(category, &entry.name, false)
+                    .await?
+                    .is_some()
+                {
+                    conflicts.push(format!("{} \"{}\"", category, entry.name));
+                }
+            }
+        }
+        if conflicts.is_empty() {
+            return Ok(());
+        }
+        Err(CliError::Duplicate(format!(
+            "{} record(s) of the backup already exist in wallet \"{}\": {}. Nothing has been imported.",
+            conflicts.len(),
+            id,
+            conflicts.join(", ")
+        )))
+    }
 
-        let did_entries = from_session
-            .fetch_all(CATEGORY_DID, None, None, false)
+    // Records of the chosen DIDs only: the DID record, its keys (including the key of a started rotation)
+    // and the aliases mapped to it. Every DID is committed at once, so the copied DIDs are skipped on resume.
+    async fn copy_did_records(
+        from: &AnyStore,
+        to: &AnyStore,
+        dids: &[String],
+        marker: &mut ResumeMarker,
+    ) -> CliResult<()> {
+        let aliases = from
+            .session(None)
+            .await?
+            .fetch_all(CATEGORY_NYM_ALIAS, None, None, false)
             .await?;
+        let copied = marker.copied(CATEGORY_DID).min(dids.len());
+        let mut progress = ProgressBar::new(CATEGORY_DID, Some(dids.len()), copied);
+
+        for did in &dids[copied..] {
+            let mut from_session = from.session(None).await?;
+            let entry = from_session
+                .fetch(CATEGORY_DID, did, false)
+                .await?
+                .ok_or_else(|| {
+                    CliError::NotFound(format!("DID \"{}\" does not exist in the wallet", did))
+                })?;
+            let did_info = DidInfo::from_bytes(&entry.value)?;
 
-        for entry in did_entries {
+            let mut to_session = to.session(None).await?;
             to_session
                 .insert(
                     &entry.category,
@@ -348,132 +1045,265 @@ impl Wallet {
                 )
                 .await
                 .ok();
+
+            let mut keys = 0;
+            for verkey in std::iter::once(&did_info.verkey).chain(did_info.next_verkey.as_ref()) {
+                // DIDs of hardware wallets have no key in the wallet
+                if let Some(key) = from_session.fetch_key(verkey, false).await? {
+                    to_session
+                        .insert_key(
+                            key.name(),
+                            &key.load_local_key()?,
+                            key.metadata(),
+                            None,
+                            None,
+                        )
+                        .await
+                        .ok();
+                    keys += 1;
+                }
+            }
+
+            let mut did_aliases = 0;
+            for alias in aliases
+                .iter()
+                .filter(|alias| &*alias.value == did_info.did.as_bytes())
+            {
+                to_session
+                    .insert(
+                        &alias.category,
+                        &alias.name,
+                        &alias.value,
+                        Some(&alias.tags),
+                        None,
+                    )
+                    .await
+                    .ok();
+                did_aliases += 1;
+            }
+            to_session.commit().await?;
+
+            marker.add(CATEGORY_DID, 1);
+            marker.add(KEYS_CATEGORY, keys);
+            marker.add(CATEGORY_NYM_ALIAS, did_aliases);
+            marker.save()?;
+            progress.inc(1);
         }
+        progress.finish();
 
-        let key_entries = from_session
+        Ok(())
+    }
+
+    // The converted store keeps libindy record categories and values, so they are stored the way
+    // libindy backup records are. Keys converted by Askar into its own key entries are copied as they are.
+    async fn copy_records_from_legacy_store(
+        from: &AnyStore,
+        to: &AnyStore,
+        marker: &mut ResumeMarker,
+    ) -> CliResult<()> {
+        let key_entries = from
+            .session(None)
+            .await?
             .fetch_all_keys(None, None, None, None, false)
             .await?;
+        let copied = marker.copied(KEYS_CATEGORY).min(key_entries.len());
+        let mut progress = ProgressBar::new(KEYS_CATEGORY, Some(key_entries.len()), copied);
+
+        for page in key_entries[copied..].chunks(PAGE_SIZE) {
+            let mut to_session = to.session(None).await?;
+            for entry in page {
+                to_session
+                    .insert_key(
+                        entry.name(),
+                        &entry.load_local_key()?,
+                        entry.metadata(),
+                        None,
+                        None,
+                    )
+                    .await
+                    .ok();
+            }
+            to_session.commit().await?;
 
-        for entry in key_entries {
-            to_session
-                .insert_key(
-                    entry.name(),
-                    &entry.load_local_key()?,
-                    entry.metadata(),
-                    None,
-                    None,
-                )
-                .await
-                .ok();
+            marker.add(KEYS_CATEGORY, page.len());
+            marker.save()?;
+            progress.inc(page.len());
         }
+        marker.add(KEYS_CATEGORY, 0);
+        progress.finish();
 
-        to_session.commit().await?;
-        from_session.commit().await?;
+        for category in LEGACY_RECORD_CATEGORIES {
+            let copied = marker.copied(category);
+            let total = from.session(None).await?.count(category, None).await?;
+            let mut progress = ProgressBar::new(category, Some(total as usize), copied);
+
+            let mut scan = from
+                .scan(None, category.to_string(), None, Some(copied as i64), None)
+                .await?;
+
+            while let Some(entries) = scan.fetch_next().await? {
+                for page in entries.chunks(PAGE_SIZE) {
+                    let mut to_session = to.session(None).await?;
+                    for entry in page {
+                        let record = BackupRecord {
+                            type_: entry.category.to_string(),
+                            id: entry.name.to_string(),
+                            value: String::from_utf8(entry.value.to_vec()).map_err(|_| {
+                                CliError::InvalidInput(format!(
+                                    "Invalid wallet content: {} record {} is not UTF-8",
+                                    entry.category, entry.name
+                                ))
+                            })?,
+                            tags: Default::default(),
+                        };
+                        Self::store_libindy_record(&mut to_session, &record).await?;
+                    }
+                    to_session.commit().await?;
+
+                    marker.add(category, page.len());
+                    marker.save()?;
+                    progress.inc(page.len());
+                }
+            }
+            marker.add(category, 0);
+            progress.finish();
+        }
 
         Ok(())
     }
 
+    // Libindy backup is a stream of records, so on resume the records copied before are read and skipped.
+    // Counts are reported per libindy record type.
     async fn copy_records_from_libindy_backup(
         backup_reader: &mut LibindyBackupReader,
         to: &AnyStore,
+        marker: &mut ResumeMarker,
     ) -> CliResult<()> {
+        let mut skip = marker.total();
+        let mut progress = ProgressBar::new("records", None, skip);
         let mut to_session = to.session(None).await?;
+        let mut page = 0;
 
         while let Some(record) = backup_reader.read_record()? {
-            match record.type_.as_str() {
-                KeyRecord::TYPE => {
-                    let key_record = KeyRecord::from_str(&record.value)?;
-                    let key = LocalKey::from_seed(KeyAlg::Ed25519, &key_record.key_bytes()?, None)?;
-
-                    to_session
-                        .insert_key(&record.id, &key, None, None, None)
-                        .await
-                        .ok();
-                }
-                DidRecord::TYPE => {
-                    let did_record = DidRecord::from_str(&record.value)?;
+            if skip > 0 {
+                skip -= 1;
+                continue;
+            }
 
-                    let did_info = DidInfo {
-                        did: did_record.did,
-                        verkey: did_record.verkey,
-                        verkey_type: KEY_TYPE.to_string(),
-                        ..DidInfo::default()
-                    };
+            Self::store_libindy_record(&mut to_session, &record).await?;
 
-                    to_session
-                        .insert(
-                            CATEGORY_DID,
-                            &did_info.did,
-                            &did_info.to_bytes()?,
-                            Some(&did_info.tags()),
-                            None,
-                        )
-                        .await
-                        .ok();
-                }
-                TemporaryDidRecord::TYPE => {
-                    let temporary_did_record = TemporaryDidRecord::from_str(&record.value)?;
-
-                    let did_entry = to_session
-                        .fetch(CATEGORY_DID, &temporary_did_record.did, true)
-                        .await?
-                        .ok_or_else(|| {
-                            CliError::NotFound(format!(
-                                "DID {} does not exits in the wallet.",
-                                temporary_did_record.did
-                            ))
-                        })?;
-
-                    let mut did_info: DidInfo = DidInfo::from_bytes(&did_entry.value)?;
-                    did_info.next_verkey = Some(temporary_did_record.verkey.to_string());
+            marker.add(&record.type_, 1);
+            page += 1;
+            if page == PAGE_SIZE {
+                to_session.commit().await?;
+                marker.save()?;
+                progress.inc(page);
+                page = 0;
+                to_session = to.session(None).await?;
+            }
+        }
 
-                    to_session
-                        .replace(
-                            CATEGORY_DID,
-                            &did_info.did,
-                            &did_info.to_bytes()?,
-                            Some(&did_entry.tags),
-                            None,
-                        )
-                        .await
-                        .ok();
-                }
-                DidMetadataRecord::TYPE => {
-                    let did_metadata_record = DidMetadataRecord::from_str(&record.value)?;
-
-                    let did_entry = to_session
-                        .fetch(CATEGORY_DID, &record.id, true)
-                        .await?
-                        .ok_or_else(|| {
-                            CliError::NotFound(format!(
-                                "DID {} does not exits in the wallet.",
-                                record.id
-                            ))
-                        })?;
-
-                    let mut did_info: DidInfo = DidInfo::from_bytes(&did_entry.value)?;
-                    did_info.metadata = Some(did_metadata_record.value);
+        to_session.commit().await?;
+        progress.inc(page);
+        progress.finish();
+        Ok(())
+    }
 
-                    to_session
-                        .replace(
-                            CATEGORY_DID,
-                            &did_info.did,
-                            &did_info.to_bytes()?,
-                            Some(&did_entry.tags),
-                            None,
-                        )
-                        .await
-                        .ok();
-                }
-                _ => {
-                    println_warn!("Unsupported record type {}", record.type_);
-                    println_warn!("Record");
-                    println_warn!("{:?}", record);
-                }
+    // Libindy records refer to DIDs by id, so DID records must be stored before their metadata and temporary verkeys
+    async fn store_libindy_record(
+        to_session: &mut AnySession,
+        record: &BackupRecord,
+    ) -> CliResult<()> {
+        match record.type_.as_str() {
+            KeyRecord::TYPE => {
+                let key_record = KeyRecord::from_str(&record.value)?;
+                let key = LocalKey::from_seed(KeyAlg::Ed25519, &key_record.key_bytes()?, None)?;
+
+                to_session
+                    .insert_key(&record.id, &key, None, None, None)
+                    .await
+                    .ok();
+            }
+            DidRecord::TYPE => {
+                let did_record = DidRecord::from_str(&record.value)?;
+
+                let did_info = DidInfo {
+                    did: did_record.did,
+                    verkey: did_record.verkey,
+                    verkey_type: KEY_TYPE.to_string(),
+                    ..DidInfo::default()
+                };
+
+                to_session
+                    .insert(
+                        CATEGORY_DID,
+                        &did_info.did,
+                        &did_info.to_bytes()?,
+                        Some(&did_info.tags()),
+                        None,
+                    )
+                    .await
+                    .ok();
+            }
+            TemporaryDidRecord::TYPE => {
+                let temporary_did_record = TemporaryDidRecord::from_str(&record.value)?;
+
+                let did_entry = to_session
+                    .fetch(CATEGORY_DID, &temporary_did_record.did, true)
+                    .await?
+                    .ok_or_else(|| {
+                        CliError::NotFound(format!(
+                            "DID {} does not exits in the wallet.",
+                            temporary_did_record.did
+                        ))
+                    })?;
+
+                let mut did_info: DidInfo = DidInfo::from_bytes(&did_entry.value)?;
+                did_info.next_verkey = Some(temporary_did_record.verkey.to_string());
+
+                to_session
+                    .replace(
+                        CATEGORY_DID,
+                        &did_info.did,
+                        &did_info.to_bytes()?,
+                        Some(&did_entry.tags),
+                        None,
+                    )
+                    .await
+                    .ok();
+            }
+            DidMetadataRecord::TYPE => {
+                let did_metadata_record = DidMetadataRecord::from_str(&record.value)?;
+
+                let did_entry = to_session
+                    .fetch(CATEGORY_DID, &record.id, true)
+                    .await?
+                    .ok_or_else(|| {
+                        CliError::NotFound(format!(
+                            "DID {} does not exits in the wallet.",
+                            record.id
+                        ))
+                    })?;
+
+                let mut did_info: DidInfo = DidInfo::from_bytes(&did_entry.value)?;
+                did_info.metadata = Some(did_metadata_record.value);
+
+                to_session
+                    .replace(
+                        CATEGORY_DID,
+                        &did_info.did,
+                        &did_info.to_bytes()?,
+                        Some(&did_entry.tags),
+                        None,
+                    )
+                    .await
+                    .ok();
+            }
+            _ => {
+                warn!("Unsupported record type {} has been skipped", record.type_);
             }
         }
-
-        to_session.commit().await.map_err(CliError::from)
+        Ok(())
     }
 
     pub async fn store_record(
@@ -484,17 +1314,17 @@ impl Wallet {
         tags: Option<&[EntryTag]>,
         new: bool,
     ) -> CliResult<()> {
-        let mut session = self.store.session(None).await?;
+        let mut session = self.session().await?;
         if new {
             session.insert(category, id, value, tags, None).await?
         } else {
             session.replace(category, id, value, tags, None).await?
         }
-        session.commit().await.map_err(CliError::from)
+        Ok(())
     }
 
     pub async fn fetch_all_records(&self, category: &str) -> CliResult<Vec<Entry>> {
-        let mut session = self.store.session(None).await?;
+        let mut session = self.session().await?;
         session
             .fetch_all(category, None, None, false)
             .await
@@ -507,7 +1337,7 @@ impl Wallet {
         id: &str,
         for_update: bool,
     ) -> CliResult<Option<Entry>> {
-        let mut session = self.store.session(None).await?;
+        let mut session = self.session().await?;
         session
             .fetch(category, &id, for_update)
             .await
@@ -515,9 +1345,25 @@ impl Wallet {
     }
 
     pub async fn remove_record(&self, category: &str, id: &str) -> CliResult<()> {
-        let mut session = self.store.session(None).await?;
-        session.remove(category, id).await.map_err(CliError::from)?;
-        session.commit().await.map_err(CliError::from)
+        let mut session = self.session().await?;
+        session.remove(category, id).await.map_err(CliError::from)
+    }
+
+    /// Number of the keys stored in the wallet per key algorithm.
+    pub fn key_algorithms(&self) -> CliResult<BTreeMap<String, usize>> {
+        block_on(async move {
+            let mut algorithms = BTreeMap::new();
+            for entry in self
+                .session()
+                .await?
+                .fetch_all_keys(None, None, None, None, false)
+                .await?
+            {
+                let algorithm = entry.algorithm().unwrap_or("unknown").to_string();
+                *algorithms.entry(algorithm).or_insert(0) += 1;
+            }
+            Ok(algorithms)
+        })
     }
 
     pub async fn insert_key(
@@ -526,7 +1372,7 @@ impl Wallet {
         key: &LocalKey,
         metadata: Option<&str>,
     ) -> CliResult<()> {
-        let mut session = self.store.session(None).await?;
+        let mut session = self.session().await?;
         session
             .insert_key(id, key, metadata, None, None)
             .await
@@ -534,7 +1380,7 @@ impl Wallet {
     }
 
     pub async fn fetch_key(&self, id: &str) -> CliResult<LocalKey> {
-        let mut session = self.store.session(None).await?;
+        let mut session = self.session().await?;
         session
             .fetch_key(id, false)
             .await?
@@ -543,3 +1389,114 @@ impl Wallet {
             .map_err(CliError::from)
     }
 }
+
+/// Directory under the wallet home only the current user can access.
+/// The libindy wallet is copied into it for the migration.
+struct MigrationDirectory {
+    path: PathBuf,
+}
+
+impl MigrationDirectory {
+    fn create(id: &str) -> std::io::Result<MigrationDirectory> {
+        let home = EnvironmentUtils::wallet_home_path();
+        fs::create_dir_all(&home)?;
+
+        // the directory left by a killed process is not reused
+        let path = home.join(format!(".{}.migrate", id));
+        if path.exists() {
+            fs::remove_dir_all(&path)?;
+        }
+
+        let mut builder = fs::DirBuilder::new();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::DirBuilderExt;
+            builder.mode(0o700);
+        }
+        builder.create(&path)?;
+        Ok(MigrationDirectory { path })
+    }
+
+    fn copy_path(&self) -> PathBuf {
+        self.path.join("legacy.db")
+    }
+}
+
+impl Drop for MigrationDirectory {
+    fn drop(&mut self) {
+        fs::remove_dir_all(&self.path).ok();
+    }
+}
+
+fn sqlite_side_file(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(suffix);
+    name.into()
+}
+
+/// Copy SQLite database together with its WAL, shared memory and journal files.
+fn copy_sqlite_files(from: &Path, to: &Path) -> std::io::Result<()> {
+    remove_sqlite_files(to);
+    fs::copy(from, to)?;
+    for suffix in SQLITE_SIDE_FILES {
+        let side_file = sqlite_side_file(from, suffix);
+        if side_file.exists() {
+            fs::copy(&side_file, sqlite_side_file(to, suffix))?;
+        }
+    }
+    Ok(())
+}
+
+fn remove_sqlite_files(path: &Path) {
+    fs::remove_file(path).ok();
+    for suffix in SQLITE_SIDE_FILES {
+        fs::remove_file(sqlite_side_file(path, suffix)).ok();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn copy_sqlite_files_works() {
+        let dir = EnvironmentUtils::tmp_file_path("copy_sqlite_files");
+        fs::create_dir_all(&dir).unwrap();
+        let from = dir.join("sqlite.db");
+        let to = dir.join("copy.db");
+        fs::write(&from, "db").unwrap();
+        fs::write(sqlite_side_file(&from, "-wal"), "wal").unwrap();
+
+        copy_sqlite_files(&from, &to).unwrap();
+        assert_eq!("db", fs::read_to_string(&to).unwrap());
+        assert_eq!(
+            "wal",
+            fs::read_to_string(sqlite_side_file(&to, "-wal")).unwrap()
+        );
+        assert!(!sqlite_side_file(&to, "-shm").exists());
+
+        fs::write(sqlite_side_file(&to, "-shm"), "shm").unwrap();
+        remove_sqlite_files(&to);
+        assert!(!to.exists());
+        assert!(!sqlite_side_file(&to, "-wal").exists());
+        assert!(!sqlite_side_file(&to, "-shm").exists());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn migration_directory_works() {
+        let dir = MigrationDirectory::create("migration_directory").unwrap();
+        let path = dir.path.clone();
+        fs::write(dir.copy_path(), "db").unwrap();
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o700);
+        }
+
+        drop(dir);
+        assert!(!path.exists());
+    }
+}
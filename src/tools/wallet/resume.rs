@@ -0,0 +1,104 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::{
+    error::{CliError, CliResult},
+    utils::environment::EnvironmentUtils,
+};
+
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// Number of records copied and committed at once.
+pub const PAGE_SIZE: usize = 100;
+
+/// Number of copied records per category.
+pub type RecordCounts = BTreeMap<String, usize>;
+
+/// Progress of an export or import saved after every committed page.
+/// The marker exists only while the copying is not finished, so an interrupted run continues
+/// from the saved position when the same command is executed again.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ResumeMarker {
+    #[serde(skip)]
+    path: PathBuf,
+    source: String,
+    copied: RecordCounts,
+}
+
+impl ResumeMarker {
+    pub fn new(path: PathBuf, source: &str) -> ResumeMarker {
+        ResumeMarker {
+            path,
+            source: source.to_string(),
+            copied: RecordCounts::new(),
+        }
+    }
+
+    /// Read the marker left by an interrupted run for the same source.
+    pub fn load(path: PathBuf, source: &str) -> CliResult<Option<ResumeMarker>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = fs::read_to_string(&path)?;
+        let mut marker = serde_json::from_str::<ResumeMarker>(&content).map_err(|err| {
+            CliError::InvalidEntityState(format!(
+                "Resume marker \"{}\" is corrupted: {}",
+                path.display(),
+                err
+            ))
+        })?;
+        if marker.source != source {
+            return Err(CliError::InvalidEntityState(format!(
+                "Resume marker \"{}\" belongs to the interrupted copying of \"{}\"",
+                path.display(),
+                marker.source
+            )));
+        }
+        marker.path = path;
+        Ok(Some(marker))
+    }
+
+    pub fn path_for(target: &Path) -> PathBuf {
+        let mut path = target.as_os_str().to_owned();
+        path.push(".resume");
+        PathBuf::from(path)
+    }
+
+    /// Marker of an import or a migration kept in the directory of the target wallet.
+    pub fn path_in_wallet(id: &str) -> PathBuf {
+        EnvironmentUtils::wallet_path(id).join("import.resume")
+    }
+
+    pub fn copied(&self, category: &str) -> usize {
+        self.copied.get(category).copied().unwrap_or(0)
+    }
+
+    pub fn total(&self) -> usize {
+        self.copied.values().sum()
+    }
+
+    pub fn add(&mut self, category: &str, count: usize) {
+        *self.copied.entry(category.to_string()).or_insert(0) += count;
+    }
+
+    pub fn counts(&self) -> RecordCounts {
+        self.copied.clone()
+    }
+
+    pub fn save(&self) -> CliResult<()> {
+        // write into a temporary file first so that the marker is never left half-written
+        let mut tmp_path = self.path.as_os_str().to_owned();
+        tmp_path.push(".tmp");
+        fs::write(&tmp_path, serde_json::to_vec(self)?)?;
+        fs::rename(&tmp_path, &self.path).map_err(CliError::from)
+    }
+
+    pub fn remove(self) -> CliResult<()> {
+        fs::remove_file(&self.path).map_err(CliError::from)
+    }
+}
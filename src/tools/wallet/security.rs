@@ -0,0 +1,236 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::{
+    error::{CliError, CliResult},
+    tools::wallet::{
+        backup::BackupRecord, key_rotation::KeyRotationRecord, wallet_config::WalletConfig, Wallet,
+    },
+    utils::file::read_file,
+};
+
+use chrono::Utc;
+use std::collections::BTreeMap;
+
+const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
+/// Settings a wallet is checked against by `wallet security-report`.
+/// Fields missing in a policy file keep their default values.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SecurityPolicy {
+    pub key_derivation_methods: Vec<String>,
+    pub key_algorithms: Vec<String>,
+    pub backup_key_derivation_methods: Vec<String>,
+    pub require_backup: bool,
+    pub max_backup_age_days: Option<i64>,
+}
+
+impl Default for SecurityPolicy {
+    fn default() -> Self {
+        SecurityPolicy {
+            key_derivation_methods: vec!["argon2m".to_string()],
+            key_algorithms: vec!["ed25519".to_string()],
+            backup_key_derivation_methods: vec!["argon2m".to_string()],
+            require_backup: true,
+            max_backup_age_days: Some(90),
+        }
+    }
+}
+
+impl SecurityPolicy {
+    pub fn read(path: &str) -> CliResult<SecurityPolicy> {
+        let content = read_file(path).map_err(|err| {
+            CliError::InvalidInput(format!("Unable to read policy \"{}\": {}", path, err))
+        })?;
+        serde_json::from_str(&content)
+            .map_err(|err| CliError::InvalidInput(format!("Invalid policy \"{}\": {}", path, err)))
+    }
+}
+
+#[derive(Debug)]
+pub struct SecurityReport {
+    pub wallet: String,
+    pub storage_type: String,
+    pub key_derivation_method: String,
+    // Time the wallet key has been set, unknown for wallets created by older CLI versions
+    pub key_rotated: Option<i64>,
+    pub key_algorithms: BTreeMap<String, usize>,
+    pub backups: Vec<BackupRecord>,
+    pub findings: Vec<String>,
+}
+
+impl SecurityReport {
+    pub fn build(wallet: &Wallet, policy: &SecurityPolicy) -> CliResult<SecurityReport> {
+        let storage_type = WalletConfig::read(&wallet.name)
+            .map(|config| config.storage_type)
+            .unwrap_or_else(|_| "unknown".to_string());
+        let mut report = SecurityReport {
+            wallet: wallet.name.to_string(),
+            storage_type,
+            key_derivation_method: wallet.key_derivation_method.to_string(),
+            key_rotated: KeyRotationRecord::get(wallet)?.map(|record| record.rotated),
+            key_algorithms: wallet.key_algorithms()?,
+            backups: BackupRecord::list(wallet)?,
+            findings: Vec::new(),
+        };
+        report.findings = report.check(policy, Utc::now().timestamp());
+        Ok(report)
+    }
+
+    fn check(&self, policy: &SecurityPolicy, now: i64) -> Vec<String> {
+        let mut findings = Vec::new();
+
+        if self.key_derivation_method == "raw" {
+            findings.push(
+                "Wallet is protected by a raw key: the key is used as is, without derivation from a passphrase"
+                    .to_string(),
+            );
+        }
+        if !policy
+            .key_derivation_methods
+            .contains(&self.key_derivation_method)
+        {
+            findings.push(format!(
+                "Wallet key derivation method \"{}\" is not allowed. Allowed methods: {}",
+                self.key_derivation_method,
+                policy.key_derivation_methods.join(", ")
+            ));
+        }
+
+        for (algorithm, count) in &self.key_algorithms {
+            if !policy.key_algorithms.contains(algorithm) {
+                findings.push(format!(
+                    "{} key(s) of algorithm \"{}\" which is not allowed. Allowed algorithms: {}",
+                    count,
+                    algorithm,
+                    policy.key_algorithms.join(", ")
+                ));
+            }
+        }
+
+        let available = self
+            .backups
+            .iter()
+            .filter(|backup| backup.exists())
+            .collect::<Vec<&BackupRecord>>();
+        if policy.require_backup && available.is_empty() {
+            findings.push(
+                "There is no backup of the wallet available on this machine. Make one with `wallet export`"
+                    .to_string(),
+            );
+        }
+        for backup in &available {
+            if !policy
+                .backup_key_derivation_methods
+                .contains(&backup.key_derivation_method)
+            {
+                findings.push(format!(
+                    "Backup \"{}\" key derivation method \"{}\" is not allowed. Allowed methods: {}",
+                    backup.path,
+                    backup.key_derivation_method,
+                    policy.backup_key_derivation_methods.join(", ")
+                ));
+            }
+        }
+        if let (Some(max_age), Some(latest)) = (policy.max_backup_age_days, available.last()) {
+            let age = (now - latest.created) / SECONDS_PER_DAY;
+            if age > max_age {
+                findings.push(format!(
+                    "The latest backup is {} days old, the policy requires one at least every {} days",
+                    age, max_age
+                ));
+            }
+        }
+
+        findings
+    }
+}
+
+/// KDF of the wallet key derivation method with its parameters.
+pub fn describe_key_derivation_method(method: &str) -> &'static str {
+    match method {
+        "argon2m" => "Argon2i, moderate: 128 MiB memory, 6 passes",
+        "argon2i" => "Argon2i, interactive: 32 MiB memory, 4 passes",
+        "raw" => "None: raw key",
+        _ => "Unknown",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: i64 = 1_700_000_000;
+
+    fn report(key_derivation_method: &str, backups: Vec<BackupRecord>) -> SecurityReport {
+        SecurityReport {
+            wallet: "wallet".to_string(),
+            storage_type: "sqlite".to_string(),
+            key_derivation_method: key_derivation_method.to_string(),
+            key_rotated: None,
+            key_algorithms: BTreeMap::from([("ed25519".to_string(), 2)]),
+            backups,
+            findings: Vec::new(),
+        }
+    }
+
+    fn backup(key_derivation_method: &str, created: i64) -> BackupRecord {
+        BackupRecord {
+            // Any path which exists
+            path: std::env::temp_dir().to_string_lossy().to_string(),
+            key_derivation_method: key_derivation_method.to_string(),
+            created,
+        }
+    }
+
+    #[test]
+    fn security_report_check_works() {
+        let report = report("argon2m", vec![backup("argon2m", NOW - SECONDS_PER_DAY)]);
+        assert!(report.check(&SecurityPolicy::default(), NOW).is_empty());
+    }
+
+    #[test]
+    fn security_report_check_works_for_weak_settings() {
+        let mut report = report("raw", vec![backup("raw", NOW - 100 * SECONDS_PER_DAY)]);
+        report.key_algorithms.insert("x25519".to_string(), 1);
+        let findings = report.check(&SecurityPolicy::default(), NOW);
+        assert_eq!(5, findings.len());
+
+        let policy = SecurityPolicy {
+            key_derivation_methods: vec!["raw".to_string()],
+            key_algorithms: vec!["ed25519".to_string(), "x25519".to_string()],
+            backup_key_derivation_methods: vec!["raw".to_string()],
+            require_backup: true,
+            max_backup_age_days: None,
+        };
+        // Raw key is always reported
+        assert_eq!(1, report.check(&policy, NOW).len());
+    }
+
+    #[test]
+    fn security_report_check_works_for_missing_backup() {
+        let mut missing = backup("argon2m", NOW);
+        missing.path = "/not/existing/backup".to_string();
+        let report = report("argon2m", vec![missing]);
+        assert_eq!(1, report.check(&SecurityPolicy::default(), NOW).len());
+
+        let policy = SecurityPolicy {
+            require_backup: false,
+            ..SecurityPolicy::default()
+        };
+        assert!(report.check(&policy, NOW).is_empty());
+    }
+
+    #[test]
+    fn security_policy_works_for_partial_policy() {
+        let policy =
+            serde_json::from_str::<SecurityPolicy>(r#"{"max_backup_age_days":30}"#).unwrap();
+        assert_eq!(Some(30), policy.max_backup_age_days);
+        assert_eq!(
+            SecurityPolicy::default().key_algorithms,
+            policy.key_algorithms
+        );
+    }
+}
@@ -0,0 +1,96 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::error::CliResult;
+
+use aries_askar::any::{AnySession, AnyStore};
+use async_lock::{Semaphore, SemaphoreGuard};
+use std::{
+    fmt::{Debug, Formatter},
+    ops::{Deref, DerefMut},
+    sync::Mutex,
+};
+
+// Keeps SQLite writers from contending for the database lock while still letting reads overlap
+pub const MAX_WALLET_SESSIONS: usize = 4;
+
+/// Bounded set of Askar sessions shared by the operations on a wallet.
+/// Sessions released by finished operations are kept open and reused by the next ones;
+/// operations exceeding the limit wait until a session is released.
+pub struct SessionPool {
+    permits: Semaphore,
+    idle: Mutex<Vec<AnySession>>,
+}
+
+/// Session borrowed from the pool. It goes back to the pool on drop.
+pub struct PooledSession<'a> {
+    pool: &'a SessionPool,
+    session: Option<AnySession>,
+    _permit: SemaphoreGuard<'a>,
+}
+
+impl SessionPool {
+    pub fn new(size: usize) -> SessionPool {
+        SessionPool {
+            permits: Semaphore::new(size),
+            idle: Mutex::new(Vec::with_capacity(size)),
+        }
+    }
+
+    pub async fn acquire<'a>(&'a self, store: &AnyStore) -> CliResult<PooledSession<'a>> {
+        let permit = self.permits.acquire().await;
+
+        let idle = self.idle.lock().ok().and_then(|mut idle| idle.pop());
+        let session = match idle {
+            Some(session) => session,
+            None => store.session(None).await?,
+        };
+
+        Ok(PooledSession {
+            pool: self,
+            session: Some(session),
+            _permit: permit,
+        })
+    }
+
+    /// Close idle sessions. It must be done before closing the store as they hold database connections.
+    pub fn clear(&self) {
+        if let Ok(mut idle) = self.idle.lock() {
+            idle.clear();
+        }
+    }
+}
+
+impl Debug for SessionPool {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let idle = self.idle.lock().map(|idle| idle.len()).unwrap_or_default();
+        f.debug_struct("SessionPool").field("idle", &idle).finish()
+    }
+}
+
+impl Deref for PooledSession<'_> {
+    type Target = AnySession;
+
+    fn deref(&self) -> &AnySession {
+        self.session
+            .as_ref()
+            .expect("session is taken only on drop")
+    }
+}
+
+impl DerefMut for PooledSession<'_> {
+    fn deref_mut(&mut self) -> &mut AnySession {
+        self.session
+            .as_mut()
+            .expect("session is taken only on drop")
+    }
+}
+
+impl Drop for PooledSession<'_> {
+    fn drop(&mut self) {
+        if let (Some(session), Ok(mut idle)) = (self.session.take(), self.pool.idle.lock()) {
+            idle.push(session);
+        }
+    }
+}
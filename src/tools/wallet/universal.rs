@@ -0,0 +1,112 @@
+/*
+    Copyright © 2023 Province of British Columbia
+    https://digital.gov.bc.ca/digital-trust
+*/
+use crate::{
+    error::{CliError, CliResult},
+    tools::did::DidInfo,
+};
+
+use indy_utils::{base58, did::DidValue, Qualifiable};
+use serde_json::Value as JsonValue;
+
+const WALLET_CONTEXT: &str = "https://w3id.org/wallet/v1";
+const KEY_TYPE: &str = "Ed25519VerificationKey2018";
+
+/// Contents of the wallet in the W3C CCG Universal Wallet 2020 (JSON-LD) format:
+/// a verification key entry controlled by every DID.
+pub struct UniversalWallet<'a> {
+    pub name: &'a str,
+    /// DIDs with the Ed25519 seeds of their keys if the private keys are exported
+    pub dids: Vec<(DidInfo, Option<&'a [u8]>)>,
+}
+
+impl<'a> UniversalWallet<'a> {
+    pub fn to_json(&self) -> CliResult<JsonValue> {
+        let contents = self
+            .dids
+            .iter()
+            .map(|(did_info, seed)| key_entry(did_info, *seed))
+            .collect::<CliResult<Vec<JsonValue>>>()?;
+        Ok(json!({
+            "@context": [WALLET_CONTEXT],
+            "id": format!("urn:indy-cli:wallet:{}", self.name),
+            "type": "UniversalWallet2020",
+            "status": "UNLOCKED",
+            "contents": contents,
+        }))
+    }
+}
+
+fn key_entry(did_info: &DidInfo, seed: Option<&[u8]>) -> CliResult<JsonValue> {
+    // Unqualified DIDs are the legacy Sovrin ones
+    let did = DidValue(did_info.did.to_string());
+    let controller = if did.is_fully_qualified() {
+        did.0
+    } else {
+        format!("did:sov:{}", did.0)
+    };
+    let mut entry = json!({
+        "@context": [WALLET_CONTEXT],
+        "id": format!("{}#verkey", controller),
+        "type": KEY_TYPE,
+        "controller": controller,
+        "publicKeyBase58": did_info.verkey,
+    });
+    if let Some(metadata) = did_info.metadata.as_ref() {
+        entry["name"] = json!(metadata);
+    }
+    if let Some(seed) = seed {
+        // Ed25519 secret key in the NaCl form: the seed followed by the public key
+        let mut private_key = seed.to_vec();
+        private_key.extend(base58::decode(&did_info.verkey).map_err(|_| {
+            CliError::InvalidEntityState(format!("Invalid verkey of DID {}", did_info.did))
+        })?);
+        entry["privateKeyBase58"] = json!(base58::encode(private_key));
+    }
+    Ok(entry)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn did_info() -> DidInfo {
+        DidInfo {
+            did: "V4SGRU86Z58d6TV7PBUe6f".to_string(),
+            verkey: "GJ1SzoWzavQYfNL9XkaJdrQejfztN4XqdsiV4ct3LXKL".to_string(),
+            verkey_type: "ed25519".to_string(),
+            method: None,
+            metadata: Some("Trustee".to_string()),
+            next_verkey: None,
+            signer: None,
+        }
+    }
+
+    #[test]
+    fn to_json_works() {
+        let seed = b"000000000000000000000000Trustee1";
+        let wallet = UniversalWallet {
+            name: "wallet",
+            dids: vec![(did_info(), Some(seed))],
+        };
+        let wallet = wallet.to_json().unwrap();
+        let entry = &wallet["contents"][0];
+        assert_eq!("did:sov:V4SGRU86Z58d6TV7PBUe6f", entry["controller"]);
+        assert_eq!("did:sov:V4SGRU86Z58d6TV7PBUe6f#verkey", entry["id"]);
+        assert_eq!("Trustee", entry["name"]);
+        let private_key = base58::decode(entry["privateKeyBase58"].as_str().unwrap()).unwrap();
+        assert_eq!(64, private_key.len());
+        assert_eq!(&seed[..], &private_key[..32]);
+    }
+
+    #[test]
+    fn to_json_works_without_private_keys() {
+        let wallet = UniversalWallet {
+            name: "wallet",
+            dids: vec![(did_info(), None)],
+        };
+        let wallet = wallet.to_json().unwrap();
+        assert!(wallet["contents"][0]["privateKeyBase58"].is_null());
+    }
+}
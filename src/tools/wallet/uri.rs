@@ -8,13 +8,14 @@ use crate::{
     utils::environment::EnvironmentUtils,
 };
 
-use crate::tools::wallet::wallet_config::WalletConfig;
+use crate::tools::wallet::{memory::MEMORY_STORE_URI, wallet_config::WalletConfig};
 use std::path::PathBuf;
 use urlencoding::encode;
 
 pub enum StorageType {
     Sqlite,
     Postgres,
+    Memory,
 }
 
 impl StorageType {
@@ -22,6 +23,7 @@ impl StorageType {
         match self {
             StorageType::Sqlite => "sqlite",
             StorageType::Postgres => "postgres",
+            StorageType::Memory => "memory",
         }
     }
 }
@@ -42,6 +44,7 @@ impl WalletUri {
         let uri = match storage_type {
             StorageType::Sqlite => Self::build_sqlite_uri(config, credentials, path),
             StorageType::Postgres => Self::build_postgres_uri(config, credentials),
+            StorageType::Memory => Ok(MEMORY_STORE_URI.to_string()),
         }?;
         Ok(WalletUri(uri))
     }
@@ -65,18 +68,14 @@ impl WalletUri {
                     path
                 }
             }
-            None => {
-                let mut path = EnvironmentUtils::wallet_path(&config.id);
-                path.push(&config.id);
-                path.set_extension("db");
-                path
-            }
+            None => Self::sqlite_path(&config.id),
         };
 
+        // SQLite parses the path from the URI itself and does not accept the extended-length prefix
         let uri = format!(
             "{}://{}",
             StorageType::Sqlite.to_str(),
-            encode(&path.to_string_lossy())
+            encode(&EnvironmentUtils::standard_path(&path).to_string_lossy())
         );
 
         Ok(uri)
@@ -146,10 +145,18 @@ impl WalletUri {
         Ok(uri)
     }
 
-    fn map_storage_type(storage_type: &str) -> CliResult<StorageType> {
+    pub fn sqlite_path(id: &str) -> PathBuf {
+        let mut path = EnvironmentUtils::wallet_path(id);
+        path.push(id);
+        path.set_extension("db");
+        path
+    }
+
+    pub fn map_storage_type(storage_type: &str) -> CliResult<StorageType> {
         match storage_type {
             "default" | "sqlite" | "sqlite_storage" => Ok(StorageType::Sqlite),
             "postgres" | "postgres_storage" => Ok(StorageType::Postgres),
+            "memory" => Ok(StorageType::Memory),
             value => Err(CliError::InvalidInput(format!(
                 "Unsupported storage type provided: {}",
                 value
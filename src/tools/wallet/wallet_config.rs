@@ -5,6 +5,10 @@
 use crate::utils::environment::EnvironmentUtils;
 
 use crate::error::{CliError, CliResult};
+use crate::tools::wallet::{
+    memory::MemoryWallets,
+    uri::{StorageType, WalletUri},
+};
 use serde_json::Value as JsonValue;
 use std::{
     fs,
@@ -21,7 +25,17 @@ pub struct WalletConfig {
 }
 
 impl WalletConfig {
-    pub(crate) fn store(&self) -> CliResult<()> {
+    pub fn store(&self) -> CliResult<()> {
+        // memory wallets are known to CLI while their store exists
+        if self.is_memory() {
+            return match MemoryWallets::contains(&self.id) {
+                true => Ok(()),
+                false => Err(CliError::InvalidInput(
+                    "Memory wallets can be created by `wallet create` only".to_string(),
+                )),
+            };
+        }
+
         Self::create_wallets_directory()?;
 
         let mut config_file = File::create(&self.path())?;
@@ -31,7 +45,15 @@ impl WalletConfig {
         Ok(())
     }
 
-    pub(crate) fn read(id: &str) -> CliResult<Self> {
+    pub fn read(id: &str) -> CliResult<Self> {
+        if MemoryWallets::contains(id) {
+            return Ok(WalletConfig {
+                id: id.to_string(),
+                storage_type: StorageType::Memory.to_str().to_string(),
+                storage_config: None,
+            });
+        }
+
         let path = EnvironmentUtils::wallet_config_path(id);
 
         let mut config_json = String::new();
@@ -41,18 +63,38 @@ impl WalletConfig {
         serde_json::from_str(&config_json).map_err(CliError::from)
     }
 
-    pub(crate) fn delete(&self) -> CliResult<()> {
+    pub fn delete(&self) -> CliResult<()> {
+        // the config of a memory wallet is gone with its store only
+        if self.is_memory() {
+            return match MemoryWallets::contains(&self.id) {
+                true => Err(CliError::InvalidEntityState(
+                    "Memory wallets cannot be detached. Use `wallet delete` to drop it".to_string(),
+                )),
+                false => Ok(()),
+            };
+        }
         fs::remove_file(&self.path()).map_err(CliError::from)
     }
 
-    pub(crate) fn exists(&self) -> bool {
-        self.path().exists()
+    pub fn exists(&self) -> bool {
+        self.path().exists() || MemoryWallets::contains(&self.id)
     }
 
     pub(crate) fn create_path(&self) -> CliResult<()> {
+        if self.is_memory() {
+            return Ok(());
+        }
         WalletDirectory::from_id(&self.id).create()
     }
 
+    /// Whether the wallet is kept in the memory of the process and nothing of it is written to disk.
+    pub fn is_memory(&self) -> bool {
+        matches!(
+            WalletUri::map_storage_type(&self.storage_type),
+            Ok(StorageType::Memory)
+        )
+    }
+
     fn path(&self) -> PathBuf {
         EnvironmentUtils::wallet_config_path(&self.id)
     }
@@ -117,6 +159,13 @@ impl WalletDirectory {
             }
         }
 
+        configs.extend(MemoryWallets::list().into_iter().map(|id| {
+            json!({
+                "id": id,
+                "storage_type": StorageType::Memory.to_str(),
+            })
+        }));
+
         configs
     }
 }
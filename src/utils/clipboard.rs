@@ -0,0 +1,35 @@
+use crate::utils::renderer::Output;
+
+use arboard::Clipboard;
+use std::cell::RefCell;
+
+thread_local! {
+    // On Linux the copied text is served by the clipboard owner, so the handle is kept for the whole session
+    static CLIPBOARD: RefCell<Option<Clipboard>> = RefCell::new(None);
+}
+
+/// Place the text on the system clipboard.
+pub fn copy(text: &str) -> Result<(), String> {
+    CLIPBOARD.with(|clipboard| {
+        let mut clipboard = clipboard.borrow_mut();
+        if clipboard.is_none() {
+            *clipboard = Some(
+                Clipboard::new()
+                    .map_err(|err| format!("System clipboard is not available: {}", err))?,
+            );
+        }
+        clipboard
+            .as_mut()
+            .map(|clipboard| clipboard.set_text(text.to_string()))
+            .transpose()
+            .map_err(|err| format!("Can't copy to the clipboard: {}", err))?;
+        Ok(())
+    })
+}
+
+/// Copy the value to the clipboard and report it to the user.
+pub fn copy_value(output: &dyn Output, value: &str, name: &str) -> Result<(), ()> {
+    copy(value).map_err(|err| println_err!(output, "{}", err))?;
+    println_succ!(output, "{} has been copied to the clipboard", name);
+    Ok(())
+}
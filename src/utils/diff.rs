@@ -0,0 +1,226 @@
+// Texts which differ in more words are reported as fully replaced to keep the LCS table small
+const MAX_TABLE_SIZE: usize = 4_000_000;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Change {
+    Equal(String),
+    Removed(String),
+    Added(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Token<'a> {
+    Equal(&'a str),
+    Removed(&'a str),
+    Added(&'a str),
+}
+
+/// Word-level difference between two texts. Whitespace is kept as separate tokens,
+/// so joining the `Equal` and `Removed` parts gives `old` and the `Equal` and `Added` parts give `new`.
+pub fn diff_words(old: &str, new: &str) -> Vec<Change> {
+    let old = tokenize(old);
+    let new = tokenize(new);
+
+    let prefix = old
+        .iter()
+        .zip(new.iter())
+        .take_while(|(old, new)| old == new)
+        .count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(old, new)| old == new)
+        .count();
+
+    let mut changes = old[..prefix]
+        .iter()
+        .map(|token| Token::Equal(token))
+        .collect::<Vec<Token>>();
+    changes.extend(diff_tokens(
+        &old[prefix..old.len() - suffix],
+        &new[prefix..new.len() - suffix],
+    ));
+    changes.extend(
+        old[old.len() - suffix..]
+            .iter()
+            .map(|token| Token::Equal(token)),
+    );
+    merge(changes)
+}
+
+pub fn count_words(changes: &[Change]) -> (usize, usize) {
+    changes
+        .iter()
+        .fold((0, 0), |(removed, added), change| match change {
+            Change::Removed(text) => (removed + text.split_whitespace().count(), added),
+            Change::Added(text) => (removed, added + text.split_whitespace().count()),
+            Change::Equal(_) => (removed, added),
+        })
+}
+
+fn tokenize(text: &str) -> Vec<&str> {
+    let mut tokens = Vec::new();
+    let mut start = 0;
+    let mut whitespace = None;
+    for (index, ch) in text.char_indices() {
+        let is_whitespace = ch.is_whitespace();
+        if whitespace.is_some() && whitespace != Some(is_whitespace) {
+            tokens.push(&text[start..index]);
+            start = index;
+        }
+        whitespace = Some(is_whitespace);
+    }
+    if start < text.len() {
+        tokens.push(&text[start..]);
+    }
+    tokens
+}
+
+fn diff_tokens<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<Token<'a>> {
+    if old.len() * new.len() > MAX_TABLE_SIZE {
+        return old
+            .iter()
+            .map(|token| Token::Removed(token))
+            .chain(new.iter().map(|token| Token::Added(token)))
+            .collect();
+    }
+
+    // lengths[i][j] is the LCS length of old[i..] and new[j..]
+    let width = new.len() + 1;
+    let mut lengths = vec![0u32; (old.len() + 1) * width];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lengths[i * width + j] = if old[i] == new[j] {
+                lengths[(i + 1) * width + j + 1] + 1
+            } else {
+                lengths[(i + 1) * width + j].max(lengths[i * width + j + 1])
+            };
+        }
+    }
+
+    let mut changes = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() && j < new.len() {
+        if old[i] == new[j] {
+            changes.push(Token::Equal(old[i]));
+            i += 1;
+            j += 1;
+        } else if lengths[(i + 1) * width + j] >= lengths[i * width + j + 1] {
+            changes.push(Token::Removed(old[i]));
+            i += 1;
+        } else {
+            changes.push(Token::Added(new[j]));
+            j += 1;
+        }
+    }
+    changes.extend(old[i..].iter().map(|token| Token::Removed(token)));
+    changes.extend(new[j..].iter().map(|token| Token::Added(token)));
+    changes
+}
+
+// Join consecutive tokens of the same kind. Whitespace inside a replaced phrase belongs to both sides,
+// so that the phrase is shown as one removal followed by one addition.
+fn merge(changes: Vec<Token>) -> Vec<Change> {
+    let mut merged = Vec::new();
+    let mut removed = String::new();
+    let mut added = String::new();
+
+    for (index, change) in changes.iter().enumerate() {
+        match *change {
+            Token::Removed(text) => removed.push_str(text),
+            Token::Added(text) => added.push_str(text),
+            Token::Equal(text)
+                if text.trim().is_empty()
+                    && !removed.is_empty()
+                    && !added.is_empty()
+                    && matches!(
+                        changes.get(index + 1),
+                        Some(Token::Removed(_)) | Some(Token::Added(_))
+                    ) =>
+            {
+                removed.push_str(text);
+                added.push_str(text);
+            }
+            Token::Equal(text) => {
+                flush(&mut merged, &mut removed, &mut added);
+                match merged.last_mut() {
+                    Some(Change::Equal(equal)) => equal.push_str(text),
+                    _ => merged.push(Change::Equal(text.to_string())),
+                }
+            }
+        }
+    }
+    flush(&mut merged, &mut removed, &mut added);
+    merged
+}
+
+fn flush(merged: &mut Vec<Change>, removed: &mut String, added: &mut String) {
+    if !removed.is_empty() {
+        merged.push(Change::Removed(std::mem::take(removed)));
+    }
+    if !added.is_empty() {
+        merged.push(Change::Added(std::mem::take(added)));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn join(changes: &[Change], skip_added: bool) -> String {
+        changes
+            .iter()
+            .filter_map(|change| match change {
+                Change::Equal(text) => Some(text.as_str()),
+                Change::Removed(text) if skip_added => Some(text.as_str()),
+                Change::Added(text) if !skip_added => Some(text.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn diff_words_works() {
+        let old = "The user agrees to the terms\nof the network.";
+        let new = "The author agrees to all the terms\nof the Sovrin network.";
+        let changes = diff_words(old, new);
+        assert_eq!(
+            changes,
+            vec![
+                Change::Equal("The ".to_string()),
+                Change::Removed("user".to_string()),
+                Change::Added("author".to_string()),
+                Change::Equal(" agrees to ".to_string()),
+                Change::Added("all ".to_string()),
+                Change::Equal("the terms\nof the".to_string()),
+                Change::Added(" Sovrin".to_string()),
+                Change::Equal(" network.".to_string()),
+            ]
+        );
+        assert_eq!(old, join(&changes, true));
+        assert_eq!(new, join(&changes, false));
+        assert_eq!((1, 3), count_words(&changes));
+    }
+
+    #[test]
+    fn diff_words_works_for_replaced_phrase() {
+        let changes = diff_words("pay the fee now", "pay nothing at all now");
+        assert_eq!(
+            changes,
+            vec![
+                Change::Equal("pay ".to_string()),
+                Change::Removed("the fee".to_string()),
+                Change::Added("nothing at all".to_string()),
+                Change::Equal(" now".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn diff_words_works_for_equal_texts() {
+        let changes = diff_words("same text", "same text");
+        assert_eq!(changes, vec![Change::Equal("same text".to_string())]);
+        assert!(diff_words("", "").is_empty());
+    }
+}
@@ -0,0 +1,159 @@
+use indy_utils::{base58, base64, hash::SHA256};
+
+const CHECKSUM_LENGTH: usize = 4;
+
+pub const ENCODINGS: [&str; 6] = [
+    "base58",
+    "base58check",
+    "base64",
+    "base64url",
+    "hex",
+    "multibase",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Encoding {
+    Base58,
+    Base58Check,
+    Base64,
+    Base64Url,
+    Hex,
+    /// Self-describing encoding: the first character names the base.
+    /// Values are encoded in base58btc (`z` prefix) which is used by `did:key` and Verifiable Credentials.
+    Multibase,
+}
+
+impl Encoding {
+    pub fn from_str(encoding: &str) -> Result<Encoding, String> {
+        match encoding {
+            "base58" => Ok(Encoding::Base58),
+            "base58check" => Ok(Encoding::Base58Check),
+            "base64" => Ok(Encoding::Base64),
+            "base64url" => Ok(Encoding::Base64Url),
+            "hex" => Ok(Encoding::Hex),
+            "multibase" => Ok(Encoding::Multibase),
+            encoding => Err(format!(
+                "Unsupported encoding \"{}\". Expected one of: {}",
+                encoding,
+                ENCODINGS.join(", ")
+            )),
+        }
+    }
+
+    pub fn encode(&self, bytes: &[u8]) -> String {
+        match self {
+            Encoding::Base58 => base58::encode(bytes),
+            Encoding::Base58Check => {
+                let mut bytes = bytes.to_vec();
+                bytes.extend_from_slice(&checksum(&bytes));
+                base58::encode(bytes)
+            }
+            Encoding::Base64 => base64::encode(bytes),
+            Encoding::Base64Url => to_url_safe(&base64::encode(bytes)),
+            Encoding::Hex => hex::encode(bytes),
+            Encoding::Multibase => format!("z{}", base58::encode(bytes)),
+        }
+    }
+
+    pub fn decode(&self, value: &str) -> Result<Vec<u8>, String> {
+        let value = value.trim();
+        let invalid = || format!("Invalid {} value", self.name());
+
+        match self {
+            Encoding::Base58 => base58::decode(value).map_err(|_| invalid()),
+            Encoding::Base58Check => {
+                let bytes = base58::decode(value).map_err(|_| invalid())?;
+                if bytes.len() < CHECKSUM_LENGTH {
+                    return Err(invalid());
+                }
+                let (payload, expected) = bytes.split_at(bytes.len() - CHECKSUM_LENGTH);
+                if checksum(payload) != expected {
+                    return Err("Invalid base58check value: checksum mismatch".to_string());
+                }
+                Ok(payload.to_vec())
+            }
+            Encoding::Base64 => base64::decode(with_padding(value)).map_err(|_| invalid()),
+            Encoding::Base64Url => {
+                base64::decode(with_padding(&from_url_safe(value))).map_err(|_| invalid())
+            }
+            Encoding::Hex => hex::decode(value).map_err(|_| invalid()),
+            Encoding::Multibase => {
+                let mut chars = value.chars();
+                let prefix = chars.next().ok_or_else(invalid)?;
+                let value = chars.as_str();
+                match prefix {
+                    'z' => Encoding::Base58.decode(value),
+                    'm' => Encoding::Base64.decode(value),
+                    'u' => Encoding::Base64Url.decode(value),
+                    'f' | 'F' => Encoding::Hex.decode(value),
+                    prefix => Err(format!(
+                        "Unsupported multibase prefix \"{}\". Expected one of: z, m, u, f",
+                        prefix
+                    )),
+                }
+            }
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Encoding::Base58 => "base58",
+            Encoding::Base58Check => "base58check",
+            Encoding::Base64 => "base64",
+            Encoding::Base64Url => "base64url",
+            Encoding::Hex => "hex",
+            Encoding::Multibase => "multibase",
+        }
+    }
+}
+
+// First bytes of double SHA256 as in Bitcoin addresses
+fn checksum(bytes: &[u8]) -> Vec<u8> {
+    SHA256::digest(&SHA256::digest(bytes))[..CHECKSUM_LENGTH].to_vec()
+}
+
+// Multibase and URL-safe values are usually written without padding
+fn with_padding(value: &str) -> String {
+    let value = value.trim_end_matches('=');
+    let padding = (4 - value.len() % 4) % 4;
+    format!("{}{}", value, "=".repeat(padding))
+}
+
+fn to_url_safe(value: &str) -> String {
+    value
+        .trim_end_matches('=')
+        .replace('+', "-")
+        .replace('/', "_")
+}
+
+fn from_url_safe(value: &str) -> String {
+    value.replace('-', "+").replace('_', "/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode_decode_works() {
+        let bytes = b"\x00\x01indy\xff";
+        for encoding in ENCODINGS {
+            let encoding = Encoding::from_str(encoding).unwrap();
+            let encoded = encoding.encode(bytes);
+            assert_eq!(encoding.decode(&encoded).unwrap(), bytes.to_vec());
+        }
+    }
+
+    #[test]
+    fn decode_works_for_multibase() {
+        assert_eq!(Encoding::Multibase.decode("f696e6479").unwrap(), b"indy");
+        assert_eq!(Encoding::Multibase.decode("maW5keQ").unwrap(), b"indy");
+        assert!(Encoding::Multibase.decode("xabc").is_err());
+    }
+
+    #[test]
+    fn decode_works_for_invalid_checksum() {
+        let encoded = base58::encode(b"indy\0\0\0\0");
+        assert!(Encoding::Base58Check.decode(&encoded).is_err());
+    }
+}
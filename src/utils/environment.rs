@@ -1,7 +1,7 @@
-use std::path::PathBuf;
-
-#[cfg(test)]
-use std::env;
+use std::{
+    env,
+    path::{Path, PathBuf},
+};
 
 pub struct EnvironmentUtils {}
 
@@ -14,9 +14,45 @@ impl EnvironmentUtils {
         } else {
             ".indy_client"
         });
+        EnvironmentUtils::platform_path(path)
+    }
+
+    /// Converts the path to the form expected by the file system of the current platform.
+    /// On Windows absolute paths get the extended-length prefix (`\\?\`),
+    /// so that wallets and pools can be stored deeper than 260 characters and on UNC shares (`\\server\share`).
+    pub fn platform_path<P: Into<PathBuf>>(path: P) -> PathBuf {
+        let path = path.into();
+        if cfg!(windows) {
+            if let Some(extended) = extended_length_path(&path.to_string_lossy()) {
+                return PathBuf::from(extended);
+            }
+        }
         path
     }
 
+    /// Path in the form other programs and libraries parsing it themselves understand (e.g. SQLite URI):
+    /// the extended-length prefix added by `platform_path` is removed. Other paths are returned as is.
+    pub fn standard_path(path: &Path) -> PathBuf {
+        let value = path.to_string_lossy();
+        if let Some(share) = value.strip_prefix(r"\\?\UNC\") {
+            return PathBuf::from(format!(r"\\{}", share));
+        }
+        match value.strip_prefix(r"\\?\") {
+            Some(path) => PathBuf::from(path),
+            None => path.to_path_buf(),
+        }
+    }
+
+    /// Prepares the terminal for CLI output: enables ANSI colors on Windows. Does nothing on other platforms.
+    /// The console code page is left untouched: the standard output is written to the console as UTF-16
+    /// (`WriteConsoleW`), so non-ASCII text is shown correctly with any code page.
+    pub fn init_console() {
+        #[cfg(windows)]
+        {
+            let _ = ansi_term::enable_ansi_support().is_ok();
+        }
+    }
+
     pub fn wallet_home_path() -> PathBuf {
         let mut path = EnvironmentUtils::indy_home_path();
         path.push("wallet");
@@ -70,14 +106,39 @@ impl EnvironmentUtils {
         path
     }
 
-    #[cfg(test)]
+    pub fn pool_capabilities_path(pool_name: &str) -> PathBuf {
+        let mut path = Self::pool_home_path();
+        path.push(pool_name);
+        path.push("capabilities");
+        path.set_extension("json");
+        path
+    }
+
+    pub fn tails_path() -> PathBuf {
+        let mut path = EnvironmentUtils::indy_home_path();
+        path.push("tails");
+        path
+    }
+
+    pub fn queue_path() -> PathBuf {
+        let mut path = EnvironmentUtils::indy_home_path();
+        path.push("queue");
+        path
+    }
+
+    pub fn queued_request_path(id: &str) -> PathBuf {
+        let mut path = Self::queue_path();
+        path.push(id);
+        path.set_extension("json");
+        path
+    }
+
     pub fn tmp_path() -> PathBuf {
         let mut path = env::temp_dir();
         path.push("indy_client");
         path
     }
 
-    #[cfg(test)]
     pub fn tmp_file_path(file_name: &str) -> PathBuf {
         let mut path = EnvironmentUtils::tmp_path();
         path.push(file_name);
@@ -92,6 +153,30 @@ impl EnvironmentUtils {
     }
 }
 
+// Windows does not normalize extended-length paths, so separators must be backslashes
+// and paths containing `.` or `..` components are left as is.
+fn extended_length_path(path: &str) -> Option<String> {
+    const VERBATIM_PREFIX: &str = r"\\?\";
+    const DEVICE_PREFIX: &str = r"\\.\";
+
+    if path.starts_with(VERBATIM_PREFIX) || path.starts_with(DEVICE_PREFIX) {
+        return None;
+    }
+    let path = path.replace('/', "\\");
+    if path.split('\\').any(|part| part == "." || part == "..") {
+        return None;
+    }
+
+    if let Some(share) = path.strip_prefix(r"\\") {
+        return Some(format!(r"{}UNC\{}", VERBATIM_PREFIX, share));
+    }
+    let bytes = path.as_bytes();
+    if bytes.len() >= 3 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':' && bytes[2] == b'\\' {
+        return Some(format!("{}{}", VERBATIM_PREFIX, path));
+    }
+    None
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -145,6 +230,46 @@ mod tests {
         assert!(path.to_string_lossy().contains("pool1"));
     }
 
+    #[test]
+    fn extended_length_path_works() {
+        assert_eq!(
+            extended_length_path(r"C:\Users\indy\.indy_client").unwrap(),
+            r"\\?\C:\Users\indy\.indy_client"
+        );
+        assert_eq!(
+            extended_length_path("C:/Users/indy/.indy_client").unwrap(),
+            r"\\?\C:\Users\indy\.indy_client"
+        );
+        assert_eq!(
+            extended_length_path(r"\\server\share\wallets").unwrap(),
+            r"\\?\UNC\server\share\wallets"
+        );
+    }
+
+    #[test]
+    fn extended_length_path_works_for_unsupported_paths() {
+        assert!(extended_length_path(r"\\?\C:\Users\indy").is_none());
+        assert!(extended_length_path(r"C:\Users\indy\..\wallets").is_none());
+        assert!(extended_length_path("wallets").is_none());
+        assert!(extended_length_path("/home/indy/.indy_client").is_none());
+    }
+
+    #[test]
+    fn standard_path_works() {
+        assert_eq!(
+            EnvironmentUtils::standard_path(Path::new(r"\\?\C:\Users\indy\.indy_client")),
+            PathBuf::from(r"C:\Users\indy\.indy_client")
+        );
+        assert_eq!(
+            EnvironmentUtils::standard_path(Path::new(r"\\?\UNC\server\share\wallets")),
+            PathBuf::from(r"\\server\share\wallets")
+        );
+        assert_eq!(
+            EnvironmentUtils::standard_path(Path::new("/home/indy/.indy_client")),
+            PathBuf::from("/home/indy/.indy_client")
+        );
+    }
+
     #[test]
     fn tmp_path_works() {
         let path = EnvironmentUtils::tmp_path();
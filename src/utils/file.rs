@@ -17,6 +17,20 @@ pub fn read_file<P: AsRef<Path>>(file: P) -> Result<String, String> {
     Ok(content)
 }
 
+// An existing file is never overwritten, on Unix the file is readable by the owner only
+pub fn write_private_file(path: &str, content: &[u8]) -> std::io::Result<()> {
+    let mut options = OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options.open(path)?;
+    file.write_all(content)?;
+    file.sync_all()
+}
+
 pub fn read_lines_from_file<P: AsRef<Path>>(
     file: P,
 ) -> Result<impl Iterator<Item = Result<String, ::std::io::Error>>, String> {
@@ -1,42 +1,83 @@
+use aries_askar::{
+    crypto::{
+        alg::chacha20::{Chacha20Key, C20P},
+        encrypt::KeyAeadInPlace,
+        kdf::{
+            argon2::{Argon2, PARAMS_INTERACTIVE, SALT_LENGTH},
+            KeyDerivation,
+        },
+        random::fill_random,
+        repr::KeySecretBytes,
+    },
+    kms::SecretBytes,
+};
 use linefeed::{Interface, Terminal};
-use std::fs::DirBuilder;
+use std::{
+    fs::{self, DirBuilder, File},
+    io::Read,
+};
 
-use crate::utils::{environment::EnvironmentUtils, file::read_lines_from_file};
+use crate::utils::{environment::EnvironmentUtils, term::print_prompt};
 
 const HISTORY_SIZE: usize = 100;
-const SECRET_DATA: [&str; 2] = [" seed=", " key="];
 
-pub fn load<T>(reader: &mut Interface<T>) -> Result<(), String>
+const ENCRYPTED_HISTORY_HEADER: &[u8] = b"indy-cli-rs encrypted history v1\n";
+const KEYBYTES: usize = 32;
+const NONCEBYTES: usize = 12;
+
+pub fn load<T>(reader: &mut Interface<T>, passphrase: Option<&str>) -> Result<(), String>
 where
     T: Terminal,
 {
     reader.set_history_size(HISTORY_SIZE);
 
     let path = EnvironmentUtils::history_file_path();
+    if !path.exists() {
+        return Ok(());
+    }
 
-    for line in read_lines_from_file(path)? {
-        if let Ok(line) = line {
-            reader.add_history(line)
-        }
+    let content =
+        fs::read(&path).map_err(|err| format!("Can't read CLI history from the file: {}", err))?;
+
+    for line in decode(&content, passphrase)?.lines() {
+        reader.add_history(line.to_string())
     }
     Ok(())
 }
 
+/// Whether the history file has been stored encrypted, so it must not be overwritten
+/// by a session without history encryption.
+pub fn is_encrypted() -> bool {
+    let mut header = [0u8; ENCRYPTED_HISTORY_HEADER.len()];
+    File::open(EnvironmentUtils::history_file_path())
+        .and_then(|mut file| file.read_exact(&mut header))
+        .map(|_| header[..] == *ENCRYPTED_HISTORY_HEADER)
+        .unwrap_or(false)
+}
+
+fn decode(content: &[u8], passphrase: Option<&str>) -> Result<String, String> {
+    match (content.strip_prefix(ENCRYPTED_HISTORY_HEADER), passphrase) {
+        (Some(encrypted), Some(passphrase)) => decrypt(encrypted, passphrase),
+        (Some(_), None) => Err(
+            "CLI history is encrypted. Enable \"encryptHistory\" in the CLI config to use it"
+                .to_string(),
+        ),
+        // History stored before encryption was enabled is taken as is
+        // and will be encrypted on the next persist.
+        (None, _) => Ok(String::from_utf8_lossy(content).to_string()),
+    }
+}
+
+// Lines carrying secrets are kept out of history by the caller
 pub fn add<T>(line: &str, reader: &Interface<T>) -> Result<(), String>
 where
     T: Terminal,
 {
-    let has_secrets = SECRET_DATA
-        .iter()
-        .any(|secret_word| line.contains(secret_word));
-
-    if !has_secrets {
-        reader.add_history(line.to_string());
-    }
+    reader.add_history(line.to_string());
     Ok(())
 }
 
-pub fn persist<T>(reader: &Interface<T>) -> Result<(), String>
+pub fn persist<T>(reader: &Interface<T>, passphrase: Option<&str>) -> Result<(), String>
 where
     T: Terminal,
 {
@@ -50,8 +91,131 @@ where
         }
     }
 
-    reader
-        .save_history(path)
-        .map_err(|err| format!("Can't store CLI history into the file: {}", err))?;
+    match passphrase {
+        Some(passphrase) => {
+            let history = {
+                let writer = reader
+                    .lock_writer_append()
+                    .map_err(|err| format!("Can't read CLI history: {}", err))?;
+                writer
+                    .history()
+                    .map(|entry| format!("{}\n", entry))
+                    .collect::<String>()
+            };
+
+            let mut content = ENCRYPTED_HISTORY_HEADER.to_vec();
+            content.extend(encrypt(&history, passphrase)?);
+
+            fs::write(path, content)
+                .map_err(|err| format!("Can't store CLI history into the file: {}", err))?;
+        }
+        None => {
+            reader
+                .save_history(path)
+                .map_err(|err| format!("Can't store CLI history into the file: {}", err))?;
+        }
+    }
     Ok(())
 }
+
+/// The passphrase is asked twice until the history is encrypted, so that a mistyped one
+/// does not leave the history unreadable.
+pub fn read_passphrase() -> Result<String, String> {
+    let confirm = !is_encrypted();
+    loop {
+        let passphrase = prompt_passphrase("Enter passphrase for CLI history: ")?;
+        if passphrase.is_empty() {
+            continue;
+        }
+        if !confirm || prompt_passphrase("Repeat passphrase for CLI history: ")? == passphrase {
+            return Ok(passphrase);
+        }
+        print_prompt("Passphrases do not match. Please try again.");
+    }
+}
+
+fn prompt_passphrase(prompt: &str) -> Result<String, String> {
+    rpassword::prompt_password(prompt)
+        .map_err(|err| format!("Can't read CLI history passphrase: {}", err))
+}
+
+fn encrypt(history: &str, passphrase: &str) -> Result<Vec<u8>, String> {
+    let mut salt = [0u8; SALT_LENGTH];
+    fill_random(&mut salt);
+
+    let mut nonce = [0u8; NONCEBYTES];
+    fill_random(&mut nonce);
+
+    let key = derive_key(passphrase, &salt)?;
+
+    let mut buffer = SecretBytes::from_slice(history.as_bytes());
+    key.encrypt_in_place(&mut buffer, &nonce, &[])
+        .map_err(|_| "Unable to encrypt CLI history".to_string())?;
+
+    let mut content = Vec::with_capacity(SALT_LENGTH + NONCEBYTES + buffer.len());
+    content.extend_from_slice(&salt);
+    content.extend_from_slice(&nonce);
+    content.extend_from_slice(&buffer);
+    Ok(content)
+}
+
+fn decrypt(content: &[u8], passphrase: &str) -> Result<String, String> {
+    if content.len() < SALT_LENGTH + NONCEBYTES {
+        return Err("Invalid CLI history file: content is too short".to_string());
+    }
+
+    let (salt, rest) = content.split_at(SALT_LENGTH);
+    let (nonce, ciphertext) = rest.split_at(NONCEBYTES);
+
+    let key = derive_key(passphrase, salt)?;
+
+    let mut buffer = SecretBytes::from_slice(ciphertext);
+    key.decrypt_in_place(&mut buffer, nonce, &[])
+        .map_err(|_| "Unable to decrypt CLI history. Passphrase may be incorrect".to_string())?;
+
+    String::from_utf8(buffer.to_vec())
+        .map_err(|_| "Invalid CLI history file: content is not UTF-8".to_string())
+}
+
+fn derive_key(passphrase: &str, salt: &[u8]) -> Result<Chacha20Key<C20P>, String> {
+    let mut key = [0u8; KEYBYTES];
+    Argon2::new(passphrase.as_bytes(), salt, PARAMS_INTERACTIVE)
+        .and_then(|mut kdf| kdf.derive_key_bytes(&mut key))
+        .map_err(|_| "Unable to derive CLI history key".to_string())?;
+
+    Chacha20Key::from_secret_bytes(&key).map_err(|_| "Unable to derive CLI history key".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encrypt_decrypt_works() {
+        let history = "pool list\nwallet list\n";
+        let encrypted = encrypt(history, "passphrase").unwrap();
+        assert!(!encrypted
+            .windows(b"wallet list".len())
+            .any(|window| window == b"wallet list"));
+        assert_eq!(history, decrypt(&encrypted, "passphrase").unwrap());
+
+        decrypt(&encrypted, "other passphrase").unwrap_err();
+        decrypt(&encrypted[..SALT_LENGTH], "passphrase").unwrap_err();
+    }
+
+    #[test]
+    fn decode_works() {
+        let mut content = ENCRYPTED_HISTORY_HEADER.to_vec();
+        content.extend(encrypt("pool list\n", "passphrase").unwrap());
+        assert_eq!("pool list\n", decode(&content, Some("passphrase")).unwrap());
+
+        // Encrypted history is not read as plain text
+        decode(&content, None).unwrap_err();
+
+        assert_eq!("pool list\n", decode(b"pool list\n", None).unwrap());
+        assert_eq!(
+            "pool list\n",
+            decode(b"pool list\n", Some("passphrase")).unwrap()
+        );
+    }
+}
@@ -0,0 +1,122 @@
+use serde_json::Value as JsonValue;
+
+/// Select values by a simplified JSONPath: `$.field.nested[0].*`.
+/// `*` matches all the fields of an object or all the items of an array.
+pub fn select<'a>(value: &'a JsonValue, path: &str) -> Result<Vec<&'a JsonValue>, String> {
+    let mut selected = vec![value];
+
+    for segment in parse(path)? {
+        selected = selected
+            .into_iter()
+            .flat_map(|value| match (&segment, value) {
+                (Segment::Field(field), JsonValue::Object(map)) => {
+                    map.get(field).into_iter().collect::<Vec<_>>()
+                }
+                (Segment::Index(index), JsonValue::Array(items)) => {
+                    items.get(*index).into_iter().collect::<Vec<_>>()
+                }
+                (Segment::Wildcard, JsonValue::Object(map)) => map.values().collect(),
+                (Segment::Wildcard, JsonValue::Array(items)) => items.iter().collect(),
+                _ => Vec::new(),
+            })
+            .collect();
+    }
+
+    Ok(selected)
+}
+
+/// Find values of the field with the given name at any depth.
+pub fn find_field<'a>(value: &'a JsonValue, field: &str) -> Vec<&'a JsonValue> {
+    let mut found = Vec::new();
+    match value {
+        JsonValue::Object(map) => {
+            for (key, value) in map {
+                if key == field {
+                    found.push(value);
+                }
+                found.extend(find_field(value, field));
+            }
+        }
+        JsonValue::Array(items) => {
+            for item in items {
+                found.extend(find_field(item, field));
+            }
+        }
+        _ => {}
+    }
+    found
+}
+
+enum Segment {
+    Field(String),
+    Index(usize),
+    Wildcard,
+}
+
+fn parse(path: &str) -> Result<Vec<Segment>, String> {
+    let invalid_path = || format!("Invalid JSON path \"{}\"", path);
+
+    let rest = path.strip_prefix('$').ok_or_else(invalid_path)?;
+    if !rest.is_empty() && !rest.starts_with('.') {
+        return Err(invalid_path());
+    }
+
+    let mut segments = Vec::new();
+    for part in rest.split('.').skip(1) {
+        let (field, indexes) = match part.find('[') {
+            Some(pos) => part.split_at(pos),
+            None => (part, ""),
+        };
+
+        match field {
+            "" if indexes.is_empty() => return Err(invalid_path()),
+            "" => {}
+            "*" => segments.push(Segment::Wildcard),
+            field => segments.push(Segment::Field(field.to_string())),
+        }
+
+        for index in indexes.split_terminator(']') {
+            let index = index.strip_prefix('[').ok_or_else(invalid_path)?;
+            segments.push(match index {
+                "*" => Segment::Wildcard,
+                index => Segment::Index(index.parse().map_err(|_| invalid_path())?),
+            });
+        }
+    }
+
+    Ok(segments)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn select_works() {
+        let value = json!({"txn": {"data": {"dest": "did", "list": [1, 2]}}});
+        assert_eq!(
+            select(&value, "$.txn.data.dest").unwrap(),
+            vec![&json!("did")]
+        );
+        assert_eq!(
+            select(&value, "$.txn.data.list[1]").unwrap(),
+            vec![&json!(2)]
+        );
+        assert_eq!(select(&value, "$.txn.*.list[*]").unwrap().len(), 2);
+        assert!(select(&value, "$.txn.unknown").unwrap().is_empty());
+    }
+
+    #[test]
+    fn select_works_for_invalid_path() {
+        let value = json!({});
+        assert!(select(&value, "txn.data").is_err());
+        assert!(select(&value, "$.txn..data").is_err());
+        assert!(select(&value, "$.list[a]").is_err());
+    }
+
+    #[test]
+    fn find_field_works() {
+        let value = json!({"endpoint": {"endpoint": "http://agent.example.com"}});
+        assert_eq!(find_field(&value, "endpoint").len(), 2);
+    }
+}
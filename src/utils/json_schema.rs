@@ -0,0 +1,356 @@
+use serde_json::{Map, Value as JsonValue};
+
+// Keywords which only describe the schema
+const ANNOTATIONS: [&str; 6] = [
+    "$schema",
+    "$id",
+    "title",
+    "description",
+    "examples",
+    "default",
+];
+const KEYWORDS: [&str; 14] = [
+    "type",
+    "properties",
+    "required",
+    "additionalProperties",
+    "items",
+    "minItems",
+    "maxItems",
+    "enum",
+    "const",
+    "minLength",
+    "maxLength",
+    "minimum",
+    "maximum",
+    "format",
+];
+const TYPES: [&str; 7] = [
+    "object", "array", "string", "number", "integer", "boolean", "null",
+];
+const FORMATS: [&str; 2] = ["uri", "hostname-port"];
+
+/// Check that the schema uses only the supported subset of JSON Schema:
+/// `type`, `properties`, `required`, `additionalProperties`, `items`, `minItems`, `maxItems`,
+/// `enum`, `const`, `minLength`, `maxLength`, `minimum`, `maximum` and `format` (`uri`, `hostname-port`).
+pub fn check_schema(schema: &JsonValue) -> Result<(), String> {
+    check_schema_at(schema, "#")
+}
+
+fn check_schema_at(schema: &JsonValue, path: &str) -> Result<(), String> {
+    let schema = match schema {
+        JsonValue::Bool(_) => return Ok(()),
+        JsonValue::Object(schema) => schema,
+        _ => return Err(format!("{}: schema must be an object", path)),
+    };
+
+    for (keyword, value) in schema {
+        if ANNOTATIONS.contains(&keyword.as_str()) {
+            continue;
+        }
+        if !KEYWORDS.contains(&keyword.as_str()) {
+            return Err(format!(
+                "{}: keyword \"{}\" is not supported",
+                path, keyword
+            ));
+        }
+        match keyword.as_str() {
+            "type" => {
+                let valid = match value {
+                    JsonValue::String(type_) => TYPES.contains(&type_.as_str()),
+                    JsonValue::Array(types) => types.iter().all(|type_| {
+                        type_
+                            .as_str()
+                            .map(|type_| TYPES.contains(&type_))
+                            .unwrap_or(false)
+                    }),
+                    _ => false,
+                };
+                if !valid {
+                    return Err(format!("{}: invalid \"type\"", path));
+                }
+            }
+            "properties" => {
+                let properties = value
+                    .as_object()
+                    .ok_or_else(|| format!("{}: \"properties\" must be an object", path))?;
+                for (name, property) in properties {
+                    check_schema_at(property, &format!("{}/properties/{}", path, name))?;
+                }
+            }
+            "required" => {
+                if !value
+                    .as_array()
+                    .map(|names| names.iter().all(JsonValue::is_string))
+                    .unwrap_or(false)
+                {
+                    return Err(format!("{}: \"required\" must be a list of names", path));
+                }
+            }
+            "additionalProperties" => {
+                check_schema_at(value, &format!("{}/additionalProperties", path))?
+            }
+            "items" => check_schema_at(value, &format!("{}/items", path))?,
+            "minItems" | "maxItems" | "minLength" | "maxLength" => {
+                if !value.is_u64() {
+                    return Err(format!("{}: \"{}\" must be a number", path, keyword));
+                }
+            }
+            "minimum" | "maximum" => {
+                if !value.is_number() {
+                    return Err(format!("{}: \"{}\" must be a number", path, keyword));
+                }
+            }
+            "enum" => {
+                if !value.is_array() {
+                    return Err(format!("{}: \"enum\" must be a list", path));
+                }
+            }
+            "format" => {
+                if !value
+                    .as_str()
+                    .map(|format| FORMATS.contains(&format))
+                    .unwrap_or(false)
+                {
+                    return Err(format!(
+                        "{}: unsupported \"format\". Supported formats: {}",
+                        path,
+                        FORMATS.join(", ")
+                    ));
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// Validate the value against the schema checked by `check_schema`.
+/// Returns the list of violations, each prefixed with the path of the invalid value (`$.endpoint.ha`).
+pub fn validate(schema: &JsonValue, value: &JsonValue) -> Vec<String> {
+    let mut errors = Vec::new();
+    validate_at(schema, value, "$", &mut errors);
+    errors
+}
+
+fn validate_at(schema: &JsonValue, value: &JsonValue, path: &str, errors: &mut Vec<String>) {
+    let schema = match schema {
+        JsonValue::Bool(true) => return,
+        JsonValue::Bool(false) => {
+            errors.push(format!("{}: value is not allowed", path));
+            return;
+        }
+        JsonValue::Object(schema) => schema,
+        _ => return,
+    };
+
+    if let Some(types) = schema.get("type") {
+        let types = match types {
+            JsonValue::Array(types) => types.iter().filter_map(JsonValue::as_str).collect(),
+            type_ => type_.as_str().into_iter().collect::<Vec<&str>>(),
+        };
+        if !types.iter().any(|type_| has_type(value, type_)) {
+            errors.push(format!("{}: expected {}", path, types.join(" or ")));
+            return;
+        }
+    }
+    if let Some(expected) = schema.get("const") {
+        if value != expected {
+            errors.push(format!("{}: expected {}", path, expected));
+        }
+    }
+    if let Some(JsonValue::Array(allowed)) = schema.get("enum") {
+        if !allowed.contains(value) {
+            let allowed = allowed
+                .iter()
+                .map(JsonValue::to_string)
+                .collect::<Vec<String>>();
+            errors.push(format!("{}: expected one of {}", path, allowed.join(", ")));
+        }
+    }
+
+    match value {
+        JsonValue::Object(object) => validate_object(schema, object, path, errors),
+        JsonValue::Array(items) => {
+            check_bound(schema, "minItems", items.len(), path, "items", errors);
+            check_bound(schema, "maxItems", items.len(), path, "items", errors);
+            if let Some(items_schema) = schema.get("items") {
+                for (index, item) in items.iter().enumerate() {
+                    validate_at(items_schema, item, &format!("{}[{}]", path, index), errors);
+                }
+            }
+        }
+        JsonValue::String(string) => {
+            let length = string.chars().count();
+            check_bound(schema, "minLength", length, path, "characters", errors);
+            check_bound(schema, "maxLength", length, path, "characters", errors);
+            if let Some(format) = schema.get("format").and_then(JsonValue::as_str) {
+                if !has_format(string, format) {
+                    errors.push(format!(
+                        "{}: \"{}\" is not a valid {}",
+                        path, string, format
+                    ));
+                }
+            }
+        }
+        JsonValue::Number(number) => {
+            let number = number.as_f64().unwrap_or_default();
+            if let Some(minimum) = schema.get("minimum").and_then(JsonValue::as_f64) {
+                if number < minimum {
+                    errors.push(format!("{}: must be at least {}", path, minimum));
+                }
+            }
+            if let Some(maximum) = schema.get("maximum").and_then(JsonValue::as_f64) {
+                if number > maximum {
+                    errors.push(format!("{}: must be at most {}", path, maximum));
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+fn validate_object(
+    schema: &Map<String, JsonValue>,
+    object: &Map<String, JsonValue>,
+    path: &str,
+    errors: &mut Vec<String>,
+) {
+    if let Some(JsonValue::Array(required)) = schema.get("required") {
+        for name in required.iter().filter_map(JsonValue::as_str) {
+            if !object.contains_key(name) {
+                errors.push(format!("{}: missing required field \"{}\"", path, name));
+            }
+        }
+    }
+
+    let properties = schema.get("properties").and_then(JsonValue::as_object);
+    for (name, field) in object {
+        let field_path = format!("{}.{}", path, name);
+        match properties.and_then(|properties| properties.get(name)) {
+            Some(field_schema) => validate_at(field_schema, field, &field_path, errors),
+            None => {
+                if let Some(additional) = schema.get("additionalProperties") {
+                    validate_at(additional, field, &field_path, errors)
+                }
+            }
+        }
+    }
+}
+
+fn check_bound(
+    schema: &Map<String, JsonValue>,
+    keyword: &str,
+    actual: usize,
+    path: &str,
+    unit: &str,
+    errors: &mut Vec<String>,
+) {
+    if let Some(bound) = schema.get(keyword).and_then(JsonValue::as_u64) {
+        let bound = bound as usize;
+        if keyword.starts_with("min") && actual < bound {
+            errors.push(format!("{}: expected at least {} {}", path, bound, unit));
+        }
+        if keyword.starts_with("max") && actual > bound {
+            errors.push(format!("{}: expected at most {} {}", path, bound, unit));
+        }
+    }
+}
+
+fn has_type(value: &JsonValue, type_: &str) -> bool {
+    match type_ {
+        "object" => value.is_object(),
+        "array" => value.is_array(),
+        "string" => value.is_string(),
+        "number" => value.is_number(),
+        "integer" => value.is_i64() || value.is_u64(),
+        "boolean" => value.is_boolean(),
+        "null" => value.is_null(),
+        _ => false,
+    }
+}
+
+fn has_format(value: &str, format: &str) -> bool {
+    match format {
+        // scheme:rest, e.g. https://agent.example.com or did:sov:123
+        "uri" => value
+            .split_once(':')
+            .map(|(scheme, rest)| {
+                !rest.is_empty()
+                    && scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+                    && scheme
+                        .chars()
+                        .all(|c| c.is_ascii_alphanumeric() || "+-.".contains(c))
+            })
+            .unwrap_or(false),
+        // Legacy `endpoint.ha` attribute: 127.0.0.1:5555
+        "hostname-port" => value
+            .rsplit_once(':')
+            .map(|(host, port)| !host.is_empty() && port.parse::<u16>().is_ok())
+            .unwrap_or(false),
+        _ => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn endpoint_schema() -> JsonValue {
+        json!({
+            "type": "object",
+            "required": ["endpoint"],
+            "properties": {
+                "endpoint": {"type": "string", "format": "uri"},
+                "routingKeys": {"type": "array", "items": {"type": "string"}, "maxItems": 2},
+                "types": {"type": "array", "items": {"enum": ["endpoint", "did-communication", "DIDComm"]}}
+            },
+            "additionalProperties": false
+        })
+    }
+
+    #[test]
+    fn validate_works() {
+        let schema = endpoint_schema();
+        check_schema(&schema).unwrap();
+
+        let value = json!({"endpoint": "https://agent.example.com", "routingKeys": ["key"], "types": ["DIDComm"]});
+        assert!(validate(&schema, &value).is_empty());
+    }
+
+    #[test]
+    fn validate_works_for_invalid_value() {
+        let value = json!({
+            "endpoint": "agent.example.com",
+            "routingKeys": ["key1", 2, "key3"],
+            "types": ["email"],
+            "extra": true
+        });
+        assert_eq!(
+            vec![
+                "$.endpoint: \"agent.example.com\" is not a valid uri",
+                "$.extra: value is not allowed",
+                "$.routingKeys: expected at most 2 items",
+                "$.routingKeys[1]: expected string",
+                "$.types[0]: expected one of \"endpoint\", \"did-communication\", \"DIDComm\"",
+            ],
+            validate(&endpoint_schema(), &value)
+        );
+        assert_eq!(
+            vec!["$: missing required field \"endpoint\""],
+            validate(&endpoint_schema(), &json!({}))
+        );
+    }
+
+    #[test]
+    fn check_schema_works_for_unsupported_keyword() {
+        let schema =
+            json!({"type": "object", "properties": {"ha": {"type": "string", "pattern": "^.*$"}}});
+        assert_eq!(
+            "#/properties/ha: keyword \"pattern\" is not supported",
+            check_schema(&schema).unwrap_err()
+        );
+        check_schema(&json!({"type": "text"})).unwrap_err();
+        check_schema(&json!({"format": "email"})).unwrap_err();
+    }
+}
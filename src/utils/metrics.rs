@@ -0,0 +1,239 @@
+use std::{
+    collections::BTreeMap,
+    fmt::Write as FmtWrite,
+    io::{BufRead, BufReader, Write},
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, TcpListener, TcpStream},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    thread,
+    time::Duration,
+};
+
+const METRICS_PATH: &str = "/metrics";
+const CONTENT_TYPE: &str = "text/plain; version=0.0.4";
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Metrics in Prometheus text exposition format.
+#[derive(Default)]
+pub struct Metrics {
+    text: String,
+}
+
+impl Metrics {
+    pub fn new() -> Metrics {
+        Metrics::default()
+    }
+
+    pub fn gauge(&mut self, name: &str, help: &str, samples: &[(Vec<(&str, &str)>, f64)]) {
+        self.metric(name, "gauge", help, samples);
+    }
+
+    pub fn counter(&mut self, name: &str, help: &str, samples: &[(Vec<(&str, &str)>, f64)]) {
+        self.metric(name, "counter", help, samples);
+    }
+
+    fn metric(&mut self, name: &str, kind: &str, help: &str, samples: &[(Vec<(&str, &str)>, f64)]) {
+        let _ = writeln!(self.text, "# HELP {} {}", name, help);
+        let _ = writeln!(self.text, "# TYPE {} {}", name, kind);
+        for (labels, value) in samples {
+            let labels = labels
+                .iter()
+                .map(|(key, value)| format!("{}=\"{}\"", key, escape_label(value)))
+                .collect::<Vec<String>>();
+            if labels.is_empty() {
+                let _ = writeln!(self.text, "{} {}", name, value);
+            } else {
+                let _ = writeln!(self.text, "{}{{{}}} {}", name, labels.join(","), value);
+            }
+        }
+    }
+
+    pub fn render(self) -> String {
+        self.text
+    }
+}
+
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// HTTP endpoint serving the latest published metrics at `/metrics` for Prometheus scraping.
+/// Metrics are published by sections, so that several sources can share the endpoint.
+pub struct MetricsServer {
+    sections: Arc<Mutex<BTreeMap<String, String>>>,
+    stopped: Arc<AtomicBool>,
+    local_address: SocketAddr,
+    pub address: String,
+}
+
+impl MetricsServer {
+    /// Listen on `address` (e.g. `0.0.0.0:9100`), the server runs in background until it is dropped.
+    pub fn start(address: &str) -> Result<MetricsServer, String> {
+        let listener = TcpListener::bind(address)
+            .map_err(|err| format!("Unable to listen on \"{}\": {}", address, err))?;
+        let local_address = listener
+            .local_addr()
+            .map_err(|err| format!("Unable to listen on \"{}\": {}", address, err))?;
+
+        let sections = Arc::new(Mutex::new(BTreeMap::<String, String>::new()));
+        let served = sections.clone();
+        let stopped = Arc::new(AtomicBool::new(false));
+        let is_stopped = stopped.clone();
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                if is_stopped.load(Ordering::SeqCst) {
+                    break;
+                }
+                let metrics = served
+                    .lock()
+                    .map(|sections| sections.values().cloned().collect::<String>())
+                    .unwrap_or_default();
+                if let Err(err) = respond(stream, &metrics) {
+                    debug!("Metrics request has failed: {}", err);
+                }
+            }
+        });
+
+        Ok(MetricsServer {
+            sections,
+            stopped,
+            local_address,
+            address: local_address.to_string(),
+        })
+    }
+
+    /// Replace the metrics of the section. A metric must be published in one section only.
+    pub fn publish(&self, section: &str, metrics: Metrics) {
+        if let Ok(mut sections) = self.sections.lock() {
+            sections.insert(section.to_string(), metrics.render());
+        }
+    }
+}
+
+impl Drop for MetricsServer {
+    fn drop(&mut self) {
+        self.stopped.store(true, Ordering::SeqCst);
+        // Wake up the listener blocked on accept so that it notices the stop
+        let _ = TcpStream::connect_timeout(&wake_address(self.local_address), READ_TIMEOUT);
+    }
+}
+
+// The wildcard address the server may listen on cannot be connected to on every platform
+fn wake_address(address: SocketAddr) -> SocketAddr {
+    match address.ip() {
+        IpAddr::V4(ip) if ip.is_unspecified() => {
+            SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), address.port())
+        }
+        IpAddr::V6(ip) if ip.is_unspecified() => {
+            SocketAddr::new(IpAddr::V6(Ipv6Addr::LOCALHOST), address.port())
+        }
+        _ => address,
+    }
+}
+
+fn respond(mut stream: TcpStream, metrics: &str) -> std::io::Result<()> {
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+
+    let mut request_line = String::new();
+    BufReader::new(&stream).read_line(&mut request_line)?;
+    let path = request_line.split_whitespace().nth(1).unwrap_or_default();
+    let path = path.split('?').next().unwrap_or_default();
+
+    let (status, content_type, body) = if path == METRICS_PATH {
+        ("200 OK", CONTENT_TYPE, metrics)
+    } else {
+        ("404 Not Found", "text/plain", "Not Found\n")
+    };
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        content_type,
+        body.len(),
+        body
+    )?;
+    stream.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+
+    #[test]
+    fn metrics_render_works() {
+        let mut metrics = Metrics::new();
+        metrics.gauge(
+            "indy_cli_node_reachable",
+            "Whether the node responds",
+            &[(vec![("pool", "sandbox"), ("node", "Node\"1")], 1.0)],
+        );
+        metrics.counter("indy_cli_requests_total", "Requests", &[(vec![], 5.0)]);
+        assert_eq!(
+            metrics.render(),
+            "# HELP indy_cli_node_reachable Whether the node responds\n\
+            # TYPE indy_cli_node_reachable gauge\n\
+            indy_cli_node_reachable{pool=\"sandbox\",node=\"Node\\\"1\"} 1\n\
+            # HELP indy_cli_requests_total Requests\n\
+            # TYPE indy_cli_requests_total counter\n\
+            indy_cli_requests_total 5\n"
+        );
+    }
+
+    #[test]
+    fn metrics_server_works() {
+        let server = MetricsServer::start("127.0.0.1:0").unwrap();
+        let mut metrics = Metrics::new();
+        metrics.gauge("indy_cli_up", "CLI is running", &[(vec![], 1.0)]);
+        server.publish("cli", metrics);
+        let mut metrics = Metrics::new();
+        metrics.counter("indy_cli_commands_total", "Commands", &[(vec![], 2.0)]);
+        server.publish("commands", metrics);
+
+        let get = |path: &str| {
+            let mut stream = TcpStream::connect(&server.address).unwrap();
+            write!(stream, "GET {} HTTP/1.1\r\nHost: localhost\r\n\r\n", path).unwrap();
+            let mut response = String::new();
+            stream.read_to_string(&mut response).unwrap();
+            response
+        };
+
+        let response = get("/metrics");
+        assert!(response.starts_with("HTTP/1.1 200 OK"));
+        assert!(response.contains("indy_cli_up 1\n"));
+        assert!(response.ends_with("indy_cli_commands_total 2\n"));
+        assert!(get("/").starts_with("HTTP/1.1 404"));
+    }
+
+    #[test]
+    fn metrics_server_stops_for_wildcard_address() {
+        let server = MetricsServer::start("0.0.0.0:0").unwrap();
+        let address = wake_address(server.local_address);
+        assert_eq!(IpAddr::V4(Ipv4Addr::LOCALHOST), address.ip());
+        drop(server);
+
+        // The listener is closed once the server thread has noticed the stop
+        let mut closed = false;
+        for _ in 0..50 {
+            if TcpStream::connect(address).is_err() {
+                closed = true;
+                break;
+            }
+            thread::sleep(Duration::from_millis(20));
+        }
+        assert!(closed);
+    }
+
+    #[test]
+    fn wake_address_works() {
+        let address = "[::]:9100".parse::<SocketAddr>().unwrap();
+        assert_eq!("[::1]:9100", wake_address(address).to_string());
+        let address = "10.0.0.1:9100".parse::<SocketAddr>().unwrap();
+        assert_eq!(address, wake_address(address));
+    }
+}
@@ -3,12 +3,25 @@ pub mod environment;
 pub mod logger;
 #[macro_use]
 pub mod term;
+pub mod clipboard;
+pub mod diff;
+pub mod encoding;
 pub mod file;
 pub mod futures;
 pub mod history;
+pub mod json_path;
+pub mod json_schema;
+pub mod metrics;
+pub mod page;
+pub mod pager;
+pub mod pdf;
+pub mod progress;
+pub mod qr;
+pub mod raster;
+pub mod redirect;
+pub mod renderer;
 pub mod table;
-#[cfg(test)]
-pub mod test;
+pub mod telemetry;
 pub mod time;
 
 #[macro_export] //TODO move to more relevant place
@@ -16,7 +29,7 @@ macro_rules! update_json_map_opt_key {
     ($map:expr, $key:expr, $val:expr) => {
         match $val {
             Some(val) => {
-                $map.insert($key.to_string(), $crate::serde_json::Value::from(val));
+                $map.insert($key.to_string(), serde_json::Value::from(val));
             }
             None => {}
         }
@@ -0,0 +1,22 @@
+// A4 in points
+pub const PAGE_WIDTH: f64 = 595.0;
+pub const PAGE_HEIGHT: f64 = 842.0;
+
+#[derive(Debug, Clone, Copy)]
+pub enum Font {
+    Regular,
+    Bold,
+    Monospace,
+}
+
+/// Single A4 page drawn in black on white.
+/// Coordinates are in points from the bottom left corner of the page.
+pub trait Page {
+    fn text(&mut self, x: f64, y: f64, size: f64, font: Font, text: &str);
+
+    /// Filled black rectangle.
+    fn rect(&mut self, x: f64, y: f64, width: f64, height: f64);
+
+    /// Encoded document.
+    fn to_bytes(&self) -> Result<Vec<u8>, String>;
+}
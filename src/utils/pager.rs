@@ -0,0 +1,120 @@
+use linefeed::{DefaultTerminal, Terminal};
+use std::{
+    env,
+    io::{self, Write},
+    process::{Command, Stdio},
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use crate::utils::{renderer::OutputFormat, term::is_term};
+
+const DEFAULT_PAGER: &str = "less -R";
+
+static PAGER_ENABLED: AtomicBool = AtomicBool::new(false);
+
+pub fn set_enabled(enabled: bool) {
+    PAGER_ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+pub fn is_enabled() -> bool {
+    PAGER_ENABLED.load(Ordering::Relaxed) && is_term()
+}
+
+/// Whether the output does not fit into the terminal and should be shown through the pager.
+pub fn is_required(output: &str) -> bool {
+    if !is_enabled() {
+        return false;
+    }
+    match terminal_height() {
+        Some(height) => output.lines().count() >= height,
+        None => false,
+    }
+}
+
+/// Print output through `$PAGER` (`less` by default) or through the internal pager if it is not available.
+pub fn show(output: &str) {
+    let pager = env::var("PAGER").unwrap_or_else(|_| DEFAULT_PAGER.to_string());
+
+    if run_external_pager(&pager, output).is_err() {
+        run_internal_pager(output);
+    }
+}
+
+pub fn print(output: &str) {
+    if is_required(output) {
+        show(output)
+    } else {
+        println!("{}", output)
+    }
+}
+
+fn terminal_height() -> Option<usize> {
+    let terminal = DefaultTerminal::new().ok()?;
+    let size = terminal.lock_write().size().ok()?;
+    Some(size.lines)
+}
+
+fn run_external_pager(pager: &str, output: &str) -> io::Result<()> {
+    let mut parts = pager.split_whitespace();
+    let program = parts
+        .next()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "Pager is not set"))?;
+
+    let mut child = Command::new(program)
+        .args(parts)
+        .stdin(Stdio::piped())
+        .spawn()?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        // Pager may exit before reading all the output
+        stdin.write_all(output.as_bytes()).ok();
+    }
+    child.wait()?;
+    Ok(())
+}
+
+fn run_internal_pager(output: &str) {
+    let lines = output.lines().collect::<Vec<&str>>();
+    let page_size = terminal_height().unwrap_or(25).saturating_sub(1).max(1);
+
+    let mut position = 0;
+    while position < lines.len() {
+        let end = (position + page_size).min(lines.len());
+        for line in &lines[position..end] {
+            println!("{}", line);
+        }
+        position = end;
+
+        if position >= lines.len() {
+            break;
+        }
+
+        print!(
+            "-- {}/{} -- (Enter: next page, /<text>: search, q: quit) ",
+            position,
+            lines.len()
+        );
+        io::stdout().flush().ok();
+
+        let mut command = String::new();
+        if io::stdin().read_line(&mut command).is_err() {
+            break;
+        }
+
+        match command.trim() {
+            "q" => break,
+            command if command.starts_with('/') => {
+                let pattern = &command[1..];
+                match lines[position..]
+                    .iter()
+                    .position(|line| line.contains(pattern))
+                {
+                    Some(found) => position += found,
+                    // the pager is shown on the terminal only, so its messages are always human readable
+                    None => println_warn!(OutputFormat::Human, "Pattern \"{}\" not found", pattern),
+                }
+            }
+            _ => {}
+        }
+    }
+}
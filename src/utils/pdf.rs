@@ -0,0 +1,130 @@
+use crate::utils::page::{Font, Page, PAGE_HEIGHT, PAGE_WIDTH};
+
+use std::fmt::Write;
+
+/// Single A4 page PDF document drawn with the standard fonts, so no font has to be embedded.
+#[derive(Default)]
+pub struct PdfPage {
+    content: String,
+}
+
+impl PdfPage {
+    pub fn new() -> PdfPage {
+        PdfPage::default()
+    }
+}
+
+impl Page for PdfPage {
+    fn text(&mut self, x: f64, y: f64, size: f64, font: Font, text: &str) {
+        let _ = writeln!(
+            self.content,
+            "BT /{} {} Tf {:.2} {:.2} Td ({}) Tj ET",
+            resource(font),
+            size,
+            x,
+            y,
+            escape(text)
+        );
+    }
+
+    fn rect(&mut self, x: f64, y: f64, width: f64, height: f64) {
+        let _ = writeln!(
+            self.content,
+            "{:.2} {:.2} {:.2} {:.2} re f",
+            x, y, width, height
+        );
+    }
+
+    fn to_bytes(&self) -> Result<Vec<u8>, String> {
+        let objects = [
+            "<< /Type /Catalog /Pages 2 0 R >>".to_string(),
+            "<< /Type /Pages /Kids [3 0 R] /Count 1 >>".to_string(),
+            format!(
+                "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] \
+                /Resources << /Font << /F1 4 0 R /F2 5 0 R /F3 6 0 R >> >> /Contents 7 0 R >>",
+                PAGE_WIDTH, PAGE_HEIGHT
+            ),
+            "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica >>".to_string(),
+            "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica-Bold >>".to_string(),
+            "<< /Type /Font /Subtype /Type1 /BaseFont /Courier >>".to_string(),
+            format!(
+                "<< /Length {} >>\nstream\n{}endstream",
+                self.content.len(),
+                self.content
+            ),
+        ];
+
+        let mut document = String::from("%PDF-1.4\n");
+        let mut offsets = Vec::new();
+        for (index, object) in objects.iter().enumerate() {
+            offsets.push(document.len());
+            let _ = write!(document, "{} 0 obj\n{}\nendobj\n", index + 1, object);
+        }
+
+        let xref = document.len();
+        let _ = write!(
+            document,
+            "xref\n0 {}\n0000000000 65535 f \n",
+            objects.len() + 1
+        );
+        for offset in offsets {
+            let _ = write!(document, "{:010} 00000 n \n", offset);
+        }
+        let _ = write!(
+            document,
+            "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF\n",
+            objects.len() + 1,
+            xref
+        );
+        Ok(document.into_bytes())
+    }
+}
+
+fn resource(font: Font) -> &'static str {
+    match font {
+        Font::Regular => "F1",
+        Font::Bold => "F2",
+        Font::Monospace => "F3",
+    }
+}
+
+// Standard fonts cover Latin-1 only, other characters are replaced
+fn escape(text: &str) -> String {
+    text.chars()
+        .map(|ch| match ch {
+            '\\' | '(' | ')' => format!("\\{}", ch),
+            ch if ch.is_ascii() && !ch.is_ascii_control() => ch.to_string(),
+            _ => "?".to_string(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pdf_page_works() {
+        let mut page = PdfPage::new();
+        page.text(50.0, 800.0, 12.0, Font::Bold, "Key (backup)");
+        page.rect(50.0, 700.0, 5.0, 5.0);
+
+        let document = String::from_utf8(page.to_bytes().unwrap()).unwrap();
+        assert!(document.starts_with("%PDF-1.4\n"));
+        assert!(document.contains("BT /F2 12 Tf 50.00 800.00 Td (Key \\(backup\\)) Tj ET"));
+        assert!(document.contains("50.00 700.00 5.00 5.00 re f"));
+        assert!(document.ends_with("%%EOF\n"));
+
+        // Cross-reference table must point to the objects
+        let xref = document[..document.len() - "\n%%EOF\n".len()]
+            .rsplit('\n')
+            .next()
+            .unwrap()
+            .parse::<usize>()
+            .unwrap();
+        assert!(document[xref..].starts_with("xref\n0 8\n"));
+        let first = document[xref..].lines().nth(3).unwrap();
+        let offset = first[..10].parse::<usize>().unwrap();
+        assert!(document[offset..].starts_with("1 0 obj"));
+    }
+}
@@ -0,0 +1,63 @@
+use std::io::{self, Write};
+
+const BAR_WIDTH: usize = 30;
+
+/// Single line progress bar redrawn in place.
+/// The bar is drawn on stderr so that it never mixes with the command output in any format,
+/// and nothing is drawn when stderr is not a terminal (batch mode, redirected output).
+pub struct ProgressBar {
+    label: String,
+    total: Option<usize>,
+    position: usize,
+    visible: bool,
+}
+
+impl ProgressBar {
+    pub fn new(label: &str, total: Option<usize>, position: usize) -> ProgressBar {
+        let progress = ProgressBar {
+            label: label.to_string(),
+            total,
+            position,
+            visible: atty::is(atty::Stream::Stderr),
+        };
+        progress.draw();
+        progress
+    }
+
+    pub fn inc(&mut self, delta: usize) {
+        self.position += delta;
+        self.draw();
+    }
+
+    pub fn finish(&self) {
+        if self.visible {
+            eprintln!();
+        }
+    }
+
+    fn draw(&self) {
+        if !self.visible {
+            return;
+        }
+        let line = match self.total {
+            Some(total) => {
+                let filled = if total == 0 {
+                    BAR_WIDTH
+                } else {
+                    BAR_WIDTH * self.position.min(total) / total
+                };
+                format!(
+                    "{} [{}{}] {}/{}",
+                    self.label,
+                    "#".repeat(filled),
+                    "-".repeat(BAR_WIDTH - filled),
+                    self.position,
+                    total
+                )
+            }
+            None => format!("{} {}", self.label, self.position),
+        };
+        eprint!("\r{}", line);
+        io::stderr().flush().ok();
+    }
+}
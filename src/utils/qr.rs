@@ -0,0 +1,14 @@
+use qrcode::{render::unicode::Dense1x2, QrCode};
+
+/// Render the data as QR code made of Unicode half blocks (two rows per terminal line).
+/// Colors are inverted so that the code is scannable on dark terminal backgrounds.
+pub fn render(data: &str) -> Result<String, String> {
+    let code =
+        QrCode::new(data.as_bytes()).map_err(|err| format!("Can't build QR code: {}", err))?;
+    Ok(code
+        .render::<Dense1x2>()
+        .dark_color(Dense1x2::Light)
+        .light_color(Dense1x2::Dark)
+        .quiet_zone(true)
+        .build())
+}
@@ -0,0 +1,256 @@
+use crate::utils::page::{Font, Page, PAGE_HEIGHT, PAGE_WIDTH};
+
+use png::{BitDepth, ColorType, Encoder};
+
+// Pixels per point, 216 dpi
+const SCALE: f64 = 3.0;
+const WHITE: u8 = 0xFF;
+const BLACK: u8 = 0x00;
+
+const GLYPH_WIDTH: usize = 5;
+const GLYPH_HEIGHT: usize = 7;
+// Space between the characters in dots
+const GLYPH_SPACING: usize = 1;
+
+// 5x7 bitmap font of the printable ASCII characters starting from the space.
+// A byte is a column of the glyph, the lowest bit is the top row.
+const GLYPHS: [[u8; GLYPH_WIDTH]; 95] = [
+    [0x00, 0x00, 0x00, 0x00, 0x00], // ' '
+    [0x00, 0x00, 0x5F, 0x00, 0x00], // '!'
+    [0x00, 0x07, 0x00, 0x07, 0x00], // '"'
+    [0x14, 0x7F, 0x14, 0x7F, 0x14], // '#'
+    [0x24, 0x2A, 0x7F, 0x2A, 0x12], // '$'
+    [0x23, 0x13, 0x08, 0x64, 0x62], // '%'
+    [0x36, 0x49, 0x55, 0x22, 0x50], // '&'
+    [0x00, 0x05, 0x03, 0x00, 0x00], // '''
+    [0x00, 0x1C, 0x22, 0x41, 0x00], // '('
+    [0x00, 0x41, 0x22, 0x1C, 0x00], // ')'
+    [0x14, 0x08, 0x3E, 0x08, 0x14], // '*'
+    [0x08, 0x08, 0x3E, 0x08, 0x08], // '+'
+    [0x00, 0x50, 0x30, 0x00, 0x00], // ','
+    [0x08, 0x08, 0x08, 0x08, 0x08], // '-'
+    [0x00, 0x60, 0x60, 0x00, 0x00], // '.'
+    [0x20, 0x10, 0x08, 0x04, 0x02], // '/'
+    [0x3E, 0x51, 0x49, 0x45, 0x3E], // '0'
+    [0x00, 0x42, 0x7F, 0x40, 0x00], // '1'
+    [0x42, 0x61, 0x51, 0x49, 0x46], // '2'
+    [0x21, 0x41, 0x45, 0x4B, 0x31], // '3'
+    [0x18, 0x14, 0x12, 0x7F, 0x10], // '4'
+    [0x27, 0x45, 0x45, 0x45, 0x39], // '5'
+    [0x3C, 0x4A, 0x49, 0x49, 0x30], // '6'
+    [0x01, 0x71, 0x09, 0x05, 0x03], // '7'
+    [0x36, 0x49, 0x49, 0x49, 0x36], // '8'
+    [0x06, 0x49, 0x49, 0x29, 0x1E], // '9'
+    [0x00, 0x36, 0x36, 0x00, 0x00], // ':'
+    [0x00, 0x56, 0x36, 0x00, 0x00], // ';'
+    [0x08, 0x14, 0x22, 0x41, 0x00], // '<'
+    [0x14, 0x14, 0x14, 0x14, 0x14], // '='
+    [0x00, 0x41, 0x22, 0x14, 0x08], // '>'
+    [0x02, 0x01, 0x51, 0x09, 0x06], // '?'
+    [0x32, 0x49, 0x79, 0x41, 0x3E], // '@'
+    [0x7E, 0x11, 0x11, 0x11, 0x7E], // 'A'
+    [0x7F, 0x49, 0x49, 0x49, 0x36], // 'B'
+    [0x3E, 0x41, 0x41, 0x41, 0x22], // 'C'
+    [0x7F, 0x41, 0x41, 0x22, 0x1C], // 'D'
+    [0x7F, 0x49, 0x49, 0x49, 0x41], // 'E'
+    [0x7F, 0x09, 0x09, 0x09, 0x01], // 'F'
+    [0x3E, 0x41, 0x49, 0x49, 0x7A], // 'G'
+    [0x7F, 0x08, 0x08, 0x08, 0x7F], // 'H'
+    [0x00, 0x41, 0x7F, 0x41, 0x00], // 'I'
+    [0x20, 0x40, 0x41, 0x3F, 0x01], // 'J'
+    [0x7F, 0x08, 0x14, 0x22, 0x41], // 'K'
+    [0x7F, 0x40, 0x40, 0x40, 0x40], // 'L'
+    [0x7F, 0x02, 0x0C, 0x02, 0x7F], // 'M'
+    [0x7F, 0x04, 0x08, 0x10, 0x7F], // 'N'
+    [0x3E, 0x41, 0x41, 0x41, 0x3E], // 'O'
+    [0x7F, 0x09, 0x09, 0x09, 0x06], // 'P'
+    [0x3E, 0x41, 0x51, 0x21, 0x5E], // 'Q'
+    [0x7F, 0x09, 0x19, 0x29, 0x46], // 'R'
+    [0x46, 0x49, 0x49, 0x49, 0x31], // 'S'
+    [0x01, 0x01, 0x7F, 0x01, 0x01], // 'T'
+    [0x3F, 0x40, 0x40, 0x40, 0x3F], // 'U'
+    [0x1F, 0x20, 0x40, 0x20, 0x1F], // 'V'
+    [0x3F, 0x40, 0x38, 0x40, 0x3F], // 'W'
+    [0x63, 0x14, 0x08, 0x14, 0x63], // 'X'
+    [0x07, 0x08, 0x70, 0x08, 0x07], // 'Y'
+    [0x61, 0x51, 0x49, 0x45, 0x43], // 'Z'
+    [0x00, 0x7F, 0x41, 0x41, 0x00], // '['
+    [0x02, 0x04, 0x08, 0x10, 0x20], // '\'
+    [0x00, 0x41, 0x41, 0x7F, 0x00], // ']'
+    [0x04, 0x02, 0x01, 0x02, 0x04], // '^'
+    [0x40, 0x40, 0x40, 0x40, 0x40], // '_'
+    [0x00, 0x01, 0x02, 0x04, 0x00], // '`'
+    [0x20, 0x54, 0x54, 0x54, 0x78], // 'a'
+    [0x7F, 0x48, 0x44, 0x44, 0x38], // 'b'
+    [0x38, 0x44, 0x44, 0x44, 0x20], // 'c'
+    [0x38, 0x44, 0x44, 0x48, 0x7F], // 'd'
+    [0x38, 0x54, 0x54, 0x54, 0x18], // 'e'
+    [0x08, 0x7E, 0x09, 0x01, 0x02], // 'f'
+    [0x0C, 0x52, 0x52, 0x52, 0x3E], // 'g'
+    [0x7F, 0x08, 0x04, 0x04, 0x78], // 'h'
+    [0x00, 0x44, 0x7D, 0x40, 0x00], // 'i'
+    [0x20, 0x40, 0x44, 0x3D, 0x00], // 'j'
+    [0x7F, 0x10, 0x28, 0x44, 0x00], // 'k'
+    [0x00, 0x41, 0x7F, 0x40, 0x00], // 'l'
+    [0x7C, 0x04, 0x18, 0x04, 0x78], // 'm'
+    [0x7C, 0x08, 0x04, 0x04, 0x78], // 'n'
+    [0x38, 0x44, 0x44, 0x44, 0x38], // 'o'
+    [0x7C, 0x14, 0x14, 0x14, 0x08], // 'p'
+    [0x08, 0x14, 0x14, 0x18, 0x7C], // 'q'
+    [0x7C, 0x08, 0x04, 0x04, 0x08], // 'r'
+    [0x48, 0x54, 0x54, 0x54, 0x20], // 's'
+    [0x04, 0x3F, 0x44, 0x40, 0x20], // 't'
+    [0x3C, 0x40, 0x40, 0x20, 0x7C], // 'u'
+    [0x1C, 0x20, 0x40, 0x20, 0x1C], // 'v'
+    [0x3C, 0x40, 0x30, 0x40, 0x3C], // 'w'
+    [0x44, 0x28, 0x10, 0x28, 0x44], // 'x'
+    [0x0C, 0x50, 0x50, 0x50, 0x3C], // 'y'
+    [0x44, 0x64, 0x54, 0x4C, 0x44], // 'z'
+    [0x00, 0x08, 0x36, 0x41, 0x00], // '{'
+    [0x00, 0x00, 0x7F, 0x00, 0x00], // '|'
+    [0x00, 0x41, 0x36, 0x08, 0x00], // '}'
+    [0x08, 0x04, 0x08, 0x10, 0x08], // '~'
+];
+
+/// Single A4 page rendered into a grayscale PNG image.
+/// The text is drawn with a 5x7 bitmap font, so the fonts differ in the bold stroke only.
+pub struct RasterPage {
+    width: usize,
+    height: usize,
+    pixels: Vec<u8>,
+}
+
+impl Default for RasterPage {
+    fn default() -> Self {
+        let (width, height) = (to_pixels(PAGE_WIDTH), to_pixels(PAGE_HEIGHT));
+        RasterPage {
+            width,
+            height,
+            pixels: vec![WHITE; width * height],
+        }
+    }
+}
+
+impl RasterPage {
+    pub fn new() -> RasterPage {
+        RasterPage::default()
+    }
+
+    // Pixel coordinates from the top left corner of the image
+    fn fill(&mut self, x: usize, y: usize, width: usize, height: usize) {
+        let (left, right) = (x.min(self.width), (x + width).min(self.width));
+        for row in y.min(self.height)..(y + height).min(self.height) {
+            self.pixels[row * self.width + left..row * self.width + right].fill(BLACK);
+        }
+    }
+}
+
+impl Page for RasterPage {
+    fn text(&mut self, x: f64, y: f64, size: f64, font: Font, text: &str) {
+        // Glyph cell is 6x8 dots
+        let dot = ((size * SCALE / 10.0) as usize).max(1);
+        let stroke = match font {
+            Font::Bold => dot + 1,
+            Font::Regular | Font::Monospace => dot,
+        };
+        let top = self
+            .height
+            .saturating_sub(to_pixels(y))
+            .saturating_sub(GLYPH_HEIGHT * dot);
+
+        let mut left = to_pixels(x);
+        for ch in text.chars() {
+            for (column, bits) in glyph(ch).iter().enumerate() {
+                for row in 0..GLYPH_HEIGHT {
+                    if (bits >> row) & 1 == 1 {
+                        self.fill(left + column * dot, top + row * dot, stroke, dot);
+                    }
+                }
+            }
+            left += (GLYPH_WIDTH + GLYPH_SPACING) * dot;
+        }
+    }
+
+    fn rect(&mut self, x: f64, y: f64, width: f64, height: f64) {
+        let (left, right) = (to_pixels(x), to_pixels(x + width));
+        let top = self.height.saturating_sub(to_pixels(y + height));
+        let bottom = self.height.saturating_sub(to_pixels(y));
+        self.fill(
+            left,
+            top,
+            right.saturating_sub(left),
+            bottom.saturating_sub(top),
+        );
+    }
+
+    fn to_bytes(&self) -> Result<Vec<u8>, String> {
+        let error = |err: png::EncodingError| format!("Can't encode PNG image: {}", err);
+
+        let mut bytes = Vec::new();
+        let mut encoder = Encoder::new(&mut bytes, self.width as u32, self.height as u32);
+        encoder.set_color(ColorType::Grayscale);
+        encoder.set_depth(BitDepth::Eight);
+        let mut writer = encoder.write_header().map_err(error)?;
+        writer.write_image_data(&self.pixels).map_err(error)?;
+        writer.finish().map_err(error)?;
+        Ok(bytes)
+    }
+}
+
+fn to_pixels(points: f64) -> usize {
+    (points * SCALE).round().max(0.0) as usize
+}
+
+// Characters out of the font are drawn as '?'
+fn glyph(ch: char) -> &'static [u8; GLYPH_WIDTH] {
+    let index = match ch {
+        ' '..='~' => ch as usize - ' ' as usize,
+        _ => '?' as usize - ' ' as usize,
+    };
+    &GLYPHS[index]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pixel(page: &RasterPage, x: usize, y: usize) -> u8 {
+        page.pixels[y * page.width + x]
+    }
+
+    #[test]
+    fn raster_page_works() {
+        let mut page = RasterPage::new();
+        assert_eq!((1785, 2526), (page.width, page.height));
+
+        // 10x10 points square in the bottom left corner
+        page.rect(0.0, 0.0, 10.0, 10.0);
+        assert_eq!(BLACK, pixel(&page, 0, page.height - 1));
+        assert_eq!(BLACK, pixel(&page, 29, page.height - 30));
+        assert_eq!(WHITE, pixel(&page, 30, page.height - 1));
+        assert_eq!(WHITE, pixel(&page, 0, page.height - 31));
+
+        // '|' is a single column in the middle of the glyph standing on the baseline
+        page.text(100.0, 100.0, 10.0, Font::Regular, "|");
+        let (x, baseline) = (300 + 2 * 3, page.height - 300);
+        assert_eq!(BLACK, pixel(&page, x, baseline - 1));
+        assert_eq!(BLACK, pixel(&page, x, baseline - 21));
+        assert_eq!(WHITE, pixel(&page, x, baseline - 22));
+        assert_eq!(WHITE, pixel(&page, x, baseline));
+        assert_eq!(WHITE, pixel(&page, x - 1, baseline - 1));
+
+        // Drawing out of the page is clipped
+        page.rect(590.0, 830.0, 100.0, 100.0);
+        page.text(590.0, 840.0, 20.0, Font::Bold, "clipped");
+
+        let png = page.to_bytes().unwrap();
+        assert!(png.starts_with(b"\x89PNG\r\n\x1a\n"));
+    }
+
+    #[test]
+    fn glyph_works() {
+        assert_eq!(&GLYPHS[0], glyph(' '));
+        assert_eq!(&[0x7E, 0x11, 0x11, 0x11, 0x7E], glyph('A'));
+        assert_eq!(glyph('?'), glyph('é'));
+    }
+}
@@ -0,0 +1,170 @@
+use gag::Redirect;
+use std::{
+    fs::{File, OpenOptions},
+    io::{self, Write},
+};
+
+/// Output redirection requested by a trailing `> file` or `>> file` of a command line.
+#[derive(Debug, PartialEq)]
+pub struct Redirection {
+    pub path: String,
+    pub append: bool,
+}
+
+/// Active redirection of the process stdout. The output goes back to the terminal on drop.
+pub struct RedirectGuard {
+    _redirect: Redirect<File>,
+}
+
+impl Redirection {
+    pub fn start(&self) -> Result<RedirectGuard, String> {
+        let file = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .append(self.append)
+            .truncate(!self.append)
+            .open(&self.path)
+            .map_err(|err| format!("Can't open file \"{}\" for output: {}", self.path, err))?;
+
+        io::stdout().flush().ok();
+        let redirect = Redirect::stdout(file)
+            .map_err(|err| format!("Can't redirect output to \"{}\": {}", self.path, err))?;
+
+        Ok(RedirectGuard {
+            _redirect: redirect,
+        })
+    }
+}
+
+impl Drop for RedirectGuard {
+    fn drop(&mut self) {
+        io::stdout().flush().ok();
+    }
+}
+
+/// Split a trailing redirection off the command line.
+/// `>` inside quoted values (e.g. JSON strings) and `>` not separated by whitespace are left as is.
+pub fn split_redirection(line: &str) -> Result<(&str, Option<Redirection>), String> {
+    let line = line.trim();
+
+    let mut is_quote_escape = false;
+    let mut is_quoted = false;
+    let mut redirect_pos = None;
+    let mut prev = ' ';
+
+    for (pos, ch) in line.char_indices() {
+        if !is_quote_escape && ch == '"' {
+            is_quoted = !is_quoted;
+        }
+
+        if !is_quoted && ch == '>' && prev.is_whitespace() && pos > 0 {
+            redirect_pos = Some(pos);
+        }
+
+        is_quote_escape = ch == '\\';
+        prev = ch;
+    }
+
+    let pos = match redirect_pos {
+        Some(pos) => pos,
+        None => return Ok((line, None)),
+    };
+
+    let (append, target) = match line[pos + 1..].strip_prefix('>') {
+        Some(target) => (true, target),
+        None => (false, &line[pos + 1..]),
+    };
+
+    let target = target.trim();
+    let path = match target.strip_prefix('"').and_then(|t| t.strip_suffix('"')) {
+        Some(path) => path,
+        None if target.contains(char::is_whitespace) => {
+            return Err(format!(
+                "Invalid output redirection \"{}\": the file path containing spaces must be quoted",
+                target
+            ))
+        }
+        None => target,
+    };
+
+    if path.is_empty() {
+        return Err("Invalid output redirection: the file path is missing".to_string());
+    }
+
+    Ok((
+        line[..pos].trim_end(),
+        Some(Redirection {
+            path: path.to_string(),
+            append,
+        }),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::{environment::EnvironmentUtils, term::print_prompt};
+    use std::fs;
+
+    #[test]
+    fn split_redirection_works() {
+        assert_eq!(
+            split_redirection("ledger get-nym did=V4SGRU86Z58d6TV7PBUe6f > nym.json").unwrap(),
+            (
+                "ledger get-nym did=V4SGRU86Z58d6TV7PBUe6f",
+                Some(Redirection {
+                    path: "nym.json".to_string(),
+                    append: false
+                })
+            )
+        );
+        assert_eq!(
+            split_redirection("pool list >> \"/tmp/my pools.txt\"").unwrap(),
+            (
+                "pool list",
+                Some(Redirection {
+                    path: "/tmp/my pools.txt".to_string(),
+                    append: true
+                })
+            )
+        );
+    }
+
+    #[test]
+    fn split_redirection_works_for_no_redirection() {
+        assert_eq!(
+            split_redirection(r#"ledger custom {"op":"x > y"}"#).unwrap(),
+            (r#"ledger custom {"op":"x > y"}"#, None)
+        );
+        assert_eq!(
+            split_redirection("ledger custom a=b>c").unwrap(),
+            ("ledger custom a=b>c", None)
+        );
+    }
+
+    #[test]
+    fn redirection_keeps_prompts_out_of_file() {
+        let path = EnvironmentUtils::tmp_file_path("redirect.txt");
+        fs::create_dir_all(EnvironmentUtils::tmp_path()).unwrap();
+        {
+            let _redirect = Redirection {
+                path: path.to_string_lossy().to_string(),
+                append: false,
+            }
+            .start()
+            .unwrap();
+            io::stdout().write_all(b"command output\n").unwrap();
+            print_prompt("Would you like to continue? (y/n)");
+        }
+        let content = fs::read_to_string(&path).unwrap();
+        assert!(content.contains("command output"));
+        assert!(!content.contains("continue"));
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn split_redirection_works_for_invalid_target() {
+        assert!(split_redirection("pool list >").is_err());
+        assert!(split_redirection("pool list > my pools.txt").is_err());
+    }
+}
@@ -0,0 +1,290 @@
+use crate::utils::{
+    table::{self, cell_value},
+    term::is_term,
+};
+
+use serde_json::{Map as JsonMap, Value as JsonValue};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OutputFormat {
+    Human = 0,
+    Json = 1,
+    Csv = 2,
+    Quiet = 3,
+}
+
+impl OutputFormat {
+    pub const NAMES: [&'static str; 4] = ["human", "json", "csv", "quiet"];
+
+    pub fn from_str(format: &str) -> Option<OutputFormat> {
+        match format {
+            // `text` is the name used by the CLI config before other formats were introduced
+            "human" | "text" => Some(OutputFormat::Human),
+            "json" => Some(OutputFormat::Json),
+            "csv" => Some(OutputFormat::Csv),
+            "quiet" => Some(OutputFormat::Quiet),
+            _ => None,
+        }
+    }
+
+    pub fn from_u8(format: u8) -> OutputFormat {
+        match format {
+            1 => OutputFormat::Json,
+            2 => OutputFormat::Csv,
+            3 => OutputFormat::Quiet,
+            _ => OutputFormat::Human,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        Self::NAMES[*self as usize]
+    }
+
+    pub fn renderer(&self) -> &'static dyn Renderer {
+        match self {
+            OutputFormat::Human => &HumanRenderer,
+            OutputFormat::Json => &JsonRenderer,
+            OutputFormat::Csv => &CsvRenderer,
+            OutputFormat::Quiet => &QuietRenderer,
+        }
+    }
+}
+
+/// Destination of command output which knows the format selected for it.
+/// The command context carries the format set by `outputFormat` config option or `set output-format` command.
+pub trait Output {
+    fn output_format(&self) -> OutputFormat;
+
+    fn renderer(&self) -> &'static dyn Renderer {
+        self.output_format().renderer()
+    }
+}
+
+impl Output for OutputFormat {
+    fn output_format(&self) -> OutputFormat {
+        *self
+    }
+}
+
+impl<T: Output + ?Sized> Output for &T {
+    fn output_format(&self) -> OutputFormat {
+        (**self).output_format()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Level {
+    Success,
+    Warning,
+    Error,
+    Accent,
+}
+
+impl Level {
+    fn name(&self) -> &'static str {
+        match self {
+            Level::Success => "success",
+            Level::Warning => "warning",
+            Level::Error => "error",
+            Level::Accent => "info",
+        }
+    }
+}
+
+/// Presentation of command results.
+/// Commands describe what to show (a message, a table, a JSON value) and the renderer of the
+/// output format kept in the command context decides how it looks.
+pub trait Renderer {
+    fn message(&self, level: Level, text: &str);
+
+    fn error(&self, code: &str, message: &str, hint: Option<&str>);
+
+    /// Rows are JSON objects, `headers` are pairs of the row field and the column title.
+    fn table(&self, rows: &[JsonValue], headers: &[(&str, &str)], empty_msg: &str);
+
+    fn value(&self, value: &JsonValue);
+}
+
+pub struct HumanRenderer;
+
+pub struct JsonRenderer;
+
+pub struct CsvRenderer;
+
+pub struct QuietRenderer;
+
+impl Renderer for HumanRenderer {
+    fn message(&self, level: Level, text: &str) {
+        if !is_term() {
+            trace!("{}", text);
+            return println!("{}", text);
+        }
+        let style = match level {
+            Level::Success => {
+                trace!("{}", text);
+                ansi_term::Color::Green.bold()
+            }
+            Level::Warning => ansi_term::Color::Yellow.bold(),
+            Level::Error => {
+                error!("{}", text);
+                ansi_term::Color::Red.bold()
+            }
+            Level::Accent => {
+                trace!("{}", text);
+                ansi_term::Style::new().bold()
+            }
+        };
+        println!("{}", style.paint(text))
+    }
+
+    fn error(&self, code: &str, message: &str, hint: Option<&str>) {
+        self.message(Level::Error, &format!("{} [{}]", message, code));
+        if let Some(hint) = hint {
+            println!("{}", hint);
+        }
+    }
+
+    fn table(&self, rows: &[JsonValue], headers: &[(&str, &str)], empty_msg: &str) {
+        if rows.is_empty() {
+            return self.message(Level::Success, empty_msg);
+        }
+        table::print_human(rows, headers)
+    }
+
+    fn value(&self, value: &JsonValue) {
+        match value {
+            JsonValue::String(value) => println!("{}", value),
+            value => println!(
+                "{}",
+                serde_json::to_string_pretty(value).unwrap_or_else(|_| value.to_string())
+            ),
+        }
+    }
+}
+
+impl Renderer for JsonRenderer {
+    fn message(&self, level: Level, text: &str) {
+        println!("{}", json!({ "level": level.name(), "message": text }))
+    }
+
+    fn error(&self, code: &str, message: &str, hint: Option<&str>) {
+        println!(
+            "{}",
+            json!({
+                "error": {
+                    "code": code,
+                    "message": message,
+                    "hint": hint,
+                }
+            })
+        )
+    }
+
+    fn table(&self, rows: &[JsonValue], headers: &[(&str, &str)], _empty_msg: &str) {
+        let rows = rows
+            .iter()
+            .map(|row| {
+                headers
+                    .iter()
+                    .map(|(key, _)| (key.to_string(), row[key].clone()))
+                    .collect::<JsonMap<String, JsonValue>>()
+            })
+            .collect::<Vec<JsonMap<String, JsonValue>>>();
+        println!("{}", json!(rows))
+    }
+
+    fn value(&self, value: &JsonValue) {
+        println!("{}", value)
+    }
+}
+
+// Messages go to stderr so that stdout contains the data only
+impl Renderer for CsvRenderer {
+    fn message(&self, level: Level, text: &str) {
+        eprintln!("{}: {}", level.name(), text)
+    }
+
+    fn error(&self, code: &str, message: &str, hint: Option<&str>) {
+        match hint {
+            Some(hint) => eprintln!("error: [{}] {} {}", code, message, hint),
+            None => eprintln!("error: [{}] {}", code, message),
+        }
+    }
+
+    fn table(&self, rows: &[JsonValue], headers: &[(&str, &str)], _empty_msg: &str) {
+        let titles = headers.iter().map(|(_, title)| csv_field(title));
+        println!("{}", titles.collect::<Vec<String>>().join(","));
+        for row in rows {
+            let fields = headers
+                .iter()
+                .map(|(key, _)| csv_field(&cell_value(&row[key])));
+            println!("{}", fields.collect::<Vec<String>>().join(","));
+        }
+    }
+
+    fn value(&self, value: &JsonValue) {
+        let rows = match value.as_array() {
+            Some(rows) if rows.iter().all(JsonValue::is_object) => rows,
+            _ => return println!("{}", csv_field(&cell_value(value))),
+        };
+        let keys = rows
+            .first()
+            .and_then(JsonValue::as_object)
+            .map(|row| row.keys().map(String::as_str).collect::<Vec<&str>>())
+            .unwrap_or_default();
+        let headers = keys.iter().map(|key| (*key, *key)).collect::<Vec<_>>();
+        self.table(rows, &headers, "")
+    }
+}
+
+// Only errors are shown, the result is reported by the exit code
+impl Renderer for QuietRenderer {
+    fn message(&self, level: Level, text: &str) {
+        if level == Level::Error {
+            eprintln!("{}", text)
+        }
+    }
+
+    fn error(&self, code: &str, message: &str, _hint: Option<&str>) {
+        eprintln!("[{}] {}", code, message)
+    }
+
+    fn table(&self, _rows: &[JsonValue], _headers: &[(&str, &str)], _empty_msg: &str) {}
+
+    fn value(&self, _value: &JsonValue) {}
+}
+
+// RFC 4180: quote fields containing separators, quotes or line breaks
+fn csv_field(value: &str) -> String {
+    if value.contains(|c| matches!(c, ',' | '"' | '\n' | '\r')) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Show a structured result of a command.
+pub fn print_value(output: &dyn Output, value: &JsonValue) {
+    output.renderer().value(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn output_format_from_str_works() {
+        for name in OutputFormat::NAMES {
+            assert_eq!(OutputFormat::from_str(name).unwrap().name(), name);
+        }
+        assert_eq!(OutputFormat::from_str("text"), Some(OutputFormat::Human));
+        assert_eq!(OutputFormat::from_str("xml"), None);
+    }
+
+    #[test]
+    fn csv_field_works() {
+        assert_eq!(csv_field("value"), "value");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+    }
+}
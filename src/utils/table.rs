@@ -1,11 +1,24 @@
+use crate::utils::{pager, renderer::Output};
+
 use prettytable::{Cell, Row, Table};
 use term::{color, Attr};
 
-pub fn print_list_table(rows: &[serde_json::Value], headers: &[(&str, &str)], empty_msg: &str) {
-    if rows.is_empty() {
-        return println_succ!("{}", empty_msg);
-    }
+pub fn print_list_table(
+    output: &dyn Output,
+    rows: &[serde_json::Value],
+    headers: &[(&str, &str)],
+    empty_msg: &str,
+) {
+    output.renderer().table(rows, headers, empty_msg)
+}
+
+pub fn print_table(output: &dyn Output, row: &serde_json::Value, headers: &[(&str, &str)]) {
+    output
+        .renderer()
+        .table(std::slice::from_ref(row), headers, "")
+}
 
+pub(crate) fn print_human(rows: &[serde_json::Value], headers: &[(&str, &str)]) {
     let mut table = Table::new();
 
     print_header(&mut table, headers);
@@ -14,17 +27,16 @@ pub fn print_list_table(rows: &[serde_json::Value], headers: &[(&str, &str)], em
         print_row(&mut table, row, headers);
     }
 
-    table.printstd();
+    print(&table);
 }
 
-pub fn print_table(row: &serde_json::Value, headers: &[(&str, &str)]) {
-    let mut table = Table::new();
-
-    print_header(&mut table, headers);
-
-    print_row(&mut table, row, headers);
-
-    table.printstd();
+fn print(table: &Table) {
+    let output = table.to_string();
+    if pager::is_required(&output) {
+        pager::show(&output)
+    } else {
+        table.printstd();
+    }
 }
 
 pub fn print_header(table: &mut Table, headers: &[(&str, &str)]) {
@@ -45,38 +57,30 @@ pub fn print_row(table: &mut Table, row: &serde_json::Value, headers: &[(&str, &
     let columns = headers
         .iter()
         .clone()
-        .map(|&(ref key, _)| {
-            let mut value = "-".to_string();
-            if row[key].is_string() {
-                value = row[key].as_str().unwrap().to_string()
-            }
-            if row[key].is_i64() {
-                value = row[key].as_i64().unwrap().to_string();
-            }
-            if row[key].is_boolean() {
-                value = row[key].as_bool().unwrap().to_string()
-            }
-            if row[key].is_array() {
-                value = row[key]
-                    .as_array()
-                    .unwrap()
-                    .iter()
-                    .map(|v| v.to_string())
-                    .collect::<Vec<String>>()
-                    .join(",")
-            }
-            if row[key].is_object() {
-                value = row[key]
-                    .as_object()
-                    .unwrap()
-                    .iter()
-                    .map(|(key, value)| format!("{}:{}", key, value))
-                    .collect::<Vec<String>>()
-                    .join(",");
-                value = format!("{{{}}}", value)
-            }
-            Cell::new(&value)
-        })
+        .map(|&(ref key, _)| Cell::new(&cell_value(&row[key])))
         .collect::<Vec<Cell>>();
     table.add_row(Row::new(columns));
 }
+
+/// Text of a table cell, shared by all the renderers showing tables. Missing values are shown as `-`.
+pub fn cell_value(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::String(value) => value.to_string(),
+        serde_json::Value::Number(value) => value.to_string(),
+        serde_json::Value::Bool(value) => value.to_string(),
+        serde_json::Value::Array(values) => values
+            .iter()
+            .map(|v| v.to_string())
+            .collect::<Vec<String>>()
+            .join(","),
+        serde_json::Value::Object(values) => {
+            let value = values
+                .iter()
+                .map(|(key, value)| format!("{}:{}", key, value))
+                .collect::<Vec<String>>()
+                .join(",");
+            format!("{{{}}}", value)
+        }
+        serde_json::Value::Null => "-".to_string(),
+    }
+}
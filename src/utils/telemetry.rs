@@ -0,0 +1,459 @@
+use aries_askar::crypto::random::fill_random;
+use serde_json::Value as JsonValue;
+use std::{
+    sync::mpsc::{channel, Sender},
+    thread::{self, JoinHandle},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+const SERVICE_NAME: &str = "indy-cli-rs";
+const TRACES_PATH: &str = "/v1/traces";
+const METRICS_PATH: &str = "/v1/metrics";
+const EXPORT_TIMEOUT: Duration = Duration::from_secs(2);
+
+// Default port of OTLP/HTTP collectors
+const DEFAULT_HTTP_PORT: u16 = 4318;
+const DEFAULT_HTTPS_PORT: u16 = 443;
+
+// OTLP span kind, status codes and aggregation temporality
+const SPAN_KIND_INTERNAL: u8 = 1;
+const STATUS_CODE_OK: u8 = 1;
+const STATUS_CODE_ERROR: u8 = 2;
+const AGGREGATION_TEMPORALITY_DELTA: u8 = 1;
+
+const COMMAND_COUNT_METRIC: &str = "indy.cli.command.count";
+const COMMAND_DURATION_METRIC: &str = "indy.cli.command.duration";
+const LEDGER_LATENCY_METRIC: &str = "indy.ledger.request.latency";
+const LEDGER_LATENCY_ATTRIBUTE: &str = "indy.ledger.request.latency_ms";
+// Span attributes copied to the data points of the command metrics
+const METRIC_ATTRIBUTES: [&str; 2] = ["indy.pool.name", "indy.txn.type"];
+
+pub struct Span {
+    name: String,
+    start: SystemTime,
+    attributes: Vec<(&'static str, JsonValue)>,
+}
+
+impl Span {
+    pub fn start(name: &str) -> Span {
+        Span {
+            name: name.to_string(),
+            start: SystemTime::now(),
+            attributes: Vec::new(),
+        }
+    }
+
+    pub fn set_attribute<T: Into<JsonValue>>(&mut self, key: &'static str, value: T) {
+        self.attributes.push((key, value.into()));
+    }
+
+    fn attribute(&self, key: &str) -> Option<&JsonValue> {
+        self.attributes
+            .iter()
+            .find(|(name, _)| *name == key)
+            .map(|(_, value)| value)
+    }
+
+    fn to_otlp_json(&self, end: SystemTime, success: bool) -> JsonValue {
+        let attributes = self
+            .attributes
+            .iter()
+            .map(|(key, value)| otlp_attribute(key, value))
+            .collect::<Vec<JsonValue>>();
+
+        json!({
+            "resourceSpans": [{
+                "resource": otlp_resource(),
+                "scopeSpans": [{
+                    "scope": { "name": SERVICE_NAME },
+                    "spans": [{
+                        "traceId": random_id(16),
+                        "spanId": random_id(8),
+                        "name": self.name,
+                        "kind": SPAN_KIND_INTERNAL,
+                        "startTimeUnixNano": unix_nanos(self.start),
+                        "endTimeUnixNano": unix_nanos(end),
+                        "attributes": attributes,
+                        "status": {
+                            "code": if success { STATUS_CODE_OK } else { STATUS_CODE_ERROR }
+                        }
+                    }]
+                }]
+            }]
+        })
+    }
+
+    /// Command counter and duration (and ledger request latency if there was a request)
+    /// as delta data points of a single command execution.
+    fn to_otlp_metrics_json(&self, end: SystemTime, success: bool) -> JsonValue {
+        let mut attributes = vec![
+            otlp_attribute("indy.command", &json!(self.name)),
+            otlp_attribute(
+                "indy.command.status",
+                &json!(if success { "ok" } else { "error" }),
+            ),
+        ];
+        attributes.extend(
+            METRIC_ATTRIBUTES
+                .iter()
+                .filter_map(|key| self.attribute(key).map(|value| otlp_attribute(key, value))),
+        );
+
+        let start = unix_nanos(self.start);
+        let end_nanos = unix_nanos(end);
+        let duration = end
+            .duration_since(self.start)
+            .unwrap_or_default()
+            .as_secs_f64()
+            * 1000.0;
+
+        let mut metrics = vec![
+            json!({
+                "name": COMMAND_COUNT_METRIC,
+                "unit": "1",
+                "sum": {
+                    "aggregationTemporality": AGGREGATION_TEMPORALITY_DELTA,
+                    "isMonotonic": true,
+                    "dataPoints": [{
+                        "attributes": attributes,
+                        "startTimeUnixNano": start,
+                        "timeUnixNano": end_nanos,
+                        "asInt": "1"
+                    }]
+                }
+            }),
+            otlp_histogram(
+                COMMAND_DURATION_METRIC,
+                &attributes,
+                &start,
+                &end_nanos,
+                duration,
+            ),
+        ];
+        if let Some(latency) = self
+            .attribute(LEDGER_LATENCY_ATTRIBUTE)
+            .and_then(JsonValue::as_f64)
+        {
+            metrics.push(otlp_histogram(
+                LEDGER_LATENCY_METRIC,
+                &attributes,
+                &start,
+                &end_nanos,
+                latency,
+            ));
+        }
+
+        json!({
+            "resourceMetrics": [{
+                "resource": otlp_resource(),
+                "scopeMetrics": [{
+                    "scope": { "name": SERVICE_NAME },
+                    "metrics": metrics
+                }]
+            }]
+        })
+    }
+}
+
+/// Sends finished spans and command metrics to the OTLP/HTTP collector from a background thread,
+/// so a slow or unavailable collector does not delay the commands.
+pub struct Exporter {
+    endpoint: String,
+    sender: Option<Sender<(String, JsonValue)>>,
+    worker: Option<JoinHandle<()>>,
+}
+
+impl Exporter {
+    pub fn start(endpoint: &str) -> Result<Exporter, String> {
+        let otlp_endpoint = OtlpEndpoint::parse(endpoint)?;
+        let (sender, receiver) = channel::<(String, JsonValue)>();
+        let worker = thread::Builder::new()
+            .name("otel-exporter".to_string())
+            .spawn(move || {
+                for (path, body) in receiver {
+                    if let Err(err) = otlp_endpoint.post(&path, &body) {
+                        warn!("Unable to export telemetry data: {}", err);
+                    }
+                }
+            })
+            .map_err(|err| format!("Unable to start OpenTelemetry exporter: {}", err))?;
+
+        Ok(Exporter {
+            endpoint: endpoint.to_string(),
+            sender: Some(sender),
+            worker: Some(worker),
+        })
+    }
+
+    pub fn endpoint(&self) -> &str {
+        &self.endpoint
+    }
+
+    /// Finish the span and queue it with the command metrics for the export.
+    pub fn export(&self, span: Span, success: bool) {
+        let end = SystemTime::now();
+        if let Some(sender) = self.sender.as_ref() {
+            sender
+                .send((TRACES_PATH.to_string(), span.to_otlp_json(end, success)))
+                .ok();
+            sender
+                .send((
+                    METRICS_PATH.to_string(),
+                    span.to_otlp_metrics_json(end, success),
+                ))
+                .ok();
+        }
+    }
+}
+
+impl Drop for Exporter {
+    // The queued data is sent before exit, every request is limited by the export timeout
+    fn drop(&mut self) {
+        self.sender.take();
+        if let Some(worker) = self.worker.take() {
+            worker.join().ok();
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct OtlpEndpoint {
+    scheme: &'static str,
+    host: String,
+    port: u16,
+    base_path: String,
+}
+
+impl OtlpEndpoint {
+    fn parse(endpoint: &str) -> Result<OtlpEndpoint, String> {
+        let (scheme, rest, default_port) = if let Some(rest) = endpoint.strip_prefix("http://") {
+            ("http", rest, DEFAULT_HTTP_PORT)
+        } else if let Some(rest) = endpoint.strip_prefix("https://") {
+            ("https", rest, DEFAULT_HTTPS_PORT)
+        } else {
+            return Err(format!(
+                "Unsupported OpenTelemetry endpoint \"{}\": \"http://\" or \"https://\" collector URL expected",
+                endpoint
+            ));
+        };
+
+        let (authority, base_path) = match rest.find('/') {
+            Some(pos) => (&rest[..pos], rest[pos..].trim_end_matches('/')),
+            None => (rest, ""),
+        };
+
+        let (host, port) = match authority.rsplit_once(':') {
+            Some((host, port)) => (
+                host,
+                port.parse::<u16>().map_err(|_| {
+                    format!("Invalid port in OpenTelemetry endpoint \"{}\"", endpoint)
+                })?,
+            ),
+            None => (authority, default_port),
+        };
+
+        if host.is_empty() {
+            return Err(format!(
+                "Invalid host in OpenTelemetry endpoint \"{}\"",
+                endpoint
+            ));
+        }
+
+        Ok(OtlpEndpoint {
+            scheme,
+            host: host.to_string(),
+            port,
+            base_path: base_path.to_string(),
+        })
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!(
+            "{}://{}:{}{}{}",
+            self.scheme, self.host, self.port, self.base_path, path
+        )
+    }
+
+    fn post(&self, path: &str, body: &JsonValue) -> Result<(), String> {
+        let to_error =
+            |err: attohttpc::Error| format!("OpenTelemetry collector request failed: {}", err);
+        attohttpc::post(self.url(path))
+            .timeout(EXPORT_TIMEOUT)
+            .json(body)
+            .map_err(to_error)?
+            .send()
+            .and_then(|response| response.error_for_status())
+            .map(|_| ())
+            .map_err(to_error)
+    }
+}
+
+pub fn validate_endpoint(endpoint: &str) -> Result<(), String> {
+    OtlpEndpoint::parse(endpoint).map(|_| ())
+}
+
+fn otlp_resource() -> JsonValue {
+    json!({
+        "attributes": [
+            { "key": "service.name", "value": { "stringValue": SERVICE_NAME } },
+            { "key": "service.version", "value": { "stringValue": env!("CARGO_PKG_VERSION") } }
+        ]
+    })
+}
+
+fn otlp_histogram(
+    name: &str,
+    attributes: &[JsonValue],
+    start: &str,
+    end: &str,
+    value: f64,
+) -> JsonValue {
+    json!({
+        "name": name,
+        "unit": "ms",
+        "histogram": {
+            "aggregationTemporality": AGGREGATION_TEMPORALITY_DELTA,
+            "dataPoints": [{
+                "attributes": attributes,
+                "startTimeUnixNano": start,
+                "timeUnixNano": end,
+                "count": "1",
+                "sum": value,
+                "min": value,
+                "max": value,
+                "bucketCounts": ["1"],
+                "explicitBounds": []
+            }]
+        }
+    })
+}
+
+fn otlp_attribute(key: &str, value: &JsonValue) -> JsonValue {
+    json!({ "key": key, "value": otlp_value(value) })
+}
+
+fn otlp_value(value: &JsonValue) -> JsonValue {
+    match value {
+        JsonValue::Bool(value) => json!({ "boolValue": value }),
+        JsonValue::Number(value) if value.is_u64() || value.is_i64() => {
+            json!({ "intValue": value.to_string() })
+        }
+        JsonValue::Number(value) => json!({ "doubleValue": value }),
+        JsonValue::String(value) => json!({ "stringValue": value }),
+        value => json!({ "stringValue": value.to_string() }),
+    }
+}
+
+fn unix_nanos(time: SystemTime) -> String {
+    time.duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos()
+        .to_string()
+}
+
+fn random_id(len: usize) -> String {
+    let mut id = vec![0u8; len];
+    fill_random(&mut id);
+    hex::encode(id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn span() -> Span {
+        let mut span = Span::start("ledger nym");
+        span.start = UNIX_EPOCH + Duration::from_secs(10);
+        span.set_attribute("indy.pool.name", "sandbox");
+        span.set_attribute(LEDGER_LATENCY_ATTRIBUTE, 120);
+        span
+    }
+
+    #[test]
+    fn otlp_endpoint_parse_works() {
+        assert_eq!(
+            OtlpEndpoint {
+                scheme: "http",
+                host: "localhost".to_string(),
+                port: DEFAULT_HTTP_PORT,
+                base_path: String::new(),
+            },
+            OtlpEndpoint::parse("http://localhost").unwrap()
+        );
+
+        let endpoint = OtlpEndpoint::parse("https://collector.example.com:8443/otlp/").unwrap();
+        assert_eq!("collector.example.com", endpoint.host);
+        assert_eq!(8443, endpoint.port);
+        assert_eq!(
+            "https://collector.example.com:8443/otlp/v1/traces",
+            endpoint.url(TRACES_PATH)
+        );
+        assert_eq!(
+            DEFAULT_HTTPS_PORT,
+            OtlpEndpoint::parse("https://collector.example.com")
+                .unwrap()
+                .port
+        );
+    }
+
+    #[test]
+    fn otlp_endpoint_parse_works_for_invalid_endpoint() {
+        OtlpEndpoint::parse("localhost:4318").unwrap_err();
+        OtlpEndpoint::parse("grpc://localhost:4317").unwrap_err();
+        OtlpEndpoint::parse("http://localhost:port").unwrap_err();
+        OtlpEndpoint::parse("http://:4318").unwrap_err();
+    }
+
+    #[test]
+    fn to_otlp_json_works() {
+        let end = UNIX_EPOCH + Duration::from_secs(11);
+        let json = span().to_otlp_json(end, false);
+
+        let resource = &json["resourceSpans"][0]["resource"]["attributes"][0];
+        assert_eq!("service.name", resource["key"]);
+        assert_eq!(SERVICE_NAME, resource["value"]["stringValue"]);
+
+        let span = &json["resourceSpans"][0]["scopeSpans"][0]["spans"][0];
+        assert_eq!("ledger nym", span["name"]);
+        assert_eq!(32, span["traceId"].as_str().unwrap().len());
+        assert_eq!(16, span["spanId"].as_str().unwrap().len());
+        assert_eq!("10000000000", span["startTimeUnixNano"]);
+        assert_eq!("11000000000", span["endTimeUnixNano"]);
+        assert_eq!(STATUS_CODE_ERROR, span["status"]["code"]);
+        assert_eq!(
+            json!([
+                { "key": "indy.pool.name", "value": { "stringValue": "sandbox" } },
+                { "key": LEDGER_LATENCY_ATTRIBUTE, "value": { "intValue": "120" } }
+            ]),
+            span["attributes"]
+        );
+    }
+
+    #[test]
+    fn to_otlp_metrics_json_works() {
+        let end = UNIX_EPOCH + Duration::from_millis(10_500);
+        let json = span().to_otlp_metrics_json(end, true);
+
+        let metrics = json["resourceMetrics"][0]["scopeMetrics"][0]["metrics"]
+            .as_array()
+            .unwrap();
+        assert_eq!(3, metrics.len());
+
+        let count = &metrics[0];
+        assert_eq!(COMMAND_COUNT_METRIC, count["name"]);
+        assert_eq!("1", count["sum"]["dataPoints"][0]["asInt"]);
+        assert_eq!(
+            json!([
+                { "key": "indy.command", "value": { "stringValue": "ledger nym" } },
+                { "key": "indy.command.status", "value": { "stringValue": "ok" } },
+                { "key": "indy.pool.name", "value": { "stringValue": "sandbox" } }
+            ]),
+            count["sum"]["dataPoints"][0]["attributes"]
+        );
+
+        assert_eq!(COMMAND_DURATION_METRIC, metrics[1]["name"]);
+        assert_eq!(500.0, metrics[1]["histogram"]["dataPoints"][0]["sum"]);
+        assert_eq!(LEDGER_LATENCY_METRIC, metrics[2]["name"]);
+        assert_eq!(120.0, metrics[2]["histogram"]["dataPoints"][0]["sum"]);
+    }
+}
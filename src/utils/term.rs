@@ -1,53 +1,103 @@
 use atty;
+#[cfg(unix)]
+use std::io::Write;
+use std::{fs::OpenOptions, io};
 
 #[macro_export]
 macro_rules! println_err {
-    ($($arg:tt)*) => (
-        if $crate::utils::term::is_term() {
-            error!($($arg)*);
-            println!("{}", ansi_term::Color::Red.bold().paint(format!($($arg)*)))
-        } else {
-            println!($($arg)*)
-        }
+    ($output:expr, $($arg:tt)*) => (
+        $crate::utils::renderer::Output::renderer(&$output)
+            .message($crate::utils::renderer::Level::Error, &format!($($arg)*))
     )
 }
 
 #[macro_export]
 macro_rules! println_succ {
-    ($($arg:tt)*) => (
-        if $crate::utils::term::is_term() {
-            trace!($($arg)*);
-            println!("{}", ansi_term::Color::Green.bold().paint(format!($($arg)*)))
-        } else {
-            println!($($arg)*)
-        }
+    ($output:expr, $($arg:tt)*) => (
+        $crate::utils::renderer::Output::renderer(&$output)
+            .message($crate::utils::renderer::Level::Success, &format!($($arg)*))
     )
 }
 
 #[macro_export]
 macro_rules! println_warn {
-    ($($arg:tt)*) => (
-        if $crate::utils::term::is_term() {
-            println!("{}", ansi_term::Color::Yellow.bold().paint(format!($($arg)*)))
-        } else {
-            trace!($($arg)*);
-            println!($($arg)*)
-        }
+    ($output:expr, $($arg:tt)*) => (
+        $crate::utils::renderer::Output::renderer(&$output)
+            .message($crate::utils::renderer::Level::Warning, &format!($($arg)*))
     )
 }
 
 #[macro_export]
 macro_rules! println_acc {
-    ($($arg:tt)*) => (
-       if $crate::utils::term::is_term() {
-            trace!($($arg)*);
-           println!("{}", ansi_term::Style::new().bold().paint(format!($($arg)*)))
-       } else {
-           println!($($arg)*)
-       }
+    ($output:expr, $($arg:tt)*) => (
+        $crate::utils::renderer::Output::renderer(&$output)
+            .message($crate::utils::renderer::Level::Accent, &format!($($arg)*))
     )
 }
 
 pub fn is_term() -> bool {
     atty::is(atty::Stream::Stdout)
 }
+
+/// Question the user answers on the terminal: a `y`/`n` confirmation or a value of a deferred parameter.
+/// It is written to the terminal directly, so neither the output format nor the output redirection hides it.
+pub fn print_prompt(text: &str) {
+    let line = format!("{}\n", ansi_term::Color::Yellow.bold().paint(text));
+    if write_tty(&line).is_err() {
+        eprintln!("{}", text);
+    }
+}
+
+#[cfg(unix)]
+fn write_tty(text: &str) -> io::Result<()> {
+    let mut tty = OpenOptions::new().write(true).open("/dev/tty")?;
+    tty.write_all(text.as_bytes())
+}
+
+// Bytes written to the console handle are decoded with the console code page,
+// so the text is passed as UTF-16 the same way the standard library writes to the console
+#[cfg(windows)]
+fn write_tty(text: &str) -> io::Result<()> {
+    use std::{os::windows::io::AsRawHandle, ptr};
+    use winapi::um::consoleapi::WriteConsoleW;
+
+    let tty = OpenOptions::new().read(true).write(true).open("CONOUT$")?;
+    let mut text = text.encode_utf16().collect::<Vec<u16>>();
+    while !text.is_empty() {
+        let mut written = 0;
+        let result = unsafe {
+            WriteConsoleW(
+                tty.as_raw_handle() as _,
+                text.as_ptr() as _,
+                text.len() as u32,
+                &mut written,
+                ptr::null_mut(),
+            )
+        };
+        if result == 0 || written == 0 {
+            return Err(io::Error::last_os_error());
+        }
+        text.drain(..written as usize);
+    }
+    Ok(())
+}
+
+#[cfg(not(any(unix, windows)))]
+fn write_tty(_text: &str) -> io::Result<()> {
+    Err(io::ErrorKind::Unsupported.into())
+}
+
+/// Colored label to show in the command prompt.
+/// Escape sequences are wrapped with `\x01`/`\x02` so that the line editor does not count them into the prompt width.
+pub fn prompt_badge(text: &str, color: ansi_term::Color) -> String {
+    if !is_term() {
+        return format!("[{}]", text);
+    }
+    let style = color.bold();
+    format!(
+        "\x01{}\x02[{}]\x01{}\x02",
+        style.prefix(),
+        text,
+        style.suffix()
+    )
+}
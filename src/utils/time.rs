@@ -1,7 +1,132 @@
-use chrono::NaiveDateTime;
+use crate::utils::renderer::{Output, OutputFormat};
 
-pub fn timestamp_to_datetime(timestamp: i64) -> String {
-    NaiveDateTime::from_timestamp_opt(timestamp, 0)
-        .map(|datetime| datetime.to_string())
+use chrono::{DateTime, FixedOffset, Local, SecondsFormat, TimeZone, Utc};
+
+const MINUTE: u64 = 60;
+const HOUR: u64 = 60 * MINUTE;
+const DAY: u64 = 24 * HOUR;
+
+/// Ledger timestamp (seconds since epoch) as shown to the user: local time labeled with the UTC offset,
+/// or ISO-8601 in UTC when the output is JSON.
+pub fn timestamp_to_datetime(output: &dyn Output, timestamp: i64) -> String {
+    Utc.timestamp_opt(timestamp, 0)
+        .single()
+        .map(|datetime| format_datetime(&datetime, &Local, output.output_format()))
         .unwrap_or_default()
 }
+
+/// RFC 3339 time (e.g. a pool upgrade schedule entry) converted the same way as the timestamps.
+/// Values which cannot be parsed are returned as is.
+pub fn rfc3339_to_datetime(output: &dyn Output, value: &str) -> String {
+    DateTime::parse_from_rfc3339(value)
+        .map(|datetime| {
+            format_datetime(
+                &datetime.with_timezone(&Utc),
+                &Local,
+                output.output_format(),
+            )
+        })
+        .unwrap_or_else(|_| value.to_string())
+}
+
+/// Duration in the largest units: `2d 3h`, `5m 10s`, `45s`.
+pub fn format_duration(seconds: u64) -> String {
+    let units = [
+        (seconds / DAY, "d"),
+        (seconds % DAY / HOUR, "h"),
+        (seconds % HOUR / MINUTE, "m"),
+        (seconds % MINUTE, "s"),
+    ];
+    let parts = units
+        .iter()
+        .skip_while(|(value, _)| *value == 0)
+        .take(2)
+        .filter(|(value, _)| *value != 0)
+        .map(|(value, unit)| format!("{}{}", value, unit))
+        .collect::<Vec<String>>();
+    if parts.is_empty() {
+        "0s".to_string()
+    } else {
+        parts.join(" ")
+    }
+}
+
+fn format_datetime<Tz: TimeZone>(
+    datetime: &DateTime<Utc>,
+    timezone: &Tz,
+    format: OutputFormat,
+) -> String
+where
+    Tz::Offset: Into<FixedOffset>,
+{
+    if format == OutputFormat::Json {
+        return datetime.to_rfc3339_opts(SecondsFormat::Secs, true);
+    }
+    let local = datetime.with_timezone(timezone);
+    let offset: FixedOffset = local.offset().clone().into();
+    let label = if offset.local_minus_utc() == 0 {
+        "UTC".to_string()
+    } else {
+        format!("UTC{}", offset)
+    };
+    format!("{} {}", local.format("%Y-%m-%d %H:%M:%S"), label)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TIMESTAMP: i64 = 1719799200;
+
+    fn datetime() -> DateTime<Utc> {
+        Utc.timestamp_opt(TIMESTAMP, 0).unwrap()
+    }
+
+    #[test]
+    fn format_datetime_works() {
+        let utc = FixedOffset::east_opt(0).unwrap();
+        assert_eq!(
+            "2024-07-01 02:00:00 UTC",
+            format_datetime(&datetime(), &utc, OutputFormat::Human)
+        );
+
+        let cest = FixedOffset::east_opt(2 * 3600).unwrap();
+        assert_eq!(
+            "2024-07-01 04:00:00 UTC+02:00",
+            format_datetime(&datetime(), &cest, OutputFormat::Human)
+        );
+
+        let pdt = FixedOffset::west_opt(7 * 3600).unwrap();
+        assert_eq!(
+            "2024-06-30 19:00:00 UTC-07:00",
+            format_datetime(&datetime(), &pdt, OutputFormat::Csv)
+        );
+    }
+
+    #[test]
+    fn format_datetime_works_for_json() {
+        let cest = FixedOffset::east_opt(2 * 3600).unwrap();
+        assert_eq!(
+            "2024-07-01T02:00:00Z",
+            format_datetime(&datetime(), &cest, OutputFormat::Json)
+        );
+    }
+
+    #[test]
+    fn rfc3339_to_datetime_works_for_invalid_value() {
+        assert_eq!(
+            "tomorrow",
+            rfc3339_to_datetime(&OutputFormat::Human, "tomorrow")
+        );
+    }
+
+    #[test]
+    fn format_duration_works() {
+        assert_eq!("0s", format_duration(0));
+        assert_eq!("45s", format_duration(45));
+        assert_eq!("5m 10s", format_duration(310));
+        assert_eq!("1h", format_duration(3600 + 5));
+        assert_eq!("2d 3h", format_duration(2 * DAY + 3 * HOUR + 59));
+        assert_eq!("1d", format_duration(DAY + MINUTE));
+    }
+}